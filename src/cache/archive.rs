@@ -0,0 +1,287 @@
+//! Extracted archive sources in the cache.
+//!
+//! Archive sources (see [`crate::source::archive`]) are extracted once into
+//! `archives/{version}_{url_hash}/`, with the SHA-256 of the downloaded archive
+//! stored next to it in `{version}_{url_hash}.sha256`. The extracted directory
+//! stands in for both the bare repository and the worktree of a Git source.
+//!
+//! HTTP validators (`ETag`, `Last-Modified`) of the download are stored in
+//! `{version}_{url_hash}.http.json`. [`Cache::refresh_archive`], used during
+//! version resolution, revalidates the extraction with a conditional request
+//! and only downloads the archive again when the server reports a change.
+
+use super::Cache;
+use super::lock::CacheLock;
+use crate::source::archive::{self, ArchiveDownload, ArchiveUrl, HttpValidators};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+impl Cache {
+    /// Path of the extracted archive for `url`, preferring the read-only base.
+    ///
+    /// Falls back to the user cache path, whether or not it exists yet.
+    pub(crate) fn archive_path(&self, archive: &ArchiveUrl) -> PathBuf {
+        let relative = Path::new("archives").join(archive.cache_dir_name());
+        self.base_path(&relative).unwrap_or_else(|| self.dir.join(relative))
+    }
+
+    /// Gets or downloads and extracts the archive source at `url`.
+    ///
+    /// Returns the extracted directory and the archive id recorded as the
+    /// resolved commit. When `expected_id` is given (from the lockfile), an
+    /// archive whose content no longer matches is rejected.
+    pub async fn get_or_fetch_archive(
+        &self,
+        url: &str,
+        expected_id: Option<&str>,
+    ) -> Result<(PathBuf, String)> {
+        self.fetch_archive(url, expected_id, false).await
+    }
+
+    /// Like [`Cache::get_or_fetch_archive`], but revalidates a cached extraction.
+    ///
+    /// When the previous download returned `ETag` or `Last-Modified`, they are
+    /// sent as a conditional request. A `304 Not Modified` keeps the cached
+    /// extraction; a changed archive replaces it. If the server cannot be
+    /// reached, the cached extraction is used. Archives pinned by a
+    /// `#sha256=` fragment cannot change and are never revalidated.
+    pub async fn refresh_archive(&self, url: &str) -> Result<(PathBuf, String)> {
+        self.fetch_archive(url, None, true).await
+    }
+
+    async fn fetch_archive(
+        &self,
+        url: &str,
+        expected_id: Option<&str>,
+        revalidate: bool,
+    ) -> Result<(PathBuf, String)> {
+        let archive = ArchiveUrl::parse(url)?;
+        let relative = Path::new("archives").join(archive.cache_dir_name());
+        let is_current = |digest: &str| {
+            archive.checksum.as_deref().is_none_or(|checksum| checksum == digest)
+                && expected_id.is_none_or(|id| archive::archive_id(digest) == id)
+        };
+
+        if let Some(base) = self.base_path(&relative)
+            && let Some(digest) = read_digest(&base)
+            && is_current(&digest)
+        {
+            return Ok((base, archive::archive_id(&digest).to_string()));
+        }
+
+        let dest = self.dir.join(&relative);
+        let _lock = CacheLock::acquire(&self.dir, &format!("archive-{}", archive.cache_dir_name()))
+            .await
+            .with_context(|| format!("Failed to acquire lock for archive: {url}"))?;
+
+        let cached = read_digest(&dest).filter(|digest| is_current(digest));
+        let validators = cached
+            .as_ref()
+            .filter(|_| revalidate && archive.checksum.is_none())
+            .and_then(|_| read_validators(&dest));
+        if let Some(digest) = &cached
+            && validators.is_none()
+        {
+            return Ok((dest, archive::archive_id(digest).to_string()));
+        }
+
+        let (bytes, digest, new_validators) =
+            match archive.download_if_modified(validators.as_ref()).await {
+                Ok(ArchiveDownload::Fetched {
+                    bytes,
+                    digest,
+                    validators,
+                }) => (bytes, digest, validators),
+                Ok(ArchiveDownload::NotModified) => {
+                    let digest = cached.expect("only cached archives are revalidated");
+                    return Ok((dest, archive::archive_id(&digest).to_string()));
+                }
+                Err(e) if cached.is_some() => {
+                    tracing::warn!("Could not revalidate archive {}: {e:#}", archive.url);
+                    let digest = cached.expect("checked above");
+                    return Ok((dest, archive::archive_id(&digest).to_string()));
+                }
+                Err(e) => return Err(e),
+            };
+        let id = archive::archive_id(&digest).to_string();
+        if let Some(expected) = expected_id
+            && expected != id
+        {
+            anyhow::bail!(
+                "Archive {} has changed since the lockfile was written (expected {expected}, got {id}). \
+                 Run 'agpm update' to accept the new archive",
+                archive.url
+            );
+        }
+
+        // Servers without conditional request support resend unchanged archives
+        if cached.as_deref() != Some(digest.as_str()) {
+            let (format, version, target) = (archive.format, archive.version.clone(), dest.clone());
+            tokio::task::spawn_blocking(move || {
+                archive::extract(&bytes, format, &version, &target)
+            })
+            .await
+            .context("Archive extraction task panicked")??;
+            tokio::fs::write(digest_path(&dest), &digest)
+                .await
+                .with_context(|| format!("Failed to record checksum for {}", dest.display()))?;
+        }
+        write_validators(&dest, &new_validators).await?;
+
+        Ok((dest, id))
+    }
+}
+
+fn sibling_path(dir: &Path, extension: &str) -> PathBuf {
+    // Not `with_extension`: the version in the directory name contains dots
+    let mut path = dir.as_os_str().to_owned();
+    path.push(extension);
+    PathBuf::from(path)
+}
+
+fn digest_path(dir: &Path) -> PathBuf {
+    sibling_path(dir, ".sha256")
+}
+
+fn validators_path(dir: &Path) -> PathBuf {
+    sibling_path(dir, ".http.json")
+}
+
+fn read_validators(dir: &Path) -> Option<HttpValidators> {
+    let content = std::fs::read_to_string(validators_path(dir)).ok()?;
+    serde_json::from_str::<HttpValidators>(&content).ok().filter(|v| !v.is_empty())
+}
+
+/// Record the validators of the latest download, or forget stale ones.
+async fn write_validators(dir: &Path, validators: &HttpValidators) -> Result<()> {
+    let path = validators_path(dir);
+    if validators.is_empty() {
+        if path.exists() {
+            tokio::fs::remove_file(&path)
+                .await
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        return Ok(());
+    }
+    tokio::fs::write(&path, serde_json::to_string(validators)?)
+        .await
+        .with_context(|| format!("Failed to record HTTP validators for {}", dir.display()))
+}
+
+/// Digest of a complete extraction, if `dir` holds one.
+fn read_digest(dir: &Path) -> Option<String> {
+    if !dir.is_dir() {
+        return None;
+    }
+    std::fs::read_to_string(digest_path(dir)).ok().map(|digest| digest.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tempfile::TempDir;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn archive_bytes(content: &str) -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, "pkg-1.0.0/agent.md", content.as_bytes()).unwrap();
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn write_archive(dir: &Path, name: &str, content: &str) -> String {
+        let path = dir.join(name);
+        std::fs::write(&path, archive_bytes(content)).unwrap();
+        format!("file://{}", path.display())
+    }
+
+    /// Archive currently served by [`conditional_server`], with its `ETag`.
+    type Served = Arc<Mutex<(Vec<u8>, String)>>;
+
+    /// Serve the archive in `served`, answering 304 when `If-None-Match` matches.
+    ///
+    /// Returns the archive URL and the status of every response sent.
+    async fn conditional_server(served: Served) -> (String, Arc<Mutex<Vec<u16>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/pkg-1.0.0.tar.gz", listener.local_addr().unwrap());
+        let statuses = Arc::new(Mutex::new(Vec::new()));
+        let log = statuses.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0u8; 4096];
+                let len = stream.read(&mut request).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..len]).to_ascii_lowercase();
+                let (body, etag) = served.lock().unwrap().clone();
+                let (status, body) = if request.contains(&format!("if-none-match: {etag}")) {
+                    (304, Vec::new())
+                } else {
+                    (200, body)
+                };
+                log.lock().unwrap().push(status);
+                let head = format!(
+                    "HTTP/1.1 {status} Archive\r\nContent-Length: {}\r\nETag: {etag}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(head.as_bytes()).await;
+                let _ = stream.write_all(&body).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+        (url, statuses)
+    }
+
+    #[tokio::test]
+    async fn test_get_or_fetch_archive() {
+        let temp = TempDir::new().unwrap();
+        let url = write_archive(temp.path(), "v1.0.0.tar.gz", "# Agent");
+        let cache = Cache::with_dir(temp.path().join("cache")).unwrap();
+
+        let (path, id) = cache.get_or_fetch_archive(&url, None).await.unwrap();
+        assert_eq!(id.len(), 40);
+        assert_eq!(std::fs::read_to_string(path.join("agent.md")).unwrap(), "# Agent");
+        assert_eq!(path, cache.archive_path(&ArchiveUrl::parse(&url).unwrap()));
+
+        // Cached extractions are reused for a matching id
+        let (again, same_id) = cache.get_or_fetch_archive(&url, Some(&id)).await.unwrap();
+        assert_eq!((again, same_id), (path.clone(), id.clone()));
+
+        // A replaced upstream archive no longer matches the lockfile
+        std::fs::remove_dir_all(&path).unwrap();
+        write_archive(temp.path(), "v1.0.0.tar.gz", "# Replaced");
+        let err = cache.get_or_fetch_archive(&url, Some(&id)).await.unwrap_err();
+        assert!(err.to_string().contains("has changed since the lockfile"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_archive_sends_conditional_requests() {
+        let temp = TempDir::new().unwrap();
+        let served: Served = Arc::new(Mutex::new((archive_bytes("# Agent"), "\"v1\"".into())));
+        let (url, statuses) = conditional_server(served.clone()).await;
+        let cache = Cache::with_dir(temp.path().join("cache")).unwrap();
+
+        let (path, id) = cache.refresh_archive(&url).await.unwrap();
+        assert_eq!(read_validators(&path).unwrap().etag.as_deref(), Some("\"v1\""));
+
+        // Unchanged upstream: 304, nothing downloaded or extracted again
+        let (again, same_id) = cache.refresh_archive(&url).await.unwrap();
+        assert_eq!((again, same_id), (path.clone(), id.clone()));
+        assert_eq!(*statuses.lock().unwrap(), vec![200, 304]);
+
+        // Installing from the lockfile does not revalidate
+        cache.get_or_fetch_archive(&url, Some(&id)).await.unwrap();
+        assert_eq!(statuses.lock().unwrap().len(), 2);
+
+        // Changed upstream: 200 with the new archive, which replaces the extraction
+        *served.lock().unwrap() = (archive_bytes("# Updated"), "\"v2\"".into());
+        let (path, new_id) = cache.refresh_archive(&url).await.unwrap();
+        assert_ne!(new_id, id);
+        assert_eq!(std::fs::read_to_string(path.join("agent.md")).unwrap(), "# Updated");
+        assert_eq!(read_validators(&path).unwrap().etag.as_deref(), Some("\"v2\""));
+        assert_eq!(*statuses.lock().unwrap(), vec![200, 304, 200]);
+    }
+}
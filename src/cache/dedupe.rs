@@ -0,0 +1,403 @@
+//! Worktree deduplication for reclaiming cache disk space.
+//!
+//! SHA-based worktrees are keyed by commit, so two commits that touch nothing
+//! but unrelated files (or that are pure tag moves) produce byte-identical
+//! checkouts. Deduplication fingerprints every worktree's contents, groups
+//! identical worktrees, and replaces the files of redundant copies with hard
+//! links to a single canonical worktree. Worktree directories stay in place so
+//! existing registry entries and `git worktree` metadata remain valid.
+//!
+//! # Safety
+//!
+//! - Each group is processed while holding the exclusive `bare-worktree-*` locks
+//!   of every repository involved, which blocks concurrent worktree creation
+//! - Groups whose locks cannot be acquired within [`DEDUPE_LOCK_TIMEOUT`] are
+//!   treated as in use and skipped
+//! - Files are replaced atomically (link to temp name + rename)
+//! - If hard links are unsupported, the redundant worktree is removed instead
+
+use super::{Cache, CacheLock, acquire_mutex_with_timeout};
+use crate::constants::DEDUPE_LOCK_TIMEOUT;
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Summary of a `cache dedupe` run.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct DedupeReport {
+    /// Number of worktrees inspected
+    pub worktrees_scanned: usize,
+    /// Number of redundant worktrees linked to (or replaced by) a canonical copy
+    pub worktrees_consolidated: usize,
+    /// Number of individual files replaced with hard links
+    pub files_linked: usize,
+    /// Bytes freed (or that would be freed in dry-run mode)
+    pub bytes_reclaimed: u64,
+    /// Worktrees skipped because their repository lock was held
+    pub skipped_in_use: Vec<PathBuf>,
+}
+
+/// A scanned worktree and the files that make up its content fingerprint.
+struct ScannedWorktree {
+    path: PathBuf,
+    fingerprint: String,
+    /// Relative file paths and their sizes, sorted by path
+    files: Vec<(PathBuf, u64)>,
+}
+
+impl Cache {
+    /// Consolidates worktrees with byte-identical content.
+    ///
+    /// The most recently used worktree in each identical group is kept as the
+    /// canonical copy; the files of every other worktree in the group are
+    /// replaced with hard links to it and the registry records the linkage.
+    ///
+    /// # Arguments
+    ///
+    /// * `dry_run` - Report what would be reclaimed without modifying anything
+    pub async fn dedupe_worktrees(&self, dry_run: bool) -> Result<DedupeReport> {
+        let worktrees_dir = self.dir.join("worktrees");
+        let mut report = DedupeReport::default();
+
+        if !worktrees_dir.exists() {
+            return Ok(report);
+        }
+
+        let scanned = tokio::task::spawn_blocking(move || scan_worktrees(&worktrees_dir))
+            .await
+            .context("Worktree scan task panicked")??;
+        report.worktrees_scanned = scanned.len();
+
+        let mut groups: BTreeMap<String, Vec<ScannedWorktree>> = BTreeMap::new();
+        for worktree in scanned {
+            groups.entry(worktree.fingerprint.clone()).or_default().push(worktree);
+        }
+
+        for (_, mut group) in groups {
+            if group.len() < 2 {
+                continue;
+            }
+
+            // Keep the most recently used worktree as canonical
+            {
+                let registry =
+                    acquire_mutex_with_timeout(&self.worktree_registry, "worktree_registry")
+                        .await?;
+                let last_used = |path: &Path| {
+                    registry
+                        .entries
+                        .values()
+                        .find(|record| record.path == path)
+                        .map_or(0, |record| record.last_used)
+                };
+                group.sort_by(|a, b| {
+                    last_used(&b.path).cmp(&last_used(&a.path)).then_with(|| a.path.cmp(&b.path))
+                });
+            }
+
+            let Some(locks) = self.acquire_group_locks(&group).await else {
+                report.skipped_in_use.extend(group.into_iter().map(|w| w.path));
+                continue;
+            };
+
+            let canonical = &group[0];
+            let mut to_remove = Vec::new();
+            for redundant in &group[1..] {
+                if !self.consolidate_worktree(canonical, redundant, dry_run, &mut report).await? {
+                    to_remove.push(redundant.path.clone());
+                }
+            }
+
+            // cleanup_worktree takes the bare-worktree lock itself
+            drop(locks);
+            for path in to_remove {
+                self.cleanup_worktree(&path).await?;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Acquires exclusive bare-repo worktree locks for every repository in a group.
+    ///
+    /// Returns `None` if any lock is held by another operation. Locks are taken in
+    /// sorted order so concurrent dedupe runs cannot deadlock.
+    async fn acquire_group_locks(&self, group: &[ScannedWorktree]) -> Option<Vec<CacheLock>> {
+        let lock_names: BTreeSet<String> = group
+            .iter()
+            .filter_map(|w| repo_key_from_worktree_dir(&w.path))
+            .map(|repo_key| format!("bare-worktree-{repo_key}"))
+            .collect();
+
+        let mut locks = Vec::with_capacity(lock_names.len());
+        for name in lock_names {
+            match CacheLock::acquire_with_timeout(&self.dir, &name, DEDUPE_LOCK_TIMEOUT).await {
+                Ok(lock) => locks.push(lock),
+                Err(e) => {
+                    tracing::debug!("Skipping dedupe group, lock {name} unavailable: {e}");
+                    return None;
+                }
+            }
+        }
+        Some(locks)
+    }
+
+    /// Hard-links one redundant worktree to the canonical copy.
+    ///
+    /// Returns `false` if linking failed and the redundant worktree should be
+    /// removed instead (never in dry-run mode).
+    async fn consolidate_worktree(
+        &self,
+        canonical: &ScannedWorktree,
+        redundant: &ScannedWorktree,
+        dry_run: bool,
+        report: &mut DedupeReport,
+    ) -> Result<bool> {
+        let canonical_path = canonical.path.clone();
+        let redundant_path = redundant.path.clone();
+        let files = redundant.files.clone();
+
+        let outcome = tokio::task::spawn_blocking(move || {
+            link_worktree_files(&canonical_path, &redundant_path, &files, dry_run)
+        })
+        .await
+        .context("Worktree link task panicked")?;
+
+        let (linked, bytes) = match outcome {
+            Ok(result) => result,
+            Err(e) => {
+                // Hard links unsupported (e.g. filesystem limitation): drop the copy instead
+                tracing::debug!(
+                    "Hard-linking {} failed ({e}), removing redundant worktree",
+                    redundant.path.display()
+                );
+                report.worktrees_consolidated += 1;
+                report.bytes_reclaimed += redundant.files.iter().map(|(_, size)| size).sum::<u64>();
+                return Ok(dry_run);
+            }
+        };
+
+        if linked == 0 {
+            // Already consolidated by a previous run
+            return Ok(true);
+        }
+
+        report.worktrees_consolidated += 1;
+        report.files_linked += linked;
+        report.bytes_reclaimed += bytes;
+
+        if !dry_run {
+            let mut registry =
+                acquire_mutex_with_timeout(&self.worktree_registry, "worktree_registry").await?;
+            let mut changed = false;
+            for record in registry.entries.values_mut() {
+                if record.path == redundant.path {
+                    record.linked_to = Some(canonical.path.clone());
+                    changed = true;
+                }
+            }
+            if changed {
+                registry.persist(&self.registry_path()).await?;
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+/// Extracts the `{owner}_{repo}` key from a `{owner}_{repo}_{sha8}` worktree directory.
+pub(super) fn repo_key_from_worktree_dir(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    let (repo_key, sha_short) = name.rsplit_once('_')?;
+    if sha_short.len() == 8 && sha_short.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(repo_key.to_string())
+    } else {
+        None
+    }
+}
+
+/// Fingerprints every worktree directory under `worktrees_dir`.
+fn scan_worktrees(worktrees_dir: &Path) -> Result<Vec<ScannedWorktree>> {
+    let mut scanned = Vec::new();
+
+    for entry in std::fs::read_dir(worktrees_dir)
+        .with_context(|| format!("Failed to read {}", worktrees_dir.display()))?
+    {
+        let path = entry?.path();
+        let hidden = path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with('.'));
+        if !path.is_dir() || hidden {
+            continue;
+        }
+
+        let mut files = Vec::new();
+        for entry in walkdir::WalkDir::new(&path)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|e| e.depth() != 1 || e.file_name() != ".git")
+        {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                let relative = entry.path().strip_prefix(&path)?.to_path_buf();
+                files.push((relative, entry.metadata()?.len()));
+            }
+        }
+        files.sort();
+
+        let mut hasher = Sha256::new();
+        for (relative, _) in &files {
+            let content = std::fs::read(path.join(relative))
+                .with_context(|| format!("Failed to read {}", path.join(relative).display()))?;
+            hasher.update(crate::utils::normalize_path_for_storage(relative).as_bytes());
+            hasher.update([0]);
+            hasher.update(Sha256::digest(&content));
+        }
+
+        scanned.push(ScannedWorktree {
+            path,
+            fingerprint: hex::encode(hasher.finalize()),
+            files,
+        });
+    }
+
+    Ok(scanned)
+}
+
+/// Replaces the files of `redundant` with hard links to the matching files in `canonical`.
+///
+/// Returns the number of files linked and the bytes reclaimed. Files that already
+/// share storage with the canonical copy are left untouched.
+fn link_worktree_files(
+    canonical: &Path,
+    redundant: &Path,
+    files: &[(PathBuf, u64)],
+    dry_run: bool,
+) -> Result<(usize, u64)> {
+    let mut linked = 0;
+    let mut bytes = 0;
+
+    for (relative, size) in files {
+        let source = canonical.join(relative);
+        let target = redundant.join(relative);
+        if is_same_file(&source, &target)? {
+            continue;
+        }
+
+        if !dry_run {
+            let temp = target.with_file_name(format!(
+                ".{}.agpm-dedupe",
+                target.file_name().and_then(|n| n.to_str()).unwrap_or("file")
+            ));
+            std::fs::hard_link(&source, &temp).with_context(|| {
+                format!("Failed to hard-link {} to {}", source.display(), temp.display())
+            })?;
+            std::fs::rename(&temp, &target).with_context(|| {
+                format!("Failed to replace {} with hard link", target.display())
+            })?;
+        }
+
+        linked += 1;
+        bytes += size;
+    }
+
+    Ok((linked, bytes))
+}
+
+#[cfg(unix)]
+fn is_same_file(a: &Path, b: &Path) -> Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+    let (a, b) = (std::fs::metadata(a)?, std::fs::metadata(b)?);
+    Ok(a.dev() == b.dev() && a.ino() == b.ino())
+}
+
+#[cfg(not(unix))]
+fn is_same_file(_a: &Path, _b: &Path) -> Result<bool> {
+    // No stable inode identity via std on this platform; re-linking is harmless
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_worktree(cache_dir: &Path, name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let worktree = cache_dir.join("worktrees").join(name);
+        for (relative, content) in files {
+            let path = worktree.join(relative);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        // Worktree .git files differ per checkout and must not affect the fingerprint
+        std::fs::write(worktree.join(".git"), format!("gitdir: {name}")).unwrap();
+        worktree
+    }
+
+    #[test]
+    fn test_repo_key_from_worktree_dir() {
+        assert_eq!(
+            repo_key_from_worktree_dir(Path::new("/c/worktrees/my_org_repo_abcdef12")),
+            Some("my_org_repo".to_string())
+        );
+        assert_eq!(repo_key_from_worktree_dir(Path::new("/c/worktrees/notaworktree")), None);
+    }
+
+    #[tokio::test]
+    async fn test_dedupe_links_identical_worktrees() -> Result<()> {
+        let temp = TempDir::new()?;
+        let files = [("agents/a.md", "# Agent"), ("snippets/s.md", "snippet body")];
+        let first = write_worktree(temp.path(), "org_repo_aaaaaaaa", &files);
+        let second = write_worktree(temp.path(), "org_repo_bbbbbbbb", &files);
+        write_worktree(temp.path(), "org_repo_cccccccc", &[("agents/a.md", "# Different")]);
+
+        let cache = Cache::with_dir(temp.path().to_path_buf())?;
+        let report = cache.dedupe_worktrees(false).await?;
+
+        assert_eq!(report.worktrees_scanned, 3);
+        assert_eq!(report.worktrees_consolidated, 1);
+        assert_eq!(report.files_linked, 2);
+        assert_eq!(report.bytes_reclaimed, ("# Agent".len() + "snippet body".len()) as u64);
+        assert!(report.skipped_in_use.is_empty());
+        assert_eq!(std::fs::read_to_string(second.join("agents/a.md"))?, "# Agent");
+        #[cfg(unix)]
+        assert!(is_same_file(&first.join("agents/a.md"), &second.join("agents/a.md"))?);
+
+        // A second run finds nothing left to reclaim
+        let report = cache.dedupe_worktrees(false).await?;
+        assert_eq!(report.worktrees_consolidated, 0);
+        assert_eq!(report.bytes_reclaimed, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dedupe_dry_run_leaves_files_untouched() -> Result<()> {
+        let temp = TempDir::new()?;
+        let files = [("agents/a.md", "# Agent")];
+        let first = write_worktree(temp.path(), "org_repo_aaaaaaaa", &files);
+        let second = write_worktree(temp.path(), "org_repo_bbbbbbbb", &files);
+
+        let cache = Cache::with_dir(temp.path().to_path_buf())?;
+        let report = cache.dedupe_worktrees(true).await?;
+
+        assert_eq!(report.worktrees_consolidated, 1);
+        assert_eq!(report.bytes_reclaimed, "# Agent".len() as u64);
+        assert!(!is_same_file(&first.join("agents/a.md"), &second.join("agents/a.md"))?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dedupe_skips_locked_repositories() -> Result<()> {
+        let temp = TempDir::new()?;
+        let files = [("agents/a.md", "# Agent")];
+        write_worktree(temp.path(), "org_repo_aaaaaaaa", &files);
+        write_worktree(temp.path(), "org_repo_bbbbbbbb", &files);
+
+        let _held = CacheLock::acquire(temp.path(), "bare-worktree-org_repo").await?;
+        let cache = Cache::with_dir(temp.path().to_path_buf())?;
+        let report = cache.dedupe_worktrees(false).await?;
+
+        assert_eq!(report.worktrees_consolidated, 0);
+        assert_eq!(report.skipped_in_use.len(), 2);
+        Ok(())
+    }
+}
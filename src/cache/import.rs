@@ -0,0 +1,108 @@
+//! Seeding the cache from existing clones.
+//!
+//! `agpm cache import` copies repositories that are already cloned elsewhere
+//! into the cache as bare mirrors, at the same path AGPM would have cloned
+//! them to. Later resolves find the bare repository in place and only fetch
+//! from it, so they work without network access.
+
+use super::Cache;
+use super::lock::CacheLock;
+use crate::git::GitRepo;
+use crate::git::command_builder::GitCommand;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Result of importing one repository into the cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportOutcome {
+    /// A new bare mirror was created at this path.
+    Imported(PathBuf),
+    /// The cache already had a repository for the URL; it was left untouched.
+    AlreadyCached(PathBuf),
+}
+
+impl Cache {
+    /// Import the Git repository at `repo_dir` as the cached mirror of `url`.
+    ///
+    /// `repo_dir` may be a working copy or a bare repository. Its branches,
+    /// tags and, for working copies, its `origin` remote-tracking branches are
+    /// copied into a bare repository whose `origin` points at `url`, so later
+    /// fetches go to the real source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `repo_dir` is not a Git repository, the cache lock
+    /// cannot be acquired, or the clone fails.
+    pub async fn import_repository(
+        &self,
+        name: &str,
+        url: &str,
+        repo_dir: &Path,
+    ) -> Result<ImportOutcome> {
+        crate::git::ensure_valid_git_repo(repo_dir)?;
+
+        let (owner, repo) =
+            crate::git::parse_git_url(url).unwrap_or(("direct".to_string(), "repo".to_string()));
+        let target = self.dir.join("sources").join(format!("{owner}_{repo}.git"));
+
+        self.ensure_cache_dir().await?;
+        let _lock = CacheLock::acquire(&self.dir, name)
+            .await
+            .with_context(|| format!("Failed to acquire lock for source: {name}"))?;
+
+        if target.exists() {
+            return Ok(ImportOutcome::AlreadyCached(target));
+        }
+
+        let repo_url = repo_dir.to_string_lossy();
+        let mirror = GitRepo::clone_bare(&repo_url, &target)
+            .await
+            .with_context(|| format!("Failed to import repository from {}", repo_dir.display()))?;
+
+        // Branches a working copy only knows as remote-tracking refs
+        GitCommand::new()
+            .args(["fetch", "--quiet", &repo_url, "+refs/remotes/origin/*:refs/remotes/origin/*"])
+            .current_dir(mirror.path())
+            .execute_success()
+            .await
+            .ok();
+
+        GitCommand::new()
+            .args(["remote", "set-url", "origin", url])
+            .current_dir(mirror.path())
+            .execute_success()
+            .await
+            .with_context(|| format!("Failed to point imported repository at {url}"))?;
+
+        Ok(ImportOutcome::Imported(target))
+    }
+}
+
+/// Whether two source URLs refer to the same repository.
+///
+/// Ignores credentials, a trailing `/` or `.git`, and the `file://` scheme of
+/// local paths.
+#[must_use]
+pub fn same_repository(a: &str, b: &str) -> bool {
+    fn normalize(url: &str) -> String {
+        let url = crate::git::strip_auth_from_url(url).unwrap_or_else(|_| url.to_string());
+        let url = url.strip_prefix("file://").unwrap_or(&url);
+        url.trim_end_matches('/').trim_end_matches(".git").to_string()
+    }
+    normalize(a) == normalize(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_repository() {
+        assert!(same_repository(
+            "https://github.com/org/repo.git",
+            "https://token@github.com/org/repo"
+        ));
+        assert!(same_repository("file:///srv/repos/tools/", "/srv/repos/tools.git"));
+        assert!(!same_repository("https://github.com/org/repo", "https://github.com/org/other"));
+    }
+}
@@ -0,0 +1,177 @@
+//! Contention metrics for `--concurrency-report`.
+//!
+//! When a [`CacheMetrics`] recorder is attached with [`Cache::with_metrics`],
+//! the cache times every lock acquisition (file locks and the in-process
+//! registries), every fetch and clone of a bare repository, and every worktree
+//! it creates, and counts worktrees served from an existing checkout. The
+//! recorder is shared by all clones of the cache, so parallel installation
+//! tasks report into the same totals. [`CacheMetrics::report`] summarizes them
+//! at the end of the command to help tune `--max-parallel`.
+//!
+//! [`Cache::with_metrics`]: super::Cache::with_metrics
+
+use serde::Serialize;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Thread-safe recorder of cache timings. All durations are kept in microseconds.
+#[derive(Debug, Default)]
+pub struct CacheMetrics {
+    lock_acquisitions: AtomicU64,
+    lock_wait_total: AtomicU64,
+    lock_wait_max: AtomicU64,
+    fetches: AtomicU64,
+    fetch_time: AtomicU64,
+    clones: AtomicU64,
+    clone_time: AtomicU64,
+    worktrees_created: AtomicU64,
+    worktree_creation_time: AtomicU64,
+    worktrees_reused: AtomicU64,
+}
+
+fn micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
+
+fn millis(micros: u64) -> f64 {
+    micros as f64 / 1000.0
+}
+
+impl CacheMetrics {
+    /// Record the time spent waiting for a lock.
+    pub fn record_lock_wait(&self, waited: Duration) {
+        let waited = micros(waited);
+        self.lock_acquisitions.fetch_add(1, Ordering::Relaxed);
+        self.lock_wait_total.fetch_add(waited, Ordering::Relaxed);
+        self.lock_wait_max.fetch_max(waited, Ordering::Relaxed);
+    }
+
+    /// Record a fetch into an existing bare repository.
+    pub fn record_fetch(&self, elapsed: Duration) {
+        self.fetches.fetch_add(1, Ordering::Relaxed);
+        self.fetch_time.fetch_add(micros(elapsed), Ordering::Relaxed);
+    }
+
+    /// Record a fresh clone of a bare repository.
+    pub fn record_clone(&self, elapsed: Duration) {
+        self.clones.fetch_add(1, Ordering::Relaxed);
+        self.clone_time.fetch_add(micros(elapsed), Ordering::Relaxed);
+    }
+
+    /// Record a newly created worktree.
+    pub fn record_worktree_created(&self, elapsed: Duration) {
+        self.worktrees_created.fetch_add(1, Ordering::Relaxed);
+        self.worktree_creation_time.fetch_add(micros(elapsed), Ordering::Relaxed);
+    }
+
+    /// Record a request served by a worktree that already existed.
+    pub fn record_worktree_reused(&self) {
+        self.worktrees_reused.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Summarize everything recorded so far.
+    #[must_use]
+    pub fn report(&self) -> ConcurrencyReport {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        let created = load(&self.worktrees_created);
+        let reused = load(&self.worktrees_reused);
+        let requests = created + reused;
+        ConcurrencyReport {
+            lock_acquisitions: load(&self.lock_acquisitions),
+            lock_wait_total_ms: millis(load(&self.lock_wait_total)),
+            lock_wait_max_ms: millis(load(&self.lock_wait_max)),
+            fetches: load(&self.fetches),
+            fetch_time_ms: millis(load(&self.fetch_time)),
+            clones: load(&self.clones),
+            clone_time_ms: millis(load(&self.clone_time)),
+            worktrees_created: created,
+            worktrees_reused: reused,
+            worktree_creation_ms: millis(load(&self.worktree_creation_time)),
+            cache_hit_rate: (requests > 0).then(|| reused as f64 / requests as f64),
+        }
+    }
+}
+
+/// Snapshot of [`CacheMetrics`], printed by `--concurrency-report`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConcurrencyReport {
+    /// Number of locks acquired
+    pub lock_acquisitions: u64,
+    /// Total time spent waiting for locks, across all tasks
+    pub lock_wait_total_ms: f64,
+    /// Longest single wait for a lock
+    pub lock_wait_max_ms: f64,
+    /// Number of fetches into existing bare repositories
+    pub fetches: u64,
+    /// Total time spent fetching
+    pub fetch_time_ms: f64,
+    /// Number of bare repositories cloned
+    pub clones: u64,
+    /// Total time spent cloning
+    pub clone_time_ms: f64,
+    /// Number of worktrees created
+    pub worktrees_created: u64,
+    /// Number of worktree requests served by an existing worktree
+    pub worktrees_reused: u64,
+    /// Total time spent creating worktrees
+    pub worktree_creation_ms: f64,
+    /// Share of worktree requests served by an existing worktree, if any were made
+    pub cache_hit_rate: Option<f64>,
+}
+
+impl fmt::Display for ConcurrencyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "  Locks:      {} acquired, {:.1}ms total wait, {:.1}ms max wait",
+            self.lock_acquisitions, self.lock_wait_total_ms, self.lock_wait_max_ms
+        )?;
+        writeln!(f, "  Fetches:    {} in {:.1}ms", self.fetches, self.fetch_time_ms)?;
+        writeln!(f, "  Clones:     {} in {:.1}ms", self.clones, self.clone_time_ms)?;
+        writeln!(
+            f,
+            "  Worktrees:  {} created in {:.1}ms, {} reused",
+            self.worktrees_created, self.worktree_creation_ms, self.worktrees_reused
+        )?;
+        match self.cache_hit_rate {
+            Some(rate) => write!(f, "  Cache hits: {:.0}%", rate * 100.0),
+            None => write!(f, "  Cache hits: n/a (no worktrees requested)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_summarizes_recorded_timings() {
+        let metrics = CacheMetrics::default();
+        metrics.record_lock_wait(Duration::from_millis(2));
+        metrics.record_lock_wait(Duration::from_millis(5));
+        metrics.record_fetch(Duration::from_millis(10));
+        metrics.record_worktree_created(Duration::from_millis(20));
+        metrics.record_worktree_reused();
+        metrics.record_worktree_reused();
+        metrics.record_worktree_reused();
+
+        let report = metrics.report();
+        assert_eq!(report.lock_acquisitions, 2);
+        assert_eq!(report.lock_wait_total_ms, 7.0);
+        assert_eq!(report.lock_wait_max_ms, 5.0);
+        assert_eq!(report.fetches, 1);
+        assert_eq!(report.clones, 0);
+        assert_eq!(report.worktrees_created, 1);
+        assert_eq!(report.worktrees_reused, 3);
+        assert_eq!(report.cache_hit_rate, Some(0.75));
+        assert!(report.to_string().contains("Cache hits: 75%"));
+    }
+
+    #[test]
+    fn test_report_without_worktrees_has_no_hit_rate() {
+        let report = CacheMetrics::default().report();
+        assert_eq!(report.cache_hit_rate, None);
+        assert!(report.to_string().contains("n/a"));
+    }
+}
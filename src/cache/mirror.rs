@@ -0,0 +1,342 @@
+//! Optional cache server for source repositories.
+//!
+//! Large teams can run a shared server close to their machines, so installing
+//! from a lockfile does not have to clone every source from upstream. It is
+//! configured in `~/.agpm/config.toml`:
+//!
+//! ```toml
+//! [cache]
+//! mirror_url = "https://agpm-cache.example.com"
+//! ```
+//!
+//! The server hosts bare Git repositories, reachable with any transport Git
+//! supports (for example `git http-backend`, or a static directory prepared
+//! with `git update-server-info`):
+//!
+//! ```text
+//! {mirror_url}/{owner}_{repo}.git
+//! ```
+//!
+//! `owner` and `repo` are derived from the source URL the same way as the
+//! cache directory names. When a worktree is needed for a commit and the
+//! source has no bare repository in the cache yet, AGPM clones it from the
+//! server instead of upstream, then:
+//!
+//! - points `origin` at the upstream URL, so later fetches go to the source;
+//! - deletes every branch and tag copied from the server, so the server can
+//!   never influence version resolution;
+//! - checks that the requested commit is present, and creates the worktree
+//!   from it with Git as usual.
+//!
+//! Git verifies every object it receives against its hash, and the worktree
+//! is checked out at the commit SHA from resolution or `agpm.lock`, so a
+//! compromised or intercepted server cannot change what gets installed.
+//!
+//! The server is purely an optimization: any failure (unreachable server,
+//! missing repository or commit) is logged and AGPM clones from upstream. Like
+//! the read-only base, the server URL is process-wide and installed once by
+//! the CLI before a command runs.
+
+use super::Cache;
+use crate::git::command_builder::GitCommand;
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use std::sync::RwLock;
+
+static MIRROR_URL: RwLock<Option<String>> = RwLock::new(None);
+
+/// Install the process-wide cache server URL from `cache.mirror_url`.
+pub fn set_mirror_url(url: Option<String>) {
+    let mut guard = MIRROR_URL.write().unwrap_or_else(std::sync::PoisonError::into_inner);
+    *guard = url;
+}
+
+/// The configured cache server URL, if any.
+#[must_use]
+pub fn mirror_url() -> Option<String> {
+    MIRROR_URL.read().unwrap_or_else(std::sync::PoisonError::into_inner).clone()
+}
+
+/// URL of the repository `{owner}_{repo}` on `mirror`.
+#[must_use]
+pub fn mirror_repo_url(mirror: &str, owner: &str, repo: &str) -> String {
+    format!("{}/{owner}_{repo}.git", mirror.trim_end_matches('/'))
+}
+
+impl Cache {
+    /// Use the cache server at `url` before cloning from upstream.
+    #[must_use]
+    pub fn with_mirror(mut self, url: Option<String>) -> Self {
+        self.mirror_url = url;
+        self
+    }
+
+    /// Returns the cache server URL, if one is configured.
+    #[must_use]
+    pub fn mirror(&self) -> Option<&str> {
+        self.mirror_url.as_deref()
+    }
+
+    /// Try to create the bare repository for `url` at `bare_repo_dir` from the
+    /// cache server, such that it contains `sha`.
+    ///
+    /// Returns `false` when no server is configured or any step failed, in
+    /// which case nothing is left at `bare_repo_dir` and the caller clones
+    /// from upstream.
+    pub(super) async fn clone_from_mirror(
+        &self,
+        owner: &str,
+        repo: &str,
+        url: &str,
+        sha: &str,
+        bare_repo_dir: &Path,
+    ) -> bool {
+        let Some(mirror) = self.mirror() else {
+            return false;
+        };
+        let mirror_url = mirror_repo_url(mirror, owner, repo);
+
+        match clone_verified(&mirror_url, url, sha, bare_repo_dir).await {
+            Ok(()) => {
+                tracing::debug!(target: "git", "Cloned {url} from cache server: {mirror_url}");
+                true
+            }
+            Err(e) => {
+                tracing::debug!(
+                    target: "git",
+                    "Cache server miss for {mirror_url}, falling back to Git: {e:#}"
+                );
+                if bare_repo_dir.exists() {
+                    tokio::fs::remove_dir_all(bare_repo_dir).await.ok();
+                }
+                false
+            }
+        }
+    }
+}
+
+/// Clone `mirror_url` to `target` as the bare repository of `url`, keeping
+/// only objects, and check that it contains `sha`.
+async fn clone_verified(mirror_url: &str, url: &str, sha: &str, target: &Path) -> Result<()> {
+    // Full clone: a partial one would fetch missing contents from upstream later
+    GitCommand::clone_bare_with_filter(mirror_url, target, false).execute_success().await?;
+
+    GitCommand::set_remote_url(url)
+        .current_dir(target)
+        .execute_success()
+        .await
+        .with_context(|| format!("Failed to point the repository at {url}"))?;
+    GitCommand::new()
+        .args(["config", "remote.origin.fetch", "+refs/heads/*:refs/remotes/origin/*"])
+        .current_dir(target)
+        .execute_success()
+        .await?;
+
+    // Refs come from the server and are not trusted; upstream fetches restore them
+    let refs = GitCommand::new()
+        .args(["for-each-ref", "--format=delete %(refname)"])
+        .current_dir(target)
+        .execute_stdout()
+        .await?;
+    if !refs.is_empty() {
+        GitCommand::new()
+            .args(["update-ref", "--stdin"])
+            .current_dir(target)
+            .execute_with_stdin(&format!("{refs}\n"))
+            .await?;
+    }
+
+    let found = GitCommand::new()
+        .args(["cat-file", "-e", &format!("{sha}^{{commit}}")])
+        .current_dir(target)
+        .execute_success()
+        .await;
+    if found.is_err() {
+        bail!("commit {sha} is not in the cache server's repository");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestGit;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    /// A repository at `temp/upstream` with one commit, and its SHA.
+    fn upstream(temp: &TempDir) -> (TestGit, String) {
+        let path = temp.path().join("upstream");
+        std::fs::create_dir_all(&path).unwrap();
+        let git = TestGit::new(&path);
+        git.init().unwrap();
+        git.config_user().unwrap();
+        std::fs::write(path.join("agent.md"), "# Agent").unwrap();
+        git.add_all().unwrap();
+        git.commit("Add agent").unwrap();
+        let sha = git.rev_parse_head().unwrap();
+        (git, sha)
+    }
+
+    /// Publish `source` on a cache server at `temp/mirror` as `{name}.git`.
+    fn serve(temp: &TempDir, source: &Path, name: &str) -> String {
+        let mirror = temp.path().join("mirror");
+        std::fs::create_dir_all(&mirror).unwrap();
+        let repo = mirror.join(format!("{name}.git"));
+        let output = std::process::Command::new("git")
+            .args(["clone", "--bare", "-q"])
+            .arg(source)
+            .arg(&repo)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        // Index files for serving the repository over dumb HTTP
+        git_output(&repo, &["update-server-info"]);
+        mirror.display().to_string()
+    }
+
+    /// Serve the files under `root` over HTTP, as Git's dumb HTTP protocol expects.
+    ///
+    /// Returns the base URL of the server.
+    async fn http_server(root: PathBuf) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0u8; 4096];
+                let len = stream.read(&mut request).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..len]).to_string();
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+                let path = path.split('?').next().unwrap_or(path).trim_start_matches('/');
+                let (status, body) = match std::fs::read(root.join(path)) {
+                    Ok(body) => ("200 OK", body),
+                    Err(_) => ("404 Not Found", Vec::new()),
+                };
+                let head = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(head.as_bytes()).await;
+                let _ = stream.write_all(&body).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+        url
+    }
+
+    fn git_output(dir: &Path, args: &[&str]) -> String {
+        let output =
+            std::process::Command::new("git").args(args).current_dir(dir).output().unwrap();
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn test_mirror_repo_url() {
+        assert_eq!(
+            mirror_repo_url("https://cache.example.com/", "org", "repo"),
+            "https://cache.example.com/org_repo.git"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_worktree_from_mirror() {
+        let temp = TempDir::new().unwrap();
+        let (git, sha) = upstream(&temp);
+        git.tag("v1.0.0").unwrap();
+        let mirror = serve(&temp, &temp.path().join("upstream"), "org_agpm-test-mirror-only");
+
+        // The upstream does not exist; everything comes from the server
+        let url = "https://github.com/org/agpm-test-mirror-only.git";
+        let cache = Cache::with_dir(temp.path().join("cache")).unwrap().with_mirror(Some(mirror));
+        let worktree =
+            cache.get_or_create_worktree_for_sha("community", url, &sha, None).await.unwrap();
+        assert_eq!(std::fs::read_to_string(worktree.join("agent.md")).unwrap(), "# Agent");
+
+        // A real worktree of a bare repository that fetches from upstream
+        assert!(worktree.join(".git").is_file());
+        let bare = temp.path().join("cache/sources/org_agpm-test-mirror-only.git");
+        assert_eq!(git_output(&bare, &["remote", "get-url", "origin"]), url);
+        assert_eq!(git_output(&worktree, &["rev-parse", "HEAD"]), sha);
+
+        // Refs from the server are dropped
+        assert_eq!(git_output(&bare, &["for-each-ref"]), "");
+    }
+
+    #[tokio::test]
+    async fn test_mirror_miss_falls_back_to_git() {
+        let temp = TempDir::new().unwrap();
+        let (git, _) = upstream(&temp);
+        let url = format!("file://{}", temp.path().join("upstream").display());
+
+        // The server only has an older state of the repository
+        let mirror = serve(&temp, &temp.path().join("upstream"), "local_upstream");
+        std::fs::write(temp.path().join("upstream/agent.md"), "# Agent v2").unwrap();
+        git.add_all().unwrap();
+        git.commit("Update agent").unwrap();
+        let sha = git.rev_parse_head().unwrap();
+
+        let cache = Cache::with_dir(temp.path().join("cache")).unwrap().with_mirror(Some(mirror));
+        let worktree =
+            cache.get_or_create_worktree_for_sha("local", &url, &sha, None).await.unwrap();
+        assert_eq!(std::fs::read_to_string(worktree.join("agent.md")).unwrap(), "# Agent v2");
+
+        // An unreachable server also falls back
+        let other = TempDir::new().unwrap();
+        let cache = Cache::with_dir(other.path().join("cache"))
+            .unwrap()
+            .with_mirror(Some("http://127.0.0.1:9".to_string()));
+        let worktree =
+            cache.get_or_create_worktree_for_sha("local", &url, &sha, None).await.unwrap();
+        assert_eq!(std::fs::read_to_string(worktree.join("agent.md")).unwrap(), "# Agent v2");
+    }
+
+    #[tokio::test]
+    async fn test_worktree_from_http_mirror() {
+        let temp = TempDir::new().unwrap();
+        let (_git, sha) = upstream(&temp);
+        let mirror = serve(&temp, &temp.path().join("upstream"), "org_agpm-test-http-mirror");
+        let server = http_server(PathBuf::from(mirror)).await;
+
+        let url = "https://github.com/org/agpm-test-http-mirror.git";
+        let cache = Cache::with_dir(temp.path().join("cache")).unwrap().with_mirror(Some(server));
+        let worktree =
+            cache.get_or_create_worktree_for_sha("community", url, &sha, None).await.unwrap();
+        assert_eq!(std::fs::read_to_string(worktree.join("agent.md")).unwrap(), "# Agent");
+
+        // Origin points upstream, not at the server it was cloned from
+        let bare = temp.path().join("cache/sources/org_agpm-test-http-mirror.git");
+        assert_eq!(git_output(&bare, &["remote", "get-url", "origin"]), url);
+        assert_eq!(git_output(&worktree, &["rev-parse", "HEAD"]), sha);
+    }
+
+    #[tokio::test]
+    async fn test_http_mirror_without_commit() {
+        let temp = TempDir::new().unwrap();
+        let (git, _) = upstream(&temp);
+        let url = format!("file://{}", temp.path().join("upstream").display());
+        let mirror = serve(&temp, &temp.path().join("upstream"), "local_upstream");
+        let server = http_server(PathBuf::from(mirror)).await;
+
+        // The commit only exists upstream
+        std::fs::write(temp.path().join("upstream/agent.md"), "# Agent v2").unwrap();
+        git.add_all().unwrap();
+        git.commit("Update agent").unwrap();
+        let sha = git.rev_parse_head().unwrap();
+
+        // The clone from the server is rejected and removed
+        let target = temp.path().join("rejected.git");
+        let mirror_url = mirror_repo_url(&server, "local", "upstream");
+        let err = clone_verified(&mirror_url, &url, &sha, &target).await.unwrap_err();
+        assert!(err.to_string().contains("is not in the cache server"), "{err}");
+        let cache = Cache::with_dir(temp.path().join("cache")).unwrap().with_mirror(Some(server));
+        assert!(!cache.clone_from_mirror("local", "upstream", &url, &sha, &target).await);
+        assert!(!target.exists());
+
+        // Resolution falls back to upstream
+        let worktree =
+            cache.get_or_create_worktree_for_sha("local", &url, &sha, None).await.unwrap();
+        assert_eq!(std::fs::read_to_string(worktree.join("agent.md")).unwrap(), "# Agent v2");
+    }
+}
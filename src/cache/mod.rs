@@ -23,22 +23,30 @@
 //! - Fsync-based verification ensures files readable after worktree creation
 //! - DashMap for lock-free concurrent worktree access
 //! - Command-instance fetch caching (single fetch per repo per command)
+//! - Blob-less fetches for commands that only resolve versions
 //! - Cross-platform path handling and cache locations
+//! - Optional read-only team cache checked before the user cache (see [`shared`])
+//! - Optional age-based removal of unused worktrees (see [`retention`])
+//! - Throwaway cache for isolated runs with `--no-cache` (see [`temporary`])
+//! - Configurable directory for temporary files (see [`temp_dir`])
+//! - Optional lock, fetch and worktree timings for `--concurrency-report` (see [`metrics`])
 
 use crate::constants::{default_lock_timeout, pending_state_timeout};
 use crate::core::error::AgpmError;
 use crate::core::file_error::{FileOperation, FileResultExt};
-use crate::git::GitRepo;
 use crate::git::command_builder::GitCommand;
+use crate::git::timeout::{GitOperation, run_with_timeout};
+use crate::git::{FetchMode, GitRepo};
 use crate::utils::fs;
 use crate::utils::security::validate_path_security;
+use crate::utils::terminal::SUCCESS;
 use anyhow::{Context, Result};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::fs as async_fs;
 use tokio::sync::{Mutex, MutexGuard, RwLock};
 
@@ -144,6 +152,9 @@ struct WorktreeRecord {
     version: String,
     path: PathBuf,
     last_used: u64,
+    /// Canonical worktree whose files this worktree hard-links to after `cache dedupe`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    linked_to: Option<PathBuf>,
 }
 
 impl WorktreeRegistry {
@@ -164,6 +175,13 @@ impl WorktreeRegistry {
             .unwrap_or_else(|_| Duration::from_secs(0))
             .as_secs();
 
+        // Preserve dedupe linkage when the same worktree is reused
+        let linked_to = self
+            .entries
+            .get(&key)
+            .filter(|record| record.path == path)
+            .and_then(|record| record.linked_to.clone());
+
         self.entries.insert(
             key,
             WorktreeRecord {
@@ -171,6 +189,7 @@ impl WorktreeRegistry {
                 version,
                 path,
                 last_used: timestamp,
+                linked_to,
             },
         );
     }
@@ -219,6 +238,33 @@ impl WorktreeRegistry {
 pub mod lock;
 pub use lock::CacheLock;
 
+mod archive;
+mod dedupe;
+pub use dedupe::DedupeReport;
+
+pub mod import;
+pub use import::ImportOutcome;
+
+pub mod metrics;
+pub use metrics::{CacheMetrics, ConcurrencyReport};
+
+pub mod mirror;
+
+mod overrides;
+
+pub mod repair;
+pub use repair::RepairReport;
+
+pub mod retention;
+pub use retention::WorktreeGcReport;
+
+pub mod shared;
+
+pub mod temp_dir;
+
+pub mod temporary;
+pub use temporary::TemporaryCache;
+
 /// Git repository cache for efficient resource management.
 ///
 /// Manages repository cloning, updating, version management, and resource copying.
@@ -235,6 +281,16 @@ pub struct Cache {
     fetched_repos: Arc<RwLock<HashSet<PathBuf>>>,
     /// Persistent worktree registry for reuse across runs
     worktree_registry: Arc<Mutex<WorktreeRegistry>>,
+    /// Read-only shared cache consulted before `dir`
+    read_only_base: Option<PathBuf>,
+    /// Cache server cloned from before upstream (see [`mirror`])
+    mirror_url: Option<String>,
+    /// Local directories used instead of worktrees, keyed by source name or URL
+    path_overrides: Arc<HashMap<String, PathBuf>>,
+    /// What fetches into existing bare repositories download
+    fetch_mode: FetchMode,
+    /// Timing recorder for `--concurrency-report`
+    metrics: Option<Arc<CacheMetrics>>,
 }
 
 impl Clone for Cache {
@@ -245,6 +301,11 @@ impl Clone for Cache {
             fetch_locks: Arc::clone(&self.fetch_locks),
             fetched_repos: Arc::clone(&self.fetched_repos),
             worktree_registry: Arc::clone(&self.worktree_registry),
+            read_only_base: self.read_only_base.clone(),
+            mirror_url: self.mirror_url.clone(),
+            path_overrides: Arc::clone(&self.path_overrides),
+            fetch_mode: self.fetch_mode,
+            metrics: self.metrics.clone(),
         }
     }
 }
@@ -265,8 +326,9 @@ impl Cache {
         version_key: &str,
         worktree_path: &Path,
     ) -> Result<()> {
-        let mut registry =
-            acquire_mutex_with_timeout(&self.worktree_registry, "worktree_registry").await?;
+        let mut registry = self
+            .timed_lock(acquire_mutex_with_timeout(&self.worktree_registry, "worktree_registry"))
+            .await?;
         registry.update(
             registry_key.to_string(),
             source_name.to_string(),
@@ -278,8 +340,9 @@ impl Cache {
     }
 
     async fn remove_worktree_record_by_path(&self, worktree_path: &Path) -> Result<()> {
-        let mut registry =
-            acquire_mutex_with_timeout(&self.worktree_registry, "worktree_registry").await?;
+        let mut registry = self
+            .timed_lock(acquire_mutex_with_timeout(&self.worktree_registry, "worktree_registry"))
+            .await?;
         if registry.remove_by_path(worktree_path) {
             registry.persist(&self.registry_path()).await?;
         }
@@ -308,18 +371,14 @@ impl Cache {
     /// Creates cache instance with default platform-specific directory.
     ///
     /// Linux/macOS: `~/.agpm/cache/`, Windows: `%LOCALAPPDATA%\agpm\cache\`.
-    /// Override with `AGPM_CACHE_DIR` environment variable.
+    /// Override with `AGPM_CACHE_DIR` environment variable. Uses the configured
+    /// `cache.read_only_base`, if any, as a shared read-only base, and
+    /// `cache.mirror_url`, if any, as a cache server for source repositories.
     pub fn new() -> Result<Self> {
         let dir = crate::config::get_cache_dir()?;
-        let registry_path = Self::registry_path_for(&dir);
-        let registry = WorktreeRegistry::load(&registry_path);
-        Ok(Self {
-            dir,
-            worktree_cache: Arc::new(DashMap::new()),
-            fetch_locks: Arc::new(DashMap::new()),
-            fetched_repos: Arc::new(RwLock::new(HashSet::new())),
-            worktree_registry: Arc::new(Mutex::new(registry)),
-        })
+        Ok(Self::with_dir(dir)?
+            .with_read_only_base(shared::read_only_base())
+            .with_mirror(mirror::mirror_url()))
     }
 
     /// Creates cache instance with custom directory (useful for testing).
@@ -332,9 +391,42 @@ impl Cache {
             fetch_locks: Arc::new(DashMap::new()),
             fetched_repos: Arc::new(RwLock::new(HashSet::new())),
             worktree_registry: Arc::new(Mutex::new(registry)),
+            read_only_base: None,
+            mirror_url: None,
+            path_overrides: Arc::new(HashMap::new()),
+            fetch_mode: FetchMode::Full,
+            metrics: None,
         })
     }
 
+    /// Download only what `mode` needs when fetching into existing bare repositories.
+    ///
+    /// Commands that resolve versions without installing use
+    /// [`FetchMode::RefsOnly`]; worktrees checked out from such a repository
+    /// still fetch the file contents they need.
+    #[must_use]
+    pub fn with_fetch_mode(mut self, mode: FetchMode) -> Self {
+        self.fetch_mode = mode;
+        self
+    }
+
+    /// Record lock waits, fetches and worktree creation in `metrics`.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: Arc<CacheMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Await a lock acquisition, recording the wait when metrics are enabled.
+    async fn timed_lock<T>(&self, acquire: impl Future<Output = Result<T>>) -> Result<T> {
+        let started = Instant::now();
+        let guard = acquire.await?;
+        if let Some(metrics) = &self.metrics {
+            metrics.record_lock_wait(started.elapsed());
+        }
+        Ok(guard)
+    }
+
     /// Ensures cache directory exists, creating if necessary. Safe to call multiple times.
     pub async fn ensure_cache_dir(&self) -> Result<()> {
         if !self.dir.exists() {
@@ -354,12 +446,18 @@ impl Cache {
         &self.dir
     }
 
-    /// Constructs worktree path for URL and SHA (does not check existence or create).
+    /// Constructs worktree path for URL and SHA (does not create it).
+    ///
+    /// Returns the worktree in the read-only base when it exists there.
     pub fn get_worktree_path(&self, url: &str, sha: &str) -> Result<PathBuf> {
+        if crate::source::archive::is_archive_url(url) {
+            return Ok(self.archive_path(&crate::source::archive::ArchiveUrl::parse(url)?));
+        }
         let (owner, repo) =
             crate::git::parse_git_url(url).map_err(|e| anyhow::anyhow!("Invalid Git URL: {e}"))?;
         let sha_short = &sha[..8.min(sha.len())];
-        Ok(self.dir.join("worktrees").join(format!("{owner}_{repo}_{sha_short}")))
+        let relative = Path::new("worktrees").join(format!("{owner}_{repo}_{sha_short}"));
+        Ok(self.base_path(&relative).unwrap_or_else(|| self.dir.join(relative)))
     }
 
     /// Gets or clones source repository to cache.
@@ -378,7 +476,7 @@ impl Cache {
         url: &str,
         version: Option<&str>,
     ) -> Result<PathBuf> {
-        self.get_or_clone_source_impl(name, url, version).await
+        self.get_or_clone_source_impl(name, url, version, true).await
     }
 
     /// Removes worktree using `git worktree remove` to properly clean up metadata.
@@ -386,7 +484,7 @@ impl Cache {
     /// This ensures both the worktree directory AND the bare repo's metadata are cleaned up,
     /// preventing "missing but already registered worktree" errors on subsequent creation.
     pub async fn cleanup_worktree(&self, worktree_path: &Path) -> Result<()> {
-        if !worktree_path.exists() {
+        if !worktree_path.exists() || self.is_in_read_only_base(worktree_path) {
             return Ok(());
         }
 
@@ -500,6 +598,11 @@ impl Cache {
             ));
         }
 
+        // Overridden sources are read from the local checkout as-is
+        if let Some(local) = self.path_override(name, url) {
+            return Ok(local.to_path_buf());
+        }
+
         // Check if this is a local path
         let is_local_path = crate::utils::is_local_path(url);
         if is_local_path {
@@ -507,19 +610,39 @@ impl Cache {
             return self.get_or_clone_source(name, url, None).await;
         }
 
-        self.ensure_cache_dir().await?;
+        // Archive sources are extracted once and have no worktrees
+        if crate::source::archive::is_archive_url(url) {
+            return Ok(self.get_or_fetch_archive(url, Some(sha)).await?.0);
+        }
 
         // Parse URL for cache structure
         let (owner, repo) =
             crate::git::parse_git_url(url).unwrap_or(("direct".to_string(), "repo".to_string()));
 
+        // Worktrees in the read-only base are used as-is
+        let sha_short = &sha[..8];
+        if let Some(base_worktree) =
+            self.base_path(&Path::new("worktrees").join(format!("{owner}_{repo}_{sha_short}")))
+        {
+            tracing::debug!(
+                target: "git",
+                "Using worktree from read-only cache base: {}",
+                base_worktree.display()
+            );
+            if let Some(metrics) = &self.metrics {
+                metrics.record_worktree_reused();
+            }
+            return Ok(base_worktree);
+        }
+
+        self.ensure_cache_dir().await?;
+
         // Define unified lock name and bare repo path for this repository
         let bare_repo_dir = self.dir.join("sources").join(format!("{owner}_{repo}.git"));
         let bare_repo_lock_name = format!("bare-repo-{owner}_{repo}");
 
         // Create SHA-based cache key
         // Using first 8 chars of SHA for directory name (like Git does)
-        let sha_short = &sha[..8];
         let cache_dir_hash = {
             use std::collections::hash_map::DefaultHasher;
             use std::hash::{Hash, Hasher};
@@ -541,6 +664,9 @@ impl Cache {
                             // Worktree already exists and is ready
                             let cached_path = cached_path.clone();
                             drop(entry);
+                            if let Some(metrics) = &self.metrics {
+                                metrics.record_worktree_reused();
+                            }
 
                             self.record_worktree_usage(&cache_key, name, sha_short, &cached_path)
                                 .await?;
@@ -648,7 +774,8 @@ impl Cache {
                     bare_repo_dir.display()
                 );
 
-                let bare_repo_lock = CacheLock::acquire(&self.dir, &bare_repo_lock_name).await?;
+                let bare_repo_lock =
+                    self.timed_lock(CacheLock::acquire(&self.dir, &bare_repo_lock_name)).await?;
 
                 // Re-check after acquiring lock (another task may have cloned it)
                 if !bare_repo_dir.exists() {
@@ -667,26 +794,35 @@ impl Cache {
                         tracing::debug!("📦 Cloning repository {url} to cache...");
                     }
 
-                    // Add timeout to prevent hung clone operations
-                    tokio::time::timeout(
-                        crate::constants::GIT_CLONE_TIMEOUT,
-                        GitRepo::clone_bare_with_context(url, &bare_repo_dir, context),
-                    )
-                    .await
-                    .map_err(|_| {
-                        anyhow::anyhow!(
-                            "Git clone operation timed out after {:?} for {}",
-                            crate::constants::GIT_CLONE_TIMEOUT,
-                            url
+                    // Ask the cache server first; its clone is not marked as
+                    // fetched, so the next resolve still fetches refs from upstream
+                    let started = Instant::now();
+                    if self.clone_from_mirror(&owner, &repo, url, sha, &bare_repo_dir).await {
+                        if let Some(metrics) = &self.metrics {
+                            metrics.record_clone(started.elapsed());
+                        }
+                    } else {
+                        // Add timeout to prevent hung clone operations
+                        run_with_timeout(
+                            GitOperation::Clone.timeout(),
+                            GitRepo::clone_bare_with_context(url, &bare_repo_dir, context),
+                            || format!("Git clone operation for {url}"),
                         )
-                    })??;
-
-                    Self::configure_connection_pooling(&bare_repo_dir).await.ok();
+                        .await?;
+                        if let Some(metrics) = &self.metrics {
+                            metrics.record_clone(started.elapsed());
+                        }
 
-                    // Mark as fetched since clone_bare_with_context already fetches
-                    acquire_rwlock_write_with_timeout(&self.fetched_repos, "fetched_repos")
+                        // Mark as fetched since clone_bare_with_context already fetches
+                        self.timed_lock(acquire_rwlock_write_with_timeout(
+                            &self.fetched_repos,
+                            "fetched_repos",
+                        ))
                         .await?
                         .insert(bare_repo_dir.clone());
+                    }
+
+                    Self::configure_connection_pooling(&bare_repo_dir).await.ok();
                 }
 
                 // Release bare repo lock before proceeding to worktree creation
@@ -702,10 +838,14 @@ impl Cache {
 
             // Acquire per-SHA worktree lock for caching/deduplication.
             let worktree_lock_name = format!("worktree-{owner}-{repo}-{sha_short}");
-            let _worktree_lock = CacheLock::acquire(&self.dir, &worktree_lock_name).await?;
+            let _worktree_lock =
+                self.timed_lock(CacheLock::acquire(&self.dir, &worktree_lock_name)).await?;
 
             // Re-check after lock
             if worktree_path.exists() {
+                if let Some(metrics) = &self.metrics {
+                    metrics.record_worktree_reused();
+                }
                 // Notify and update cache to Ready
                 let notify_to_wake = extract_notify_handle(&self.worktree_cache, &cache_key);
                 self.worktree_cache
@@ -742,24 +882,19 @@ impl Cache {
             // to its own subdirectory in .git/worktrees/). Exclusive locks are used only
             // for deletion/pruning operations that modify shared state.
             let bare_repo_worktree_lock_name = format!("bare-worktree-{owner}_{repo}");
-            let _bare_worktree_lock =
-                CacheLock::acquire_shared(&self.dir, &bare_repo_worktree_lock_name).await?;
+            let _bare_worktree_lock = self
+                .timed_lock(CacheLock::acquire_shared(&self.dir, &bare_repo_worktree_lock_name))
+                .await?;
 
             // Create worktree using SHA directly
             // Add timeout to prevent hung worktree creation
-            let worktree_result = tokio::time::timeout(
-                crate::constants::GIT_WORKTREE_TIMEOUT,
+            let started = Instant::now();
+            let worktree_result = run_with_timeout(
+                GitOperation::Worktree.timeout(),
                 bare_repo.create_worktree_with_context(&worktree_path, Some(sha), context),
+                || format!("Git worktree creation for {url} @ {sha_short}"),
             )
-            .await
-            .map_err(|_| {
-                anyhow::anyhow!(
-                    "Git worktree creation timed out after {:?} for {} @ {}",
-                    crate::constants::GIT_WORKTREE_TIMEOUT,
-                    url,
-                    sha_short
-                )
-            })?;
+            .await;
 
             // Keep lock held until cache is updated to ensure git state is fully settled
             match worktree_result {
@@ -785,6 +920,9 @@ impl Cache {
 
                     // Release bare repo lock - worktree creation is complete
                     drop(_bare_worktree_lock);
+                    if let Some(metrics) = &self.metrics {
+                        metrics.record_worktree_created(started.elapsed());
+                    }
 
                     // Fsync both directories to ensure all file entries are visible.
                     // This fixes APFS/filesystem buffer cache issues where files aren't
@@ -874,6 +1012,7 @@ impl Cache {
         name: &str,
         url: &str,
         version: Option<&str>,
+        use_base: bool,
     ) -> Result<PathBuf> {
         // Check if this is a local path (not a git repository URL)
         let is_local_path = crate::utils::is_local_path(url);
@@ -902,10 +1041,27 @@ impl Cache {
             return Ok(canonical_path);
         }
 
+        if crate::source::archive::is_archive_url(url) {
+            return Ok(self.get_or_fetch_archive(url, None).await?.0);
+        }
+
+        // Bare repositories in the read-only base are used without fetching
+        let base_repo = self.bare_repo_path(url);
+        if use_base && self.is_in_read_only_base(&base_repo) {
+            tracing::debug!(
+                target: "agpm::cache",
+                "Using {} from read-only cache base: {}",
+                name,
+                base_repo.display()
+            );
+            return Ok(base_repo);
+        }
+
         self.ensure_cache_dir().await?;
 
         // Acquire lock for this source to prevent concurrent access
-        let _lock = CacheLock::acquire(&self.dir, name)
+        let _lock = self
+            .timed_lock(CacheLock::acquire(&self.dir, name))
             .await
             .with_context(|| format!("Failed to acquire lock for source: {name}"))?;
 
@@ -932,9 +1088,12 @@ impl Cache {
             if crate::utils::is_git_url(url) {
                 // Check if we've already fetched this repo in this command instance
                 let already_fetched = {
-                    let fetched =
-                        acquire_rwlock_read_with_timeout(&self.fetched_repos, "fetched_repos")
-                            .await?;
+                    let fetched = self
+                        .timed_lock(acquire_rwlock_read_with_timeout(
+                            &self.fetched_repos,
+                            "fetched_repos",
+                        ))
+                        .await?;
                     fetched.contains(&source_dir)
                 };
 
@@ -952,7 +1111,12 @@ impl Cache {
                         url
                     );
                     let repo = crate::git::GitRepo::new(&source_dir);
-                    if let Err(e) = repo.fetch(None).await {
+                    let started = Instant::now();
+                    let fetched = repo.fetch_with_mode(None, self.fetch_mode).await;
+                    if let Some(metrics) = &self.metrics {
+                        metrics.record_fetch(started.elapsed());
+                    }
+                    if let Err(e) = fetched {
                         tracing::warn!(
                             target: "agpm::cache",
                             "Failed to fetch updates for {}: {}",
@@ -961,9 +1125,12 @@ impl Cache {
                         );
                     } else {
                         // Mark this repo as fetched for this command execution
-                        let mut fetched =
-                            acquire_rwlock_write_with_timeout(&self.fetched_repos, "fetched_repos")
-                                .await?;
+                        let mut fetched = self
+                            .timed_lock(acquire_rwlock_write_with_timeout(
+                                &self.fetched_repos,
+                                "fetched_repos",
+                            ))
+                            .await?;
                         fetched.insert(source_dir.clone());
                         tracing::debug!(
                             target: "agpm::cache",
@@ -1025,9 +1192,13 @@ impl Cache {
         tracing::debug!("📦 Cloning {} to cache...", url);
 
         // Clone as a bare repository for better concurrency and worktree support
+        let started = Instant::now();
         GitRepo::clone_bare(url, target)
             .await
             .with_context(|| format!("Failed to clone repository from {url}"))?;
+        if let Some(metrics) = &self.metrics {
+            metrics.record_clone(started.elapsed());
+        }
 
         // Debug: List what was cloned
         if cfg!(test)
@@ -1108,7 +1279,7 @@ impl Cache {
         })?;
 
         if show_output {
-            println!("  ✅ Installed {}", target_path.display());
+            println!("  {SUCCESS}Installed {}", target_path.display());
         }
 
         Ok(())
@@ -1116,15 +1287,15 @@ impl Cache {
 
     /// Removes cached repositories not in active sources list.
     ///
-    /// Returns count of removed directories. Displays progress messages.
+    /// Returns the names of the removed directories.
     ///
     /// # Arguments
     ///
     /// * `active_sources` - Source names to preserve
-    pub async fn clean_unused(&self, active_sources: &[String]) -> Result<usize> {
+    pub async fn clean_unused(&self, active_sources: &[String]) -> Result<Vec<String>> {
         self.ensure_cache_dir().await?;
 
-        let mut removed_count = 0;
+        let mut removed = Vec::new();
         let mut entries = async_fs::read_dir(&self.dir)
             .await
             .with_context(|| "Failed to read cache directory")?;
@@ -1137,16 +1308,15 @@ impl Cache {
                 let dir_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
                 if !active_sources.contains(&dir_name.to_string()) {
-                    println!("🗑️  Removing unused cache: {dir_name}");
                     async_fs::remove_dir_all(&path).await.with_context(|| {
                         format!("Failed to remove cache directory: {}", path.display())
                     })?;
-                    removed_count += 1;
+                    removed.push(dir_name.to_string());
                 }
             }
         }
 
-        Ok(removed_count)
+        Ok(removed)
     }
 
     /// Calculates total cache size in bytes (recursive, returns 0 if not exists).
@@ -1169,7 +1339,6 @@ impl Cache {
     pub async fn clear_all(&self) -> Result<()> {
         if self.dir.exists() {
             async_fs::remove_dir_all(&self.dir).await.with_context(|| "Failed to clear cache")?;
-            println!("🗑️  Cleared all cache");
         }
         Ok(())
     }
@@ -1234,7 +1403,7 @@ mod tests {
         cache.ensure_cache_dir().await.unwrap();
 
         let removed = cache.clean_unused(&["active".to_string()]).await.unwrap();
-        assert_eq!(removed, 0);
+        assert!(removed.is_empty());
     }
 
     #[tokio::test]
@@ -1260,7 +1429,7 @@ mod tests {
 
         let removed = cache.clean_unused(&["active".to_string()]).await.unwrap();
 
-        assert_eq!(removed, 2);
+        assert_eq!(removed.len(), 2);
         assert!(active_dir.exists());
         assert!(!unused_dir.exists());
         assert!(!another_unused.exists());
@@ -1440,7 +1609,7 @@ mod tests {
         let removed = cache.clean_unused(&["keep".to_string()]).await.unwrap();
 
         // Should only remove the "remove" directory, not the file
-        assert_eq!(removed, 1);
+        assert_eq!(removed, vec!["remove".to_string()]);
         assert!(temp_dir.path().join("keep").exists());
         assert!(!temp_dir.path().join("remove").exists());
         assert!(temp_dir.path().join("file.txt").exists());
@@ -1513,7 +1682,7 @@ mod tests {
         // Empty active list should remove all
         let removed = cache.clean_unused(&[]).await.unwrap();
 
-        assert_eq!(removed, 2);
+        assert_eq!(removed.len(), 2);
         assert!(!temp_dir.path().join("source1").exists());
         assert!(!temp_dir.path().join("source2").exists());
     }
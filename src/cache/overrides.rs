@@ -0,0 +1,77 @@
+//! Local path overrides for Git sources.
+//!
+//! A project's `[path-overrides]` table maps a source name or URL to a local
+//! checkout. Versions are still resolved against the remote repository, but
+//! whenever a worktree for an overridden source is requested, the local
+//! directory is returned instead, so every resource from that source is read
+//! from the working copy being developed.
+
+use super::Cache;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+impl Cache {
+    /// Read sources matching a key of `overrides` (source name or URL) from
+    /// the mapped local directories instead of worktrees.
+    #[must_use]
+    pub fn with_path_overrides(mut self, overrides: HashMap<String, PathBuf>) -> Self {
+        self.path_overrides = Arc::new(overrides);
+        self
+    }
+
+    /// Whether any source is read from a local checkout.
+    #[must_use]
+    pub fn has_path_overrides(&self) -> bool {
+        !self.path_overrides.is_empty()
+    }
+
+    /// Returns the local directory overriding the source `name` at `url`, if any.
+    ///
+    /// The source name takes precedence over the URL.
+    #[must_use]
+    pub fn path_override(&self, name: &str, url: &str) -> Option<&Path> {
+        self.path_overrides.get(name).or_else(|| self.path_overrides.get(url)).map(PathBuf::as_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SHA: &str = "0123456789abcdef0123456789abcdef01234567";
+
+    #[tokio::test]
+    async fn test_path_override_replaces_worktree() {
+        let temp = TempDir::new().unwrap();
+        let local = temp.path().join("checkout");
+        std::fs::create_dir_all(&local).unwrap();
+
+        let overrides = HashMap::from([
+            ("community".to_string(), local.clone()),
+            ("https://git.invalid/org/tools.git".to_string(), local.clone()),
+        ]);
+        let cache =
+            Cache::with_dir(temp.path().join("cache")).unwrap().with_path_overrides(overrides);
+
+        assert_eq!(
+            cache.path_override("community", "https://git.invalid/a.git"),
+            Some(local.as_path())
+        );
+        assert_eq!(
+            cache.path_override("tools", "https://git.invalid/org/tools.git"),
+            Some(local.as_path())
+        );
+        assert_eq!(cache.path_override("other", "https://git.invalid/b.git"), None);
+        assert!(cache.has_path_overrides());
+
+        // No clone is attempted for an overridden source
+        let worktree = cache
+            .get_or_create_worktree_for_sha("community", "https://git.invalid/a.git", SHA, None)
+            .await
+            .unwrap();
+        assert_eq!(worktree, local);
+        assert!(!temp.path().join("cache/sources").exists());
+    }
+}
@@ -0,0 +1,114 @@
+//! Repair of a single corrupt bare repository (`agpm cache repair`).
+//!
+//! An interrupted clone or a damaged pack can leave one source's bare
+//! repository unusable while the rest of the cache is fine. Repairing a source
+//! removes its bare repository and every worktree checked out from it, clones
+//! it again and checks the new clone before reporting success. Other sources
+//! and the read-only shared cache base are never touched.
+
+use super::dedupe::repo_key_from_worktree_dir;
+use super::{Cache, CacheLock};
+use crate::core::file_error::{FileOperation, FileResultExt};
+use crate::git::command_builder::GitCommand;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+/// Summary of a repaired source.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct RepairReport {
+    /// Name of the repaired source
+    pub source: String,
+    /// Source URL, without credentials
+    pub url: String,
+    /// The re-cloned bare repository
+    pub path: PathBuf,
+    /// Worktrees of the source that were removed
+    pub worktrees_removed: Vec<PathBuf>,
+}
+
+impl Cache {
+    /// Removes and re-clones the bare repository of source `name` at `url`.
+    ///
+    /// Worktrees of the source are removed along with it; they are recreated
+    /// on the next install. Holds the source's locks for the whole operation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `url` is a local path or archive, if the locks
+    /// cannot be acquired, or if the new clone fails or does not pass
+    /// `git fsck`.
+    pub async fn repair_source(&self, name: &str, url: &str) -> Result<RepairReport> {
+        if crate::utils::is_local_path(url) || crate::source::archive::is_archive_url(url) {
+            anyhow::bail!("Source '{name}' is not a Git repository, so it has no cached clone");
+        }
+
+        let (owner, repo) =
+            crate::git::parse_git_url(url).unwrap_or(("direct".to_string(), "repo".to_string()));
+        let repo_key = format!("{owner}_{repo}");
+        let bare_repo = self.dir.join("sources").join(format!("{repo_key}.git"));
+
+        self.ensure_cache_dir().await?;
+        // Same locks, in the same order, as cloning and worktree creation
+        let _source_lock = CacheLock::acquire(&self.dir, name)
+            .await
+            .with_context(|| format!("Failed to acquire lock for source: {name}"))?;
+        let _bare_repo_lock =
+            CacheLock::acquire(&self.dir, &format!("bare-repo-{repo_key}")).await?;
+        let _worktree_lock =
+            CacheLock::acquire(&self.dir, &format!("bare-worktree-{repo_key}")).await?;
+
+        let mut worktrees_removed = Vec::new();
+        let worktrees_dir = self.dir.join("worktrees");
+        if worktrees_dir.exists() {
+            let mut entries = tokio::fs::read_dir(&worktrees_dir).await.with_file_context(
+                FileOperation::Read,
+                &worktrees_dir,
+                "listing worktrees to repair",
+                "cache::repair",
+            )?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path.is_dir() && repo_key_from_worktree_dir(&path).as_deref() == Some(&repo_key)
+                {
+                    worktrees_removed.push(path);
+                }
+            }
+        }
+        worktrees_removed.sort();
+        for path in &worktrees_removed {
+            tokio::fs::remove_dir_all(path).await.with_file_context(
+                FileOperation::Write,
+                path,
+                "removing worktree of repaired source",
+                "cache::repair",
+            )?;
+            self.remove_worktree_record_by_path(path).await?;
+        }
+
+        if bare_repo.exists() {
+            tokio::fs::remove_dir_all(&bare_repo).await.with_file_context(
+                FileOperation::Write,
+                &bare_repo,
+                "removing corrupt bare repository",
+                "cache::repair",
+            )?;
+        }
+
+        self.clone_source(url, &bare_repo)
+            .await
+            .with_context(|| format!("Failed to re-clone source '{name}'"))?;
+        GitCommand::new()
+            .args(["fsck", "--connectivity-only", "--no-dangling"])
+            .current_dir(&bare_repo)
+            .execute_success()
+            .await
+            .with_context(|| format!("Re-cloned repository of source '{name}' failed git fsck"))?;
+
+        Ok(RepairReport {
+            source: name.to_string(),
+            url: crate::git::strip_auth_from_url(url).unwrap_or_else(|_| url.to_string()),
+            path: bare_repo,
+            worktrees_removed,
+        })
+    }
+}
@@ -0,0 +1,206 @@
+//! Age-based removal of unused worktrees.
+//!
+//! SHA-based worktrees are kept after installation so later runs can reuse
+//! them, which means they accumulate as dependencies move to new commits. With
+//! `cache.worktree_max_age_days` set in `~/.agpm/config.toml`, every
+//! `agpm install` ends with a pass that removes worktrees whose `last_used`
+//! timestamp in the worktree registry is older than the limit. The same pass
+//! runs on demand with `agpm cache gc`.
+//!
+//! # Safety
+//!
+//! - Worktrees used by the current command are never removed
+//! - The on-disk registry is re-read first, so worktrees another AGPM process
+//!   used since this one started are treated as fresh
+//! - A worktree whose repository lock cannot be acquired within
+//!   [`WORKTREE_GC_LOCK_TIMEOUT`] is assumed to be in use and skipped
+//! - The read-only shared cache base is never touched
+
+use super::dedupe::repo_key_from_worktree_dir;
+use super::{Cache, CacheLock, WorktreeRegistry, WorktreeState, acquire_mutex_with_timeout};
+use crate::constants::WORKTREE_GC_LOCK_TIMEOUT;
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+static WORKTREE_MAX_AGE: RwLock<Option<Duration>> = RwLock::new(None);
+
+/// Install the process-wide worktree age limit from `cache.worktree_max_age_days`.
+pub fn set_worktree_max_age(max_age: Option<Duration>) {
+    let mut guard = WORKTREE_MAX_AGE.write().unwrap_or_else(std::sync::PoisonError::into_inner);
+    *guard = max_age;
+}
+
+/// The configured worktree age limit, if any.
+#[must_use]
+pub fn worktree_max_age() -> Option<Duration> {
+    *WORKTREE_MAX_AGE.read().unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Summary of a worktree garbage collection pass.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct WorktreeGcReport {
+    /// Number of worktrees in the registry
+    pub worktrees_scanned: usize,
+    /// Worktrees removed (or that would be removed in dry-run mode)
+    pub removed: Vec<PathBuf>,
+    /// Bytes freed (or that would be freed in dry-run mode)
+    pub bytes_reclaimed: u64,
+    /// Stale worktrees skipped because they are in use
+    pub skipped_in_use: Vec<PathBuf>,
+}
+
+impl Cache {
+    /// Removes worktrees that have not been used for longer than `max_age`.
+    ///
+    /// Registry records whose worktree directory no longer exists are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_age` - Worktrees last used longer ago than this are removed
+    /// * `dry_run` - Report what would be removed without modifying anything
+    pub async fn remove_stale_worktrees(
+        &self,
+        max_age: Duration,
+        dry_run: bool,
+    ) -> Result<WorktreeGcReport> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let cutoff = now.saturating_sub(max_age.as_secs());
+
+        // Another process may have used a worktree since this cache loaded the registry
+        let on_disk = WorktreeRegistry::load(&self.registry_path());
+        let mut last_used: BTreeMap<PathBuf, u64> = BTreeMap::new();
+        {
+            let registry =
+                acquire_mutex_with_timeout(&self.worktree_registry, "worktree_registry").await?;
+            for record in registry.entries.values().chain(on_disk.entries.values()) {
+                let used = last_used.entry(record.path.clone()).or_default();
+                *used = (*used).max(record.last_used);
+            }
+        }
+
+        let in_use: HashSet<PathBuf> = self
+            .worktree_cache
+            .iter()
+            .filter_map(|entry| match entry.value() {
+                WorktreeState::Ready(path) => Some(path.clone()),
+                WorktreeState::Pending(_) => None,
+            })
+            .collect();
+
+        let mut report = WorktreeGcReport {
+            worktrees_scanned: last_used.len(),
+            ..Default::default()
+        };
+
+        for (path, used) in last_used {
+            if used > cutoff || self.is_in_read_only_base(&path) {
+                continue;
+            }
+            if !path.exists() {
+                if !dry_run {
+                    self.remove_worktree_record_by_path(&path).await?;
+                }
+                continue;
+            }
+            if in_use.contains(&path) || !self.worktree_lock_available(&path).await {
+                report.skipped_in_use.push(path);
+                continue;
+            }
+
+            let size = crate::utils::fs::get_directory_size(&path).await.unwrap_or(0);
+            if !dry_run {
+                // cleanup_worktree takes the bare-worktree lock itself
+                self.cleanup_worktree(&path).await?;
+            }
+            report.bytes_reclaimed += size;
+            report.removed.push(path);
+        }
+
+        Ok(report)
+    }
+
+    /// Whether the repository lock guarding `worktree` can be acquired right now.
+    async fn worktree_lock_available(&self, worktree: &std::path::Path) -> bool {
+        let Some(repo_key) = repo_key_from_worktree_dir(worktree) else {
+            return true;
+        };
+        let name = format!("bare-worktree-{repo_key}");
+        match CacheLock::acquire_with_timeout(&self.dir, &name, WORKTREE_GC_LOCK_TIMEOUT).await {
+            Ok(_lock) => true,
+            Err(e) => {
+                tracing::debug!(
+                    "Skipping worktree {}, lock {name} unavailable: {e}",
+                    worktree.display()
+                );
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    async fn add_worktree(cache: &Cache, name: &str, age: u64) -> PathBuf {
+        let path = cache.dir.join("worktrees").join(name);
+        std::fs::create_dir_all(path.join("agents")).unwrap();
+        std::fs::write(path.join("agents/a.md"), "# Agent").unwrap();
+
+        cache
+            .record_worktree_usage(name, "https://example.com/org/repo.git", "v1.0.0", &path)
+            .await
+            .unwrap();
+        let mut registry = cache.worktree_registry.lock().await;
+        registry.entries.get_mut(name).unwrap().last_used -= age;
+        registry.persist(&cache.registry_path()).await.unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn test_remove_stale_worktrees() -> Result<()> {
+        let temp = TempDir::new()?;
+        let cache = Cache::with_dir(temp.path().to_path_buf())?;
+        let stale = add_worktree(&cache, "org_repo_aaaaaaaa", 10 * DAY).await;
+        let fresh = add_worktree(&cache, "org_repo_bbbbbbbb", DAY).await;
+
+        let max_age = Duration::from_secs(7 * DAY);
+        let report = cache.remove_stale_worktrees(max_age, true).await?;
+        assert_eq!(report.worktrees_scanned, 2);
+        assert_eq!(report.removed, vec![stale.clone()]);
+        assert_eq!(report.bytes_reclaimed, "# Agent".len() as u64);
+        assert!(stale.exists(), "dry run must not remove anything");
+
+        let report = cache.remove_stale_worktrees(max_age, false).await?;
+        assert_eq!(report.removed, vec![stale.clone()]);
+        assert!(!stale.exists());
+        assert!(fresh.exists());
+
+        let registry = WorktreeRegistry::load(&cache.registry_path());
+        assert_eq!(registry.entries.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_remove_stale_worktrees_skips_in_use() -> Result<()> {
+        let temp = TempDir::new()?;
+        let cache = Cache::with_dir(temp.path().to_path_buf())?;
+        let current = add_worktree(&cache, "org_repo_aaaaaaaa", 10 * DAY).await;
+        cache.worktree_cache.insert("current".to_string(), WorktreeState::Ready(current.clone()));
+        let locked = add_worktree(&cache, "other_repo_bbbbbbbb", 10 * DAY).await;
+        let _held = CacheLock::acquire(temp.path(), "bare-worktree-other_repo").await?;
+
+        let report = cache.remove_stale_worktrees(Duration::from_secs(DAY), false).await?;
+        assert!(report.removed.is_empty());
+        assert_eq!(report.skipped_in_use, vec![current.clone(), locked.clone()]);
+        assert!(current.exists());
+        assert!(locked.exists());
+        Ok(())
+    }
+}
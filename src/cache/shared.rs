@@ -0,0 +1,246 @@
+//! Read-only shared cache with a per-user overlay.
+//!
+//! Teams can point `cache.read_only_base` in `~/.agpm/config.toml` at a cache
+//! directory on a network drive that is populated out of band (for example by a
+//! scheduled `agpm install` under a service account). The base is laid out like
+//! a regular cache (`sources/` and `worktrees/`) and is consulted first:
+//!
+//! - A bare repository present in the base is used as-is for version
+//!   resolution. It is never fetched, so keeping it current is up to whoever
+//!   maintains the base. When a tag, branch or commit requested by the project
+//!   is missing from it, the source is cloned or fetched into the user cache
+//!   and resolved from there instead.
+//! - A worktree present in the base is used for installation without creating
+//!   one in the user cache.
+//!
+//! Anything missing from the base is cloned and checked out in the regular
+//! user cache, which acts as a writable overlay. AGPM never writes to the base:
+//! locks, the worktree registry, cleanup and `cache clean` only touch the user
+//! cache. Like the Git settings, the base is process-wide and installed once by
+//! the CLI before a command runs.
+
+use super::Cache;
+use crate::git::command_builder::GitCommand;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+static READ_ONLY_BASE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Install the process-wide read-only cache base from `cache.read_only_base`.
+pub fn set_read_only_base(base: Option<PathBuf>) {
+    let mut guard = READ_ONLY_BASE.write().unwrap_or_else(std::sync::PoisonError::into_inner);
+    *guard = base;
+}
+
+/// The configured read-only cache base, if any.
+#[must_use]
+pub fn read_only_base() -> Option<PathBuf> {
+    READ_ONLY_BASE.read().unwrap_or_else(std::sync::PoisonError::into_inner).clone()
+}
+
+impl Cache {
+    /// Use `base` as the read-only cache checked before this cache.
+    #[must_use]
+    pub fn with_read_only_base(mut self, base: Option<PathBuf>) -> Self {
+        self.read_only_base = base;
+        self
+    }
+
+    /// Returns the read-only cache base, if one is configured.
+    #[must_use]
+    pub fn read_only_base(&self) -> Option<&Path> {
+        self.read_only_base.as_deref()
+    }
+
+    /// Path of the bare repository for `url`, preferring the read-only base.
+    ///
+    /// Falls back to the user cache path, whether or not it exists yet.
+    #[must_use]
+    pub fn bare_repo_path(&self, url: &str) -> PathBuf {
+        let (owner, repo) =
+            crate::git::parse_git_url(url).unwrap_or(("direct".to_string(), "repo".to_string()));
+        let relative = Path::new("sources").join(format!("{owner}_{repo}.git"));
+        self.base_path(&relative).unwrap_or_else(|| self.dir.join(relative))
+    }
+
+    /// Like [`Cache::get_or_clone_source`], but only uses a bare repository
+    /// from the read-only base if it contains every ref in `refs`.
+    ///
+    /// A base that lacks one of them (for example a tag created after it was
+    /// last populated) is skipped, and the source is cloned or fetched into
+    /// the user cache instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source cannot be cloned or fetched.
+    pub async fn get_or_clone_source_with_refs(
+        &self,
+        name: &str,
+        url: &str,
+        refs: &[&str],
+    ) -> Result<PathBuf> {
+        let base_repo = self.bare_repo_path(url);
+        if !self.is_in_read_only_base(&base_repo) {
+            return self.get_or_clone_source(name, url, None).await;
+        }
+
+        for reference in refs {
+            let reference = crate::git::raw_ref::raw_ref_spec(reference).unwrap_or(reference);
+            let found = GitCommand::new()
+                .args(["rev-parse", "--verify", "--quiet", &format!("{reference}^{{commit}}")])
+                .current_dir(&base_repo)
+                .execute_success()
+                .await
+                .is_ok();
+            if !found {
+                tracing::debug!(
+                    target: "agpm::cache",
+                    "'{}' is missing from {} in the read-only cache base, using the user cache",
+                    reference,
+                    name
+                );
+                return self.get_or_clone_source_impl(name, url, None, false).await;
+            }
+        }
+
+        self.get_or_clone_source(name, url, None).await
+    }
+
+    /// Existing path below the read-only base, if any.
+    pub(super) fn base_path(&self, relative: &Path) -> Option<PathBuf> {
+        let path = self.read_only_base.as_ref()?.join(relative);
+        path.exists().then_some(path)
+    }
+
+    /// Whether `path` lives in the read-only base and must not be modified.
+    pub(super) fn is_in_read_only_base(&self, path: &Path) -> bool {
+        self.read_only_base.as_ref().is_some_and(|base| path.starts_with(base))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestGit;
+    use tempfile::TempDir;
+
+    fn commit_file(git: &TestGit, name: &str, content: &str) -> String {
+        std::fs::write(git.repo_path().join(name), content).unwrap();
+        git.add_all().unwrap();
+        git.commit(&format!("Add {name}")).unwrap();
+        git.rev_parse_head().unwrap()
+    }
+
+    fn upstream(temp: &TempDir, name: &str) -> (TestGit, String) {
+        let path = temp.path().join("upstream").join(name);
+        std::fs::create_dir_all(&path).unwrap();
+        let git = TestGit::new(&path);
+        git.init().unwrap();
+        git.config_user().unwrap();
+        let url = format!("file://{}", path.display());
+        (git, url)
+    }
+
+    #[tokio::test]
+    async fn test_read_only_base_hits() {
+        let temp = TempDir::new().unwrap();
+        let (git, url) = upstream(&temp, "shared");
+        let sha = commit_file(&git, "agent.md", "# Agent");
+
+        // Populate the team cache as a regular cache
+        let base_dir = temp.path().join("base");
+        let team = Cache::with_dir(base_dir.clone()).unwrap();
+        team.get_or_clone_source("shared", &url, None).await.unwrap();
+        let base_worktree =
+            team.get_or_create_worktree_for_sha("shared", &url, &sha, None).await.unwrap();
+
+        let user_dir = temp.path().join("user");
+        let cache = Cache::with_dir(user_dir.clone()).unwrap().with_read_only_base(Some(base_dir));
+
+        let source = cache.get_or_clone_source("shared", &url, None).await.unwrap();
+        assert!(cache.is_in_read_only_base(&source), "{}", source.display());
+        assert_eq!(cache.bare_repo_path(&url), source);
+
+        let worktree =
+            cache.get_or_create_worktree_for_sha("shared", &url, &sha, None).await.unwrap();
+        assert_eq!(worktree, base_worktree);
+        assert_eq!(cache.get_worktree_path(&url, &sha).unwrap(), base_worktree);
+        assert!(worktree.join("agent.md").exists());
+
+        // Nothing was cloned or checked out in the user cache
+        assert!(!user_dir.join("sources").exists());
+        assert!(!user_dir.join("worktrees").exists());
+
+        // Cleanup never touches the base
+        cache.cleanup_worktree(&worktree).await.unwrap();
+        assert!(base_worktree.exists());
+    }
+
+    #[tokio::test]
+    async fn test_read_only_base_miss_writes_to_overlay() {
+        let temp = TempDir::new().unwrap();
+        let (git, url) = upstream(&temp, "shared");
+        commit_file(&git, "agent.md", "# Agent");
+        let (other_git, other_url) = upstream(&temp, "other");
+        commit_file(&other_git, "snippet.md", "# Snippet");
+
+        let base_dir = temp.path().join("base");
+        let team = Cache::with_dir(base_dir.clone()).unwrap();
+        team.get_or_clone_source("shared", &url, None).await.unwrap();
+
+        // A commit made after the base was populated
+        let new_sha = commit_file(&git, "agent.md", "# Agent v2");
+
+        let user_dir = temp.path().join("user");
+        let cache =
+            Cache::with_dir(user_dir.clone()).unwrap().with_read_only_base(Some(base_dir.clone()));
+
+        // A source missing from the base is cloned into the user cache
+        let source = cache.get_or_clone_source("other", &other_url, None).await.unwrap();
+        assert!(source.starts_with(&user_dir), "{}", source.display());
+        assert!(!base_dir.join("sources/local_other.git").exists());
+
+        // A worktree missing from the base is created in the user cache
+        let worktree =
+            cache.get_or_create_worktree_for_sha("shared", &url, &new_sha, None).await.unwrap();
+        assert!(worktree.starts_with(&user_dir), "{}", worktree.display());
+        assert_eq!(std::fs::read_to_string(worktree.join("agent.md")).unwrap(), "# Agent v2");
+        assert!(!base_dir.join("worktrees").exists());
+    }
+
+    #[tokio::test]
+    async fn test_read_only_base_missing_ref_uses_overlay() {
+        let temp = TempDir::new().unwrap();
+        let (git, url) = upstream(&temp, "shared");
+        commit_file(&git, "agent.md", "# Agent");
+        git.tag("v1.0.0").unwrap();
+
+        let base_dir = temp.path().join("base");
+        let team = Cache::with_dir(base_dir.clone()).unwrap();
+        team.get_or_clone_source("shared", &url, None).await.unwrap();
+
+        // A release tagged after the base was populated
+        let new_sha = commit_file(&git, "agent.md", "# Agent v2");
+        git.tag("v2.0.0").unwrap();
+
+        let user_dir = temp.path().join("user");
+        let cache =
+            Cache::with_dir(user_dir.clone()).unwrap().with_read_only_base(Some(base_dir.clone()));
+
+        // Refs the base has are served from it
+        let source =
+            cache.get_or_clone_source_with_refs("shared", &url, &["v1.0.0"]).await.unwrap();
+        assert!(cache.is_in_read_only_base(&source), "{}", source.display());
+
+        // A missing tag falls back to the user cache, where it resolves
+        let source = cache
+            .get_or_clone_source_with_refs("shared", &url, &["v1.0.0", "v2.0.0"])
+            .await
+            .unwrap();
+        assert!(source.starts_with(&user_dir), "{}", source.display());
+        let sha = crate::git::GitRepo::new(&source).resolve_to_sha(Some("v2.0.0")).await.unwrap();
+        assert_eq!(sha, new_sha);
+        assert!(!base_dir.join("sources/local_shared.git/refs/tags/v2.0.0").exists());
+    }
+}
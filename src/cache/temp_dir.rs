@@ -0,0 +1,75 @@
+//! Configurable directory for temporary files.
+//!
+//! The throwaway cache of `--no-cache` holds full clones and worktrees, which
+//! can exceed a small `TMPDIR` such as a tmpfs. `cache.temp_dir` in the global
+//! config, or the `AGPM_TMPDIR` environment variable (which wins), moves these
+//! temporary files to another directory. Like the other cache settings it is
+//! process-wide and installed once by the CLI, which checks at startup that
+//! the directory is writable.
+//!
+//! Staging directories for extracted archives are always created next to
+//! their destination in the cache, so they can be renamed into place.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use crate::core::file_error::{FileOperation, FileResultExt};
+
+/// Environment variable overriding `cache.temp_dir`.
+pub const TEMP_DIR_ENV: &str = "AGPM_TMPDIR";
+
+static TEMP_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Install the process-wide temporary directory, or restore the system default.
+pub fn set_temp_dir(dir: Option<PathBuf>) {
+    *TEMP_DIR.write().unwrap_or_else(std::sync::PoisonError::into_inner) = dir;
+}
+
+/// Directory for temporary files: the configured one, or the system default.
+#[must_use]
+pub fn temp_dir() -> PathBuf {
+    TEMP_DIR
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone()
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+/// Create `dir` if needed and check that files can be written to it.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be created or is not writable.
+pub fn validate_temp_dir(dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir).with_file_context(
+        FileOperation::CreateDir,
+        dir,
+        "creating the temporary directory",
+        "cache::temp_dir::validate_temp_dir",
+    )?;
+    tempfile::tempfile_in(dir).with_file_context(
+        FileOperation::Write,
+        dir,
+        "checking that the temporary directory is writable",
+        "cache::temp_dir::validate_temp_dir",
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_temp_dir() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let nested = temp.path().join("staging/agpm");
+        validate_temp_dir(&nested).unwrap();
+        assert!(nested.is_dir());
+
+        let file = temp.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        assert!(validate_temp_dir(&file).is_err());
+    }
+}
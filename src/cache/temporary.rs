@@ -0,0 +1,55 @@
+//! Throwaway cache directory for `--no-cache`.
+//!
+//! While a [`TemporaryCache`] is alive, [`crate::config::get_cache_dir`]
+//! returns a fresh directory under the temp dir (see [`super::temp_dir`]) instead of the
+//! configured cache (including `AGPM_CACHE_DIR`), so every source is cloned
+//! from scratch and the real cache is never read or written. The directory is
+//! deleted when the guard is dropped at the end of the command. Like the other
+//! cache settings it is process-wide and installed once by the CLI.
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::sync::RwLock;
+use tempfile::TempDir;
+
+static TEMPORARY_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// The active temporary cache directory, if `--no-cache` is in effect.
+#[must_use]
+pub fn temporary_dir() -> Option<PathBuf> {
+    TEMPORARY_DIR.read().unwrap_or_else(std::sync::PoisonError::into_inner).clone()
+}
+
+/// Guard owning the temporary cache directory.
+///
+/// Dropping it removes the directory and restores the configured cache.
+#[derive(Debug)]
+pub struct TemporaryCache {
+    _dir: TempDir,
+}
+
+impl TemporaryCache {
+    /// Create a fresh temporary cache and make it the process-wide cache directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the temporary directory cannot be created.
+    pub fn activate() -> Result<Self> {
+        let dir = tempfile::Builder::new()
+            .prefix("agpm-cache-")
+            .tempdir_in(super::temp_dir::temp_dir())
+            .context("Failed to create temporary cache directory for --no-cache")?;
+        tracing::debug!("Using temporary cache at {}", dir.path().display());
+        *TEMPORARY_DIR.write().unwrap_or_else(std::sync::PoisonError::into_inner) =
+            Some(dir.path().to_path_buf());
+        Ok(Self {
+            _dir: dir,
+        })
+    }
+}
+
+impl Drop for TemporaryCache {
+    fn drop(&mut self) {
+        *TEMPORARY_DIR.write().unwrap_or_else(std::sync::PoisonError::into_inner) = None;
+    }
+}
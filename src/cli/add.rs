@@ -200,6 +200,11 @@ async fn add_dependency_with_manifest_path(
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             }));
         }
     }
@@ -503,6 +508,11 @@ fn parse_dependency_spec(
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
         ))
     } else if is_local_path {
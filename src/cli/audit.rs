@@ -0,0 +1,415 @@
+//! Report security-relevant issues in a project.
+//!
+//! This module provides the `audit` command, a one-shot security review of the
+//! manifest, lockfile and installed tool configuration. It reuses the checks
+//! behind `require_tag` and adds heuristics for common supply-chain risks:
+//!
+//! | Code | Severity | Finding |
+//! | --- | --- | --- |
+//! | `insecure-source` | high | A source is fetched over plain HTTP |
+//! | `unlisted-source` | high | A source is not in `audit.allowed_sources` |
+//! | `branch-pin` | medium | A dependency tracks a branch and can change at any time |
+//! | `ref-pin` | medium | A dependency tracks a raw ref (`ref:`) that can be force-pushed |
+//! | `commit-pin` | low | A dependency pins a commit instead of a reviewed tag |
+//! | `hook-command` | medium | A hook runs shell commands on tool events |
+//! | `mcp-env-secret` | medium | An MCP server is passed secrets through its environment |
+//!
+//! The allow-list is read from the global configuration:
+//!
+//! ```toml
+//! [audit]
+//! allowed_sources = ["https://github.com/my-org/"]
+//! ```
+//!
+//! Nothing is fetched: dependencies are classified from the manifest alone, so
+//! a plain name in `version` counts as a branch unless it looks like a version.
+//! MCP servers are inspected in the installed configuration files.
+//!
+//! The command exits with an error when any high-severity finding is reported.
+//!
+//! # Examples
+//!
+//! ```bash
+//! agpm audit
+//! agpm audit --format json
+//! ```
+
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::{AuditConfig, GlobalConfig};
+use crate::core::ResourceType;
+use crate::lockfile::LockFile;
+use crate::manifest::{Manifest, find_manifest_with_optional};
+use crate::mcp::McpServerConfig;
+use crate::resolver::tag_policy::tag_policy_violation;
+use crate::version::{VersionConstraint, parse_version_constraint};
+
+/// Substrings of environment variable names that usually hold secrets.
+const SECRET_MARKERS: &[&str] = &["TOKEN", "SECRET", "KEY", "PASSWORD", "PASSWD", "CREDENTIAL"];
+
+/// Output format for `agpm audit`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum AuditFormat {
+    /// Human-readable report.
+    #[default]
+    Text,
+    /// JSON object for scripts and CI.
+    Json,
+}
+
+/// Command to report security-relevant issues in the project.
+#[derive(Args, Debug)]
+pub struct AuditCommand {
+    /// Output format (text, json)
+    #[arg(short = 'f', long, value_enum, default_value_t = AuditFormat::Text)]
+    format: AuditFormat,
+
+    /// Global config file to read the allow-list from.
+    ///
+    /// This field is populated from the global --config flag via
+    /// execute_with_config; `None` uses the default location.
+    #[arg(skip)]
+    pub config_path: Option<PathBuf>,
+}
+
+/// How serious a finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+    High,
+    Medium,
+    Low,
+}
+
+/// A single audit finding.
+#[derive(Debug, Serialize)]
+struct Finding {
+    severity: Severity,
+    code: &'static str,
+    /// What the finding is about, e.g. `source 'community'`.
+    subject: String,
+    message: String,
+}
+
+/// The full audit report.
+#[derive(Debug, Serialize)]
+struct AuditReport {
+    high: usize,
+    medium: usize,
+    low: usize,
+    findings: Vec<Finding>,
+}
+
+impl AuditCommand {
+    /// Execute the audit command with an optional manifest path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest or global config cannot be loaded,
+    /// or if any high-severity finding is reported.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let project_dir = manifest_path.parent().unwrap_or_else(|| Path::new("."));
+
+        let (manifest, _) = Manifest::load_with_private(&manifest_path)?;
+        let lockfile_path = project_dir.join("agpm.lock");
+        let lockfile = if lockfile_path.exists() {
+            Some(LockFile::load(&lockfile_path)?)
+        } else {
+            None
+        };
+        let global = GlobalConfig::load_with_optional(self.config_path).await?;
+
+        let report = audit(&manifest, lockfile.as_ref(), &global, project_dir);
+        match self.format {
+            AuditFormat::Text => print_report(&report),
+            AuditFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        }
+
+        if report.high > 0 {
+            anyhow::bail!("{} high-severity finding(s)", report.high);
+        }
+        Ok(())
+    }
+}
+
+/// Run every check and collect the findings, most severe first.
+fn audit(
+    manifest: &Manifest,
+    lockfile: Option<&LockFile>,
+    global: &GlobalConfig,
+    project_dir: &Path,
+) -> AuditReport {
+    let mut findings = Vec::new();
+    let sources = effective_sources(manifest, global);
+    audit_sources(&sources, &global.audit, &mut findings);
+    audit_pins(manifest, &sources, &mut findings);
+    audit_hooks(manifest, lockfile, &mut findings);
+    if let Some(lockfile) = lockfile {
+        audit_mcp_env(manifest, lockfile, project_dir, &mut findings);
+    }
+
+    findings
+        .sort_by(|a, b| (a.severity, a.code, &a.subject).cmp(&(b.severity, b.code, &b.subject)));
+    let count = |severity| findings.iter().filter(|f| f.severity == severity).count();
+    AuditReport {
+        high: count(Severity::High),
+        medium: count(Severity::Medium),
+        low: count(Severity::Low),
+        findings,
+    }
+}
+
+/// Sources defined in the manifest, plus global sources that dependencies use.
+fn effective_sources<'a>(
+    manifest: &'a Manifest,
+    global: &'a GlobalConfig,
+) -> BTreeMap<&'a str, &'a str> {
+    let mut sources: BTreeMap<&str, &str> =
+        manifest.sources.iter().map(|(name, url)| (name.as_str(), url.as_str())).collect();
+    for (_, dep, _) in manifest.all_dependencies_with_types() {
+        if let Some(source) = dep.get_source()
+            && let Some((name, url)) = global.sources.get_key_value(source)
+        {
+            sources.entry(name.as_str()).or_insert(url.as_str());
+        }
+    }
+    sources
+}
+
+/// Whether `url` is a Git repository, as opposed to a local path or archive.
+fn is_git_url(url: &str) -> bool {
+    !crate::utils::is_local_path(url) && !crate::source::archive::is_archive_url(url)
+}
+
+fn audit_sources(
+    sources: &BTreeMap<&str, &str>,
+    allow_list: &AuditConfig,
+    findings: &mut Vec<Finding>,
+) {
+    for (&name, &url) in sources {
+        if crate::utils::is_local_path(url) {
+            continue;
+        }
+        if url.to_ascii_lowercase().starts_with("http://") {
+            findings.push(Finding {
+                severity: Severity::High,
+                code: "insecure-source",
+                subject: format!("source '{name}'"),
+                message: "is fetched over plain HTTP; use HTTPS or SSH".to_string(),
+            });
+        }
+        if !allow_list.allows_source(url) {
+            findings.push(Finding {
+                severity: Severity::High,
+                code: "unlisted-source",
+                subject: format!("source '{name}'"),
+                message: "is not in audit.allowed_sources".to_string(),
+            });
+        }
+    }
+}
+
+fn audit_pins(manifest: &Manifest, sources: &BTreeMap<&str, &str>, findings: &mut Vec<Finding>) {
+    // Without fetching, only names that look like versions are taken as tags
+    let is_tag =
+        |version: &str| matches!(parse_version_constraint(version), VersionConstraint::Tag(_));
+
+    for (name, dep, resource_type) in manifest.all_dependencies_with_types() {
+        let Some(url) = dep.get_source().and_then(|source| sources.get(source)) else {
+            continue;
+        };
+        if !is_git_url(url) {
+            continue;
+        }
+        if let Some(reason) = tag_policy_violation(&dep, is_tag) {
+            let (severity, code) = if reason.starts_with("pins commit") {
+                (Severity::Low, "commit-pin")
+            } else if reason.starts_with("tracks ref") {
+                (Severity::Medium, "ref-pin")
+            } else {
+                (Severity::Medium, "branch-pin")
+            };
+            findings.push(Finding {
+                severity,
+                code,
+                subject: format!("{resource_type} '{name}'"),
+                message: reason,
+            });
+        }
+    }
+}
+
+fn audit_hooks(manifest: &Manifest, lockfile: Option<&LockFile>, findings: &mut Vec<Finding>) {
+    // The lockfile also knows transitive hooks; fall back to the manifest
+    let hooks: Vec<String> = match lockfile {
+        Some(lockfile) => lockfile
+            .hooks
+            .iter()
+            .filter(|h| h.install != Some(false))
+            .map(|h| h.display_name().to_string())
+            .collect(),
+        None => manifest.hooks.keys().cloned().collect(),
+    };
+    for name in hooks {
+        findings.push(Finding {
+            severity: Severity::Medium,
+            code: "hook-command",
+            subject: format!("{} '{name}'", ResourceType::Hook),
+            message: "runs shell commands on tool events; review its source".to_string(),
+        });
+    }
+}
+
+fn audit_mcp_env(
+    manifest: &Manifest,
+    lockfile: &LockFile,
+    project_dir: &Path,
+    findings: &mut Vec<Finding>,
+) {
+    // Group installed servers by the config file they were merged into
+    let mut by_target: BTreeMap<PathBuf, Vec<&str>> = BTreeMap::new();
+    for entry in lockfile.mcp_servers.iter().filter(|s| s.install != Some(false)) {
+        let tool = entry
+            .tool
+            .clone()
+            .unwrap_or_else(|| manifest.get_default_tool(ResourceType::McpServer));
+        if let Some(target) = manifest.get_merge_target(&tool, ResourceType::McpServer) {
+            by_target.entry(project_dir.join(target)).or_default().push(entry.lookup_name());
+        }
+    }
+
+    for (target, names) in by_target {
+        let Ok(config) = crate::utils::read_json_file::<serde_json::Value>(&target) else {
+            continue;
+        };
+        let Some(servers) = config
+            .get("mcpServers")
+            .or_else(|| config.get("mcp"))
+            .and_then(serde_json::Value::as_object)
+        else {
+            continue;
+        };
+        for name in names {
+            let Some(server) = servers
+                .get(name)
+                .and_then(|value| serde_json::from_value::<McpServerConfig>(value.clone()).ok())
+            else {
+                continue;
+            };
+            let secrets = secret_env_names(&server);
+            if !secrets.is_empty() {
+                findings.push(Finding {
+                    severity: Severity::Medium,
+                    code: "mcp-env-secret",
+                    subject: format!("{} '{name}'", ResourceType::McpServer),
+                    message: format!("receives secrets through {}", secrets.join(", ")),
+                });
+            }
+        }
+    }
+}
+
+/// Environment variables of `server` whose names suggest they hold secrets.
+fn secret_env_names(server: &McpServerConfig) -> Vec<String> {
+    let mut names: Vec<String> = server
+        .env
+        .iter()
+        .flat_map(|env| env.keys())
+        .filter(|key| {
+            let upper = key.to_ascii_uppercase();
+            SECRET_MARKERS.iter().any(|marker| upper.contains(marker))
+        })
+        .cloned()
+        .collect();
+    names.sort();
+    names
+}
+
+fn print_report(report: &AuditReport) {
+    for finding in &report.findings {
+        let severity = match finding.severity {
+            Severity::High => "high".red().bold(),
+            Severity::Medium => "medium".yellow(),
+            Severity::Low => "low".normal(),
+        };
+        println!("{severity} [{}] {} {}", finding.code, finding.subject, finding.message);
+    }
+
+    if report.findings.is_empty() {
+        println!("{} No security findings", "✓".green());
+    } else {
+        println!(
+            "\n{} finding(s): {} high, {} medium, {} low",
+            report.findings.len(),
+            report.high,
+            report.medium,
+            report.low
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(content: &str) -> Manifest {
+        toml::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn test_audit_sources_and_pins() {
+        let manifest = manifest(
+            r#"
+[sources]
+plain = "http://git.example.com/plain.git"
+org = "https://github.com/my-org/agents.git"
+local = "../local-resources"
+
+[agents]
+tagged = { source = "org", path = "agents/a.md", version = "v1.0.0" }
+ranged = { source = "org", path = "agents/b.md", version = "^1.0.0" }
+branch = { source = "org", path = "agents/c.md", branch = "main" }
+commit = { source = "plain", path = "agents/d.md", rev = "abc1234" }
+proposal = { source = "org", path = "agents/f.md", version = "ref:refs/pull/42/head" }
+floating = { source = "local", path = "agents/e.md" }
+"#,
+        );
+        let global: GlobalConfig =
+            toml::from_str("[audit]\nallowed_sources = [\"https://github.com/my-org/\"]\n")
+                .unwrap();
+
+        let report = audit(&manifest, None, &global, Path::new("."));
+        let codes: Vec<_> = report.findings.iter().map(|f| (f.code, f.subject.as_str())).collect();
+        assert_eq!(
+            codes,
+            vec![
+                ("insecure-source", "source 'plain'"),
+                ("unlisted-source", "source 'plain'"),
+                ("branch-pin", "agent 'branch'"),
+                ("ref-pin", "agent 'proposal'"),
+                ("commit-pin", "agent 'commit'"),
+            ]
+        );
+        assert_eq!((report.high, report.medium, report.low), (2, 2, 1));
+
+        // Without an allow-list every source is allowed
+        let report = audit(&manifest, None, &GlobalConfig::default(), Path::new("."));
+        assert!(report.findings.iter().all(|f| f.code != "unlisted-source"));
+    }
+
+    #[test]
+    fn test_secret_env_names() {
+        let server: McpServerConfig = serde_json::from_str(
+            r#"{"command": "npx", "env": {"GITHUB_TOKEN": "${GITHUB_TOKEN}", "api_key": "x", "LOG_LEVEL": "debug"}}"#,
+        )
+        .unwrap();
+        assert_eq!(secret_env_names(&server), vec!["GITHUB_TOKEN", "api_key"]);
+    }
+}
@@ -0,0 +1,142 @@
+//! Source repository export for `agpm bundle export`.
+//!
+//! Packs every file of a source repository checkout (hidden files and
+//! directories excepted, as for linting) into a gzip-compressed tarball. The
+//! tarball can be published or staged as a CI artifact and consumed offline as
+//! an archive source (see [`crate::source::archive`]), which takes its version
+//! from the file name, e.g. `v1.2.0.tar.gz`.
+//!
+//! The artifact is written to the repository root unless another directory is
+//! given, which is created when missing. An existing artifact is only replaced
+//! with `force`.
+
+use anyhow::{Context, Result, bail};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+use crate::utils::normalize_path_for_storage;
+
+/// Result of exporting a repository.
+#[derive(Debug)]
+pub struct ExportSummary {
+    /// Path of the written artifact.
+    pub path: PathBuf,
+    /// Number of files packed.
+    pub files: usize,
+    /// SHA-256 of the artifact as hex, for `#sha256=` source URL fragments.
+    pub sha256: String,
+}
+
+/// Pack the repository at `root` into `output_dir/name`.
+///
+/// `output_dir` defaults to `root` and `name` to `<repository name>.tar.gz`.
+///
+/// # Errors
+///
+/// Returns an error if `root` is not a directory, `name` is not a plain file
+/// name, the artifact exists and `force` is not set, or any file cannot be
+/// read or written.
+pub fn export_repository(
+    root: &Path,
+    output_dir: Option<&Path>,
+    name: Option<&str>,
+    force: bool,
+) -> Result<ExportSummary> {
+    let root = crate::utils::safe_canonicalize(root)
+        .with_context(|| format!("Repository path not found: {}", root.display()))?;
+    if !root.is_dir() {
+        bail!("Repository path is not a directory: {}", root.display());
+    }
+
+    let name = match name {
+        Some(name) => {
+            if name.is_empty() || Path::new(name).file_name() != Some(name.as_ref()) {
+                bail!("Artifact name must be a file name without directories: '{name}'");
+            }
+            name.to_string()
+        }
+        None => {
+            let repo_name = root.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+            format!("{repo_name}.tar.gz")
+        }
+    };
+
+    let output_dir = output_dir.map_or_else(|| root.clone(), Path::to_path_buf);
+    std::fs::create_dir_all(&output_dir)
+        .with_context(|| format!("Failed to create output directory: {}", output_dir.display()))?;
+    let path = crate::utils::safe_canonicalize(&output_dir)?.join(&name);
+    if path.exists() && !force {
+        bail!("{} already exists\n\nHint: Use --force to overwrite it.", path.display());
+    }
+
+    let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    let mut files = 0;
+    let walker = walkdir::WalkDir::new(&root)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'));
+    for entry in walker {
+        let entry = entry?;
+        // Never pack a previous artifact written into the repository itself
+        if !entry.file_type().is_file() || entry.path() == path {
+            continue;
+        }
+        let relative = normalize_path_for_storage(entry.path().strip_prefix(&root)?);
+        builder
+            .append_path_with_name(entry.path(), &relative)
+            .with_context(|| format!("Failed to add {relative} to the artifact"))?;
+        files += 1;
+    }
+    let bytes = builder.into_inner()?.finish()?;
+
+    crate::utils::fs::atomic_write(&path, &bytes)
+        .with_context(|| format!("Failed to write artifact: {}", path.display()))?;
+
+    Ok(ExportSummary {
+        path,
+        files,
+        sha256: hex::encode(Sha256::digest(&bytes)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entries(artifact: &Path) -> Vec<String> {
+        let file = std::fs::File::open(artifact).unwrap();
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        archive
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_export_repository() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("resources");
+        std::fs::create_dir_all(root.join("agents")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join("agents/reviewer.md"), "# Reviewer\n").unwrap();
+        std::fs::write(root.join("README.md"), "# Resources\n").unwrap();
+        std::fs::write(root.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+
+        // Defaults to the repository root, and the artifact never packs itself
+        let summary = export_repository(&root, None, None, false).unwrap();
+        assert_eq!(summary.path.file_name().unwrap(), "resources.tar.gz");
+        assert_eq!(summary.files, 2);
+        let summary = export_repository(&root, None, None, true).unwrap();
+        assert_eq!(summary.files, 2);
+        assert_eq!(entries(&summary.path), ["README.md", "agents/reviewer.md"]);
+
+        let err = export_repository(&root, None, None, false).unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+        let err = export_repository(&root, None, Some("../v1.tar.gz"), false).unwrap_err();
+        assert!(err.to_string().contains("without directories"), "{err}");
+    }
+}
@@ -0,0 +1,313 @@
+//! Source repository linting for `agpm bundle lint`.
+//!
+//! Every Markdown and JSON file in the repository (hidden files and
+//! directories excepted) is checked for problems that would otherwise only
+//! surface when a consumer installs the resource:
+//!
+//! - Markdown frontmatter that is not valid YAML or does not match the
+//!   resource metadata schema
+//! - JSON that does not parse, hook files under `hooks/` that are not valid
+//!   hook configurations, and MCP server files under `mcp-servers/` without a
+//!   `command` or `url`
+//! - Declared dependencies with unknown resource types, or whose paths do not
+//!   resolve to a file inside the repository (patterns must match at least one
+//!   file)
+//! - Markdown links to `.md` files that do not exist
+//!
+//! Dependency paths are resolved like the transitive resolver does: paths
+//! starting with `./` or `../` and bare file names are relative to the
+//! declaring file, all other paths are relative to the repository root.
+//! Templated frontmatter and paths are skipped, as they can only be checked
+//! with a project's template variables.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::core::ResourceType;
+use crate::hooks::{HookConfig, validate_hook_config};
+use crate::manifest::DependencySpec;
+use crate::markdown::MarkdownMetadata;
+use crate::markdown::frontmatter::FrontmatterParser;
+use crate::markdown::reference_extractor::extract_file_references;
+use crate::mcp::McpServerConfig;
+use crate::resolver::path_resolver::is_file_relative_path;
+use crate::source::scan::{ResourceFile, resource_files};
+
+/// A problem found in a single file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintProblem {
+    /// Path of the file relative to the repository root.
+    pub file: String,
+    /// Description of the problem.
+    pub message: String,
+}
+
+/// Result of linting a repository.
+#[derive(Debug, Default, Serialize)]
+pub struct LintReport {
+    /// Number of Markdown and JSON files checked.
+    pub files_checked: usize,
+    /// Problems found, ordered by file.
+    pub problems: Vec<LintProblem>,
+}
+
+/// Lint every Markdown and JSON resource below `root`.
+pub fn lint_repository(root: &Path) -> Result<LintReport> {
+    let root = crate::utils::safe_canonicalize(root)
+        .with_context(|| format!("Repository path not found: {}", root.display()))?;
+    if !root.is_dir() {
+        return Err(anyhow::anyhow!("Repository path is not a directory: {}", root.display()));
+    }
+
+    let mut report = LintReport::default();
+    for file in resource_files(&root) {
+        let file = file?;
+        let linter = FileLinter {
+            root: &root,
+            file: &file,
+        };
+        report.files_checked += 1;
+
+        let content = match std::fs::read_to_string(&file.path) {
+            Ok(content) => content,
+            Err(e) => {
+                report.problems.push(linter.problem(format!("Cannot read file: {e}")));
+                continue;
+            }
+        };
+
+        if file.is_json {
+            linter.lint_json(&content, &mut report.problems);
+        } else {
+            linter.lint_markdown(&content, &mut report.problems);
+        }
+    }
+
+    Ok(report)
+}
+
+struct FileLinter<'a> {
+    root: &'a Path,
+    file: &'a ResourceFile,
+}
+
+impl FileLinter<'_> {
+    fn problem(&self, message: String) -> LintProblem {
+        LintProblem {
+            file: self.file.relative.clone(),
+            message,
+        }
+    }
+
+    fn lint_markdown(&self, content: &str, problems: &mut Vec<LintProblem>) {
+        let parser = FrontmatterParser::new();
+        if let Some(raw) = parser.extract_raw_frontmatter(content)
+            && !raw.trim().is_empty()
+            && !is_templated(&raw)
+        {
+            match serde_yaml::from_str::<MarkdownMetadata>(&raw) {
+                Ok(metadata) => {
+                    let agpm_dependencies =
+                        metadata.get_agpm_metadata().and_then(|agpm| agpm.dependencies);
+                    for deps in [metadata.dependencies, agpm_dependencies].into_iter().flatten() {
+                        self.check_dependencies(&deps, problems);
+                    }
+                }
+                Err(e) => problems.push(self.problem(format!("Invalid frontmatter: {e}"))),
+            }
+        }
+
+        let dir = self.file.path.parent().unwrap_or(self.root);
+        for reference in extract_file_references(content) {
+            if is_templated(&reference) || is_install_path(&reference) {
+                continue;
+            }
+            if !dir.join(&reference).exists() && !self.root.join(&reference).exists() {
+                problems.push(self.problem(format!("Broken link: {reference}")));
+            }
+        }
+    }
+
+    fn lint_json(&self, content: &str, problems: &mut Vec<LintProblem>) {
+        let value: serde_json::Value = match serde_json::from_str(content) {
+            Ok(value) => value,
+            Err(e) => {
+                problems.push(self.problem(format!("Invalid JSON: {e}")));
+                return;
+            }
+        };
+
+        if let Some(deps) = value.get("dependencies") {
+            match serde_json::from_value::<BTreeMap<String, Vec<DependencySpec>>>(deps.clone()) {
+                Ok(deps) => self.check_dependencies(&deps, problems),
+                Err(e) => problems.push(self.problem(format!("Invalid dependencies: {e}"))),
+            }
+        }
+
+        if self.file.in_directory("hooks") {
+            match serde_json::from_value::<HookConfig>(value) {
+                // Script paths are only meaningful once installed, so validate
+                // against a bare file name to skip that check
+                Ok(config) => {
+                    if let Err(e) = validate_hook_config(&config, Path::new("hook.json")) {
+                        problems.push(self.problem(format!("Invalid hook configuration: {e}")));
+                    }
+                }
+                Err(e) => {
+                    problems.push(self.problem(format!("Invalid hook configuration: {e}")));
+                }
+            }
+        } else if self.file.in_directory("mcp-servers") {
+            match serde_json::from_value::<McpServerConfig>(value) {
+                Ok(config) if config.command.is_none() && config.url.is_none() => {
+                    problems.push(
+                        self.problem("MCP server must define either `command` or `url`".into()),
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    problems.push(self.problem(format!("Invalid MCP server configuration: {e}")));
+                }
+            }
+        }
+    }
+
+    fn check_dependencies(
+        &self,
+        deps: &BTreeMap<String, Vec<DependencySpec>>,
+        problems: &mut Vec<LintProblem>,
+    ) {
+        for (type_key, specs) in deps {
+            if ResourceType::from_frontmatter_str(type_key).is_none() {
+                problems.push(
+                    self.problem(format!("Unknown resource type '{type_key}' in dependencies")),
+                );
+                continue;
+            }
+            for spec in specs {
+                if is_templated(&spec.path) {
+                    continue;
+                }
+                if let Some(message) = self.check_dependency_path(&spec.path) {
+                    problems.push(self.problem(message));
+                }
+            }
+        }
+    }
+
+    fn check_dependency_path(&self, dep_path: &str) -> Option<String> {
+        let base = if is_file_relative_path(dep_path) || !dep_path.contains('/') {
+            self.file.path.parent().unwrap_or(self.root)
+        } else {
+            self.root
+        };
+        let candidate = base.join(dep_path);
+
+        if dep_path.contains(['*', '?', '[']) {
+            let matches: Vec<PathBuf> = glob::glob(&candidate.to_string_lossy())
+                .map(|paths| paths.filter_map(std::result::Result::ok).collect())
+                .unwrap_or_default();
+            return if matches.iter().any(|p| self.is_inside(p)) {
+                None
+            } else {
+                Some(format!("Dependency pattern '{dep_path}' matches no files in the repository"))
+            };
+        }
+
+        if !candidate.exists() {
+            Some(format!("Dependency '{dep_path}' not found in the repository"))
+        } else if !self.is_inside(&candidate) {
+            Some(format!("Dependency '{dep_path}' resolves outside the repository"))
+        } else {
+            None
+        }
+    }
+
+    fn is_inside(&self, path: &Path) -> bool {
+        path.canonicalize().is_ok_and(|p| p.starts_with(self.root))
+    }
+}
+
+/// Whether `text` contains Tera syntax and can only be checked after rendering.
+fn is_templated(text: &str) -> bool {
+    text.contains("{{") || text.contains("{%")
+}
+
+/// References to install locations such as `.claude/agents/helper.md` point
+/// into a consumer's project, not into the repository.
+fn is_install_path(reference: &str) -> bool {
+    reference.starts_with('.') && !reference.starts_with("./") && !reference.starts_with("../")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_lint_clean_repository() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "agents/reviewer.md",
+            "---\ndescription: Reviewer\ndependencies:\n  snippets:\n    - path: snippets/style.md\n  agents:\n    - path: ./helper.md\n---\n\nSee [helper](./helper.md) and `.claude/agents/agpm/helper.md`.\n",
+        );
+        write(root, "agents/helper.md", "# Helper\n");
+        write(root, "snippets/style.md", "# Style\n");
+        write(
+            root,
+            "hooks/format.json",
+            r#"{"events": ["PostToolUse"], "matcher": "Edit", "type": "command", "command": "fmt"}"#,
+        );
+        write(root, "mcp-servers/fs.json", r#"{"command": "npx", "args": ["fs"]}"#);
+        write(root, ".github/broken.json", "{");
+
+        let report = lint_repository(root).unwrap();
+        assert_eq!(report.files_checked, 5);
+        assert!(report.problems.is_empty(), "{:?}", report.problems);
+    }
+
+    #[test]
+    fn test_lint_reports_problems() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "agents/broken.md",
+            "---\ndependencies:\n  agents:\n    - path: ./missing.md\n  widgets:\n    - path: x.md\n  snippets:\n    - path: snippets/*.md\n---\n\nSee [gone](../docs/gone.md).\n",
+        );
+        write(root, "agents/invalid.md", "---\ntags: [unclosed\n---\n");
+        write(root, "hooks/bad.json", r#"{"events": [], "type": "command", "command": "x"}"#);
+        write(root, "mcp-servers/empty.json", r#"{"args": []}"#);
+        write(root, "snippets/data.json", "{ not json");
+
+        let report = lint_repository(root).unwrap();
+        let problems: Vec<(&str, &str)> =
+            report.problems.iter().map(|p| (p.file.as_str(), p.message.as_str())).collect();
+
+        let has = |file: &str, needle: &str| {
+            problems.iter().any(|(f, m)| *f == file && m.contains(needle))
+        };
+        assert!(has("agents/broken.md", "Dependency './missing.md' not found"), "{problems:?}");
+        assert!(has("agents/broken.md", "Unknown resource type 'widgets'"), "{problems:?}");
+        assert!(
+            has("agents/broken.md", "pattern 'snippets/*.md' matches no files"),
+            "{problems:?}"
+        );
+        assert!(has("agents/broken.md", "Broken link: ../docs/gone.md"), "{problems:?}");
+        assert!(has("agents/invalid.md", "Invalid frontmatter"), "{problems:?}");
+        assert!(has("hooks/bad.json", "at least one event"), "{problems:?}");
+        assert!(has("mcp-servers/empty.json", "`command` or `url`"), "{problems:?}");
+        assert!(has("snippets/data.json", "Invalid JSON"), "{problems:?}");
+        assert_eq!(problems.len(), 8, "{problems:?}");
+    }
+}
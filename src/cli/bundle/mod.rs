@@ -0,0 +1,167 @@
+//! Tools for publishers of AGPM source repositories.
+//!
+//! The `bundle` command works on a local checkout of a source repository
+//! rather than on a project, so it needs no manifest.
+//!
+//! # Examples
+//!
+//! Check a repository before tagging a release:
+//! ```bash
+//! agpm bundle lint
+//! agpm bundle lint ../my-resources --format json
+//! ```
+//!
+//! Stage the repository as a tarball for an archive source:
+//! ```bash
+//! agpm bundle export --output-dir dist --name v1.0.0.tar.gz
+//! ```
+//!
+//! See [`lint`] for the checks performed and [`export`] for the artifact layout.
+
+mod export;
+mod lint;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use std::path::PathBuf;
+
+use crate::cli::validate::OutputFormat;
+
+/// Command to work with a local source repository.
+#[derive(Args)]
+pub struct BundleCommand {
+    /// Bundle operation to perform
+    #[command(subcommand)]
+    command: BundleSubcommands,
+}
+
+/// Subcommands for source repository operations.
+#[derive(Subcommand)]
+enum BundleSubcommands {
+    /// Validate every resource in a source repository.
+    ///
+    /// Scans all Markdown and JSON files, validating frontmatter, declared
+    /// transitive dependencies, internal links and MCP server and hook
+    /// configurations. Exits with a non-zero status when problems are found.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm bundle lint                 # Lint the current directory
+    /// agpm bundle lint ./resources     # Lint another checkout
+    /// agpm bundle lint --format json   # Machine-readable report
+    /// ```
+    Lint {
+        /// Path to the repository root
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Pack a source repository into a `.tar.gz` artifact.
+    ///
+    /// Hidden files and directories such as `.git` are left out. The artifact
+    /// can be consumed offline as an archive source, which reads its version
+    /// from the file name.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm bundle export                                       # ./<repo>.tar.gz
+    /// agpm bundle export --output-dir dist --name v1.0.0.tar.gz
+    /// agpm bundle export ../my-resources --output-dir out --force
+    /// ```
+    Export {
+        /// Path to the repository root
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Directory to write the artifact to, created if missing (default: the repository root)
+        #[arg(long)]
+        output_dir: Option<PathBuf>,
+
+        /// File name of the artifact (default: `<repository name>.tar.gz`)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Overwrite an existing artifact
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+impl BundleCommand {
+    /// Execute the bundle command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the repository cannot be read, for `lint` if any
+    /// problems were found, and for `export` if the artifact cannot be written.
+    pub async fn execute(self) -> Result<()> {
+        match self.command {
+            BundleSubcommands::Lint {
+                path,
+                format,
+            } => execute_lint(&path, &format),
+            BundleSubcommands::Export {
+                path,
+                output_dir,
+                name,
+                force,
+            } => {
+                let summary = export::export_repository(
+                    &path,
+                    output_dir.as_deref(),
+                    name.as_deref(),
+                    force,
+                )?;
+                println!(
+                    "{} Exported {} file(s) to {}",
+                    "✓".green(),
+                    summary.files,
+                    summary.path.display()
+                );
+                println!("  sha256: {}", summary.sha256);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn execute_lint(path: &std::path::Path, format: &OutputFormat) -> Result<()> {
+    let report = lint::lint_repository(path)?;
+    let problem_count = report.problems.len();
+
+    match format {
+        OutputFormat::Json => {
+            let output = serde_json::json!({
+                "files_checked": report.files_checked,
+                "problems": report.problems,
+                "valid": problem_count == 0,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        OutputFormat::Text => {
+            for problem in &report.problems {
+                println!("{} {}: {}", "✗".red(), problem.file, problem.message);
+            }
+            if problem_count == 0 {
+                println!(
+                    "{} Checked {} file(s), no problems found",
+                    "✓".green(),
+                    report.files_checked
+                );
+            } else {
+                println!();
+                println!("Checked {} file(s): {} problem(s)", report.files_checked, problem_count);
+            }
+        }
+    }
+
+    if problem_count > 0 {
+        return Err(anyhow::anyhow!("Bundle lint found {problem_count} problem(s)"));
+    }
+    Ok(())
+}
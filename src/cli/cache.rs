@@ -11,6 +11,11 @@
 //! - **Complete Cleanup**: Clear entire cache directory
 //! - **Size Reporting**: Human-readable cache size formatting
 //! - **Usage Analysis**: Identify active vs. unused cache entries
+//! - **Deduplication**: Hard-link worktrees with identical content
+//! - **Age-Based Cleanup**: Remove worktrees unused for a configured number of days
+//! - **Import**: Seed the cache from repositories already cloned elsewhere
+//! - **Locate**: Print or open the cache directory or a source's bare repository
+//! - **Repair**: Re-clone one source's corrupt bare repository
 //!
 //! # Cache Structure
 //!
@@ -37,6 +42,41 @@
 //! agpm cache clean --all
 //! ```
 //!
+//! Consolidate identical worktrees:
+//! ```bash
+//! agpm cache dedupe --dry-run
+//! agpm cache dedupe
+//! ```
+//!
+//! Remove worktrees unused for more than 30 days:
+//! ```bash
+//! agpm cache gc --max-age-days 30
+//! ```
+//!
+//! Print or open the cache directory:
+//! ```bash
+//! agpm cache path
+//! agpm cache path --source community
+//! agpm cache open
+//! ```
+//!
+//! Import existing clones so later installs work offline:
+//! ```bash
+//! agpm cache import ~/src/agent-repos
+//! agpm cache import ~/src/community-agents --source community
+//! ```
+//!
+//! Re-clone a corrupt repository without clearing the rest of the cache:
+//! ```bash
+//! agpm cache repair --source community
+//! ```
+//!
+//! Machine-readable output for monitoring scripts:
+//! ```bash
+//! agpm cache stats --format json
+//! agpm cache gc --max-age-days 30 --format json
+//! ```
+//!
 //! # Cache Management Strategy
 //!
 //! ## Automatic Cache Population
@@ -66,13 +106,17 @@
 //! - File system errors during cleanup operations
 //! - Manifest file parsing errors (for usage analysis)
 
-use anyhow::Result;
-use clap::{Args, Subcommand};
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand, ValueEnum};
 use colored::Colorize;
+use serde::Serialize;
 
-use crate::cache::Cache;
+use crate::cache::import::same_repository;
+use crate::cache::{Cache, ImportOutcome, WorktreeGcReport};
+use crate::git::GitRepo;
 use crate::manifest::{Manifest, find_manifest_with_optional};
-use std::path::PathBuf;
+use crate::utils::terminal::{SEARCH, SPARKLES, SUCCESS, TRASH, WARNING};
+use std::path::{Path, PathBuf};
 
 /// Command to manage the global Git repository cache.
 ///
@@ -107,6 +151,109 @@ pub struct CacheCommand {
     /// Cache management operation to perform
     #[command(subcommand)]
     command: Option<CacheSubcommands>,
+
+    /// Output format (text, json)
+    #[arg(long, global = true, value_enum, default_value_t = CacheFormat::Text)]
+    format: CacheFormat,
+}
+
+/// Output format for `agpm cache` subcommands.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum CacheFormat {
+    /// Human-readable messages with colors and emoji.
+    #[default]
+    Text,
+    /// A single JSON object describing the result.
+    Json,
+}
+
+/// Routes cache command output to the selected [`CacheFormat`].
+///
+/// In text mode, messages are printed as the operation progresses. In JSON
+/// mode they are suppressed and only the final report is printed, so stdout
+/// always holds exactly one JSON document.
+#[derive(Clone, Copy)]
+struct Reporter {
+    format: CacheFormat,
+}
+
+impl Reporter {
+    fn is_text(self) -> bool {
+        self.format == CacheFormat::Text
+    }
+
+    /// Print a progress or result message in text mode.
+    fn line(self, message: impl std::fmt::Display) {
+        if self.is_text() {
+            println!("{message}");
+        }
+    }
+
+    /// Print a warning; it goes to stderr in JSON mode to keep stdout parseable.
+    fn warn(self, message: impl std::fmt::Display) {
+        if self.is_text() {
+            println!("{WARNING}{message}");
+        } else {
+            eprintln!("Warning: {message}");
+        }
+    }
+
+    /// Print the final report in JSON mode.
+    fn finish<T: Serialize>(self, report: &T) -> Result<()> {
+        if !self.is_text() {
+            println!("{}", serde_json::to_string_pretty(report)?);
+        }
+        Ok(())
+    }
+}
+
+/// JSON report for `agpm cache info`.
+#[derive(Serialize)]
+struct InfoReport {
+    location: PathBuf,
+    size_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    read_only_base: Option<PathBuf>,
+    repositories: Vec<String>,
+}
+
+/// JSON report for `agpm cache clean`.
+#[derive(Serialize)]
+struct PathReport {
+    path: PathBuf,
+}
+
+#[derive(Serialize)]
+struct CleanReport {
+    all: bool,
+    removed: Vec<String>,
+    stale_locks_removed: usize,
+    bytes_reclaimed: u64,
+}
+
+/// JSON report for `agpm cache import`.
+#[derive(Serialize, Default)]
+struct ImportReport {
+    imported: Vec<ImportedRepository>,
+    already_cached: Vec<ImportedRepository>,
+    skipped: Vec<PathBuf>,
+}
+
+/// A repository handled by `agpm cache import`.
+#[derive(Serialize)]
+struct ImportedRepository {
+    source: String,
+    url: String,
+    from: PathBuf,
+    path: PathBuf,
+}
+
+/// JSON report for `agpm cache gc` and `agpm cache dedupe`.
+#[derive(Serialize)]
+struct DryRunReport<'a, T> {
+    dry_run: bool,
+    #[serde(flatten)]
+    report: &'a T,
 }
 
 /// Subcommands for cache management operations.
@@ -163,13 +310,144 @@ enum CacheSubcommands {
     /// - **Tips**: Helpful commands for cache management
     ///
     /// This is the default command when no subcommand is specified.
+    /// `stats` is an alias.
     ///
     /// # Examples
     /// ```bash
-    /// agpm cache info    # Explicit info command
-    /// agpm cache         # Defaults to info
+    /// agpm cache info                 # Explicit info command
+    /// agpm cache                      # Defaults to info
+    /// agpm cache stats --format json  # Sizes and repositories as JSON
     /// ```
+    #[command(visible_alias = "stats")]
     Info,
+
+    /// Consolidate worktrees whose resource files are byte-identical.
+    ///
+    /// Different commits frequently check out identical content (e.g. tags that
+    /// only differ in unrelated files). This command fingerprints every cached
+    /// worktree and replaces the files of redundant copies with hard links to a
+    /// single canonical worktree, recording the linkage in the worktree registry.
+    ///
+    /// Worktrees belonging to repositories that are currently locked by another
+    /// AGPM process are skipped rather than waited on.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm cache dedupe            # Consolidate identical worktrees
+    /// agpm cache dedupe --dry-run  # Report what would be reclaimed
+    /// ```
+    Dedupe {
+        /// Report reclaimable space without modifying the cache
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Remove worktrees that have not been used recently.
+    ///
+    /// Removes worktrees whose last use, as recorded in the worktree registry,
+    /// is older than the age limit. The limit defaults to
+    /// `cache.worktree_max_age_days` from `~/.agpm/config.toml`, which also
+    /// makes `agpm install` run this cleanup automatically.
+    ///
+    /// Worktrees belonging to repositories that are currently locked by another
+    /// AGPM process are skipped rather than waited on.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm cache gc                       # Use cache.worktree_max_age_days
+    /// agpm cache gc --max-age-days 14     # Override the configured limit
+    /// agpm cache gc --dry-run             # Report what would be removed
+    /// ```
+    Gc {
+        /// Remove worktrees unused for more than this many days
+        #[arg(long, value_name = "DAYS")]
+        max_age_days: Option<u64>,
+
+        /// Report what would be removed without modifying the cache
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Print the cache directory, or the bare repository of one source.
+    ///
+    /// Only the path is printed, so the output can be used directly in
+    /// scripts. The directory honors `AGPM_CACHE_DIR` and `--no-cache`. With
+    /// `--source`, the path is printed even if the source was not cloned yet;
+    /// a repository in `cache.read_only_base` is preferred when it exists.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm cache path
+    /// cd "$(agpm cache path --source community)"
+    /// ```
+    Path {
+        /// Print the bare repository of this manifest source instead
+        #[arg(long, value_name = "NAME")]
+        source: Option<String>,
+    },
+
+    /// Open the cache directory in the platform file explorer.
+    ///
+    /// Uses `open` on macOS, `explorer` on Windows and `xdg-open` elsewhere.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm cache open
+    /// agpm cache open --source community
+    /// ```
+    Open {
+        /// Open the bare repository of this manifest source instead
+        #[arg(long, value_name = "NAME")]
+        source: Option<String>,
+    },
+
+    /// Seed the cache from repositories that are already cloned.
+    ///
+    /// Each repository is copied into the cache as a bare mirror at the path
+    /// AGPM would clone its source to, so later installs reuse it instead of
+    /// cloning and keep working without network access. Working copies and
+    /// bare repositories are both accepted.
+    ///
+    /// Without `--source`, `DIR` is a directory of repositories (or a single
+    /// repository), and each one is matched to a manifest source by its
+    /// `origin` remote. Repositories matching no source are imported under
+    /// their remote URL with a warning; those without a remote are skipped.
+    ///
+    /// With `--source`, `DIR` is the repository for that manifest source. A
+    /// warning is printed if its `origin` remote is a different URL.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm cache import ~/src/agent-repos
+    /// agpm cache import ~/src/community-agents --source community
+    /// ```
+    Import {
+        /// Directory of Git repositories, or the repository itself
+        #[arg(value_name = "DIR")]
+        dir: PathBuf,
+
+        /// Import DIR as the repository of this manifest source
+        #[arg(long, value_name = "NAME")]
+        source: Option<String>,
+    },
+
+    /// Re-clone the bare repository of one source.
+    ///
+    /// Use this when a single cached repository is corrupt, for example after
+    /// an interrupted clone. The source's bare repository and its worktrees
+    /// are removed and the repository is cloned again and checked with
+    /// `git fsck`; other sources are left intact. Waits for other AGPM
+    /// processes using the source to finish.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm cache repair --source community
+    /// ```
+    Repair {
+        /// Manifest source whose repository to re-clone
+        #[arg(long, value_name = "NAME")]
+        source: String,
+    },
 }
 
 impl CacheCommand {
@@ -280,20 +558,263 @@ impl CacheCommand {
         cache: Cache,
         manifest_path: Option<PathBuf>,
     ) -> Result<()> {
+        let reporter = Reporter {
+            format: self.format,
+        };
         match self.command {
             Some(CacheSubcommands::Clean {
                 all,
             }) => {
                 if all {
-                    self.clean_all(cache).await
+                    self.clean_all(cache, reporter).await
                 } else {
-                    self.clean_unused(cache, manifest_path).await
+                    self.clean_unused(cache, manifest_path, reporter).await
                 }
             }
-            Some(CacheSubcommands::Info) | None => self.show_info(cache).await,
+            Some(CacheSubcommands::Info) | None => self.show_info(cache, reporter).await,
+            Some(CacheSubcommands::Dedupe {
+                dry_run,
+            }) => self.dedupe(cache, dry_run, reporter).await,
+            Some(CacheSubcommands::Gc {
+                max_age_days,
+                dry_run,
+            }) => self.gc(cache, max_age_days, dry_run, reporter).await,
+            Some(CacheSubcommands::Import {
+                ref dir,
+                ref source,
+            }) => self.import(&cache, manifest_path, dir, source.as_deref(), reporter).await,
+            Some(CacheSubcommands::Path {
+                ref source,
+            }) => {
+                let path = cache_path(&cache, manifest_path, source.as_deref())?;
+                if reporter.is_text() {
+                    println!("{}", path.display());
+                }
+                reporter.finish(&PathReport {
+                    path,
+                })
+            }
+            Some(CacheSubcommands::Repair {
+                ref source,
+            }) => self.repair(&cache, manifest_path, source, reporter).await,
+            Some(CacheSubcommands::Open {
+                ref source,
+            }) => open_in_file_explorer(&cache_path(&cache, manifest_path, source.as_deref())?),
         }
     }
 
+    /// Import already-cloned repositories into the cache as bare mirrors.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The cache instance to import into
+    /// * `manifest_path` - Optional path to the manifest file
+    /// * `dir` - Directory of repositories, or a single repository
+    /// * `source` - Manifest source that `dir` is the repository of
+    /// * `reporter` - Where to send the output
+    async fn import(
+        &self,
+        cache: &Cache,
+        manifest_path: Option<PathBuf>,
+        dir: &Path,
+        source: Option<&str>,
+        reporter: Reporter,
+    ) -> Result<()> {
+        let manifest = find_manifest_with_optional(manifest_path)
+            .ok()
+            .map(|path| Manifest::load(&path))
+            .transpose()?;
+        let mut report = ImportReport::default();
+
+        if let Some(name) = source {
+            let url = manifest
+                .as_ref()
+                .and_then(|m| m.sources.get(name))
+                .ok_or_else(|| anyhow::anyhow!("Source '{name}' is not defined in agpm.toml"))?;
+            match GitRepo::new(dir).get_remote_url().await {
+                Ok(remote) if !same_repository(&remote, url) => reporter.warn(format!(
+                    "{} has origin {remote}, but source '{name}' is {url}",
+                    dir.display()
+                )),
+                Ok(_) => {}
+                Err(_) => reporter
+                    .warn(format!("{} has no origin remote; importing as '{name}'", dir.display())),
+            }
+            import_one(cache, name, url, dir, &mut report, reporter).await?;
+            return reporter.finish(&report);
+        }
+
+        let repositories = if crate::git::is_git_repository(dir) {
+            vec![dir.to_path_buf()]
+        } else {
+            let mut repositories = Vec::new();
+            let mut entries = tokio::fs::read_dir(dir)
+                .await
+                .with_context(|| format!("Failed to read {}", dir.display()))?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path.is_dir() && crate::git::is_git_repository(&path) {
+                    repositories.push(path);
+                }
+            }
+            repositories.sort();
+            repositories
+        };
+        if repositories.is_empty() {
+            anyhow::bail!("No Git repositories found in {}", dir.display());
+        }
+
+        for repo_dir in repositories {
+            let Ok(remote) = GitRepo::new(&repo_dir).get_remote_url().await else {
+                reporter.warn(format!(
+                    "Skipping {}: no origin remote (use --source to name its source)",
+                    repo_dir.display()
+                ));
+                report.skipped.push(repo_dir);
+                continue;
+            };
+            let matched = manifest.as_ref().and_then(|m| {
+                let mut sources: Vec<_> = m.sources.iter().collect();
+                sources.sort();
+                sources.into_iter().find(|(_, url)| same_repository(url, &remote))
+            });
+            let (name, url) = match matched {
+                Some((name, url)) => (name.clone(), url.clone()),
+                None => {
+                    reporter.warn(format!(
+                        "{} ({remote}) does not match any source in agpm.toml",
+                        repo_dir.display()
+                    ));
+                    let name = repo_dir
+                        .file_name()
+                        .map_or_else(|| remote.clone(), |n| n.to_string_lossy().into_owned());
+                    (name, remote)
+                }
+            };
+            import_one(cache, &name, &url, &repo_dir, &mut report, reporter).await?;
+        }
+
+        reporter.finish(&report)
+    }
+
+    /// Re-clone the bare repository of one manifest source.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The cache instance to repair
+    /// * `manifest_path` - Optional path to the manifest file
+    /// * `source` - Manifest source whose repository to re-clone
+    /// * `reporter` - Where to send the output
+    async fn repair(
+        &self,
+        cache: &Cache,
+        manifest_path: Option<PathBuf>,
+        source: &str,
+        reporter: Reporter,
+    ) -> Result<()> {
+        let manifest = Manifest::load(&find_manifest_with_optional(manifest_path)?)?;
+        let url = manifest
+            .sources
+            .get(source)
+            .ok_or_else(|| anyhow::anyhow!("Source '{source}' is not defined in agpm.toml"))?;
+
+        reporter.line(format!("{SEARCH}Re-cloning {source}..."));
+        let report = cache.repair_source(source, url).await?;
+        if !report.worktrees_removed.is_empty() {
+            reporter.line(format!("  Removed {} worktrees", report.worktrees_removed.len()));
+        }
+        reporter.line(
+            format!("{SUCCESS}Repaired {source} at {}", report.path.display()).green().bold(),
+        );
+        reporter.finish(&report)
+    }
+
+    /// Remove worktrees unused for longer than the age limit and report the space reclaimed.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The cache instance to operate on
+    /// * `max_age_days` - Age limit overriding `cache.worktree_max_age_days`
+    /// * `dry_run` - Only report what would be removed
+    /// * `reporter` - Where to send the output
+    async fn gc(
+        &self,
+        cache: Cache,
+        max_age_days: Option<u64>,
+        dry_run: bool,
+        reporter: Reporter,
+    ) -> Result<()> {
+        let max_age = max_age_days
+            .map(|days| std::time::Duration::from_secs(days * 24 * 60 * 60))
+            .or_else(crate::cache::retention::worktree_max_age)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No worktree age limit configured. Pass --max-age-days or set \
+                     cache.worktree_max_age_days in ~/.agpm/config.toml"
+                )
+            })?;
+
+        reporter.line(format!("{SEARCH}Scanning worktrees for stale entries..."));
+        let report = cache.remove_stale_worktrees(max_age, dry_run).await?;
+        if reporter.is_text() {
+            print_gc_report(&report, dry_run);
+        }
+        reporter.finish(&DryRunReport {
+            dry_run,
+            report: &report,
+        })
+    }
+
+    /// Consolidate identical worktrees and report the space reclaimed.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The cache instance to operate on
+    /// * `dry_run` - Only report what would be consolidated
+    /// * `reporter` - Where to send the output
+    async fn dedupe(&self, cache: Cache, dry_run: bool, reporter: Reporter) -> Result<()> {
+        reporter.line(format!("{SEARCH}Scanning worktrees for identical content..."));
+
+        let report = cache.dedupe_worktrees(dry_run).await?;
+
+        if !report.skipped_in_use.is_empty() {
+            reporter.line(format!(
+                "{WARNING}Skipped {} worktrees currently in use by another operation",
+                report.skipped_in_use.len()
+            ));
+        }
+
+        if report.worktrees_consolidated == 0 {
+            reporter.line(format!(
+                "{SPARKLES}No duplicate worktrees found ({} scanned)",
+                report.worktrees_scanned
+            ));
+        } else if dry_run {
+            reporter.line(format!(
+                "Would consolidate {} of {} worktrees, reclaiming {}",
+                report.worktrees_consolidated,
+                report.worktrees_scanned,
+                format_size(report.bytes_reclaimed)
+            ));
+        } else {
+            reporter.line(
+                format!(
+                    "{SUCCESS}Consolidated {} of {} worktrees, reclaimed {}",
+                    report.worktrees_consolidated,
+                    report.worktrees_scanned,
+                    format_size(report.bytes_reclaimed)
+                )
+                .green()
+                .bold(),
+            );
+        }
+
+        reporter.finish(&DryRunReport {
+            dry_run,
+            report: &report,
+        })
+    }
+
     /// Remove all cached repositories regardless of usage.
     ///
     /// This method performs complete cache cleanup by removing the entire cache
@@ -308,6 +829,7 @@ impl CacheCommand {
     /// # Arguments
     ///
     /// * `cache` - The cache instance to operate on
+    /// * `reporter` - Where to send the output
     ///
     /// # Returns
     ///
@@ -319,21 +841,28 @@ impl CacheCommand {
     /// - Removes the entire cache directory tree
     /// - All subsequent operations will need to re-clone repositories
     /// - Performance impact on next install/update operations
-    async fn clean_all(&self, cache: Cache) -> Result<()> {
-        println!("🗑️  Cleaning all cache...");
+    async fn clean_all(&self, cache: Cache, reporter: Reporter) -> Result<()> {
+        reporter.line(format!("{TRASH}Cleaning all cache..."));
 
         // Also clean up stale lock files (older than 1 hour)
         let cache_dir = cache.cache_dir();
-        if let Ok(removed) = crate::cache::lock::cleanup_stale_locks(cache_dir, 3600).await
-            && removed > 0
-        {
-            println!("  Removed {removed} stale lock files");
+        let lock_removed =
+            crate::cache::lock::cleanup_stale_locks(cache_dir, 3600).await.unwrap_or(0);
+        if lock_removed > 0 {
+            reporter.line(format!("  Removed {lock_removed} stale lock files"));
         }
 
+        let removed = list_repositories(cache.get_cache_location()).await?;
+        let bytes_reclaimed = cache.get_cache_size().await?;
         cache.clear_all().await?;
 
-        println!("{}", "✅ Cache cleared successfully".green().bold());
-        Ok(())
+        reporter.line(format!("{SUCCESS}Cache cleared successfully").green().bold());
+        reporter.finish(&CleanReport {
+            all: true,
+            removed,
+            stale_locks_removed: lock_removed,
+            bytes_reclaimed,
+        })
     }
 
     /// Remove only cached repositories that are not referenced in the current manifest.
@@ -356,6 +885,7 @@ impl CacheCommand {
     ///
     /// * `cache` - The cache instance to operate on
     /// * `manifest_path` - Optional path to the manifest file
+    /// * `reporter` - Where to send the output
     ///
     /// # Behavior Without Manifest
     ///
@@ -376,8 +906,13 @@ impl CacheCommand {
     /// - Cache entries "official" and "community" are preserved
     /// - Cache entry "old-unused" is removed
     /// - Cache entry "another-project" is removed
-    async fn clean_unused(&self, cache: Cache, manifest_path: Option<PathBuf>) -> Result<()> {
-        println!("🔍 Scanning for unused cache entries...");
+    async fn clean_unused(
+        &self,
+        cache: Cache,
+        manifest_path: Option<PathBuf>,
+        reporter: Reporter,
+    ) -> Result<()> {
+        reporter.line(format!("{SEARCH}Scanning for unused cache entries..."));
 
         // Find manifest to get active sources
         let active_sources = if let Ok(manifest_path) = find_manifest_with_optional(manifest_path) {
@@ -385,31 +920,47 @@ impl CacheCommand {
             manifest.sources.keys().cloned().collect::<Vec<_>>()
         } else {
             // No manifest found, can't determine what's in use
-            println!("⚠️  No agpm.toml found. Use --all to clear entire cache.");
-            return Ok(());
+            reporter.warn("No agpm.toml found. Use --all to clear entire cache.");
+            return reporter.finish(&CleanReport {
+                all: false,
+                removed: Vec::new(),
+                stale_locks_removed: 0,
+                bytes_reclaimed: 0,
+            });
         };
 
-        let removed = cache.clean_unused(&active_sources).await?;
+        let size_before = cache.get_cache_size().await?;
+        let mut removed = cache.clean_unused(&active_sources).await?;
+        removed.sort();
+        for name in &removed {
+            reporter.line(format!("{TRASH}Removing unused cache: {name}"));
+        }
 
         // Also clean up stale lock files (older than 1 hour)
         let cache_dir = cache.cache_dir();
         let lock_removed =
             crate::cache::lock::cleanup_stale_locks(cache_dir, 3600).await.unwrap_or(0);
+        let bytes_reclaimed = size_before.saturating_sub(cache.get_cache_size().await?);
 
-        if removed > 0 || lock_removed > 0 {
+        if !removed.is_empty() || lock_removed > 0 {
             let mut messages = Vec::new();
-            if removed > 0 {
-                messages.push(format!("{removed} unused cache entries"));
+            if !removed.is_empty() {
+                messages.push(format!("{} unused cache entries", removed.len()));
             }
             if lock_removed > 0 {
                 messages.push(format!("{lock_removed} stale lock files"));
             }
-            println!("{}", format!("✅ Removed {}", messages.join(" and ")).green().bold());
+            reporter.line(format!("{SUCCESS}Removed {}", messages.join(" and ")).green().bold());
         } else {
-            println!("✨ Cache is already clean - no unused entries found");
+            reporter.line(format!("{SPARKLES}Cache is already clean - no unused entries found"));
         }
 
-        Ok(())
+        reporter.finish(&CleanReport {
+            all: false,
+            removed,
+            stale_locks_removed: lock_removed,
+            bytes_reclaimed,
+        })
     }
 
     /// Display comprehensive information about the cache directory and contents.
@@ -439,6 +990,7 @@ impl CacheCommand {
     /// # Arguments
     ///
     /// * `cache` - The cache instance to analyze
+    /// * `reporter` - Where to send the output
     ///
     /// # Returns
     ///
@@ -451,43 +1003,178 @@ impl CacheCommand {
     /// - Shows empty cache state when no repositories are cached
     /// - Provides actionable tips for cache management
     /// - Uses async I/O for efficient directory scanning
-    async fn show_info(&self, cache: Cache) -> Result<()> {
+    async fn show_info(&self, cache: Cache, reporter: Reporter) -> Result<()> {
         let location = cache.get_cache_location();
         let size = cache.get_cache_size().await?;
+        let repos = list_repositories(location).await?;
+
+        if !reporter.is_text() {
+            return reporter.finish(&InfoReport {
+                location: location.to_path_buf(),
+                size_bytes: size,
+                read_only_base: cache.read_only_base().map(std::path::Path::to_path_buf),
+                repositories: repos,
+            });
+        }
 
         println!("{}", "Cache Information".bold());
         println!("  Location: {}", location.display());
         println!("  Size: {}", format_size(size));
+        if let Some(base) = cache.read_only_base() {
+            println!("  Read-only base: {}", base.display());
+        }
 
-        // List cached repositories
-        if location.exists() {
-            let mut entries = tokio::fs::read_dir(location).await?;
-            let mut repos = Vec::new();
-
-            while let Some(entry) = entries.next_entry().await? {
-                if entry.path().is_dir()
-                    && let Some(name) = entry.path().file_name()
-                {
-                    repos.push(name.to_string_lossy().to_string());
-                }
-            }
-
-            if !repos.is_empty() {
-                println!("\n{}", "Cached repositories:".bold());
-                for repo in repos {
-                    println!("  • {repo}");
-                }
+        if !repos.is_empty() {
+            println!("\n{}", "Cached repositories:".bold());
+            for repo in repos {
+                println!("  • {repo}");
             }
         }
 
         println!("\n{}", "Tip:".yellow());
         println!("  Use 'agpm cache clean' to remove unused cache");
         println!("  Use 'agpm cache clean --all' to clear all cache");
+        println!("  Use 'agpm cache dedupe' to consolidate identical worktrees");
+        println!("  Use 'agpm cache gc --max-age-days <DAYS>' to remove unused worktrees");
 
         Ok(())
     }
 }
 
+/// The cache directory, or the bare repository of manifest source `source`.
+fn cache_path(
+    cache: &Cache,
+    manifest_path: Option<PathBuf>,
+    source: Option<&str>,
+) -> Result<PathBuf> {
+    let Some(name) = source else {
+        return Ok(cache.get_cache_location().to_path_buf());
+    };
+    let manifest = Manifest::load(&find_manifest_with_optional(manifest_path)?)?;
+    let url = manifest
+        .sources
+        .get(name)
+        .ok_or_else(|| anyhow::anyhow!("Source '{name}' is not defined in agpm.toml"))?;
+    Ok(cache.bare_repo_path(url))
+}
+
+/// Launch the platform file explorer at `path`.
+fn open_in_file_explorer(path: &Path) -> Result<()> {
+    if !path.exists() {
+        anyhow::bail!("{} does not exist yet; run 'agpm install' first", path.display());
+    }
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    let status = std::process::Command::new(opener)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to launch '{opener}' for {}", path.display()))?;
+    // explorer.exe reports a failure exit code even when the window opened
+    if !status.success() && !cfg!(target_os = "windows") {
+        anyhow::bail!("'{opener}' exited with {status} for {}", path.display());
+    }
+    Ok(())
+}
+
+/// List the top-level directories of the cache, sorted by name.
+///
+/// Returns an empty list when the cache directory does not exist.
+/// Import `repo_dir` as the cached repository of source `name` at `url`.
+async fn import_one(
+    cache: &Cache,
+    name: &str,
+    url: &str,
+    repo_dir: &Path,
+    report: &mut ImportReport,
+    reporter: Reporter,
+) -> Result<()> {
+    let outcome = cache.import_repository(name, url, repo_dir).await?;
+    let (path, imported) = match outcome {
+        ImportOutcome::Imported(path) => (path, true),
+        ImportOutcome::AlreadyCached(path) => (path, false),
+    };
+    if imported {
+        reporter.line(format!("{SUCCESS}Imported {name} from {}", repo_dir.display()));
+    } else {
+        reporter.line(format!("{SPARKLES}{name} is already cached at {}", path.display()));
+    }
+    let entry = ImportedRepository {
+        source: name.to_string(),
+        url: crate::git::strip_auth_from_url(url).unwrap_or_else(|_| url.to_string()),
+        from: repo_dir.to_path_buf(),
+        path,
+    };
+    if imported {
+        report.imported.push(entry);
+    } else {
+        report.already_cached.push(entry);
+    }
+    Ok(())
+}
+
+async fn list_repositories(location: &std::path::Path) -> Result<Vec<String>> {
+    let mut repos = Vec::new();
+    if !location.exists() {
+        return Ok(repos);
+    }
+
+    let mut entries = tokio::fs::read_dir(location).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.path().is_dir()
+            && let Some(name) = entry.path().file_name()
+        {
+            repos.push(name.to_string_lossy().to_string());
+        }
+    }
+    repos.sort();
+    Ok(repos)
+}
+
+/// Print the outcome of a worktree garbage collection pass.
+///
+/// Shared by `agpm cache gc` and the automatic cleanup at the end of `agpm install`.
+pub(super) fn print_gc_report(report: &WorktreeGcReport, dry_run: bool) {
+    for path in &report.removed {
+        let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+        println!("  {TRASH}{name}");
+    }
+
+    if !report.skipped_in_use.is_empty() {
+        println!(
+            "{WARNING}Skipped {} stale worktrees currently in use by another operation",
+            report.skipped_in_use.len()
+        );
+    }
+
+    if report.removed.is_empty() {
+        println!("{SPARKLES}No stale worktrees found ({} scanned)", report.worktrees_scanned);
+    } else if dry_run {
+        println!(
+            "Would remove {} of {} worktrees, reclaiming {}",
+            report.removed.len(),
+            report.worktrees_scanned,
+            format_size(report.bytes_reclaimed)
+        );
+    } else {
+        println!(
+            "{}",
+            format!(
+                "{SUCCESS}Removed {} of {} worktrees, reclaimed {}",
+                report.removed.len(),
+                report.worktrees_scanned,
+                format_size(report.bytes_reclaimed)
+            )
+            .green()
+            .bold()
+        );
+    }
+}
+
 /// Format byte size into human-readable string with appropriate units.
 ///
 /// This function converts raw byte values into human-readable format using
@@ -527,7 +1214,7 @@ impl CacheCommand {
 /// assert_eq!(format_size(1048576), "1.00 MB");
 /// assert_eq!(format_size(1073741824), "1.00 GB");
 /// ```
-fn format_size(bytes: u64) -> String {
+pub(super) fn format_size(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
 
     if bytes == 0 {
@@ -583,6 +1270,7 @@ mod tests {
 
         let cmd = CacheCommand {
             command: Some(CacheSubcommands::Info),
+            format: CacheFormat::Text,
         };
 
         // Create a cache directory with some test content
@@ -617,6 +1305,7 @@ mod tests {
             command: Some(CacheSubcommands::Clean {
                 all: true,
             }),
+            format: CacheFormat::Text,
         };
 
         cmd.execute_with_cache(cache).await?;
@@ -650,6 +1339,7 @@ mod tests {
             command: Some(CacheSubcommands::Clean {
                 all: false,
             }),
+            format: CacheFormat::Text,
         };
 
         // Pass a non-existent manifest path to ensure no manifest is found
@@ -694,6 +1384,7 @@ mod tests {
             command: Some(CacheSubcommands::Clean {
                 all: false,
             }),
+            format: CacheFormat::Text,
         };
 
         cmd.execute_with_cache_and_manifest(cache, Some(manifest_path)).await?;
@@ -707,6 +1398,31 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_cache_dedupe_command() -> Result<()> {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new()?;
+        for name in ["org_repo_aaaaaaaa", "org_repo_bbbbbbbb"] {
+            let worktree = temp_dir.path().join("worktrees").join(name);
+            std::fs::create_dir_all(&worktree)?;
+            std::fs::write(worktree.join("agent.md"), "# Agent")?;
+        }
+        let cache = Cache::with_dir(temp_dir.path().to_path_buf())?;
+
+        let cmd = CacheCommand {
+            command: Some(CacheSubcommands::Dedupe {
+                dry_run: false,
+            }),
+            format: CacheFormat::Text,
+        };
+        cmd.execute_with_cache(cache).await?;
+
+        let cache = Cache::with_dir(temp_dir.path().to_path_buf())?;
+        assert_eq!(cache.dedupe_worktrees(true).await?.worktrees_consolidated, 0);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_cache_default_command() -> Result<()> {
         use tempfile::TempDir;
@@ -717,6 +1433,7 @@ mod tests {
         // Test that no subcommand defaults to Info
         let cmd = CacheCommand {
             command: None,
+            format: CacheFormat::Text,
         };
 
         cmd.execute_with_cache(cache).await?;
@@ -735,6 +1452,7 @@ mod tests {
 
         let cmd = CacheCommand {
             command: Some(CacheSubcommands::Info),
+            format: CacheFormat::Text,
         };
 
         cmd.execute_with_cache(cache).await?;
@@ -768,6 +1486,7 @@ mod tests {
 
         let cmd = CacheCommand {
             command: Some(CacheSubcommands::Info),
+            format: CacheFormat::Text,
         };
 
         cmd.execute_with_cache(cache).await?;
@@ -780,6 +1499,7 @@ mod tests {
 
         let cmd = CacheCommand {
             command: Some(CacheSubcommands::Info),
+            format: CacheFormat::Text,
         };
 
         // This uses the default cache directory
@@ -799,6 +1519,7 @@ mod tests {
             command: Some(CacheSubcommands::Clean {
                 all: true,
             }),
+            format: CacheFormat::Text,
         };
 
         cmd.execute_with_cache(cache).await?;
@@ -839,6 +1560,7 @@ mod tests {
             command: Some(CacheSubcommands::Clean {
                 all: false,
             }),
+            format: CacheFormat::Text,
         };
 
         cmd.execute_with_cache_and_manifest(cache, Some(manifest_path)).await?;
@@ -869,6 +1591,7 @@ mod tests {
 
         let cmd = CacheCommand {
             command: Some(CacheSubcommands::Info),
+            format: CacheFormat::Text,
         };
 
         cmd.execute_with_cache(cache).await?;
@@ -898,6 +1621,7 @@ mod tests {
             command: Some(CacheSubcommands::Clean {
                 all: false,
             }),
+            format: CacheFormat::Text,
         };
 
         // Pass a non-existent manifest path to ensure no manifest is found
@@ -991,6 +1715,7 @@ mod tests {
             command: Some(CacheSubcommands::Clean {
                 all: false,
             }),
+            format: CacheFormat::Text,
         };
 
         cmd.execute_with_cache_and_manifest(cache, Some(manifest_path)).await?;
@@ -12,6 +12,8 @@
 //!
 //! - **`display_dry_run_results()`**: Rich dry-run output with CI exit codes
 //! - **`display_no_changes()`**: Context-appropriate "no changes" messages
+//! - **`display_resolution_explanations()`**: Per-dependency `--explain` report
+//! - **`apply_path_overrides()`**: Announces and applies `[path-overrides]`
 //!
 //! # Legacy Support
 //!
@@ -31,6 +33,7 @@ use std::path::{Path, PathBuf};
 use tokio::io::{AsyncBufReadExt, BufReader};
 
 use crate::manifest::{Manifest, find_manifest};
+use crate::utils::terminal::ROCKET;
 
 /// Common trait for CLI command execution pattern
 pub trait CommandExecutor: Sized {
@@ -465,7 +468,7 @@ pub async fn handle_legacy_ccpm_migration(
 
     if should_migrate {
         println!();
-        println!("{}", "🚀 Starting migration...".cyan());
+        println!("{}", format!("{ROCKET}Starting migration...").cyan());
 
         // Perform the migration with automatic installation
         let migrate_cmd = super::migrate::MigrateCommand::new(Some(dir.clone()), false, false);
@@ -964,6 +967,242 @@ pub fn display_no_changes(mode: OperationMode, quiet: bool) {
     }
 }
 
+/// Output format for `--explain`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExplainFormat {
+    /// Human-readable report grouped by dependency.
+    #[default]
+    Text,
+    /// JSON array with one object per dependency.
+    Json,
+}
+
+/// Display resolution explanations grouped by dependency.
+///
+/// Explanations are printed even in quiet mode since they were explicitly
+/// requested. JSON output is a single array so it can be piped to `jq`.
+///
+/// # Arguments
+///
+/// * `format` - Text or JSON output
+/// * `manifest` - Manifest used to attribute explanations to dependencies
+/// * `explanations` - Explanations recorded by the resolver
+///
+/// # Errors
+///
+/// Returns an error if JSON serialization fails
+pub fn display_resolution_explanations(
+    format: ExplainFormat,
+    manifest: &Manifest,
+    explanations: &[crate::resolver::explain::VersionExplanation],
+) -> Result<()> {
+    use crate::resolver::explain::CandidateStatus;
+
+    let grouped = crate::resolver::explain::group_by_dependency(manifest, explanations);
+
+    if format == ExplainFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&grouped)?);
+        return Ok(());
+    }
+
+    if grouped.is_empty() {
+        println!("No versions were resolved (nothing to explain)");
+        return Ok(());
+    }
+
+    println!("{}", "Resolution explanation:".bold());
+    for entry in &grouped {
+        let e = &entry.explanation;
+        let header = match (&entry.resource_type, &entry.name) {
+            (Some(resource_type), Some(name)) => format!("{resource_type}/{name}"),
+            _ => "(transitive)".to_string(),
+        };
+        println!("\n  {} {}", header.cyan(), format!("{}@{}", e.source, e.requested).dimmed());
+
+        for candidate in &e.candidates {
+            let line = format!("{:<24} {}", candidate.tag, candidate.status.describe());
+            match candidate.status {
+                CandidateStatus::Selected => println!("    {} {}", "✓".green(), line.green()),
+                CandidateStatus::Superseded => println!("    {} {line}", "·".dimmed()),
+                _ => println!("    {} {}", "✗".red(), line.dimmed()),
+            }
+        }
+
+        println!("    {} {}", "→".cyan(), e.reason);
+        if let (Some(resolved_ref), Some(sha)) = (&e.resolved_ref, &e.resolved_sha) {
+            println!("    {} {resolved_ref} ({})", "→".cyan(), &sha[..8.min(sha.len())]);
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Output format for `--since` and `--concurrency-report`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum DiffFormat {
+    /// Human-readable report grouped by resource type.
+    #[default]
+    Text,
+    /// JSON object with one entry per changed resource.
+    Json,
+}
+
+/// Load the older lockfile passed to `--since`.
+///
+/// # Errors
+///
+/// Returns an error if the file does not exist or cannot be read or parsed
+pub fn load_since_lockfile(path: &Path) -> Result<crate::lockfile::LockFile> {
+    if !path.exists() {
+        anyhow::bail!("Lockfile passed to --since not found: {}", path.display());
+    }
+    crate::lockfile::LockFile::load(path)
+        .with_context(|| format!("Failed to load lockfile passed to --since: {}", path.display()))
+}
+
+/// Display how `new` differs from the lockfile passed to `--since`.
+///
+/// The report is printed even in quiet mode since it was explicitly requested.
+/// JSON output is a single object so it can be piped to `jq` or posted as a
+/// pull request comment.
+///
+/// # Arguments
+///
+/// * `old` - The lockfile passed to `--since`
+/// * `new` - The current or newly resolved lockfile
+/// * `format` - Text or JSON output
+///
+/// # Errors
+///
+/// Returns an error if JSON serialization fails
+pub fn display_lockfile_diff(
+    old: &crate::lockfile::LockFile,
+    new: &crate::lockfile::LockFile,
+    format: DiffFormat,
+) -> Result<()> {
+    use crate::lockfile::diff::{ChangeKind, LockfileDiff};
+
+    let diff = LockfileDiff::between(old, new);
+
+    if format == DiffFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+        return Ok(());
+    }
+
+    if diff.is_empty() {
+        println!("✓ {}", "No lockfile changes".green());
+        return Ok(());
+    }
+
+    let describe = |version: &Option<String>, commit: &Option<String>| match (version, commit) {
+        (Some(version), _) => version.clone(),
+        (None, Some(sha)) => sha[..8.min(sha.len())].to_string(),
+        (None, None) => "local".to_string(),
+    };
+
+    println!("{}", "Lockfile changes:".bold());
+    let mut current_type = None;
+    for change in &diff.changes {
+        if current_type != Some(change.resource_type) {
+            current_type = Some(change.resource_type);
+            println!("\n  {}", change.resource_type.to_plural().bold());
+        }
+        let old = describe(&change.old_version, &change.old_commit);
+        let new = describe(&change.new_version, &change.new_commit);
+        match change.kind {
+            ChangeKind::Added => {
+                println!("    {} {} {}", "+".green(), change.name.cyan(), new.green());
+            }
+            ChangeKind::Removed => {
+                println!("    {} {} {}", "-".red(), change.name.cyan(), old.red());
+            }
+            ChangeKind::Changed => {
+                println!(
+                    "    {} {} {} → {}",
+                    "~".yellow(),
+                    change.name.cyan(),
+                    old.yellow(),
+                    new.green()
+                );
+            }
+        }
+    }
+
+    let count = |kind| diff.changes.iter().filter(|c| c.kind == kind).count();
+    println!();
+    println!(
+        "{}",
+        format!(
+            "Total: {} added, {} removed, {} changed",
+            count(ChangeKind::Added),
+            count(ChangeKind::Removed),
+            count(ChangeKind::Changed)
+        )
+        .bold()
+    );
+
+    Ok(())
+}
+
+/// Print installed resources whose content is byte-identical.
+///
+/// Groups are built from the checksums recorded in the lockfile (see
+/// [`crate::lockfile::duplicates::find_duplicates`]), so this must run after
+/// installation has filled them in.
+pub fn display_duplicates(lockfile: &crate::lockfile::LockFile) {
+    let groups = crate::lockfile::duplicates::find_duplicates(lockfile);
+    if groups.is_empty() {
+        println!("✓ {}", "No duplicate resource content".green());
+        return;
+    }
+
+    println!("{}", "Duplicate content:".bold());
+    for group in &groups {
+        let short = group.checksum.get(..19).unwrap_or(&group.checksum);
+        println!(
+            "
+  {} ({} copies)",
+            short.yellow(),
+            group.resources.len()
+        );
+        for resource in &group.resources {
+            println!(
+                "    {} {} {}",
+                resource.resource_type,
+                resource.name.cyan(),
+                format!("→ {}", resource.installed_at).dimmed()
+            );
+        }
+    }
+
+    let redundant: usize = groups.iter().map(|g| g.resources.len() - 1).sum();
+    println!();
+    println!(
+        "{}",
+        format!("Total: {} duplicate sets, {} redundant files", groups.len(), redundant).bold()
+    );
+}
+
+/// Print the lock, fetch and worktree timings collected for `--concurrency-report`.
+///
+/// # Errors
+///
+/// Returns an error if the report cannot be serialized to JSON
+pub fn display_concurrency_report(
+    metrics: &crate::cache::CacheMetrics,
+    format: DiffFormat,
+) -> Result<()> {
+    let report = metrics.report();
+    if format == DiffFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", "Concurrency report:".bold());
+        println!("{report}");
+    }
+    Ok(())
+}
+
 /// Handle missing gitignore entries by offering to add them interactively.
 ///
 /// When missing gitignore entries are detected, this function offers to add
@@ -1119,6 +1358,40 @@ pub async fn handle_missing_gitignore_entries(
     Ok(true)
 }
 
+/// Apply the manifest's `[path-overrides]` to `cache`.
+///
+/// Each active override is announced with a warning (unless `quiet`), since
+/// the installed content then differs from what the lockfile's commits contain.
+///
+/// # Errors
+///
+/// Returns an error if an override points to a directory that does not exist.
+pub fn apply_path_overrides(
+    cache: crate::cache::Cache,
+    manifest: &Manifest,
+    quiet: bool,
+) -> Result<crate::cache::Cache> {
+    let overrides = manifest.resolved_path_overrides();
+    let mut active: Vec<_> = overrides.iter().collect();
+    active.sort();
+    for (source, dir) in active {
+        if !dir.is_dir() {
+            anyhow::bail!(
+                "Path override for '{source}' points to a missing directory: {}",
+                dir.display()
+            );
+        }
+        if !quiet {
+            eprintln!(
+                "{}Path override active: '{source}' is read from {}",
+                crate::utils::terminal::WARNING,
+                dir.display()
+            );
+        }
+    }
+    Ok(cache.with_path_overrides(overrides))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -12,6 +12,7 @@
 //! - **Interactive Editing**: Open configuration in system editor
 //! - **Source Management**: Add/remove global Git repository sources
 //! - **Path Information**: Display configuration file location
+//! - **Scriptable Editing**: Get and set individual keys, preserving comments
 //! - **Token Security**: Mask sensitive information in output
 //!
 //! # Global Configuration vs Project Manifest
@@ -51,6 +52,13 @@
 //! agpm config path
 //! ```
 //!
+//! Read and write individual settings from scripts:
+//! ```bash
+//! agpm config get git.timeout
+//! agpm config set git.timeout 600
+//! agpm config set sources.private https://github.com/org/private.git
+//! ```
+//!
 //! # Configuration File Structure
 //!
 //! The global configuration follows this format:
@@ -104,12 +112,13 @@
 //! - Source name conflicts (when adding sources)
 
 use crate::core::file_error::{FileOperation, FileResultExt};
+use crate::utils::terminal::{FAILURE, SUCCESS, WARNING};
 use anyhow::Result;
 use clap::{Args, Subcommand};
 use colored::Colorize;
 use std::path::PathBuf;
 
-use crate::config::GlobalConfig;
+use crate::config::{GlobalConfig, edit};
 
 /// Command to manage global AGPM configuration settings.
 ///
@@ -290,6 +299,40 @@ enum ConfigSubcommands {
     /// ```
     ListSources,
 
+    /// Print the value of a single configuration key.
+    ///
+    /// Keys are dotted paths into the configuration file, such as
+    /// `git.timeout` or `sources.private`. Strings are printed without quotes.
+    /// Fails if the key is not set in the file.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm config get git.timeout
+    /// ```
+    Get {
+        /// Dotted key to read (e.g. `cache.mirror_url`)
+        key: String,
+    },
+
+    /// Set a single configuration key, preserving comments and formatting.
+    ///
+    /// `true` and `false` are stored as booleans and integers as integers;
+    /// any other value is stored as a string. The edited file must still be a
+    /// valid configuration. Unknown keys are written with a warning.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm config set git.partial true
+    /// agpm config set cache.mirror_url https://agpm-cache.example.com
+    /// ```
+    Set {
+        /// Dotted key to write (e.g. `git.timeout`)
+        key: String,
+
+        /// New value for the key
+        value: String,
+    },
+
     /// Display the path to the global configuration file.
     ///
     /// Shows the full file system path to the global configuration file.
@@ -321,6 +364,8 @@ impl ConfigCommand {
     /// - `AddSource { name, url }` → Add new global source
     /// - `RemoveSource { name }` → Remove existing global source
     /// - `ListSources` → Display all configured sources (with token masking)
+    /// - `Get { key }` → Print a single configuration value
+    /// - `Set { key, value }` → Write a single configuration value
     /// - `Path` → Show configuration file path
     ///
     /// # Security Handling
@@ -379,6 +424,13 @@ impl ConfigCommand {
                 name,
             }) => Self::remove_source_with_path(name, config_path).await,
             Some(ConfigSubcommands::ListSources) => Self::list_sources_with_path(config_path).await,
+            Some(ConfigSubcommands::Get {
+                key,
+            }) => Self::get_with_path(&key, config_path).await,
+            Some(ConfigSubcommands::Set {
+                key,
+                value,
+            }) => Self::set_with_path(&key, &value, config_path).await,
             Some(ConfigSubcommands::Path) => {
                 Self::show_path(config_path);
                 Ok(())
@@ -392,7 +444,7 @@ impl ConfigCommand {
         });
 
         if config_path.exists() && !force {
-            println!("❌ Global config already exists at: {}", config_path.display());
+            println!("{FAILURE}Global config already exists at: {}", config_path.display());
             println!("   Use --force to overwrite");
             return Ok(());
         }
@@ -411,7 +463,7 @@ impl ConfigCommand {
 
         config.save_to(&config_path).await?;
 
-        println!("✅ Created global config at: {}", config_path.display());
+        println!("{SUCCESS}Created global config at: {}", config_path.display());
         println!("\n{}", "Example configuration:".bold());
         println!("{}", toml::to_string_pretty(&config)?);
         println!("\n{}", "Next steps:".yellow());
@@ -431,7 +483,7 @@ impl ConfigCommand {
         };
 
         if config_path.exists() && !force {
-            println!("❌ Global config already exists at: {}", config_path.display());
+            println!("{FAILURE}Global config already exists at: {}", config_path.display());
             println!("   Use --force to overwrite");
             return Ok(());
         }
@@ -449,7 +501,7 @@ impl ConfigCommand {
         }
         config.save_to(&config_path).await?;
 
-        println!("✅ Created global config at: {}", config_path.display());
+        println!("{SUCCESS}Created global config at: {}", config_path.display());
         println!("\n{}", "Example configuration:".bold());
         println!("{}", toml::to_string_pretty(&config)?);
         println!("\n{}", "Next steps:".yellow());
@@ -485,7 +537,7 @@ impl ConfigCommand {
         });
 
         if !config_path.exists() {
-            println!("❌ No global config found. Creating one...");
+            println!("{FAILURE}No global config found. Creating one...");
             let config = GlobalConfig::init_example();
             config.save().await?;
         }
@@ -505,9 +557,9 @@ impl ConfigCommand {
         let status = std::process::Command::new(&editor).arg(&config_path).status()?;
 
         if status.success() {
-            println!("✅ Config edited successfully");
+            println!("{SUCCESS}Config edited successfully");
         } else {
-            println!("❌ Editor exited with error");
+            println!("{FAILURE}Editor exited with error");
         }
 
         Ok(())
@@ -522,7 +574,7 @@ impl ConfigCommand {
             GlobalConfig::load_with_optional(config_path.clone()).await.unwrap_or_default();
 
         if config.has_source(&name) {
-            println!("⚠️  Source '{name}' already exists");
+            println!("{WARNING}Source '{name}' already exists");
             println!("   Current URL: {}", config.get_source(&name).unwrap());
             println!("   New URL: {url}");
             println!("   Updating...");
@@ -534,7 +586,7 @@ impl ConfigCommand {
         });
         config.save_to(&save_path).await?;
 
-        println!("✅ Added global source '{}': {}", name.green(), url);
+        println!("{SUCCESS}Added global source '{}': {}", name.green(), url);
 
         if url.contains("YOUR_TOKEN") || url.contains("TOKEN") {
             println!("\n{}", "Warning:".yellow());
@@ -554,9 +606,9 @@ impl ConfigCommand {
                     .unwrap_or_else(|_| PathBuf::from("~/.agpm/config.toml"))
             });
             config.save_to(&save_path).await?;
-            println!("✅ Removed global source '{}'", name.red());
+            println!("{SUCCESS}Removed global source '{}'", name.red());
         } else {
-            println!("❌ Source '{name}' not found in global config");
+            println!("{FAILURE}Source '{name}' not found in global config");
         }
 
         Ok(())
@@ -599,6 +651,36 @@ impl ConfigCommand {
         Ok(())
     }
 
+    async fn get_with_path(key: &str, config_path: Option<PathBuf>) -> Result<()> {
+        let config_path = config_path.unwrap_or_else(|| {
+            GlobalConfig::default_path().unwrap_or_else(|_| PathBuf::from("~/.agpm/config.toml"))
+        });
+        let doc = edit::load_document(&config_path).await?;
+        match edit::get_value(&doc, key) {
+            Some(value) => {
+                println!("{value}");
+                Ok(())
+            }
+            None => anyhow::bail!("Config key '{key}' is not set in {}", config_path.display()),
+        }
+    }
+
+    async fn set_with_path(key: &str, value: &str, config_path: Option<PathBuf>) -> Result<()> {
+        let config_path = config_path.unwrap_or_else(|| {
+            GlobalConfig::default_path().unwrap_or_else(|_| PathBuf::from("~/.agpm/config.toml"))
+        });
+        if !edit::is_known_key(key) {
+            eprintln!("{WARNING}Unknown config key '{key}'; it will be written but ignored");
+        }
+
+        let mut doc = edit::load_document(&config_path).await?;
+        edit::set_value(&mut doc, key, edit::parse_value(value))?;
+        edit::save_document(&doc, &config_path).await?;
+
+        println!("{SUCCESS}Set {} = {value}", key.green());
+        Ok(())
+    }
+
     fn show_path(config_path: Option<PathBuf>) {
         let config_path = config_path.unwrap_or_else(|| {
             GlobalConfig::default_path().unwrap_or_else(|_| PathBuf::from("~/.agpm/config.toml"))
@@ -1569,4 +1651,46 @@ mod tests {
             _ => panic!("Wrong variant"),
         }
     }
+
+    #[tokio::test]
+    async fn test_config_get_set_preserves_comments() -> Result<()> {
+        let temp = TempDir::new()?;
+        let config_path = temp.path().join("config.toml");
+        tokio::fs::write(&config_path, "# My settings\n[git]\ntimeout = 60 # seconds\n").await?;
+
+        for (key, value) in [("git.timeout", "600"), ("git.partial", "true"), ("foo", "bar")] {
+            let cmd = ConfigCommand {
+                command: Some(ConfigSubcommands::Set {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                }),
+            };
+            cmd.execute(Some(config_path.clone())).await?;
+        }
+
+        let content = tokio::fs::read_to_string(&config_path).await?;
+        assert!(content.contains("# My settings"));
+        let config = GlobalConfig::load_from(&config_path).await?;
+        assert_eq!(config.git.timeout, Some(600));
+        assert_eq!(config.git.partial, Some(true));
+
+        let get = |key: &str| ConfigCommand {
+            command: Some(ConfigSubcommands::Get {
+                key: key.to_string(),
+            }),
+        };
+        get("git.timeout").execute(Some(config_path.clone())).await?;
+        assert!(get("cache.mirror_url").execute(Some(config_path.clone())).await.is_err());
+
+        // A value of the wrong type leaves the file untouched
+        let invalid = ConfigCommand {
+            command: Some(ConfigSubcommands::Set {
+                key: "git.timeout".to_string(),
+                value: "soon".to_string(),
+            }),
+        };
+        assert!(invalid.execute(Some(config_path.clone())).await.is_err());
+        assert_eq!(tokio::fs::read_to_string(&config_path).await?, content);
+        Ok(())
+    }
 }
@@ -0,0 +1,248 @@
+//! Export the resolved dependency graph.
+//!
+//! This module provides the `graph` command, which prints the dependency graph
+//! recorded in `agpm.lock` in Graphviz DOT format (or JSON) for documentation
+//! and tooling. Nodes are resources labelled with their type and version, and
+//! edges point from a resource to each transitive dependency it declares.
+//!
+//! The graph is built with the resolver's [`DependencyGraph`], using the same
+//! node identity (resource type, name and source) as during resolution.
+//!
+//! # Examples
+//!
+//! Render the graph as SVG:
+//! ```bash
+//! agpm graph | dot -Tsvg -o deps.svg
+//! ```
+//!
+//! Output as JSON:
+//! ```bash
+//! agpm graph --format json
+//! ```
+
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::lockfile::lockfile_dependency_ref::LockfileDependencyRef;
+use crate::lockfile::{LockFile, LockedResource};
+use crate::manifest::find_manifest_with_optional;
+use crate::resolver::{DependencyGraph, DependencyNode};
+
+/// Output format for `agpm graph`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT, ready to pipe into `dot`.
+    #[default]
+    Dot,
+    /// JSON object with `nodes` and `edges` arrays.
+    Json,
+}
+
+/// Command to print the resolved dependency graph.
+#[derive(Args, Debug)]
+pub struct GraphCommand {
+    /// Output format (dot, json)
+    #[arg(short = 'f', long, value_enum, default_value_t = GraphFormat::Dot)]
+    format: GraphFormat,
+}
+
+/// A resource in the exported graph.
+#[derive(Debug, Serialize)]
+struct GraphNode {
+    id: String,
+    #[serde(rename = "type")]
+    resource_type: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+}
+
+/// A dependency from one resource to another, by node id.
+#[derive(Debug, Serialize)]
+struct GraphEdge {
+    from: String,
+    to: String,
+}
+
+/// The exported graph, with nodes and edges sorted for stable output.
+#[derive(Debug, Serialize)]
+struct GraphExport {
+    nodes: Vec<GraphNode>,
+    edges: Vec<GraphEdge>,
+}
+
+impl GraphCommand {
+    /// Execute the graph command with an optional manifest path.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let project_dir = manifest_path.parent().unwrap_or_else(|| std::path::Path::new("."));
+
+        let manifest = crate::manifest::Manifest::load(&manifest_path)?;
+        let command_context =
+            crate::cli::common::CommandContext::new(manifest, project_dir.to_path_buf())?;
+        let lockfile = command_context
+            .load_lockfile_with_regeneration(true, "graph")?
+            .ok_or_else(|| anyhow::anyhow!("No lockfile found. Run 'agpm install' first."))?;
+
+        let export = build_export(&lockfile);
+        match self.format {
+            GraphFormat::Dot => print!("{}", render_dot(&export)),
+            GraphFormat::Json => println!("{}", serde_json::to_string_pretty(&export)?),
+        }
+        Ok(())
+    }
+}
+
+fn node_for(resource: &LockedResource) -> DependencyNode {
+    DependencyNode::with_source(
+        resource.resource_type,
+        resource.name.clone(),
+        resource.source.clone(),
+    )
+}
+
+/// Find the lockfile entry a `dependencies` reference points to.
+fn find_dependency<'a>(
+    lockfile: &'a LockFile,
+    dep_ref: &LockfileDependencyRef,
+) -> Option<&'a LockedResource> {
+    let resources = lockfile.get_resources(&dep_ref.resource_type);
+    resources
+        .iter()
+        .find(|r| r.name == dep_ref.path && r.source == dep_ref.source)
+        .or_else(|| resources.iter().find(|r| r.name == dep_ref.path))
+}
+
+/// Build the resolver's dependency graph from the edges recorded in the lockfile.
+fn build_graph(lockfile: &LockFile) -> DependencyGraph {
+    let mut graph = DependencyGraph::new();
+    for resource in lockfile.all_resources() {
+        let from = node_for(resource);
+        graph.add_node(from.clone());
+        for dep in &resource.dependencies {
+            let target = LockfileDependencyRef::from_str(dep)
+                .ok()
+                .and_then(|dep_ref| find_dependency(lockfile, &dep_ref));
+            match target {
+                Some(target) => graph.add_dependency(from.clone(), node_for(target)),
+                None => tracing::debug!("Dependency '{}' of '{}' not in lockfile", dep, from),
+            }
+        }
+    }
+    graph
+}
+
+fn build_export(lockfile: &LockFile) -> GraphExport {
+    // Entries that differ only by tool or template variables share a node
+    let mut versions: HashMap<DependencyNode, Option<String>> = HashMap::new();
+    for resource in lockfile.all_resources() {
+        versions.entry(node_for(resource)).or_insert_with(|| resource.version.clone());
+    }
+
+    let graph = build_graph(lockfile);
+    let nodes: BTreeMap<String, GraphNode> = graph
+        .nodes()
+        .into_iter()
+        .map(|node| {
+            let id = node.display_name();
+            let graph_node = GraphNode {
+                id: id.clone(),
+                resource_type: node.resource_type.to_string(),
+                version: versions.get(&node).cloned().flatten(),
+                name: node.name,
+                source: node.source,
+            };
+            (id, graph_node)
+        })
+        .collect();
+
+    let mut edges: Vec<GraphEdge> = graph
+        .edges()
+        .into_iter()
+        .map(|(from, to)| GraphEdge {
+            from: from.display_name(),
+            to: to.display_name(),
+        })
+        .collect();
+    edges.sort_by(|a, b| (&a.from, &a.to).cmp(&(&b.from, &b.to)));
+
+    GraphExport {
+        nodes: nodes.into_values().collect(),
+        edges,
+    }
+}
+
+/// Quote a string as a DOT identifier.
+fn dot_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+fn render_dot(export: &GraphExport) -> String {
+    let mut out = String::from("digraph dependencies {\n    rankdir=LR;\n    node [shape=box];\n");
+    for node in &export.nodes {
+        let mut label = format!("{}\n{}", node.resource_type, node.name);
+        if let Some(version) = &node.version {
+            label.push_str(&format!("\n{version}"));
+        }
+        out.push_str(&format!("    {} [label={}];\n", dot_quote(&node.id), dot_quote(&label)));
+    }
+    for edge in &export.edges {
+        out.push_str(&format!("    {} -> {};\n", dot_quote(&edge.from), dot_quote(&edge.to)));
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ResourceType;
+    use crate::lockfile::LockedResourceBuilder;
+
+    fn resource(name: &str, resource_type: ResourceType, deps: &[&str]) -> LockedResource {
+        let mut resource = LockedResourceBuilder::new(
+            name.to_string(),
+            format!("{name}.md"),
+            "sha256:abc".to_string(),
+            format!(".claude/{name}.md"),
+            resource_type,
+        )
+        .source(Some("community".to_string()))
+        .version(Some("v1.0.0".to_string()))
+        .build();
+        resource.dependencies = deps.iter().map(|d| d.to_string()).collect();
+        resource
+    }
+
+    #[test]
+    fn test_render_dot() {
+        let mut lockfile = LockFile::new();
+        lockfile.agents.push(resource(
+            "agents/reviewer",
+            ResourceType::Agent,
+            &["community/snippet:snippets/style@v1.0.0", "community/snippet:missing@v1.0.0"],
+        ));
+        lockfile.snippets.push(resource("snippets/style", ResourceType::Snippet, &[]));
+
+        let dot = render_dot(&build_export(&lockfile));
+        assert_eq!(
+            dot,
+            "digraph dependencies {\n    rankdir=LR;\n    node [shape=box];\n    \
+             \"community/agent:agents/reviewer\" [label=\"agent\\nagents/reviewer\\nv1.0.0\"];\n    \
+             \"community/snippet:snippets/style\" [label=\"snippet\\nsnippets/style\\nv1.0.0\"];\n    \
+             \"community/agent:agents/reviewer\" -> \"community/snippet:snippets/style\";\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_dot_quote_escapes() {
+        assert_eq!(dot_quote(r#"a"b\c"#), r#""a\"b\\c""#);
+    }
+}
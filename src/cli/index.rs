@@ -0,0 +1,342 @@
+//! Build a searchable index of the resources offered by every source.
+//!
+//! This module provides the `index` command. Each source of the manifest (or
+//! each source named on the command line) is fetched, and its newest stable version tag (or
+//! the default branch when it has no version tags) is checked out. Every
+//! Markdown and JSON file is then read, and its frontmatter metadata and
+//! declared transitive dependencies are written to a JSON index:
+//!
+//! ```json
+//! {
+//!   "sources": {
+//!     "community": {
+//!       "version": "v1.2.0",
+//!       "commit": "4f3c...",
+//!       "resources": {
+//!         "agents/reviewer.md": {
+//!           "type": "agent",
+//!           "title": "Code Reviewer",
+//!           "tags": ["review"],
+//!           "dependencies": { "snippets": ["snippets/style.md"] }
+//!         }
+//!       }
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! Re-indexing is incremental: a source whose commit matches the one already
+//! recorded in the index keeps its entries without being checked out or read
+//! again. Local directory and archive sources have no commit and are always
+//! read. Files are found with the same walk as `agpm bundle lint`.
+//!
+//! # Examples
+//!
+//! ```bash
+//! agpm index
+//! agpm index community --output catalog.json
+//! agpm index --full
+//! ```
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::cache::Cache;
+use crate::core::ResourceType;
+use crate::git::GitRepo;
+use crate::manifest::{DependencySpec, Manifest, find_manifest_with_optional};
+use crate::markdown::MarkdownMetadata;
+use crate::markdown::frontmatter::FrontmatterParser;
+use crate::source::SourceManager;
+use crate::source::scan::{ResourceFile, resource_files};
+
+/// Default file name of the index, next to `agpm.toml`.
+const DEFAULT_INDEX_FILE: &str = "agpm-index.json";
+
+/// Command to index the resources of all sources.
+#[derive(Args, Debug)]
+pub struct IndexCommand {
+    /// Only index these sources (default: all sources of the manifest)
+    #[arg(value_name = "SOURCE")]
+    sources: Vec<String>,
+
+    /// Index file to write (default: agpm-index.json next to agpm.toml)
+    #[arg(short, long, value_name = "PATH")]
+    output: Option<PathBuf>,
+
+    /// Re-read every source, even when its commit is already indexed
+    #[arg(long)]
+    full: bool,
+}
+
+/// The index file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ResourceIndex {
+    /// Indexed sources by name.
+    #[serde(default)]
+    pub sources: BTreeMap<String, IndexedSource>,
+}
+
+/// The resources of one source at one version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedSource {
+    /// Version tag or branch that was indexed; `None` for local sources.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Commit that was indexed; `None` for local and archive sources.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Resources by path within the source.
+    pub resources: BTreeMap<String, IndexedResource>,
+}
+
+/// Metadata of one resource file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedResource {
+    /// Resource type implied by the file's directory.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub resource_type: Option<ResourceType>,
+    /// Title from the frontmatter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Description from the frontmatter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Version from the frontmatter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Author from the frontmatter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Tags from the frontmatter.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Declared transitive dependencies by resource type, as `path` or
+    /// `path@version`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, Vec<String>>,
+}
+
+impl IndexCommand {
+    /// Execute the index command with an optional manifest path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a requested source is not defined, a source cannot
+    /// be fetched or read, or the index cannot be written.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let project_dir = manifest_path.parent().unwrap_or_else(|| Path::new("."));
+        let manifest = Manifest::load(&manifest_path)?;
+        let sources = SourceManager::from_manifest_with_global(&manifest).await?;
+
+        // Sources of the manifest by default; URLs come from the source manager
+        // so credentials from the global config apply
+        let names: Vec<String> = if self.sources.is_empty() {
+            manifest.sources.keys().cloned().collect()
+        } else {
+            self.sources.clone()
+        };
+        let selected = names
+            .into_iter()
+            .map(|name| {
+                let url = sources
+                    .get_source_url(&name)
+                    .ok_or_else(|| anyhow::anyhow!("Source '{name}' is not defined"))?;
+                Ok((name, url))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let output = self.output.unwrap_or_else(|| project_dir.join(DEFAULT_INDEX_FILE));
+        let mut index = if output.exists() {
+            let content = std::fs::read_to_string(&output)
+                .with_context(|| format!("Failed to read index: {}", output.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse index: {}", output.display()))?
+        } else {
+            ResourceIndex::default()
+        };
+
+        let cache = Cache::new()?;
+        for (name, url) in selected {
+            let previous = if self.full {
+                None
+            } else {
+                index.sources.get(&name)
+            };
+            let (indexed, reused) = index_source(&cache, &name, &url, previous).await?;
+            let at = match (&indexed.version, &indexed.commit) {
+                (Some(version), Some(commit)) => format!(" {version} ({})", &commit[..7]),
+                _ => String::new(),
+            };
+            let note = if reused {
+                " (unchanged)"
+            } else {
+                ""
+            };
+            println!("  {}{at}: {} resource(s){note}", name.cyan(), indexed.resources.len());
+            index.sources.insert(name, indexed);
+        }
+
+        let json = serde_json::to_string_pretty(&index)?;
+        crate::utils::fs::atomic_write(&output, format!("{json}\n").as_bytes())
+            .with_context(|| format!("Failed to write index: {}", output.display()))?;
+        println!("{} Wrote {}", "✓".green(), output.display());
+        Ok(())
+    }
+}
+
+/// Index one source, reusing `previous` when it was built from the same commit.
+///
+/// Returns the indexed source and whether `previous` was reused.
+async fn index_source(
+    cache: &Cache,
+    name: &str,
+    url: &str,
+    previous: Option<&IndexedSource>,
+) -> Result<(IndexedSource, bool)> {
+    let checkout = cache
+        .get_or_clone_source(name, url, None)
+        .await
+        .with_context(|| format!("Failed to fetch source '{name}'"))?;
+    if crate::utils::is_local_path(url) || crate::source::archive::is_archive_url(url) {
+        let indexed = IndexedSource {
+            version: None,
+            commit: None,
+            resources: index_directory(&checkout)?,
+        };
+        return Ok((indexed, false));
+    }
+
+    let repo = GitRepo::new(&checkout);
+    let tags = repo.list_tags().await?;
+    let (version, reference) = match crate::resolver::find_best_matching_tag(">=0.0.0", tags).ok() {
+        Some(tag) => (tag.clone(), tag),
+        None => {
+            let branch = repo.get_default_branch().await?;
+            (branch.clone(), format!("origin/{branch}"))
+        }
+    };
+    let commit = match repo.resolve_to_sha(Some(&reference)).await {
+        Ok(commit) => commit,
+        // Local repositories without an origin only have the plain branch
+        Err(_) => repo.resolve_to_sha(Some(&version)).await?,
+    };
+
+    if let Some(previous) = previous
+        && previous.commit.as_deref() == Some(commit.as_str())
+    {
+        return Ok((previous.clone(), true));
+    }
+
+    let worktree = cache.get_or_create_worktree_for_sha(name, url, &commit, Some("index")).await?;
+    let indexed = IndexedSource {
+        version: Some(version),
+        commit: Some(commit),
+        resources: index_directory(&worktree)?,
+    };
+    Ok((indexed, false))
+}
+
+/// Read the metadata of every resource file below `root`.
+fn index_directory(root: &Path) -> Result<BTreeMap<String, IndexedResource>> {
+    let root = crate::utils::safe_canonicalize(root)?;
+    if !root.is_dir() {
+        bail!("Source checkout is not a directory: {}", root.display());
+    }
+
+    let mut resources = BTreeMap::new();
+    for file in resource_files(&root) {
+        let file = file?;
+        let content = std::fs::read_to_string(&file.path)
+            .with_context(|| format!("Failed to read {}", file.path.display()))?;
+        resources.insert(file.relative.clone(), index_file(&file, &content));
+    }
+    Ok(resources)
+}
+
+/// Extract the metadata of one file.
+///
+/// Frontmatter that does not parse (e.g. because it is templated) leaves the
+/// metadata empty rather than failing the whole index.
+fn index_file(file: &ResourceFile, content: &str) -> IndexedResource {
+    let mut resource = IndexedResource {
+        resource_type: file.resource_type(),
+        ..IndexedResource::default()
+    };
+
+    let mut all_dependencies = Vec::new();
+    if file.is_json {
+        if let Some(deps) = serde_json::from_str::<serde_json::Value>(content)
+            .ok()
+            .and_then(|value| value.get("dependencies").cloned())
+            .and_then(|deps| serde_json::from_value(deps).ok())
+        {
+            all_dependencies.push(deps);
+        }
+    } else if let Some(metadata) = FrontmatterParser::new()
+        .extract_raw_frontmatter(content)
+        .and_then(|raw| serde_yaml::from_str::<MarkdownMetadata>(&raw).ok())
+    {
+        let agpm_dependencies = metadata.get_agpm_metadata().and_then(|agpm| agpm.dependencies);
+        all_dependencies.extend([metadata.dependencies, agpm_dependencies].into_iter().flatten());
+        resource.title = metadata.title;
+        resource.description = metadata.description;
+        resource.version = metadata.version;
+        resource.author = metadata.author;
+        resource.tags = metadata.tags;
+    }
+
+    for deps in all_dependencies {
+        let deps: BTreeMap<String, Vec<DependencySpec>> = deps;
+        for (resource_type, specs) in deps {
+            let entry = resource.dependencies.entry(resource_type).or_default();
+            entry.extend(specs.into_iter().map(|spec| match spec.version {
+                Some(version) => format!("{}@{version}", spec.path),
+                None => spec.path,
+            }));
+        }
+    }
+    resource
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_index_directory() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("agents")).unwrap();
+        std::fs::create_dir_all(root.join("mcp-servers")).unwrap();
+        std::fs::write(
+            root.join("agents/reviewer.md"),
+            "---\ntitle: Reviewer\ntags: [review]\ndependencies:\n  snippets:\n    - path: \
+             snippets/style.md\n      version: v1.0.0\n---\n# Reviewer\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("agents/templated.md"), "---\ntitle: {{ x }\n---\n").unwrap();
+        std::fs::write(root.join("mcp-servers/fs.json"), r#"{"command": "npx"}"#).unwrap();
+
+        let resources = index_directory(root).unwrap();
+        assert_eq!(resources.len(), 3);
+
+        let reviewer = &resources["agents/reviewer.md"];
+        assert_eq!(reviewer.resource_type, Some(ResourceType::Agent));
+        assert_eq!(reviewer.title.as_deref(), Some("Reviewer"));
+        assert_eq!(reviewer.tags, ["review"]);
+        assert_eq!(reviewer.dependencies["snippets"], ["snippets/style.md@v1.0.0"]);
+
+        // Unparseable frontmatter still lists the file, without metadata
+        let templated = &resources["agents/templated.md"];
+        assert_eq!(templated.title, None);
+        assert_eq!(resources["mcp-servers/fs.json"].resource_type, Some(ResourceType::McpServer));
+    }
+}
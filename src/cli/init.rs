@@ -59,6 +59,7 @@ use std::path::PathBuf;
 use toml_edit::{DocumentMut, Item, Table};
 
 use crate::manifest::tool_config::ToolsConfig;
+use crate::utils::terminal::TIP;
 
 /// Builds the default manifest template programmatically from the actual default configurations.
 ///
@@ -480,8 +481,7 @@ impl InitCommand {
         println!("\n  Then run {} to install", "agpm install".bright_white());
 
         println!(
-            "\n{} If Claude Code can't find installed resources, run {} in Claude Code",
-            "💡".cyan(),
+            "\n{TIP}If Claude Code can't find installed resources, run {} in Claude Code",
             "/config".bright_white()
         );
         println!(
@@ -541,8 +541,7 @@ impl InitCommand {
         println!("{} Updated agpm.toml with default configurations", "✓".green());
 
         println!(
-            "\n{} If Claude Code can't find installed resources, run {} in Claude Code",
-            "💡".cyan(),
+            "\n{TIP}If Claude Code can't find installed resources, run {} in Claude Code",
             "/config".bright_white()
         );
         println!(
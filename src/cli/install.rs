@@ -54,6 +54,25 @@
 //! agpm install --dry-run
 //! ```
 //!
+//! Report lockfile changes relative to an older lockfile (e.g. for a PR comment):
+//! ```bash
+//! agpm install --since base/agpm.lock --format json
+//! ```
+//!
+//! Reinstall only resources that differ from the lockfile:
+//! ```bash
+//! agpm install --changed-only
+//! ```
+//!
+//! Add transitive dependencies to the manifest as direct dependencies:
+//! ```bash
+//! agpm install --save-transitive
+//! ```
+//!
+//! In a workspace root (a manifest with a `[workspace]` section), `install`
+//! installs every member project with its own lockfile, then the root's own
+//! dependencies. See [`crate::manifest::workspace`].
+//!
 //! # Installation Process
 //!
 //! 1. **Manifest Loading**: Reads `agpm.toml` to understand dependencies
@@ -124,12 +143,74 @@ use anyhow::Result;
 use clap::Args;
 use std::path::{Path, PathBuf};
 
-use crate::cache::Cache;
+use crate::cache::{Cache, CacheMetrics};
+use crate::cli::common::{DiffFormat, ExplainFormat};
 use crate::constants::{FALLBACK_CORE_COUNT, MIN_PARALLELISM, PARALLELISM_CORE_MULTIPLIER};
 use crate::core::{OperationContext, ResourceIterator};
 use crate::lockfile::LockFile;
 use crate::manifest::{ResourceDependency, find_manifest_with_optional};
-use crate::resolver::DependencyResolver;
+use crate::resolver::{DependencyResolver, ResolutionStrategy};
+use crate::utils::terminal::WARNING;
+
+/// Fail unless every installed file matches its checksum in `lockfile`.
+fn verify_installation(
+    lockfile: &LockFile,
+    project_dir: &Path,
+    strip_banners: bool,
+    normalize_eol: bool,
+    quiet: bool,
+) -> Result<()> {
+    let issues = crate::installer::verify_installed_files(
+        lockfile,
+        project_dir,
+        strip_banners,
+        normalize_eol,
+    );
+    if issues.is_empty() {
+        if !quiet {
+            println!("✓ Installed files match agpm.lock");
+        }
+        return Ok(());
+    }
+
+    let details: Vec<String> = issues
+        .iter()
+        .map(|issue| {
+            let state = if issue.missing {
+                "missing"
+            } else {
+                "modified"
+            };
+            format!("  {state}: {} ({} '{}')", issue.installed_at, issue.resource_type, issue.name)
+        })
+        .collect();
+    Err(anyhow::anyhow!(
+        "Installed files do not match agpm.lock:\n{}\n\n\
+         Hint: Run 'agpm install --frozen' to restore the locked content.",
+        details.join("\n")
+    ))
+}
+
+/// The lockfile describing what is currently installed, if any.
+///
+/// In `--frozen` mode the lockfile is used as-is, so it is its own baseline.
+/// Otherwise the previous `agpm.lock` is merged with `agpm.private.lock`.
+fn installation_baseline(
+    frozen: bool,
+    lockfile: &LockFile,
+    old_lockfile: Option<&LockFile>,
+    project_dir: &Path,
+) -> Option<LockFile> {
+    if frozen {
+        return Some(lockfile.clone());
+    }
+    old_lockfile.cloned().map(|mut old| {
+        if let Ok(Some(private_lock)) = crate::lockfile::PrivateLockFile::load(project_dir) {
+            old.merge_private(&private_lock);
+        }
+        old
+    })
+}
 
 /// Check if the fast path can be used to skip dependency resolution.
 ///
@@ -223,12 +304,16 @@ fn can_use_fast_path(
 /// # Examples
 ///
 /// ```rust,no_run
+/// use agpm_cli::cli::common::DiffFormat;
 /// use agpm_cli::cli::install::InstallCommand;
+/// use agpm_cli::resolver::ResolutionStrategy;
 ///
 /// // Standard installation
 /// let cmd = InstallCommand {
+///     names: Vec::new(),
 ///     no_lock: false,
 ///     frozen: false,
+///     verify: false,
 ///     no_cache: false,
 ///     max_parallel: None,
 ///     quiet: false,
@@ -236,13 +321,29 @@ fn can_use_fast_path(
 ///     verbose: false,
 ///     no_transitive: false,
 ///     dry_run: false,
+///     explain: None,
+///     resolution: ResolutionStrategy::Highest,
+///     since: None,
+///     format: DiffFormat::Text,
+///     concurrency_report: false,
+///     report_duplicates: false,
+///     changed_only: false,
+///     save_transitive: false,
+///     prune_disabled: false,
+///     prune: false,
+///     symlink: false,
+///     emit_checksums: false,
 ///     yes: false,
+///     env: None,
+///     install_root: None,
 /// };
 ///
 /// // CI/Production installation (frozen lockfile)
 /// let cmd = InstallCommand {
+///     names: Vec::new(),
 ///     no_lock: false,
 ///     frozen: true,
+///     verify: false,
 ///     no_cache: false,
 ///     max_parallel: Some(2),
 ///     quiet: false,
@@ -250,11 +351,36 @@ fn can_use_fast_path(
 ///     verbose: false,
 ///     no_transitive: false,
 ///     dry_run: false,
+///     explain: None,
+///     resolution: ResolutionStrategy::Highest,
+///     since: None,
+///     format: DiffFormat::Text,
+///     concurrency_report: false,
+///     report_duplicates: false,
+///     changed_only: false,
+///     save_transitive: false,
+///     prune_disabled: false,
+///     prune: false,
+///     symlink: false,
+///     emit_checksums: false,
 ///     yes: false,
+///     env: None,
+///     install_root: None,
 /// };
 /// ```
 #[derive(Args)]
+#[command(group(clap::ArgGroup::new("reports").multiple(true).args(["since", "concurrency_report"])))]
 pub struct InstallCommand {
+    /// Install only these dependencies and their transitive dependencies
+    ///
+    /// Names refer to entries in `agpm.toml`. Only the named resources and
+    /// everything they depend on are written; all other installed files and
+    /// lockfile entries are left as they were.
+    ///
+    /// Example: `agpm install my-agent utils-snippet`
+    #[arg(value_name = "NAME", conflicts_with_all = ["changed_only", "save_transitive"])]
+    pub names: Vec<String>,
+
     /// Don't write lockfile after installation
     ///
     /// Prevents the command from creating or updating the `agpm.lock` file.
@@ -271,6 +397,15 @@ pub struct InstallCommand {
     #[arg(long)]
     pub frozen: bool,
 
+    /// With `--frozen`, verify installed files instead of installing
+    ///
+    /// Checks every installed file against the checksum recorded in the
+    /// lockfile and fails if any file is missing or modified. Nothing is
+    /// resolved or written, so CI can assert that the project contains
+    /// exactly what is locked, byte for byte.
+    #[arg(long, requires = "frozen", conflicts_with_all = ["dry_run", "changed_only", "names", "since"])]
+    pub verify: bool,
+
     /// Don't use cache, clone fresh repositories
     ///
     /// Disables the local Git repository cache and clones repositories
@@ -346,6 +481,116 @@ pub struct InstallCommand {
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Explain how each dependency's version was resolved
+    ///
+    /// For every dependency, shows the candidate tags that were considered,
+    /// which were filtered out (prefix mismatch, prerelease, unsatisfied
+    /// constraint) and why the final version was chosen. Use `--explain=json`
+    /// for machine-readable output; it implies `--quiet` so stdout holds only
+    /// the JSON report. Forces full resolution, bypassing the lockfile fast path.
+    #[arg(long, value_enum, value_name = "FORMAT", num_args = 0..=1, require_equals = true, default_missing_value = "text")]
+    pub explain: Option<ExplainFormat>,
+
+    /// Which version to pick when several tags satisfy a constraint
+    ///
+    /// `lowest` resolves every version constraint to the lowest matching tag
+    /// instead of the highest, to check that constraints are not
+    /// under-specified (like Cargo's `-Z minimal-versions`). Prefix and
+    /// prerelease rules still apply. Forces full resolution, bypassing the
+    /// lockfile fast path.
+    #[arg(long, value_enum, value_name = "STRATEGY", default_value_t)]
+    pub resolution: ResolutionStrategy,
+
+    /// Report how the lockfile differs from an older one
+    ///
+    /// After the command finishes, compares the resulting lockfile with the
+    /// one at this path (for example `agpm.lock` from a pull request's base
+    /// branch) and prints added, removed and changed resources with their
+    /// version transitions, grouped by type.
+    #[arg(long, value_name = "LOCKFILE")]
+    pub since: Option<PathBuf>,
+
+    /// Output format for `--since` and `--concurrency-report` (text, json)
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t, requires = "reports")]
+    pub format: DiffFormat,
+
+    /// Report lock contention, fetch and worktree timings
+    ///
+    /// After the command finishes, prints how many cache locks were taken and
+    /// how long tasks waited for them (total and longest wait), the time spent
+    /// fetching, cloning and creating worktrees, and how many worktrees were
+    /// reused, to help choose a `--max-parallel` value.
+    #[arg(long)]
+    pub concurrency_report: bool,
+
+    /// Report installed resources with byte-identical content
+    ///
+    /// After installation, groups installed files by the checksum recorded in
+    /// the lockfile and lists every set of two or more, such as differently
+    /// named dependencies pointing at the same upstream file, as candidates
+    /// for consolidation.
+    #[arg(long, conflicts_with = "dry_run")]
+    pub report_duplicates: bool,
+
+    /// Only install resources that differ from the lockfile
+    ///
+    /// Compares each installed file against the checksum recorded in the
+    /// lockfile and installs only resources that are new, missing, modified
+    /// on disk, or whose resolved inputs changed. Unchanged resources are
+    /// skipped entirely, hook and MCP server configs are only regenerated
+    /// when one of their entries changed, and an up-to-date lockfile is not
+    /// rewritten. Local dependencies are always re-checked.
+    #[arg(long)]
+    pub changed_only: bool,
+
+    /// Add transitive dependencies to the manifest as direct dependencies
+    ///
+    /// After a successful installation, every transitive dependency from a
+    /// Git source that is not yet declared in `agpm.toml` is written to the
+    /// matching section, pinned to its resolved version, so it can be
+    /// patched or pinned explicitly. Existing formatting and comments are
+    /// preserved, and running it again adds nothing new. Private dependencies
+    /// and sources from the private or environment overlays are skipped.
+    #[arg(long, conflicts_with_all = ["no_transitive", "frozen", "dry_run"])]
+    pub save_transitive: bool,
+
+    /// Omit resource types disabled in `[install]` from the lockfile
+    ///
+    /// By default, dependencies of types switched off in the manifest's
+    /// `[install]` section are still resolved and recorded in `agpm.lock`
+    /// with `install = false`. With this flag they are left out of the
+    /// lockfile entirely.
+    #[arg(long, conflicts_with = "frozen")]
+    pub prune_disabled: bool,
+
+    /// Remove everything the lockfile no longer accounts for
+    ///
+    /// Drops lockfile entries that no manifest dependency reaches, deletes
+    /// files in managed install directories (e.g. `.claude/agents/agpm`)
+    /// without a lockfile entry, and strips AGPM-managed MCP servers without
+    /// one from merged config files. Use it after the lockfile was
+    /// changed outside `agpm install`, for example by a `git pull`.
+    #[arg(long, conflicts_with_all = ["frozen", "dry_run"])]
+    pub prune: bool,
+
+    /// Symlink local resources to their source files instead of copying
+    ///
+    /// Same as `install_mode = "symlink"` in the `[installer]` section: files
+    /// from local paths and local directory sources are linked, so edits show
+    /// up without reinstalling. Git sources and resources that are patched or
+    /// templated are still copied.
+    #[arg(long)]
+    pub symlink: bool,
+
+    /// Write CHECKSUMS.txt listing every installed file and its SHA-256
+    ///
+    /// Same as `emit_checksums = true` in the `[installer]` section. The
+    /// sidecar is written to the project directory in the format of
+    /// `sha256sum`, so the installation can be verified with
+    /// `sha256sum -c CHECKSUMS.txt`.
+    #[arg(long, conflicts_with = "dry_run")]
+    pub emit_checksums: bool,
+
     /// Automatically accept migration prompts
     ///
     /// When set, automatically accepts migration prompts for legacy CCPM files
@@ -353,6 +598,20 @@ pub struct InstallCommand {
     /// CI/CD pipelines and automated scripts.
     #[arg(short = 'y', long)]
     pub yes: bool,
+
+    /// Environment overlay to merge (for programmatic use, not exposed as CLI arg)
+    ///
+    /// This field is populated from the global --env flag (or `AGPM_ENV`) via
+    /// execute_with_config and selects `agpm.<env>.toml`.
+    #[arg(skip)]
+    pub env: Option<String>,
+
+    /// Directory to remap tool base directories under (for programmatic use, not exposed as CLI arg)
+    ///
+    /// This field is populated from the global --install-root flag via
+    /// execute_with_config.
+    #[arg(skip)]
+    pub install_root: Option<std::path::PathBuf>,
 }
 
 impl Default for InstallCommand {
@@ -382,8 +641,10 @@ impl InstallCommand {
     #[allow(dead_code)] // Used by Default impl and in tests
     pub const fn new() -> Self {
         Self {
+            names: Vec::new(),
             no_lock: false,
             frozen: false,
+            verify: false,
             no_cache: false,
             max_parallel: None,
             quiet: false,
@@ -391,7 +652,21 @@ impl InstallCommand {
             verbose: false,
             no_transitive: false,
             dry_run: false,
+            explain: None,
+            resolution: ResolutionStrategy::Highest,
+            since: None,
+            format: DiffFormat::Text,
+            concurrency_report: false,
+            report_duplicates: false,
+            changed_only: false,
+            save_transitive: false,
+            prune_disabled: false,
+            prune: false,
+            symlink: false,
+            emit_checksums: false,
             yes: false,
+            env: None,
+            install_root: None,
         }
     }
 
@@ -412,8 +687,10 @@ impl InstallCommand {
     #[allow(dead_code)] // Used in integration tests for quiet mode testing
     pub const fn new_quiet() -> Self {
         Self {
+            names: Vec::new(),
             no_lock: false,
             frozen: false,
+            verify: false,
             no_cache: false,
             max_parallel: None,
             quiet: true,
@@ -421,7 +698,21 @@ impl InstallCommand {
             verbose: false,
             no_transitive: false,
             dry_run: false,
+            explain: None,
+            resolution: ResolutionStrategy::Highest,
+            since: None,
+            format: DiffFormat::Text,
+            concurrency_report: false,
+            report_duplicates: false,
+            changed_only: false,
+            save_transitive: false,
+            prune_disabled: false,
+            prune: false,
+            symlink: false,
+            emit_checksums: false,
             yes: false,
+            env: None,
+            install_root: None,
         }
     }
 
@@ -486,14 +777,37 @@ impl InstallCommand {
     /// [agents]
     /// my-agent = { source = "official", path = "agents/my-agent.md", version = "v1.0.0" }
     /// ```
-    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+    pub async fn execute_with_manifest_path(
+        mut self,
+        manifest_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Keep stdout to the JSON report so it can be piped to `jq`
+        if self.explain == Some(ExplainFormat::Json) {
+            self.quiet = true;
+            self.no_progress = true;
+        }
+
         // Find manifest file
-        let manifest_path = if let Ok(path) = find_manifest_with_optional(manifest_path) {
-            path
-        } else {
-            // Check if legacy CCPM files exist and offer interactive migration
-            match crate::cli::common::handle_legacy_ccpm_migration(None, self.yes).await {
+        let explicit_dir = manifest_path.as_deref().and_then(|path| {
+            let path = std::path::absolute(path).ok()?;
+            if path.is_dir() {
+                Some(path)
+            } else {
+                path.parent().map(Path::to_path_buf)
+            }
+        });
+        let manifest_path = match find_manifest_with_optional(manifest_path) {
+            Ok(path) => path,
+            // Check if legacy CCPM files exist and offer interactive migration, next to
+            // the requested manifest when --manifest-path was given
+            Err(not_found) => match crate::cli::common::handle_legacy_ccpm_migration(
+                explicit_dir.clone(),
+                self.yes,
+            )
+            .await
+            {
                 Ok(Some(path)) => path,
+                Ok(None) if explicit_dir.is_some() => return Err(not_found),
                 Ok(None) => {
                     return Err(anyhow::anyhow!(
                         "No agpm.toml found in current directory or any parent directory.\n\n\
@@ -505,7 +819,7 @@ impl InstallCommand {
                     ));
                 }
                 Err(e) => return Err(e),
-            }
+            },
         };
 
         self.execute_from_path(Some(&manifest_path)).await
@@ -527,7 +841,30 @@ impl InstallCommand {
             return Err(anyhow::anyhow!("No agpm.toml found at {}", manifest_path.display()));
         }
 
-        let (mut manifest, _patch_conflicts) = Manifest::load_with_private(&manifest_path)?;
+        if self.frozen && self.install_root.is_some() {
+            anyhow::bail!(
+                "--install-root cannot be combined with --frozen: the lockfile records \
+                 install paths, so they must be re-resolved under the new root.\n\n\
+                 Hint: Use --no-lock to install under the root without changing agpm.lock."
+            );
+        }
+
+        let (mut manifest, _patch_conflicts) =
+            Manifest::load_with_overlays(&manifest_path, self.env.as_deref())?;
+        manifest.install.prune_disabled = self.prune_disabled;
+        manifest.install_root = self.install_root.clone();
+        if self.symlink {
+            manifest.installer.install_mode = crate::manifest::InstallMode::Symlink;
+        }
+        manifest.installer.emit_checksums |= self.emit_checksums;
+
+        // Install workspace members first; the root itself may only be a list of members
+        if !manifest.workspace.is_default() {
+            self.install_workspace_members(&manifest, &manifest_path).await?;
+            if manifest.all_dependencies().is_empty() {
+                return Ok(());
+            }
+        }
 
         // Note: Private patches silently override project patches when they conflict.
         // This allows users to customize their local configuration without modifying
@@ -566,6 +903,13 @@ impl InstallCommand {
                 }
             }
         }
+        for name in &self.names {
+            if !manifest.all_resources().iter().any(|(_, dep_name, _)| dep_name == name) {
+                return Err(anyhow::anyhow!("Dependency '{name}' not found in manifest"));
+            }
+        }
+        let since_lockfile =
+            self.since.as_deref().map(crate::cli::common::load_since_lockfile).transpose()?;
         let total_deps = manifest.all_dependencies().len();
 
         // Initialize multi-phase progress for all progress tracking
@@ -576,6 +920,14 @@ impl InstallCommand {
         let actual_project_dir =
             manifest_path.parent().ok_or_else(|| anyhow::anyhow!("Invalid manifest path"))?;
 
+        // Serialize with other agpm processes in this project from reading the
+        // existing lockfile through resolution, installation and the lockfile write
+        let _project_lock = crate::installer::ProjectLock::acquire(
+            actual_project_dir,
+            crate::installer::PROJECT_LOCK_NAME,
+        )
+        .await?;
+
         // Check for existing lockfile
         let lockfile_path = actual_project_dir.join("agpm.lock");
 
@@ -598,6 +950,18 @@ impl InstallCommand {
             }
         };
 
+        if self.verify {
+            let lockfile = existing_lockfile
+                .ok_or_else(|| anyhow::anyhow!("No lockfile found. Run 'agpm install' first."))?;
+            return verify_installation(
+                &lockfile,
+                actual_project_dir,
+                manifest.installer.header || manifest.installer.footer,
+                manifest.integrity.normalize_eol,
+                self.quiet,
+            );
+        }
+
         // Check for legacy format migration (old paths → agpm/ subdirectory)
         // Only check if we have an existing lockfile (indicates prior installation)
         let existing_lockfile = if existing_lockfile.is_some() && !self.frozen {
@@ -609,6 +973,12 @@ impl InstallCommand {
                 command_context.reload_manifest()?;
                 // Update local manifest variable to use reloaded manifest
                 manifest = command_context.manifest.clone();
+                manifest.install.prune_disabled = self.prune_disabled;
+                manifest.install_root = self.install_root.clone();
+                if self.symlink {
+                    manifest.installer.install_mode = crate::manifest::InstallMode::Symlink;
+                }
+                manifest.installer.emit_checksums |= self.emit_checksums;
                 // Reload lockfile after migration since paths have changed
                 command_context.load_lockfile_with_regeneration(true, "install")?
             } else {
@@ -619,7 +989,12 @@ impl InstallCommand {
         };
 
         // Initialize cache (always needed now, even with --no-cache)
-        let cache = Cache::new()?;
+        let metrics = self.concurrency_report.then(|| Arc::new(CacheMetrics::default()));
+        let mut cache = Cache::new()?;
+        if let Some(metrics) = &metrics {
+            cache = cache.with_metrics(Arc::clone(metrics));
+        }
+        let cache = crate::cli::common::apply_path_overrides(cache, &manifest, self.quiet)?;
 
         // Calculate max concurrency (used for both resolution and installation)
         let max_concurrency = self.max_parallel.unwrap_or_else(|| {
@@ -640,6 +1015,10 @@ impl InstallCommand {
             Some(operation_context.clone()),
         )
         .await?;
+        if self.explain.is_some() {
+            resolver.set_explain(true);
+        }
+        resolver.set_resolution_strategy(self.resolution);
 
         // Pre-sync sources phase (if not frozen and we have remote deps)
         let has_remote_deps =
@@ -649,12 +1028,16 @@ impl InstallCommand {
         let current_manifest_hash = manifest.compute_dependency_hash();
         let has_mutable = manifest.has_mutable_dependencies();
 
-        let use_fast_path = can_use_fast_path(
-            existing_lockfile.as_ref(),
-            &current_manifest_hash,
-            has_mutable,
-            self.frozen,
-        );
+        // --explain needs a real resolution pass to have anything to report, and
+        // --resolution=lowest must not reuse versions chosen the other way
+        let use_fast_path = self.explain.is_none()
+            && self.resolution == ResolutionStrategy::Highest
+            && can_use_fast_path(
+                existing_lockfile.as_ref(),
+                &current_manifest_hash,
+                has_mutable,
+                self.frozen,
+            );
 
         // Skip pre-sync if using fast path (worktrees already exist from previous install)
         if !self.frozen && has_remote_deps && !use_fast_path {
@@ -719,6 +1102,18 @@ impl InstallCommand {
             resolver.resolve_with_options(!self.no_transitive, progress).await?
         };
 
+        if let Some(format) = self.explain {
+            if self.frozen {
+                eprintln!("Note: --explain has no effect with --frozen (lockfile used as-is)");
+            } else {
+                crate::cli::common::display_resolution_explanations(
+                    format,
+                    &manifest,
+                    &resolver.explanations(),
+                )?;
+            }
+        }
+
         // Store fast-path metadata in lockfile for next run's detection
         lockfile.manifest_hash = Some(current_manifest_hash);
         lockfile.has_mutable_deps = Some(has_mutable);
@@ -737,8 +1132,42 @@ impl InstallCommand {
             None
         };
 
+        // Named install: only the named dependencies and their transitive closure are
+        // taken from this resolution, everything else stays as previously installed
+        let subset_changes = if self.names.is_empty() {
+            None
+        } else {
+            let baseline = installation_baseline(
+                self.frozen,
+                &lockfile,
+                old_lockfile.as_ref(),
+                actual_project_dir,
+            );
+            let (subset, changes) =
+                crate::installer::merge_subset(&lockfile, baseline.as_ref(), &self.names);
+            lockfile = subset;
+            Some(changes)
+        };
+
+        // --prune: entries no manifest dependency reaches are not installed or saved
+        if self.prune {
+            let pruned = crate::cli::prune::prune_lockfile(&mut lockfile);
+            if !pruned.is_empty() {
+                lockfile.resource_count = Some(lockfile.all_resources().len());
+                if !self.quiet {
+                    println!("✓ Pruned {} unreachable lockfile entries", pruned.len());
+                }
+            }
+        }
+
         // Handle dry-run mode: show what would be installed without making changes
         if self.dry_run {
+            if let Some(since) = &since_lockfile {
+                crate::cli::common::display_lockfile_diff(since, &lockfile, self.format)?;
+            }
+            if let Some(metrics) = &metrics {
+                crate::cli::common::display_concurrency_report(metrics, self.format)?;
+            }
             return crate::cli::common::display_dry_run_results(
                 &lockfile,
                 old_lockfile.as_ref(),
@@ -746,10 +1175,29 @@ impl InstallCommand {
             );
         }
 
-        // Acquire resource lock for cross-process coordination during file writes
-        // Resolution has completed above (outside lock), now we serialize file operations
-        let _resource_lock =
-            crate::installer::ProjectLock::acquire(actual_project_dir, "resource").await?;
+        // --changed-only: find what differs from the previous installation. Without a
+        // previous lockfile there is nothing to compare against, so install everything.
+        let change_set = if self.changed_only {
+            installation_baseline(self.frozen, &lockfile, old_lockfile.as_ref(), actual_project_dir)
+                .map(|baseline| {
+                    let changes = crate::installer::detect_changed_resources(
+                        &lockfile,
+                        &baseline,
+                        actual_project_dir,
+                        manifest.installer.header || manifest.installer.footer,
+                        manifest.integrity.normalize_eol,
+                    );
+                    changes.carry_forward(&mut lockfile, &baseline);
+                    tracing::info!(
+                        "Changed-only: {} changed, {} unchanged",
+                        changes.changed.len(),
+                        changes.unchanged_count
+                    );
+                    changes
+                })
+        } else {
+            subset_changes
+        };
 
         let total_resources = ResourceIterator::count_total_resources(&lockfile);
 
@@ -769,6 +1217,7 @@ impl InstallCommand {
         // is that a subsequent tool invocation fails to find the file (easily fixed by
         // running `agpm install` again).
         let all_files_exist = use_fast_path
+            && change_set.is_none()
             && lockfile.all_resources().iter().all(|res| {
                 // Only check files that should be installed (install != false)
                 if res.install == Some(false) {
@@ -782,6 +1231,16 @@ impl InstallCommand {
 
         let installed_count = if total_resources == 0 {
             0
+        } else if change_set.as_ref().is_some_and(|changes| changes.changed.is_empty()) {
+            // Changed-only: nothing differs from the lockfile, skip installation entirely
+            if !self.quiet && !self.no_progress {
+                multi_phase.start_phase(
+                    InstallationPhase::Installing,
+                    Some(&format!("({total_resources} resources)")),
+                );
+                multi_phase.complete_phase(Some("All up to date"));
+            }
+            0
         } else if all_files_exist {
             // Ultra-fast path: all files exist, skip installation entirely
             if !self.quiet && !self.no_progress {
@@ -797,11 +1256,18 @@ impl InstallCommand {
             );
             0 // No files actually installed (they all exist)
         } else {
+            let (filter, install_total) = match &change_set {
+                Some(changes) => {
+                    (ResourceFilter::Changed(changes.changed.clone()), changes.changed.len())
+                }
+                None => (ResourceFilter::All, total_resources),
+            };
+
             // Start installation phase
             if !self.quiet && !self.no_progress {
                 multi_phase.start_phase(
                     InstallationPhase::Installing,
-                    Some(&format!("({total_resources} resources)")),
+                    Some(&format!("({install_total} resources)")),
                 );
             }
 
@@ -816,7 +1282,7 @@ impl InstallCommand {
                 manifest.token_warning_threshold.unwrap_or(global_config.token_warning_threshold);
 
             match install_resources(
-                ResourceFilter::All,
+                filter,
                 &lockfile_for_install,
                 &manifest,
                 actual_project_dir,
@@ -866,12 +1332,39 @@ impl InstallCommand {
                 old_lockfile.as_ref(),
                 self.quiet,
                 self.no_lock,
+                false,
+                change_set.as_ref(),
             )
             .await?;
 
             hook_count = hook_count_result;
             server_count = server_count_result;
 
+            if self.prune {
+                let pruned =
+                    crate::cli::prune::prune_project(&lockfile, &manifest, actual_project_dir)
+                        .await?;
+                if !pruned.is_empty() && !self.quiet {
+                    println!("✓ Pruned {} untracked artifact(s)", pruned.len());
+                    if self.verbose {
+                        for item in &pruned {
+                            println!("  - {item}");
+                        }
+                    }
+                }
+            }
+
+            if manifest.installer.emit_checksums {
+                let rehash = manifest.installer.header
+                    || manifest.installer.footer
+                    || manifest.integrity.normalize_eol;
+                let listed =
+                    crate::installer::write_checksums(&lockfile, actual_project_dir, rehash)?;
+                if !self.quiet && self.verbose {
+                    println!("✓ Wrote {} ({listed} files)", crate::installer::CHECKSUMS_FILE);
+                }
+            }
+
             // Complete finalizing phase
             if !self.quiet && !self.no_progress && installed_count > 0 {
                 multi_phase.complete_phase(Some("Installation finalized"));
@@ -883,6 +1376,55 @@ impl InstallCommand {
             return Err(error);
         }
 
+        if self.save_transitive {
+            use crate::manifest::promote::{
+                find_transitive_dependencies, write_promoted_dependencies,
+            };
+
+            // Promote against agpm.toml alone, without the private and env overlays
+            let project_manifest = Manifest::load(&manifest_path)?;
+            let promoted = find_transitive_dependencies(&project_manifest, &lockfile);
+            write_promoted_dependencies(&manifest_path, &promoted)?;
+            if !self.quiet {
+                if promoted.is_empty() {
+                    println!("No transitive dependencies to add to agpm.toml");
+                } else {
+                    println!(
+                        "✓ Added {} transitive {} to agpm.toml:",
+                        promoted.len(),
+                        if promoted.len() == 1 {
+                            "dependency"
+                        } else {
+                            "dependencies"
+                        }
+                    );
+                    for dep in &promoted {
+                        println!(
+                            "  [{}] {} = {}:{}@{}",
+                            dep.resource_type.to_plural(),
+                            dep.name,
+                            dep.source,
+                            dep.path,
+                            dep.version
+                        );
+                    }
+                }
+            }
+        }
+
+        // Remove worktrees unused for longer than cache.worktree_max_age_days.
+        // Everything this install needed was just marked as used, so it is kept.
+        if let Some(max_age) = crate::cache::retention::worktree_max_age() {
+            match cache.remove_stale_worktrees(max_age, false).await {
+                Ok(report) if !self.quiet && !report.removed.is_empty() => {
+                    println!();
+                    crate::cli::cache::print_gc_report(&report, false);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Failed to remove stale worktrees: {e:#}"),
+            }
+        }
+
         // Validate project configuration and offer to add missing gitignore entries
         if !self.quiet && installed_count > 0 {
             let validation =
@@ -918,6 +1460,74 @@ impl InstallCommand {
             );
         }
 
+        if let Some(since) = &since_lockfile {
+            crate::cli::common::display_lockfile_diff(since, &lockfile, self.format)?;
+        }
+
+        if self.report_duplicates {
+            crate::cli::common::display_duplicates(&lockfile);
+        }
+
+        if let Some(metrics) = &metrics {
+            crate::cli::common::display_concurrency_report(metrics, self.format)?;
+        }
+
+        Ok(())
+    }
+
+    /// Install every member of the root manifest's `[workspace]` and check that
+    /// members agree on the resources they share.
+    ///
+    /// Each member is installed like a standalone project, writing its own
+    /// lockfile; all of them resolve through the shared Git cache.
+    async fn install_workspace_members(
+        &self,
+        manifest: &crate::manifest::Manifest,
+        manifest_path: &Path,
+    ) -> Result<()> {
+        use crate::manifest::{Manifest, workspace};
+
+        if !self.names.is_empty() {
+            anyhow::bail!(
+                "Named installs are not supported for a workspace root.\n\n\
+                 Hint: Run 'agpm install <name>' in the member directory instead."
+            );
+        }
+
+        let root_dir = manifest_path.parent().unwrap_or_else(|| Path::new("."));
+        let member_manifests = manifest.workspace.member_manifests(root_dir)?;
+        let mut member_lockfiles = Vec::new();
+        for (member, member_manifest) in manifest.workspace.members.iter().zip(&member_manifests) {
+            if !Manifest::load(member_manifest)?.workspace.is_default() {
+                anyhow::bail!(
+                    "Workspace member '{}' cannot define its own [workspace]",
+                    member.display()
+                );
+            }
+            if !self.quiet {
+                println!("Installing workspace member {}", member.display());
+            }
+            Box::pin(self.execute_from_path(Some(member_manifest))).await.map_err(|e| {
+                e.context(format!("Failed to install workspace member '{}'", member.display()))
+            })?;
+
+            let lockfile_path = member_manifest.with_file_name("agpm.lock");
+            if lockfile_path.exists() {
+                member_lockfiles
+                    .push((member.display().to_string(), LockFile::load(&lockfile_path)?));
+            }
+        }
+
+        let conflicts = workspace::find_conflicts(&member_lockfiles);
+        if !conflicts.is_empty() {
+            let details: Vec<String> = conflicts.iter().map(|c| format!("  {c}")).collect();
+            anyhow::bail!(
+                "Workspace members resolve {} resource(s) differently:\n{}\n\n\
+                 Hint: Align the versions in the member manifests.",
+                conflicts.len(),
+                details.join("\n")
+            );
+        }
         Ok(())
     }
 }
@@ -987,7 +1597,7 @@ fn detect_tag_movement(old_lockfile: &LockFile, new_lockfile: &LockFile, quiet:
                 // Check if the same tag now points to a different commit
                 if old_version == new_version && old_commit != new_commit && !quiet {
                     eprintln!(
-                        "⚠️  Warning: Tag '{}' for {} '{}' has moved from {} to {}",
+                        "{WARNING}Warning: Tag '{}' for {} '{}' has moved from {} to {}",
                         new_version,
                         resource_type,
                         new_resource.display_name(),
@@ -1071,8 +1681,10 @@ mod tests {
         Manifest::new().save(&manifest_path).unwrap();
 
         let cmd = InstallCommand {
+            names: Vec::new(),
             no_lock: true,
             frozen: false,
+            verify: false,
             no_cache: false,
             max_parallel: None,
             quiet: false,
@@ -1080,7 +1692,21 @@ mod tests {
             verbose: false,
             no_transitive: false,
             dry_run: false,
+            explain: None,
+            resolution: ResolutionStrategy::Highest,
+            since: None,
+            format: DiffFormat::Text,
+            concurrency_report: false,
+            report_duplicates: false,
             yes: false,
+            env: None,
+            install_root: None,
+            changed_only: false,
+            save_transitive: false,
+            prune_disabled: false,
+            prune: false,
+            symlink: false,
+            emit_checksums: false,
         };
 
         cmd.execute_from_path(Some(&manifest_path)).await?;
@@ -1118,6 +1744,11 @@ This is a test agent.",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
         );
         manifest.save(&manifest_path)?;
@@ -1184,6 +1815,11 @@ Body",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
         );
         manifest.save(&manifest_path)?;
@@ -1211,6 +1847,7 @@ Body",
                 variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
                 is_private: false,
                 approximate_token_count: None,
+                deprecated: false,
             }],
             snippets: vec![],
             mcp_servers: vec![],
@@ -1224,8 +1861,10 @@ Body",
         .save(&lockfile_path)?;
 
         let cmd = InstallCommand {
+            names: Vec::new(),
             no_lock: false,
             frozen: true,
+            verify: false,
             no_cache: false,
             max_parallel: None,
             quiet: false,
@@ -1233,7 +1872,21 @@ Body",
             verbose: false,
             no_transitive: false,
             dry_run: false,
+            explain: None,
+            resolution: ResolutionStrategy::Highest,
+            since: None,
+            format: DiffFormat::Text,
+            concurrency_report: false,
+            report_duplicates: false,
             yes: false,
+            env: None,
+            install_root: None,
+            changed_only: false,
+            save_transitive: false,
+            prune_disabled: false,
+            prune: false,
+            symlink: false,
+            emit_checksums: false,
         };
 
         cmd.execute_from_path(Some(&manifest_path)).await?;
@@ -1265,6 +1918,11 @@ Body",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
         );
         manifest.save(&manifest_path)?;
@@ -1312,6 +1970,11 @@ Body",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
         );
         manifest.save(&manifest_path)?;
@@ -1356,6 +2019,11 @@ Body",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
         );
         manifest.save(&manifest_path)?;
@@ -1398,13 +2066,20 @@ Body",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
         );
         manifest.save(&manifest_path)?;
 
         let cmd = InstallCommand {
+            names: Vec::new(),
             no_lock: false,
             frozen: false,
+            verify: false,
             no_cache: false,
             max_parallel: None,
             quiet: true, // Suppress output in test
@@ -1413,6 +2088,20 @@ Body",
             no_transitive: false,
             dry_run: true,
             yes: false,
+            env: None,
+            install_root: None,
+            explain: None,
+            resolution: ResolutionStrategy::Highest,
+            since: None,
+            format: DiffFormat::Text,
+            concurrency_report: false,
+            report_duplicates: false,
+            changed_only: false,
+            save_transitive: false,
+            prune_disabled: false,
+            prune: false,
+            symlink: false,
+            emit_checksums: false,
         };
 
         // In dry-run mode, this should return an error indicating changes would be made
@@ -1460,6 +2149,11 @@ Body",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
         );
         manifest.add_mcp_server(
@@ -1480,6 +2174,11 @@ Body",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
         );
         manifest.save(&manifest_path)?;
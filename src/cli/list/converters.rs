@@ -17,5 +17,7 @@ pub fn lockentry_to_listitem(
         tool: Some(entry.tool.clone().unwrap_or_else(|| "claude-code".to_string())),
         applied_patches: entry.applied_patches.clone(),
         approximate_token_count: entry.approximate_token_count,
+        deprecated: entry.deprecated,
+        nearest_tag: None,
     }
 }
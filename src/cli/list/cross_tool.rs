@@ -0,0 +1,170 @@
+//! Cross-tool view for `agpm list --duplicates-across-tools`.
+//!
+//! With several tools enabled, the same resource can be declared more than
+//! once with a different `tool` each, for example an agent installed for both
+//! Claude Code and OpenCode. This module groups lockfile entries by where they
+//! come from (type, source, path and version) and lists the tools each one is
+//! installed into, so intentional and accidental duplicates are easy to tell
+//! apart.
+
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::lockfile::LockedResource;
+
+/// One installation of a resource for a tool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ToolInstall {
+    /// Tool the resource is installed for.
+    pub tool: String,
+    /// Display name of the lockfile entry.
+    pub name: String,
+    /// Installation path, relative to the project directory.
+    pub installed_at: String,
+}
+
+/// A resource origin and every tool it is installed into.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OriginGroup {
+    /// Resource type.
+    pub resource_type: String,
+    /// Source name, or `None` for local resources.
+    pub source: Option<String>,
+    /// Path within the source.
+    pub path: String,
+    /// Requested version, if any.
+    pub version: Option<String>,
+    /// Installations, sorted by tool.
+    pub tools: Vec<ToolInstall>,
+    /// Whether the resource is installed into more than one tool.
+    pub multi_tool: bool,
+}
+
+/// Group `entries` by type, source, path and version.
+///
+/// Groups are sorted by type, then source and path.
+pub fn group_by_origin<'a>(
+    entries: impl IntoIterator<Item = &'a LockedResource>,
+) -> Vec<OriginGroup> {
+    let mut groups: BTreeMap<_, Vec<ToolInstall>> = BTreeMap::new();
+    for entry in entries {
+        let key = (
+            entry.resource_type.to_string(),
+            entry.source.clone(),
+            entry.path.clone(),
+            entry.version.clone(),
+        );
+        groups.entry(key).or_default().push(ToolInstall {
+            tool: entry.tool.as_deref().unwrap_or("claude-code").to_string(),
+            name: entry.display_name().to_string(),
+            installed_at: entry.installed_at.clone(),
+        });
+    }
+
+    groups
+        .into_iter()
+        .map(|((resource_type, source, path, version), mut tools)| {
+            tools.sort_by(|a, b| a.tool.cmp(&b.tool).then_with(|| a.name.cmp(&b.name)));
+            let multi_tool = tools.windows(2).any(|pair| pair[0].tool != pair[1].tool);
+            OriginGroup {
+                resource_type,
+                source,
+                path,
+                version,
+                tools,
+                multi_tool,
+            }
+        })
+        .collect()
+}
+
+/// Print the cross-tool view in the requested format.
+pub fn output(groups: &[OriginGroup], format: &str) -> Result<()> {
+    let multi_tool = groups.iter().filter(|g| g.multi_tool).count();
+
+    if format == "json" {
+        let json = serde_json::json!({
+            "resources": groups,
+            "summary": {
+                "resources": groups.len(),
+                "multi_tool": multi_tool,
+            },
+        });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if groups.is_empty() {
+        println!("No installed resources found.");
+        return Ok(());
+    }
+
+    println!("{}", "Installed resources by tool:".bold());
+    println!();
+    for group in groups {
+        let origin = match (&group.source, &group.version) {
+            (Some(source), Some(version)) => format!("{source}:{}@{version}", group.path),
+            (Some(source), None) => format!("{source}:{}", group.path),
+            (None, _) => group.path.clone(),
+        };
+        let marker = if group.multi_tool {
+            format!("  {}", "multi-tool".yellow())
+        } else {
+            String::new()
+        };
+        println!("  {} {}{marker}", group.resource_type, origin.bold());
+        for install in &group.tools {
+            println!(
+                "    {:<12} {}  {}",
+                install.tool,
+                install.name,
+                install.installed_at.dimmed()
+            );
+        }
+    }
+    println!();
+    println!("{} resources, {multi_tool} installed into more than one tool", groups.len());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ResourceType;
+    use crate::lockfile::LockedResourceBuilder;
+
+    fn agent(name: &str, tool: &str, installed_at: &str) -> LockedResource {
+        LockedResourceBuilder::new(
+            name.to_string(),
+            "agents/reviewer.md".to_string(),
+            String::new(),
+            installed_at.to_string(),
+            ResourceType::Agent,
+        )
+        .source(Some("community".to_string()))
+        .version(Some("v1.0.0".to_string()))
+        .tool(Some(tool.to_string()))
+        .build()
+    }
+
+    #[test]
+    fn test_group_by_origin_flags_multi_tool_installs() {
+        let entries = [
+            agent("reviewer-oc", "opencode", ".opencode/agent/agpm/reviewer.md"),
+            agent("reviewer", "claude-code", ".claude/agents/agpm/reviewer.md"),
+        ];
+        let mut other = agent("reviewer", "claude-code", ".claude/agents/agpm/v2/reviewer.md");
+        other.version = Some("v2.0.0".to_string());
+
+        let groups = group_by_origin(entries.iter().chain([&other]));
+        assert_eq!(groups.len(), 2);
+        assert!(groups[0].multi_tool);
+        let tools: Vec<&str> = groups[0].tools.iter().map(|t| t.tool.as_str()).collect();
+        assert_eq!(tools, vec!["claude-code", "opencode"]);
+        assert_eq!(groups[1].version.as_deref(), Some("v2.0.0"));
+        assert!(!groups[1].multi_tool);
+    }
+}
@@ -0,0 +1,60 @@
+//! Nearest-tag annotations for commit-pinned entries.
+//!
+//! Entries pinned with `rev` show a bare commit SHA as their version. When the
+//! source repository is already in the cache, the commit is described relative
+//! to its closest tag (`abc1234 (v1.2.0+3)`). Nothing is fetched: entries whose
+//! repository or commit is not available locally are shown unchanged.
+
+use std::path::PathBuf;
+use tracing::debug;
+
+use super::formatters::ListItem;
+use crate::cache::Cache;
+use crate::git::GitRepo;
+use crate::lockfile::LockFile;
+
+/// Attach the nearest tag to every commit-pinned item whose commit is cached.
+pub(super) async fn annotate_nearest_tags(items: &mut [ListItem], lockfile: &LockFile) {
+    if !items.iter().any(is_commit_pinned) {
+        return;
+    }
+    let cache = match Cache::new() {
+        Ok(cache) => cache,
+        Err(e) => {
+            debug!("Skipping nearest-tag lookup, cache unavailable: {e:#}");
+            return;
+        }
+    };
+
+    for item in items.iter_mut().filter(|item| is_commit_pinned(item)) {
+        let (Some(source), Some(commit)) = (&item.source, &item.resolved_commit) else {
+            continue;
+        };
+        let Some(url) = lockfile.sources.iter().find(|s| s.name == *source).map(|s| &s.url) else {
+            continue;
+        };
+        let repo_path = if crate::utils::is_local_path(url) {
+            PathBuf::from(url)
+        } else {
+            cache.bare_repo_path(url)
+        };
+        if !repo_path.exists() {
+            continue;
+        }
+
+        match GitRepo::new(&repo_path).describe_nearest_tag(commit).await {
+            Ok(nearest) => item.nearest_tag = Some(nearest),
+            Err(e) => debug!("No nearest tag for '{}': {e:#}", item.name),
+        }
+    }
+}
+
+/// Whether the item's version is (a prefix of) its resolved commit SHA.
+fn is_commit_pinned(item: &ListItem) -> bool {
+    let (Some(version), Some(commit)) = (&item.version, &item.resolved_commit) else {
+        return false;
+    };
+    version.len() >= 7
+        && version.chars().all(|c| c.is_ascii_hexdigit())
+        && commit.starts_with(&version.to_ascii_lowercase())
+}
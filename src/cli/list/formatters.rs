@@ -3,6 +3,7 @@ use colored::Colorize;
 use std::collections::{BTreeMap, HashMap};
 
 use crate::cache::Cache;
+use crate::git::NearestTag;
 use crate::lockfile::LockFile;
 use crate::lockfile::patch_display::extract_patch_displays;
 
@@ -60,6 +61,41 @@ pub struct ListItem {
     pub applied_patches: std::collections::BTreeMap<String, toml::Value>,
     /// Approximate token count of the installed resource content
     pub approximate_token_count: Option<u64>,
+    /// Whether the resource is marked deprecated in its frontmatter
+    pub deprecated: bool,
+    /// Closest tag to a commit-pinned version, when the commit is cached
+    pub nearest_tag: Option<NearestTag>,
+}
+
+impl ListItem {
+    /// Whether the version is a raw ref (`ref:...`) that can be force-pushed
+    fn is_raw_ref(&self) -> bool {
+        self.version.as_deref().is_some_and(crate::git::raw_ref::is_raw_ref)
+    }
+
+    /// Name with `(patched)`, `(deprecated)` and `(mutable ref)` indicators for table output
+    fn display_name(&self) -> String {
+        let mut name = self.name.clone();
+        if !self.applied_patches.is_empty() {
+            name.push_str(" (patched)");
+        }
+        if self.deprecated {
+            name.push_str(" (deprecated)");
+        }
+        if self.is_raw_ref() {
+            name.push_str(" (mutable ref)");
+        }
+        name
+    }
+
+    /// Version with the nearest tag of a commit pin, e.g. `abc1234 (v1.2.0+3)`
+    fn display_version(&self) -> String {
+        let version = self.version.as_deref().unwrap_or("latest");
+        match &self.nearest_tag {
+            Some(nearest) => format!("{version} ({nearest})"),
+            None => version.to_string(),
+        }
+    }
 }
 
 /// Output items in the specified format
@@ -67,6 +103,8 @@ pub fn output_items(items: &[ListItem], config: &OutputConfig) -> Result<()> {
     if items.is_empty() {
         if config.format == "json" {
             println!("{{}}");
+        } else if config.format == "parseable" {
+            // No resources, no lines
         } else {
             println!("No installed resources found.");
         }
@@ -78,6 +116,7 @@ pub fn output_items(items: &[ListItem], config: &OutputConfig) -> Result<()> {
         "yaml" => output_yaml(items)?,
         "compact" => output_compact(items),
         "simple" => output_simple(items),
+        "parseable" => output_parseable(items),
         _ => output_table(items, config),
     }
 
@@ -164,6 +203,16 @@ fn output_json(items: &[ListItem]) -> Result<()> {
             if let Some(token_count) = item.approximate_token_count {
                 obj["approximate_token_count"] = serde_json::Value::Number(token_count.into());
             }
+            if item.deprecated {
+                obj["deprecated"] = serde_json::Value::Bool(true);
+            }
+            if item.is_raw_ref() {
+                obj["mutable_ref"] = serde_json::Value::Bool(true);
+            }
+            if let Some(ref nearest) = item.nearest_tag {
+                obj["nearest_tag"] = serde_json::Value::String(nearest.tag.clone());
+                obj["commits_since_tag"] = serde_json::Value::Number(nearest.distance.into());
+            }
 
             obj
         })
@@ -209,6 +258,22 @@ fn output_yaml(items: &[ListItem]) -> Result<()> {
                     serde_yaml::Value::Number(token_count.into()),
                 );
             }
+            if item.deprecated {
+                obj.insert("deprecated".to_string(), serde_yaml::Value::Bool(true));
+            }
+            if item.is_raw_ref() {
+                obj.insert("mutable_ref".to_string(), serde_yaml::Value::Bool(true));
+            }
+            if let Some(ref nearest) = item.nearest_tag {
+                obj.insert(
+                    "nearest_tag".to_string(),
+                    serde_yaml::Value::String(nearest.tag.clone()),
+                );
+                obj.insert(
+                    "commits_since_tag".to_string(),
+                    serde_yaml::Value::Number(nearest.distance.into()),
+                );
+            }
 
             obj
         })
@@ -222,8 +287,7 @@ fn output_yaml(items: &[ListItem]) -> Result<()> {
 fn output_compact(items: &[ListItem]) {
     for item in items {
         let source = item.source.as_deref().unwrap_or("local");
-        let version = item.version.as_deref().unwrap_or("latest");
-        println!("{} {} {}", item.name, version, source);
+        println!("{} {} {}", item.name, item.display_version(), source);
     }
 }
 
@@ -234,6 +298,31 @@ fn output_simple(items: &[ListItem]) {
     }
 }
 
+/// Output in parseable format: `type\tname\tsource\tversion\tpath` per line
+///
+/// The column layout is a stable interface for scripts; do not reorder it.
+fn output_parseable(items: &[ListItem]) {
+    for item in items {
+        println!("{}", parseable_line(item));
+    }
+}
+
+/// One `--parseable` line, with tabs and newlines inside fields replaced by spaces
+pub(super) fn parseable_line(item: &ListItem) -> String {
+    let path = item.installed_at.as_deref().or(item.path.as_deref());
+    [
+        Some(item.resource_type.as_str()),
+        Some(item.name.as_str()),
+        item.source.as_deref(),
+        item.version.as_deref(),
+        path,
+    ]
+    .into_iter()
+    .map(|field| field.unwrap_or_default().replace(['\t', '\n', '\r'], " "))
+    .collect::<Vec<_>>()
+    .join("\t")
+}
+
 /// Column widths for table formatting
 struct ColumnWidths {
     name: usize,
@@ -246,24 +335,8 @@ struct ColumnWidths {
 impl ColumnWidths {
     fn calculate(items: &[ListItem]) -> Self {
         Self {
-            name: items
-                .iter()
-                .map(|i| {
-                    if i.applied_patches.is_empty() {
-                        i.name.len()
-                    } else {
-                        i.name.len() + 10 // " (patched)" suffix
-                    }
-                })
-                .max()
-                .unwrap_or(4)
-                .max(4), // "Name" header
-            version: items
-                .iter()
-                .map(|i| i.version.as_deref().unwrap_or("latest").len())
-                .max()
-                .unwrap_or(7)
-                .max(7), // "Version" header
+            name: items.iter().map(|i| i.display_name().len()).max().unwrap_or(4).max(4), // "Name" header
+            version: items.iter().map(|i| i.display_version().len()).max().unwrap_or(7).max(7), // "Version" header
             source: items
                 .iter()
                 .map(|i| i.source.as_deref().unwrap_or("local").len())
@@ -355,7 +428,7 @@ fn output_table(items: &[ListItem], config: &OutputConfig) {
 /// Print a single item in detailed mode with patch comparison
 async fn print_item_detailed(item: &ListItem, lockfile: &LockFile, cache: Option<&Cache>) {
     let source = item.source.as_deref().unwrap_or("local");
-    let version = item.version.as_deref().unwrap_or("latest");
+    let version = item.display_version();
 
     println!("    {}", item.name.bright_white());
     println!("      Source: {}", source.bright_black());
@@ -373,6 +446,12 @@ async fn print_item_detailed(item: &ListItem, lockfile: &LockFile, cache: Option
         let formatted = crate::tokens::format_token_count(token_count as usize);
         println!("      Tokens: ~{}", formatted.bright_black());
     }
+    if item.deprecated {
+        println!("      Deprecated: {}", "yes".yellow());
+    }
+    if item.is_raw_ref() {
+        println!("      Mutable ref: {}", "yes (can change if force-pushed)".yellow());
+    }
 
     // Show patches with original → overridden comparison
     if !item.applied_patches.is_empty() {
@@ -442,15 +521,11 @@ fn find_locked_resource<'a>(
 /// Print a single item in table format with dynamic column widths
 fn print_item_with_widths(item: &ListItem, widths: &ColumnWidths) {
     let source = item.source.as_deref().unwrap_or("local");
-    let version = item.version.as_deref().unwrap_or("latest");
+    let version = item.display_version();
     let tool = item.tool.as_deref().unwrap_or("claude-code");
 
     // Build the name field with proper padding before adding colors
-    let name_with_indicator = if !item.applied_patches.is_empty() {
-        format!("{} (patched)", item.name)
-    } else {
-        item.name.clone()
-    };
+    let name_with_indicator = item.display_name();
 
     // Apply padding to plain text, then colorize
     let name_field = format!("{:<width$}", name_with_indicator, width = widths.name);
@@ -472,15 +547,11 @@ fn print_item_with_widths(item: &ListItem, widths: &ColumnWidths) {
 /// Print a single item
 fn print_item(item: &ListItem, format: &str, files: bool, detailed: bool) {
     let source = item.source.as_deref().unwrap_or("local");
-    let version = item.version.as_deref().unwrap_or("latest");
+    let version = item.display_version();
 
     if format == "table" && !files && !detailed {
         // Table format with fixed width (fallback, prefer print_item_with_width)
-        let name_with_indicator = if !item.applied_patches.is_empty() {
-            format!("{} (patched)", item.name)
-        } else {
-            item.name.clone()
-        };
+        let name_with_indicator = item.display_name();
 
         let name_field = format!("{:<32}", name_with_indicator);
         let colored_name = name_field.bright_white();
@@ -516,6 +587,12 @@ fn print_item(item: &ListItem, format: &str, files: bool, detailed: bool) {
             let formatted = crate::tokens::format_token_count(token_count as usize);
             println!("      Tokens: ~{}", formatted.bright_black());
         }
+        if item.deprecated {
+            println!("      Deprecated: {}", "yes".yellow());
+        }
+        if item.is_raw_ref() {
+            println!("      Mutable ref: {}", "yes (can change if force-pushed)".yellow());
+        }
         if !item.applied_patches.is_empty() {
             println!("      {}", "Patches:".cyan());
             let mut patch_keys: Vec<_> = item.applied_patches.keys().collect();
@@ -0,0 +1,374 @@
+//! Filesystem reconciliation for `agpm list --installed`.
+//!
+//! The regular `list` output only reflects the lockfile. This module compares
+//! it against the files on disk: every installable lockfile entry is reported
+//! as `tracked` when its file exists or `missing` when it does not, and every
+//! file found in a managed install directory (for example
+//! `.claude/agents/agpm`) without a lockfile entry is reported as `untracked`.
+//!
+//! Managed directories come from the resource paths of each enabled tool in
+//! the manifest's `[tools]` configuration. Skills are directories, so only the
+//! skill directory itself is matched, not the files inside it. Hooks and MCP
+//! servers are merged into shared config files and are not reported.
+
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use crate::core::ResourceType;
+use crate::lockfile::LockFile;
+use crate::manifest::Manifest;
+use crate::utils::normalize_path_for_storage;
+
+/// How a path relates to the lockfile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallStatus {
+    /// Recorded in the lockfile and present on disk.
+    Tracked,
+    /// Present in a managed directory but not recorded in the lockfile.
+    Untracked,
+    /// Recorded in the lockfile but absent from disk.
+    Missing,
+}
+
+impl InstallStatus {
+    fn label(self) -> colored::ColoredString {
+        match self {
+            Self::Tracked => "tracked".green(),
+            Self::Untracked => "untracked".yellow(),
+            Self::Missing => "missing".red(),
+        }
+    }
+}
+
+/// A single path in the reconciliation view.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InstalledEntry {
+    /// Path relative to the project directory, with forward slashes.
+    pub path: String,
+    /// Reconciliation status of the path.
+    pub status: InstallStatus,
+    /// Resource type, inferred from the managed directory for untracked paths.
+    pub resource_type: String,
+    /// Display name of the lockfile entry (tracked and missing paths only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Tool the resource is installed for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+}
+
+impl InstalledEntry {
+    fn annotation(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{} ({} {})", self.status.label(), self.resource_type, name),
+            None => format!("{} ({})", self.status.label(), self.resource_type),
+        }
+    }
+}
+
+/// Reconcile `lockfile` with the files under `project_dir`.
+///
+/// Only resource types accepted by `include` are reported. The result is
+/// sorted by path.
+pub fn reconcile(
+    lockfile: &LockFile,
+    manifest: &Manifest,
+    project_dir: &Path,
+    include: impl Fn(ResourceType) -> bool,
+) -> Vec<InstalledEntry> {
+    let mut entries = Vec::new();
+    let mut known = HashSet::new();
+
+    for resource_type in ResourceType::all() {
+        if !include(*resource_type) || is_merged(*resource_type) {
+            continue;
+        }
+        for entry in lockfile.get_resources(resource_type) {
+            if !entry.install.unwrap_or(true) || entry.installed_at.is_empty() {
+                continue;
+            }
+            let path = normalize_path_for_storage(&entry.installed_at);
+            let full_path = project_dir.join(&entry.installed_at);
+            let exists = if *resource_type == ResourceType::Skill {
+                full_path.is_dir()
+            } else {
+                full_path.is_file()
+            };
+            if !known.insert(path.clone()) {
+                continue;
+            }
+            entries.push(InstalledEntry {
+                path,
+                status: if exists {
+                    InstallStatus::Tracked
+                } else {
+                    InstallStatus::Missing
+                },
+                resource_type: resource_type.to_string(),
+                name: Some(entry.display_name().to_string()),
+                tool: entry.tool.clone(),
+            });
+        }
+    }
+
+    let mut tools: Vec<_> = manifest.get_tools_config().types.iter().collect();
+    tools.sort_by(|a, b| a.0.cmp(b.0));
+    for (tool, config) in tools {
+        if !config.enabled {
+            continue;
+        }
+        for resource_type in ResourceType::all() {
+            if !include(*resource_type) || is_merged(*resource_type) {
+                continue;
+            }
+            let Some(dir) = manifest.get_artifact_resource_path(tool, *resource_type) else {
+                continue;
+            };
+            for path in scan_managed_dir(project_dir, &dir, *resource_type) {
+                if known.insert(path.clone()) {
+                    entries.push(InstalledEntry {
+                        path,
+                        status: InstallStatus::Untracked,
+                        resource_type: resource_type.to_string(),
+                        name: None,
+                        tool: Some(tool.clone()),
+                    });
+                }
+            }
+        }
+    }
+
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    entries
+}
+
+/// Hooks and MCP servers are merged into config files instead of installed.
+fn is_merged(resource_type: ResourceType) -> bool {
+    matches!(resource_type, ResourceType::Hook | ResourceType::McpServer)
+}
+
+/// List the installable paths under a managed directory, relative to the project.
+///
+/// Skills are the immediate subdirectories; all other types are the files
+/// anywhere below `dir`. Hidden files and directories are ignored.
+fn scan_managed_dir(project_dir: &Path, dir: &Path, resource_type: ResourceType) -> Vec<String> {
+    let root = project_dir.join(dir);
+    if !root.is_dir() {
+        return Vec::new();
+    }
+
+    let max_depth = if resource_type == ResourceType::Skill {
+        1
+    } else {
+        usize::MAX
+    };
+
+    walkdir::WalkDir::new(&root)
+        .min_depth(1)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(std::result::Result::ok)
+        .filter(|e| {
+            if resource_type == ResourceType::Skill {
+                e.file_type().is_dir()
+            } else {
+                e.file_type().is_file()
+            }
+        })
+        .filter_map(|e| e.path().strip_prefix(project_dir).ok().map(normalize_path_for_storage))
+        .collect()
+}
+
+/// Render entries as a directory tree rooted at the project directory.
+#[must_use]
+pub fn render_tree(entries: &[InstalledEntry]) -> Vec<String> {
+    #[derive(Default)]
+    struct Node<'a> {
+        children: BTreeMap<&'a str, Node<'a>>,
+        entry: Option<&'a InstalledEntry>,
+    }
+
+    fn render(node: &Node<'_>, prefix: &str, lines: &mut Vec<String>) {
+        let count = node.children.len();
+        for (i, (name, child)) in node.children.iter().enumerate() {
+            let is_last = i + 1 == count;
+            let connector = if is_last {
+                "└── "
+            } else {
+                "├── "
+            };
+            let line = match child.entry {
+                Some(entry) => format!("{prefix}{connector}{name}  {}", entry.annotation()),
+                None => format!("{prefix}{connector}{}", format!("{name}/").blue()),
+            };
+            lines.push(line);
+            let child_prefix = format!(
+                "{prefix}{}",
+                if is_last {
+                    "    "
+                } else {
+                    "│   "
+                }
+            );
+            render(child, &child_prefix, lines);
+        }
+    }
+
+    let mut root = Node::default();
+    for entry in entries {
+        let mut node = &mut root;
+        for component in entry.path.split('/').filter(|c| !c.is_empty()) {
+            node = node.children.entry(component).or_default();
+        }
+        node.entry = Some(entry);
+    }
+
+    // Top-level directories (e.g. `.claude`) are printed without connectors
+    let mut lines = Vec::new();
+    for (name, node) in &root.children {
+        match node.entry {
+            Some(entry) => lines.push(format!("{name}  {}", entry.annotation())),
+            None => lines.push(format!("{}", format!("{name}/").blue())),
+        }
+        render(node, "", &mut lines);
+    }
+    lines
+}
+
+/// Print the reconciliation view in the requested format.
+pub fn output(entries: &[InstalledEntry], format: &str, tree: bool) -> Result<()> {
+    let count = |status| entries.iter().filter(|e| e.status == status).count();
+    let (tracked, untracked, missing) = (
+        count(InstallStatus::Tracked),
+        count(InstallStatus::Untracked),
+        count(InstallStatus::Missing),
+    );
+
+    if format == "json" {
+        let json = serde_json::json!({
+            "resources": entries,
+            "summary": {
+                "tracked": tracked,
+                "untracked": untracked,
+                "missing": missing,
+            },
+        });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No installed resources found.");
+        return Ok(());
+    }
+
+    println!("{}", "Installed resources on disk:".bold());
+    println!();
+    if tree {
+        for line in render_tree(entries) {
+            println!("{line}");
+        }
+    } else {
+        for entry in entries {
+            println!("  {}  {}", entry.path, entry.annotation());
+        }
+    }
+    println!();
+    println!("{tracked} tracked, {untracked} untracked, {missing} missing");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::LockedResourceBuilder;
+    use tempfile::TempDir;
+
+    fn locked(
+        name: &str,
+        installed_at: &str,
+        resource_type: ResourceType,
+    ) -> crate::lockfile::LockedResource {
+        LockedResourceBuilder::new(
+            name.to_string(),
+            format!("{name}.md"),
+            String::new(),
+            installed_at.to_string(),
+            resource_type,
+        )
+        .tool(Some("claude-code".to_string()))
+        .build()
+    }
+
+    #[test]
+    fn test_reconcile_classifies_paths() {
+        let temp = TempDir::new().unwrap();
+        let agents = temp.path().join(".claude/agents/agpm");
+        std::fs::create_dir_all(agents.join("nested")).unwrap();
+        std::fs::write(agents.join("reviewer.md"), "# Reviewer").unwrap();
+        std::fs::write(agents.join("nested/stray.md"), "# Stray").unwrap();
+        std::fs::write(agents.join(".DS_Store"), "").unwrap();
+        let skills = temp.path().join(".claude/skills/agpm");
+        std::fs::create_dir_all(skills.join("pdf")).unwrap();
+        std::fs::write(skills.join("pdf/SKILL.md"), "---\nname: pdf\n---\n").unwrap();
+        std::fs::create_dir_all(skills.join("orphan")).unwrap();
+
+        let lockfile = LockFile {
+            agents: vec![
+                locked("reviewer", ".claude/agents/agpm/reviewer.md", ResourceType::Agent),
+                locked("gone", ".claude/agents/agpm/gone.md", ResourceType::Agent),
+            ],
+            skills: vec![locked("pdf", ".claude/skills/agpm/pdf", ResourceType::Skill)],
+            ..Default::default()
+        };
+
+        let entries = reconcile(&lockfile, &Manifest::new(), temp.path(), |_| true);
+        let summary: Vec<(&str, InstallStatus)> =
+            entries.iter().map(|e| (e.path.as_str(), e.status)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (".claude/agents/agpm/gone.md", InstallStatus::Missing),
+                (".claude/agents/agpm/nested/stray.md", InstallStatus::Untracked),
+                (".claude/agents/agpm/reviewer.md", InstallStatus::Tracked),
+                (".claude/skills/agpm/orphan", InstallStatus::Untracked),
+                (".claude/skills/agpm/pdf", InstallStatus::Tracked),
+            ]
+        );
+        assert_eq!(entries[1].resource_type, "agent");
+        assert_eq!(entries[3].resource_type, "skill");
+
+        let agents_only =
+            reconcile(&lockfile, &Manifest::new(), temp.path(), |t| t == ResourceType::Agent);
+        assert_eq!(agents_only.len(), 3);
+    }
+
+    #[test]
+    fn test_render_tree() {
+        let entry = |path: &str, status| InstalledEntry {
+            path: path.to_string(),
+            status,
+            resource_type: "agent".to_string(),
+            name: None,
+            tool: None,
+        };
+        let entries = vec![
+            entry(".claude/agents/agpm/a.md", InstallStatus::Tracked),
+            entry(".claude/agents/agpm/b.md", InstallStatus::Missing),
+        ];
+
+        let lines = render_tree(&entries);
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].contains(".claude/"));
+        assert!(lines[1].starts_with("└── ") && lines[1].contains("agents/"));
+        assert!(lines[2].starts_with("    └── ") && lines[2].contains("agpm/"));
+        assert!(lines[3].starts_with("        ├── a.md  ") && lines[3].contains("tracked"));
+        assert!(lines[4].starts_with("        └── b.md  ") && lines[4].contains("missing"));
+    }
+}
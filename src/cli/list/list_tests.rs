@@ -21,6 +21,11 @@ fn create_default_command() -> ListCommand {
         files: false,
         verbose: false,
         sort: None,
+        installed: false,
+        tree: false,
+        parseable: false,
+        duplicates_across_tools: false,
+        env: None,
     }
 }
 
@@ -54,6 +59,11 @@ fn create_test_manifest() -> crate::manifest::Manifest {
             install: None,
 
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
     );
 
@@ -81,6 +91,11 @@ fn create_test_manifest() -> crate::manifest::Manifest {
             install: None,
 
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
     );
 
@@ -129,6 +144,7 @@ fn create_test_lockfile() -> crate::lockfile::LockFile {
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     });
 
     lockfile.agents.push(LockedResource {
@@ -151,6 +167,7 @@ fn create_test_lockfile() -> crate::lockfile::LockFile {
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     });
 
     // Add snippets
@@ -174,6 +191,7 @@ fn create_test_lockfile() -> crate::lockfile::LockFile {
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     });
 
     lockfile
@@ -347,6 +365,25 @@ fn test_validate_arguments_invalid_sort() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_validate_arguments_installed_format() -> Result<()> {
+    let cmd = ListCommand {
+        installed: true,
+        format: "json".to_string(),
+        ..create_default_command()
+    };
+    cmd.validate_arguments()?;
+
+    let cmd = ListCommand {
+        installed: true,
+        format: "yaml".to_string(),
+        ..create_default_command()
+    };
+    let result = cmd.validate_arguments();
+    assert!(result.unwrap_err().to_string().contains("for --installed"));
+    Ok(())
+}
+
 #[test]
 fn test_should_show_agents() -> Result<()> {
     // Show agents when no specific type filter
@@ -517,6 +554,11 @@ fn test_matches_filters_source() -> Result<()> {
         install: None,
 
         template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+        sha256: None,
+        on_conflict: None,
+        preserve_structure: None,
+        channel: None,
+        priority: None,
     }));
 
     let dep_with_different_source = ResourceDependency::Detailed(Box::new(DetailedDependency {
@@ -535,6 +577,11 @@ fn test_matches_filters_source() -> Result<()> {
         install: None,
 
         template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+        sha256: None,
+        on_conflict: None,
+        preserve_structure: None,
+        channel: None,
+        priority: None,
     }));
 
     let dep_without_source = ResourceDependency::Simple("local/file.md".to_string());
@@ -585,6 +632,7 @@ fn test_matches_lockfile_filters_source() -> Result<()> {
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     };
 
     let entry_with_different_source = LockedResource {
@@ -607,6 +655,7 @@ fn test_matches_lockfile_filters_source() -> Result<()> {
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     };
 
     let entry_without_source = LockedResource {
@@ -629,6 +678,7 @@ fn test_matches_lockfile_filters_source() -> Result<()> {
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     };
 
     assert!(cmd.matches_lockfile_filters("test", &entry_with_source, "agent"));
@@ -664,6 +714,7 @@ fn test_matches_lockfile_filters_search() -> Result<()> {
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     };
 
     assert!(cmd.matches_lockfile_filters("code-reviewer", &entry, "agent"));
@@ -692,6 +743,8 @@ fn test_sort_items() -> Result<()> {
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
             approximate_token_count: None,
+            deprecated: false,
+            nearest_tag: None,
         },
         ListItem {
             name: "alpha".to_string(),
@@ -705,6 +758,8 @@ fn test_sort_items() -> Result<()> {
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
             approximate_token_count: None,
+            deprecated: false,
+            nearest_tag: None,
         },
     ];
 
@@ -734,6 +789,8 @@ fn test_sort_items_by_version() -> Result<()> {
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
             approximate_token_count: None,
+            deprecated: false,
+            nearest_tag: None,
         },
         ListItem {
             name: "test2".to_string(),
@@ -747,6 +804,8 @@ fn test_sort_items_by_version() -> Result<()> {
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
             approximate_token_count: None,
+            deprecated: false,
+            nearest_tag: None,
         },
     ];
 
@@ -776,6 +835,8 @@ fn test_sort_items_by_source() -> Result<()> {
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
             approximate_token_count: None,
+            deprecated: false,
+            nearest_tag: None,
         },
         ListItem {
             name: "test2".to_string(),
@@ -789,6 +850,8 @@ fn test_sort_items_by_source() -> Result<()> {
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
             approximate_token_count: None,
+            deprecated: false,
+            nearest_tag: None,
         },
         ListItem {
             name: "test3".to_string(),
@@ -802,6 +865,8 @@ fn test_sort_items_by_source() -> Result<()> {
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
             approximate_token_count: None,
+            deprecated: false,
+            nearest_tag: None,
         },
     ];
 
@@ -832,6 +897,8 @@ fn test_sort_items_by_type() -> Result<()> {
             tool: Some("agpm".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
             approximate_token_count: None,
+            deprecated: false,
+            nearest_tag: None,
         },
         ListItem {
             name: "test2".to_string(),
@@ -845,6 +912,8 @@ fn test_sort_items_by_type() -> Result<()> {
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
             approximate_token_count: None,
+            deprecated: false,
+            nearest_tag: None,
         },
     ];
 
@@ -878,6 +947,7 @@ fn test_lockentry_to_listitem() -> Result<()> {
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: true,
     };
 
     let list_item = converters::lockentry_to_listitem(&lock_entry, "agent");
@@ -890,6 +960,7 @@ fn test_lockentry_to_listitem() -> Result<()> {
     assert_eq!(list_item.installed_at, Some("agents/test-agent.md".to_string()));
     assert_eq!(list_item.checksum, Some("sha256:def456".to_string()));
     assert_eq!(list_item.resolved_commit, Some("abc123".to_string()));
+    assert!(list_item.deprecated);
     Ok(())
 }
 
@@ -40,6 +40,16 @@
 //! agpm list --format tree
 //! ```
 //!
+//! Compare the lockfile with the files on disk:
+//! ```bash
+//! agpm list --tree --installed
+//! ```
+//!
+//! Show which tools each resource is installed into:
+//! ```bash
+//! agpm list --duplicates-across-tools
+//! ```
+//!
 //! List specific dependencies:
 //! ```bash
 //! agpm list my-agent utils-snippet
@@ -77,6 +87,8 @@
 //! The command primarily reads from:
 //! - **Primary**: `agpm.lock` - Contains installed resource information
 //! - **Secondary**: `agpm.toml` - Used for manifest comparison and validation
+//! - **Filesystem**: With `--installed`, the managed install directories of each
+//!   enabled tool are scanned and reconciled with the lockfile
 //!
 //! # Error Conditions
 //!
@@ -91,10 +103,14 @@ use std::path::PathBuf;
 
 use crate::cache::Cache;
 use crate::manifest::{Manifest, find_manifest_with_optional};
+use crate::utils::terminal::WARNING;
 
 mod converters;
+mod cross_tool;
+mod describe;
 mod filters;
 mod formatters;
+pub(super) mod installed;
 
 #[cfg(test)]
 mod list_tests;
@@ -126,6 +142,7 @@ pub use formatters::{ListItem, OutputConfig};
 ///     files: false,
 ///     verbose: false,
 ///     sort: None,
+///     env: None,
 /// };
 ///
 /// // List only agents with detailed information
@@ -141,6 +158,7 @@ pub use formatters::{ListItem, OutputConfig};
 ///     files: true,
 ///     verbose: false,
 ///     sort: Some("name".to_string()),
+///     env: None,
 /// };
 /// ```
 #[derive(Args)]
@@ -244,6 +262,42 @@ pub struct ListCommand {
     /// - `type`: Sort by resource type (agents first, then snippets)
     #[arg(long, value_name = "FIELD")]
     sort: Option<String>,
+
+    /// Reconcile the lockfile with the files on disk
+    ///
+    /// Scans the managed install directories of every enabled tool and reports
+    /// each resource as tracked, untracked (present but not in the lockfile),
+    /// or missing (in the lockfile but absent). Supports the `table` and
+    /// `json` formats.
+    #[arg(long, conflicts_with = "manifest")]
+    installed: bool,
+
+    /// Show the `--installed` view as a directory tree
+    #[arg(long, requires = "installed")]
+    tree: bool,
+
+    /// Print one tab-separated line per resource for scripts
+    ///
+    /// Columns are `type`, `name`, `source`, `version` and `path`, in that
+    /// order, with no header or decorations. `path` is the installed location,
+    /// or the path within the source with `--manifest`. Missing values are
+    /// empty fields. The layout is stable across AGPM versions.
+    #[arg(long, conflicts_with_all = ["format", "detailed", "installed"])]
+    parseable: bool,
+
+    /// Group resources by source, path and version and show the tools each is installed into
+    ///
+    /// Flags resources installed into more than one tool, for auditing
+    /// multi-tool setups. Supports the `table` and `json` formats.
+    #[arg(long, conflicts_with_all = ["manifest", "installed", "parseable"])]
+    duplicates_across_tools: bool,
+
+    /// Environment overlay to merge (for programmatic use, not exposed as CLI arg)
+    ///
+    /// This field is populated from the global --env flag (or `AGPM_ENV`) via
+    /// execute_with_config and selects `agpm.<env>.toml`.
+    #[arg(skip)]
+    pub env: Option<String>,
 }
 
 impl ListCommand {
@@ -298,6 +352,8 @@ impl ListCommand {
     ///     files: false,
     ///     verbose: false,
     ///     sort: Some("name".to_string()),
+    ///     parseable: false,
+    ///     env: None,
     /// };
     /// // cmd.execute_with_manifest_path(None).await?;
     /// # Ok::<(), anyhow::Error>(())
@@ -331,6 +387,9 @@ impl ListCommand {
         if self.manifest {
             // List from manifest
             self.list_from_manifest(&manifest_path)?;
+        } else if self.installed {
+            // Reconcile the lockfile with the filesystem
+            self.list_installed(&manifest_path, project_dir)?;
         } else {
             // List from lockfile
             self.list_from_lockfile(project_dir).await?;
@@ -351,6 +410,20 @@ impl ListCommand {
             }
         }
 
+        if self.installed && !matches!(self.format.as_str(), "table" | "json") {
+            return Err(anyhow::anyhow!(
+                "Invalid format '{}' for --installed. Valid formats are: table, json",
+                self.format
+            ));
+        }
+
+        if self.duplicates_across_tools && !matches!(self.format.as_str(), "table" | "json") {
+            return Err(anyhow::anyhow!(
+                "Invalid format '{}' for --duplicates-across-tools. Valid formats are: table, json",
+                self.format
+            ));
+        }
+
         // Validate type filter
         if let Some(ref t) = self.r#type {
             match t.as_str() {
@@ -381,7 +454,7 @@ impl ListCommand {
     }
 
     fn list_from_manifest(&self, manifest_path: &std::path::Path) -> Result<()> {
-        let manifest = Manifest::load(manifest_path)?;
+        let manifest = Manifest::load_for_env(manifest_path, self.env.as_deref())?;
 
         // Collect and filter dependencies
         let mut items = Vec::new();
@@ -421,6 +494,8 @@ impl ListCommand {
                             ),
                             applied_patches: std::collections::BTreeMap::new(),
                             approximate_token_count: None,
+                            deprecated: false,
+                            nearest_tag: None,
                         });
                     }
                 }
@@ -446,6 +521,8 @@ impl ListCommand {
                         )),
                         applied_patches: std::collections::BTreeMap::new(),
                         approximate_token_count: None,
+                        deprecated: false,
+                        nearest_tag: None,
                     });
                 }
             }
@@ -464,18 +541,21 @@ impl ListCommand {
         let lockfile_path = project_dir.join("agpm.lock");
 
         if !lockfile_path.exists() {
-            if self.format == "json" {
+            if self.parseable {
+                // Nothing installed: no lines
+            } else if self.format == "json" {
                 println!("{{}}");
             } else {
                 println!("No installed resources found.");
-                println!("⚠️  agpm.lock not found. Run 'agpm install' first.");
+                println!("{WARNING}agpm.lock not found. Run 'agpm install' first.");
             }
             return Ok(());
         }
 
         // Create a temporary manifest for CommandContext (we only need it for lockfile loading)
         let manifest_path = project_dir.join("agpm.toml");
-        let manifest = crate::manifest::Manifest::load(&manifest_path)?;
+        let manifest =
+            crate::manifest::Manifest::load_for_env(&manifest_path, self.env.as_deref())?;
         let command_context =
             crate::cli::common::CommandContext::new(manifest, project_dir.to_path_buf())?;
 
@@ -484,18 +564,33 @@ impl ListCommand {
             Some(lockfile) => lockfile,
             None => {
                 // Lockfile was regenerated and doesn't exist yet
-                if self.format == "json" {
+                if self.parseable {
+                    // Nothing installed: no lines
+                } else if self.format == "json" {
                     println!("{{}}");
                 } else {
                     println!("No installed resources found.");
                     println!(
-                        "⚠️  Lockfile was invalid and has been removed. Run 'agpm install' to regenerate it."
+                        "{WARNING}Lockfile was invalid and has been removed. Run 'agpm install' to regenerate it."
                     );
                 }
                 return Ok(());
             }
         };
 
+        if self.duplicates_across_tools {
+            let entries = crate::core::ResourceType::all()
+                .iter()
+                .filter(|resource_type| self.should_show_resource_type(**resource_type))
+                .flat_map(|resource_type| {
+                    let type_str = resource_type.to_string();
+                    lockfile.get_resources(resource_type).iter().filter(move |entry| {
+                        self.matches_lockfile_filters(&entry.name, entry, &type_str)
+                    })
+                });
+            return cross_tool::output(&cross_tool::group_by_origin(entries), &self.format);
+        }
+
         // Create cache if needed for detailed mode with patches
         let cache = if self.detailed {
             Some(Cache::new().context("Failed to initialize cache")?)
@@ -523,6 +618,9 @@ impl ListCommand {
             }
         }
 
+        // Describe commit pins relative to their nearest tag (offline)
+        describe::annotate_nearest_tags(&mut items, &lockfile).await;
+
         // Sort items
         self.sort_items(&mut items);
 
@@ -546,6 +644,30 @@ impl ListCommand {
         Ok(())
     }
 
+    fn list_installed(
+        &self,
+        manifest_path: &std::path::Path,
+        project_dir: &std::path::Path,
+    ) -> Result<()> {
+        let manifest = Manifest::load_for_env(manifest_path, self.env.as_deref())?;
+
+        // Without a lockfile every file in a managed directory is untracked
+        let lockfile = if project_dir.join("agpm.lock").exists() {
+            let command_context = crate::cli::common::CommandContext::new(
+                manifest.clone(),
+                project_dir.to_path_buf(),
+            )?;
+            command_context.load_lockfile_with_regeneration(true, "list")?.unwrap_or_default()
+        } else {
+            crate::lockfile::LockFile::default()
+        };
+
+        let entries = installed::reconcile(&lockfile, &manifest, project_dir, |resource_type| {
+            self.should_show_resource_type(resource_type)
+        });
+        installed::output(&entries, &self.format, self.tree)
+    }
+
     /// Determine if a resource type should be shown based on filters
     fn should_show_resource_type(&self, resource_type: crate::core::ResourceType) -> bool {
         filters::should_show_resource_type(
@@ -599,7 +721,11 @@ impl ListCommand {
     fn output_items(&self, items: &[ListItem], title: &str) -> Result<()> {
         let config = OutputConfig {
             title: title.to_string(),
-            format: self.format.clone(),
+            format: if self.parseable {
+                "parseable".to_string()
+            } else {
+                self.format.clone()
+            },
             files: self.files,
             detailed: self.detailed,
             verbose: self.verbose,
@@ -0,0 +1,70 @@
+//! Maintenance operations on the `agpm.lock` lockfile.
+//!
+//! This module provides the `lock` command. Its `sort` subcommand rewrites an
+//! existing lockfile in the canonical order the resolver produces, without
+//! changing any resolved values. This removes ordering churn left behind by
+//! hand-merged lockfiles.
+//!
+//! # Examples
+//!
+//! Canonicalize the lockfile after resolving a merge conflict:
+//! ```bash
+//! agpm lock sort
+//! ```
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use std::path::PathBuf;
+
+use crate::lockfile::LockFile;
+use crate::manifest::find_manifest_with_optional;
+
+/// Command to operate on the lockfile directly.
+#[derive(Args, Debug)]
+pub struct LockCommand {
+    /// Lockfile operation to perform
+    #[command(subcommand)]
+    command: LockSubcommands,
+}
+
+/// Subcommands for lockfile maintenance.
+#[derive(Subcommand, Debug)]
+enum LockSubcommands {
+    /// Rewrite agpm.lock in canonical order.
+    ///
+    /// Sorts sources by name and resources by type, then name, and writes
+    /// fields in their standard order. Resolved versions, commits and
+    /// checksums are left untouched.
+    Sort,
+}
+
+impl LockCommand {
+    /// Execute the lock command with an optional manifest path.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let project_dir = manifest_path.parent().unwrap_or_else(|| std::path::Path::new("."));
+        let lockfile_path = project_dir.join("agpm.lock");
+
+        match self.command {
+            LockSubcommands::Sort => sort_lockfile(&lockfile_path),
+        }
+    }
+}
+
+/// Rewrite the lockfile at `lockfile_path` in canonical order.
+fn sort_lockfile(lockfile_path: &std::path::Path) -> Result<()> {
+    if !lockfile_path.exists() {
+        anyhow::bail!("No lockfile found. Run 'agpm install' first.");
+    }
+
+    // Saving normalizes the lockfile, which applies the canonical ordering
+    let lockfile = LockFile::load(lockfile_path)?;
+    if lockfile.save_if_changed(lockfile_path)? {
+        println!("{} {}", "Sorted".green(), lockfile_path.display());
+    } else {
+        println!("{}", "Lockfile is already in canonical order".green());
+    }
+    Ok(())
+}
@@ -18,6 +18,7 @@ use std::path::{Path, PathBuf};
 
 use crate::cli::install::InstallCommand;
 use crate::lockfile::LockFile;
+use crate::utils::terminal::{PACKAGE, PARTY, SEARCH, SUCCESS, TIP, WARNING};
 
 // Gitignore section markers for migration detection
 const AGPM_MANAGED_ENTRIES: &str = "# AGPM managed entries";
@@ -203,11 +204,14 @@ pub async fn run_format_migration(project_dir: &Path) -> Result<()> {
     let detection = detect_old_format(project_dir);
 
     if !detection.needs_migration() {
-        println!("✅ {}", "No format migration needed - project already uses new format.".green());
+        println!(
+            "{SUCCESS}{}",
+            "No format migration needed - project already uses new format.".green()
+        );
         return Ok(());
     }
 
-    println!("📦 {}", "Migrating AGPM installation to new format...".cyan());
+    println!("{PACKAGE}{}", "Migrating AGPM installation to new format...".cyan());
 
     // 1. Move resources to new paths
     if !detection.old_resource_paths.is_empty() {
@@ -255,10 +259,9 @@ pub async fn run_format_migration(project_dir: &Path) -> Result<()> {
     }
 
     // 5. Print completion message
-    println!("\n✅ {}", "Format migration complete!".green().bold());
+    println!("\n{SUCCESS}{}", "Format migration complete!".green().bold());
     println!(
-        "\n{} If Claude Code can't find installed resources, run {} in Claude Code",
-        "💡".cyan(),
+        "\n{TIP}If Claude Code can't find installed resources, run {} in Claude Code",
         "/config".bright_white()
     );
     println!("   and set {} to {}.", "Respect .gitignore in file picker".yellow(), "false".green());
@@ -510,7 +513,9 @@ fn replace_tools_section(project_dir: &Path) -> Result<()> {
 ///
 /// This command performs three types of migrations:
 ///
-/// 1. **CCPM → AGPM naming**: Renames ccpm.toml and ccpm.lock to agpm.* equivalents
+/// 1. **CCPM → AGPM naming**: Renames ccpm.toml, ccpm.lock and their ccpm.private.*
+///    counterparts to agpm.* equivalents,
+///    updates references to the old file names, and notes the migration in agpm.toml
 /// 2. **Format migration**: Moves resources from flat paths to agpm/ subdirectories
 ///    and removes the old gitignore managed section
 /// 3. **Tools configuration**: Replaces old-style `[tools]` sections (with paths like
@@ -532,6 +537,9 @@ fn replace_tools_section(project_dir: &Path) -> Result<()> {
 /// # Dry run to see what would change
 /// agpm migrate --dry-run
 ///
+/// # Replace existing agpm.toml/agpm.lock with the legacy files
+/// agpm migrate --force
+///
 /// # Skip automatic installation (for testing)
 /// agpm migrate --skip-install
 /// ```
@@ -564,6 +572,13 @@ pub struct MigrateCommand {
     /// format to the new agpm/ subdirectory format.
     #[arg(long)]
     format_only: bool,
+
+    /// Overwrite existing agpm.toml/agpm.lock with the legacy CCPM files.
+    ///
+    /// Without this flag, migration refuses to run when both the legacy and
+    /// the AGPM file exist.
+    #[arg(long)]
+    force: bool,
 }
 
 impl MigrateCommand {
@@ -588,6 +603,7 @@ impl MigrateCommand {
             dry_run,
             skip_install,
             format_only: false,
+            force: false,
         }
     }
 
@@ -614,7 +630,7 @@ impl MigrateCommand {
         // Phase 2: Format migration (old gitignore-managed to new agpm/ subdirectory)
         let format_detection = detect_old_format(&dir);
         if format_detection.needs_migration() {
-            println!("\n🔍 Checking for old-format AGPM installation...");
+            println!("\n{SEARCH}Checking for old-format AGPM installation...");
 
             if !format_detection.old_resource_paths.is_empty() {
                 println!(
@@ -645,21 +661,27 @@ impl MigrateCommand {
                 any_migration_performed = true;
             }
         } else if !self.format_only {
-            println!("\n✅ {}", "Project already uses new agpm/ subdirectory format.".green());
+            println!(
+                "\n{SUCCESS}{}",
+                "Project already uses new agpm/ subdirectory format.".green()
+            );
         }
 
         // Run installation to finalize artifact locations
         if any_migration_performed && !self.skip_install && !self.dry_run {
-            println!("\n📦 {}", "Running installation to finalize artifact locations...".cyan());
+            println!(
+                "\n{PACKAGE}{}",
+                "Running installation to finalize artifact locations...".cyan()
+            );
 
             let install_cmd = InstallCommand::new();
             let manifest_path = dir.join("agpm.toml");
             match install_cmd.execute_from_path(Some(&manifest_path)).await {
                 Ok(()) => {
-                    println!("✅ {}", "Artifacts finalized in correct locations".green());
+                    println!("{SUCCESS}{}", "Artifacts finalized in correct locations".green());
                 }
                 Err(e) => {
-                    eprintln!("\n⚠️  {}", "Warning: Installation failed".yellow());
+                    eprintln!("\n{WARNING}{}", "Warning: Installation failed".yellow());
                     eprintln!("   {}", format!("Error: {}", e).yellow());
                     eprintln!("   {}", "You may need to run 'agpm install' manually".yellow());
                 }
@@ -668,11 +690,11 @@ impl MigrateCommand {
 
         if any_migration_performed && !self.dry_run {
             println!(
-                "\n💡 Remember to:\n  • Review the changes\n  • Run {} to verify\n  • Commit the changes to version control",
+                "\n{TIP}Remember to:\n  • Review the changes\n  • Run {} to verify\n  • Commit the changes to version control",
                 "agpm validate".cyan()
             );
         } else if !any_migration_performed {
-            println!("\n✅ {}", "No migrations needed - project is up to date.".green());
+            println!("\n{SUCCESS}{}", "No migrations needed - project is up to date.".green());
         }
 
         Ok(())
@@ -682,47 +704,47 @@ impl MigrateCommand {
     ///
     /// Returns true if migration was performed.
     async fn run_ccpm_migration(&self, dir: &Path) -> Result<bool> {
-        println!("🔍 Checking for legacy CCPM files in: {}", dir.display());
+        println!("{SEARCH}Checking for legacy CCPM files in: {}", dir.display());
 
-        let ccpm_toml = dir.join("ccpm.toml");
-        let ccpm_lock = dir.join("ccpm.lock");
         let agpm_toml = dir.join("agpm.toml");
-        let agpm_lock = dir.join("agpm.lock");
+        let ccpm_toml_exists = dir.join("ccpm.toml").exists();
 
-        let ccpm_toml_exists = ccpm_toml.exists();
-        let ccpm_lock_exists = ccpm_lock.exists();
-        let agpm_toml_exists = agpm_toml.exists();
-        let agpm_lock_exists = agpm_lock.exists();
+        // Legacy files present in the project, private ones included so they
+        // stay covered by the rewritten .gitignore entries
+        let renames: Vec<_> =
+            CCPM_FILE_NAMES.iter().filter(|(old, _)| dir.join(old).exists()).collect();
 
         // Check if there are any CCPM files to migrate
-        if !ccpm_toml_exists && !ccpm_lock_exists {
-            println!("✅ {}", "No legacy CCPM files found.".green());
+        if renames.is_empty() {
+            println!("{SUCCESS}{}", "No legacy CCPM files found.".green());
             return Ok(false);
         }
 
         // Check for conflicts
-        let mut conflicts = Vec::new();
-        if ccpm_toml_exists && agpm_toml_exists {
-            conflicts.push("agpm.toml already exists");
-        }
-        if ccpm_lock_exists && agpm_lock_exists {
-            conflicts.push("agpm.lock already exists");
-        }
+        let conflicts: Vec<_> = renames
+            .iter()
+            .filter(|(_, new)| dir.join(new).exists())
+            .map(|(_, new)| format!("{new} already exists"))
+            .collect();
 
         if !conflicts.is_empty() {
-            bail!(
-                "Migration conflict: {}. Please resolve conflicts manually.",
-                conflicts.join(" and ")
+            if !self.force {
+                bail!(
+                    "Migration conflict: {}. Use --force to overwrite, or resolve conflicts manually.",
+                    conflicts.join(" and ")
+                );
+            }
+            println!(
+                "{WARNING}{}",
+                format!("Overwriting existing files (--force): {}", conflicts.join(" and "))
+                    .yellow()
             );
         }
 
         // Display what will be migrated
-        println!("\n📦 CCPM files to migrate:");
-        if ccpm_toml_exists {
-            println!("  • ccpm.toml → agpm.toml");
-        }
-        if ccpm_lock_exists {
-            println!("  • ccpm.lock → agpm.lock");
+        println!("\n{PACKAGE}CCPM files to migrate:");
+        for (old, new) in &renames {
+            println!("  • {old} → {new}");
         }
 
         if self.dry_run {
@@ -734,24 +756,94 @@ impl MigrateCommand {
         }
 
         // Perform the migration
-        if ccpm_toml_exists {
-            std::fs::rename(&ccpm_toml, &agpm_toml)
-                .context("Failed to rename ccpm.toml to agpm.toml")?;
-            println!("✅ {}", "Renamed ccpm.toml → agpm.toml".green());
+        for (old, new) in &renames {
+            rename_replacing(&dir.join(old), &dir.join(new))
+                .with_context(|| format!("Failed to rename {old} to {new}"))?;
+            println!("{SUCCESS}{}", format!("Renamed {old} → {new}").green());
         }
 
-        if ccpm_lock_exists {
-            std::fs::rename(&ccpm_lock, &agpm_lock)
-                .context("Failed to rename ccpm.lock to agpm.lock")?;
-            println!("✅ {}", "Renamed ccpm.lock → agpm.lock".green());
+        for file in update_ccpm_references(dir)? {
+            println!("{SUCCESS}{}", format!("Updated CCPM references in {file}").green());
+        }
+
+        if ccpm_toml_exists {
+            add_migration_note(&agpm_toml)?;
         }
 
-        println!("\n🎉 {}", "CCPM naming migration completed successfully!".green().bold());
+        println!("\n{PARTY}{}", "CCPM naming migration completed successfully!".green().bold());
 
         Ok(true)
     }
 }
 
+/// Legacy file names and their AGPM replacements.
+const CCPM_FILE_NAMES: &[(&str, &str)] = &[
+    ("ccpm.private.toml", "agpm.private.toml"),
+    ("ccpm.private.lock", "agpm.private.lock"),
+    ("ccpm.toml", "agpm.toml"),
+    ("ccpm.lock", "agpm.lock"),
+];
+
+/// Note added to the top of a manifest renamed from ccpm.toml.
+const CCPM_MIGRATION_NOTE: &str = "# Migrated from ccpm.toml by `agpm migrate`.";
+
+/// Rename `from` to `to`, replacing `to` if it exists (`rename` fails on Windows otherwise).
+fn rename_replacing(from: &Path, to: &Path) -> Result<()> {
+    if to.exists() {
+        std::fs::remove_file(to)?;
+    }
+    std::fs::rename(from, to)?;
+    Ok(())
+}
+
+/// Replace references to legacy CCPM file names in `content`.
+///
+/// Returns `None` when there is nothing to replace.
+fn rewrite_ccpm_references(content: &str) -> Option<String> {
+    if !CCPM_FILE_NAMES.iter().any(|(old, _)| content.contains(old)) {
+        return None;
+    }
+
+    let mut result = content.to_string();
+    for (old, new) in CCPM_FILE_NAMES {
+        result = result.replace(old, new);
+    }
+    Some(result)
+}
+
+/// Update references to ccpm.* file names in agpm.toml and .gitignore.
+///
+/// The lockfile is left alone: it only holds generated data, and its source
+/// URLs and paths may legitimately contain "ccpm".
+///
+/// Returns the names of the files that were changed.
+fn update_ccpm_references(project_dir: &Path) -> Result<Vec<&'static str>> {
+    let mut updated = Vec::new();
+    for file in ["agpm.toml", ".gitignore"] {
+        let path = project_dir.join(file);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        if let Some(new_content) = rewrite_ccpm_references(&content) {
+            std::fs::write(&path, new_content)
+                .with_context(|| format!("Failed to update {}", path.display()))?;
+            updated.push(file);
+        }
+    }
+    Ok(updated)
+}
+
+/// Record the migration at the top of the manifest, once.
+fn add_migration_note(manifest_path: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(manifest_path)?;
+    if content.starts_with(CCPM_MIGRATION_NOTE) {
+        return Ok(());
+    }
+    std::fs::write(manifest_path, format!("{CCPM_MIGRATION_NOTE}\n{content}"))
+        .context("Failed to add migration note to agpm.toml")?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -766,6 +858,7 @@ mod tests {
             dry_run: false,
             skip_install: true,
             format_only: false,
+            force: false,
         };
 
         cmd.execute().await?;
@@ -786,6 +879,7 @@ mod tests {
             dry_run: false,
             skip_install: true,
             format_only: false,
+            force: false,
         };
 
         cmd.execute().await?;
@@ -809,6 +903,7 @@ mod tests {
             dry_run: true,
             skip_install: true,
             format_only: false,
+            force: false,
         };
 
         cmd.execute().await?;
@@ -833,6 +928,7 @@ mod tests {
             dry_run: false,
             skip_install: true,
             format_only: false,
+            force: false,
         };
 
         let result = cmd.execute().await;
@@ -841,6 +937,71 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_migrate_conflict_with_force() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(temp_dir.path().join("ccpm.toml"), "[sources]\nlegacy = \"../legacy\"\n")?;
+        fs::write(temp_dir.path().join("agpm.toml"), "[sources]\n")?;
+
+        let cmd = MigrateCommand {
+            path: Some(temp_dir.path().to_path_buf()),
+            dry_run: false,
+            skip_install: true,
+            format_only: false,
+            force: true,
+        };
+
+        cmd.execute().await?;
+
+        assert!(!temp_dir.path().join("ccpm.toml").exists());
+        let manifest = fs::read_to_string(temp_dir.path().join("agpm.toml"))?;
+        assert!(manifest.contains("legacy"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_migrate_updates_references_and_is_idempotent() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(
+            temp_dir.path().join("ccpm.toml"),
+            "# Dependencies are locked in ccpm.lock\n[sources]\n",
+        )?;
+        fs::write(temp_dir.path().join("ccpm.lock"), "version = 1\n")?;
+        fs::write(temp_dir.path().join("ccpm.private.toml"), "[sources]\n")?;
+        fs::write(
+            temp_dir.path().join(".gitignore"),
+            "target/\nccpm.private.toml\nccpm.private.lock\n",
+        )?;
+
+        let migrate = || MigrateCommand {
+            path: Some(temp_dir.path().to_path_buf()),
+            dry_run: false,
+            skip_install: true,
+            format_only: false,
+            force: false,
+        };
+
+        migrate().execute().await?;
+
+        let manifest = fs::read_to_string(temp_dir.path().join("agpm.toml"))?;
+        assert_eq!(
+            manifest,
+            format!("{CCPM_MIGRATION_NOTE}\n# Dependencies are locked in agpm.lock\n[sources]\n")
+        );
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join(".gitignore"))?,
+            "target/\nagpm.private.toml\nagpm.private.lock\n"
+        );
+        // The private manifest is renamed so the rewritten entry still ignores it
+        assert!(!temp_dir.path().join("ccpm.private.toml").exists());
+        assert!(temp_dir.path().join("agpm.private.toml").exists());
+
+        // A second run finds nothing to do and leaves the files alone
+        migrate().execute().await?;
+        assert_eq!(fs::read_to_string(temp_dir.path().join("agpm.toml"))?, manifest);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_migrate_only_toml() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -853,6 +1014,7 @@ mod tests {
             dry_run: false,
             skip_install: true,
             format_only: false,
+            force: false,
         };
 
         cmd.execute().await?;
@@ -874,6 +1036,7 @@ mod tests {
             dry_run: false,
             skip_install: true,
             format_only: false,
+            force: false,
         };
 
         cmd.execute().await?;
@@ -896,6 +1059,7 @@ mod tests {
             dry_run: false,
             skip_install: false, // Enable automatic installation
             format_only: false,
+            force: false,
         };
 
         let result = cmd.execute().await;
@@ -928,6 +1092,7 @@ mod tests {
             dry_run: false,
             skip_install: false, // Enable automatic installation
             format_only: false,
+            force: false,
         };
 
         // Should succeed - migration doesn't fail even if installation fails
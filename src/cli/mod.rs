@@ -21,12 +21,23 @@
 //! - `remove` - Remove sources and dependencies from the project manifest  
 //! - `install` - Install dependencies from the manifest
 //! - `update` - Update dependencies within version constraints
+//! - `pin` - Pin manifest constraints to the versions in the lockfile
+//! - `reinstall` - Restore installed resources from their locked commits
+//! - `lock` - Maintain the lockfile (e.g. `lock sort` to canonicalize it)
+//! - `resolve` - Check that the manifest resolves (`--manifest-only` skips worktrees)
 //!
 //! ## Information and Inspection
 //! - `list` - List installed resources from the lockfile
 //! - `tree` - Display dependency tree for installed resources
+//! - `graph` - Export the dependency graph as Graphviz DOT or JSON
 //! - `outdated` - Check for available updates to dependencies
 //! - `validate` - Validate project configuration and dependencies
+//! - `schema` - Print a JSON Schema for `agpm.toml` for editor tooling
+//! - `status` - Summarize lockfile drift, installed files and cache size
+//! - `index` - Build a JSON index of the resources offered by every source
+//!
+//! ## Publishing
+//! - `bundle` - Lint a source repository before releasing it, or export it as a tarball
 //!
 //! ## System Management
 //! - `cache` - Manage the global Git repository cache
@@ -100,7 +111,11 @@
 //! - `--verbose` - Enable debug output
 //! - `--quiet` - Suppress all output except errors
 //! - `--no-progress` - Disable progress bars and spinners
+//! - `--color` - When to use colors and emoji (`auto`, `always`, `never`)
 //! - `--config` - Path to custom config file
+//! - `--install-root` - Remap all tool directories under one root directory
+//! - `--insecure` - Skip TLS certificate verification for Git (discouraged)
+//! - `--json-errors` - Print failures as a JSON object on stderr
 //!
 //! # Example
 //!
@@ -116,15 +131,26 @@
 //! ```
 
 mod add;
+mod audit;
+mod bundle;
 mod cache;
 pub mod common;
 mod config;
+mod graph;
+mod index;
 mod init;
 pub mod install;
 mod list;
+mod lock;
 mod migrate;
 mod outdated;
+mod pin;
+mod prune;
+mod reinstall;
 mod remove;
+mod resolve;
+mod schema;
+mod status;
 mod tree;
 mod update;
 /// Self-update functionality for upgrading AGPM to newer versions.
@@ -137,12 +163,14 @@ mod update;
 /// both CLI usage and programmatic access to the self-update features.
 pub mod upgrade;
 pub mod validate;
+mod verify_sources;
 
 #[cfg(test)]
 mod tests;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
+use colored::Colorize;
 use std::path::PathBuf;
 
 /// Runtime configuration for CLI execution.
@@ -204,6 +232,49 @@ pub struct CliConfig {
     /// - Alternative configuration layouts
     /// - Shared configuration in team environments
     pub config_path: Option<String>,
+
+    /// Environment overlay selected via `--env` or `AGPM_ENV`.
+    ///
+    /// When set, `install`, `update`, `status`, `outdated`, `list`, `tree`, and
+    /// `validate` merge `agpm.<env>.toml` on top of `agpm.toml`.
+    pub env: Option<String>,
+
+    /// Git operation timeout in seconds from `--git-timeout`.
+    ///
+    /// When `None`, `git.timeout` from the global config is used if set,
+    /// otherwise the built-in defaults. `Some(0)` disables the timeout.
+    pub git_timeout: Option<u64>,
+
+    /// When to style output with colors and emoji, from `--color`.
+    pub color: crate::utils::terminal::ColorChoice,
+
+    /// Directory to remap all tool base directories under, from `--install-root`.
+    ///
+    /// Passed to `install` and `update`.
+    pub install_root: Option<PathBuf>,
+
+    /// Skip TLS certificate verification for Git, from `--insecure`.
+    ///
+    /// Also enabled by `git.insecure_ssl` in the global config.
+    pub insecure: bool,
+
+    /// Print failures as a JSON object instead of text, from `--json-errors`.
+    ///
+    /// Read by `main` when reporting the error of a failed command.
+    pub json_errors: bool,
+
+    /// File to additionally write logs to, from `--log-file` or `AGPM_LOG_FILE`.
+    ///
+    /// Read by `main` when installing the tracing subscriber.
+    pub log_file: Option<PathBuf>,
+
+    /// Rotation policy for `log_file`, from `--log-rotate`.
+    pub log_rotate: Option<crate::utils::log_file::LogRotation>,
+
+    /// Use a throwaway temporary cache instead of the configured one, from `--no-cache`.
+    ///
+    /// The read-only cache base is ignored as well, so every source is cloned fresh.
+    pub no_cache: bool,
 }
 
 impl CliConfig {
@@ -249,7 +320,12 @@ impl CliConfig {
 /// All subcommands inherit these global options:
 /// - **Verbosity control**: `--verbose` and `--quiet` for output level
 /// - **Configuration**: `--config` for custom config file paths
-/// - **UI control**: `--no-progress` for automation-friendly output
+/// - **UI control**: `--no-progress` for automation-friendly output and
+///   `--color` for colors and emoji
+/// - **Environment**: `--env` to merge an `agpm.<env>.toml` overlay
+/// - **Network**: `--git-timeout` to tune Git clone/fetch/worktree timeouts
+/// - **Scripting**: `--json-errors` to report failures as a JSON object
+/// - **Cache**: `--no-cache` to run against a throwaway temporary cache
 ///
 /// # Examples
 ///
@@ -410,6 +486,120 @@ pub struct Cli {
     /// ```
     #[arg(long, global = true)]
     no_progress: bool,
+
+    /// When to use colors and emoji in output.
+    ///
+    /// With `auto`, output is styled only when stdout is a terminal and the
+    /// `NO_COLOR` environment variable is not set, so redirected output and
+    /// CI logs stay free of escape codes. `always` and `never` override the
+    /// detection.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// agpm --color never install > install.log
+    /// agpm --color always list | less -R
+    /// ```
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value_t)]
+    color: crate::utils::terminal::ColorChoice,
+
+    /// Environment overlay to merge on top of agpm.toml.
+    ///
+    /// Selects `agpm.<env>.toml` next to the manifest (e.g. `agpm.staging.toml`).
+    /// The overlay can add or override sources, dependencies, default tools,
+    /// and patches, but cannot define `[tools]`. Private configuration
+    /// (`agpm.private.toml`) is still applied last.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// agpm --env staging install
+    /// AGPM_ENV=prod agpm update
+    /// agpm --env staging outdated
+    /// ```
+    #[arg(long, global = true, env = "AGPM_ENV", value_name = "ENV")]
+    env: Option<String>,
+
+    /// Timeout in seconds for Git clone, fetch, and worktree creation.
+    ///
+    /// Overrides the built-in limits (120s for clones, 60s for worktrees,
+    /// 5 minutes for fetches) and `git.timeout` from the global config. Use `0`
+    /// to disable the timeout entirely, e.g. for very large repositories on
+    /// slow networks.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// agpm --git-timeout 600 install   # Allow up to 10 minutes per operation
+    /// agpm --timeout 0 update          # Never time out
+    /// ```
+    #[arg(long, visible_alias = "timeout", global = true, value_name = "SECS")]
+    git_timeout: Option<u64>,
+
+    /// Install all tool directories under this root instead of the project.
+    ///
+    /// Remaps every tool base directory (`.claude`, `.opencode`, ...) and merge
+    /// target (`.mcp.json`, ...) under a single directory, e.g. for
+    /// containerized builds. Relative paths are resolved against the project
+    /// directory. Applies to `install` and `update`; the lockfile records the
+    /// remapped paths, so combine with `--no-lock` to leave it untouched.
+    #[arg(long, global = true, value_name = "DIR")]
+    install_root: Option<PathBuf>,
+
+    /// Disable TLS certificate verification for Git. INSECURE, avoid if possible.
+    ///
+    /// Sets `http.sslVerify=false` for every Git operation, which lets anyone
+    /// on the network path intercept or tamper with sources. Only intended as
+    /// a last resort behind a TLS-intercepting corporate proxy; prefer adding
+    /// the proxy's CA certificate to Git (`http.sslCAInfo`). A warning is
+    /// printed on every run while this (or `git.insecure_ssl`) is active.
+    #[arg(long, global = true, alias = "no-verify-ssl")]
+    insecure: bool,
+
+    /// Print failures as a single JSON object on stderr.
+    ///
+    /// Instead of the formatted error text, a failing command prints
+    /// `{"error": {"code", "message", "context", "details", "suggestions"}}`
+    /// so scripts can branch on `code` without parsing messages. The exit
+    /// status is still non-zero.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// agpm --json-errors install 2> error.json
+    /// ```
+    #[arg(long, global = true)]
+    json_errors: bool,
+
+    /// Also write logs to this file, at debug level unless `RUST_LOG` is set.
+    ///
+    /// Independent of `--verbose`: the console stays quiet while the file
+    /// records everything, which helps debugging intermittent CI failures.
+    /// The file is appended to, and credentials in URLs are redacted.
+    #[arg(long, global = true, value_name = "PATH", env = "AGPM_LOG_FILE")]
+    log_file: Option<PathBuf>,
+
+    /// Rotate the log file by size (e.g. `10MB`) or `daily`.
+    ///
+    /// Size rotation keeps up to five backups (`agpm.log.1` ...); daily
+    /// rotation renames the previous day's file to `agpm.log.YYYY-MM-DD`.
+    #[arg(long, global = true, value_name = "SIZE|daily", requires = "log_file")]
+    log_rotate: Option<crate::utils::log_file::LogRotation>,
+
+    /// Use a fresh temporary cache that is deleted when the command exits.
+    ///
+    /// Every source is cloned from scratch and neither the configured cache
+    /// (`~/.agpm/cache` or `AGPM_CACHE_DIR`) nor `cache.read_only_base` is
+    /// read or written. Useful to debug cache problems or reproduce a run on
+    /// a clean machine.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// agpm --no-cache install --no-lock
+    /// ```
+    #[arg(long, global = true)]
+    no_cache: bool,
 }
 
 /// Available subcommands for the AGPM CLI.
@@ -426,11 +616,24 @@ pub struct Cli {
 /// - [`Remove`](Commands::Remove): Remove sources and dependencies
 /// - [`Install`](Commands::Install): Install dependencies from manifest
 /// - [`Update`](Commands::Update): Update dependencies within constraints
+/// - [`Pin`](Commands::Pin): Pin constraints to the lockfile
+/// - [`Reinstall`](Commands::Reinstall): Restore resources from the lockfile
+/// - [`Lock`](Commands::Lock): Canonicalize the lockfile
+/// - [`Resolve`](Commands::Resolve): Check that dependencies resolve
 ///
 /// ## Information & Validation
 /// - [`List`](Commands::List): Display installed resources
 /// - [`Tree`](Commands::Tree): Display dependency tree
+/// - [`Graph`](Commands::Graph): Export the dependency graph
 /// - [`Validate`](Commands::Validate): Verify project configuration
+/// - [`Schema`](Commands::Schema): Export the manifest JSON Schema
+/// - [`Status`](Commands::Status): Summarize project health
+/// - [`Index`](Commands::Index): Index the resources of every source
+/// - [`VerifySources`](Commands::VerifySources): Check locked tags against upstream
+/// - [`Audit`](Commands::Audit): Report security-relevant issues
+///
+/// ## Publishing
+/// - [`Bundle`](Commands::Bundle): Lint or export a source repository
 ///
 /// ## System Management
 /// - [`Cache`](Commands::Cache): Manage Git repository cache
@@ -513,6 +716,39 @@ enum Commands {
     /// See [`update::UpdateCommand`] for detailed options and behavior.
     Update(update::UpdateCommand),
 
+    /// Pin manifest constraints to the versions in the lockfile.
+    ///
+    /// Rewrites `agpm.toml` so range constraints become the resolved tags and
+    /// branch-tracked dependencies record their resolved commit as `rev`.
+    /// Formatting and comments are preserved.
+    ///
+    /// See [`pin::PinCommand`] for detailed options and behavior.
+    Pin(pin::PinCommand),
+
+    /// Restore installed resources from the lockfile.
+    ///
+    /// Re-copies the named resources from their locked commits and verifies
+    /// the locked checksums, without resolving or touching other resources.
+    ///
+    /// See [`reinstall::ReinstallCommand`] for detailed options and behavior.
+    Reinstall(reinstall::ReinstallCommand),
+
+    /// Maintain the `agpm.lock` lockfile.
+    ///
+    /// `lock sort` rewrites the lockfile in the canonical order the resolver
+    /// produces without changing any resolved values, e.g. after a manual merge.
+    ///
+    /// See [`lock::LockCommand`] for detailed options and behavior.
+    Lock(lock::LockCommand),
+
+    /// Check that dependencies resolve without installing them.
+    ///
+    /// Prints the resolved version map. `--manifest-only` resolves from tag
+    /// listings and frontmatter without creating worktrees, for fast CI checks.
+    ///
+    /// See [`resolve::ResolveCommand`] for detailed options and behavior.
+    Resolve(resolve::ResolveCommand),
+
     /// Check for available updates to installed dependencies.
     ///
     /// Compares installed versions from the lockfile against available versions
@@ -549,6 +785,14 @@ enum Commands {
     /// See [`tree::TreeCommand`] for detailed options and behavior.
     Tree(tree::TreeCommand),
 
+    /// Export the resolved dependency graph.
+    ///
+    /// Prints the graph recorded in the lockfile in Graphviz DOT format, ready
+    /// to pipe into `dot`, or as JSON with `--format json`.
+    ///
+    /// See [`graph::GraphCommand`] for detailed options and behavior.
+    Graph(graph::GraphCommand),
+
     /// Validate AGPM project configuration and dependencies.
     ///
     /// Performs comprehensive validation of the project manifest, dependencies,
@@ -558,6 +802,57 @@ enum Commands {
     /// See [`validate::ValidateCommand`] for detailed options and behavior.
     Validate(validate::ValidateCommand),
 
+    /// Print a JSON Schema describing `agpm.toml`.
+    ///
+    /// Generated from the manifest types so editors can offer completion and
+    /// validation for the manifest.
+    ///
+    /// See [`schema::SchemaCommand`] for detailed options and behavior.
+    Schema(schema::SchemaCommand),
+
+    /// Summarize project health at a glance.
+    ///
+    /// Reports lockfile drift, dependency counts by type, missing installed
+    /// files, branch-tracked dependencies and cache size without fetching
+    /// any sources.
+    ///
+    /// See [`status::StatusCommand`] for detailed options and behavior.
+    Status(status::StatusCommand),
+
+    /// Build a JSON index of the resources offered by every source.
+    ///
+    /// Fetches each source, reads the frontmatter metadata and declared
+    /// dependencies of every resource, and writes them to `agpm-index.json`.
+    /// Sources whose commit is already indexed are not read again.
+    ///
+    /// See [`index::IndexCommand`] for detailed options and behavior.
+    Index(index::IndexCommand),
+
+    /// Check that locked tags still point at the locked commits upstream.
+    ///
+    /// Queries each source in parallel and flags tags that were moved or
+    /// deleted since the lockfile was written, for example by a force-push.
+    ///
+    /// See [`verify_sources::VerifySourcesCommand`] for detailed options and behavior.
+    VerifySources(verify_sources::VerifySourcesCommand),
+
+    /// Report security-relevant issues in the project.
+    ///
+    /// Flags plain-HTTP and non-allow-listed sources, branch and commit pins,
+    /// hooks that run commands, and MCP servers given secrets through their
+    /// environment. Exits with an error on high-severity findings.
+    ///
+    /// See [`audit::AuditCommand`] for detailed options and behavior.
+    Audit(audit::AuditCommand),
+
+    /// Work with a local source repository.
+    ///
+    /// Provides tools for resource publishers, such as linting a whole
+    /// repository before tagging a release or packing it into a tarball.
+    ///
+    /// See [`bundle::BundleCommand`] for detailed options and behavior.
+    Bundle(bundle::BundleCommand),
+
     /// Manage the global Git repository cache.
     ///
     /// Provides operations for managing the global cache directory where
@@ -665,6 +960,15 @@ impl Cli {
             log_level,
             no_progress: self.no_progress,
             config_path: self.config.clone(),
+            env: self.env.clone(),
+            git_timeout: self.git_timeout,
+            color: self.color,
+            install_root: self.install_root.clone(),
+            insecure: self.insecure,
+            json_errors: self.json_errors,
+            log_file: self.log_file.clone(),
+            log_rotate: self.log_rotate,
+            no_cache: self.no_cache,
         }
     }
 
@@ -714,6 +1018,11 @@ impl Cli {
     /// # });
     /// ```
     pub async fn execute_with_config(self, config: CliConfig) -> Result<()> {
+        Self::apply_global_settings(&config).await?;
+        // Held until the command finishes; dropping it deletes the temporary cache
+        let _temporary_cache =
+            config.no_cache.then(crate::cache::TemporaryCache::activate).transpose()?;
+
         // Check for updates automatically (non-blocking, best-effort)
         // Skip for the upgrade command itself to avoid recursion
         if !matches!(self.command, Commands::Upgrade(_)) {
@@ -729,23 +1038,56 @@ impl Cli {
                 // Pass no_progress and verbose flags to install command
                 cmd.no_progress = cmd.no_progress || config.no_progress;
                 cmd.verbose = config.log_level == Some("debug".to_string());
+                cmd.env = config.env.clone();
+                cmd.install_root = config.install_root.clone();
                 cmd.execute_with_manifest_path(self.manifest_path).await
             }
             Commands::Update(mut cmd) => {
                 // Pass no_progress and verbose flags to update command
                 cmd.no_progress = cmd.no_progress || config.no_progress;
                 cmd.verbose = config.log_level == Some("debug".to_string());
+                cmd.env = config.env.clone();
+                cmd.install_root = config.install_root.clone();
                 cmd.execute_with_manifest_path(self.manifest_path).await
             }
             Commands::Outdated(mut cmd) => {
                 // Pass no_progress flag to outdated command
                 cmd.no_progress = cmd.no_progress || config.no_progress;
+                cmd.env = config.env.clone();
                 cmd.execute_with_manifest_path(self.manifest_path).await
             }
+            Commands::Pin(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::Reinstall(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::Lock(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::Resolve(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
             Commands::Upgrade(cmd) => upgrade::execute(cmd).await,
-            Commands::List(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
-            Commands::Tree(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
-            Commands::Validate(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::List(mut cmd) => {
+                cmd.env = config.env.clone();
+                cmd.execute_with_manifest_path(self.manifest_path).await
+            }
+            Commands::Tree(mut cmd) => {
+                cmd.env = config.env.clone();
+                cmd.execute_with_manifest_path(self.manifest_path).await
+            }
+            Commands::Graph(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::Validate(mut cmd) => {
+                cmd.env = config.env.clone();
+                cmd.execute_with_manifest_path(self.manifest_path).await
+            }
+            Commands::Schema(cmd) => cmd.execute().await,
+            Commands::Status(mut cmd) => {
+                cmd.env = config.env.clone();
+                cmd.execute_with_manifest_path(self.manifest_path).await
+            }
+            Commands::Index(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::VerifySources(cmd) => {
+                cmd.execute_with_manifest_path(self.manifest_path).await
+            }
+            Commands::Audit(mut cmd) => {
+                cmd.config_path = config.config_path.as_ref().map(PathBuf::from);
+                cmd.execute_with_manifest_path(self.manifest_path).await
+            }
+            Commands::Bundle(cmd) => cmd.execute().await,
             Commands::Cache(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
             Commands::Config(cmd) => {
                 // Pass config_path to config command if provided
@@ -756,6 +1098,79 @@ impl Cli {
         }
     }
 
+    /// Install the process-wide output, Git and cache settings from the CLI and global config.
+    ///
+    /// `--color` selects styled output, `--git-timeout` wins over `git.timeout`,
+    /// `git.partial` selects partial clones, `git.submodules` toggles submodule
+    /// checkout, `--insecure` or `git.insecure_ssl` disables TLS verification
+    /// (with a warning), `cache.read_only_base` sets the shared cache base
+    /// (ignored with `--no-cache`),
+    /// `cache.mirror_url` sets the cache server and
+    /// `cache.worktree_max_age_days` enables age-based worktree cleanup and
+    /// `AGPM_TMPDIR` or `cache.temp_dir` sets the directory for temporary
+    /// files. An unreadable config simply leaves the built-in defaults in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configured temporary directory is not writable.
+    async fn apply_global_settings(config: &CliConfig) -> Result<()> {
+        crate::utils::terminal::set_color_choice(config.color);
+
+        let global = crate::config::GlobalConfig::load_with_optional(
+            config.config_path.as_ref().map(PathBuf::from),
+        )
+        .await
+        .unwrap_or_default();
+        let git = global.git;
+
+        crate::git::timeout::set_timeout_override(config.git_timeout.or(git.timeout));
+        crate::git::partial::set_partial_clone(git.partial);
+        crate::git::submodules::set_submodules(git.submodules.unwrap_or(true));
+
+        let insecure = config.insecure || git.insecure_ssl.unwrap_or(false);
+        crate::git::ssl::set_insecure_ssl(insecure);
+        if insecure {
+            // Printed even with --quiet: this must never go unnoticed
+            eprintln!(
+                "{}{}",
+                crate::utils::terminal::WARNING,
+                crate::git::ssl::INSECURE_SSL_WARNING.yellow().bold()
+            );
+        }
+
+        let read_only_base =
+            global.cache.read_only_base.filter(|_| !config.no_cache).and_then(|base| {
+                crate::utils::platform::resolve_path(&base)
+                    .inspect_err(|e| tracing::warn!("Ignoring cache.read_only_base '{base}': {e}"))
+                    .ok()
+            });
+        crate::cache::shared::set_read_only_base(read_only_base);
+        crate::cache::mirror::set_mirror_url(global.cache.mirror_url.clone());
+        crate::cache::retention::set_worktree_max_age(
+            global
+                .cache
+                .worktree_max_age_days
+                .map(|days| std::time::Duration::from_secs(days * 24 * 60 * 60)),
+        );
+
+        let temp_dir = match std::env::var(crate::cache::temp_dir::TEMP_DIR_ENV) {
+            Ok(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+            _ => global
+                .cache
+                .temp_dir
+                .map(|dir| {
+                    crate::utils::platform::resolve_path(&dir)
+                        .with_context(|| format!("Invalid cache.temp_dir '{dir}'"))
+                })
+                .transpose()?,
+        };
+        if let Some(dir) = &temp_dir {
+            crate::cache::temp_dir::validate_temp_dir(dir)?;
+        }
+        crate::cache::temp_dir::set_temp_dir(temp_dir);
+        Ok(())
+    }
+
     /// Check for AGPM updates automatically based on configuration.
     ///
     /// This method performs a non-blocking, best-effort check for updates.
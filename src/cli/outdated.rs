@@ -140,6 +140,7 @@
 //!     no_fetch: false,
 //!     max_parallel: None,
 //!     no_progress: false,
+//!     env: None,
 //! };
 //!
 //! cmd.execute_with_manifest_path(None).await?;
@@ -157,7 +158,7 @@ use tracing::{debug, info};
 
 use crate::cache::Cache;
 use crate::core::OperationContext;
-use crate::git::parse_git_url;
+use crate::git::FetchMode;
 use crate::lockfile::LockedResource;
 use crate::manifest::{Manifest, find_manifest_with_optional};
 use crate::resolver::DependencyResolver;
@@ -194,6 +195,7 @@ use crate::utils::progress::{InstallationPhase, MultiPhaseProgress};
 ///     no_fetch: false,
 ///     max_parallel: None,
 ///     no_progress: false,
+///     env: None,
 /// };
 ///
 /// cmd.execute_with_manifest_path(None).await?;
@@ -214,6 +216,7 @@ use crate::utils::progress::{InstallationPhase, MultiPhaseProgress};
 ///     no_fetch: false,
 ///     max_parallel: Some(5),
 ///     no_progress: true,
+///     env: None,
 /// };
 ///
 /// cmd.execute_with_manifest_path(None).await?;
@@ -235,6 +238,7 @@ use crate::utils::progress::{InstallationPhase, MultiPhaseProgress};
 ///     no_fetch: false, // Always check latest
 ///     max_parallel: Some(10), // Parallel for speed
 ///     no_progress: true, // No TTY in CI
+///     env: None,
 /// };
 ///
 /// cmd.execute_with_manifest_path(None).await?;
@@ -267,6 +271,13 @@ pub struct OutdatedCommand {
     /// Don't show progress bars (automatically set by global option)
     #[arg(skip)]
     pub no_progress: bool,
+
+    /// Environment overlay to merge (for programmatic use, not exposed as CLI arg)
+    ///
+    /// This field is populated from the global --env flag (or `AGPM_ENV`) via
+    /// execute_with_config and selects `agpm.<env>.toml`.
+    #[arg(skip)]
+    pub env: Option<String>,
 }
 
 /// Information about a dependency's update status.
@@ -516,6 +527,7 @@ impl Default for OutdatedCommand {
             no_fetch: false,
             max_parallel: None,
             no_progress: false,
+            env: None,
         }
     }
 }
@@ -622,6 +634,7 @@ impl OutdatedCommand {
     ///     no_fetch: false,
     ///     max_parallel: None,
     ///     no_progress: false,
+    ///     env: None,
     /// };
     ///
     /// let manifest_path = PathBuf::from("./agpm.toml");
@@ -633,7 +646,7 @@ impl OutdatedCommand {
         info!("Checking for outdated dependencies");
 
         // 1. Load manifest and lockfile
-        let manifest = Manifest::load(&manifest_path)
+        let manifest = Manifest::load_for_env(&manifest_path, self.env.as_deref())
             .with_context(|| format!("Failed to load manifest from {manifest_path:?}"))?;
 
         let project_dir =
@@ -661,8 +674,11 @@ impl OutdatedCommand {
             }
         };
 
-        // 2. Initialize cache and resolver
-        let cache = Cache::new().context("Failed to initialize cache")?;
+        // 2. Initialize cache and resolver. Nothing is installed, so fetches
+        // skip file contents; checkouts for transitive metadata fetch them on demand.
+        let cache = Cache::new()
+            .context("Failed to initialize cache")?
+            .with_fetch_mode(FetchMode::RefsOnly);
 
         // 3. Create resolver for version resolution
         let mut resolver = DependencyResolver::new(manifest.clone(), cache.clone())
@@ -838,6 +854,7 @@ impl OutdatedCommand {
         // Get the version constraint
         let constraint_str = dep
             .get_version()
+            .or_else(|| manifest.default_version_for(dep))
             .map_or_else(|| "latest".to_string(), std::string::ToString::to_string);
 
         // The new_entry version is the latest compatible (resolved by DependencyResolver.update())
@@ -849,11 +866,13 @@ impl OutdatedCommand {
             .get(source_name)
             .ok_or_else(|| anyhow::anyhow!("Source {source_name} not found in manifest"))?;
 
-        let (owner, repo) = parse_git_url(source_url)
-            .unwrap_or_else(|_| ("unknown".to_string(), source_name.to_string()));
+        // Archive sources are pinned by their URL and have no other versions
+        if crate::source::archive::is_archive_url(source_url) {
+            debug!("Skipping archive source dependency: {}", name);
+            return Ok(None);
+        }
 
-        let bare_repo_path =
-            cache.get_cache_location().join("sources").join(format!("{owner}_{repo}.git"));
+        let bare_repo_path = cache.bare_repo_path(source_url);
 
         if !bare_repo_path.exists() {
             debug!("Repository not found in cache at {:?}, skipping", bare_repo_path);
@@ -909,7 +928,9 @@ impl OutdatedCommand {
 
         // Determine if updates are available
         let has_update = old_entry.resolved_commit != new_entry.resolved_commit;
-        let has_major_update = latest_compatible != latest_available;
+        // latest-by-date ignores semver ordering, so there is nothing "beyond" it
+        let has_major_update = constraint_str != crate::version::LATEST_BY_DATE
+            && latest_compatible != latest_available;
 
         Ok(Some(OutdatedInfo {
             name: name.to_string(),
@@ -1019,6 +1040,7 @@ impl OutdatedCommand {
     ///     # no_fetch: false,
     ///     # max_parallel: None,
     ///     # no_progress: false,
+    ///     env: None,
     /// };
     ///
     /// let outdated = vec![];
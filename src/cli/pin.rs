@@ -0,0 +1,85 @@
+//! Pin manifest dependencies to the versions in the lockfile.
+//!
+//! This module provides the `pin` command, which freezes `agpm.toml` to the
+//! exact state recorded in `agpm.lock`, typically right after `agpm update`.
+//! Range constraints become the resolved tags, and branch-tracked dependencies
+//! get a `rev` with the resolved commit. Formatting and comments in the
+//! manifest are preserved.
+//!
+//! # Examples
+//!
+//! Pin everything to the current lockfile:
+//! ```bash
+//! agpm pin
+//! ```
+//!
+//! Preview the changes without writing the manifest:
+//! ```bash
+//! agpm pin --dry-run
+//! ```
+
+use anyhow::{Context, Result};
+use clap::Args;
+use colored::Colorize;
+use std::path::PathBuf;
+
+use crate::manifest::find_manifest_with_optional;
+use crate::manifest::pin::{PinChange, find_pins, write_pins};
+
+/// Command to pin manifest constraints to the resolved versions.
+#[derive(Args, Debug)]
+pub struct PinCommand {
+    /// Show the changes without writing agpm.toml
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl PinCommand {
+    /// Execute the pin command with an optional manifest path.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let project_dir = manifest_path.parent().unwrap_or_else(|| std::path::Path::new("."));
+
+        let manifest = crate::manifest::Manifest::load(&manifest_path)?;
+        let command_context =
+            crate::cli::common::CommandContext::new(manifest.clone(), project_dir.to_path_buf())?;
+        let lockfile = command_context
+            .load_lockfile_with_regeneration(true, "pin")?
+            .ok_or_else(|| anyhow::anyhow!("No lockfile found. Run 'agpm install' first."))?;
+
+        let pins = find_pins(&manifest, &lockfile);
+        if pins.is_empty() {
+            println!("{}", "All dependencies are already pinned".green());
+            return Ok(());
+        }
+
+        for pin in &pins {
+            let name = format!("{}/{}", pin.resource_type, pin.name);
+            match &pin.change {
+                PinChange::Version {
+                    from,
+                    to,
+                } => println!("  {} {from} -> {}", name.cyan(), to.green()),
+                PinChange::Rev {
+                    branch,
+                    sha,
+                } => println!("  {} {branch} -> rev {}", name.cyan(), sha.green()),
+            }
+        }
+
+        let count = pins.len();
+        let noun = if count == 1 {
+            "dependency"
+        } else {
+            "dependencies"
+        };
+        if self.dry_run {
+            println!("\n{} {count} {noun} (dry run, agpm.toml not modified)", "Would pin".yellow());
+        } else {
+            write_pins(&manifest_path, &pins)?;
+            println!("\n{} {count} {noun} in {}", "Pinned".green(), manifest_path.display());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+//! Project reconciliation for `agpm install --prune`.
+//!
+//! A regular install removes the files of dependencies that were in the
+//! previous lockfile but not in the new one. When the lockfile changed some
+//! other way (pulled from version control, regenerated with `agpm lock`, or
+//! deleted), the files and merged config entries of removed dependencies are
+//! left behind. `--prune` compares the project with the final lockfile instead:
+//!
+//! - lockfile entries no longer reachable from a manifest dependency are dropped;
+//! - files and skill directories in managed install directories (for example
+//!   `.claude/agents/agpm`) without a lockfile entry are deleted;
+//! - MCP servers and hooks tagged as AGPM-managed (`_agpm` metadata) without
+//!   a lockfile entry are stripped from their merge targets. Entries added by
+//!   hand are kept.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::list::installed::{InstallStatus, reconcile};
+use super::remove::plan::{strip_config, unreachable_resources};
+use crate::core::ResourceType;
+use crate::lockfile::LockFile;
+use crate::manifest::Manifest;
+
+/// Drop lockfile entries that no manifest dependency reaches.
+///
+/// Returns the dropped entries as `<type> <name>`, sorted.
+pub fn prune_lockfile(lockfile: &mut LockFile) -> Vec<String> {
+    let unreachable = unreachable_resources(lockfile);
+    let mut pruned = Vec::new();
+    for resource_type in ResourceType::all() {
+        lockfile.get_resources_mut(resource_type).retain(|resource| {
+            let key = (*resource_type, resource.name.clone(), resource.installed_at.clone());
+            let keep = !unreachable.contains(&key);
+            if !keep {
+                pruned.push(format!("{resource_type} {}", resource.name));
+            }
+            keep
+        });
+    }
+    pruned.sort();
+    pruned
+}
+
+/// Delete installed artifacts and merged config entries that `lockfile` does not record.
+///
+/// Returns the removed paths and config keys (`<file>: <key>`), sorted.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be removed or a merge target cannot be rewritten.
+pub async fn prune_project(
+    lockfile: &LockFile,
+    manifest: &Manifest,
+    project_dir: &Path,
+) -> Result<Vec<String>> {
+    let mut pruned = Vec::new();
+
+    let untracked = reconcile(lockfile, manifest, project_dir, |_| true)
+        .into_iter()
+        .filter(|entry| entry.status == InstallStatus::Untracked);
+    for entry in untracked {
+        let path = project_dir.join(&entry.path);
+        if path.is_dir() {
+            tokio::fs::remove_dir_all(&path)
+                .await
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        } else {
+            tokio::fs::remove_file(&path)
+                .await
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        crate::installer::cleanup_empty_dirs(&path).await?;
+        pruned.push(entry.path);
+    }
+
+    let mut targets: Vec<(ResourceType, String)> = Vec::new();
+    for (tool, config) in &manifest.get_tools_config().types {
+        if !config.enabled {
+            continue;
+        }
+        for resource_type in [ResourceType::Hook, ResourceType::McpServer] {
+            if let Some(target) = manifest.get_merge_target(tool, resource_type) {
+                let target = crate::utils::normalize_path_for_storage(&target);
+                if !targets.contains(&(resource_type, target.clone())) {
+                    targets.push((resource_type, target));
+                }
+            }
+        }
+    }
+    for (resource_type, target) in targets {
+        let path = project_dir.join(&target);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let Ok(mut config) = serde_json::from_str::<Value>(&content) else {
+            continue;
+        };
+        let stale = stale_config_names(&config, resource_type, lockfile);
+        if stale.is_empty() {
+            continue;
+        }
+        let keys = strip_config(&mut config, resource_type, &stale);
+        let content = serde_json::to_string_pretty(&config)?;
+        crate::utils::fs::atomic_write(&path, content.as_bytes())
+            .with_context(|| format!("Failed to update {}", path.display()))?;
+        pruned.extend(keys.into_iter().map(|key| format!("{target}: {key}")));
+    }
+
+    pruned.sort();
+    Ok(pruned)
+}
+
+/// Names of AGPM-managed entries in a merged config that `lockfile` does not record.
+///
+/// MCP servers are matched by their key, hooks by `_agpm.dependency_name`.
+fn stale_config_names(
+    config: &Value,
+    resource_type: ResourceType,
+    lockfile: &LockFile,
+) -> Vec<String> {
+    let installed =
+        lockfile.get_resources(&resource_type).iter().filter(|r| r.install != Some(false));
+    let mut stale = Vec::new();
+    match resource_type {
+        ResourceType::McpServer => {
+            let known: HashSet<&str> = installed.map(|r| r.lookup_name()).collect();
+            for section in ["mcpServers", "mcp"] {
+                let Some(servers) = config.get(section).and_then(Value::as_object) else {
+                    continue;
+                };
+                for (name, server) in servers {
+                    let managed =
+                        server.pointer("/_agpm/managed").and_then(Value::as_bool).unwrap_or(false);
+                    if managed && !known.contains(name.as_str()) {
+                        stale.push(name.clone());
+                    }
+                }
+            }
+        }
+        ResourceType::Hook => {
+            let known: HashSet<&str> = installed.map(|r| r.name.as_str()).collect();
+            let hooks = config
+                .get("hooks")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flat_map(|events| events.values())
+                .filter_map(Value::as_array)
+                .flatten()
+                .filter_map(|group| group.get("hooks").and_then(Value::as_array))
+                .flatten();
+            for hook in hooks {
+                if let Some(name) = hook.pointer("/_agpm/dependency_name").and_then(Value::as_str)
+                    && !known.contains(name)
+                    && !stale.iter().any(|s| s == name)
+                {
+                    stale.push(name.to_string());
+                }
+            }
+        }
+        _ => {}
+    }
+    stale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::LockedResourceBuilder;
+    use serde_json::json;
+
+    fn mcp_server(name: &str) -> crate::lockfile::LockedResource {
+        LockedResourceBuilder::new(
+            format!("mcp-servers/{name}"),
+            format!("mcp-servers/{name}.json"),
+            String::new(),
+            ".mcp.json".to_string(),
+            ResourceType::McpServer,
+        )
+        .manifest_alias(Some(name.to_string()))
+        .build()
+    }
+
+    #[test]
+    fn test_stale_config_names_keeps_user_and_locked_entries() {
+        let lockfile = LockFile {
+            mcp_servers: vec![mcp_server("kept")],
+            ..LockFile::new()
+        };
+        let config = json!({
+            "mcpServers": {
+                "kept": { "command": "a", "_agpm": { "managed": true } },
+                "removed": { "command": "b", "_agpm": { "managed": true } },
+                "mine": { "command": "c" }
+            }
+        });
+        assert_eq!(
+            stale_config_names(&config, ResourceType::McpServer, &lockfile),
+            vec!["removed".to_string()]
+        );
+
+        let hooks = json!({
+            "hooks": { "PreToolUse": [{ "matcher": "Bash", "hooks": [
+                { "type": "command", "command": "x", "_agpm": { "dependency_name": "hooks/old" } },
+                { "type": "command", "command": "y" }
+            ]}]}
+        });
+        assert_eq!(
+            stale_config_names(&hooks, ResourceType::Hook, &lockfile),
+            vec!["hooks/old".to_string()]
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Restore individual installed resources from the lockfile.
+//!
+//! This module provides the `reinstall` command. When an installed file was
+//! corrupted or edited by hand, `agpm reinstall <name>` copies the resource
+//! again from the worktree of its locked commit (creating the worktree when it
+//! is missing) and checks the result against the checksum in `agpm.lock`.
+//! Nothing is resolved, the lockfile is not rewritten, and other installed
+//! resources are left alone.
+//!
+//! Hooks and MCP servers are fragments merged into a shared configuration file.
+//! Reinstalling one re-applies the locked fragments of its file, which restores
+//! the named fragment; fragments that match their locked content come out
+//! unchanged.
+//!
+//! Names are matched against the manifest names of direct dependencies and the
+//! lockfile names of all entries, so transitive dependencies can be restored
+//! too.
+//!
+//! # Examples
+//!
+//! ```bash
+//! agpm reinstall code-reviewer
+//! agpm reinstall code-reviewer rust-style
+//! ```
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use colored::Colorize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::cache::Cache;
+use crate::core::ResourceType;
+use crate::installer::{ResourceFilter, install_resources};
+use crate::lockfile::{LockFile, LockedResource};
+use crate::manifest::{Manifest, find_manifest_with_optional};
+
+/// Command to restore installed resources from their locked commits.
+#[derive(Args, Debug)]
+pub struct ReinstallCommand {
+    /// Names of the resources to reinstall
+    #[arg(required = true, value_name = "NAME")]
+    names: Vec<String>,
+}
+
+impl ReinstallCommand {
+    /// Execute the reinstall command with an optional manifest path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no lockfile, a name matches no locked
+    /// resource, a resource cannot be installed, or a restored file does not
+    /// match its locked checksum.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let project_dir = manifest_path.parent().unwrap_or_else(|| Path::new("."));
+        let lockfile_path = project_dir.join("agpm.lock");
+        if !lockfile_path.exists() {
+            bail!("No lockfile found. Run 'agpm install' first.");
+        }
+
+        let manifest = Manifest::load(&manifest_path)?;
+        let lockfile = LockFile::load(&lockfile_path)?;
+        let selected = select_resources(&lockfile, &self.names)?;
+        let cache = Cache::new()?;
+
+        let files: HashSet<_> =
+            selected.iter().filter(|entry| !is_merged(entry)).map(|entry| entry.id()).collect();
+        if !files.is_empty() {
+            let lockfile = Arc::new(lockfile.clone());
+            let results = install_resources(
+                ResourceFilter::Changed(files),
+                &lockfile,
+                &manifest,
+                project_dir,
+                cache.clone(),
+                false,
+                None,
+                None,
+                false,
+                None,
+                false,
+                None,
+            )
+            .await?;
+            for (id, checksum) in &results.checksums {
+                let Some(entry) = selected.iter().find(|entry| entry.id() == *id) else {
+                    continue;
+                };
+                if !entry.checksum.is_empty() && *checksum != entry.checksum {
+                    bail!(
+                        "Reinstalled {} does not match its locked checksum (expected {}, got {checksum})\n\n\
+                         Hint: The lockfile may be out of date. Run 'agpm install' to refresh it.",
+                        entry.installed_at,
+                        entry.checksum
+                    );
+                }
+            }
+        }
+
+        if selected.iter().any(|entry| entry.resource_type == ResourceType::Hook) {
+            let settings_path = project_dir.join(
+                manifest
+                    .get_merge_target("claude-code", ResourceType::Hook)
+                    .unwrap_or_else(|| ".claude/settings.local.json".into()),
+            );
+            crate::hooks::install_hooks(&lockfile, project_dir, &settings_path, &cache).await?;
+        }
+
+        let tools: HashSet<&str> = selected
+            .iter()
+            .filter(|entry| entry.resource_type == ResourceType::McpServer)
+            .map(|entry| entry.tool.as_deref().unwrap_or("claude-code"))
+            .collect();
+        for tool in tools {
+            reapply_mcp_servers(&lockfile, &manifest, project_dir, &cache, tool).await?;
+        }
+
+        for entry in &selected {
+            let target = if is_merged(entry) {
+                format!("{} fragment", entry.resource_type)
+            } else {
+                entry.installed_at.clone()
+            };
+            println!("{} Reinstalled {} ({target})", "✓".green(), entry.display_name());
+        }
+        Ok(())
+    }
+}
+
+/// Whether the resource is merged into a shared configuration file.
+fn is_merged(entry: &LockedResource) -> bool {
+    matches!(entry.resource_type, ResourceType::Hook | ResourceType::McpServer)
+}
+
+/// Find the locked entries matching `names`, failing on the first unknown name.
+fn select_resources(lockfile: &LockFile, names: &[String]) -> Result<Vec<LockedResource>> {
+    let mut selected: Vec<LockedResource> = Vec::new();
+    for name in names {
+        let matches: Vec<&LockedResource> = lockfile
+            .all_resources()
+            .into_iter()
+            .filter(|entry| entry.manifest_alias.as_deref() == Some(name) || entry.name == *name)
+            .collect();
+        if matches.is_empty() {
+            bail!("No resource named '{name}' in agpm.lock");
+        }
+        for entry in matches {
+            if entry.install == Some(false) {
+                bail!(
+                    "'{name}' is not installed (install = false), so there is nothing to restore"
+                );
+            }
+            if !selected.iter().any(|s| s.id() == entry.id()) {
+                selected.push(entry.clone());
+            }
+        }
+    }
+    Ok(selected)
+}
+
+/// Re-apply the locked MCP servers of one tool to its configuration file.
+async fn reapply_mcp_servers(
+    lockfile: &LockFile,
+    manifest: &Manifest,
+    project_dir: &Path,
+    cache: &Cache,
+    tool: &str,
+) -> Result<()> {
+    use crate::mcp::handlers::McpHandler;
+
+    let Some(handler) = crate::mcp::handlers::get_mcp_handler(tool) else {
+        return Ok(());
+    };
+    let artifact_base = manifest.get_tool_base_path(tool).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Tool '{tool}' is not configured. Please define it in [default-tools] section."
+        )
+    })?;
+    let servers: Vec<LockedResource> = lockfile
+        .mcp_servers
+        .iter()
+        .filter(|s| s.install != Some(false) && s.tool.as_deref().unwrap_or("claude-code") == tool)
+        .cloned()
+        .collect();
+    handler
+        .configure_mcp_servers(
+            project_dir,
+            &project_dir.join(artifact_base),
+            &servers,
+            cache,
+            manifest,
+        )
+        .await
+        .with_context(|| format!("Failed to configure MCP servers for artifact type '{tool}'"))?;
+    Ok(())
+}
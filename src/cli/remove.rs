@@ -30,17 +30,25 @@
 //! ```bash
 //! agpm remove source old-repo --force
 //! ```
+//!
+//! Preview what would be deleted without changing anything:
+//! ```bash
+//! agpm remove dep agent code-reviewer --dry-run
+//! ```
 
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Result, anyhow};
 use clap::{Args, Subcommand};
 use colored::Colorize;
 
 use crate::core::ResourceType;
+use crate::lockfile::LockFile;
 use crate::manifest::{Manifest, find_manifest_with_optional};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 mod helpers;
+pub(super) mod plan;
 use helpers::*;
+use plan::RemovalPlan;
 
 /// Command to remove sources and dependencies from a AGPM project.
 #[derive(Args)]
@@ -48,6 +56,12 @@ pub struct RemoveCommand {
     /// The specific remove operation to perform
     #[command(subcommand)]
     command: RemoveSubcommand,
+
+    /// Show the manifest entry, installed files, lockfile entries and merged config
+    /// that would be removed, plus newly orphaned transitive dependencies, without
+    /// changing anything
+    #[arg(long, global = true)]
+    dry_run: bool,
 }
 
 /// Subcommands for the remove command.
@@ -140,48 +154,93 @@ impl RemoveCommand {
     ///     command: RemoveSubcommand::Source {
     ///         name: "my-source".to_string(),
     ///         force: false,
-    ///     }
+    ///     },
+    ///     dry_run: false,
     /// };
     ///
     /// cmd.execute_with_manifest_path(None).await?;
     /// ```
     pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let dry_run = self.dry_run;
         match self.command {
             RemoveSubcommand::Source {
                 name,
                 force,
-            } => remove_source_with_manifest_path(&name, force, manifest_path).await,
+            } => remove_source_with_manifest_path(&name, force, dry_run, manifest_path).await,
             RemoveSubcommand::Dep(dep_command) => match dep_command {
                 RemoveDependencySubcommand::Agent {
                     name,
-                } => remove_dependency_with_manifest_path(&name, "agent", manifest_path).await,
+                } => {
+                    remove_dependency_with_manifest_path(&name, "agent", dry_run, manifest_path)
+                        .await
+                }
                 RemoveDependencySubcommand::Snippet {
                     name,
-                } => remove_dependency_with_manifest_path(&name, "snippet", manifest_path).await,
+                } => {
+                    remove_dependency_with_manifest_path(&name, "snippet", dry_run, manifest_path)
+                        .await
+                }
                 RemoveDependencySubcommand::Command {
                     name,
-                } => remove_dependency_with_manifest_path(&name, "command", manifest_path).await,
+                } => {
+                    remove_dependency_with_manifest_path(&name, "command", dry_run, manifest_path)
+                        .await
+                }
                 RemoveDependencySubcommand::McpServer {
                     name,
-                } => remove_dependency_with_manifest_path(&name, "mcp-server", manifest_path).await,
+                } => {
+                    remove_dependency_with_manifest_path(
+                        &name,
+                        "mcp-server",
+                        dry_run,
+                        manifest_path,
+                    )
+                    .await
+                }
                 RemoveDependencySubcommand::Script {
                     name,
-                } => remove_dependency_with_manifest_path(&name, "script", manifest_path).await,
+                } => {
+                    remove_dependency_with_manifest_path(&name, "script", dry_run, manifest_path)
+                        .await
+                }
                 RemoveDependencySubcommand::Hook {
                     name,
-                } => remove_dependency_with_manifest_path(&name, "hook", manifest_path).await,
+                } => {
+                    remove_dependency_with_manifest_path(&name, "hook", dry_run, manifest_path)
+                        .await
+                }
                 RemoveDependencySubcommand::Skill {
                     name,
-                } => remove_dependency_with_manifest_path(&name, "skill", manifest_path).await,
+                } => {
+                    remove_dependency_with_manifest_path(&name, "skill", dry_run, manifest_path)
+                        .await
+                }
             },
         }
     }
 }
 
+/// Load the project lockfile a removal plan is built from, if there is one.
+///
+/// A dry run never regenerates or backs up an invalid lockfile.
+fn load_lockfile_for_removal(
+    manifest: &Manifest,
+    project_root: &Path,
+    dry_run: bool,
+) -> Result<Option<LockFile>> {
+    if !project_root.join("agpm.lock").exists() {
+        return Ok(None);
+    }
+    let command_context =
+        crate::cli::common::CommandContext::new(manifest.clone(), project_root.to_path_buf())?;
+    command_context.load_lockfile_with_regeneration(!dry_run, "remove")
+}
+
 /// Remove a source from the manifest with optional manifest path
 async fn remove_source_with_manifest_path(
     name: &str,
     force: bool,
+    dry_run: bool,
     manifest_path: Option<PathBuf>,
 ) -> Result<()> {
     // Find manifest file
@@ -216,45 +275,24 @@ async fn remove_source_with_manifest_path(
         }
     }
 
-    // Remove the source
-    manifest.sources.remove(name);
+    // Plan the removal from the current lockfile so a dry run shows exactly what happens
+    let project_root = manifest_path.parent().unwrap();
+    let lockfile = load_lockfile_for_removal(&manifest, project_root, dry_run)?;
+    let plan = RemovalPlan::for_source(name, lockfile.as_ref(), project_root);
+    if dry_run {
+        plan.print_preview();
+        return Ok(());
+    }
 
-    // Save the manifest
+    // Remove the source and save the manifest
+    manifest.sources.remove(name);
     manifest.save(&manifest_path)?;
 
-    // Update lockfile to remove entries from this source
-    let lockfile_path = manifest_path.parent().unwrap().join("agpm.lock");
-
-    if lockfile_path.exists() {
-        // Create command context for enhanced lockfile loading
-        let project_root = manifest_path.parent().unwrap();
-        let command_context =
-            crate::cli::common::CommandContext::new(manifest.clone(), project_root.to_path_buf())?;
-
-        // Use enhanced lockfile loading with automatic regeneration
-        let mut lockfile = match command_context.load_lockfile_with_regeneration(true, "remove")? {
-            Some(lockfile) => lockfile,
-            None => {
-                // Lockfile was invalid and has been removed, nothing to update
-                return Ok(());
-            }
-        };
-
-        // Find and remove installed files from this source
-        let installed_paths = collect_installed_paths_for_source(&lockfile, name);
-        delete_installed_files(project_root, &installed_paths).await?;
-
-        // Remove the source from lockfile
-        remove_source_from_lockfile(&mut lockfile, name);
-
-        // Save the updated lockfile
-        lockfile.save(&lockfile_path)?;
-
-        // Update private lockfile - remove entries for removed resources
-        update_private_lockfile(project_root, &installed_paths, ResourceType::Agent)?;
-    }
+    // Delete installed files and drop the source's entries from the lockfiles
+    plan.execute(project_root, lockfile, &project_root.join("agpm.lock")).await?;
 
     println!("{}", format!("Removed source '{name}'").green());
+    plan.print_orphan_notice();
 
     Ok(())
 }
@@ -263,6 +301,7 @@ async fn remove_source_with_manifest_path(
 async fn remove_dependency_with_manifest_path(
     name: &str,
     dep_type: &str,
+    dry_run: bool,
     manifest_path: Option<PathBuf>,
 ) -> Result<()> {
     // Find manifest file
@@ -273,10 +312,8 @@ async fn remove_dependency_with_manifest_path(
     let resource_type: ResourceType =
         dep_type.parse().map_err(|_| anyhow!("Invalid dependency type: {dep_type}"))?;
 
-    // Get the dependencies for this resource type and check if it exists
-    let dependencies = get_dependencies_for_type_mut(&mut manifest, resource_type);
-
-    if !dependencies.contains_key(name) {
+    // Check that the dependency exists
+    if !get_dependencies_for_type(&manifest, resource_type).contains_key(name) {
         let type_display = dep_type.replace('-', " ");
         return Err(anyhow!(
             "{} '{}' not found in manifest",
@@ -286,71 +323,25 @@ async fn remove_dependency_with_manifest_path(
         ));
     }
 
-    // Remove the dependency
-    let removed = dependencies.remove(name).is_some();
-
-    if !removed {
-        return Err(anyhow!("{} '{}' not found in manifest", dep_type.replace('-', " "), name));
+    // Plan the removal from the current lockfile so a dry run shows exactly what happens
+    let project_root = manifest_path.parent().unwrap();
+    let lockfile = load_lockfile_for_removal(&manifest, project_root, dry_run)?;
+    let plan = RemovalPlan::for_dependency(resource_type, name, lockfile.as_ref(), project_root);
+    if dry_run {
+        plan.print_preview();
+        return Ok(());
     }
 
-    // Save the manifest
+    // Remove the dependency and save the manifest
+    get_dependencies_for_type_mut(&mut manifest, resource_type).remove(name);
     manifest.save(&manifest_path)?;
 
     let dep_type_display = dep_type.replace('-', " ");
     println!("{}", format!("Removed {dep_type_display} '{name}'").green());
 
-    let project_root = manifest_path.parent().unwrap();
-
-    // For MCP servers and hooks, also update the settings file
-    let settings_path = project_root.join(".claude/settings.local.json");
-    update_settings_file(&settings_path, name, resource_type)?;
-
-    // Update lockfile and remove installed files
-    let lockfile_path = manifest_path.parent().unwrap().join("agpm.lock");
-    if lockfile_path.exists() {
-        // Create command context for enhanced lockfile loading
-        let project_root = manifest_path.parent().unwrap();
-        let command_context =
-            crate::cli::common::CommandContext::new(manifest.clone(), project_root.to_path_buf())?;
-
-        // Use enhanced lockfile loading with automatic regeneration
-        let mut lockfile = match command_context.load_lockfile_with_regeneration(true, "remove")? {
-            Some(lockfile) => lockfile,
-            None => {
-                // Lockfile was invalid and has been removed, nothing to update
-                return Ok(());
-            }
-        };
-
-        // Find the installed file path and remove it
-        let installed_path =
-            get_installed_path_from_lockfile(&lockfile, name, resource_type, project_root);
-
-        // Delete the installed file/directory if it exists
-        if let Some(path) = installed_path
-            && path.exists()
-        {
-            // Skills are directories, other resources are files
-            if resource_type == ResourceType::Skill {
-                tokio::fs::remove_dir_all(&path).await.with_context(|| {
-                    format!("Failed to remove installed skill directory: {}", path.display())
-                })?;
-            } else {
-                tokio::fs::remove_file(&path).await.with_context(|| {
-                    format!("Failed to remove installed file: {}", path.display())
-                })?;
-            }
-        }
-
-        // Remove the dependency from the appropriate section
-        remove_from_lockfile(&mut lockfile, name, resource_type);
-
-        // Save the updated lockfile
-        lockfile.save(&lockfile_path)?;
-
-        // Update private lockfile - remove entry for this resource
-        update_private_lockfile(project_root, &[name.to_string()], resource_type)?;
-    }
+    // Delete installed files, strip merged MCP/hook config and update the lockfiles
+    plan.execute(project_root, lockfile, &project_root.join("agpm.lock")).await?;
+    plan.print_orphan_notice();
 
     Ok(())
 }
@@ -359,7 +350,6 @@ async fn remove_dependency_with_manifest_path(
 mod tests {
     use super::*;
 
-    use crate::lockfile::LockFile;
     use std::fs;
     use tempfile::TempDir;
 
@@ -383,9 +373,13 @@ existing = "https://github.com/test/repo.git"
         // Change to temp directory
 
         // Try to remove non-existent source
-        let result =
-            remove_source_with_manifest_path("nonexistent", false, Some(manifest_path.clone()))
-                .await;
+        let result = remove_source_with_manifest_path(
+            "nonexistent",
+            false,
+            false,
+            Some(manifest_path.clone()),
+        )
+        .await;
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
         Ok(())
@@ -410,7 +404,8 @@ another-source = "https://github.com/another/repo.git"
         fs::write(&manifest_path, manifest_content).unwrap();
 
         // Remove a source
-        remove_source_with_manifest_path("test-source", false, Some(manifest_path.clone())).await?;
+        remove_source_with_manifest_path("test-source", false, false, Some(manifest_path.clone()))
+            .await?;
 
         // Verify it was removed
         let manifest = Manifest::load(&manifest_path).unwrap();
@@ -439,9 +434,13 @@ test-agent = { source = "used-source", path = "agents/test.md", version = "v1.0.
         fs::write(&manifest_path, manifest_content).unwrap();
 
         // Try to remove a source in use without force
-        let result =
-            remove_source_with_manifest_path("used-source", false, Some(manifest_path.clone()))
-                .await;
+        let result = remove_source_with_manifest_path(
+            "used-source",
+            false,
+            false,
+            Some(manifest_path.clone()),
+        )
+        .await;
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("still being used"));
         Ok(())
@@ -467,7 +466,8 @@ test-agent = { source = "used-source", path = "agents/test.md", version = "v1.0.
         fs::write(&manifest_path, manifest_content).unwrap();
 
         // Remove a source in use with force
-        remove_source_with_manifest_path("used-source", true, Some(manifest_path.clone())).await?;
+        remove_source_with_manifest_path("used-source", true, false, Some(manifest_path.clone()))
+            .await?;
 
         // Verify the source was removed from the raw TOML
         // (can't use Manifest::load since the dependency still references the removed source)
@@ -495,6 +495,7 @@ test-agent = { source = "used-source", path = "agents/test.md", version = "v1.0.
         let result = remove_dependency_with_manifest_path(
             "nonexistent",
             "agent",
+            false,
             Some(manifest_path.clone()),
         )
         .await;
@@ -522,8 +523,13 @@ another-agent = "../test/another.md"
         fs::write(&manifest_path, manifest_content).unwrap();
 
         // Remove an agent
-        remove_dependency_with_manifest_path("test-agent", "agent", Some(manifest_path.clone()))
-            .await?;
+        remove_dependency_with_manifest_path(
+            "test-agent",
+            "agent",
+            false,
+            Some(manifest_path.clone()),
+        )
+        .await?;
 
         // Verify it was removed
         let manifest = Manifest::load(&manifest_path).unwrap();
@@ -553,6 +559,7 @@ test-snippet = "../test/snippet.md"
         remove_dependency_with_manifest_path(
             "test-snippet",
             "snippet",
+            false,
             Some(manifest_path.clone()),
         )
         .await?;
@@ -584,6 +591,7 @@ test-command = "../test/command.md"
         remove_dependency_with_manifest_path(
             "test-command",
             "command",
+            false,
             Some(manifest_path.clone()),
         )
         .await?;
@@ -614,6 +622,7 @@ test-server = "../local/mcp-servers/test-server.json"
         remove_dependency_with_manifest_path(
             "test-server",
             "mcp-server",
+            false,
             Some(manifest_path.clone()),
         )
         .await?;
@@ -643,8 +652,13 @@ another-script = "../test/another.sh"
         fs::write(&manifest_path, manifest_content).unwrap();
 
         // Remove a script
-        remove_dependency_with_manifest_path("test-script", "script", Some(manifest_path.clone()))
-            .await?;
+        remove_dependency_with_manifest_path(
+            "test-script",
+            "script",
+            false,
+            Some(manifest_path.clone()),
+        )
+        .await?;
 
         // Verify it was removed
         let manifest = Manifest::load(&manifest_path).unwrap();
@@ -673,8 +687,13 @@ post-commit = "../test/another_hook.json"
         fs::write(&manifest_path, manifest_content).unwrap();
 
         // Remove a hook
-        remove_dependency_with_manifest_path("pre-commit", "hook", Some(manifest_path.clone()))
-            .await?;
+        remove_dependency_with_manifest_path(
+            "pre-commit",
+            "hook",
+            false,
+            Some(manifest_path.clone()),
+        )
+        .await?;
 
         // Verify it was removed
         let manifest = Manifest::load(&manifest_path).unwrap();
@@ -702,6 +721,7 @@ post-commit = "../test/another_hook.json"
         let result = remove_dependency_with_manifest_path(
             "test",
             "invalid-type",
+            false,
             Some(manifest_path.clone()),
         )
         .await;
@@ -752,11 +772,17 @@ test-agent = "../test/agent.md"
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
         lockfile.save(&lockfile_path).unwrap();
         // Remove an agent (should update lockfile)
-        remove_dependency_with_manifest_path("test-agent", "agent", Some(manifest_path.clone()))
-            .await?;
+        remove_dependency_with_manifest_path(
+            "test-agent",
+            "agent",
+            false,
+            Some(manifest_path.clone()),
+        )
+        .await?;
 
         // Verify the agent was removed from lockfile
         let updated_lockfile = LockFile::load(&lockfile_path).unwrap();
@@ -795,9 +821,13 @@ test-hook = { source = "used-source", path = "hooks/test.json", version = "v1.0.
         fs::write(&manifest_path, manifest_content).unwrap();
 
         // Try to remove source without force
-        let result =
-            remove_source_with_manifest_path("used-source", false, Some(manifest_path.clone()))
-                .await;
+        let result = remove_source_with_manifest_path(
+            "used-source",
+            false,
+            false,
+            Some(manifest_path.clone()),
+        )
+        .await;
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(err_msg.contains("agent 'test-agent'"));
@@ -832,6 +862,7 @@ test = "https://github.com/test/repo.git"
                 name: "test".to_string(),
                 force: false,
             },
+            dry_run: false,
         };
         cmd.execute_with_manifest_path(Some(manifest_path.clone())).await?;
         Ok(())
@@ -893,6 +924,7 @@ test-snippet = { source = "test-source", path = "snippets/test.md", version = "v
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         // Add snippet with installed path (relative to project directory)
@@ -916,6 +948,7 @@ test-snippet = { source = "test-source", path = "snippets/test.md", version = "v
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         lockfile.save(&lockfile_path).unwrap();
@@ -939,6 +972,7 @@ test-snippet = { source = "test-source", path = "snippets/test.md", version = "v
         remove_dependency_with_manifest_path(
             "test-snippet",
             "snippet",
+            false,
             Some(manifest_path.clone()),
         )
         .await
@@ -950,7 +984,7 @@ test-snippet = { source = "test-source", path = "snippets/test.md", version = "v
         assert!(agent_file.exists(), "Agent file should still exist after snippet removal");
 
         // Remove the source (should remove remaining agent)
-        remove_source_with_manifest_path("test-source", true, Some(manifest_path.clone()))
+        remove_source_with_manifest_path("test-source", true, false, Some(manifest_path.clone()))
             .await
             .unwrap();
 
@@ -1010,6 +1044,7 @@ test-hook = "../test/hook.json"
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
         lockfile.hooks.push(LockedResource {
             name: "test-hook".to_string(),
@@ -1031,11 +1066,17 @@ test-hook = "../test/hook.json"
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
         lockfile.save(&lockfile_path).unwrap();
         // Remove script
-        remove_dependency_with_manifest_path("test-script", "script", Some(manifest_path.clone()))
-            .await?;
+        remove_dependency_with_manifest_path(
+            "test-script",
+            "script",
+            false,
+            Some(manifest_path.clone()),
+        )
+        .await?;
 
         // Verify script was removed from lockfile
         let updated_lockfile = LockFile::load(&lockfile_path).unwrap();
@@ -1043,8 +1084,13 @@ test-hook = "../test/hook.json"
         assert_eq!(updated_lockfile.hooks.len(), 1);
 
         // Remove hook
-        remove_dependency_with_manifest_path("test-hook", "hook", Some(manifest_path.clone()))
-            .await?;
+        remove_dependency_with_manifest_path(
+            "test-hook",
+            "hook",
+            false,
+            Some(manifest_path.clone()),
+        )
+        .await?;
 
         // Verify hook was removed from lockfile
         let final_lockfile = LockFile::load(&lockfile_path).unwrap();
@@ -1103,6 +1149,7 @@ test-snippet = "../local/snippet.md"
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
         lockfile.snippets.push(LockedResource {
             name: "test-snippet".to_string(),
@@ -1124,12 +1171,14 @@ test-snippet = "../local/snippet.md"
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
         lockfile.save(&lockfile_path).unwrap();
         // Remove a snippet
         remove_dependency_with_manifest_path(
             "test-snippet",
             "snippet",
+            false,
             Some(manifest_path.clone()),
         )
         .await?;
@@ -1140,8 +1189,13 @@ test-snippet = "../local/snippet.md"
         assert_eq!(updated_lockfile.agents.len(), 1, "Agent should still be in lockfile");
 
         // Remove the agent
-        remove_dependency_with_manifest_path("test-agent", "agent", Some(manifest_path.clone()))
-            .await?;
+        remove_dependency_with_manifest_path(
+            "test-agent",
+            "agent",
+            false,
+            Some(manifest_path.clone()),
+        )
+        .await?;
 
         // Verify lockfile was updated again
         let updated_lockfile = LockFile::load(&lockfile_path).unwrap();
@@ -1149,7 +1203,8 @@ test-snippet = "../local/snippet.md"
         assert_eq!(updated_lockfile.sources.len(), 1, "Source should still be in lockfile");
 
         // Remove the source
-        remove_source_with_manifest_path("test-source", false, Some(manifest_path.clone())).await?;
+        remove_source_with_manifest_path("test-source", false, false, Some(manifest_path.clone()))
+            .await?;
 
         // Verify source was removed from lockfile
         let updated_lockfile = LockFile::load(&lockfile_path).unwrap();
@@ -1199,6 +1254,7 @@ test-server = "../mcp/test-server.json"
         remove_dependency_with_manifest_path(
             "test-server",
             "mcp-server",
+            false,
             Some(manifest_path.clone()),
         )
         .await?;
@@ -1247,8 +1303,13 @@ test-hook = "../hooks/test-hook.json"
         fs::write(&settings_path, settings_content).unwrap();
 
         // Remove hook
-        remove_dependency_with_manifest_path("test-hook", "hook", Some(manifest_path.clone()))
-            .await?;
+        remove_dependency_with_manifest_path(
+            "test-hook",
+            "hook",
+            false,
+            Some(manifest_path.clone()),
+        )
+        .await?;
 
         // Verify settings file was updated (test-hook removed but other-hook remains)
         let updated_settings = fs::read_to_string(&settings_path).unwrap();
@@ -1302,6 +1363,7 @@ test-script = "../test/script.sh"
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
         lockfile.save(&lockfile_path).unwrap();
 
@@ -1311,8 +1373,13 @@ test-script = "../test/script.sh"
         assert!(script_file.exists());
 
         // Remove script
-        remove_dependency_with_manifest_path("test-script", "script", Some(manifest_path.clone()))
-            .await?;
+        remove_dependency_with_manifest_path(
+            "test-script",
+            "script",
+            false,
+            Some(manifest_path.clone()),
+        )
+        .await?;
 
         // Verify script file was deleted
         assert!(!script_file.exists());
@@ -1322,4 +1389,94 @@ test-script = "../test/script.sh"
         assert_eq!(updated_lockfile.scripts.len(), 0);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_remove_hook_strips_managed_hooks_unless_dry_run() -> Result<()> {
+        use crate::lockfile::LockedResource;
+
+        let temp = TempDir::new().unwrap();
+        let manifest_path = temp.path().join("agpm.toml");
+        let settings_path = temp.path().join(".claude/settings.local.json");
+        fs::write(&manifest_path, "[hooks]\nguard = \"../hooks/guard.json\"\n").unwrap();
+
+        let mut lockfile = LockFile::new();
+        lockfile.hooks.push(LockedResource {
+            name: "hooks/guard".to_string(),
+            source: None,
+            url: None,
+            path: "../hooks/guard.json".to_string(),
+            version: None,
+            resolved_commit: None,
+            checksum: "sha256:test".to_string(),
+            installed_at: ".claude/settings.local.json".to_string(),
+            dependencies: vec![],
+            resource_type: crate::core::ResourceType::Hook,
+
+            tool: Some("claude-code".to_string()),
+            manifest_alias: Some("guard".to_string()),
+            context_checksum: None,
+            applied_patches: std::collections::BTreeMap::new(),
+            install: None,
+            variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            is_private: false,
+            approximate_token_count: None,
+            deprecated: false,
+        });
+        lockfile.save(&temp.path().join("agpm.lock")).unwrap();
+
+        std::fs::create_dir_all(temp.path().join(".claude")).unwrap();
+        let settings = r#"{
+  "hooks": {
+    "PreToolUse": [
+      {
+        "matcher": "Bash",
+        "hooks": [
+          { "type": "command", "command": "guard.sh", "_agpm": { "managed": true, "dependency_name": "hooks/guard", "source": "", "version": "", "installed_at": "" } },
+          { "type": "command", "command": "user.sh" }
+        ]
+      }
+    ],
+    "Stop": [
+      {
+        "matcher": "",
+        "hooks": [
+          { "type": "command", "command": "guard.sh", "_agpm": { "managed": true, "dependency_name": "hooks/guard", "source": "", "version": "", "installed_at": "" } }
+        ]
+      }
+    ]
+  }
+}"#;
+        fs::write(&settings_path, settings).unwrap();
+
+        let plan = RemovalPlan::for_dependency(
+            ResourceType::Hook,
+            "guard",
+            Some(&LockFile::load(&temp.path().join("agpm.lock"))?),
+            temp.path(),
+        );
+        assert!(plan.installed_paths.is_empty(), "the shared settings file is never deleted");
+        assert_eq!(
+            plan.config_contributions,
+            vec![
+                ".claude/settings.local.json: hooks.PreToolUse",
+                ".claude/settings.local.json: hooks.Stop"
+            ]
+        );
+
+        remove_dependency_with_manifest_path("guard", "hook", true, Some(manifest_path.clone()))
+            .await?;
+        assert_eq!(fs::read_to_string(&settings_path).unwrap(), settings);
+        assert!(Manifest::load(&manifest_path)?.hooks.contains_key("guard"));
+
+        remove_dependency_with_manifest_path("guard", "hook", false, Some(manifest_path.clone()))
+            .await?;
+        let updated: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&settings_path).unwrap()).unwrap();
+        let pre_tool_use = updated["hooks"]["PreToolUse"][0]["hooks"].as_array().unwrap();
+        assert_eq!(pre_tool_use.len(), 1);
+        assert_eq!(pre_tool_use[0]["command"], "user.sh");
+        assert!(updated["hooks"].get("Stop").is_none());
+        assert!(LockFile::load(&temp.path().join("agpm.lock"))?.hooks.is_empty());
+        Ok(())
+    }
 }
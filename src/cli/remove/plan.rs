@@ -0,0 +1,419 @@
+//! Removal plans shared by `remove` and `remove --dry-run`.
+//!
+//! A [`RemovalPlan`] lists everything one removal touches. `--dry-run` prints
+//! it and the real removal executes it, so the preview always matches what is
+//! actually deleted.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::Value;
+
+use crate::core::ResourceType;
+use crate::lockfile::{LockFile, LockedResource};
+
+use super::helpers::update_private_lockfile;
+
+/// What is being removed.
+#[derive(Debug, Clone)]
+enum Target {
+    /// A `[<type>]` manifest entry and every lockfile entry it produced.
+    Dependency(ResourceType, String),
+    /// A `[sources]` entry and every lockfile entry resolved from it.
+    Source(String),
+}
+
+/// A merged configuration file and the keys a removal strips from it.
+#[derive(Debug, Clone)]
+struct ConfigEdit {
+    /// Path relative to the project root.
+    path: String,
+    /// Whether MCP servers or hooks are stripped.
+    resource_type: ResourceType,
+    /// Names whose entries are stripped: MCP server keys and hook `dependency_name`s.
+    names: Vec<String>,
+    /// Human-readable keys that will be removed, e.g. `mcpServers.filesystem`.
+    keys: Vec<String>,
+}
+
+/// Everything one `remove` invocation deletes.
+#[derive(Debug, Clone)]
+pub struct RemovalPlan {
+    target: Target,
+    /// Manifest entry removed, e.g. `[agents] reviewer`.
+    pub manifest_entry: String,
+    /// Installed files and skill directories deleted, relative to the project root.
+    pub installed_paths: Vec<String>,
+    /// Lockfile entries dropped, as `<type> <name>`.
+    pub lockfile_entries: Vec<String>,
+    /// Keys stripped from merged configuration files, as `<file>: <key>`.
+    pub config_contributions: Vec<String>,
+    /// Transitive dependencies nothing needs after the removal, as `<type> <name>`.
+    /// They stay installed until the next `agpm install` prunes them.
+    pub orphaned: Vec<String>,
+    config_edits: Vec<ConfigEdit>,
+    removed_names: Vec<(ResourceType, String)>,
+}
+
+impl RemovalPlan {
+    /// Plan removing the `name` dependency of `resource_type`.
+    pub fn for_dependency(
+        resource_type: ResourceType,
+        name: &str,
+        lockfile: Option<&LockFile>,
+        project_root: &Path,
+    ) -> Self {
+        let mut plan = Self::build(
+            Target::Dependency(resource_type, name.to_string()),
+            format!("[{}] {name}", resource_type.to_plural()),
+            lockfile,
+            project_root,
+        );
+        // Older installs keyed MCP servers and hooks by manifest name in settings.local.json
+        if matches!(resource_type, ResourceType::McpServer | ResourceType::Hook) {
+            plan.add_config_edit(
+                project_root,
+                ".claude/settings.local.json",
+                resource_type,
+                &[name.to_string()],
+            );
+        }
+        plan
+    }
+
+    /// Plan removing the `name` source and everything resolved from it.
+    pub fn for_source(name: &str, lockfile: Option<&LockFile>, project_root: &Path) -> Self {
+        Self::build(
+            Target::Source(name.to_string()),
+            format!("[sources] {name}"),
+            lockfile,
+            project_root,
+        )
+    }
+
+    fn build(
+        target: Target,
+        manifest_entry: String,
+        lockfile: Option<&LockFile>,
+        project_root: &Path,
+    ) -> Self {
+        let mut plan = Self {
+            target,
+            manifest_entry,
+            installed_paths: Vec::new(),
+            lockfile_entries: Vec::new(),
+            config_contributions: Vec::new(),
+            orphaned: Vec::new(),
+            config_edits: Vec::new(),
+            removed_names: Vec::new(),
+        };
+        let Some(lockfile) = lockfile else {
+            return plan;
+        };
+
+        for resource_type in ResourceType::all() {
+            for resource in lockfile.get_resources(resource_type) {
+                if !plan.removes(*resource_type, resource) {
+                    continue;
+                }
+                plan.lockfile_entries.push(format!("{resource_type} {}", resource.name));
+                plan.removed_names.push((*resource_type, resource.name.clone()));
+
+                if matches!(resource_type, ResourceType::McpServer | ResourceType::Hook) {
+                    // installed_at is the shared merge target, never deleted as a whole
+                    let key = match resource_type {
+                        ResourceType::McpServer => resource.lookup_name(),
+                        _ => &resource.name,
+                    };
+                    plan.add_config_edit(
+                        project_root,
+                        &resource.installed_at,
+                        *resource_type,
+                        &[key.to_string()],
+                    );
+                } else if project_root.join(&resource.installed_at).exists()
+                    && !plan.installed_paths.contains(&resource.installed_at)
+                {
+                    plan.installed_paths.push(resource.installed_at.clone());
+                }
+            }
+        }
+
+        let mut remaining = lockfile.clone();
+        plan.apply_to_lockfile(&mut remaining);
+        let already_orphaned = unreachable_resources(lockfile);
+        plan.orphaned = unreachable_resources(&remaining)
+            .difference(&already_orphaned)
+            .map(|(resource_type, name, _)| format!("{resource_type} {name}"))
+            .collect();
+        plan.orphaned.sort();
+        plan
+    }
+
+    /// Whether `resource`, from the lockfile's `resource_type` section, belongs to the target.
+    fn removes(&self, resource_type: ResourceType, resource: &LockedResource) -> bool {
+        match &self.target {
+            Target::Dependency(target_type, name) => {
+                resource_type == *target_type && resource.lookup_name() == name
+            }
+            Target::Source(name) => resource.source.as_deref() == Some(name.as_str()),
+        }
+    }
+
+    /// Record the keys `names` contribute to the merged config at `path`, if any.
+    fn add_config_edit(
+        &mut self,
+        project_root: &Path,
+        path: &str,
+        resource_type: ResourceType,
+        names: &[String],
+    ) {
+        let Some(mut config) = read_json(&project_root.join(path)) else {
+            return;
+        };
+        let keys = strip_config(&mut config, resource_type, names);
+        if keys.is_empty() {
+            return;
+        }
+        let index = match self
+            .config_edits
+            .iter()
+            .position(|edit| edit.path == path && edit.resource_type == resource_type)
+        {
+            Some(index) => index,
+            None => {
+                self.config_edits.push(ConfigEdit {
+                    path: path.to_string(),
+                    resource_type,
+                    names: Vec::new(),
+                    keys: Vec::new(),
+                });
+                self.config_edits.len() - 1
+            }
+        };
+        let edit = &mut self.config_edits[index];
+        edit.names.extend_from_slice(names);
+        for key in keys {
+            if !edit.keys.contains(&key) {
+                self.config_contributions.push(format!("{path}: {key}"));
+                edit.keys.push(key);
+            }
+        }
+    }
+
+    /// Drop the planned entries (and, for sources, the source itself) from `lockfile`.
+    pub fn apply_to_lockfile(&self, lockfile: &mut LockFile) {
+        if let Target::Source(name) = &self.target {
+            lockfile.sources.retain(|s| &s.name != name);
+        }
+        for resource_type in ResourceType::all() {
+            lockfile.get_resources_mut(resource_type).retain(|r| !self.removes(*resource_type, r));
+        }
+    }
+
+    /// Delete installed files, strip merged configs and update the lockfiles.
+    ///
+    /// The manifest is saved by the caller; `lockfile` is the one the plan was built from.
+    pub async fn execute(
+        &self,
+        project_root: &Path,
+        lockfile: Option<LockFile>,
+        lockfile_path: &Path,
+    ) -> Result<()> {
+        for path_str in &self.installed_paths {
+            let path = project_root.join(path_str);
+            if path.is_dir() {
+                tokio::fs::remove_dir_all(&path).await.with_context(|| {
+                    format!("Failed to remove installed skill directory: {}", path.display())
+                })?;
+            } else if path.exists() {
+                tokio::fs::remove_file(&path).await.with_context(|| {
+                    format!("Failed to remove installed file: {}", path.display())
+                })?;
+            }
+        }
+
+        for edit in &self.config_edits {
+            let path = project_root.join(&edit.path);
+            if let Some(mut config) = read_json(&path) {
+                strip_config(&mut config, edit.resource_type, &edit.names);
+                let content = serde_json::to_string_pretty(&config)?;
+                crate::utils::fs::atomic_write(&path, content.as_bytes())
+                    .with_context(|| format!("Failed to update {}", path.display()))?;
+            }
+        }
+
+        if let Some(mut lockfile) = lockfile {
+            self.apply_to_lockfile(&mut lockfile);
+            lockfile.save(lockfile_path)?;
+
+            for resource_type in ResourceType::all() {
+                let mut names: Vec<String> = self
+                    .removed_names
+                    .iter()
+                    .filter(|(t, _)| t == resource_type)
+                    .map(|(_, name)| name.clone())
+                    .collect();
+                if let Target::Dependency(t, name) = &self.target
+                    && t == resource_type
+                {
+                    names.push(name.clone());
+                }
+                if !names.is_empty() {
+                    update_private_lockfile(project_root, &names, *resource_type)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Print the plan for `--dry-run`.
+    pub fn print_preview(&self) {
+        let what = match &self.target {
+            Target::Dependency(resource_type, name) => {
+                format!("{} '{name}'", resource_type.to_string().replace('-', " "))
+            }
+            Target::Source(name) => format!("source '{name}'"),
+        };
+        println!("{}", format!("Would remove {what}:").bold());
+        print_section("Manifest entry", std::slice::from_ref(&self.manifest_entry));
+        print_section("Installed files", &self.installed_paths);
+        print_section("Lockfile entries", &self.lockfile_entries);
+        print_section("Merged config", &self.config_contributions);
+        print_section(
+            "Orphaned transitive dependencies (pruned by the next install)",
+            &self.orphaned,
+        );
+        println!("{}", "Dry run: nothing was changed".yellow());
+    }
+
+    /// Print a reminder about orphans left behind after a real removal.
+    pub fn print_orphan_notice(&self) {
+        if !self.orphaned.is_empty() {
+            println!(
+                "{}",
+                format!(
+                    "{} transitive dependencies are no longer needed; run 'agpm install' to prune them: {}",
+                    self.orphaned.len(),
+                    self.orphaned.join(", ")
+                )
+                .yellow()
+            );
+        }
+    }
+}
+
+fn print_section(title: &str, items: &[String]) {
+    println!("  {title}:");
+    if items.is_empty() {
+        println!("    {}", "(none)".dimmed());
+    }
+    for item in items {
+        println!("    {item}");
+    }
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Remove the entries `names` contribute to a merged config and describe what was removed.
+///
+/// MCP servers are keyed by name under `mcpServers` (or `mcp` for OpenCode). Hooks are
+/// AGPM-managed commands tagged with `_agpm.dependency_name` inside each event's matcher
+/// groups; empty groups and events are dropped with them.
+pub(crate) fn strip_config(
+    config: &mut Value,
+    resource_type: ResourceType,
+    names: &[String],
+) -> Vec<String> {
+    let mut removed = Vec::new();
+    match resource_type {
+        ResourceType::McpServer => {
+            for section in ["mcpServers", "mcp"] {
+                if let Some(servers) = config.get_mut(section).and_then(Value::as_object_mut) {
+                    for name in names {
+                        if servers.remove(name).is_some() {
+                            removed.push(format!("{section}.{name}"));
+                        }
+                    }
+                }
+            }
+        }
+        ResourceType::Hook => {
+            let Some(events) = config.get_mut("hooks").and_then(Value::as_object_mut) else {
+                return removed;
+            };
+            for name in names {
+                if events.remove(name).is_some() {
+                    removed.push(format!("hooks.{name}"));
+                }
+            }
+            let names: HashSet<&str> = names.iter().map(String::as_str).collect();
+            let is_removed = |hook: &Value| {
+                hook.pointer("/_agpm/dependency_name")
+                    .and_then(Value::as_str)
+                    .is_some_and(|dep| names.contains(dep))
+            };
+            events.retain(|event, groups| {
+                let Some(groups) = groups.as_array_mut() else {
+                    return true;
+                };
+                let before = groups.len();
+                let mut stripped = false;
+                groups.retain_mut(|group| {
+                    let Some(hooks) = group.get_mut("hooks").and_then(Value::as_array_mut) else {
+                        return true;
+                    };
+                    let count = hooks.len();
+                    hooks.retain(|hook| !is_removed(hook));
+                    stripped |= hooks.len() != count;
+                    !hooks.is_empty() || count == 0
+                });
+                if stripped {
+                    removed.push(format!("hooks.{event}"));
+                }
+                !(groups.is_empty() && before > 0)
+            });
+        }
+        _ => {}
+    }
+    removed
+}
+
+/// Lockfile entries not reachable from any direct manifest dependency.
+pub(crate) fn unreachable_resources(
+    lockfile: &LockFile,
+) -> HashSet<(ResourceType, String, String)> {
+    let mut visited = HashSet::new();
+    let mut stack: Vec<(ResourceType, &LockedResource)> = ResourceType::all()
+        .iter()
+        .flat_map(|t| lockfile.get_resources(t).iter().map(|r| (*t, r)))
+        .filter(|(_, r)| r.is_direct_manifest())
+        .collect();
+    while let Some((resource_type, resource)) = stack.pop() {
+        if !visited.insert((resource_type, resource.name.clone(), resource.installed_at.clone())) {
+            continue;
+        }
+        for dep_ref in resource.parsed_dependencies() {
+            let candidates = lockfile.get_resources(&dep_ref.resource_type);
+            let found = candidates
+                .iter()
+                .find(|r| r.name == dep_ref.path && r.source == dep_ref.source)
+                .or_else(|| candidates.iter().find(|r| r.name == dep_ref.path));
+            if let Some(dep) = found {
+                stack.push((dep_ref.resource_type, dep));
+            }
+        }
+    }
+    ResourceType::all()
+        .iter()
+        .flat_map(|t| {
+            lockfile.get_resources(t).iter().map(|r| (*t, r.name.clone(), r.installed_at.clone()))
+        })
+        .filter(|key| !visited.contains(key))
+        .collect()
+}
@@ -0,0 +1,174 @@
+//! Check that the manifest resolves and print the resolved version map.
+//!
+//! This module provides the `resolve` command. By default it runs the same
+//! resolver as `install` without writing the lockfile or installing anything.
+//! With `--manifest-only` it resolves from tag listings and frontmatter read
+//! straight from the cached bare repositories, without creating worktrees,
+//! which makes it a fast "will this resolve?" check for CI. See
+//! [`crate::resolver::manifest_only`] for what that mode checks.
+//!
+//! The command exits with an error when resolution fails.
+//!
+//! # Examples
+//!
+//! ```bash
+//! agpm resolve --manifest-only
+//! agpm resolve --manifest-only --format json
+//! ```
+
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use colored::Colorize;
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::cache::Cache;
+use crate::manifest::{Manifest, find_manifest_with_optional};
+use crate::resolver::DependencyResolver;
+use crate::resolver::manifest_only::{ResolvedResource, resolve_manifest_only};
+
+/// Output format for `agpm resolve`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ResolveFormat {
+    /// One line per resolved resource.
+    #[default]
+    Text,
+    /// JSON object with `success` and a `resources` array.
+    Json,
+}
+
+/// Command to resolve dependencies without installing them.
+#[derive(Args, Debug)]
+pub struct ResolveCommand {
+    /// Resolve from tags and frontmatter only, without creating worktrees
+    #[arg(long)]
+    manifest_only: bool,
+
+    /// Output format (text, json)
+    #[arg(short = 'f', long, value_enum, default_value_t = ResolveFormat::Text)]
+    format: ResolveFormat,
+}
+
+/// The JSON report.
+#[derive(Debug, Serialize)]
+struct ResolveReport<'a> {
+    success: bool,
+    manifest_only: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    resources: &'a [ResolvedResource],
+}
+
+impl ResolveCommand {
+    /// Execute the resolve command with an optional manifest path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be loaded or does not resolve.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let manifest = Manifest::load(&manifest_path)?;
+
+        let result = if self.manifest_only {
+            resolve_manifest_only(&manifest, Cache::new()?).await
+        } else {
+            resolve_full(manifest).await
+        };
+
+        match (result, self.format) {
+            (Ok(resources), ResolveFormat::Json) => {
+                self.print_json(&resources, None)?;
+                Ok(())
+            }
+            (Ok(resources), ResolveFormat::Text) => {
+                let mode = if self.manifest_only {
+                    " (manifest only)"
+                } else {
+                    ""
+                };
+                println!("{} Resolved {} resource(s){mode}", "✓".green(), resources.len());
+                for resource in &resources {
+                    println!("  {}", describe(resource));
+                }
+                Ok(())
+            }
+            (Err(e), ResolveFormat::Json) => {
+                self.print_json(&[], Some(format!("{e:#}")))?;
+                Err(e)
+            }
+            (Err(e), ResolveFormat::Text) => Err(e.context("Dependency resolution failed")),
+        }
+    }
+
+    fn print_json(&self, resources: &[ResolvedResource], error: Option<String>) -> Result<()> {
+        let report = ResolveReport {
+            success: error.is_none(),
+            manifest_only: self.manifest_only,
+            error,
+            resources,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        Ok(())
+    }
+}
+
+/// Resolve with the full resolver, as `install` does, and list the lockfile it would write.
+async fn resolve_full(manifest: Manifest) -> Result<Vec<ResolvedResource>> {
+    let mut resolver = DependencyResolver::new(manifest, Cache::new()?).await?;
+    let lockfile = resolver.resolve().await?;
+    let mut resources: Vec<ResolvedResource> = lockfile
+        .all_resources()
+        .into_iter()
+        .map(|entry| ResolvedResource {
+            resource_type: entry.resource_type,
+            name: entry.name.clone(),
+            source: entry.source.clone(),
+            path: entry.path.clone(),
+            version: entry.version.clone(),
+            commit: entry.resolved_commit.clone(),
+        })
+        .collect();
+    resources.sort_by(|a, b| (a.resource_type, &a.name).cmp(&(b.resource_type, &b.name)));
+    Ok(resources)
+}
+
+/// One line of text output: `agent reviewer  community@v1.0.0 (4f3c2a1)`.
+fn describe(resource: &ResolvedResource) -> String {
+    let location = match (&resource.source, &resource.version) {
+        (Some(source), Some(version)) => format!("{source}@{version}"),
+        (Some(source), None) => source.clone(),
+        (None, _) => resource.path.clone(),
+    };
+    let commit = match &resource.commit {
+        Some(commit) if commit.len() >= 7 => format!(" ({})", &commit[..7]),
+        _ => String::new(),
+    };
+    format!("{} {}  {}{commit}", resource.resource_type, resource.name.cyan(), location)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ResourceType;
+
+    #[test]
+    fn test_describe() {
+        let mut resource = ResolvedResource {
+            resource_type: ResourceType::Agent,
+            name: "reviewer".to_string(),
+            source: Some("community".to_string()),
+            path: "agents/reviewer.md".to_string(),
+            version: Some("v1.0.0".to_string()),
+            commit: Some("4f3c2a1b9e".to_string()),
+        };
+        let line = describe(&resource);
+        assert!(line.starts_with("agent "), "{line}");
+        assert!(line.ends_with("  community@v1.0.0 (4f3c2a1)"), "{line}");
+
+        resource.source = None;
+        resource.version = None;
+        resource.commit = None;
+        assert!(describe(&resource).ends_with("  agents/reviewer.md"));
+    }
+}
@@ -0,0 +1,50 @@
+//! Export a JSON Schema for `agpm.toml`.
+//!
+//! This module provides the `schema` command, which prints a JSON Schema
+//! describing the manifest format. Point an editor's TOML language support at
+//! it to get completion and validation for `agpm.toml`. The schema is
+//! generated from the manifest types, so it always matches the running version
+//! of AGPM.
+//!
+//! # Examples
+//!
+//! Save the schema next to the manifest:
+//! ```bash
+//! agpm schema > agpm.schema.json
+//! ```
+//!
+//! Or write it directly:
+//! ```bash
+//! agpm schema --output agpm.schema.json
+//! ```
+
+use anyhow::{Context, Result};
+use clap::Args;
+use std::path::PathBuf;
+
+use crate::manifest::schema::manifest_schema;
+
+/// Command to print the manifest JSON Schema.
+#[derive(Args, Debug)]
+pub struct SchemaCommand {
+    /// Write the schema to this file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+impl SchemaCommand {
+    /// Execute the schema command.
+    pub async fn execute(self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&manifest_schema())
+            .context("Failed to serialize manifest schema")?;
+        match self.output {
+            Some(path) => tokio::fs::write(&path, format!("{json}\n"))
+                .await
+                .with_context(|| format!("Failed to write schema to {}", path.display())),
+            None => {
+                println!("{json}");
+                Ok(())
+            }
+        }
+    }
+}
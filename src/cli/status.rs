@@ -0,0 +1,254 @@
+//! Summarize project health at a glance.
+//!
+//! This module provides the `status` command, which aggregates several checks
+//! that otherwise live in separate commands into one offline report:
+//!
+//! - whether `agpm.lock` is in sync with the manifest (via the manifest's
+//!   dependency hash, the same check the install fast path uses)
+//! - the number of manifest dependencies by resource type
+//! - lockfile entries whose installed files are missing from disk
+//! - dependencies that track a branch and are therefore not reproducible
+//! - the size of the global Git cache
+//!
+//! No source repositories are fetched, so the command stays fast.
+//!
+//! # Examples
+//!
+//! ```bash
+//! agpm status
+//! agpm status --format json
+//! ```
+
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::cache::Cache;
+use crate::cli::list::installed::{InstallStatus, reconcile};
+use crate::lockfile::LockFile;
+use crate::manifest::{Manifest, ResourceDependency, find_manifest_with_optional};
+
+/// Output format for `agpm status`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum StatusFormat {
+    /// Human-readable summary.
+    #[default]
+    Text,
+    /// JSON object for scripts and tooling.
+    Json,
+}
+
+/// Command to summarize the state of the project.
+#[derive(Args, Debug)]
+pub struct StatusCommand {
+    /// Output format (text, json)
+    #[arg(short = 'f', long, value_enum, default_value_t = StatusFormat::Text)]
+    format: StatusFormat,
+
+    /// Environment overlay to merge (for programmatic use, not exposed as CLI arg)
+    ///
+    /// This field is populated from the global --env flag (or `AGPM_ENV`) via
+    /// execute_with_config and selects `agpm.<env>.toml`.
+    #[arg(skip)]
+    pub env: Option<String>,
+}
+
+/// Whether the lockfile matches the manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum LockfileState {
+    /// The lockfile was resolved from the current manifest.
+    UpToDate,
+    /// The manifest's dependencies changed since the lockfile was written.
+    OutOfDate,
+    /// The lockfile predates manifest hashing, so drift cannot be detected.
+    Unknown,
+    /// There is no lockfile yet.
+    Missing,
+}
+
+/// A dependency that follows a branch instead of a tag or commit.
+#[derive(Debug, Serialize)]
+struct BranchTracked {
+    #[serde(rename = "type")]
+    resource_type: String,
+    name: String,
+    branch: String,
+}
+
+/// The global Git cache.
+#[derive(Debug, Serialize)]
+struct CacheStatus {
+    path: String,
+    size_bytes: u64,
+}
+
+/// The full status report.
+#[derive(Debug, Serialize)]
+struct StatusReport {
+    manifest: String,
+    lockfile: LockfileState,
+    dependencies: BTreeMap<String, usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locked_resources: Option<usize>,
+    missing_files: Vec<String>,
+    branch_tracked: Vec<BranchTracked>,
+    cache: CacheStatus,
+}
+
+impl StatusCommand {
+    /// Execute the status command with an optional manifest path.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let project_dir = manifest_path.parent().unwrap_or_else(|| Path::new("."));
+
+        let (manifest, _) = Manifest::load_with_overlays(&manifest_path, self.env.as_deref())?;
+        let lockfile_path = project_dir.join("agpm.lock");
+        let lockfile = if lockfile_path.exists() {
+            Some(LockFile::load(&lockfile_path)?)
+        } else {
+            None
+        };
+
+        let cache = Cache::new()?;
+        let cache = CacheStatus {
+            path: cache.get_cache_location().display().to_string(),
+            size_bytes: cache.get_cache_size().await?,
+        };
+
+        let report = build_report(&manifest_path, &manifest, lockfile.as_ref(), project_dir, cache);
+        match self.format {
+            StatusFormat::Text => print_report(&report),
+            StatusFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        }
+        Ok(())
+    }
+}
+
+/// Compare the lockfile's recorded manifest hash with the current manifest.
+fn lockfile_state(manifest: &Manifest, lockfile: Option<&LockFile>) -> LockfileState {
+    let Some(lockfile) = lockfile else {
+        return LockfileState::Missing;
+    };
+    match &lockfile.manifest_hash {
+        Some(hash) if *hash == manifest.compute_dependency_hash() => LockfileState::UpToDate,
+        Some(_) => LockfileState::OutOfDate,
+        None => LockfileState::Unknown,
+    }
+}
+
+/// The branch a Git dependency follows, if it is not pinned to a tag or commit.
+fn tracked_branch(dep: &ResourceDependency) -> Option<String> {
+    if dep.is_local() || !dep.is_mutable() {
+        return None;
+    }
+    match dep {
+        ResourceDependency::Detailed(d) => {
+            d.branch.clone().or_else(|| d.version.clone()).or_else(|| Some("main".to_string()))
+        }
+        ResourceDependency::Simple(_) => None,
+    }
+}
+
+fn build_report(
+    manifest_path: &Path,
+    manifest: &Manifest,
+    lockfile: Option<&LockFile>,
+    project_dir: &Path,
+    cache: CacheStatus,
+) -> StatusReport {
+    let mut dependencies = BTreeMap::new();
+    let mut branch_tracked = Vec::new();
+    for (resource_type, name, dep) in manifest.all_resources() {
+        *dependencies.entry(resource_type.to_plural().to_string()).or_default() += 1;
+        if let Some(branch) = tracked_branch(dep) {
+            branch_tracked.push(BranchTracked {
+                resource_type: resource_type.to_string(),
+                name: name.to_string(),
+                branch,
+            });
+        }
+    }
+    branch_tracked.sort_by(|a, b| (&a.resource_type, &a.name).cmp(&(&b.resource_type, &b.name)));
+
+    let missing_files = lockfile
+        .map(|lockfile| {
+            reconcile(lockfile, manifest, project_dir, |_| true)
+                .into_iter()
+                .filter(|entry| entry.status == InstallStatus::Missing)
+                .map(|entry| entry.path)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    StatusReport {
+        manifest: manifest_path.display().to_string(),
+        lockfile: lockfile_state(manifest, lockfile),
+        dependencies,
+        locked_resources: lockfile.map(|lockfile| lockfile.all_resources().len()),
+        missing_files,
+        branch_tracked,
+        cache,
+    }
+}
+
+fn print_report(report: &StatusReport) {
+    println!("{:<16}{}", "Manifest:", report.manifest);
+
+    let lockfile = match report.lockfile {
+        LockfileState::UpToDate => "up to date".green(),
+        LockfileState::OutOfDate => "out of date (run 'agpm install')".yellow(),
+        LockfileState::Unknown => "unknown (lockfile has no manifest hash)".yellow(),
+        LockfileState::Missing => "missing (run 'agpm install')".red(),
+    };
+    println!("{:<16}{}", "Lockfile:", lockfile);
+
+    let total: usize = report.dependencies.values().sum();
+    if total == 0 {
+        println!("{:<16}none", "Dependencies:");
+    } else {
+        let by_type: Vec<_> =
+            report.dependencies.iter().map(|(kind, count)| format!("{kind}: {count}")).collect();
+        println!("{:<16}{} ({})", "Dependencies:", total, by_type.join(", "));
+    }
+
+    if let Some(locked) = report.locked_resources {
+        if report.missing_files.is_empty() {
+            println!("{:<16}{} locked, no missing files", "Installed:", locked);
+        } else {
+            println!(
+                "{:<16}{} locked, {}",
+                "Installed:",
+                locked,
+                format!("{} missing", report.missing_files.len()).red()
+            );
+            for path in &report.missing_files {
+                println!("  - {path}");
+            }
+        }
+    }
+
+    if report.branch_tracked.is_empty() {
+        println!("{:<16}none", "Branch-tracked:");
+    } else {
+        println!(
+            "{:<16}{}",
+            "Branch-tracked:",
+            format!("{} (not reproducible)", report.branch_tracked.len()).yellow()
+        );
+        for dep in &report.branch_tracked {
+            println!("  - {} '{}' tracks {}", dep.resource_type, dep.name, dep.branch);
+        }
+    }
+
+    println!(
+        "{:<16}{} at {}",
+        "Cache:",
+        crate::cli::cache::format_size(report.cache.size_bytes),
+        report.cache.path
+    );
+}
@@ -69,6 +69,7 @@ use crate::core::ResourceType;
 use crate::lockfile::patch_display::extract_patch_displays;
 use crate::lockfile::{LockFile, LockedResource};
 use crate::manifest::find_manifest_with_optional;
+use crate::utils::terminal::WARNING;
 
 /// Command to display dependency trees.
 ///
@@ -173,6 +174,13 @@ pub struct TreeCommand {
     /// ```
     #[arg(long)]
     detailed: bool,
+
+    /// Environment overlay to merge (for programmatic use, not exposed as CLI arg)
+    ///
+    /// This field is populated from the global --env flag (or `AGPM_ENV`) via
+    /// execute_with_config and selects `agpm.<env>.toml`.
+    #[arg(skip)]
+    pub env: Option<String>,
 }
 
 impl TreeCommand {
@@ -210,14 +218,15 @@ impl TreeCommand {
                 println!("{{}}");
             } else {
                 println!("No lockfile found.");
-                println!("⚠️  Run 'agpm install' first to generate agpm.lock");
+                println!("{WARNING}Run 'agpm install' first to generate agpm.lock");
             }
             return Ok(());
         }
 
         // Create command context for enhanced lockfile loading
         let manifest_path = project_dir.join("agpm.toml");
-        let manifest = crate::manifest::Manifest::load(&manifest_path)?;
+        let manifest =
+            crate::manifest::Manifest::load_for_env(&manifest_path, self.env.as_deref())?;
         let command_context =
             crate::cli::common::CommandContext::new(manifest, project_dir.to_path_buf())?;
 
@@ -231,7 +240,7 @@ impl TreeCommand {
                 } else {
                     println!("No lockfile found.");
                     println!(
-                        "⚠️  Lockfile was invalid and has been removed. Run 'agpm install' to regenerate it."
+                        "{WARNING}Lockfile was invalid and has been removed. Run 'agpm install' to regenerate it."
                     );
                 }
                 return Ok(());
@@ -1061,6 +1070,7 @@ mod tests {
             mcp_servers: false,
             invert: false,
             detailed: false,
+            env: None,
         }
     }
 
@@ -8,7 +8,8 @@
 //! # Features
 //!
 //! - **Constraint-Aware Updates**: Respects version constraints in the manifest
-//! - **Selective Updates**: Can update specific dependencies by name
+//! - **Selective Updates**: Can update specific dependencies by name, rewriting
+//!   only their lockfile entries
 //! - **Dry Run Mode**: Preview changes without actually updating
 //! - **Dependency Resolution**: Ensures all dependencies remain compatible
 //! - **Lockfile Updates**: Updates the lockfile with new resolved versions
@@ -32,6 +33,16 @@
 //! agpm update --dry-run
 //! ```
 //!
+//! Pick which available updates to apply:
+//! ```bash
+//! agpm update --interactive
+//! ```
+//!
+//! Refresh branch-tracked dependencies, leaving tag- and commit-pinned ones as locked:
+//! ```bash
+//! agpm update --branches-only
+//! ```
+//!
 //! Check for available updates (exit code 1 if updates available):
 //! ```bash
 //! agpm update --check
@@ -47,6 +58,11 @@
 //! agpm update --max-parallel 4
 //! ```
 //!
+//! Show what changed relative to an older lockfile:
+//! ```bash
+//! agpm update --since base/agpm.lock
+//! ```
+//!
 //! # Update Logic
 //!
 //! The update process follows these rules:
@@ -76,13 +92,18 @@
 use anyhow::{Context, Result};
 use clap::Args;
 use colored::Colorize;
+use std::io::{self, IsTerminal, Write};
 use std::path::PathBuf;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, BufReader};
 
-use crate::cache::Cache;
+use crate::cache::{Cache, CacheMetrics};
+use crate::cli::common::{DiffFormat, ExplainFormat};
 use crate::core::{OperationContext, ResourceIterator};
-use crate::lockfile::LockFile;
+use crate::git::FetchMode;
+use crate::lockfile::{LockFile, LockedResource};
 use crate::manifest::{Manifest, ResourceDependency, find_manifest_with_optional};
-use crate::resolver::DependencyResolver;
+use crate::resolver::{DependencyResolver, ResolutionStrategy};
+use crate::utils::terminal::{INFO, WARNING};
 
 /// Command-line arguments for the update command.
 ///
@@ -118,10 +139,13 @@ use crate::resolver::DependencyResolver;
 /// # Options
 ///
 /// - `dependencies`: Optional list of specific dependencies to update
+/// - `--branches-only`: Only update dependencies that track a branch
+/// - `--interactive`: Choose which available updates to apply
 /// - `--dry-run`: Preview updates without applying changes
 /// - `--check`: Show available updates in minimal format
 /// - `--force`: Ignore version constraints (dangerous)
 /// - `--backup`: Create lockfile backup before updating
+/// - `--rollback`: Restore the lockfile from before the last change
 /// - `--verbose`: Show detailed update progress
 /// - `--quiet`: Suppress all output except errors
 ///
@@ -150,6 +174,7 @@ use crate::resolver::DependencyResolver;
 /// All operations are designed to be atomic when possible, with rollback
 /// support through the backup option.
 #[derive(Debug, Args)]
+#[command(group(clap::ArgGroup::new("reports").multiple(true).args(["since", "concurrency_report"])))]
 pub struct UpdateCommand {
     /// Specific dependencies to update.
     ///
@@ -160,6 +185,23 @@ pub struct UpdateCommand {
     #[arg(value_name = "DEPENDENCY")]
     pub dependencies: Vec<String>,
 
+    /// Update only dependencies that track a branch.
+    ///
+    /// Re-resolves dependencies that follow a branch (`branch = "..."` or a
+    /// branch name as `version`) to the branch's latest commit. Entries pinned
+    /// to a tag, version range or commit keep their locked versions.
+    #[arg(long, conflicts_with = "dependencies")]
+    pub branches_only: bool,
+
+    /// Choose which available updates to apply.
+    ///
+    /// Lists each dependency with an update as `current → available` and lets
+    /// you toggle entries before anything is installed. Only the selected
+    /// dependencies are re-resolved; the rest keep their locked versions.
+    /// Requires a terminal; in scripts, name the dependencies instead.
+    #[arg(long, conflicts_with_all = ["dependencies", "branches_only", "check"])]
+    pub interactive: bool,
+
     /// Preview updates without applying changes.
     ///
     /// Shows a detailed list of what would be updated, including version
@@ -189,6 +231,15 @@ pub struct UpdateCommand {
     #[arg(long)]
     pub backup: bool,
 
+    /// Roll back to the lockfile from before the last install or update.
+    ///
+    /// Restores the newest backup from `.agpm/backups/lockfiles/` (written
+    /// whenever `install` or `update` changes `agpm.lock`) and reinstalls the
+    /// resources that differ from it. The backup is consumed, so rolling back
+    /// again goes one step further back.
+    #[arg(long, conflicts_with_all = ["dependencies", "branches_only", "interactive", "dry_run", "check", "backup"])]
+    pub rollback: bool,
+
     /// Show detailed progress information during update.
     ///
     /// Displays additional information about each phase of the update process,
@@ -213,6 +264,45 @@ pub struct UpdateCommand {
     #[arg(long, value_name = "NUMBER")]
     pub max_parallel: Option<usize>,
 
+    /// Explain how each dependency's version was resolved.
+    ///
+    /// Shows the candidate tags considered for every dependency, which were
+    /// filtered out and why the final version was chosen. Use `--explain=json`
+    /// for machine-readable output; it implies `--quiet` so stdout holds only
+    /// the JSON report.
+    #[arg(long, value_enum, value_name = "FORMAT", num_args = 0..=1, require_equals = true, default_missing_value = "text")]
+    pub explain: Option<ExplainFormat>,
+
+    /// Which version to pick when several tags satisfy a constraint.
+    ///
+    /// `lowest` resolves every version constraint to the lowest matching tag
+    /// instead of the highest, to check that constraints are not
+    /// under-specified. Prefix and prerelease rules still apply.
+    #[arg(long, value_enum, value_name = "STRATEGY", default_value_t)]
+    pub resolution: ResolutionStrategy,
+
+    /// Report how the lockfile differs from an older one
+    ///
+    /// After the command finishes, compares the resulting lockfile with the
+    /// one at this path (for example `agpm.lock` from a pull request's base
+    /// branch) and prints added, removed and changed resources with their
+    /// version transitions, grouped by type.
+    #[arg(long, value_name = "LOCKFILE")]
+    pub since: Option<PathBuf>,
+
+    /// Output format for `--since` and `--concurrency-report` (text, json)
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t, requires = "reports")]
+    pub format: DiffFormat,
+
+    /// Report lock contention, fetch and worktree timings
+    ///
+    /// After the command finishes, prints how many cache locks were taken and
+    /// how long tasks waited for them (total and longest wait), the time spent
+    /// fetching, cloning and creating worktrees, and how many worktrees were
+    /// reused, to help choose a `--max-parallel` value.
+    #[arg(long)]
+    pub concurrency_report: bool,
+
     /// Disable progress bars (for programmatic use, not exposed as CLI arg)
     #[arg(skip)]
     pub no_progress: bool,
@@ -224,6 +314,20 @@ pub struct UpdateCommand {
     /// CI/CD pipelines and automated scripts.
     #[arg(short = 'y', long)]
     pub yes: bool,
+
+    /// Environment overlay to merge (for programmatic use, not exposed as CLI arg)
+    ///
+    /// This field is populated from the global --env flag (or `AGPM_ENV`) via
+    /// execute_with_config and selects `agpm.<env>.toml`.
+    #[arg(skip)]
+    pub env: Option<String>,
+
+    /// Directory to remap tool base directories under (for programmatic use, not exposed as CLI arg)
+    ///
+    /// This field is populated from the global --install-root flag via
+    /// execute_with_config.
+    #[arg(skip)]
+    pub install_root: Option<std::path::PathBuf>,
 }
 
 impl UpdateCommand {
@@ -288,7 +392,16 @@ impl UpdateCommand {
     /// # }));
     /// ```
     /// Execute the update command with an optional manifest path
-    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+    pub async fn execute_with_manifest_path(
+        mut self,
+        manifest_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Keep stdout to the JSON report so it can be piped to `jq`
+        if self.explain == Some(ExplainFormat::Json) {
+            self.quiet = true;
+            self.no_progress = true;
+        }
+
         // Find manifest file
         let manifest_path = find_manifest_with_optional(manifest_path).with_context(|| {
             "No agpm.toml found in current directory or any parent directory.\n\n\
@@ -309,36 +422,66 @@ impl UpdateCommand {
             return Err(anyhow::anyhow!("Manifest file {} not found", manifest_path.display()));
         }
 
+        if self.interactive && !io::stdin().is_terminal() {
+            anyhow::bail!(
+                "--interactive requires a terminal.\n\n\
+                Name the dependencies to update instead, e.g. 'agpm update my-agent'."
+            );
+        }
+
         let project_dir = manifest_path.parent().unwrap();
         let multi_phase = Arc::new(MultiPhaseProgress::new(!self.quiet && !self.no_progress));
 
         // Load manifest with private config merged
-        let (manifest, _conflicts) =
-            Manifest::load_with_private(&manifest_path).with_context(|| {
-                format!(
-                    "Failed to parse manifest file: {}\n\n\
+        let (mut manifest, _conflicts) =
+            Manifest::load_with_overlays(&manifest_path, self.env.as_deref()).with_context(
+                || {
+                    format!(
+                        "Failed to parse manifest file: {}\n\n\
                 Please check the TOML syntax and fix any errors before updating.",
-                    manifest_path.display()
-                )
-            })?;
+                        manifest_path.display()
+                    )
+                },
+            )?;
+        manifest.install_root = self.install_root.clone();
+        let since_lockfile =
+            self.since.as_deref().map(crate::cli::common::load_since_lockfile).transpose()?;
+
+        // Serialize with other agpm processes in this project until the lockfile is written
+        let project_lock = crate::installer::ProjectLock::acquire(
+            project_dir,
+            crate::installer::PROJECT_LOCK_NAME,
+        )
+        .await?;
 
         // Load existing lockfile or perform fresh install if missing
         let lockfile_path = project_dir.join("agpm.lock");
         let existing_lockfile = if lockfile_path.exists() {
             LockFile::load(&lockfile_path)?
+        } else if self.rollback {
+            LockFile::new()
         } else {
             if !self.quiet && !self.no_progress {
-                println!("⚠️  No lockfile found");
-                println!("ℹ️  Performing fresh install");
+                println!("{WARNING}No lockfile found");
+                println!("{INFO}Performing fresh install");
             }
 
             // Use the install command to do the actual installation
-            let install_cmd = if self.quiet {
+            let mut install_cmd = if self.quiet {
                 crate::cli::install::InstallCommand::new_quiet()
             } else {
                 crate::cli::install::InstallCommand::new()
             };
-
+            install_cmd.env = self.env.clone();
+            install_cmd.install_root = self.install_root.clone();
+            install_cmd.explain = self.explain;
+            install_cmd.resolution = self.resolution;
+            install_cmd.since = self.since.clone();
+            install_cmd.format = self.format;
+            install_cmd.concurrency_report = self.concurrency_report;
+
+            // The install command takes the project lock itself
+            drop(project_lock);
             return install_cmd.execute_from_path(Some(&manifest_path)).await;
         };
 
@@ -359,12 +502,28 @@ impl UpdateCommand {
                 .await
                 .with_context(|| format!("Failed to create backup at {}", backup_path.display()))?;
             if !self.quiet && !self.no_progress {
-                println!("ℹ️  Created backup: {}", backup_path.display());
+                println!("{INFO}Created backup: {}", backup_path.display());
             }
         }
 
         // Determine what to update
-        let deps_to_update = if self.dependencies.is_empty() {
+        let deps_to_update = if self.branches_only {
+            let tracked = branch_tracked_dependencies(&manifest);
+            if tracked.is_empty() {
+                if !self.quiet && !self.no_progress {
+                    println!("{INFO}No branch-tracked dependencies to update");
+                }
+                if let Some(since) = &since_lockfile {
+                    crate::cli::common::display_lockfile_diff(
+                        since,
+                        &existing_lockfile,
+                        self.format,
+                    )?;
+                }
+                return Ok(());
+            }
+            Some(tracked)
+        } else if self.dependencies.is_empty() {
             None
         } else {
             Some(self.dependencies.clone())
@@ -375,17 +534,32 @@ impl UpdateCommand {
             manifest.all_dependencies().iter().any(|(_, dep)| dep.get_source().is_some());
 
         // Initialize cache for both resolution and installation
-        let cache = Cache::new()?;
+        // --dry-run and --check install nothing, so fetches can skip file contents
+        let fetch_mode = if self.dry_run || self.check {
+            FetchMode::RefsOnly
+        } else {
+            FetchMode::Full
+        };
+        let metrics = self.concurrency_report.then(|| Arc::new(CacheMetrics::default()));
+        let mut cache = Cache::new()?.with_fetch_mode(fetch_mode);
+        if let Some(metrics) = &metrics {
+            cache = cache.with_metrics(Arc::clone(metrics));
+        }
+        let cache = crate::cli::common::apply_path_overrides(cache, &manifest, self.quiet)?;
 
         // Resolve updated dependencies
         let mut resolver = DependencyResolver::new(manifest.clone(), cache.clone()).await?;
+        if self.explain.is_some() {
+            resolver.set_explain(true);
+        }
+        resolver.set_resolution_strategy(self.resolution);
 
         // Create operation context for warning deduplication
         let operation_context = Arc::new(OperationContext::new());
         resolver.set_operation_context(operation_context);
 
         // Get all dependencies for pre-syncing (only if we have remote deps)
-        if has_remote_deps {
+        if has_remote_deps && !self.rollback {
             let all_deps: Vec<(String, ResourceDependency)> = manifest
                 .all_dependencies_with_types()
                 .into_iter()
@@ -408,40 +582,55 @@ impl UpdateCommand {
         } else {
             None
         };
-        let mut new_lockfile =
-            resolver.update(&existing_lockfile, deps_to_update.clone(), progress).await?;
+        let mut new_lockfile = if self.rollback {
+            // Nothing is resolved: the backup is installed as recorded
+            let Some(backup) = crate::lockfile::backup::restore_latest_backup(project_dir)? else {
+                anyhow::bail!(
+                    "No lockfile backup to roll back to.\n\n\
+                    'agpm install' and 'agpm update' save the previous agpm.lock to {} \
+                    whenever they change it.",
+                    crate::lockfile::backup::BACKUP_DIR
+                );
+            };
+            if !self.quiet && !self.no_progress {
+                println!("{INFO}Restored agpm.lock from {}", backup.display());
+            }
+            LockFile::load(&lockfile_path)?
+        } else {
+            resolver.update(&existing_lockfile, deps_to_update.clone(), progress).await?
+        };
+
+        if let Some(format) = self.explain {
+            crate::cli::common::display_resolution_explanations(
+                format,
+                &manifest,
+                &resolver.explanations(),
+            )?;
+        }
 
         // Compare lockfiles to see what changed
-        let mut updates = Vec::new();
-        ResourceIterator::for_each_resource(&new_lockfile, |_, new_entry| {
-            // Use (display_name, source) pair for correct matching
-            // display_name() uses manifest_alias if present, otherwise name
-            // This ensures backward compatibility with old lockfiles
-            if let Some((_, old_entry)) = ResourceIterator::find_resource_by_name_and_source(
-                &existing_lockfile,
-                new_entry.display_name(),
-                new_entry.source.as_deref(),
-            ) {
-                // Resource needs update if:
-                // 1. Version changed (resolved_commit differs), OR
-                // 2. Patches changed (applied_patches differs)
-                let version_changed = old_entry.resolved_commit != new_entry.resolved_commit;
-                let patches_changed = old_entry.applied_patches != new_entry.applied_patches;
-
-                if version_changed || patches_changed {
-                    let old_version =
-                        old_entry.version.clone().unwrap_or_else(|| "latest".to_string());
-                    let new_version =
-                        new_entry.version.clone().unwrap_or_else(|| "latest".to_string());
-                    updates.push((
-                        new_entry.name.clone(),
-                        new_entry.source.clone(),
-                        old_version,
-                        new_version,
-                    ));
-                }
+        let mut updates = changed_resources(&existing_lockfile, &new_lockfile);
+        let mut targeted = !self.dependencies.is_empty();
+
+        if self.interactive && !updates.is_empty() {
+            multi_phase.clear();
+            let choices = update_choices(&existing_lockfile, &new_lockfile);
+            let selected = select_updates(
+                &choices,
+                &mut BufReader::new(tokio::io::stdin()),
+                &mut io::stdout(),
+            )
+            .await?;
+            if selected.is_empty() {
+                println!("{INFO}No updates selected");
+                return Ok(());
             }
-        });
+
+            // Re-resolve only the selected dependencies, keeping the rest locked
+            new_lockfile = resolver.update(&existing_lockfile, Some(selected), None).await?;
+            updates = changed_resources(&existing_lockfile, &new_lockfile);
+            targeted = true;
+        }
 
         // Display results
         if updates.is_empty() {
@@ -475,15 +664,16 @@ impl UpdateCommand {
                         println!("{} {}", "Would update".green(), "(dry run)".yellow());
                     }
                 }
+                if let Some(since) = &since_lockfile {
+                    crate::cli::common::display_lockfile_diff(since, &new_lockfile, self.format)?;
+                }
+                if let Some(metrics) = &metrics {
+                    crate::cli::common::display_concurrency_report(metrics, self.format)?;
+                }
                 // Return with error to indicate updates are available (exit code 1 for CI)
                 return Err(anyhow::anyhow!("Dry-run detected updates available (exit 1)"));
             }
 
-            // Acquire resource lock for cross-process coordination during file writes
-            // Resolution has completed above (outside lock), now we serialize file operations
-            let _resource_lock =
-                crate::installer::ProjectLock::acquire(project_dir, "resource").await?;
-
             // Install all updated resources first, before saving lockfile
             if !self.quiet && !self.no_progress && !updates.is_empty() {
                 multi_phase.start_phase(
@@ -538,6 +728,14 @@ impl UpdateCommand {
                 multi_phase.start_phase(InstallationPhase::Finalizing, None);
             }
 
+            // Record the same fast-path metadata as install, so a targeted
+            // update does not drop it from the lockfile. A restored backup keeps
+            // its own, which may describe an older manifest.
+            if !self.rollback {
+                new_lockfile.manifest_hash = Some(manifest.compute_dependency_hash());
+                new_lockfile.has_mutable_deps = Some(manifest.has_mutable_dependencies());
+            }
+
             // Call shared finalization function (this will configure hooks and MCP servers!)
             let (_hook_count, _server_count) = crate::installer::finalize_installation(
                 &mut new_lockfile,
@@ -546,7 +744,9 @@ impl UpdateCommand {
                 &cache,
                 Some(&existing_lockfile), // Pass old lockfile for artifact cleanup
                 self.quiet,
-                false, // no_lock - always save lockfile in update command
+                false,    // no_lock - always save lockfile in update command
+                targeted, // targeted updates keep other entries as they are
+                None,
             )
             .await?;
 
@@ -591,10 +791,191 @@ impl UpdateCommand {
             }
         }
 
+        if let Some(since) = &since_lockfile {
+            crate::cli::common::display_lockfile_diff(since, &new_lockfile, self.format)?;
+        }
+
+        if let Some(metrics) = &metrics {
+            crate::cli::common::display_concurrency_report(metrics, self.format)?;
+        }
+
         Ok(())
     }
 }
 
+/// Call `f` with the old and new entry of each resource whose commit or
+/// applied patches differ between the two lockfiles.
+fn for_each_change(
+    existing: &LockFile,
+    new: &LockFile,
+    mut f: impl FnMut(&LockedResource, &LockedResource),
+) {
+    ResourceIterator::for_each_resource(new, |_, new_entry| {
+        // Use (display_name, source) pair for correct matching
+        // display_name() uses manifest_alias if present, otherwise name
+        // This ensures backward compatibility with old lockfiles
+        if let Some((_, old_entry)) = ResourceIterator::find_resource_by_name_and_source(
+            existing,
+            new_entry.display_name(),
+            new_entry.source.as_deref(),
+        ) {
+            // Resource needs update if:
+            // 1. Version changed (resolved_commit differs), OR
+            // 2. Patches changed (applied_patches differs)
+            let version_changed = old_entry.resolved_commit != new_entry.resolved_commit;
+            let patches_changed = old_entry.applied_patches != new_entry.applied_patches;
+
+            if version_changed || patches_changed {
+                f(old_entry, new_entry);
+            }
+        }
+    });
+}
+
+/// Changed resources as `(name, source, old_version, new_version)`, as
+/// expected by [`ResourceFilter::Updated`](crate::installer::ResourceFilter::Updated).
+fn changed_resources(
+    existing: &LockFile,
+    new: &LockFile,
+) -> Vec<(String, Option<String>, String, String)> {
+    let mut updates = Vec::new();
+    for_each_change(existing, new, |old_entry, new_entry| {
+        updates.push((
+            new_entry.name.clone(),
+            new_entry.source.clone(),
+            display_version(old_entry),
+            display_version(new_entry),
+        ));
+    });
+    updates
+}
+
+fn display_version(entry: &LockedResource) -> String {
+    entry.version.clone().unwrap_or_else(|| "latest".to_string())
+}
+
+/// An available update offered by `--interactive`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct UpdateChoice {
+    /// Name accepted by [`DependencyResolver::update`]: the manifest name for
+    /// direct dependencies, the lockfile name for transitive ones.
+    name: String,
+    current: String,
+    available: String,
+}
+
+/// One choice per dependency that would change, in lockfile order.
+fn update_choices(existing: &LockFile, new: &LockFile) -> Vec<UpdateChoice> {
+    let mut choices: Vec<UpdateChoice> = Vec::new();
+    for_each_change(existing, new, |old_entry, new_entry| {
+        let name = new_entry.display_name();
+        // A dependency installed for several tools is offered once
+        if !choices.iter().any(|choice| choice.name == name) {
+            choices.push(UpdateChoice {
+                name: name.to_string(),
+                current: display_version(old_entry),
+                available: display_version(new_entry),
+            });
+        }
+    });
+    choices
+}
+
+/// Let the user toggle which of `choices` to apply.
+///
+/// Every update starts selected. Each input line toggles the numbers it lists
+/// (`2`, `1 3`, `1,4` or ranges like `2-5`); `a` selects all, `n` none, an empty
+/// line confirms and `q` (or end of input) cancels. Returns the selected names
+/// in listing order, which is empty when cancelled.
+async fn select_updates<R, W>(
+    choices: &[UpdateChoice],
+    input: &mut R,
+    output: &mut W,
+) -> Result<Vec<String>>
+where
+    R: AsyncBufRead + Unpin,
+    W: Write,
+{
+    let mut selected = vec![true; choices.len()];
+    loop {
+        writeln!(output)?;
+        for (index, (choice, on)) in choices.iter().zip(&selected).enumerate() {
+            let mark = if *on {
+                "x".green()
+            } else {
+                " ".normal()
+            };
+            writeln!(
+                output,
+                "  [{mark}] {:>2}. {} {} → {}",
+                index + 1,
+                choice.name.cyan(),
+                choice.current.yellow(),
+                choice.available.green()
+            )?;
+        }
+        write!(
+            output,
+            "{} ",
+            "Toggle numbers (e.g. 1 3 or 2-4), a = all, n = none, Enter = apply, q = cancel:"
+                .green()
+        )?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line).await? == 0 {
+            return Ok(Vec::new());
+        }
+        match line.trim() {
+            "" => break,
+            "q" => return Ok(Vec::new()),
+            "a" => selected.fill(true),
+            "n" => selected.fill(false),
+            toggles => match parse_toggles(toggles, choices.len()) {
+                Ok(indices) => {
+                    for index in indices {
+                        selected[index] = !selected[index];
+                    }
+                }
+                Err(e) => writeln!(output, "{} {e}", "Invalid selection:".red())?,
+            },
+        }
+    }
+
+    Ok(choices.iter().zip(selected).filter(|(_, on)| *on).map(|(c, _)| c.name.clone()).collect())
+}
+
+/// Zero-based indices listed in a toggle line such as `1 3` or `2-4,6`.
+fn parse_toggles(line: &str, count: usize) -> Result<Vec<usize>> {
+    let parse = |number: &str| -> Result<usize> {
+        let value: usize =
+            number.trim().parse().with_context(|| format!("'{number}' is not a number"))?;
+        if value == 0 || value > count {
+            anyhow::bail!("{value} is not between 1 and {count}");
+        }
+        Ok(value - 1)
+    };
+
+    let mut indices = Vec::new();
+    for part in line.split([',', ' ']).filter(|part| !part.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => indices.extend(parse(start)?..=parse(end)?),
+            None => indices.push(parse(part)?),
+        }
+    }
+    Ok(indices)
+}
+
+/// Names of the Git dependencies in the manifest that follow a branch.
+fn branch_tracked_dependencies(manifest: &Manifest) -> Vec<String> {
+    manifest
+        .all_dependencies()
+        .into_iter()
+        .filter(|(_, dep)| !dep.is_local() && dep.is_mutable())
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -609,6 +990,8 @@ mod tests {
     fn create_update_command() -> UpdateCommand {
         UpdateCommand {
             dependencies: vec![],
+            branches_only: false,
+            interactive: false,
             dry_run: false,
             check: false,
             backup: false,
@@ -617,6 +1000,14 @@ mod tests {
             no_progress: true, // No progress bars in tests
             max_parallel: None,
             yes: false,
+            env: None,
+            install_root: None,
+            explain: None,
+            resolution: ResolutionStrategy::Highest,
+            since: None,
+            format: DiffFormat::Text,
+            concurrency_report: false,
+            rollback: false,
         }
     }
 
@@ -645,6 +1036,11 @@ mod tests {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
         );
 
@@ -663,10 +1059,29 @@ mod tests {
             private_patches: crate::manifest::patches::ManifestPatches::default(),
             manifest_dir: None,
             default_tools: HashMap::new(),
+            default_versions: HashMap::new(),
+            tag_patterns: HashMap::new(),
+            path_aliases: HashMap::new(),
             project: None,
             private_dependency_names: std::collections::HashSet::new(),
+            env: None,
+            env_patches: crate::manifest::patches::ManifestPatches::default(),
+            env_dependency_names: std::collections::HashSet::new(),
+            defaulted_tool_names: std::collections::HashSet::new(),
+            install_root: None,
             gitignore: true,
+            require_tag: false,
+            require_validated: false,
+            tag_preference: crate::version::TagPreference::default(),
             token_warning_threshold: None,
+            installer: crate::manifest::InstallerConfig::default(),
+            install: crate::manifest::InstallToggles::default(),
+            transitive: crate::manifest::TransitivePolicy::default(),
+            resolution: crate::manifest::ResolutionPolicy::default(),
+            integrity: crate::manifest::IntegrityConfig::default(),
+            constraints: std::collections::BTreeMap::new(),
+            workspace: crate::manifest::WorkspaceConfig::default(),
+            path_overrides: HashMap::new(),
         }
     }
 
@@ -700,6 +1115,7 @@ mod tests {
                 variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
                 is_private: false,
                 approximate_token_count: None,
+                deprecated: false,
             }],
             snippets: vec![],
             mcp_servers: vec![],
@@ -1133,6 +1549,8 @@ mod tests {
     fn test_update_command_defaults() {
         let cmd = UpdateCommand {
             dependencies: vec![],
+            branches_only: false,
+            interactive: false,
             dry_run: false,
             check: false,
             backup: false,
@@ -1141,6 +1559,14 @@ mod tests {
             no_progress: false,
             max_parallel: None,
             yes: false,
+            env: None,
+            install_root: None,
+            explain: None,
+            resolution: ResolutionStrategy::Highest,
+            since: None,
+            format: DiffFormat::Text,
+            concurrency_report: false,
+            rollback: false,
         };
 
         assert!(cmd.dependencies.is_empty());
@@ -1156,6 +1582,8 @@ mod tests {
     fn test_update_command_with_all_flags() {
         let cmd = UpdateCommand {
             dependencies: vec!["dep1".to_string(), "dep2".to_string()],
+            branches_only: false,
+            interactive: false,
             dry_run: true,
             check: true,
             backup: true,
@@ -1164,6 +1592,14 @@ mod tests {
             no_progress: true,
             max_parallel: Some(4),
             yes: true,
+            env: None,
+            install_root: None,
+            explain: None,
+            resolution: ResolutionStrategy::Highest,
+            since: None,
+            format: DiffFormat::Text,
+            concurrency_report: false,
+            rollback: false,
         };
 
         assert_eq!(cmd.dependencies.len(), 2);
@@ -1174,4 +1610,55 @@ mod tests {
         assert!(cmd.quiet);
         assert!(cmd.yes);
     }
+
+    fn locked_agent(alias: &str, version: &str, commit: &str) -> LockedResource {
+        crate::lockfile::LockedResourceBuilder::new(
+            format!("agents/{alias}"),
+            format!("agents/{alias}.md"),
+            String::new(),
+            format!(".claude/agents/{alias}.md"),
+            crate::core::ResourceType::Agent,
+        )
+        .source(Some("community".to_string()))
+        .version(Some(version.to_string()))
+        .resolved_commit(Some(commit.to_string()))
+        .manifest_alias(Some(alias.to_string()))
+        .build()
+    }
+
+    #[tokio::test]
+    async fn test_interactive_selection_maps_to_update_names() -> Result<()> {
+        let mut existing = LockFile::new();
+        let mut resolved = LockFile::new();
+        for (alias, old, new) in [("alpha", "v1.0.0", "v1.1.0"), ("beta", "v1.0.0", "v1.0.0")] {
+            existing.agents.push(locked_agent(alias, old, old));
+            resolved.agents.push(locked_agent(alias, new, new));
+        }
+        existing.agents.push(locked_agent("gamma", "v2.0.0", "v2.0.0"));
+        resolved.agents.push(locked_agent("gamma", "v2.1.0", "v2.1.0"));
+
+        let choices = update_choices(&existing, &resolved);
+        let summary: Vec<_> = choices
+            .iter()
+            .map(|c| (c.name.as_str(), c.current.as_str(), c.available.as_str()))
+            .collect();
+        assert_eq!(summary, vec![("alpha", "v1.0.0", "v1.1.0"), ("gamma", "v2.0.0", "v2.1.0")]);
+
+        // Everything starts selected; toggling 1 leaves gamma
+        let mut output = Vec::new();
+        let selected = select_updates(&choices, &mut &b"1\n\n"[..], &mut output).await?;
+        assert_eq!(selected, vec!["gamma"]);
+
+        // Out-of-range input is reported and the selection is kept
+        let mut output = Vec::new();
+        let selected = select_updates(&choices, &mut &b"n\n3\n1-2\n\n"[..], &mut output).await?;
+        assert_eq!(selected, vec!["alpha", "gamma"]);
+        assert!(String::from_utf8(output)?.contains("3 is not between 1 and 2"));
+
+        // Cancelling, explicitly or by closing input, selects nothing
+        let mut output = Vec::new();
+        assert!(select_updates(&choices, &mut &b"q\n"[..], &mut output).await?.is_empty());
+        assert!(select_updates(&choices, &mut &b"2\n"[..], &mut output).await?.is_empty());
+        Ok(())
+    }
 }
@@ -33,6 +33,7 @@ use clap::Args;
 ///     quiet: false,
 ///     strict: false,
 ///     render: false,
+///     env: None,
 /// };
 ///
 /// // Comprehensive CI validation
@@ -47,6 +48,7 @@ use clap::Args;
 ///     quiet: true,
 ///     strict: true,
 ///     render: false,
+///     env: None,
 /// };
 /// ```
 #[derive(Args)]
@@ -141,6 +143,13 @@ pub struct ValidateCommand {
     /// - Testing template rendering without modifying the filesystem
     #[arg(long)]
     pub render: bool,
+
+    /// Environment overlay to merge (for programmatic use, not exposed as CLI arg)
+    ///
+    /// This field is populated from the global --env flag (or `AGPM_ENV`) via
+    /// execute_with_config and selects `agpm.<env>.toml`.
+    #[arg(skip)]
+    pub env: Option<String>,
 }
 
 /// Output format options for validation results.
@@ -60,6 +60,7 @@ impl ValidateCommand {
     ///     quiet: false,
     ///     strict: false,
     ///     render: false,
+    ///     env: None,
     /// };
     /// // cmd.execute().await?;
     /// ```
@@ -98,6 +99,7 @@ impl ValidateCommand {
     ///     json: false,
     ///     paths: false,
     ///     fix: false,
+    ///     env: None,
     /// };
     ///
     /// cmd.execute_with_manifest_path(Some(PathBuf::from("./agpm.toml"))).await?;
@@ -179,6 +181,7 @@ impl ValidateCommand {
         // Load and validate manifest structure
         let manifest = validators::validate_manifest(
             &manifest_path,
+            self.env.as_deref(),
             &self.format,
             self.verbose,
             self.quiet,
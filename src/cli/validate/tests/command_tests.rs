@@ -29,6 +29,7 @@ async fn test_validate_custom_file_path() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -52,6 +53,7 @@ async fn test_execute_with_no_manifest_json_format() {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute().await;
@@ -75,6 +77,7 @@ async fn test_execute_with_no_manifest_text_format() {
         quiet: false, // Not quiet - should print error message
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute().await;
@@ -98,6 +101,7 @@ async fn test_execute_with_no_manifest_quiet_mode() {
         quiet: true, // Quiet mode - should not print
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute().await;
@@ -121,6 +125,7 @@ async fn test_execute_from_path_nonexistent_file_json() {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(nonexistent_path).await;
@@ -144,6 +149,7 @@ async fn test_execute_from_path_nonexistent_file_text() {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(nonexistent_path).await;
@@ -164,6 +170,7 @@ async fn test_validate_command_defaults() {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
     assert_eq!(cmd.file, None);
     assert!(!cmd.resolve);
@@ -193,6 +200,7 @@ async fn test_execute_without_manifest_file() {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute().await;
@@ -218,6 +226,7 @@ async fn test_execute_with_specified_file() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute().await;
@@ -241,8 +250,39 @@ async fn test_execute_with_nonexistent_specified_file() {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute().await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_validate_applies_env_overlay() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let manifest_path = temp.path().join("agpm.toml");
+    let mut manifest = Manifest::new();
+    manifest.add_source("test".to_string(), "https://github.com/test/repo.git".to_string());
+    manifest.save(&manifest_path).unwrap();
+
+    // Overlays cannot define [tools], so validating with --env must fail
+    std::fs::write(temp.path().join("agpm.dev.toml"), "[tools.claude-code]\npath = \".claude\"\n")?;
+
+    let cmd = |env: Option<&str>| ValidateCommand {
+        file: None,
+        resolve: false,
+        check_lock: false,
+        sources: false,
+        paths: false,
+        format: OutputFormat::Text,
+        verbose: false,
+        quiet: true,
+        strict: false,
+        render: false,
+        env: env.map(str::to_string),
+    };
+
+    cmd(None).execute_from_path(manifest_path.clone()).await?;
+    assert!(cmd(Some("dev")).execute_from_path(manifest_path).await.is_err());
+    Ok(())
+}
@@ -27,6 +27,7 @@ async fn test_validate_json_format() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -54,6 +55,7 @@ async fn test_validate_strict_mode() -> Result<()> {
         quiet: true,
         strict: true, // Strict mode treats warnings as errors
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -83,6 +85,7 @@ async fn test_validate_verbose_mode() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -116,6 +119,11 @@ async fn test_validate_json_error_format() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             },
         )),
         true,
@@ -133,6 +141,7 @@ async fn test_validate_json_error_format() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -159,6 +168,7 @@ async fn test_validate_verbose_output() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -187,6 +197,7 @@ async fn test_validate_strict_mode_with_warnings() -> Result<()> {
         quiet: false,
         strict: true, // Strict mode
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -214,6 +225,7 @@ async fn test_validate_quiet_mode() -> Result<()> {
         quiet: true, // Enable quiet
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -248,6 +260,11 @@ async fn test_validate_json_output_success() -> Result<()> {
             install: None,
 
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
     );
     manifest.save(&manifest_path)?;
@@ -263,6 +280,7 @@ async fn test_validate_json_output_success() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -289,6 +307,7 @@ async fn test_validate_manifest_toml_syntax_error_json() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -324,6 +343,11 @@ async fn test_validate_resolve_with_error_json_output() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             },
         )),
         true,
@@ -341,6 +365,7 @@ async fn test_validate_resolve_with_error_json_output() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -380,6 +405,7 @@ async fn test_validate_sources_accessibility_error_json() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -414,6 +440,11 @@ async fn test_validate_check_paths_missing_snippets_json() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             },
         )),
     );
@@ -430,6 +461,7 @@ async fn test_validate_check_paths_missing_snippets_json() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -462,6 +494,7 @@ async fn test_validate_lockfile_syntax_error_json() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -490,6 +523,7 @@ async fn test_validate_strict_mode_with_json_output() -> Result<()> {
         quiet: true,
         strict: true, // Strict mode with JSON output
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -518,6 +552,7 @@ async fn test_validate_strict_mode_text_output() -> Result<()> {
         quiet: false, // Not quiet - should print error message
         strict: true,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -557,6 +592,7 @@ async fn test_validate_verbose_mode_with_summary() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -593,6 +629,7 @@ async fn test_json_output_format() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -619,6 +656,7 @@ async fn test_validation_with_verbose_mode() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -645,6 +683,7 @@ async fn test_validation_with_quiet_mode() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -672,6 +711,7 @@ async fn test_validation_with_strict_mode_and_warnings() -> Result<()> {
         quiet: false,
         strict: true, // Strict mode will fail on warnings
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -698,6 +738,7 @@ async fn test_validation_json_output_with_errors() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -721,6 +762,7 @@ async fn test_validation_with_manifest_not_found_json() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -744,6 +786,7 @@ async fn test_validation_with_manifest_not_found_text() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -777,6 +820,7 @@ async fn test_validation_with_verbose_and_text_format() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
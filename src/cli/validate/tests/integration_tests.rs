@@ -20,6 +20,7 @@ async fn test_validate_no_manifest() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -48,6 +49,7 @@ async fn test_validate_valid_manifest() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -81,6 +83,11 @@ async fn test_validate_invalid_manifest() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             },
         )),
         true,
@@ -98,6 +105,7 @@ async fn test_validate_invalid_manifest() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -124,6 +132,7 @@ async fn test_validate_manifest_toml_syntax_error() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -158,6 +167,11 @@ async fn test_validate_manifest_structure_error() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             },
         )),
         true,
@@ -175,6 +189,7 @@ async fn test_validate_manifest_structure_error() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -212,6 +227,7 @@ another-agent = { source = "test", path = "agent.md", version = "v2.0.0" }
         quiet: true,
         strict: false,
         render: false,
+        env: None,
     };
 
     // Version conflicts are automatically resolved during installation
@@ -249,6 +265,11 @@ async fn test_validate_with_outdated_version_warnings() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             },
         )),
         true,
@@ -266,6 +287,7 @@ async fn test_validate_with_outdated_version_warnings() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -293,6 +315,7 @@ async fn test_validate_final_success_with_warnings() -> Result<()> {
         quiet: false,
         strict: false, // Not strict - warnings don't cause failure
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -329,6 +352,7 @@ async fn test_validate_all_checks_enabled() -> Result<()> {
         quiet: false,
         strict: true,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -359,6 +383,7 @@ async fn test_validate_with_specific_file_path() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute().await;
@@ -410,6 +435,11 @@ async fn test_validation_with_outdated_version_warning() -> Result<()> {
             install: None,
 
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
     );
     manifest.save(&manifest_path)?;
@@ -425,6 +455,7 @@ async fn test_validation_with_outdated_version_warning() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -32,6 +32,11 @@ async fn test_validate_with_resolve() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             },
         )),
         true,
@@ -49,6 +54,7 @@ async fn test_validate_with_resolve() -> Result<()> {
         quiet: true, // Make quiet to avoid output
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -82,6 +88,7 @@ async fn test_validate_check_lock_consistent() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -121,6 +128,7 @@ async fn test_validate_check_lock_with_extra_entries() -> Result<()> {
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     });
     lockfile.save(&temp.path().join("agpm.lock")).unwrap();
 
@@ -135,6 +143,7 @@ async fn test_validate_check_lock_with_extra_entries() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -169,6 +178,7 @@ async fn test_validate_check_lock() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path.clone()).await;
@@ -199,6 +209,7 @@ async fn test_validate_check_lock() -> Result<()> {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         }],
         snippets: vec![],
         mcp_servers: vec![],
@@ -222,6 +233,7 @@ async fn test_validate_check_lock() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -256,6 +268,11 @@ async fn test_validate_resolve_dependency_not_found_error() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             },
         )),
         true,
@@ -279,6 +296,11 @@ async fn test_validate_resolve_dependency_not_found_error() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             },
         )),
         false,
@@ -296,6 +318,7 @@ async fn test_validate_resolve_dependency_not_found_error() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -324,6 +347,7 @@ async fn test_validate_lockfile_missing_warning() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -367,6 +391,7 @@ async fn test_validate_lockfile_missing_dependencies() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -407,6 +432,7 @@ async fn test_validate_lockfile_extra_entries_error() -> Result<()> {
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -421,6 +447,7 @@ async fn test_validate_lockfile_extra_entries_error() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -451,6 +478,7 @@ async fn test_validation_with_lockfile_consistency_check_no_lockfile() -> Result
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -493,6 +521,7 @@ async fn test_validation_with_inconsistent_lockfile() -> Result<()> {
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -507,6 +536,7 @@ async fn test_validation_with_inconsistent_lockfile() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -537,6 +567,7 @@ async fn test_validation_with_invalid_lockfile_syntax() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -585,6 +616,7 @@ async fn test_validation_with_missing_lockfile_dependencies() -> Result<()> {
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -599,6 +631,7 @@ async fn test_validation_with_missing_lockfile_dependencies() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
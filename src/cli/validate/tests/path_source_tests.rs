@@ -37,6 +37,11 @@ async fn test_validate_check_paths_local() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             },
         )),
         true,
@@ -54,6 +59,7 @@ async fn test_validate_check_paths_local() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -87,6 +93,7 @@ async fn test_validate_paths_check() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path.clone()).await;
@@ -107,6 +114,7 @@ async fn test_validate_paths_check() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -144,6 +152,7 @@ async fn test_validate_check_sources() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     // This will check if the local source is accessible
@@ -180,6 +189,11 @@ async fn test_validate_check_paths() -> Result<()> {
             install: None,
 
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
     );
     manifest.save(&manifest_path).unwrap();
@@ -198,6 +212,7 @@ async fn test_validate_check_paths() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -235,6 +250,7 @@ async fn test_validate_sources_accessibility_error() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -271,6 +287,11 @@ async fn test_validate_check_paths_snippets_and_commands() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             },
         )),
     );
@@ -295,6 +316,11 @@ async fn test_validate_check_paths_snippets_and_commands() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             },
         )),
     );
@@ -318,6 +344,7 @@ async fn test_validate_check_paths_snippets_and_commands() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -346,6 +373,7 @@ async fn test_validate_sources_check_with_invalid_url() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -376,6 +404,7 @@ async fn test_validation_with_local_paths_check() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -409,6 +438,7 @@ async fn test_validation_with_existing_local_paths() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -470,6 +500,7 @@ See [helper](.agpm/snippets/helper.md) for details.
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -484,6 +515,7 @@ See [helper](.agpm/snippets/helper.md) for details.
         quiet: false,
         strict: false,
         render: true,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -541,6 +573,7 @@ Also check `.claude/nonexistent.md`.
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -555,6 +588,7 @@ Also check `.claude/nonexistent.md`.
         quiet: false,
         strict: false,
         render: true,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -614,6 +648,7 @@ Visit http://example.com for more info.
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -628,6 +663,7 @@ Visit http://example.com for more info.
         quiet: false,
         strict: false,
         render: true,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -689,6 +725,7 @@ Inline code `example.md` should also be ignored.
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -703,6 +740,7 @@ Inline code `example.md` should also be ignored.
         quiet: false,
         strict: false,
         render: true,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -763,6 +801,7 @@ async fn test_file_reference_validation_multiple_resources() -> Result<()> {
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     });
     lockfile.commands.push(LockedResource {
         name: "cmd1".to_string(),
@@ -783,6 +822,7 @@ async fn test_file_reference_validation_multiple_resources() -> Result<()> {
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -797,6 +837,7 @@ async fn test_file_reference_validation_multiple_resources() -> Result<()> {
         quiet: false,
         strict: false,
         render: true,
+        env: None,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -8,6 +8,7 @@ use crate::cache::Cache;
 use crate::core::OperationContext;
 use crate::manifest::Manifest;
 use crate::resolver::DependencyResolver;
+use crate::utils::terminal::REFRESH;
 
 use super::super::command::OutputFormat;
 use super::super::results::ValidationResults;
@@ -41,7 +42,7 @@ pub async fn validate_dependencies(
     errors: &mut Vec<String>,
 ) -> Result<()> {
     if verbose && !quiet {
-        println!("\n🔄 Checking dependency resolution...");
+        println!("\n{REFRESH}Checking dependency resolution...");
     }
 
     let cache = Cache::new()?;
@@ -5,6 +5,8 @@ use colored::Colorize;
 use std::path::Path;
 
 use crate::core::ResourceType;
+use crate::installer::{DeprecatedResource, read_deprecation};
+use crate::utils::terminal::SEARCH;
 
 use super::{OutputFormat, ValidationContext};
 
@@ -12,7 +14,8 @@ use super::{OutputFormat, ValidationContext};
 ///
 /// This function compares the manifest dependencies with those recorded in the
 /// lockfile to identify inconsistencies. It warns if dependencies are missing
-/// from the lockfile or if extra entries exist.
+/// from the lockfile or if extra entries exist, and about resources flagged as
+/// deprecated.
 ///
 /// # Arguments
 ///
@@ -36,10 +39,22 @@ pub async fn validate_lockfile(ctx: &mut ValidationContext<'_>, project_dir: &Pa
         return Ok(());
     }
 
-    ctx.print_verbose("\n🔍 Checking lockfile consistency...");
+    ctx.print_verbose(&format!("\n{SEARCH}Checking lockfile consistency..."));
 
     match crate::lockfile::LockFile::load(&lockfile_path) {
         Ok(lockfile) => {
+            // Deprecated resources are warnings, so --strict turns them into failures
+            for entry in lockfile.all_resources().into_iter().filter(|e| e.deprecated) {
+                let deprecated = DeprecatedResource {
+                    name: entry.display_name().to_string(),
+                    resource_type: entry.resource_type,
+                    deprecation: read_deprecation(entry, project_dir).unwrap_or_default(),
+                };
+                let warning = deprecated.warning();
+                ctx.print(&format!("{} {}", "⚠".yellow(), warning));
+                ctx.warnings.push(warning);
+            }
+
             // Check that all manifest dependencies are in lockfile
             let mut missing = Vec::new();
             let mut extra = Vec::new();
@@ -155,7 +170,7 @@ async fn validate_private_lockfile(
     }
 
     if verbose && !quiet {
-        println!("\n🔍 Checking private lockfile...");
+        println!("\n{SEARCH}Checking private lockfile...");
     }
 
     match crate::lockfile::PrivateLockFile::load(project_dir) {
@@ -5,6 +5,7 @@ use colored::Colorize;
 use std::path::Path;
 
 use crate::manifest::Manifest;
+use crate::utils::terminal::SEARCH;
 
 use super::super::command::OutputFormat;
 use super::super::results::ValidationResults;
@@ -17,6 +18,7 @@ use super::super::results::ValidationResults;
 /// # Arguments
 ///
 /// * `manifest_path` - Path to the manifest file
+/// * `env` - Environment overlay (`agpm.<env>.toml`) to merge, if any
 /// * `format` - Output format for validation results
 /// * `verbose` - Whether to enable verbose output
 /// * `quiet` - Whether to suppress non-error output
@@ -27,8 +29,10 @@ use super::super::results::ValidationResults;
 /// # Returns
 ///
 /// Returns `Ok(Manifest)` if the manifest is valid, or `Err` if validation fails.
+#[allow(clippy::too_many_arguments)]
 pub async fn validate_manifest(
     manifest_path: &Path,
+    env: Option<&str>,
     format: &OutputFormat,
     verbose: bool,
     quiet: bool,
@@ -37,11 +41,11 @@ pub async fn validate_manifest(
     errors: &mut Vec<String>,
 ) -> Result<Manifest> {
     if verbose && !quiet {
-        println!("🔍 Validating {}...", manifest_path.display());
+        println!("{SEARCH}Validating {}...", manifest_path.display());
     }
 
     // Load and validate manifest structure
-    let manifest = match Manifest::load(manifest_path) {
+    let manifest = match Manifest::load_for_env(manifest_path, env) {
         Ok(m) => {
             if verbose && !quiet {
                 println!("✓ Manifest structure is valid");
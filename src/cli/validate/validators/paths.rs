@@ -1,5 +1,6 @@
 //! Local file path validation.
 
+use crate::utils::terminal::SEARCH;
 use anyhow::Result;
 use colored::Colorize;
 use std::path::Path;
@@ -20,7 +21,7 @@ use super::{OutputFormat, ValidationContext};
 ///
 /// Returns `Ok(())` if all local paths exist, or `Err` if any are missing.
 pub async fn validate_paths(ctx: &mut ValidationContext<'_>, manifest_path: &Path) -> Result<()> {
-    ctx.print_verbose("\n🔍 Checking local file paths...");
+    ctx.print_verbose(&format!("\n{SEARCH}Checking local file paths..."));
 
     let mut missing_paths = Vec::new();
 
@@ -10,6 +10,7 @@ use crate::cli::common::CommandContext;
 use crate::core::ResourceType;
 use crate::markdown::reference_extractor::{extract_file_references, validate_file_references};
 use crate::templating::{RenderingMetadata, TemplateContextBuilder, TemplateRenderer};
+use crate::utils::terminal::SEARCH;
 
 use super::{OutputFormat, ValidationContext};
 
@@ -33,7 +34,7 @@ use super::{OutputFormat, ValidationContext};
 /// Returns `Ok(())` if all templates render successfully and file references are valid,
 /// or `Err` if validation fails.
 pub async fn validate_templates(ctx: &mut ValidationContext<'_>, project_dir: &Path) -> Result<()> {
-    ctx.print_verbose("\n🔍 Validating template rendering...");
+    ctx.print_verbose(&format!("\n{SEARCH}Validating template rendering..."));
 
     // Load lockfile - required for template context
     let lockfile_path = project_dir.join("agpm.lock");
@@ -81,12 +82,10 @@ pub async fn validate_templates(ctx: &mut ValidationContext<'_>, project_dir: &P
     macro_rules! validate_resource_template {
         ($name:expr, $entry:expr, $resource_type:expr) => {{
             // Read the resource content
-            let content = if $entry.source.is_some() && $entry.resolved_commit.is_some() {
+            let content = if let (Some(source_name), Some(sha)) =
+                ($entry.source.as_ref(), $entry.resolved_commit.as_ref())
+            {
                 // Git resource - read from worktree
-                // Safe: checked is_some() above in the if condition
-                let source_name = $entry.source.as_ref().unwrap();
-                // Safe: checked is_some() above in the if condition
-                let sha = $entry.resolved_commit.as_ref().unwrap();
                 let url = match $entry.url.as_ref() {
                     Some(u) => u,
                     None => {
@@ -161,7 +160,8 @@ pub async fn validate_templates(ctx: &mut ValidationContext<'_>, project_dir: &P
                 project_config,
                 Arc::clone(&cache),
                 project_dir.to_path_buf(),
-            );
+            )
+            .with_dependency_field(ctx.manifest.transitive.dependency_field());
             // Use canonical name from lockfile entry, not manifest key
             let resource_id = crate::lockfile::ResourceId::new(
                 $entry.name.clone(),
@@ -267,7 +267,7 @@ pub async fn validate_templates(ctx: &mut ValidationContext<'_>, project_dir: &P
 
     // Validate file references in markdown content
     if ctx.verbose && !ctx.quiet {
-        println!("\n🔍 Validating file references in markdown content...");
+        println!("\n{SEARCH}Validating file references in markdown content...");
     }
 
     let mut file_reference_errors = Vec::new();
@@ -277,12 +277,10 @@ pub async fn validate_templates(ctx: &mut ValidationContext<'_>, project_dir: &P
     macro_rules! validate_file_references_in_resource {
         ($name:expr, $entry:expr) => {{
             // Read the resource content
-            let content = if $entry.source.is_some() && $entry.resolved_commit.is_some() {
+            let content = if let (Some(source_name), Some(sha)) =
+                ($entry.source.as_ref(), $entry.resolved_commit.as_ref())
+            {
                 // Git resource - read from worktree
-                // Safe: checked is_some() above in the if condition
-                let source_name = $entry.source.as_ref().unwrap();
-                // Safe: checked is_some() above in the if condition
-                let sha = $entry.resolved_commit.as_ref().unwrap();
                 let url = match $entry.url.as_ref() {
                     Some(u) => u,
                     None => {
@@ -0,0 +1,336 @@
+//! Verify that locked tags still point at the locked commits.
+//!
+//! This module provides the `verify-sources` command. For every lockfile entry
+//! whose version is a tag, it asks the upstream repository where that tag
+//! points today and compares the answer with the `resolved_commit` recorded in
+//! `agpm.lock`. A tag that moved (or disappeared) means the content behind a
+//! "pinned" version changed upstream, typically through a force-push, which is
+//! worth investigating before trusting a release.
+//!
+//! Each source is queried once with `git ls-remote`, and sources are checked in
+//! parallel. The local cache is neither read nor modified.
+//!
+//! Entries that track a branch or are pinned to a commit are skipped, as are
+//! local dependencies.
+//!
+//! # Examples
+//!
+//! ```bash
+//! agpm verify-sources
+//! agpm verify-sources --format json
+//! agpm verify-sources --max-parallel 4
+//! ```
+
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use colored::Colorize;
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use crate::constants::{FALLBACK_CORE_COUNT, MIN_PARALLELISM, PARALLELISM_CORE_MULTIPLIER};
+use crate::git::GitRepo;
+use crate::lockfile::{LockFile, LockedResource};
+use crate::manifest::{Manifest, find_manifest_with_optional};
+use crate::source::SourceManager;
+
+/// Output format for `agpm verify-sources`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum VerifyFormat {
+    /// Human-readable report.
+    #[default]
+    Text,
+    /// JSON object for scripts and CI.
+    Json,
+}
+
+/// Command to check locked tags against their upstream repositories.
+#[derive(Args, Debug)]
+pub struct VerifySourcesCommand {
+    /// Output format (text, json)
+    #[arg(short = 'f', long, value_enum, default_value_t = VerifyFormat::Text)]
+    format: VerifyFormat,
+
+    /// Maximum number of sources queried in parallel
+    #[arg(long, value_name = "NUMBER")]
+    max_parallel: Option<usize>,
+}
+
+/// Outcome of checking one locked tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum TagStatus {
+    /// The tag still points at the locked commit.
+    Ok,
+    /// The tag now points at a different commit.
+    Moved,
+    /// The tag no longer exists upstream.
+    Missing,
+}
+
+/// A lockfile entry pinned to a tag, and what upstream says about it.
+#[derive(Debug, Serialize)]
+struct TagCheck {
+    #[serde(rename = "type")]
+    resource_type: String,
+    name: String,
+    source: String,
+    tag: String,
+    locked_commit: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remote_commit: Option<String>,
+    status: TagStatus,
+}
+
+/// A source that could not be queried.
+#[derive(Debug, Serialize)]
+struct SourceError {
+    source: String,
+    error: String,
+}
+
+/// The full verification report.
+#[derive(Debug, Serialize)]
+struct VerifyReport {
+    checked: usize,
+    moved: usize,
+    missing: usize,
+    entries: Vec<TagCheck>,
+    errors: Vec<SourceError>,
+}
+
+impl VerifySourcesCommand {
+    /// Execute the verify-sources command with an optional manifest path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no lockfile, if any source cannot be
+    /// queried, or if any locked tag moved or disappeared upstream.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let lockfile_path = manifest_path.with_file_name("agpm.lock");
+        if !lockfile_path.exists() {
+            anyhow::bail!(
+                "No lockfile found at {}. Run 'agpm install' first to create a lockfile.",
+                lockfile_path.display()
+            );
+        }
+
+        let manifest = Manifest::load(&manifest_path)?;
+        let lockfile = LockFile::load(&lockfile_path)?;
+        let sources = SourceManager::from_manifest_with_global(&manifest).await?;
+
+        let report = self.verify(&lockfile, &sources).await;
+        match self.format {
+            VerifyFormat::Text => print_report(&report),
+            VerifyFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        }
+
+        if !report.errors.is_empty() {
+            anyhow::bail!("Failed to query {} source(s)", report.errors.len());
+        }
+        if report.moved + report.missing > 0 {
+            anyhow::bail!(
+                "{} locked tag(s) no longer point at the locked commit",
+                report.moved + report.missing
+            );
+        }
+        Ok(())
+    }
+
+    /// Query every source with tag-pinned entries and compare the results.
+    async fn verify(&self, lockfile: &LockFile, sources: &SourceManager) -> VerifyReport {
+        // Group entries by source so each remote is queried once
+        let mut by_source: BTreeMap<&str, Vec<&LockedResource>> = BTreeMap::new();
+        for entry in lockfile.all_resources() {
+            if let (Some(source), Some(_), Some(_)) =
+                (&entry.source, &entry.version, &entry.resolved_commit)
+            {
+                by_source.entry(source).or_default().push(entry);
+            }
+        }
+
+        let max_parallel = self.max_parallel.unwrap_or_else(|| {
+            let cores = std::thread::available_parallelism()
+                .map(std::num::NonZero::get)
+                .unwrap_or(FALLBACK_CORE_COUNT);
+            std::cmp::max(MIN_PARALLELISM, cores * PARALLELISM_CORE_MULTIPLIER)
+        });
+
+        let results: Vec<_> = stream::iter(by_source)
+            .map(|(source, entries)| async move {
+                // Prefer the (possibly authenticated) configured URL, falling
+                // back to the URL recorded when the lockfile was written
+                let url = sources.get_source_url(source).or_else(|| {
+                    lockfile.sources.iter().find(|s| s.name == source).map(|s| s.url.clone())
+                });
+                let refs = match url {
+                    Some(url) => GitRepo::list_remote_refs(&url).await,
+                    None => Err(anyhow::anyhow!("Source '{source}' is not defined")),
+                };
+                (source, entries, refs)
+            })
+            .buffer_unordered(max_parallel.max(1))
+            .collect()
+            .await;
+
+        let mut entries = Vec::new();
+        let mut errors = Vec::new();
+        for (source, locked, refs) in results {
+            let refs = match refs {
+                Ok(refs) => refs,
+                Err(e) => {
+                    errors.push(SourceError {
+                        source: source.to_string(),
+                        error: format!("{e:#}"),
+                    });
+                    continue;
+                }
+            };
+            entries.extend(locked.into_iter().filter_map(|entry| check_entry(entry, &refs)));
+        }
+        entries.sort_by(|a, b| {
+            (&a.source, &a.resource_type, &a.name).cmp(&(&b.source, &b.resource_type, &b.name))
+        });
+        errors.sort_by(|a, b| a.source.cmp(&b.source));
+
+        VerifyReport {
+            checked: entries.len(),
+            moved: entries.iter().filter(|e| e.status == TagStatus::Moved).count(),
+            missing: entries.iter().filter(|e| e.status == TagStatus::Missing).count(),
+            entries,
+            errors,
+        }
+    }
+}
+
+/// Compare one entry with the refs its source advertises.
+///
+/// Returns `None` when the entry's version is not a tag: a branch name, a
+/// commit SHA, or a ref that no longer exists and was never a tag.
+fn check_entry(entry: &LockedResource, refs: &HashMap<String, String>) -> Option<TagCheck> {
+    let tag = entry.version.as_deref()?;
+    let locked_commit = entry.resolved_commit.as_deref()?;
+
+    let remote_commit = refs.get(&format!("refs/tags/{tag}")).cloned();
+    // Branch-tracked entries are locked as `origin/<branch>`
+    let branch = format!("refs/heads/{}", tag.strip_prefix("origin/").unwrap_or(tag));
+    let status = match &remote_commit {
+        Some(sha) if sha == locked_commit => TagStatus::Ok,
+        Some(_) => TagStatus::Moved,
+        // Branches and commit pins are not tags; a version that matches
+        // neither a tag nor a branch is a tag that was deleted upstream
+        None if refs.contains_key(&branch) => return None,
+        None if locked_commit.starts_with(tag) => return None,
+        None => TagStatus::Missing,
+    };
+
+    Some(TagCheck {
+        resource_type: entry.resource_type.to_string(),
+        name: entry.display_name().to_string(),
+        source: entry.source.clone().unwrap_or_default(),
+        tag: tag.to_string(),
+        locked_commit: locked_commit.to_string(),
+        remote_commit,
+        status,
+    })
+}
+
+fn print_report(report: &VerifyReport) {
+    for error in &report.errors {
+        println!("{} {}: {}", "✗".red(), error.source, error.error);
+    }
+
+    let flagged: Vec<_> = report.entries.iter().filter(|e| e.status != TagStatus::Ok).collect();
+    for entry in &flagged {
+        let short = |sha: &str| sha.chars().take(8).collect::<String>();
+        match entry.status {
+            TagStatus::Moved => println!(
+                "{} {} '{}' ({}@{}): tag moved from {} to {}",
+                "✗".red(),
+                entry.resource_type,
+                entry.name,
+                entry.source,
+                entry.tag,
+                short(&entry.locked_commit),
+                short(entry.remote_commit.as_deref().unwrap_or_default())
+            ),
+            TagStatus::Missing => println!(
+                "{} {} '{}' ({}@{}): tag no longer exists upstream",
+                "✗".red(),
+                entry.resource_type,
+                entry.name,
+                entry.source,
+                entry.tag
+            ),
+            TagStatus::Ok => {}
+        }
+    }
+
+    if report.checked == 0 {
+        println!("No tag-pinned dependencies to verify");
+    } else if flagged.is_empty() {
+        println!(
+            "{} All {} locked tag(s) still point at the locked commits",
+            "✓".green(),
+            report.checked
+        );
+    } else {
+        println!(
+            "{}",
+            format!(
+                "{} of {} locked tag(s) changed upstream; review before trusting them",
+                flagged.len(),
+                report.checked
+            )
+            .yellow()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ResourceType;
+    use crate::lockfile::LockedResourceBuilder;
+
+    fn entry(version: &str, commit: &str) -> LockedResource {
+        LockedResourceBuilder::new(
+            "agents/helper".to_string(),
+            "agents/helper.md".to_string(),
+            String::new(),
+            ".claude/agents/agpm/helper.md".to_string(),
+            ResourceType::Agent,
+        )
+        .source(Some("community".to_string()))
+        .version(Some(version.to_string()))
+        .resolved_commit(Some(commit.to_string()))
+        .build()
+    }
+
+    #[test]
+    fn test_check_entry_classifies_refs() {
+        let locked = "a".repeat(40);
+        let refs = HashMap::from([
+            ("refs/tags/v1.0.0".to_string(), locked.clone()),
+            ("refs/tags/v2.0.0".to_string(), "b".repeat(40)),
+            ("refs/heads/main".to_string(), "c".repeat(40)),
+        ]);
+
+        let ok = check_entry(&entry("v1.0.0", &locked), &refs).unwrap();
+        assert_eq!(ok.status, TagStatus::Ok);
+
+        let moved = check_entry(&entry("v2.0.0", &locked), &refs).unwrap();
+        assert_eq!(moved.status, TagStatus::Moved);
+        assert_eq!(moved.remote_commit, Some("b".repeat(40)));
+
+        let missing = check_entry(&entry("v3.0.0", &locked), &refs).unwrap();
+        assert_eq!(missing.status, TagStatus::Missing);
+
+        assert!(check_entry(&entry("main", &locked), &refs).is_none());
+        assert!(check_entry(&entry("origin/main", &locked), &refs).is_none());
+        assert!(check_entry(&entry("aaaaaaa", &locked), &refs).is_none());
+    }
+}
@@ -0,0 +1,229 @@
+//! Scriptable editing of the global configuration by dotted key.
+//!
+//! `agpm config get <key>` and `agpm config set <key> <value>` address settings
+//! in `~/.agpm/config.toml` with dotted keys such as `git.timeout` or
+//! `sources.private`. The file is edited with `toml_edit`, so comments,
+//! ordering and formatting outside the changed key are preserved.
+//!
+//! Values given to `set` are typed from their text: `true`/`false` become
+//! booleans, integers become integers, and anything else is a string. After an
+//! edit, the whole document must still parse as a [`GlobalConfig`], so a value
+//! of the wrong type for a known key is rejected before anything is written.
+
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use toml_edit::{DocumentMut, Item, Table, Value};
+
+use super::GlobalConfig;
+
+/// Dotted keys of every scalar setting in the global configuration.
+///
+/// Source URLs are addressed as `sources.<name>` and are always known.
+pub const KNOWN_KEYS: &[&str] = &[
+    "max_content_file_size",
+    "token_warning_threshold",
+    "credential_store",
+    "upgrade.check_on_startup",
+    "upgrade.check_interval",
+    "git.timeout",
+    "git.partial",
+    "git.submodules",
+    "git.insecure_ssl",
+    "cache.read_only_base",
+    "cache.mirror_url",
+    "cache.worktree_max_age_days",
+    "cache.temp_dir",
+];
+
+/// Whether `key` names a setting AGPM understands.
+#[must_use]
+pub fn is_known_key(key: &str) -> bool {
+    KNOWN_KEYS.contains(&key)
+        || key.strip_prefix("sources.").is_some_and(|name| !name.is_empty() && !name.contains('.'))
+}
+
+/// Parse a command-line value: `true`/`false` as booleans, integers as
+/// integers, and anything else as a string.
+#[must_use]
+pub fn parse_value(raw: &str) -> Value {
+    match raw {
+        "true" => Value::from(true),
+        "false" => Value::from(false),
+        _ => raw.parse::<i64>().map_or_else(|_| Value::from(raw), Value::from),
+    }
+}
+
+/// Look up `key` in `doc`, formatted for output.
+///
+/// Strings are returned without quotes; other values use their TOML form.
+/// Returns `None` when the key is not set.
+#[must_use]
+pub fn get_value(doc: &DocumentMut, key: &str) -> Option<String> {
+    let mut item = doc.as_item();
+    for part in key.split('.') {
+        item = item.as_table_like()?.get(part)?;
+    }
+    match item.as_value()? {
+        Value::String(s) => Some(s.value().clone()),
+        value => {
+            let mut value = value.clone();
+            value.decor_mut().clear();
+            Some(value.to_string())
+        }
+    }
+}
+
+/// Set `key` in `doc` to `value`, creating intermediate tables as needed.
+///
+/// # Errors
+///
+/// Returns an error if the key is empty or a parent key holds a plain value,
+/// or if the resulting document is not a valid global configuration.
+pub fn set_value(doc: &mut DocumentMut, key: &str, value: Value) -> Result<()> {
+    let parts: Vec<&str> = key.split('.').collect();
+    if parts.iter().any(|part| part.is_empty()) {
+        bail!("Invalid config key '{key}'");
+    }
+    let (leaf, parents) = parts.split_last().expect("split always yields a part");
+
+    let mut table = doc.as_table_mut() as &mut dyn toml_edit::TableLike;
+    for (depth, part) in parents.iter().enumerate() {
+        let entry = table.entry(part).or_insert_with(|| {
+            let mut child = Table::new();
+            child.set_implicit(true);
+            Item::Table(child)
+        });
+        table = entry.as_table_like_mut().with_context(|| {
+            format!("Config key '{}' is not a table", parents[..=depth].join("."))
+        })?;
+    }
+    match table.get_mut(leaf) {
+        // Keep surrounding whitespace and trailing comments of a replaced value
+        Some(Item::Value(existing)) => {
+            let decor = existing.decor().clone();
+            *existing = value;
+            *existing.decor_mut() = decor;
+        }
+        _ => {
+            table.insert(leaf, Item::Value(value));
+        }
+    }
+
+    toml::from_str::<GlobalConfig>(&doc.to_string())
+        .with_context(|| format!("Invalid value for config key '{key}'"))?;
+    Ok(())
+}
+
+/// Read the global config at `path` as an editable document.
+///
+/// A missing file yields an empty document.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not valid TOML.
+pub async fn load_document(path: &Path) -> Result<DocumentMut> {
+    if !path.exists() {
+        return Ok(DocumentMut::new());
+    }
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read global config from {}", path.display()))?;
+    content
+        .parse()
+        .with_context(|| format!("Failed to parse global config from {}", path.display()))
+}
+
+/// Write `doc` to `path` with the same restrictive permissions as
+/// [`GlobalConfig::save_to`].
+///
+/// # Errors
+///
+/// Returns an error if the directory or file cannot be written.
+pub async fn save_document(doc: &DocumentMut, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create config directory: {}", parent.display()))?;
+    }
+    tokio::fs::write(path, doc.to_string())
+        .await
+        .with_context(|| format!("Failed to write global config to {}", path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+            .await
+            .with_context(|| format!("Failed to set permissions for {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"# Global AGPM configuration
+token_warning_threshold = 50000 # tokens
+
+[sources]
+# Team repository
+private = "https://github.com/org/private.git"
+"#;
+
+    #[test]
+    fn test_parse_value_types() {
+        assert_eq!(parse_value("true").as_bool(), Some(true));
+        assert_eq!(parse_value("false").as_bool(), Some(false));
+        assert_eq!(parse_value("600").as_integer(), Some(600));
+        assert_eq!(parse_value("keychain").as_str(), Some("keychain"));
+        assert_eq!(parse_value("1.5").as_str(), Some("1.5"));
+    }
+
+    #[test]
+    fn test_round_trip_key_types() {
+        let mut doc: DocumentMut = CONFIG.parse().unwrap();
+
+        set_value(&mut doc, "git.partial", parse_value("true")).unwrap();
+        set_value(&mut doc, "git.timeout", parse_value("600")).unwrap();
+        set_value(&mut doc, "credential_store", parse_value("keychain")).unwrap();
+        set_value(&mut doc, "sources.community", parse_value("https://example.com/c.git")).unwrap();
+        set_value(&mut doc, "token_warning_threshold", parse_value("75000")).unwrap();
+
+        assert_eq!(get_value(&doc, "git.partial").as_deref(), Some("true"));
+        assert_eq!(get_value(&doc, "git.timeout").as_deref(), Some("600"));
+        assert_eq!(get_value(&doc, "credential_store").as_deref(), Some("keychain"));
+        assert_eq!(
+            get_value(&doc, "sources.community").as_deref(),
+            Some("https://example.com/c.git")
+        );
+        assert_eq!(get_value(&doc, "token_warning_threshold").as_deref(), Some("75000"));
+        assert_eq!(get_value(&doc, "cache.mirror_url"), None);
+
+        // Comments survive the edit and the result loads as a GlobalConfig
+        let text = doc.to_string();
+        assert!(text.contains("# Global AGPM configuration"));
+        assert!(text.contains("# Team repository"));
+        let config: GlobalConfig = toml::from_str(&text).unwrap();
+        assert_eq!(config.git.timeout, Some(600));
+        assert_eq!(config.git.partial, Some(true));
+        assert_eq!(config.token_warning_threshold, 75000);
+        assert_eq!(config.sources.len(), 2);
+    }
+
+    #[test]
+    fn test_set_value_rejects_invalid() {
+        let mut doc: DocumentMut = CONFIG.parse().unwrap();
+        assert!(set_value(&mut doc, "git.timeout", parse_value("soon")).is_err());
+        assert!(set_value(&mut doc, "token_warning_threshold.x", parse_value("1")).is_err());
+        assert!(set_value(&mut doc, "git..timeout", parse_value("1")).is_err());
+    }
+
+    #[test]
+    fn test_is_known_key() {
+        assert!(is_known_key("git.timeout"));
+        assert!(is_known_key("sources.private"));
+        assert!(!is_known_key("sources."));
+        assert!(!is_known_key("git.timeot"));
+    }
+}
@@ -15,6 +15,8 @@
 //!
 //! # Modules
 //!
+//! - `credentials` - Optional OS keychain backend for per-source tokens
+//! - `env_file` - Project-local `.env.agpm` files for `token_env` variables
 //! - `global` - Global configuration management with authentication token support
 //! - `parser` - Generic TOML parsing utilities with error context
 //!
@@ -182,10 +184,15 @@
 //! # }
 //! ```
 
+mod credentials;
+pub mod edit;
+mod env_file;
 mod global;
 mod parser;
 
-pub use global::{GlobalConfig, GlobalConfigManager};
+pub use credentials::{CredentialStore, KeychainStore, SecretStore, apply_credentials};
+pub use env_file::{DEFAULT_ENV_FILE, EnvFile, apply_token_env};
+pub use global::{AuditConfig, CacheConfig, GitConfig, GlobalConfig, GlobalConfigManager};
 pub use parser::parse_config;
 
 use crate::core::file_error::{FileOperation, FileResultExt};
@@ -199,8 +206,9 @@ use std::path::PathBuf;
 ///
 /// # Location Priority
 ///
-/// 1. `AGPM_CACHE_DIR` environment variable (if set)
-/// 2. Platform-specific cache directory:
+/// 1. The throwaway directory of `--no-cache` (see [`crate::cache::temporary`])
+/// 2. `AGPM_CACHE_DIR` environment variable (if set)
+/// 3. Platform-specific cache directory:
 ///    - Windows: `%LOCALAPPDATA%\agpm\cache`
 ///    - macOS/Linux: `~/.agpm/cache`
 ///
@@ -227,6 +235,11 @@ use std::path::PathBuf;
 /// - The cache directory cannot be created
 /// - Insufficient permissions for directory creation
 pub fn get_cache_dir() -> Result<PathBuf> {
+    // `--no-cache` wins over every configured location
+    if let Some(dir) = crate::cache::temporary::temporary_dir() {
+        return Ok(dir);
+    }
+
     // Check for environment variable override first (essential for testing)
     if let Ok(dir) = std::env::var("AGPM_CACHE_DIR") {
         return Ok(PathBuf::from(dir));
@@ -58,6 +58,12 @@ pub const STARTING_BACKOFF_DELAY_MS: u64 = 25;
 #[cfg(not(windows))]
 pub const STARTING_BACKOFF_DELAY_MS: u64 = 10;
 
+/// Default timeout for any single Git command (5 minutes).
+///
+/// Applies to every [`GitCommand`](crate::git::command_builder::GitCommand),
+/// including fetches, unless a tighter per-operation limit is enforced.
+pub const GIT_COMMAND_TIMEOUT: Duration = Duration::from_secs(300);
+
 /// Timeout for Git fetch operations (60 seconds).
 ///
 /// This timeout prevents hung network connections from blocking
@@ -116,3 +122,15 @@ pub const PARALLELISM_CORE_MULTIPLIER: usize = 2;
 ///
 /// Used as a fallback when `std::thread::available_parallelism()` returns an error.
 pub const FALLBACK_CORE_COUNT: usize = 4;
+
+/// Timeout for acquiring worktree locks during `cache dedupe` (2 seconds).
+///
+/// Deduplication is opportunistic: a worktree whose lock cannot be acquired
+/// quickly is assumed to be in use and is skipped rather than waited on.
+pub const DEDUPE_LOCK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Timeout for acquiring worktree locks during age-based worktree cleanup (2 seconds).
+///
+/// A stale worktree whose repository lock is held by another operation is
+/// skipped and left for a later pass.
+pub const WORKTREE_GC_LOCK_TIMEOUT: Duration = Duration::from_secs(2);
@@ -605,6 +605,124 @@ pub enum AgpmError {
     },
 }
 
+impl AgpmError {
+    /// Stable machine-readable code for this error, e.g. `manifest-not-found`.
+    ///
+    /// Codes are kebab-case variant names and are reported as `code` by
+    /// `--json-errors`, so scripts can branch on the kind of failure without
+    /// parsing messages.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::GitCommandError {
+                ..
+            } => "git-command-error",
+            Self::GitNotFound => "git-not-found",
+            Self::GitRepoInvalid {
+                ..
+            } => "git-repo-invalid",
+            Self::GitAuthenticationFailed {
+                ..
+            } => "git-authentication-failed",
+            Self::GitCloneFailed {
+                ..
+            } => "git-clone-failed",
+            Self::GitCheckoutFailed {
+                ..
+            } => "git-checkout-failed",
+            Self::ConfigError {
+                ..
+            } => "config-error",
+            Self::ManifestNotFound => "manifest-not-found",
+            Self::ManifestParseError {
+                ..
+            } => "manifest-parse-error",
+            Self::ManifestValidationError {
+                ..
+            } => "manifest-validation-error",
+            Self::LockfileParseError {
+                ..
+            } => "lockfile-parse-error",
+            Self::InvalidLockfileError {
+                ..
+            } => "invalid-lockfile-error",
+            Self::ResourceNotFound {
+                ..
+            } => "resource-not-found",
+            Self::ResourceFileNotFound {
+                ..
+            } => "resource-file-not-found",
+            Self::SourceNotFound {
+                ..
+            } => "source-not-found",
+            Self::SourceUnreachable {
+                ..
+            } => "source-unreachable",
+            Self::InvalidVersionConstraint {
+                ..
+            } => "invalid-version-constraint",
+            Self::VersionNotFound {
+                ..
+            } => "version-not-found",
+            Self::AlreadyInstalled {
+                ..
+            } => "already-installed",
+            Self::InvalidResourceType {
+                ..
+            } => "invalid-resource-type",
+            Self::InvalidResourceStructure {
+                ..
+            } => "invalid-resource-structure",
+            Self::CircularDependency {
+                ..
+            } => "circular-dependency",
+            Self::DependencyResolutionFailed {
+                ..
+            } => "dependency-resolution-failed",
+            Self::DependencyResolutionMismatch {
+                ..
+            } => "dependency-resolution-mismatch",
+            Self::NetworkError {
+                ..
+            } => "network-error",
+            Self::FileSystemError {
+                ..
+            } => "file-system-error",
+            Self::PermissionDenied {
+                ..
+            } => "permission-denied",
+            Self::DirectoryNotEmpty {
+                ..
+            } => "directory-not-empty",
+            Self::InvalidDependency {
+                ..
+            } => "invalid-dependency",
+            Self::InvalidResource {
+                ..
+            } => "invalid-resource",
+            Self::DependencyNotMet {
+                ..
+            } => "dependency-not-met",
+            Self::ConfigNotFound {
+                ..
+            } => "config-not-found",
+            Self::ChecksumMismatch {
+                ..
+            } => "checksum-mismatch",
+            Self::PlatformNotSupported {
+                ..
+            } => "platform-not-supported",
+            Self::IoError(_) => "io-error",
+            Self::TomlError(_) => "toml-error",
+            Self::TomlSerError(_) => "toml-serialize-error",
+            Self::SemverError(_) => "semver-error",
+            Self::Other {
+                ..
+            } => "other",
+        }
+    }
+}
+
 /// Error context wrapper that provides user-friendly error information
 ///
 /// `ErrorContext` wraps a [`AgpmError`] and adds optional user-friendly messages,
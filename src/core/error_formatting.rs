@@ -200,6 +200,33 @@ pub fn user_friendly_error(error: anyhow::Error) -> ErrorContext {
     .with_details("An unexpected error occurred. Please report this issue if it persists.")
 }
 
+/// Convert any error into the JSON object printed by `--json-errors`
+///
+/// The error is classified like [`user_friendly_error`]; the object also keeps
+/// the full error chain as `context`, which the text output leaves out:
+///
+/// ```json
+/// {"error": {"code": "other", "message": "...", "context": ["..."],
+///            "details": "...", "suggestions": ["..."]}}
+/// ```
+#[must_use]
+pub fn json_error(error: anyhow::Error) -> serde_json::Value {
+    let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+    let ctx = user_friendly_error(error);
+    let message = ctx.error.to_string();
+    let context: Vec<String> = chain.into_iter().filter(|m| *m != message).collect();
+
+    serde_json::json!({
+        "error": {
+            "code": ctx.error.code(),
+            "message": message,
+            "context": context,
+            "details": ctx.details,
+            "suggestions": ctx.suggestion.into_iter().collect::<Vec<_>>(),
+        }
+    })
+}
+
 /// Create a user-friendly error context from an [`AgpmError`]
 ///
 /// This function analyzes the error type and provides:
@@ -287,6 +314,19 @@ pub fn create_error_context(error: &AgpmError) -> ErrorContext {
         })
         .with_suggestion("Check file permissions and try running with appropriate privileges")
         .with_details(format!("Permission denied for '{}' on path: {}", operation, path)),
+        AgpmError::CircularDependency {
+            chain,
+        } => ErrorContext::new(AgpmError::CircularDependency {
+            chain: chain.clone(),
+        })
+        .with_suggestion(
+            "Remove the frontmatter `dependencies` entry that closes the cycle, \
+             or move shared content into a resource that none of them depend on",
+        )
+        .with_details(format!(
+            "Each resource below declares the next one as a dependency:\n  {}",
+            chain.replace(" → ", "\n  → ")
+        )),
         AgpmError::DependencyResolutionMismatch {
             resource,
             declared_count,
@@ -327,6 +367,17 @@ mod tests {
     use super::*;
     use std::io;
 
+    #[test]
+    fn test_json_error() {
+        let error = anyhow::Error::from(AgpmError::ManifestNotFound).context("Failed to load");
+        let json = json_error(error);
+
+        assert_eq!(json["error"]["code"], "manifest-not-found");
+        assert_eq!(json["error"]["message"], AgpmError::ManifestNotFound.to_string());
+        assert_eq!(json["error"]["context"], serde_json::json!(["Failed to load"]));
+        assert!(!json["error"]["suggestions"].as_array().unwrap().is_empty());
+    }
+
     #[test]
     fn test_user_friendly_error_io_permission_denied() {
         let io_err = io::Error::new(io::ErrorKind::PermissionDenied, "Access denied");
@@ -412,6 +412,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         lockfile.snippets.push(LockedResource {
@@ -433,6 +434,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         lockfile
@@ -465,6 +467,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         lockfile.agents.push(LockedResource {
@@ -486,6 +489,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         // Add commands from source1
@@ -508,6 +512,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         // Add scripts
@@ -530,6 +535,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         // Add hooks
@@ -552,6 +558,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         // Add MCP servers
@@ -574,6 +581,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         // Add resource without source
@@ -596,6 +604,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         lockfile
@@ -645,6 +654,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         // Verify the agent was added
@@ -1102,6 +1112,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         let groups = ResourceIterator::group_by_source(&lockfile);
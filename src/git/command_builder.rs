@@ -10,6 +10,8 @@ use std::time::Duration;
 use tokio::process::Command;
 use tokio::time::timeout;
 
+use super::timeout::GitOperation;
+use crate::constants::GIT_COMMAND_TIMEOUT;
 use crate::core::AgpmError;
 use crate::utils::platform::get_git_command;
 
@@ -106,12 +108,27 @@ impl Default for GitCommand {
             capture_output: true,
             env_vars: Vec::new(),
             // Default timeout of 5 minutes for most git operations
-            timeout_duration: Some(Duration::from_secs(300)),
+            timeout_duration: Some(GIT_COMMAND_TIMEOUT),
             context: None,
         }
     }
 }
 
+/// Timeout for `operation` given the `--git-timeout` override.
+///
+/// Without an override the builder's `current` timeout is kept; otherwise the
+/// override applies, with `0` disabling the timeout.
+fn effective_timeout(
+    override_secs: Option<u64>,
+    operation: GitOperation,
+    current: Option<Duration>,
+) -> Option<Duration> {
+    match override_secs {
+        None => current,
+        Some(_) => super::timeout::resolve_timeout(override_secs, operation),
+    }
+}
+
 impl GitCommand {
     /// Creates a new Git command builder with default settings.
     ///
@@ -324,6 +341,16 @@ impl GitCommand {
         self
     }
 
+    /// Apply the `--git-timeout` override to a long-running operation.
+    ///
+    /// Without an override the builder keeps its general 5-minute limit; the
+    /// tighter per-operation defaults are enforced by the cache.
+    fn with_operation_timeout(self, operation: GitOperation) -> Self {
+        let timeout =
+            effective_timeout(super::timeout::override_secs(), operation, self.timeout_duration);
+        self.with_timeout(timeout)
+    }
+
     /// Set a context for logging (e.g., dependency name)
     ///
     /// The context is included in debug log messages to help distinguish between
@@ -406,6 +433,9 @@ impl GitCommand {
             tracing::trace!(target: "git", "Setting env var: {}={}", key, value);
             cmd.env(key, value);
         }
+        if let Some((key, value)) = super::ssl::ssl_env() {
+            cmd.env(key, value);
+        }
 
         if self.capture_output {
             cmd.stdout(Stdio::piped());
@@ -499,7 +529,11 @@ impl GitCommand {
 
             let error = if effective_args.first().is_some_and(|arg| arg == "clone") {
                 // Use the URL we stored when building the command, not by parsing args
-                let url = self.clone_url.unwrap_or_else(|| "unknown".to_string());
+                // Credentials in the URL must not reach error output
+                let url = self.clone_url.map_or_else(
+                    || "unknown".to_string(),
+                    |url| crate::git::strip_auth_from_url(&url).unwrap_or(url),
+                );
                 AgpmError::GitCloneFailed {
                     url,
                     reason: stderr.to_string(),
@@ -666,6 +700,9 @@ impl GitCommand {
             tracing::trace!(target: "git", "Setting env var: {}={}", key, value);
             cmd.env(key, value);
         }
+        if let Some((key, value)) = super::ssl::ssl_env() {
+            cmd.env(key, value);
+        }
 
         // Set up piped stdin for writing
         cmd.stdin(Stdio::piped());
@@ -790,7 +827,7 @@ impl GitCommand {
         cmd.args.push(url.to_string());
         cmd.args.push(target.as_ref().display().to_string());
         cmd.clone_url = Some(url.to_string());
-        cmd
+        cmd.with_operation_timeout(GitOperation::Clone)
     }
 
     /// Create a clone command with specific depth
@@ -799,7 +836,27 @@ impl GitCommand {
     pub fn fetch() -> Self {
         // Use --all to fetch from all remotes and --tags to get tags
         // For bare repositories, we need to ensure remote tracking branches are created
-        Self::new().args(["fetch", "--all", "--tags", "--force"])
+        Self::new()
+            .args(["fetch", "--all", "--tags", "--force"])
+            .with_operation_timeout(GitOperation::Fetch)
+    }
+
+    /// Create a fetch command that skips file contents (`--filter=blob:none`)
+    ///
+    /// Servers without filter support ignore the filter and send everything.
+    pub fn fetch_refs_only() -> Self {
+        Self::new()
+            .args(["fetch", "--all", "--tags", "--force", "--filter=blob:none"])
+            .with_operation_timeout(GitOperation::Fetch)
+    }
+
+    /// Create a fetch command for a single ref, stored locally under the same name
+    ///
+    /// The `+` forces the update so a force-pushed ref replaces the old value.
+    pub fn fetch_ref(ref_spec: &str) -> Self {
+        Self::new()
+            .args(["fetch", "--force", "origin", &format!("+{ref_spec}:{ref_spec}")])
+            .with_operation_timeout(GitOperation::Fetch)
     }
 
     /// Create a checkout command
@@ -822,6 +879,61 @@ impl GitCommand {
         Self::new().args(["tag", "-l", "--sort=version:refname"])
     }
 
+    /// Create a command listing tags with their creation time (Unix seconds), newest first
+    pub fn list_tags_by_date() -> Self {
+        Self::new().args([
+            "for-each-ref",
+            "--sort=-creatordate",
+            "--format=%(creatordate:unix) %(refname:strip=2)",
+            "refs/tags",
+        ])
+    }
+
+    /// Create a command listing tags with their object type and annotation message,
+    /// one NUL-terminated record per tag
+    pub fn list_tag_messages() -> Self {
+        Self::new().args([
+            "for-each-ref",
+            "--format=%(objecttype) %(refname:strip=2)%0a%(contents)%00",
+            "refs/tags",
+        ])
+    }
+
+    /// Create a command listing tags with the commit each points at. The peeled
+    /// commit (`%(*objectname)`) is only set for annotated tags
+    pub fn list_tag_commits() -> Self {
+        Self::new().args([
+            "for-each-ref",
+            "--format=%(refname:strip=2) %(objectname) %(*objectname)",
+            "refs/tags",
+        ])
+    }
+
+    /// Create a command printing a file as it is at a commit
+    pub fn show_file(commit: &str, path: &str) -> Self {
+        Self::new().args(["show".to_string(), format!("{commit}:{path}")])
+    }
+
+    /// Create a command listing the paths of all files at a commit
+    pub fn list_files(commit: &str) -> Self {
+        Self::new().args(["ls-tree", "-r", "--name-only", "-z", commit])
+    }
+
+    /// Create a command describing a commit relative to its closest tag
+    pub fn describe_tags(commit: &str) -> Self {
+        Self::new().args(["describe", "--tags", "--long", commit])
+    }
+
+    /// Create a command reading the remote default branch recorded as `origin/HEAD`
+    pub fn origin_head() -> Self {
+        Self::new().args(["symbolic-ref", "refs/remotes/origin/HEAD"])
+    }
+
+    /// Create a command asking the remote for its default branch and recording it as `origin/HEAD`
+    pub fn set_origin_head() -> Self {
+        Self::new().args(["remote", "set-head", "origin", "--auto"])
+    }
+
     /// Create a branch list command
     pub fn list_branches() -> Self {
         Self::new().args(["branch", "-r"])
@@ -852,6 +964,11 @@ impl GitCommand {
         Self::new().args(["ls-remote", "--heads", url])
     }
 
+    /// Create a ls-remote command listing every ref advertised by a remote
+    pub fn ls_remote_refs(url: &str) -> Self {
+        Self::new().args(["ls-remote", url])
+    }
+
     /// Create a command to verify a reference exists
     pub fn verify_ref(ref_name: &str) -> Self {
         Self::new().args(["rev-parse", "--verify", ref_name])
@@ -946,19 +1063,23 @@ impl GitCommand {
     /// # }
     /// ```
     pub fn clone_bare(url: &str, target: impl AsRef<Path>) -> Self {
+        Self::clone_bare_with_filter(url, target, super::partial::use_blob_filter(url))
+    }
+
+    /// Create a bare clone command, optionally as a blob-less partial clone.
+    ///
+    /// [`clone_bare`](Self::clone_bare) decides `partial` from the `git.partial`
+    /// setting; with `--filter=blob:none` only commits, trees and tags are
+    /// downloaded, and file contents are fetched when a worktree is checked out.
+    pub(crate) fn clone_bare_with_filter(
+        url: &str,
+        target: impl AsRef<Path>,
+        partial: bool,
+    ) -> Self {
         let mut cmd = Self::new();
         let mut args = vec!["clone".to_string(), "--bare".to_string(), "--progress".to_string()];
 
-        // Only use partial clone for remote repositories
-        // Local repositories (file://, absolute paths, relative paths) need full clones
-        // to work properly with worktrees, especially when they're bare repositories
-        let is_local = url.starts_with("file://")
-            || url.starts_with('/')
-            || url.starts_with('.')
-            || url.starts_with('~')
-            || (url.len() > 1 && url.chars().nth(1) == Some(':')); // Windows paths like C:
-
-        if !is_local {
+        if partial {
             args.push("--filter=blob:none".to_string());
         }
 
@@ -970,7 +1091,7 @@ impl GitCommand {
 
         cmd.args.extend(args);
         cmd.clone_url = Some(url.to_string());
-        cmd
+        cmd.with_operation_timeout(GitOperation::Clone)
     }
 
     /// Create a clone command for local file:// URLs with proper arguments and error context.
@@ -1009,7 +1130,7 @@ impl GitCommand {
             target.as_ref().display().to_string(),
         ];
         cmd.clone_url = Some(url.to_string()); // Properly set for error reporting
-        cmd
+        cmd.with_operation_timeout(GitOperation::Clone)
     }
 
     /// Create a worktree add command for parallel-safe Git operations.
@@ -1081,7 +1202,7 @@ impl GitCommand {
             cmd.args.push(ref_name.to_string());
         }
 
-        cmd
+        cmd.with_operation_timeout(GitOperation::Worktree)
     }
 
     /// Remove a worktree and clean up associated files.
@@ -1133,6 +1254,39 @@ impl GitCommand {
         ])
     }
 
+    /// Initialize and check out all submodules of a worktree, recursively.
+    ///
+    /// Must be executed from the worktree. Submodules are cloned from the URLs
+    /// in `.gitmodules`, so the command uses the clone timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `allow_file_transport` - Permit `file://` and plain-path submodule URLs,
+    ///   which Git blocks by default since 2.38.1 (`protocol.file.allow`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use agpm_cli::git::command_builder::GitCommand;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// GitCommand::submodule_update(false)
+    ///     .current_dir("/tmp/work-v1")
+    ///     .execute_success()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn submodule_update(allow_file_transport: bool) -> Self {
+        let mut cmd = Self::new();
+        if allow_file_transport {
+            cmd.args.push("-c".to_string());
+            cmd.args.push("protocol.file.allow=always".to_string());
+        }
+        cmd.args.extend(["submodule", "update", "--init", "--recursive"].map(String::from));
+        cmd.with_operation_timeout(GitOperation::Clone)
+    }
+
     /// List all worktrees associated with a repository.
     ///
     /// This command returns information about all worktrees linked to the
@@ -1248,6 +1402,20 @@ mod tests {
         // - "Git stdout (raw): git version X.Y.Z"
     }
 
+    #[test]
+    fn test_operation_timeout_override() {
+        // Without an override the builder keeps its current timeout
+        let default = Some(GIT_COMMAND_TIMEOUT);
+        assert_eq!(effective_timeout(None, GitOperation::Clone, default), default);
+
+        // Long-running operations pick up the override, and zero disables the timeout
+        let hour = Some(Duration::from_secs(3600));
+        for operation in [GitOperation::Clone, GitOperation::Fetch, GitOperation::Worktree] {
+            assert_eq!(effective_timeout(Some(3600), operation, default), hour);
+            assert_eq!(effective_timeout(Some(0), operation, default), None);
+        }
+    }
+
     #[test]
     fn test_command_builder_with_dir() {
         let cmd = GitCommand::new().current_dir("/tmp/repo").arg("status");
@@ -0,0 +1,123 @@
+//! Nearest-tag descriptions for commits (`git describe --tags`).
+//!
+//! Dependencies pinned with `rev` are recorded by commit SHA, which says little
+//! to a reader. Describing the commit relative to the closest reachable tag
+//! (`v1.2.0+3` for three commits past `v1.2.0`) gives a readable hint of where
+//! it sits in the history. The lookup only reads the local repository, so it
+//! works offline whenever the commit is already in the cache.
+
+use anyhow::{Context, Result};
+use std::fmt;
+
+use super::GitRepo;
+use super::command_builder::GitCommand;
+
+/// The closest tag reachable from a commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NearestTag {
+    /// Name of the tag.
+    pub tag: String,
+    /// Number of commits between the tag and the described commit.
+    pub distance: u32,
+}
+
+impl NearestTag {
+    /// Parse the output of `git describe --tags --long` (`<tag>-<n>-g<sha>`).
+    ///
+    /// Tag names may themselves contain dashes, so the output is split from
+    /// the right.
+    #[must_use]
+    pub fn parse(describe: &str) -> Option<Self> {
+        let mut parts = describe.trim().rsplitn(3, '-');
+        let abbrev = parts.next()?;
+        let distance = parts.next()?.parse().ok()?;
+        let tag = parts.next()?;
+        if !abbrev.starts_with('g') || tag.is_empty() {
+            return None;
+        }
+        Some(Self {
+            tag: tag.to_string(),
+            distance,
+        })
+    }
+}
+
+impl fmt::Display for NearestTag {
+    /// `v1.2.0` for a tagged commit, `v1.2.0+3` for three commits past it.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.distance == 0 {
+            write!(f, "{}", self.tag)
+        } else {
+            write!(f, "{}+{}", self.tag, self.distance)
+        }
+    }
+}
+
+impl GitRepo {
+    /// Describe `commit` relative to the closest tag reachable from it.
+    ///
+    /// Runs `git describe --tags --long` against this repository without
+    /// fetching.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the commit is not in the repository, no tag is
+    /// reachable from it, or the output cannot be parsed.
+    pub async fn describe_nearest_tag(&self, commit: &str) -> Result<NearestTag> {
+        let stdout = GitCommand::describe_tags(commit)
+            .current_dir(self.path())
+            .execute_stdout()
+            .await
+            .with_context(|| format!("Failed to describe commit {commit} in {:?}", self.path()))?;
+        NearestTag::parse(&stdout)
+            .with_context(|| format!("Unexpected git describe output: {stdout}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestGit;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_and_display() {
+        let tag = NearestTag::parse("v1.2.0-3-gabc1234").unwrap();
+        assert_eq!(tag.tag, "v1.2.0");
+        assert_eq!(tag.distance, 3);
+        assert_eq!(tag.to_string(), "v1.2.0+3");
+
+        let exact = NearestTag::parse("release-2024-01-0-g0123abc\n").unwrap();
+        assert_eq!(exact.tag, "release-2024-01");
+        assert_eq!(exact.to_string(), "release-2024-01");
+
+        assert!(NearestTag::parse("abc1234").is_none());
+        assert!(NearestTag::parse("v1.0.0-x-gabc1234").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_describe_commit_past_tag() -> Result<()> {
+        let temp = TempDir::new()?;
+        let git = TestGit::new(temp.path());
+        git.init()?;
+        git.config_user()?;
+        std::fs::write(temp.path().join("a.md"), "one")?;
+        git.add_all()?;
+        git.commit("one")?;
+        git.tag("v1.0.0")?;
+        for i in 0..2 {
+            std::fs::write(temp.path().join("a.md"), format!("change {i}"))?;
+            git.add_all()?;
+            git.commit("change")?;
+        }
+
+        let repo = GitRepo::new(temp.path());
+        let head = repo.get_current_commit().await?;
+        let nearest = repo.describe_nearest_tag(&head).await?;
+        assert_eq!(nearest.to_string(), "v1.0.0+2");
+
+        let tagged = repo.resolve_to_sha(Some("v1.0.0")).await?;
+        assert_eq!(repo.describe_nearest_tag(&tagged).await?.to_string(), "v1.0.0");
+        Ok(())
+    }
+}
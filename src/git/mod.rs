@@ -11,16 +11,30 @@
 //! - **Cross-platform**: Windows, macOS, Linux support
 //! - **Progress reporting**: User feedback during long operations
 //! - **Tag caching**: Per-instance caching for performance (v0.4.11+)
+//! - **Configurable timeouts**: Clone, fetch and worktree limits via `--git-timeout`
+//! - **Partial clones**: Blob-less cache clones, tunable via `git.partial`, and
+//!   blob-less fetches for resolution-only commands
+//! - **Submodules**: Submodule checkout in worktrees, disabled via `git.submodules`
+//! - **Nearest tags**: Readable `v1.2.0+3` descriptions of commit SHAs
+//! - **Raw refs**: `ref:refs/pull/42/head` versions fetched on demand
+//! - **Insecure TLS**: Opt-in escape hatch disabling certificate checks via `git.insecure_ssl`
 //!
 //! # Security
 //!
 //! - Command injection prevention via proper argument passing
 //! - Credentials never logged or exposed in errors
-//! - HTTPS verification enabled by default
+//! - HTTPS verification enabled by default; only `--insecure` or
+//!   `git.insecure_ssl` turns it off, with a warning on every run
 
 pub mod command_builder;
+pub mod describe;
+pub mod partial;
+pub mod raw_ref;
+pub mod ssl;
+pub mod submodules;
 #[cfg(test)]
 mod tests;
+pub mod timeout;
 
 use crate::core::AgpmError;
 use crate::git::command_builder::GitCommand;
@@ -28,6 +42,9 @@ use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
+pub use describe::NearestTag;
+pub use partial::FetchMode;
+
 /// A Git repository handle providing async operations via CLI commands.
 ///
 #[derive(Debug, Clone)]
@@ -110,6 +127,18 @@ impl GitRepo {
     /// - The remote repository is unavailable
     /// - The local repository is in an invalid state
     pub async fn fetch(&self, auth_url: Option<&str>) -> Result<()> {
+        self.fetch_with_mode(auth_url, FetchMode::Full).await
+    }
+
+    /// Fetches updates like [`fetch`](Self::fetch), downloading only what `mode` needs.
+    ///
+    /// With [`FetchMode::RefsOnly`] file contents are skipped; Git fetches them
+    /// on demand when a worktree is checked out.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`fetch`](Self::fetch).
+    pub async fn fetch_with_mode(&self, auth_url: Option<&str>, mode: FetchMode) -> Result<()> {
         // Note: file:// URLs are local repositories, but we still need to fetch
         // from them to get updates from the source repository
 
@@ -120,7 +149,30 @@ impl GitRepo {
         }
 
         // Now fetch with the potentially updated URL
-        GitCommand::fetch().current_dir(&self.path).execute_success().await?;
+        match mode {
+            FetchMode::Full => {
+                GitCommand::fetch().current_dir(&self.path).execute_success().await?;
+            }
+            FetchMode::RefsOnly => {
+                // A filtered fetch records its filter as the remote's default,
+                // which would make every later fetch blob-less too
+                let had_filter = GitCommand::new()
+                    .args(["config", "--get", "remote.origin.partialclonefilter"])
+                    .current_dir(&self.path)
+                    .execute_success()
+                    .await
+                    .is_ok();
+                GitCommand::fetch_refs_only().current_dir(&self.path).execute_success().await?;
+                if !had_filter {
+                    GitCommand::new()
+                        .args(["config", "--unset", "remote.origin.partialclonefilter"])
+                        .current_dir(&self.path)
+                        .execute_success()
+                        .await
+                        .ok();
+                }
+            }
+        }
 
         Ok(())
     }
@@ -246,6 +298,133 @@ impl GitRepo {
             ))
     }
 
+    /// Lists all tags with their creation time, most recently created first.
+    ///
+    /// The creation time is the tagger date of annotated tags and the commit
+    /// date of lightweight tags, in seconds since the Unix epoch. Used for the
+    /// `latest-by-date` version keyword.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Git command fails or its output cannot be parsed.
+    pub async fn list_tags_by_date(&self) -> Result<Vec<(String, i64)>> {
+        let stdout = GitCommand::list_tags_by_date()
+            .current_dir(&self.path)
+            .execute_stdout()
+            .await
+            .with_context(|| format!("Failed to list git tags by date in {:?}", self.path))?;
+
+        stdout
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (date, tag) = line
+                    .split_once(' ')
+                    .with_context(|| format!("Unexpected for-each-ref output: {line}"))?;
+                let date = date
+                    .parse()
+                    .with_context(|| format!("Invalid creation date for tag '{tag}': {date}"))?;
+                Ok((tag.to_string(), date))
+            })
+            .collect()
+    }
+
+    /// Lists every tag with the commit it points at.
+    ///
+    /// Annotated tags are peeled to their commit, so tags naming the same
+    /// commit map to the same SHA whether they are annotated or lightweight.
+    /// Used to find the tags that alias one release.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Git command fails or its output cannot be parsed.
+    pub async fn list_tag_commits(&self) -> Result<std::collections::HashMap<String, String>> {
+        let stdout = GitCommand::list_tag_commits()
+            .current_dir(&self.path)
+            .execute_stdout()
+            .await
+            .with_context(|| format!("Failed to list git tag commits in {:?}", self.path))?;
+
+        stdout
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let mut fields = line.split(' ');
+                let (Some(tag), Some(object)) = (fields.next(), fields.next()) else {
+                    anyhow::bail!("Unexpected for-each-ref output: {line}");
+                };
+                let commit = fields.next().filter(|peeled| !peeled.is_empty()).unwrap_or(object);
+                Ok((tag.to_string(), commit.to_string()))
+            })
+            .collect()
+    }
+
+    /// Lists the annotation messages of all annotated tags.
+    ///
+    /// Reads the tag objects (what `git cat-file tag` shows) in a single
+    /// command. Lightweight tags have no message and are left out. Used to
+    /// filter tags by release `channel`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Git command fails or its output cannot be parsed.
+    pub async fn list_tag_messages(&self) -> Result<std::collections::HashMap<String, String>> {
+        let stdout = GitCommand::list_tag_messages()
+            .current_dir(&self.path)
+            .execute_stdout()
+            .await
+            .with_context(|| format!("Failed to list git tag messages in {:?}", self.path))?;
+
+        let mut messages = std::collections::HashMap::new();
+        for record in stdout.split('\0') {
+            let record = record.trim_start_matches('\n');
+            if record.is_empty() {
+                continue;
+            }
+            let (header, message) = record.split_once('\n').unwrap_or((record, ""));
+            let (object_type, tag) = header
+                .split_once(' ')
+                .with_context(|| format!("Unexpected for-each-ref output: {header}"))?;
+            if object_type == "tag" {
+                messages.insert(tag.to_string(), message.trim_end().to_string());
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Lists the paths of all files at `commit`, without checking it out.
+    ///
+    /// Works on bare repositories. Paths are relative to the repository root
+    /// and use `/` separators.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Git command fails, e.g. when `commit` does not exist.
+    pub async fn list_files_at(&self, commit: &str) -> Result<Vec<String>> {
+        let output = GitCommand::list_files(commit)
+            .current_dir(&self.path)
+            .execute()
+            .await
+            .with_context(|| format!("Failed to list files at {commit} in {:?}", self.path))?;
+        Ok(output.stdout.split('\0').filter(|path| !path.is_empty()).map(str::to_string).collect())
+    }
+
+    /// Reads the file at `path` as it is at `commit`, without checking it out.
+    ///
+    /// Works on bare repositories.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not exist at `commit` or the Git command fails.
+    pub async fn read_file_at(&self, commit: &str, path: &str) -> Result<String> {
+        let output = GitCommand::show_file(commit, path)
+            .current_dir(&self.path)
+            .execute()
+            .await
+            .with_context(|| format!("Failed to read {path} at {commit} in {:?}", self.path))?;
+        Ok(output.stdout)
+    }
+
     /// Retrieves the URL of the remote 'origin' repository.
     ///
     /// # Return Value
@@ -311,6 +490,48 @@ impl GitRepo {
             .context("Failed to verify remote repository")
     }
 
+    /// Lists the refs a remote repository currently advertises.
+    ///
+    /// Queries the remote directly with `git ls-remote`, so the result reflects
+    /// upstream state rather than the local cache. Annotated tags are peeled:
+    /// `refs/tags/<name>` maps to the commit the tag points at, not the tag object.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The repository URL to query (may include authentication)
+    ///
+    /// # Returns
+    ///
+    /// A map from full ref name (e.g. `refs/tags/v1.0.0`) to commit SHA.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the remote cannot be reached or authentication fails.
+    pub async fn list_remote_refs(url: &str) -> Result<std::collections::HashMap<String, String>> {
+        let output = GitCommand::ls_remote_refs(url).execute_stdout().await.with_context(|| {
+            format!(
+                "Failed to list refs of {}",
+                strip_auth_from_url(url).unwrap_or_else(|_| url.to_string())
+            )
+        })?;
+
+        let mut refs = std::collections::HashMap::new();
+        let mut peeled = Vec::new();
+        for line in output.lines() {
+            let Some((sha, name)) = line.split_once('\t') else {
+                continue;
+            };
+            match name.strip_suffix("^{}") {
+                Some(tag) => peeled.push((tag.to_string(), sha.to_string())),
+                None => {
+                    refs.insert(name.to_string(), sha.to_string());
+                }
+            }
+        }
+        refs.extend(peeled);
+        Ok(refs)
+    }
+
     /// Fetch updates for a bare repository with logging context.
     async fn ensure_bare_repo_has_refs_with_context(&self, context: Option<&str>) -> Result<()> {
         // Try to fetch to ensure we have refs
@@ -446,14 +667,7 @@ impl GitRepo {
             // If no reference provided, try to use the default branch
             let default_branch = if reference.is_none() && retry_count == 0 {
                 // Try to get the default branch
-                GitCommand::new()
-                    .args(["symbolic-ref", "refs/remotes/origin/HEAD"])
-                    .current_dir(&self.path)
-                    .execute_stdout()
-                    .await
-                    .ok()
-                    .and_then(|s| s.strip_prefix("refs/remotes/origin/").map(String::from))
-                    .or_else(|| Some("main".to_string()))
+                Some(self.get_default_branch().await.unwrap_or_else(|_| "main".to_string()))
             } else {
                 None
             };
@@ -475,48 +689,8 @@ impl GitRepo {
 
             match result {
                 Ok(()) => {
-                    // Initialize and update submodules in the new worktree
-                    let worktree_repo = Self::new(worktree_path);
-
-                    // Initialize submodules
-                    let mut init_cmd =
-                        GitCommand::new().args(["submodule", "init"]).current_dir(worktree_path);
-
-                    if let Some(ctx) = context {
-                        init_cmd = init_cmd.with_context(ctx);
-                    }
-
-                    if let Err(e) = init_cmd.execute_success().await {
-                        let error_str = e.to_string();
-                        // Only ignore errors indicating no submodules are present
-                        if !error_str.contains("No submodule mapping found")
-                            && !error_str.contains("no submodule")
-                        {
-                            // For other errors, return them
-                            return Err(e).context("Failed to initialize submodules");
-                        }
-                    }
-
-                    // Update submodules
-                    let mut update_cmd = GitCommand::new()
-                        .args(["submodule", "update", "--recursive"])
-                        .current_dir(worktree_path);
-
-                    if let Some(ctx) = context {
-                        update_cmd = update_cmd.with_context(ctx);
-                    }
-
-                    if let Err(e) = update_cmd.execute_success().await {
-                        let error_str = e.to_string();
-                        // Ignore errors related to no submodules
-                        if !error_str.contains("No submodule mapping found")
-                            && !error_str.contains("no submodule")
-                        {
-                            return Err(e).context("Failed to update submodules");
-                        }
-                    }
-
-                    return Ok(worktree_repo);
+                    submodules::update_worktree_submodules(worktree_path, context).await?;
+                    return Ok(Self::new(worktree_path));
                 }
                 Err(e) => {
                     let error_str = e.to_string();
@@ -608,26 +782,9 @@ impl GitRepo {
 
                         match force_cmd.execute_success().await {
                             Ok(()) => {
-                                // Initialize and update submodules in the new worktree
-                                let worktree_repo = Self::new(worktree_path);
-
-                                let mut init_cmd = GitCommand::new()
-                                    .args(["submodule", "init"])
-                                    .current_dir(worktree_path);
-                                if let Some(ctx) = context {
-                                    init_cmd = init_cmd.with_context(ctx);
-                                }
-                                let _ = init_cmd.execute_success().await;
-
-                                let mut update_cmd = GitCommand::new()
-                                    .args(["submodule", "update", "--recursive"])
-                                    .current_dir(worktree_path);
-                                if let Some(ctx) = context {
-                                    update_cmd = update_cmd.with_context(ctx);
-                                }
-                                let _ = update_cmd.execute_success().await;
-
-                                return Ok(worktree_repo);
+                                submodules::update_worktree_submodules(worktree_path, context)
+                                    .await?;
+                                return Ok(Self::new(worktree_path));
                             }
                             Err(e2) => {
                                 // Fall through to other recovery paths with the original error context
@@ -658,49 +815,9 @@ impl GitRepo {
 
                         match head_result {
                             Ok(()) => {
-                                // Initialize and update submodules in the new worktree
-                                let worktree_repo = Self::new(worktree_path);
-
-                                // Initialize submodules
-                                let mut init_cmd = GitCommand::new()
-                                    .args(["submodule", "init"])
-                                    .current_dir(worktree_path);
-
-                                if let Some(ctx) = context {
-                                    init_cmd = init_cmd.with_context(ctx);
-                                }
-
-                                if let Err(e) = init_cmd.execute_success().await {
-                                    let error_str = e.to_string();
-                                    // Only ignore errors indicating no submodules are present
-                                    if !error_str.contains("No submodule mapping found")
-                                        && !error_str.contains("no submodule")
-                                    {
-                                        // For other errors, return them
-                                        return Err(e).context("Failed to initialize submodules");
-                                    }
-                                }
-
-                                // Update submodules
-                                let mut update_cmd = GitCommand::new()
-                                    .args(["submodule", "update", "--recursive"])
-                                    .current_dir(worktree_path);
-
-                                if let Some(ctx) = context {
-                                    update_cmd = update_cmd.with_context(ctx);
-                                }
-
-                                if let Err(e) = update_cmd.execute_success().await {
-                                    let error_str = e.to_string();
-                                    // Ignore errors related to no submodules
-                                    if !error_str.contains("No submodule mapping found")
-                                        && !error_str.contains("no submodule")
-                                    {
-                                        return Err(e).context("Failed to update submodules");
-                                    }
-                                }
-
-                                return Ok(worktree_repo);
+                                submodules::update_worktree_submodules(worktree_path, context)
+                                    .await?;
+                                return Ok(Self::new(worktree_path));
                             }
                             Err(head_err) => {
                                 // If HEAD also fails, return the original error
@@ -963,6 +1080,8 @@ impl GitRepo {
     /// - Git command execution fails
     pub async fn resolve_to_sha(&self, ref_spec: Option<&str>) -> Result<String> {
         let reference = ref_spec.unwrap_or("HEAD");
+        // A `ref:` version names a ref fetched under the same local name
+        let reference = raw_ref::raw_ref_spec(reference).unwrap_or(reference);
 
         // Optimization: if it's already a full SHA, return it directly
         if reference.len() == 40 && reference.chars().all(|c| c.is_ascii_hexdigit()) {
@@ -1040,6 +1159,12 @@ impl GitRepo {
 
     /// Gets the default branch name for the repository.
     ///
+    /// This is the remote's default branch (`main`, `master`, `trunk`, ...),
+    /// used for dependencies without a version. Fetching does not record it,
+    /// so the first call asks the remote (`git remote set-head origin --auto`,
+    /// the equivalent of `ls-remote --symref origin HEAD`) and stores the answer
+    /// as `origin/HEAD`. Later calls for the same repository read it locally.
+    ///
     /// # Returns
     ///
     /// # Errors
@@ -1048,18 +1173,28 @@ impl GitRepo {
     /// - Lock conflicts occur (propagated for caller to retry)
     /// - Default branch cannot be determined
     pub async fn get_default_branch(&self) -> Result<String> {
-        let result = GitCommand::new()
-            .args(["symbolic-ref", "refs/remotes/origin/HEAD"])
-            .current_dir(&self.path)
-            .execute_stdout()
-            .await;
+        if let Some(branch) = self.read_origin_head().await? {
+            return Ok(branch);
+        }
+
+        // Not recorded yet: ask the remote once. Repositories without a
+        // reachable origin fall through to the current branch.
+        if GitCommand::set_origin_head().current_dir(&self.path).execute_success().await.is_ok()
+            && let Some(branch) = self.read_origin_head().await?
+        {
+            return Ok(branch);
+        }
+
+        // Fallback: try to get current branch (for non-bare repos or if symbolic-ref fails)
+        self.get_current_branch().await
+    }
 
-        match result {
+    /// Reads the remote default branch recorded as `origin/HEAD`, if any.
+    async fn read_origin_head(&self) -> Result<Option<String>> {
+        match GitCommand::origin_head().current_dir(&self.path).execute_stdout().await {
+            // A ref outside refs/remotes/origin/ is treated as not recorded
             Ok(symbolic_ref) => {
-                if let Some(branch) = symbolic_ref.strip_prefix("refs/remotes/origin/") {
-                    return Ok(branch.to_string());
-                }
-                // If parsing fails, fall through to the next method.
+                Ok(symbolic_ref.strip_prefix("refs/remotes/origin/").map(str::to_string))
             }
             Err(e) => {
                 let error_str = e.to_string();
@@ -1068,11 +1203,9 @@ impl GitRepo {
                 if !error_str.contains("not a symbolic ref") && !error_str.contains("not found") {
                     return Err(e).context("Failed to get default branch via symbolic-ref");
                 }
+                Ok(None)
             }
         }
-
-        // Fallback: try to get current branch (for non-bare repos or if symbolic-ref fails)
-        self.get_current_branch().await
     }
 }
 
@@ -1178,6 +1311,9 @@ pub fn ensure_valid_git_repo(path: &Path) -> Result<()> {
 pub fn parse_git_url(url: &str) -> Result<(String, String)> {
     use std::path::Path;
 
+    // Credentials are not part of the repository identity
+    let url = &strip_auth_from_url(url)?;
+
     // Handle file:// URLs
     if url.starts_with("file://") {
         let path_str = url.trim_start_matches("file://");
@@ -0,0 +1,111 @@
+//! Partial clone (`--filter=blob:none`) configuration for cache clones.
+//!
+//! Bare clones in the cache only need commits, trees and tags to resolve
+//! versions; file contents are fetched on demand by Git when a worktree is
+//! checked out. By default AGPM requests a blob-less partial clone for remote
+//! repositories and a full clone for local ones. `git.partial` in
+//! `~/.agpm/config.toml` overrides this:
+//!
+//! - `partial = true` also uses partial clones for `file://` URLs, provided the
+//!   source repository allows filtering (`uploadpack.allowFilter`)
+//! - `partial = false` always performs full clones
+//!
+//! Plain filesystem paths are always cloned in full, as Git ignores `--filter`
+//! for local clones. Like the timeout override, the setting is process-wide and
+//! installed once by the CLI before a command runs.
+//!
+//! Fetches into an existing cache clone can also skip blobs: commands that only
+//! resolve versions, such as `outdated`, fetch with [`FetchMode::RefsOnly`].
+//! The fetched commits then lack file contents, which Git downloads from the
+//! remote when a worktree is checked out, so installs still get complete files.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+const DEFAULT: u8 = 0;
+const ALWAYS: u8 = 1;
+const NEVER: u8 = 2;
+
+static PARTIAL_CLONE: AtomicU8 = AtomicU8::new(DEFAULT);
+
+/// What a fetch into a cache clone downloads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FetchMode {
+    /// Branches, tags and every object they reference.
+    #[default]
+    Full,
+    /// Branches, tags, commits and trees, but no file contents.
+    ///
+    /// Enough to list tags and resolve versions. The repository becomes a
+    /// partial clone, and missing blobs are fetched when a worktree needs them.
+    RefsOnly,
+}
+
+/// Install the process-wide partial clone setting from `git.partial`.
+///
+/// `None` restores the default of partial clones for remote repositories only.
+pub fn set_partial_clone(partial: Option<bool>) {
+    let value = match partial {
+        None => DEFAULT,
+        Some(true) => ALWAYS,
+        Some(false) => NEVER,
+    };
+    PARTIAL_CLONE.store(value, Ordering::Relaxed);
+}
+
+/// The configured `git.partial` value, if any.
+#[must_use]
+pub fn partial_clone() -> Option<bool> {
+    match PARTIAL_CLONE.load(Ordering::Relaxed) {
+        ALWAYS => Some(true),
+        NEVER => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether a bare clone of `url` should use `--filter=blob:none`.
+#[must_use]
+pub fn use_blob_filter(url: &str) -> bool {
+    resolve_blob_filter(url, partial_clone())
+}
+
+fn resolve_blob_filter(url: &str, partial: Option<bool>) -> bool {
+    if is_local_path(url) {
+        return false;
+    }
+    match partial {
+        Some(enabled) => enabled,
+        None => !url.starts_with("file://"),
+    }
+}
+
+/// Filesystem paths (absolute, relative, home-relative, or Windows drive paths).
+pub(super) fn is_local_path(url: &str) -> bool {
+    url.starts_with('/')
+        || url.starts_with('.')
+        || url.starts_with('~')
+        || (url.len() > 1 && url.chars().nth(1) == Some(':'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_blob_filter_default() {
+        assert!(resolve_blob_filter("https://github.com/example/repo.git", None));
+        assert!(resolve_blob_filter("git@github.com:example/repo.git", None));
+        assert!(!resolve_blob_filter("file:///tmp/repo.git", None));
+        assert!(!resolve_blob_filter("/tmp/repo.git", None));
+    }
+
+    #[test]
+    fn test_resolve_blob_filter_configured() {
+        assert!(resolve_blob_filter("file:///tmp/repo.git", Some(true)));
+        assert!(!resolve_blob_filter("https://github.com/example/repo.git", Some(false)));
+
+        // Git ignores --filter for plain local paths
+        for path in ["/tmp/repo.git", "./repo", "~/repo", "C:\\repo"] {
+            assert!(!resolve_blob_filter(path, Some(true)), "{path}");
+        }
+    }
+}
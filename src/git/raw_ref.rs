@@ -0,0 +1,113 @@
+//! Raw Git refs as dependency versions (`ref:refs/pull/42/head`).
+//!
+//! A `version` starting with `ref:` names an arbitrary ref on the remote, such
+//! as the head of a pull request. Such refs are not covered by the normal
+//! fetch refspec, so the ref is fetched explicitly into the cached repository
+//! under the same name and resolved to the commit it points at. That commit is
+//! recorded in the lockfile like any other pin.
+//!
+//! Raw refs are not reproducible: the owner of the ref can force-push it at any
+//! time, and the next resolution then picks up a different commit. `agpm list`
+//! and `agpm audit` flag dependencies that use them.
+
+use anyhow::{Context, Result};
+
+use super::GitRepo;
+use super::command_builder::GitCommand;
+
+/// Prefix marking a `version` as a raw ref spec.
+pub const RAW_REF_PREFIX: &str = "ref:";
+
+/// The ref spec of a `ref:` version, or `None` for any other version.
+///
+/// # Examples
+///
+/// ```
+/// use agpm_cli::git::raw_ref::raw_ref_spec;
+///
+/// assert_eq!(raw_ref_spec("ref:refs/pull/42/head"), Some("refs/pull/42/head"));
+/// assert_eq!(raw_ref_spec("v1.0.0"), None);
+/// ```
+#[must_use]
+pub fn raw_ref_spec(version: &str) -> Option<&str> {
+    version.strip_prefix(RAW_REF_PREFIX)
+}
+
+/// Returns `true` if `version` is a `ref:` raw ref spec.
+#[must_use]
+pub fn is_raw_ref(version: &str) -> bool {
+    raw_ref_spec(version).is_some()
+}
+
+impl GitRepo {
+    /// Fetch `spec` from `origin` and resolve it to a commit SHA.
+    ///
+    /// The ref is stored locally under the same name, replacing any previous
+    /// value, so a force-pushed ref is picked up on the next fetch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `spec` is not a fully qualified ref (`refs/...`),
+    /// the remote does not have it, or the fetch fails.
+    pub async fn fetch_raw_ref(&self, spec: &str) -> Result<String> {
+        if !spec.starts_with("refs/") || spec.contains(':') {
+            anyhow::bail!(
+                "Invalid ref '{spec}': expected a fully qualified ref such as 'refs/pull/42/head'"
+            );
+        }
+
+        GitCommand::fetch_ref(spec)
+            .current_dir(self.path())
+            .execute_success()
+            .await
+            .with_context(|| format!("Failed to fetch ref '{spec}'"))?;
+
+        GitCommand::rev_parse(&format!("{spec}^{{commit}}"))
+            .current_dir(self.path())
+            .execute_stdout()
+            .await
+            .with_context(|| format!("Failed to resolve ref '{spec}' to a commit"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestGit;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_raw_ref_spec() {
+        assert_eq!(raw_ref_spec("ref:refs/pull/42/head"), Some("refs/pull/42/head"));
+        assert!(is_raw_ref("ref:refs/heads/main"));
+        assert!(!is_raw_ref("refs/heads/main"));
+        assert!(!is_raw_ref("^1.0.0"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_raw_ref() -> Result<()> {
+        let temp = TempDir::new()?;
+        let upstream = temp.path().join("upstream");
+        std::fs::create_dir(&upstream)?;
+        let git = TestGit::new(&upstream);
+        git.init()?;
+        git.config_user()?;
+        std::fs::write(upstream.join("a.md"), "one")?;
+        git.add_all()?;
+        git.commit("one")?;
+        git.create_branch("proposal")?;
+        std::fs::write(upstream.join("a.md"), "two")?;
+        git.add_all()?;
+        git.commit("two")?;
+        let proposed = git.rev_parse_head()?;
+        git.update_ref("refs/pull/42/head", &proposed)?;
+
+        let clone = temp.path().join("clone");
+        let repo = GitRepo::clone(&format!("file://{}", upstream.display()), &clone).await?;
+        assert_eq!(repo.fetch_raw_ref("refs/pull/42/head").await?, proposed);
+
+        assert!(repo.fetch_raw_ref("refs/pull/7/head").await.is_err());
+        assert!(repo.fetch_raw_ref("pull/42/head").await.is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+//! Disabling TLS certificate verification for Git (`git.insecure_ssl`).
+//!
+//! Behind a TLS-intercepting corporate proxy, HTTPS clones fail because the
+//! proxy's certificate is not trusted. The right fix is to add the proxy's CA
+//! to Git's trust store (`http.sslCAInfo`). As a last resort, `--insecure` or
+//! `insecure_ssl = true` under `[git]` in `~/.agpm/config.toml` turns
+//! certificate verification off for every Git command AGPM runs, equivalent
+//! to `http.sslVerify=false`. This exposes sources to man-in-the-middle
+//! attacks, so the CLI prints a warning on every run while it is active.
+//!
+//! The setting is never enabled implicitly. Like the other Git settings, it is
+//! process-wide and installed once by the CLI before a command runs. Only Git
+//! operations are affected; archive downloads and self-update still verify
+//! certificates.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Environment variable Git reads as an override of `http.sslVerify=false`.
+///
+/// Used instead of `-c` so it does not interfere with configuration passed
+/// through `GIT_CONFIG_COUNT`.
+const SSL_NO_VERIFY_ENV: &str = "GIT_SSL_NO_VERIFY";
+
+static INSECURE_SSL: AtomicBool = AtomicBool::new(false);
+
+/// Install the process-wide setting from `--insecure` or `git.insecure_ssl`.
+pub fn set_insecure_ssl(enabled: bool) {
+    INSECURE_SSL.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether Git commands skip TLS certificate verification.
+#[must_use]
+pub fn insecure_ssl() -> bool {
+    INSECURE_SSL.load(Ordering::Relaxed)
+}
+
+/// Environment variable to set on a Git process, or `None` unless verification is disabled.
+pub(super) fn ssl_env() -> Option<(&'static str, &'static str)> {
+    ssl_env_for(insecure_ssl())
+}
+
+/// Environment variable disabling verification when `insecure` is set.
+fn ssl_env_for(insecure: bool) -> Option<(&'static str, &'static str)> {
+    insecure.then_some((SSL_NO_VERIFY_ENV, "true"))
+}
+
+/// Warning printed on every run while verification is disabled.
+pub const INSECURE_SSL_WARNING: &str = "TLS certificate verification is DISABLED for Git \
+     (--insecure / git.insecure_ssl).\n\
+     Sources can be intercepted or tampered with. Only use this behind a trusted \
+     TLS-intercepting proxy, and prefer adding its CA certificate via git's http.sslCAInfo.";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ssl_env() {
+        assert_eq!(ssl_env_for(true), Some(("GIT_SSL_NO_VERIFY", "true")));
+        assert_eq!(ssl_env_for(false), None);
+    }
+}
@@ -0,0 +1,80 @@
+//! Submodule checkout for worktrees (`git.submodules`).
+//!
+//! Some source repositories keep resources inside Git submodules. By default,
+//! every worktree whose commit has a `.gitmodules` file gets its submodules
+//! initialized and checked out recursively right after creation, so dependency
+//! paths that point into a submodule resolve and install like any other file.
+//! With `submodules = false` under `[git]` in `~/.agpm/config.toml`, submodule
+//! directories stay empty.
+//!
+//! Submodule URLs are resolved by Git relative to the source URL. For local
+//! sources (plain paths and `file://` URLs) the file transport, which Git
+//! blocks for submodules by default, is allowed; remote sources keep Git's
+//! default policy. Like the other Git settings, this one is process-wide and
+//! installed once by the CLI before a command runs.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::command_builder::GitCommand;
+
+static SUBMODULES: AtomicBool = AtomicBool::new(true);
+
+/// Install the process-wide submodule setting from `git.submodules`.
+pub fn set_submodules(enabled: bool) {
+    SUBMODULES.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether new worktrees check out their submodules.
+#[must_use]
+pub fn submodules_enabled() -> bool {
+    SUBMODULES.load(Ordering::Relaxed)
+}
+
+/// Check out the submodules of a freshly created worktree, if enabled.
+///
+/// Does nothing when `git.submodules` is `false` or the commit has no `.gitmodules`.
+pub(super) async fn update_worktree_submodules(
+    worktree_path: &Path,
+    context: Option<&str>,
+) -> Result<()> {
+    if !submodules_enabled() || !worktree_path.join(".gitmodules").exists() {
+        return Ok(());
+    }
+
+    let origin = GitCommand::new()
+        .args(["config", "--get", "remote.origin.url"])
+        .current_dir(worktree_path)
+        .execute_stdout()
+        .await
+        .unwrap_or_default();
+
+    let mut cmd = GitCommand::submodule_update(is_local_url(&origin)).current_dir(worktree_path);
+    if let Some(ctx) = context {
+        cmd = cmd.with_context(ctx);
+    }
+    cmd.execute_success().await.with_context(|| {
+        format!("Failed to check out submodules in worktree {}", worktree_path.display())
+    })
+}
+
+/// Plain filesystem paths and `file://` URLs.
+fn is_local_url(url: &str) -> bool {
+    url.starts_with("file://") || super::partial::is_local_path(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_local_url() {
+        assert!(is_local_url("/tmp/repo.git"));
+        assert!(is_local_url("file:///tmp/repo.git"));
+        assert!(is_local_url("C:/repos/agents"));
+        assert!(!is_local_url("https://github.com/example/repo.git"));
+        assert!(!is_local_url("git@github.com:example/repo.git"));
+        assert!(!is_local_url(""));
+    }
+}
@@ -0,0 +1,166 @@
+//! Configurable timeouts for long-running Git operations.
+//!
+//! By default the cache bounds clones by [`GIT_CLONE_TIMEOUT`] and worktree
+//! creation by [`GIT_WORKTREE_TIMEOUT`], while fetches only have the general
+//! [`GIT_COMMAND_TIMEOUT`]. On slow or flaky networks all three can be replaced
+//! with a single value from the global `--git-timeout <SECS>` flag or
+//! `git.timeout` in `~/.agpm/config.toml`, where `0` disables the timeout.
+//!
+//! The override is process-wide: it is installed once by the CLI before a
+//! command runs, and read by [`GitCommand`](super::command_builder::GitCommand)
+//! and the cache whenever one of these operations starts.
+//!
+//! [`GIT_CLONE_TIMEOUT`]: crate::constants::GIT_CLONE_TIMEOUT
+//! [`GIT_COMMAND_TIMEOUT`]: crate::constants::GIT_COMMAND_TIMEOUT
+//! [`GIT_WORKTREE_TIMEOUT`]: crate::constants::GIT_WORKTREE_TIMEOUT
+
+use anyhow::{Result, anyhow};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::constants::{GIT_CLONE_TIMEOUT, GIT_COMMAND_TIMEOUT, GIT_WORKTREE_TIMEOUT};
+
+/// Sentinel for "no override configured".
+const NO_OVERRIDE: u64 = u64::MAX;
+
+static TIMEOUT_OVERRIDE_SECS: AtomicU64 = AtomicU64::new(NO_OVERRIDE);
+
+/// A long-running Git operation with its own default timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitOperation {
+    /// `git clone` (including bare clones into the cache).
+    Clone,
+    /// `git fetch` of an existing repository.
+    Fetch,
+    /// `git worktree add`.
+    Worktree,
+}
+
+impl GitOperation {
+    /// Built-in timeout used when no override is configured.
+    #[must_use]
+    pub const fn default_timeout(self) -> Duration {
+        match self {
+            Self::Clone => GIT_CLONE_TIMEOUT,
+            Self::Fetch => GIT_COMMAND_TIMEOUT,
+            Self::Worktree => GIT_WORKTREE_TIMEOUT,
+        }
+    }
+
+    /// Effective timeout for this operation, or `None` if disabled.
+    #[must_use]
+    pub fn timeout(self) -> Option<Duration> {
+        resolve_timeout(override_secs(), self)
+    }
+}
+
+/// Install the process-wide timeout override in seconds.
+///
+/// `None` restores the built-in defaults and `Some(0)` disables timeouts for
+/// clone, fetch and worktree creation.
+pub fn set_timeout_override(secs: Option<u64>) {
+    TIMEOUT_OVERRIDE_SECS.store(secs.unwrap_or(NO_OVERRIDE), Ordering::Relaxed);
+}
+
+/// The configured override in seconds, if any.
+#[must_use]
+pub fn override_secs() -> Option<u64> {
+    match TIMEOUT_OVERRIDE_SECS.load(Ordering::Relaxed) {
+        NO_OVERRIDE => None,
+        secs => Some(secs),
+    }
+}
+
+/// Compute the timeout for `operation` given an optional override.
+pub(super) fn resolve_timeout(
+    override_secs: Option<u64>,
+    operation: GitOperation,
+) -> Option<Duration> {
+    match override_secs {
+        None => Some(operation.default_timeout()),
+        Some(0) => None,
+        Some(secs) => Some(Duration::from_secs(secs)),
+    }
+}
+
+/// Run `future`, failing if it does not complete within `timeout`.
+///
+/// A `timeout` of `None` waits indefinitely. `describe` is only evaluated on
+/// timeout and names the operation in the error message.
+///
+/// # Errors
+///
+/// Returns the future's own error, or a timeout error naming the operation.
+pub async fn run_with_timeout<T, F, D>(
+    timeout: Option<Duration>,
+    future: F,
+    describe: D,
+) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+    D: FnOnce() -> String,
+{
+    let Some(duration) = timeout else {
+        return future.await;
+    };
+
+    tokio::time::timeout(duration, future).await.map_err(|_| {
+        anyhow!(
+            "{} timed out after {:?} (adjust with --git-timeout or git.timeout, 0 disables)",
+            describe(),
+            duration
+        )
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_timeout_defaults() {
+        assert_eq!(resolve_timeout(None, GitOperation::Clone), Some(GIT_CLONE_TIMEOUT));
+        assert_eq!(resolve_timeout(None, GitOperation::Fetch), Some(GIT_COMMAND_TIMEOUT));
+        assert_eq!(resolve_timeout(None, GitOperation::Worktree), Some(GIT_WORKTREE_TIMEOUT));
+    }
+
+    #[test]
+    fn test_resolve_timeout_override() {
+        for operation in [GitOperation::Clone, GitOperation::Fetch, GitOperation::Worktree] {
+            assert_eq!(resolve_timeout(Some(600), operation), Some(Duration::from_secs(600)));
+            assert_eq!(resolve_timeout(Some(0), operation), None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_with_timeout_applies_duration() {
+        let result = run_with_timeout(
+            Some(Duration::from_millis(10)),
+            async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(())
+            },
+            || "Git clone of example".to_string(),
+        )
+        .await;
+
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("Git clone of example timed out"), "unexpected error: {err}");
+    }
+
+    #[tokio::test]
+    async fn test_run_with_timeout_disabled_waits() {
+        let value = run_with_timeout(
+            None,
+            async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(42)
+            },
+            || unreachable!("no timeout configured"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(value, 42);
+    }
+}
@@ -292,34 +292,36 @@ fn event_to_string(event: &HookEvent) -> String {
     }
 }
 
-/// Configure hooks from source files into .claude/settings.local.json
+/// Configure hooks from source files into `settings_path`
 ///
 /// This function:
 /// 1. Reads hook JSON files directly from source locations (no file copying)
 /// 2. Converts them to Claude Code format
-/// 3. Updates .claude/settings.local.json with proper event-based structure
+/// 3. Updates `settings_path` (normally `.claude/settings.local.json`) with proper
+///    event-based structure
 /// 4. Can be called from both `add` and `install` commands
 ///
 /// Returns the count of hooks that were actually changed
 pub async fn install_hooks(
     lockfile: &crate::lockfile::LockFile,
     project_root: &Path,
+    settings_path: &Path,
     cache: &crate::cache::Cache,
 ) -> Result<usize> {
     if lockfile.hooks.is_empty() {
         return Ok(0);
     }
 
-    let claude_dir = project_root.join(".claude");
-    let settings_path = claude_dir.join("settings.local.json");
-
     // Ensure directory exists
-    crate::utils::fs::ensure_dir(&claude_dir)?;
+    if let Some(settings_dir) = settings_path.parent() {
+        crate::utils::fs::ensure_dir(settings_dir)?;
+    }
 
     // Load hook configurations directly from source files
     let mut hook_configs = HashMap::new();
 
-    for entry in &lockfile.hooks {
+    // Hooks with install=false (e.g. `hooks = false`) are not configured
+    for entry in lockfile.hooks.iter().filter(|h| h.install != Some(false)) {
         // Get the source file path
         let source_path = if let Some(source_name) = &entry.source {
             let url = entry
@@ -369,7 +371,7 @@ pub async fn install_hooks(
     }
 
     // Load existing settings
-    let mut settings = crate::mcp::ClaudeSettings::load_or_default(&settings_path)?;
+    let mut settings = crate::mcp::ClaudeSettings::load_or_default(settings_path)?;
 
     // Convert hooks to Claude Code format
     let claude_hooks = convert_to_claude_format(hook_configs)?;
@@ -400,7 +402,7 @@ pub async fn install_hooks(
         settings.hooks = Some(claude_hooks);
 
         // Save updated settings
-        settings.save(&settings_path)?;
+        settings.save(settings_path)?;
 
         Ok(configured_count)
     } else {
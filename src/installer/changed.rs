@@ -0,0 +1,363 @@
+//! Change detection for `agpm install --changed-only`.
+//!
+//! Compares the freshly resolved lockfile against the previous one and the files
+//! on disk to find the resources that actually need installing. A resource is
+//! unchanged when every content-affecting input matches the previous lockfile
+//! entry and the installed file still has the recorded checksum. Everything else
+//! (new entries, moved or missing files, edited files, local dependencies) is
+//! reported as changed.
+//!
+//! Hooks and MCP servers are merged into shared configuration files rather than
+//! installed individually, so they are tracked as groups: the merged config is
+//! only recomputed when one of its contributing entries changed.
+//!
+//! The same on-disk checksum comparison backs `agpm install --frozen --verify`,
+//! which reports drifted files instead of reinstalling them.
+
+use anyhow::Result;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::core::ResourceType;
+use crate::lockfile::{LockFile, LockedResource, ResourceId};
+
+use super::resource::inputs_match;
+
+/// Resources that differ from the previous installation.
+#[derive(Debug, Default, Clone)]
+pub struct ChangeSet {
+    /// Installable resources that must be (re)installed.
+    pub changed: HashSet<ResourceId>,
+    /// Number of installable resources that were found unchanged.
+    pub unchanged_count: usize,
+    /// Whether any hook entry was added, removed, or changed.
+    pub hooks_changed: bool,
+    /// Whether any MCP server entry was added, removed, or changed.
+    pub mcp_servers_changed: bool,
+}
+
+impl ChangeSet {
+    /// Returns `true` when nothing needs to be installed or reconfigured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && !self.hooks_changed && !self.mcp_servers_changed
+    }
+
+    /// Copy checksums and token counts of unchanged resources from `baseline`.
+    ///
+    /// Unchanged resources are not passed to the installer, so the values it
+    /// would have produced are carried forward from the previous lockfile.
+    /// When nothing changed at all, source fetch timestamps are kept as well so
+    /// the lockfile content stays byte-identical and is not rewritten.
+    pub fn carry_forward(&self, lockfile: &mut LockFile, baseline: &LockFile) {
+        if self.is_empty() {
+            for source in &mut lockfile.sources {
+                if let Some(old) =
+                    baseline.sources.iter().find(|s| s.name == source.name && s.url == source.url)
+                {
+                    source.fetched_at.clone_from(&old.fetched_at);
+                }
+            }
+        }
+
+        let mut checksums = Vec::new();
+        let mut context_checksums = Vec::new();
+        let mut token_counts = Vec::new();
+
+        for entry in lockfile.all_resources() {
+            if is_config_fragment(entry) || self.changed.contains(&entry.id()) {
+                continue;
+            }
+            if let Some(old) = baseline.find_resource(&entry.name, &entry.resource_type) {
+                checksums.push((entry.id(), old.checksum.clone()));
+                context_checksums.push((entry.id(), old.context_checksum.clone()));
+                token_counts.push((entry.id(), old.approximate_token_count));
+            }
+        }
+
+        lockfile.apply_installation_results(checksums, context_checksums, Vec::new(), token_counts);
+    }
+}
+
+/// Determine which resources in `lockfile` differ from `baseline` or the disk.
+///
+/// Templated resources embed content from their dependencies, so they are
+/// treated as changed whenever any other resource changed.
+#[must_use]
+pub fn detect_changed_resources(
+    lockfile: &LockFile,
+    baseline: &LockFile,
+    project_dir: &Path,
+    strip_banners: bool,
+    normalize_eol: bool,
+) -> ChangeSet {
+    let mut changes = ChangeSet::default();
+    let mut templated = Vec::new();
+
+    for entry in lockfile.all_resources() {
+        if is_config_fragment(entry) {
+            continue;
+        }
+
+        if !is_unchanged(entry, baseline, project_dir, strip_banners, normalize_eol) {
+            changes.changed.insert(entry.id());
+        } else if entry.context_checksum.is_some() {
+            templated.push(entry.id());
+        } else {
+            changes.unchanged_count += 1;
+        }
+    }
+
+    if changes.changed.is_empty() {
+        changes.unchanged_count += templated.len();
+    } else {
+        changes.changed.extend(templated);
+    }
+
+    changes.hooks_changed = fragments_changed(&lockfile.hooks, &baseline.hooks);
+    changes.mcp_servers_changed = fragments_changed(&lockfile.mcp_servers, &baseline.mcp_servers);
+
+    changes
+}
+
+/// Hooks and MCP servers are merged into settings files instead of installed.
+fn is_config_fragment(entry: &LockedResource) -> bool {
+    matches!(entry.resource_type, ResourceType::Hook | ResourceType::McpServer)
+}
+
+fn is_unchanged(
+    entry: &LockedResource,
+    baseline: &LockFile,
+    project_dir: &Path,
+    strip_banners: bool,
+    normalize_eol: bool,
+) -> bool {
+    // Local files can change at any time without the lockfile noticing
+    if entry.is_local() {
+        return false;
+    }
+
+    let Some(old) = baseline.find_resource(&entry.name, &entry.resource_type) else {
+        return false;
+    };
+    if !inputs_match(entry, old) || entry.installed_at != old.installed_at {
+        return false;
+    }
+
+    // Content-only dependencies have nothing on disk to verify
+    if !entry.install.unwrap_or(true) || entry.installed_at.is_empty() {
+        return true;
+    }
+
+    match installed_checksum(entry, project_dir, strip_banners, normalize_eol) {
+        Some(Ok(checksum)) => checksum == old.checksum,
+        Some(Err(e)) => {
+            tracing::debug!("Treating {} as changed: {e}", entry.name);
+            false
+        }
+        None => false,
+    }
+}
+
+/// Checksum of the file or skill directory installed for `entry`.
+///
+/// Returns `None` when nothing is installed at the recorded path.
+fn installed_checksum(
+    entry: &LockedResource,
+    project_dir: &Path,
+    strip_banners: bool,
+    normalize_eol: bool,
+) -> Option<Result<String>> {
+    let path = project_dir.join(&entry.installed_at);
+    if entry.resource_type == ResourceType::Skill {
+        path.is_dir().then(|| LockFile::compute_directory_checksum_with_eol(&path, normalize_eol))
+    } else {
+        path.is_file()
+            .then(|| LockFile::compute_checksum_with_eol(&path, strip_banners, normalize_eol))
+    }
+}
+
+/// An installed resource whose files no longer match the lockfile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityIssue {
+    /// Path recorded in the lockfile, relative to the project directory.
+    pub installed_at: String,
+    /// Display name of the lockfile entry.
+    pub name: String,
+    /// Resource type of the lockfile entry.
+    pub resource_type: ResourceType,
+    /// `true` when nothing is installed at the path, `false` when the content differs.
+    pub missing: bool,
+}
+
+/// Compare every installed file against the checksum recorded in `lockfile`.
+///
+/// Hooks and MCP servers are merged into shared configuration files and
+/// content-only dependencies install nothing, so neither is checked. The
+/// result is sorted by path. Set `strip_banners` when `installer.header` or
+/// `installer.footer` is enabled, and `normalize_eol` to hash line endings as
+/// `integrity.normalize_eol` does.
+#[must_use]
+pub fn verify_installed_files(
+    lockfile: &LockFile,
+    project_dir: &Path,
+    strip_banners: bool,
+    normalize_eol: bool,
+) -> Vec<IntegrityIssue> {
+    let mut issues: Vec<_> = lockfile
+        .all_resources()
+        .into_iter()
+        .filter(|entry| {
+            !is_config_fragment(entry)
+                && entry.install.unwrap_or(true)
+                && !entry.installed_at.is_empty()
+        })
+        .filter_map(|entry| {
+            let missing = match installed_checksum(entry, project_dir, strip_banners, normalize_eol)
+            {
+                Some(Ok(checksum)) if checksum == entry.checksum => return None,
+                Some(_) => false,
+                None => true,
+            };
+            Some(IntegrityIssue {
+                installed_at: entry.installed_at.clone(),
+                name: entry.display_name().to_string(),
+                resource_type: entry.resource_type,
+                missing,
+            })
+        })
+        .collect();
+    issues.sort_by(|a, b| a.installed_at.cmp(&b.installed_at));
+    issues
+}
+
+/// A merged config group changed if any entry was added, removed, or altered.
+fn fragments_changed(current: &[LockedResource], previous: &[LockedResource]) -> bool {
+    if current.len() != previous.len() {
+        return true;
+    }
+
+    current.iter().any(|entry| {
+        entry.is_local()
+            || previous
+                .iter()
+                .find(|old| old.name == entry.name && old.tool == entry.tool)
+                .is_none_or(|old| !inputs_match(entry, old))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolver::lockfile_builder::VariantInputs;
+    use tempfile::TempDir;
+
+    fn git_resource(name: &str, resource_type: ResourceType, content: &str) -> LockedResource {
+        LockedResource {
+            name: name.to_string(),
+            source: Some("community".to_string()),
+            url: Some("https://github.com/example/repo.git".to_string()),
+            version: Some("v1.0.0".to_string()),
+            path: format!("{name}.md"),
+            resolved_commit: Some("a".repeat(40)),
+            checksum: crate::installer::resource::compute_file_checksum(content),
+            context_checksum: None,
+            installed_at: format!(".claude/agents/{name}.md"),
+            dependencies: vec![],
+            resource_type,
+            tool: Some("claude-code".to_string()),
+            manifest_alias: Some(name.to_string()),
+            applied_patches: std::collections::BTreeMap::new(),
+            install: None,
+            variant_inputs: VariantInputs::default(),
+            is_private: false,
+            approximate_token_count: Some(10),
+            deprecated: false,
+        }
+    }
+
+    #[test]
+    fn test_detect_changed_resources() {
+        let temp = TempDir::new().unwrap();
+        let agents_dir = temp.path().join(".claude/agents");
+        std::fs::create_dir_all(&agents_dir).unwrap();
+        std::fs::write(agents_dir.join("same.md"), "same").unwrap();
+        std::fs::write(agents_dir.join("edited.md"), "locally edited").unwrap();
+        std::fs::write(agents_dir.join("bumped.md"), "old").unwrap();
+
+        let baseline = LockFile {
+            agents: vec![
+                git_resource("same", ResourceType::Agent, "same"),
+                git_resource("edited", ResourceType::Agent, "original"),
+                git_resource("missing", ResourceType::Agent, "missing"),
+                git_resource("bumped", ResourceType::Agent, "old"),
+            ],
+            hooks: vec![git_resource("on-save", ResourceType::Hook, "{}")],
+            ..Default::default()
+        };
+
+        let mut lockfile = baseline.clone();
+        lockfile.agents[3].resolved_commit = Some("b".repeat(40));
+        for entry in &mut lockfile.agents {
+            entry.checksum = String::new();
+        }
+
+        let changes = detect_changed_resources(&lockfile, &baseline, temp.path(), false, false);
+
+        let changed: HashSet<&str> = changes.changed.iter().map(ResourceId::name).collect();
+        assert_eq!(changed, HashSet::from(["edited", "missing", "bumped"]));
+        assert_eq!(changes.unchanged_count, 1);
+        assert!(!changes.hooks_changed);
+        assert!(!changes.mcp_servers_changed);
+
+        changes.carry_forward(&mut lockfile, &baseline);
+        assert_eq!(lockfile.agents[0].checksum, baseline.agents[0].checksum);
+        assert!(lockfile.agents[1].checksum.is_empty());
+    }
+
+    #[test]
+    fn test_detect_changed_resources_nothing_changed() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join(".claude/agents")).unwrap();
+        std::fs::write(temp.path().join(".claude/agents/a.md"), "a").unwrap();
+
+        let baseline = LockFile {
+            agents: vec![git_resource("a", ResourceType::Agent, "a")],
+            hooks: vec![git_resource("on-save", ResourceType::Hook, "{}")],
+            ..Default::default()
+        };
+
+        let changes = detect_changed_resources(&baseline, &baseline, temp.path(), false, false);
+        assert!(changes.is_empty());
+
+        // A removed hook forces the merged hook config to be recomputed
+        let mut lockfile = baseline.clone();
+        lockfile.hooks.clear();
+        assert!(
+            detect_changed_resources(&lockfile, &baseline, temp.path(), false, false).hooks_changed
+        );
+    }
+
+    #[test]
+    fn test_verify_installed_files_keeps_banner_text_unless_stripping() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join(".claude/agents")).unwrap();
+        std::fs::write(
+            temp.path().join(".claude/agents/a.md"),
+            "<!-- Managed by AGPM: community/a.md@v1.0.0 -->\n# A\n",
+        )
+        .unwrap();
+
+        let lockfile = LockFile {
+            agents: vec![git_resource("a", ResourceType::Agent, "# A\n")],
+            ..Default::default()
+        };
+
+        // Without installer.header or installer.footer, banner-like text is content
+        let issues = verify_installed_files(&lockfile, temp.path(), false, false);
+        assert_eq!(issues.len(), 1);
+        assert!(!issues[0].missing);
+
+        assert!(verify_installed_files(&lockfile, temp.path(), true, false).is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+//! Checksum sidecar for downstream verification (`installer.emit_checksums`).
+//!
+//! After installation, `CHECKSUMS.txt` in the project directory lists every
+//! installed file with its SHA-256 in the format written by `sha256sum`, so the
+//! installation can be checked with `sha256sum -c CHECKSUMS.txt` from the
+//! project directory.
+//!
+//! Digests are taken from the lockfile. Files are hashed from disk instead when
+//! the lockfile checksum does not cover their exact bytes: with a banner or
+//! footer (`installer.header`, `installer.footer`), which checksums exclude,
+//! with `integrity.normalize_eol`, which hashes CRLF as LF, and for skills,
+//! which are recorded with one checksum per directory. Hooks
+//! and MCP servers are merged into shared config files and are not listed.
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+use crate::core::ResourceType;
+use crate::core::file_error::{FileOperation, FileResultExt};
+use crate::lockfile::LockFile;
+use crate::utils::fs::atomic_write;
+
+/// Name of the sidecar file, relative to the project directory.
+pub const CHECKSUMS_FILE: &str = "CHECKSUMS.txt";
+
+/// Write `CHECKSUMS.txt` for the files installed from `lockfile`.
+///
+/// Set `rehash` when lockfile checksums may differ from the installed bytes:
+/// with a header or footer, or with `integrity.normalize_eol`. Returns the
+/// number of files listed.
+///
+/// # Errors
+///
+/// Returns an error if an installed file cannot be read or the sidecar cannot
+/// be written.
+pub fn write_checksums(lockfile: &LockFile, project_dir: &Path, rehash: bool) -> Result<usize> {
+    let entries = checksum_entries(lockfile, project_dir, rehash)?;
+    let content: String =
+        entries.iter().map(|(path, digest)| format!("{digest}  {path}\n")).collect();
+    atomic_write(&project_dir.join(CHECKSUMS_FILE), content.as_bytes())?;
+    Ok(entries.len())
+}
+
+/// `(path, hex digest)` of every installed file, sorted by path.
+fn checksum_entries(
+    lockfile: &LockFile,
+    project_dir: &Path,
+    rehash: bool,
+) -> Result<Vec<(String, String)>> {
+    let mut entries = Vec::new();
+    for entry in lockfile.all_resources() {
+        if matches!(entry.resource_type, ResourceType::Hook | ResourceType::McpServer)
+            || !entry.install.unwrap_or(true)
+            || entry.installed_at.is_empty()
+        {
+            continue;
+        }
+
+        let installed_at = entry.installed_at.replace('\\', "/");
+        if entry.resource_type == ResourceType::Skill {
+            let dir = project_dir.join(&installed_at);
+            for file in walkdir::WalkDir::new(&dir).sort_by_file_name() {
+                let file = file?;
+                if file.file_type().is_file() {
+                    let relative = file.path().strip_prefix(&dir)?.to_string_lossy();
+                    let path = format!("{installed_at}/{}", relative.replace('\\', "/"));
+                    entries.push((path, hash_file(file.path())?));
+                }
+            }
+            continue;
+        }
+
+        let digest = match entry.checksum.strip_prefix("sha256:") {
+            Some(digest) if !rehash => digest.to_string(),
+            _ => hash_file(&project_dir.join(&installed_at))?,
+        };
+        entries.push((installed_at, digest));
+    }
+    entries.sort();
+    entries.dedup();
+    Ok(entries)
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    let content = std::fs::read(path).with_file_context(
+        FileOperation::Read,
+        path,
+        "hashing installed file for CHECKSUMS.txt",
+        "installer::checksums",
+    )?;
+    Ok(hex::encode(Sha256::digest(&content)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::installer::resource::compute_file_checksum;
+    use crate::lockfile::{LockedResource, LockedResourceBuilder};
+
+    fn entry(name: &str, installed_at: &str, content: &str, rt: ResourceType) -> LockedResource {
+        LockedResourceBuilder::new(
+            name.to_string(),
+            format!("{name}.md"),
+            compute_file_checksum(content),
+            installed_at.to_string(),
+            rt,
+        )
+        .build()
+    }
+
+    #[test]
+    fn test_write_checksums() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let project = temp.path();
+        std::fs::create_dir_all(project.join(".claude/agents/agpm")).unwrap();
+        std::fs::write(project.join(".claude/agents/agpm/reviewer.md"), "# Reviewer\n").unwrap();
+        std::fs::create_dir_all(project.join(".claude/skills/agpm/pdf")).unwrap();
+        std::fs::write(project.join(".claude/skills/agpm/pdf/SKILL.md"), "# PDF\n").unwrap();
+
+        let mut lockfile = LockFile::new();
+        lockfile.agents.push(entry(
+            "reviewer",
+            ".claude/agents/agpm/reviewer.md",
+            "# Reviewer\n",
+            ResourceType::Agent,
+        ));
+        lockfile.skills.push(entry("pdf", ".claude/skills/agpm/pdf", "", ResourceType::Skill));
+        lockfile.hooks.push(entry(
+            "pre-commit",
+            ".claude/settings.local.json",
+            "{}",
+            ResourceType::Hook,
+        ));
+
+        assert_eq!(write_checksums(&lockfile, project, false).unwrap(), 2);
+        let sidecar = std::fs::read_to_string(project.join(CHECKSUMS_FILE)).unwrap();
+        assert_eq!(
+            sidecar,
+            format!(
+                "{}  .claude/agents/agpm/reviewer.md\n{}  .claude/skills/agpm/pdf/SKILL.md\n",
+                hex::encode(Sha256::digest(b"# Reviewer\n")),
+                hex::encode(Sha256::digest(b"# PDF\n")),
+            )
+        );
+    }
+}
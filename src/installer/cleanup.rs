@@ -1,5 +1,6 @@
 //! Cleanup utilities for removing obsolete artifacts.
 
+use crate::core::ResourceType;
 use crate::lockfile::LockFile;
 use anyhow::{Context, Result};
 
@@ -203,11 +204,15 @@ pub async fn cleanup_removed_artifacts(
     let mut removed = Vec::new();
 
     // Collect installed paths from new lockfile (only resources that should have files on disk)
-    // Resources with install=false are content-only and should not have files
+    // Resources with install=false are content-only and should not have files.
+    // Hooks and MCP servers point at shared config files, which are always kept.
     let new_paths: HashSet<String> = new_lockfile
         .all_resources()
         .into_iter()
-        .filter(|r| r.install != Some(false))
+        .filter(|r| {
+            r.install != Some(false)
+                || matches!(r.resource_type, ResourceType::Hook | ResourceType::McpServer)
+        })
         .map(|r| r.installed_at.clone())
         .collect();
 
@@ -450,7 +455,7 @@ pub async fn cleanup_removed_artifacts(
 ///
 /// - **v0.3.18**: Introduced alongside [`cleanup_removed_artifacts`]
 /// - Complements relative path preservation by cleaning up old directory structures
-async fn cleanup_empty_dirs(file_path: &std::path::Path) -> Result<()> {
+pub(crate) async fn cleanup_empty_dirs(file_path: &std::path::Path) -> Result<()> {
     let mut current = file_path.parent();
 
     while let Some(dir) = current {
@@ -217,6 +217,7 @@ mod tests {
             install: None,
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         let result = check_gitignore_entries(temp.path(), &lockfile).await;
@@ -255,6 +256,7 @@ mod tests {
             install: None,
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         let result = check_gitignore_entries(temp.path(), &lockfile).await;
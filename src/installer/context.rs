@@ -234,12 +234,19 @@ impl<'a> InstallContextBuilder<'a> {
         // Clone cache and wrap in Arc for TemplateContextBuilder
         // The clone is necessary because we have &Cache but need Arc<Cache>
         // Cache cloning is relatively cheap (Arc'd internals) and only happens once per installation
-        let template_context_builder = Arc::new(crate::templating::TemplateContextBuilder::new(
-            lockfile_for_builder,
-            project_config,
-            Arc::new(self.cache.clone()),
-            self.project_dir.to_path_buf(),
-        ));
+        let dependency_field =
+            self.manifest.map_or(crate::manifest::TransitivePolicy::DEFAULT_FIELD, |m| {
+                m.transitive.dependency_field()
+            });
+        let template_context_builder = Arc::new(
+            crate::templating::TemplateContextBuilder::new(
+                lockfile_for_builder,
+                project_config,
+                Arc::new(self.cache.clone()),
+                self.project_dir.to_path_buf(),
+            )
+            .with_dependency_field(dependency_field),
+        );
 
         InstallContext {
             project_dir: self.project_dir,
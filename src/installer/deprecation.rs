@@ -0,0 +1,169 @@
+//! Deprecation detection for installed resources.
+//!
+//! Upstream authors sunset a resource by adding `deprecated: true` (and
+//! optionally `deprecated_message` and `replacement`) to its frontmatter. After
+//! installation the installed files are scanned for these fields, the lockfile
+//! entries are flagged so `agpm list` can show them, and a warning is printed
+//! for each deprecated resource.
+
+use std::path::{Path, PathBuf};
+
+use crate::core::ResourceType;
+use crate::lockfile::{LockFile, LockedResource};
+use crate::markdown::{Deprecation, MarkdownDocument};
+
+/// A resource whose frontmatter marks it as deprecated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeprecatedResource {
+    /// Display name of the resource (manifest alias when available).
+    pub name: String,
+    /// Type of the resource.
+    pub resource_type: ResourceType,
+    /// Deprecation details from the frontmatter.
+    pub deprecation: Deprecation,
+}
+
+impl DeprecatedResource {
+    /// One-line warning describing the deprecation and suggested replacement.
+    #[must_use]
+    pub fn warning(&self) -> String {
+        let mut warning = format!("{} '{}' is deprecated", self.resource_type, self.name);
+        if let Some(message) = &self.deprecation.message {
+            warning.push_str(": ");
+            warning.push_str(message);
+        }
+        if let Some(replacement) = &self.deprecation.replacement {
+            warning.push_str(&format!(" (use '{replacement}' instead)"));
+        }
+        warning
+    }
+}
+
+/// Read the deprecation notice of an installed resource, if it has one.
+///
+/// Returns `None` for resources without an installed Markdown file (hooks, MCP
+/// servers, scripts, content-only dependencies) and for files that cannot be
+/// read or parsed.
+#[must_use]
+pub fn read_deprecation(entry: &LockedResource, project_dir: &Path) -> Option<Deprecation> {
+    let path = installed_markdown_path(entry, project_dir)?;
+    MarkdownDocument::read(&path).ok()?.metadata?.deprecation()
+}
+
+/// Flag deprecated resources in `lockfile` and return them.
+///
+/// Every entry's `deprecated` flag is recomputed from the installed file, so a
+/// resource whose upstream lifts the deprecation is cleared again.
+pub fn mark_deprecated_resources(
+    lockfile: &mut LockFile,
+    project_dir: &Path,
+) -> Vec<DeprecatedResource> {
+    let mut deprecated = Vec::new();
+
+    for resource_type in ResourceType::all() {
+        for entry in lockfile.get_resources_mut(resource_type) {
+            let deprecation = read_deprecation(entry, project_dir);
+            entry.deprecated = deprecation.is_some();
+            if let Some(deprecation) = deprecation {
+                deprecated.push(DeprecatedResource {
+                    name: entry.display_name().to_string(),
+                    resource_type: *resource_type,
+                    deprecation,
+                });
+            }
+        }
+    }
+
+    deprecated
+}
+
+fn installed_markdown_path(entry: &LockedResource, project_dir: &Path) -> Option<PathBuf> {
+    if !entry.install.unwrap_or(true) || entry.installed_at.is_empty() {
+        return None;
+    }
+
+    let path = project_dir.join(&entry.installed_at);
+    match entry.resource_type {
+        ResourceType::Skill => Some(path.join("SKILL.md")),
+        ResourceType::Hook | ResourceType::McpServer => None,
+        _ => crate::markdown::is_markdown_file(&path).then_some(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::LockedResourceBuilder;
+    use tempfile::TempDir;
+
+    fn agent(name: &str) -> LockedResource {
+        LockedResourceBuilder::new(
+            name.to_string(),
+            format!("agents/{name}.md"),
+            String::new(),
+            format!(".claude/agents/{name}.md"),
+            ResourceType::Agent,
+        )
+        .build()
+    }
+
+    #[test]
+    fn test_mark_deprecated_resources() {
+        let temp = TempDir::new().unwrap();
+        let agents_dir = temp.path().join(".claude/agents");
+        std::fs::create_dir_all(&agents_dir).unwrap();
+        std::fs::write(
+            agents_dir.join("old.md"),
+            "---\ndeprecated: true\ndeprecated_message: No longer maintained\nreplacement: agents/new.md\n---\n\n# Old",
+        )
+        .unwrap();
+        std::fs::write(agents_dir.join("new.md"), "---\ntitle: New\n---\n\n# New").unwrap();
+
+        let mut previously_deprecated = agent("new");
+        previously_deprecated.deprecated = true;
+        let mut lockfile = LockFile {
+            agents: vec![agent("old"), previously_deprecated, agent("missing")],
+            ..Default::default()
+        };
+
+        let deprecated = mark_deprecated_resources(&mut lockfile, temp.path());
+
+        assert_eq!(deprecated.len(), 1);
+        assert_eq!(
+            deprecated[0].warning(),
+            "agent 'old' is deprecated: No longer maintained (use 'agents/new.md' instead)"
+        );
+        let flags: Vec<bool> = lockfile.agents.iter().map(|a| a.deprecated).collect();
+        assert_eq!(flags, vec![true, false, false]);
+    }
+
+    #[test]
+    fn test_deprecated_skill_reads_skill_md() {
+        let temp = TempDir::new().unwrap();
+        let skill_dir = temp.path().join(".claude/skills/legacy");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(skill_dir.join("SKILL.md"), "---\nname: legacy\ndeprecated: true\n---\n")
+            .unwrap();
+
+        let skill = LockedResourceBuilder::new(
+            "legacy".to_string(),
+            "skills/legacy".to_string(),
+            String::new(),
+            ".claude/skills/legacy".to_string(),
+            ResourceType::Skill,
+        )
+        .build();
+
+        let deprecation = read_deprecation(&skill, temp.path()).unwrap();
+        assert_eq!(deprecation, Deprecation::default());
+        assert_eq!(
+            DeprecatedResource {
+                name: skill.name,
+                resource_type: ResourceType::Skill,
+                deprecation,
+            }
+            .warning(),
+            "skill 'legacy' is deprecated"
+        );
+    }
+}
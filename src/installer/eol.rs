@@ -0,0 +1,57 @@
+//! Line-ending normalization for checksums (`integrity.normalize_eol`).
+//!
+//! Git's `core.autocrlf` converts LF to CRLF when checking out on Windows, so
+//! the same commit can yield different bytes, and different checksums in
+//! `agpm.lock`, depending on the platform that ran `agpm install`. With
+//! `integrity.normalize_eol = true`, every CRLF is hashed as LF, both for the
+//! checksum recorded at installation and whenever an installed file is
+//! checked against it.
+//!
+//! Only hashing is affected: installed files are written with the line
+//! endings of the content after patches and templating. Banners and footers
+//! (`installer.header`, `installer.footer`) are stripped before normalization,
+//! and a `sha256` pin in the manifest is compared with the normalized
+//! checksum. Files that are not valid UTF-8 are hashed as they are.
+
+use std::borrow::Cow;
+
+use super::resource::compute_file_checksum;
+
+/// `content` with every CRLF line ending replaced by LF.
+#[must_use]
+pub fn normalize_eol(content: &str) -> Cow<'_, str> {
+    if content.contains("\r\n") {
+        Cow::Owned(content.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(content)
+    }
+}
+
+/// Checksum of `content` as recorded in the lockfile, normalizing line
+/// endings first when `normalize` is set.
+#[must_use]
+pub fn compute_content_checksum(content: &str, normalize: bool) -> String {
+    if normalize {
+        compute_file_checksum(&normalize_eol(content))
+    } else {
+        compute_file_checksum(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_eol() {
+        assert_eq!(normalize_eol("a\r\nb\r\n"), "a\nb\n");
+        assert!(matches!(normalize_eol("a\nb\n"), Cow::Borrowed(_)));
+        // A lone CR is not a line ending Git converts
+        assert_eq!(normalize_eol("a\rb\n"), "a\rb\n");
+
+        let lf = "---\ntitle: Reviewer\n---\n\n# Reviewer\n";
+        let crlf = lf.replace('\n', "\r\n");
+        assert_eq!(compute_content_checksum(&crlf, true), compute_content_checksum(lf, false));
+        assert_ne!(compute_content_checksum(&crlf, false), compute_content_checksum(lf, false));
+    }
+}
@@ -0,0 +1,354 @@
+//! "Managed by AGPM" banners and provenance footers for installed files.
+//!
+//! With `installer.header = true` in `agpm.toml`, installed files carry a
+//! one-line banner naming their source and version and asking readers not to
+//! edit them. The banner uses a syntax that keeps the file valid:
+//!
+//! - Markdown: an HTML comment placed after the frontmatter (or on the first
+//!   line when there is none), so frontmatter parsing is unaffected
+//! - JSON: an `"_agpm"` string member inserted as the first key of the
+//!   top-level object
+//!
+//! Other file types (scripts, skill directories) are left untouched.
+//!
+//! With `installer.footer = true`, Markdown files also end with an
+//! `<!-- agpm:footer ... -->` comment listing the resolved source, version,
+//! commit and path as `key: value` lines, for audit tooling. JSON has no
+//! comment syntax, so JSON files get no footer.
+//!
+//! Neither block is part of the resource content: checksums are computed on
+//! the content without them, and [`strip_banners`] is applied when an installed
+//! file is hashed while either option is enabled, so enabling them never
+//! changes the lockfile.
+
+use std::borrow::Cow;
+use std::path::Path;
+
+use crate::lockfile::LockedResource;
+
+const MARKER: &str = "Managed by AGPM";
+const MARKDOWN_PREFIX: &str = "<!-- Managed by AGPM";
+const JSON_PREFIX: &str = "\n  \"_agpm\": \"Managed by AGPM";
+const FOOTER_START: &str = "\n<!-- agpm:footer\n";
+const FOOTER_END: &str = "-->\n";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HeaderFormat {
+    Markdown,
+    Json,
+}
+
+impl HeaderFormat {
+    fn for_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Some(Self::Json),
+            _ if crate::markdown::is_markdown_file(path) => Some(Self::Markdown),
+            _ => None,
+        }
+    }
+}
+
+/// Banner text for `entry`, e.g. `Managed by AGPM — do not edit. Source: community@v1.0.0 (agents/reviewer.md)`.
+fn header_text(entry: &LockedResource) -> String {
+    let origin = match (&entry.source, &entry.version, &entry.resolved_commit) {
+        (Some(source), Some(version), _) => format!("{source}@{version}"),
+        (Some(source), None, Some(commit)) => {
+            format!("{source}@{}", &commit[..commit.len().min(8)])
+        }
+        (Some(source), None, None) => source.clone(),
+        (None, ..) => "local".to_string(),
+    };
+    let text = format!("{MARKER} — do not edit. Source: {origin} ({})", entry.path);
+
+    // Keep the banner a single line that cannot terminate a comment or string early
+    sanitize(&text).chars().filter(|c| *c != '"' && *c != '\\').collect()
+}
+
+/// Strip anything that could end an HTML comment or break a line early.
+fn sanitize(text: &str) -> String {
+    text.replace("-->", "->").chars().filter(|c| !c.is_control()).collect()
+}
+
+/// Footer block for `entry`, from the leading newline to the closing `-->`.
+fn footer_block(entry: &LockedResource) -> String {
+    let mut block = FOOTER_START.to_string();
+    let fields = [
+        ("source", entry.source.as_deref().unwrap_or("local")),
+        ("version", entry.version.as_deref().unwrap_or_default()),
+        ("commit", entry.resolved_commit.as_deref().unwrap_or_default()),
+        ("path", entry.path.as_str()),
+    ];
+    for (key, value) in fields.into_iter().filter(|(_, value)| !value.is_empty()) {
+        block.push_str(&format!("{key}: {}\n", sanitize(value)));
+    }
+    block.push_str(FOOTER_END);
+    block
+}
+
+/// Add the banner for `entry` to `content` destined for `dest_path`.
+///
+/// Returns `content` unchanged for file types without a banner format.
+#[must_use]
+pub fn add_header(content: &str, dest_path: &Path, entry: &LockedResource) -> String {
+    let text = header_text(entry);
+    match HeaderFormat::for_path(dest_path) {
+        Some(HeaderFormat::Markdown) => match markdown_offset(content) {
+            Some(offset) => {
+                format!("{}<!-- {text} -->\n{}", &content[..offset], &content[offset..])
+            }
+            None => content.to_string(),
+        },
+        Some(HeaderFormat::Json) => match json_offset(content) {
+            Some(offset) => {
+                let rest = &content[offset..];
+                let separator = if rest.trim_start().starts_with('}') {
+                    ""
+                } else {
+                    ","
+                };
+                format!("{}\n  \"_agpm\": \"{text}\"{separator}{rest}", &content[..offset])
+            }
+            None => content.to_string(),
+        },
+        None => content.to_string(),
+    }
+}
+
+/// Append the provenance footer for `entry` to `content` destined for `dest_path`.
+///
+/// Only Markdown files get a footer; other content is returned unchanged.
+#[must_use]
+pub fn add_footer(content: &str, dest_path: &Path, entry: &LockedResource) -> String {
+    match HeaderFormat::for_path(dest_path) {
+        Some(HeaderFormat::Markdown) => format!("{content}{}", footer_block(entry)),
+        _ => content.to_string(),
+    }
+}
+
+/// Remove a footer added by [`add_footer`], if present.
+#[must_use]
+pub fn strip_footer(content: &str) -> &str {
+    &content[..footer_offset(content).unwrap_or(content.len())]
+}
+
+/// Remove both the banner and the footer, if present.
+#[must_use]
+pub fn strip_banners(content: &str) -> Cow<'_, str> {
+    strip_header(strip_footer(content))
+}
+
+/// Remove a banner added by [`add_header`], if present.
+///
+/// Detection is based on content rather than file extension, so the result
+/// is the same whichever path the content was read from.
+#[must_use]
+pub fn strip_header(content: &str) -> Cow<'_, str> {
+    if let Some(offset) = markdown_offset(content) {
+        let rest = &content[offset..];
+        if rest.starts_with(MARKDOWN_PREFIX)
+            && let Some(end) = rest.find('\n')
+            && rest[..end].trim_end().ends_with("-->")
+        {
+            return Cow::Owned(format!("{}{}", &content[..offset], &rest[end + 1..]));
+        }
+    }
+
+    if let Some(offset) = json_offset(content) {
+        let rest = &content[offset..];
+        if rest.starts_with(JSON_PREFIX)
+            && let Some(close) = rest[JSON_PREFIX.len()..].find('"')
+        {
+            let mut end = JSON_PREFIX.len() + close + 1;
+            if rest[end..].starts_with(',') {
+                end += 1;
+            }
+            return Cow::Owned(format!("{}{}", &content[..offset], &rest[end..]));
+        }
+    }
+
+    Cow::Borrowed(content)
+}
+
+/// Whether `content` starts with (or has after its frontmatter) an AGPM banner.
+#[must_use]
+pub fn has_header(content: &str) -> bool {
+    matches!(strip_header(content), Cow::Owned(_))
+}
+
+/// Whether the file at `path` already matches the configured banner setting.
+///
+/// Missing or unreadable files are reported as matching; they are reinstalled
+/// anyway because their checksum cannot match.
+pub(crate) fn header_matches(path: &Path, enabled: bool) -> bool {
+    if HeaderFormat::for_path(path).is_none() {
+        return true;
+    }
+    match std::fs::read_to_string(path) {
+        Ok(content) => has_header(&content) == enabled,
+        Err(_) => true,
+    }
+}
+
+/// Whether the file at `path` ends with exactly the footer `entry` would get.
+///
+/// Pass `None` when footers are disabled, in which case the file must have
+/// none. Missing or unreadable files are reported as matching, as for
+/// [`header_matches`].
+pub(crate) fn footer_matches(path: &Path, entry: Option<&LockedResource>) -> bool {
+    if HeaderFormat::for_path(path) != Some(HeaderFormat::Markdown) {
+        return true;
+    }
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return true;
+    };
+    let actual = footer_offset(&content).map(|offset| &content[offset..]);
+    match entry {
+        Some(entry) => actual == Some(footer_block(entry).as_str()),
+        None => actual.is_none(),
+    }
+}
+
+/// Byte offset where a trailing footer block starts, if `content` ends with one.
+fn footer_offset(content: &str) -> Option<usize> {
+    let offset = content.rfind(FOOTER_START)?;
+    let body = &content[offset + FOOTER_START.len()..];
+    (body.ends_with(FOOTER_END) && !body[..body.len() - FOOTER_END.len()].contains("-->"))
+        .then_some(offset)
+}
+
+/// Byte offset where a Markdown banner goes: after the frontmatter, or 0.
+///
+/// Returns `None` when the closing frontmatter delimiter is the last line
+/// without a trailing newline, where a banner cannot be inserted cleanly.
+fn markdown_offset(content: &str) -> Option<usize> {
+    let Some(first_line_end) = content.find('\n') else {
+        return Some(0);
+    };
+    if content[..first_line_end].trim_end_matches('\r') != "---" {
+        return Some(0);
+    }
+
+    let mut offset = first_line_end + 1;
+    while offset < content.len() {
+        let line_end = content[offset..].find('\n').map(|i| offset + i);
+        let line = &content[offset..line_end.unwrap_or(content.len())];
+        if line.trim_end_matches('\r') == "---" {
+            return line_end.map(|end| end + 1);
+        }
+        match line_end {
+            Some(end) => offset = end + 1,
+            None => break,
+        }
+    }
+
+    // An opening delimiter without a closing one is not frontmatter
+    Some(0)
+}
+
+/// Byte offset just after the opening brace of a top-level JSON object.
+fn json_offset(content: &str) -> Option<usize> {
+    let start = content.len() - content.trim_start().len();
+    content[start..].starts_with('{').then_some(start + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ResourceType;
+    use crate::lockfile::LockedResourceBuilder;
+
+    fn entry(path: &str) -> LockedResource {
+        LockedResourceBuilder::new(
+            "example".to_string(),
+            path.to_string(),
+            String::new(),
+            String::new(),
+            ResourceType::Agent,
+        )
+        .source(Some("community".to_string()))
+        .version(Some("v1.0.0".to_string()))
+        .build()
+    }
+
+    #[test]
+    fn test_markdown_header_round_trip() {
+        let entry = entry("agents/example.md");
+        let dest = Path::new(".claude/agents/example.md");
+
+        let with_frontmatter = "---\ndescription: Example\n---\n\n# Example\n";
+        let installed = add_header(with_frontmatter, dest, &entry);
+        assert_eq!(
+            installed,
+            "---\ndescription: Example\n---\n<!-- Managed by AGPM — do not edit. Source: community@v1.0.0 (agents/example.md) -->\n\n# Example\n"
+        );
+        assert!(has_header(&installed));
+        assert_eq!(strip_header(&installed), with_frontmatter);
+
+        let plain = "# Example\n\nBody";
+        let installed = add_header(plain, dest, &entry);
+        assert!(installed.starts_with("<!-- Managed by AGPM"));
+        assert_eq!(strip_header(&installed), plain);
+        assert!(!has_header(plain));
+    }
+
+    #[test]
+    fn test_json_header_round_trip() {
+        let entry = entry("snippets/settings.json");
+        let dest = Path::new(".agpm/snippets/settings.json");
+
+        let object = "{\n  \"model\": \"sonnet\"\n}\n";
+        let installed = add_header(object, dest, &entry);
+        assert_eq!(
+            installed,
+            "{\n  \"_agpm\": \"Managed by AGPM — do not edit. Source: community@v1.0.0 (snippets/settings.json)\",\n  \"model\": \"sonnet\"\n}\n"
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&installed).unwrap();
+        assert_eq!(parsed["model"], "sonnet");
+        assert_eq!(strip_header(&installed), object);
+
+        let empty = "{}";
+        let installed = add_header(empty, dest, &entry);
+        serde_json::from_str::<serde_json::Value>(&installed).unwrap();
+        assert_eq!(strip_header(&installed), empty);
+
+        // Arrays have no place for a member, so they are left as-is
+        assert_eq!(add_header("[1, 2]", dest, &entry), "[1, 2]");
+    }
+
+    #[test]
+    fn test_footer_round_trip() {
+        let mut entry = entry("agents/example.md");
+        entry.resolved_commit = Some("a".repeat(40));
+        let dest = Path::new(".claude/agents/example.md");
+
+        let content = "---\ndescription: Example\n---\n\n# Example\n";
+        let installed = add_footer(content, dest, &entry);
+        assert_eq!(
+            installed,
+            format!(
+                "{content}\n<!-- agpm:footer\nsource: community\nversion: v1.0.0\ncommit: {}\npath: agents/example.md\n-->\n",
+                "a".repeat(40)
+            )
+        );
+        assert_eq!(strip_footer(&installed), content);
+
+        // Content without a trailing newline round-trips too
+        let installed = add_footer("# Example", dest, &entry);
+        assert_eq!(strip_footer(&installed), "# Example");
+
+        // Both blocks together
+        let installed = add_footer(&add_header(content, dest, &entry), dest, &entry);
+        assert_eq!(strip_banners(&installed), content);
+
+        // JSON has no comment syntax, so it is left alone
+        let json = "{}";
+        assert_eq!(add_footer(json, Path::new("settings.json"), &entry), json);
+    }
+
+    #[test]
+    fn test_header_skips_other_file_types() {
+        let entry = entry("scripts/build.sh");
+        let script = "#!/bin/sh\necho hi\n";
+        assert_eq!(add_header(script, Path::new(".claude/scripts/build.sh"), &entry), script);
+    }
+}
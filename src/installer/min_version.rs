@@ -0,0 +1,148 @@
+//! Minimum AGPM version gating for installed resources.
+//!
+//! Resources that depend on newer installer features declare the oldest
+//! supported release in their frontmatter:
+//!
+//! ```yaml
+//! ---
+//! min_agpm_version: 0.5.0
+//! ---
+//! ```
+//!
+//! Before a resource is written, the declared version is compared with the
+//! running build. Depending on `installer.min_version_policy` an older AGPM
+//! either fails the installation with an error naming the resource, or installs
+//! it with a warning. Without the check such resources fail later in confusing
+//! ways, for example with unrendered template syntax.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use semver::Version;
+
+use crate::lockfile::LockedResource;
+use crate::manifest::MinVersionPolicy;
+use crate::markdown::MarkdownDocument;
+use crate::utils::terminal::WARNING;
+
+/// Check a resource's `min_agpm_version` against the running AGPM.
+///
+/// `content` is the resource's Markdown source (for skills, `SKILL.md`).
+/// Resources without the field always pass.
+///
+/// # Errors
+///
+/// Returns an error if the declared version is not a valid semantic version,
+/// or if it is newer than the running AGPM and `policy` is
+/// [`MinVersionPolicy::Error`].
+pub fn check_min_agpm_version(
+    content: &str,
+    entry: &LockedResource,
+    policy: MinVersionPolicy,
+) -> Result<()> {
+    if let Some(warning) = check_against(content, entry, policy, env!("CARGO_PKG_VERSION"))? {
+        eprintln!("{WARNING}{} {warning}", "Warning:".yellow());
+    }
+    Ok(())
+}
+
+/// Check against the `running` version, returning the warning to print under
+/// [`MinVersionPolicy::Warn`].
+fn check_against(
+    content: &str,
+    entry: &LockedResource,
+    policy: MinVersionPolicy,
+    running: &str,
+) -> Result<Option<String>> {
+    // Most resources don't declare the field; skip parsing their frontmatter
+    if !content.contains("min_agpm_version") {
+        return Ok(None);
+    }
+    let Some(metadata) = MarkdownDocument::parse(content).ok().and_then(|doc| doc.metadata) else {
+        return Ok(None);
+    };
+    let Some(declared) = metadata.min_agpm_version() else {
+        return Ok(None);
+    };
+
+    let name = entry.display_name();
+    let required =
+        Version::parse(declared.strip_prefix('v').unwrap_or(&declared)).with_context(|| {
+            format!(
+                "{} '{name}' declares an invalid min_agpm_version '{declared}'",
+                entry.resource_type
+            )
+        })?;
+    let running = Version::parse(running)?;
+    if running >= required {
+        return Ok(None);
+    }
+
+    let message = format!(
+        "{} '{name}' requires AGPM {required} or newer, but this is AGPM {running}",
+        entry.resource_type
+    );
+    match policy {
+        MinVersionPolicy::Error => anyhow::bail!(
+            "{message}\n\nRun 'agpm upgrade', or pin an older version of '{name}'. \
+             Set min_version_policy = \"warn\" under [installer] to install it anyway."
+        ),
+        MinVersionPolicy::Warn => Ok(Some(message)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ResourceType;
+    use crate::lockfile::LockedResourceBuilder;
+
+    fn agent() -> LockedResource {
+        LockedResourceBuilder::new(
+            "reviewer".to_string(),
+            "agents/reviewer.md".to_string(),
+            String::new(),
+            ".claude/agents/reviewer.md".to_string(),
+            ResourceType::Agent,
+        )
+        .build()
+    }
+
+    const NEEDS_0_5: &str = "---\nmin_agpm_version: v0.5.0\n---\n# Reviewer\n";
+
+    #[test]
+    fn test_newer_requirement_fails_with_error_policy() {
+        let err = check_against(NEEDS_0_5, &agent(), MinVersionPolicy::Error, "0.4.14")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with(
+                "agent 'reviewer' requires AGPM 0.5.0 or newer, but this is AGPM 0.4.14"
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_newer_requirement_passes_with_warn_policy() {
+        let warning = check_against(NEEDS_0_5, &agent(), MinVersionPolicy::Warn, "0.4.14").unwrap();
+        assert_eq!(
+            warning.as_deref(),
+            Some("agent 'reviewer' requires AGPM 0.5.0 or newer, but this is AGPM 0.4.14")
+        );
+    }
+
+    #[test]
+    fn test_satisfied_or_missing_requirement_passes() {
+        let check = |content| check_against(content, &agent(), MinVersionPolicy::Error, "0.5.0");
+        assert_eq!(check(NEEDS_0_5).unwrap(), None);
+        assert_eq!(check("# Reviewer\n").unwrap(), None);
+        assert_eq!(check("---\ntitle: Reviewer\n---\n# Reviewer\n").unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalid_requirement_is_an_error() {
+        let content = "---\nmin_agpm_version: soon\n---\n";
+        let err = check_against(content, &agent(), MinVersionPolicy::Warn, "0.4.14").unwrap_err();
+        assert!(err.to_string().contains("invalid min_agpm_version 'soon'"), "{err}");
+    }
+}
@@ -23,7 +23,8 @@
 //! 3. **Parallel processing**: Installs multiple resources concurrently using dedicated worktrees
 //! 4. **Content validation**: Validates markdown format and structure
 //! 5. **Atomic installation**: Files are written atomically to prevent corruption
-//! 6. **Progress tracking**: Real-time progress updates during parallel operations
+//! 6. **Progress tracking**: Real-time progress updates during parallel operations, with a
+//!    summary grouped by resource type (live on a terminal, periodic log lines otherwise)
 //! 7. **Artifact cleanup**: Automatically removes old files from previous installations when paths change
 //!
 //! # Artifact Cleanup (v0.3.18+)
@@ -56,29 +57,47 @@ use crate::constants::{
 };
 use crate::lockfile::ResourceId;
 use crate::utils::progress::{InstallationPhase, MultiPhaseProgress};
+use crate::utils::terminal::WARNING;
 use anyhow::Result;
+use colored::Colorize;
 
+mod changed;
+mod checksums;
 mod cleanup;
 mod config_check;
 mod context;
+mod deprecation;
+mod eol;
+mod header;
+mod min_version;
 pub mod project_lock;
 mod resource;
 mod selective;
 mod skills;
+mod subset;
+mod summary;
+mod symlink;
 
 #[cfg(test)]
 mod tests;
 
+pub use changed::{ChangeSet, IntegrityIssue, detect_changed_resources, verify_installed_files};
+pub use checksums::{CHECKSUMS_FILE, write_checksums};
+pub(crate) use cleanup::cleanup_empty_dirs;
 pub use cleanup::cleanup_removed_artifacts;
 pub use config_check::{ConfigValidation, validate_config};
 pub use context::InstallContext;
-pub use project_lock::ProjectLock;
+pub use deprecation::{DeprecatedResource, mark_deprecated_resources, read_deprecation};
+pub use eol::{compute_content_checksum, normalize_eol};
+pub use header::{add_footer, add_header, has_header, strip_banners, strip_footer, strip_header};
+pub use min_version::check_min_agpm_version;
+pub use project_lock::{PROJECT_LOCK_NAME, ProjectLock};
 pub use selective::install_updated_resources;
+pub use subset::{merge_subset, select_named_resources};
 
 use resource::{
-    apply_resource_patches, compute_file_checksum, read_source_content, render_resource_content,
-    should_skip_installation, should_skip_trusted, validate_markdown_content,
-    write_resource_to_disk,
+    apply_resource_patches, read_source_content, render_resource_content, should_skip_installation,
+    should_skip_trusted, validate_markdown_content, verify_pinned_checksum, write_resource_to_disk,
 };
 
 use skills::{collect_skill_patches, compute_skill_directory_checksum, install_skill_directory};
@@ -226,6 +245,7 @@ impl InstallationResults {
 }
 
 use futures::stream::{self, StreamExt};
+use std::io::IsTerminal;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -233,7 +253,7 @@ use tokio::sync::Mutex;
 use crate::cache::Cache;
 use crate::core::ResourceIterator;
 use crate::lockfile::{LockFile, LockedResource};
-use crate::manifest::Manifest;
+use crate::manifest::{Manifest, ResourceDependency};
 use indicatif::ProgressBar;
 use std::collections::HashSet;
 
@@ -338,30 +358,50 @@ pub async fn install_resource(
     // Fast path: Trust lockfile checksums without recomputing
     // This is safe when manifest hash matches and all deps are immutable
     if let Some(result) = should_skip_trusted(entry, &dest_path, context) {
+        verify_pinned_checksum(entry, &result.1, context)?;
         return Ok(result);
     }
 
     // For skills (directory-based resources), use directory checksum
+    let normalize_eol = context.manifest.is_some_and(|m| m.integrity.normalize_eol);
     let existing_checksum = if entry.resource_type == crate::core::ResourceType::Skill {
         if dest_path.exists() && dest_path.is_dir() {
             let path = dest_path.clone();
-            tokio::task::spawn_blocking(move || LockFile::compute_directory_checksum(&path))
-                .await??
-                .into()
+            tokio::task::spawn_blocking(move || {
+                LockFile::compute_directory_checksum_with_eol(&path, normalize_eol)
+            })
+            .await??
+            .into()
         } else {
             None
         }
     } else if dest_path.exists() {
         let path = dest_path.clone();
-        tokio::task::spawn_blocking(move || LockFile::compute_checksum(&path)).await??.into()
+        let strip_banners =
+            context.manifest.is_some_and(|m| m.installer.header || m.installer.footer);
+        tokio::task::spawn_blocking(move || {
+            LockFile::compute_checksum_with_eol(&path, strip_banners, normalize_eol)
+        })
+        .await??
+        .into()
     } else {
         None
     };
 
+    // Checksums ignore the banner and footer, so toggling `installer.header` or
+    // `installer.footer` (or a footer going stale) needs its own check
+    let header = context.manifest.is_some_and(|m| m.installer.header);
+    let footer = context.manifest.is_some_and(|m| m.installer.footer);
+    let header_matches = entry.resource_type == crate::core::ResourceType::Skill
+        || (header::header_matches(&dest_path, header)
+            && header::footer_matches(&dest_path, footer.then_some(entry)));
+
     // Early-exit optimization: Skip if nothing changed (Git dependencies only)
-    if let Some((checksum, context_checksum, patches, token_count)) =
-        should_skip_installation(entry, &dest_path, existing_checksum.as_ref(), context)
+    if header_matches
+        && let Some((checksum, context_checksum, patches, token_count)) =
+            should_skip_installation(entry, &dest_path, existing_checksum.as_ref(), context)
     {
+        verify_pinned_checksum(entry, &checksum, context)?;
         return Ok((false, checksum, context_checksum, patches, token_count));
     }
 
@@ -399,6 +439,7 @@ pub async fn install_resource(
             } else {
                 entry.checksum.clone()
             };
+            verify_pinned_checksum(entry, &dir_checksum, context)?;
 
             (actually_installed, dir_checksum, None, applied_patches, None)
         } else {
@@ -409,6 +450,9 @@ pub async fn install_resource(
             // Validate markdown format
             validate_markdown_content(&content)?;
 
+            // Refuse (or warn about) resources that need a newer AGPM
+            check_min_agpm_version(&content, entry, min_version_policy(context))?;
+
             // Apply patches (before templating)
             let (patched_content, applied_patches) =
                 apply_resource_patches(&content, entry, context)?;
@@ -434,22 +478,52 @@ pub async fn install_resource(
                 }
             }
 
-            // Calculate file checksum of final content
-            let file_checksum = compute_file_checksum(&final_content);
+            // Calculate file checksum of final content, refusing content that breaks a sha256 pin
+            let file_checksum = compute_content_checksum(&final_content, normalize_eol);
+            verify_pinned_checksum(entry, &file_checksum, context)?;
 
             // Determine if content has changed
-            let content_changed = existing_checksum.as_ref() != Some(&file_checksum);
+            let content_changed =
+                existing_checksum.as_ref() != Some(&file_checksum) || !header_matches;
 
-            // Write to disk if needed
+            // The banner and footer are added after checksumming so they never reach the lockfile
+            let disk_content = if header {
+                add_header(&final_content, &dest_path, entry)
+            } else {
+                final_content
+            };
+            let disk_content = if footer {
+                add_footer(&disk_content, &dest_path, entry)
+            } else {
+                disk_content
+            };
+
+            // Symlink mode links unchanged local content instead of copying it
             let should_install = entry.install.unwrap_or(true);
-            let actually_installed = write_resource_to_disk(
-                &dest_path,
-                &final_content,
-                should_install,
-                content_changed,
-                context,
-            )
-            .await?;
+            let link_source = context
+                .manifest
+                .filter(|m| m.installer.install_mode == crate::manifest::InstallMode::Symlink)
+                .filter(|_| should_install && disk_content == content)
+                .and_then(|_| symlink::local_source_path(entry, context.project_dir));
+            let linked = match link_source {
+                Some(source) => symlink::link_resource(&dest_path, &source)?,
+                None => None,
+            };
+
+            // Otherwise write to disk if needed, replacing any previous symlink
+            let actually_installed = match linked {
+                Some(changed) => changed,
+                None => {
+                    write_resource_to_disk(
+                        &dest_path,
+                        &disk_content,
+                        should_install,
+                        content_changed || dest_path.is_symlink(),
+                        context,
+                    )
+                    .await?
+                }
+            };
 
             (
                 actually_installed,
@@ -463,6 +537,11 @@ pub async fn install_resource(
     Ok((actually_installed, file_checksum, context_checksum, applied_patches, token_count))
 }
 
+/// The manifest's `installer.min_version_policy`, or the default without a manifest.
+fn min_version_policy(context: &InstallContext<'_>) -> crate::manifest::MinVersionPolicy {
+    context.manifest.map(|m| m.installer.min_version_policy).unwrap_or_default()
+}
+
 /// Install a single resource with progress bar updates for user feedback.
 ///
 /// This function wraps [`install_resource`] with progress bar integration to provide
@@ -587,6 +666,11 @@ pub(crate) async fn install_resource_for_parallel(
 /// changes, as tracked by the update command. This enables efficient
 /// incremental updates without full reinstallation.
 ///
+/// ## Changed Resources Only
+/// [`ResourceFilter::Changed`] processes only resources whose installed files
+/// are missing or no longer match the lockfile, as used by
+/// `agpm install --changed-only`.
+///
 /// # Examples
 ///
 /// Install all resources:
@@ -635,6 +719,13 @@ pub enum ResourceFilter {
     /// - Old version (for tracking)
     /// - New version (to install)
     Updated(Vec<(String, Option<String>, String, String)>),
+
+    /// Install only resources that differ from the previous installation.
+    ///
+    /// Used by `install --changed-only`; the set is computed by
+    /// [`detect_changed_resources`]. Hooks and MCP servers are never part of
+    /// the set because they are configured during finalization.
+    Changed(HashSet<ResourceId>),
 }
 
 /// Resource installation function supporting multiple progress configurations.
@@ -788,6 +879,13 @@ fn collect_install_entries(
                 .map(|(entry, dir)| (entry.clone(), dir.into_owned()))
                 .collect()
         }
+        ResourceFilter::Changed(changed) => {
+            ResourceIterator::collect_all_entries(lockfile, manifest)
+                .into_iter()
+                .filter(|(entry, _)| changed.contains(&entry.id()))
+                .map(|(entry, dir)| (entry.clone(), dir.into_owned()))
+                .collect()
+        }
         ResourceFilter::Updated(updates) => {
             // Collect only the updated entries
             let mut entries = Vec::new();
@@ -826,15 +924,30 @@ fn collect_install_entries(
         return Vec::new();
     }
 
-    // Sort entries for deterministic processing order
+    // Sort entries for deterministic processing order: higher priorities first,
+    // then by type and name
     let mut sorted_entries = all_entries;
     sorted_entries.sort_by(|(a, _), (b, _)| {
-        a.resource_type.cmp(&b.resource_type).then_with(|| a.name.cmp(&b.name))
+        install_priority(manifest, b)
+            .cmp(&install_priority(manifest, a))
+            .then_with(|| a.resource_type.cmp(&b.resource_type))
+            .then_with(|| a.name.cmp(&b.name))
     });
 
     sorted_entries
 }
 
+/// The `priority` of the manifest dependency an entry was installed for.
+///
+/// Transitive dependencies have no manifest alias and get the default `0`.
+fn install_priority(manifest: &Manifest, entry: &LockedResource) -> i32 {
+    entry
+        .manifest_alias
+        .as_deref()
+        .and_then(|alias| manifest.get_dependencies(entry.resource_type)?.get(alias))
+        .map_or(0, ResourceDependency::get_priority)
+}
+
 /// Pre-warm cache by creating all needed worktrees upfront.
 ///
 /// Creates worktrees for all unique (source, url, sha) combinations to enable
@@ -866,7 +979,7 @@ async fn pre_warm_worktrees(
     }
 
     let context = match filter {
-        ResourceFilter::All => "pre-warm",
+        ResourceFilter::All | ResourceFilter::Changed(_) => "pre-warm",
         ResourceFilter::Updated(_) => "update-pre-warm",
     };
 
@@ -930,101 +1043,187 @@ async fn execute_parallel_installation(
 
     let total = entries.len();
 
+    // Aggregated summary: a live counter on a terminal, periodic log lines otherwise
+    let summary = progress.as_ref().filter(|pm| pm.is_enabled()).map(|_| {
+        let live = std::io::stderr().is_terminal();
+        Arc::new(std::sync::Mutex::new(summary::InstallSummary::new(total, live)))
+    });
+
+    // Entries arrive sorted by priority; each priority tier is installed in
+    // parallel, and completes before the next one starts
+    let mut tiers: Vec<Vec<(LockedResource, String)>> = Vec::new();
+    let mut tier_priority = None;
+    for item in entries {
+        let priority = install_priority(manifest, &item.0);
+        if tier_priority != Some(priority) {
+            tier_priority = Some(priority);
+            tiers.push(Vec::new());
+        }
+        if let Some(tier) = tiers.last_mut() {
+            tier.push(item);
+        }
+    }
+
     // Process installations in parallel with active tracking
-    stream::iter(entries)
-        .map(|(entry, resource_dir)| {
-            let project_dir = project_dir.to_path_buf();
-            let installed_count = Arc::clone(&installed_count);
-            let type_counts = Arc::clone(&type_counts);
-            let cache = cache.clone();
-            let progress = progress.clone();
-            let entry_type = entry.resource_type;
-            async move {
-                // Signal that this resource is starting
-                if let Some(ref pm) = progress {
-                    pm.mark_resource_active(&entry);
-                }
+    let install_one = |(entry, resource_dir): (LockedResource, String)| {
+        let project_dir = project_dir.to_path_buf();
+        let installed_count = Arc::clone(&installed_count);
+        let type_counts = Arc::clone(&type_counts);
+        let cache = cache.clone();
+        let progress = progress.clone();
+        let summary = summary.clone();
+        let entry_type = entry.resource_type;
+        async move {
+            // Signal that this resource is starting
+            if let Some(ref pm) = progress {
+                pm.mark_resource_active(&entry);
+            }
 
-                let install_context = InstallContext::with_common_options_and_trust(
-                    &project_dir,
-                    &cache,
-                    Some(manifest),
-                    Some(lockfile),
-                    force_refresh,
-                    verbose,
-                    old_lockfile,
-                    trust_lockfile_checksums,
-                    token_warning_threshold,
-                );
-
-                let res =
-                    install_resource_for_parallel(&entry, &resource_dir, &install_context).await;
-
-                // Handle result and track completion
-                match res {
-                    Ok((actually_installed, file_checksum, context_checksum, applied_patches, token_count)) => {
-                        // Always increment the counter (regardless of whether file was written)
-                        let timeout = default_lock_timeout();
-                        let mut count = match tokio::time::timeout(timeout, installed_count.lock()).await {
+            let install_context = InstallContext::with_common_options_and_trust(
+                &project_dir,
+                &cache,
+                Some(manifest),
+                Some(lockfile),
+                force_refresh,
+                verbose,
+                old_lockfile,
+                trust_lockfile_checksums,
+                token_warning_threshold,
+            );
+
+            let res = install_resource_for_parallel(&entry, &resource_dir, &install_context).await;
+
+            // Handle result and track completion
+            match res {
+                Ok((
+                    actually_installed,
+                    file_checksum,
+                    context_checksum,
+                    applied_patches,
+                    token_count,
+                )) => {
+                    // Always increment the counter (regardless of whether file was written)
+                    let timeout = default_lock_timeout();
+                    let mut count = match tokio::time::timeout(timeout, installed_count.lock())
+                        .await
+                    {
+                        Ok(guard) => guard,
+                        Err(_) => {
+                            eprintln!(
+                                "[DEADLOCK] Timeout waiting for installed_count lock after {:?}",
+                                timeout
+                            );
+                            return Err((
+                                entry.id(),
+                                anyhow::anyhow!(
+                                    "Timeout waiting for installed_count lock after {:?} - possible deadlock",
+                                    timeout
+                                ),
+                            ));
+                        }
+                    };
+                    *count += 1;
+
+                    // Track by type for summary (only count those actually written to disk)
+                    if actually_installed {
+                        let mut type_guard = match tokio::time::timeout(timeout, type_counts.lock())
+                            .await
+                        {
                             Ok(guard) => guard,
                             Err(_) => {
-                                eprintln!("[DEADLOCK] Timeout waiting for installed_count lock after {:?}", timeout);
-                                return Err((entry.id(), anyhow::anyhow!("Timeout waiting for installed_count lock after {:?} - possible deadlock", timeout)));
+                                eprintln!(
+                                    "[DEADLOCK] Timeout waiting for type_counts lock after {:?}",
+                                    timeout
+                                );
+                                return Err((
+                                    entry.id(),
+                                    anyhow::anyhow!(
+                                        "Timeout waiting for type_counts lock after {:?} - possible deadlock",
+                                        timeout
+                                    ),
+                                ));
                             }
                         };
-                        *count += 1;
-
-                        // Track by type for summary (only count those actually written to disk)
-                        if actually_installed {
-                            let mut type_guard = match tokio::time::timeout(timeout, type_counts.lock()).await {
-                                Ok(guard) => guard,
-                                Err(_) => {
-                                    eprintln!("[DEADLOCK] Timeout waiting for type_counts lock after {:?}", timeout);
-                                    return Err((entry.id(), anyhow::anyhow!("Timeout waiting for type_counts lock after {:?} - possible deadlock", timeout)));
-                                }
-                            };
-                            *type_guard.entry(entry_type).or_insert(0) += 1;
-                        }
+                        *type_guard.entry(entry_type).or_insert(0) += 1;
+                    }
 
-                        // Signal completion and update counter
-                        if let Some(ref pm) = progress {
-                            pm.mark_resource_complete(&entry, *count, total);
+                    // Signal completion and update counter
+                    if let Some(ref pm) = progress {
+                        pm.mark_resource_complete(&entry, *count, total);
+                        if let Some(ref summary) = summary {
+                            report_install_summary(pm, summary, entry_type);
                         }
-
-                        Ok((
-                            entry.id(),
-                            actually_installed,
-                            file_checksum,
-                            context_checksum,
-                            applied_patches,
-                            token_count,
-                        ))
                     }
-                    Err(err) => {
-                        // On error, still increment counter and clear the slot
-                        let timeout = default_lock_timeout();
-                        let mut count = match tokio::time::timeout(timeout, installed_count.lock()).await {
-                            Ok(guard) => guard,
-                            Err(_) => {
-                                eprintln!("[DEADLOCK] Timeout waiting for installed_count lock after {:?}", timeout);
-                                return Err((entry.id(), anyhow::anyhow!("Timeout waiting for installed_count lock after {:?} - possible deadlock", timeout)));
-                            }
-                        };
-                        *count += 1;
 
-                        // Clear the slot for this failed resource
-                        if let Some(ref pm) = progress {
-                            pm.mark_resource_complete(&entry, *count, total);
+                    Ok((
+                        entry.id(),
+                        actually_installed,
+                        file_checksum,
+                        context_checksum,
+                        applied_patches,
+                        token_count,
+                    ))
+                }
+                Err(err) => {
+                    // On error, still increment counter and clear the slot
+                    let timeout = default_lock_timeout();
+                    let mut count = match tokio::time::timeout(timeout, installed_count.lock())
+                        .await
+                    {
+                        Ok(guard) => guard,
+                        Err(_) => {
+                            eprintln!(
+                                "[DEADLOCK] Timeout waiting for installed_count lock after {:?}",
+                                timeout
+                            );
+                            return Err((
+                                entry.id(),
+                                anyhow::anyhow!(
+                                    "Timeout waiting for installed_count lock after {:?} - possible deadlock",
+                                    timeout
+                                ),
+                            ));
                         }
+                    };
+                    *count += 1;
 
-                        Err((entry.id(), err))
+                    // Clear the slot for this failed resource
+                    if let Some(ref pm) = progress {
+                        pm.mark_resource_complete(&entry, *count, total);
                     }
+
+                    Err((entry.id(), err))
                 }
             }
-        })
-        .buffered(concurrency)
-        .collect()
-        .await
+        }
+    };
+
+    let mut results = Vec::with_capacity(total);
+    for tier in tiers {
+        results.extend(
+            stream::iter(tier).map(&install_one).buffered(concurrency).collect::<Vec<_>>().await,
+        );
+    }
+    results
+}
+
+/// Count an installed resource in the aggregated summary and show it if due.
+fn report_install_summary(
+    progress: &MultiPhaseProgress,
+    summary: &std::sync::Mutex<summary::InstallSummary>,
+    resource_type: crate::core::ResourceType,
+) {
+    let Ok(mut summary) = summary.lock() else {
+        return;
+    };
+    let Some(line) = summary.record(resource_type) else {
+        return;
+    };
+    if summary.is_live() {
+        progress.set_counter_message(&format!("Installing resources: {line}"));
+    } else {
+        progress.suspend(|| println!("  {line}"));
+    }
 }
 
 /// Process installation results and aggregate checksums.
@@ -1208,9 +1407,10 @@ pub async fn install_resources(
 /// 1. **Hook Configuration** - Configures Claude Code hooks from source files
 /// 2. **MCP Server Setup** - Groups and configures MCP servers by tool type
 /// 3. **Patch Application** - Applies and tracks project/private patches
-/// 4. **Artifact Cleanup** - Removes old files from previous installations
-/// 5. **Lockfile Saving** - Writes main lockfile with checksums (unless --no-lock)
-/// 6. **Private Lockfile** - Saves private patches to separate file
+/// 4. **Deprecation Check** - Flags and warns about resources deprecated in frontmatter
+/// 5. **Artifact Cleanup** - Removes old files from previous installations
+/// 6. **Lockfile Saving** - Writes main lockfile with checksums (unless --no-lock)
+/// 7. **Private Lockfile** - Saves private patches to separate file
 ///
 /// # Arguments
 ///
@@ -1221,6 +1421,11 @@ pub async fn install_resources(
 /// * `old_lockfile` - Optional previous lockfile for artifact cleanup
 /// * `quiet` - Whether to suppress output messages
 /// * `no_lock` - Whether to skip lockfile saving (development mode)
+/// * `preserve_lockfile` - Whether to rewrite only the changed entries of an
+///   existing `agpm.lock`, keeping the rest byte-identical (`update <name>`)
+/// * `changes` - Change set from `install --changed-only`; hooks and MCP servers
+///   are only reconfigured if their entries changed, and lockfiles are only
+///   rewritten if their content differs
 ///
 /// # Returns
 ///
@@ -1258,6 +1463,8 @@ pub async fn install_resources(
 ///     None,    // no old lockfile (fresh install)
 ///     false,   // not quiet
 ///     false,   // create lockfile
+///     false,   // render the whole lockfile
+///     None,    // no change detection
 /// ).await?;
 ///
 /// println!("Configured {} hooks and {} servers", hooks, servers);
@@ -1272,6 +1479,7 @@ pub async fn install_resources(
 /// - Patch tracking: project patches stored in lockfile, private in separate file
 /// - Artifact cleanup only runs if old lockfile exists (update scenario)
 /// - Private lockfile automatically deleted if empty
+#[allow(clippy::too_many_arguments)]
 pub async fn finalize_installation(
     lockfile: &mut LockFile,
     manifest: &Manifest,
@@ -1280,17 +1488,38 @@ pub async fn finalize_installation(
     old_lockfile: Option<&LockFile>,
     quiet: bool,
     no_lock: bool,
+    preserve_lockfile: bool,
+    changes: Option<&ChangeSet>,
 ) -> Result<(usize, usize)> {
     use anyhow::Context;
 
     let mut hook_count = 0;
     let mut server_count = 0;
 
+    // With --changed-only, merged configs are left alone unless a fragment changed
+    let skip_hooks = changes.is_some_and(|c| !c.hooks_changed);
+    let skip_mcp_servers = changes.is_some_and(|c| !c.mcp_servers_changed);
+    // Types disabled in [install] stay in the lockfile with install=false
+    let enabled_hooks = lockfile.hooks.iter().filter(|h| h.install != Some(false)).count();
+    let enabled_servers = lockfile.mcp_servers.iter().filter(|s| s.install != Some(false)).count();
+    if skip_hooks {
+        hook_count = enabled_hooks;
+    }
+    if skip_mcp_servers {
+        server_count = enabled_servers;
+    }
+
     // Handle hooks if present
-    if !lockfile.hooks.is_empty() {
+    if !skip_hooks && enabled_hooks > 0 {
         // Configure hooks directly from source files (no copying)
-        let hooks_changed = crate::hooks::install_hooks(lockfile, project_dir, cache).await?;
-        hook_count = lockfile.hooks.len();
+        let settings_path = project_dir.join(
+            manifest
+                .get_merge_target("claude-code", crate::core::ResourceType::Hook)
+                .unwrap_or_else(|| ".claude/settings.local.json".into()),
+        );
+        let hooks_changed =
+            crate::hooks::install_hooks(lockfile, project_dir, &settings_path, cache).await?;
+        hook_count = enabled_hooks;
 
         // Always show hooks configuration feedback with changed count
         if !quiet {
@@ -1307,7 +1536,7 @@ pub async fn finalize_installation(
     }
 
     // Handle MCP servers if present - group by artifact type
-    if !lockfile.mcp_servers.is_empty() {
+    if !skip_mcp_servers && enabled_servers > 0 {
         use crate::mcp::handlers::McpHandler;
         use std::collections::HashMap;
 
@@ -1316,7 +1545,8 @@ pub async fn finalize_installation(
             HashMap::new();
         {
             // Scope to limit the immutable borrow of lockfile
-            for server in &lockfile.mcp_servers {
+            // Servers with install=false (e.g. `mcp-servers = false`) are not configured
+            for server in lockfile.mcp_servers.iter().filter(|s| s.install != Some(false)) {
                 let tool = server.tool.clone().unwrap_or_else(|| "claude-code".to_string());
                 servers_by_type.entry(tool).or_default().push(server.clone());
             }
@@ -1333,8 +1563,7 @@ pub async fn finalize_installation(
             if let Some(handler) = crate::mcp::handlers::get_mcp_handler(&artifact_type) {
                 // Get artifact base directory - must be properly configured
                 let artifact_base = manifest
-                    .get_tool_config(&artifact_type)
-                    .map(|c| &c.path)
+                    .get_tool_base_path(&artifact_type)
                     .ok_or_else(|| {
                         anyhow::anyhow!(
                             "Tool '{}' is not configured. Please define it in [default-tools] section.",
@@ -1392,6 +1621,14 @@ pub async fn finalize_installation(
         }
     }
 
+    // Flag resources whose frontmatter marks them as deprecated
+    let deprecated = mark_deprecated_resources(lockfile, project_dir);
+    if !quiet {
+        for resource in &deprecated {
+            eprintln!("{WARNING}{} {}", "Warning:".yellow(), resource.warning());
+        }
+    }
+
     // Clean up removed or moved artifacts if old lockfile provided
     if let Some(old) = old_lockfile {
         if let Ok(removed) = cleanup_removed_artifacts(old, lockfile, project_dir).await {
@@ -1401,17 +1638,50 @@ pub async fn finalize_installation(
         }
     }
 
+    // Checksums of overridden sources describe local checkouts, not the
+    // locked commits, so they must not reach the shared lockfile
+    let no_lock = no_lock || cache.has_path_overrides();
+    if no_lock && cache.has_path_overrides() && !quiet {
+        eprintln!("Note: agpm.lock was not updated while path overrides are active");
+    }
+
     if !no_lock {
         // Split lockfile into public and private parts
         let (public_lock, private_lock) = lockfile.split_by_privacy();
 
-        // Save public lockfile (team-shared)
-        public_lock.save(&project_dir.join("agpm.lock")).with_context(|| {
-            format!("Failed to save lockfile to {}", project_dir.join("agpm.lock").display())
-        })?;
+        let lockfile_path = project_dir.join("agpm.lock");
+
+        // Keep the previous lockfile for `agpm update --rollback`
+        let keep = crate::config::GlobalConfig::load()
+            .await
+            .ok()
+            .and_then(|config| config.lockfile_backups)
+            .unwrap_or(crate::lockfile::backup::DEFAULT_LOCKFILE_BACKUPS);
+        public_lock.backup_before_save(&lockfile_path, project_dir, keep)?;
+
+        if changes.is_some() {
+            // Leave unchanged lockfiles untouched so --changed-only performs no writes
+            public_lock.save_if_changed(&lockfile_path).with_context(|| {
+                format!("Failed to save lockfile to {}", lockfile_path.display())
+            })?;
+            private_lock
+                .save_if_changed(project_dir)
+                .with_context(|| "Failed to save private lockfile")?;
+        } else if preserve_lockfile {
+            // Keep untouched entries byte-identical to minimize the diff
+            public_lock.save_preserving(&lockfile_path).with_context(|| {
+                format!("Failed to save lockfile to {}", lockfile_path.display())
+            })?;
+            private_lock.save(project_dir).with_context(|| "Failed to save private lockfile")?;
+        } else {
+            // Save public lockfile (team-shared)
+            public_lock.save(&lockfile_path).with_context(|| {
+                format!("Failed to save lockfile to {}", lockfile_path.display())
+            })?;
 
-        // Save private lockfile (user-specific, automatically deletes if empty)
-        private_lock.save(project_dir).with_context(|| "Failed to save private lockfile")?;
+            // Save private lockfile (user-specific, automatically deletes if empty)
+            private_lock.save(project_dir).with_context(|| "Failed to save private lockfile")?;
+        }
     }
 
     Ok((hook_count, server_count))
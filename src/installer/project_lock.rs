@@ -4,6 +4,12 @@
 //! like resource installation. The locks are automatically released when
 //! the lock object is dropped.
 //!
+//! `install` and `update` hold the [`PROJECT_LOCK_NAME`] lock from before
+//! dependency resolution until `agpm.lock` has been written, so concurrent
+//! invocations in the same project run one after another instead of racing on
+//! the lockfile. It is independent of the cache locks, which only coordinate
+//! access to shared Git repositories.
+//!
 //! # Async Safety
 //!
 //! All file operations are wrapped in `spawn_blocking` to avoid blocking the tokio
@@ -20,6 +26,9 @@ use std::time::Duration;
 use tokio_retry::strategy::ExponentialBackoff;
 use tracing::debug;
 
+/// Lock serializing commands that resolve dependencies and write `agpm.lock`.
+pub const PROJECT_LOCK_NAME: &str = "project";
+
 /// A file lock for project-level operations.
 ///
 /// Provides cross-process synchronization for operations like resource
@@ -128,17 +137,7 @@ impl ProjectLock {
         // Create lock file path
         let lock_path = locks_dir.join(format!("{lock_name}.lock"));
 
-        // CRITICAL: Use spawn_blocking for file open to avoid blocking tokio runtime
-        let lock_path_clone = lock_path.clone();
-        let file = tokio::task::spawn_blocking(move || {
-            OpenOptions::new().create(true).write(true).truncate(false).open(&lock_path_clone)
-        })
-        .await
-        .with_context(|| "spawn_blocking panicked")?
-        .with_context(|| format!("Failed to open lock file: {}", lock_path.display()))?;
-
-        // Wrap file in Arc for sharing with spawn_blocking
-        let file = Arc::new(file);
+        let mut file = open_lock_file(&lock_path).await?;
 
         // Acquire exclusive lock with timeout and exponential backoff
         let start = std::time::Instant::now();
@@ -155,6 +154,11 @@ impl ProjectLock {
                 .with_context(|| "spawn_blocking panicked")?;
 
             match lock_result {
+                Ok(true) if !is_current_lock_file(&file, &lock_path).await? => {
+                    // The previous holder removed the file on release, so this lock
+                    // excludes nobody. Lock the file now at `lock_path` instead.
+                    file = open_lock_file(&lock_path).await?;
+                }
                 Ok(true) => {
                     debug!(
                         lock_name = %display_name,
@@ -171,11 +175,7 @@ impl ProjectLock {
                     // Check remaining time before sleeping to avoid exceeding timeout
                     let remaining = timeout.saturating_sub(start.elapsed());
                     if remaining.is_zero() {
-                        return Err(anyhow::anyhow!(
-                            "Timeout acquiring project lock '{}' after {:?}",
-                            lock_name,
-                            timeout
-                        ));
+                        return Err(timeout_error(project_dir, lock_name, timeout));
                     }
                     // Sleep for the shorter of delay or remaining time
                     tokio::time::sleep(delay.min(remaining)).await;
@@ -184,10 +184,62 @@ impl ProjectLock {
         }
 
         // If backoff iterator exhausted without acquiring lock, return timeout error
-        Err(anyhow::anyhow!("Timeout acquiring project lock '{}' after {:?}", lock_name, timeout))
+        Err(timeout_error(project_dir, lock_name, timeout))
     }
 }
 
+/// Open (creating if needed) the lock file without blocking the runtime.
+async fn open_lock_file(lock_path: &Path) -> Result<Arc<File>> {
+    let lock_path_clone = lock_path.to_path_buf();
+    let file = tokio::task::spawn_blocking(move || {
+        OpenOptions::new().create(true).write(true).truncate(false).open(&lock_path_clone)
+    })
+    .await
+    .with_context(|| "spawn_blocking panicked")?
+    .with_context(|| format!("Failed to open lock file: {}", lock_path.display()))?;
+
+    // Wrap file in Arc for sharing with spawn_blocking
+    Ok(Arc::new(file))
+}
+
+/// Whether the locked handle still refers to the file at `lock_path`.
+///
+/// Locks remove their file on drop, so a waiter can end up locking an unlinked
+/// file while a newcomer creates and locks a fresh one at the same path.
+#[cfg(unix)]
+async fn is_current_lock_file(file: &Arc<File>, lock_path: &Path) -> Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+
+    let file = Arc::clone(file);
+    let lock_path = lock_path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        Ok(match (file.metadata(), std::fs::metadata(&lock_path)) {
+            (Ok(held), Ok(current)) => held.dev() == current.dev() && held.ino() == current.ino(),
+            _ => false,
+        })
+    })
+    .await
+    .with_context(|| "spawn_blocking panicked")?
+}
+
+/// The standard library exposes no stable file identity on other platforms,
+/// so the handle is assumed to be current.
+#[cfg(not(unix))]
+async fn is_current_lock_file(_file: &Arc<File>, _lock_path: &Path) -> Result<bool> {
+    Ok(true)
+}
+
+/// Error for a lock still held by another process when the timeout expires.
+fn timeout_error(project_dir: &Path, lock_name: &str, timeout: Duration) -> anyhow::Error {
+    anyhow::anyhow!(
+        "Another agpm process is running in {} (timeout acquiring project lock '{}' after {:?}).\n\n\
+         Wait for it to finish and try again.",
+        project_dir.display(),
+        lock_name,
+        timeout
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -329,13 +381,18 @@ mod tests {
         // Verify timeout occurred
         assert!(result.is_err(), "Expected timeout error");
 
-        // Verify error message mentions timeout
+        // Verify error message mentions timeout and the competing process
         let error_msg = result.unwrap_err().to_string();
         assert!(
             error_msg.contains("Timeout") || error_msg.contains("timeout"),
             "Error message should mention timeout: {}",
             error_msg
         );
+        assert!(
+            error_msg.contains("Another agpm process is running"),
+            "Error message should explain the cause: {}",
+            error_msg
+        );
 
         // Verify timeout happened around the expected time
         assert!(elapsed >= Duration::from_millis(50), "Timeout too quick: {:?}", elapsed);
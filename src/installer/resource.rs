@@ -156,8 +156,6 @@ pub fn apply_resource_patches(
     entry: &LockedResource,
     context: &InstallContext<'_>,
 ) -> Result<(String, crate::manifest::patches::AppliedPatches)> {
-    let empty_patches = std::collections::BTreeMap::new();
-
     if context.project_patches.is_some() || context.private_patches.is_some() {
         use crate::manifest::patches::apply_patches_to_content_with_origin;
 
@@ -173,24 +171,25 @@ pub fn apply_resource_patches(
             entry.manifest_alias
         );
 
+        let version = entry.version.as_deref();
         let project_patch_data = context
             .project_patches
-            .and_then(|patches| patches.get(resource_type, lookup_name))
-            .unwrap_or(&empty_patches);
+            .and_then(|patches| patches.get_for_version(resource_type, lookup_name, version))
+            .unwrap_or_default();
 
         tracing::debug!("Found {} project patches for {}", project_patch_data.len(), lookup_name);
 
         let private_patch_data = context
             .private_patches
-            .and_then(|patches| patches.get(resource_type, lookup_name))
-            .unwrap_or(&empty_patches);
+            .and_then(|patches| patches.get_for_version(resource_type, lookup_name, version))
+            .unwrap_or_default();
 
         let file_path = entry.installed_at.as_str();
         apply_patches_to_content_with_origin(
             content,
             file_path,
-            project_patch_data,
-            private_patch_data,
+            &project_patch_data,
+            &private_patch_data,
         )
         .with_context(|| format!("Failed to apply patches to resource {}", entry.name))
     } else {
@@ -405,12 +404,23 @@ async fn render_full_file(
         depth: 0,
     };
 
+    // Render resources pulled in with `{% include %}` before the file itself
+    let includes = crate::templating::includes::render_includes(
+        template_context_builder,
+        entry,
+        content,
+        context.max_content_file_size,
+    )
+    .await
+    .with_context(|| format!("Failed to resolve includes for resource '{}'", entry.name))?;
+
     let mut renderer = TemplateRenderer::new(
         true,
         context.project_dir.to_path_buf(),
         context.max_content_file_size,
     )
-    .with_context(|| "Failed to create template renderer")?;
+    .with_context(|| "Failed to create template renderer")?
+    .with_includes(includes);
 
     let rendered_content = renderer
         .render_template(content, &template_context, Some(&rendering_metadata))
@@ -461,6 +471,44 @@ pub fn compute_file_checksum(content: &str) -> String {
     format!("sha256:{}", hex::encode(hash))
 }
 
+/// Fail if `checksum` differs from the `sha256` pinned for `entry` in the manifest.
+///
+/// Only direct dependencies can be pinned; they are looked up by their manifest
+/// alias. The pin may be written with or without the `sha256:` prefix.
+///
+/// # Errors
+///
+/// Returns an error naming the expected and actual hashes on mismatch.
+pub(crate) fn verify_pinned_checksum(
+    entry: &LockedResource,
+    checksum: &str,
+    context: &InstallContext<'_>,
+) -> Result<()> {
+    let Some(pinned) = context
+        .manifest
+        .zip(entry.manifest_alias.as_deref())
+        .and_then(|(manifest, alias)| manifest.get_dependencies(entry.resource_type)?.get(alias))
+        .and_then(|dep| dep.get_sha256())
+    else {
+        return Ok(());
+    };
+
+    let expected = format!("sha256:{}", pinned.trim_start_matches("sha256:").to_ascii_lowercase());
+    if checksum != expected {
+        anyhow::bail!(
+            "Content hash mismatch for {} '{}'\n  expected: {expected}\n  actual:   {checksum}\n\n\
+             The content resolved from {}{} does not match the sha256 pinned in agpm.toml, \
+             so it was not installed. If the upstream change is expected, update the sha256 \
+             to the actual value.",
+            entry.resource_type,
+            entry.display_name(),
+            entry.path,
+            entry.version.as_deref().map(|v| format!(" @ {v}")).unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
 /// Check if all inputs affecting final content are unchanged between lockfile entries.
 ///
 /// This compares the fields that determine resource content and installation behavior:
@@ -470,7 +518,7 @@ pub fn compute_file_checksum(content: &str) -> String {
 /// - `install`: Whether the resource should be installed to disk
 ///
 /// If all four match, the rendered content and installation state will be identical.
-fn inputs_match(entry: &LockedResource, old_entry: &LockedResource) -> bool {
+pub(super) fn inputs_match(entry: &LockedResource, old_entry: &LockedResource) -> bool {
     entry.resolved_commit == old_entry.resolved_commit
         && entry.variant_inputs == old_entry.variant_inputs
         && entry.applied_patches == old_entry.applied_patches
@@ -513,7 +561,7 @@ pub(crate) fn should_skip_trusted(
     }
 
     // Only optimize for Git dependencies (local files can change anytime)
-    if context.force_refresh || entry.is_local() {
+    if context.force_refresh || entry.is_local() || is_path_overridden(entry, context) {
         return None;
     }
 
@@ -568,6 +616,14 @@ pub(crate) fn should_skip_trusted(
     ))
 }
 
+/// Whether `entry` comes from a source read from a local checkout via
+/// `[path-overrides]`, whose content can change without a new commit.
+fn is_path_overridden(entry: &LockedResource, context: &InstallContext<'_>) -> bool {
+    entry.source.as_deref().is_some_and(|source| {
+        context.cache.path_override(source, entry.url.as_deref().unwrap_or_default()).is_some()
+    })
+}
+
 /// Check if installation should be skipped (early-exit optimization).
 ///
 /// This function implements the early-exit optimization for Git-based dependencies
@@ -591,7 +647,7 @@ pub fn should_skip_installation(
     context: &InstallContext<'_>,
 ) -> Option<(String, Option<String>, crate::manifest::patches::AppliedPatches, Option<u64>)> {
     // Only optimize for Git dependencies
-    if context.force_refresh || entry.is_local() {
+    if context.force_refresh || entry.is_local() || is_path_overridden(entry, context) {
         return None;
     }
 
@@ -35,6 +35,7 @@ pub fn collect_skill_patches(
 ) -> AppliedPatches {
     let resource_type = entry.resource_type.to_plural();
     let lookup_name = entry.lookup_name();
+    let version = entry.version.as_deref();
 
     tracing::debug!(
         "Collecting skill patches: resource_type={}, lookup_name={}, name={}, manifest_alias={:?}",
@@ -46,16 +47,14 @@ pub fn collect_skill_patches(
 
     let project_patches = context
         .project_patches
-        .and_then(|patches| patches.get(resource_type, lookup_name))
-        .cloned()
+        .and_then(|patches| patches.get_for_version(resource_type, lookup_name, version))
         .unwrap_or_default();
 
     tracing::debug!("Found {} project patches for skill {}", project_patches.len(), lookup_name);
 
     let private_patches = context
         .private_patches
-        .and_then(|patches| patches.get(resource_type, lookup_name))
-        .cloned()
+        .and_then(|patches| patches.get_for_version(resource_type, lookup_name, version))
         .unwrap_or_default();
 
     tracing::debug!("Found {} private patches for skill {}", private_patches.len(), lookup_name);
@@ -133,6 +132,13 @@ pub async fn install_skill_directory(
         crate::skills::extract_skill_metadata_from_info(&dir_info, &source_dir)
             .with_context(|| format!("Invalid skill directory: {}", source_dir.display()))?;
 
+    let skill_md = tokio::fs::read_to_string(source_dir.join("SKILL.md")).await.unwrap_or_default();
+    super::check_min_agpm_version(
+        &skill_md,
+        entry,
+        context.manifest.map(|m| m.installer.min_version_policy).unwrap_or_default(),
+    )?;
+
     tracing::debug!(
         "Installing skill '{}' with {} files: {}",
         skill_frontmatter.name,
@@ -247,7 +253,8 @@ pub async fn compute_skill_directory_checksum(
         checksum_path.display()
     );
 
-    let checksum = LockFile::compute_directory_checksum(&checksum_path)?;
+    let normalize_eol = context.manifest.is_some_and(|m| m.integrity.normalize_eol);
+    let checksum = LockFile::compute_directory_checksum_with_eol(&checksum_path, normalize_eol)?;
     tracing::debug!(
         "Calculated directory checksum for skill {}: {} (from: {})",
         entry.name,
@@ -0,0 +1,114 @@
+//! Subset selection for `agpm install <name>...`.
+//!
+//! Installing by name restricts an installation to the named manifest
+//! dependencies and everything they transitively depend on. The selection is
+//! expressed as a [`ChangeSet`] so the regular `--changed-only` machinery
+//! installs just those resources, and the lockfile written afterwards keeps
+//! every other entry exactly as it was in the previous lockfile.
+
+use std::collections::HashSet;
+
+use crate::core::ResourceType;
+use crate::lockfile::{LockFile, LockedResource, ResourceId};
+
+use super::changed::ChangeSet;
+
+/// Entries for the named manifest dependencies and their transitive closure.
+///
+/// A name matches an entry's manifest alias (direct and pattern dependencies)
+/// or its canonical name, the same way `agpm update <name>` selects entries.
+#[must_use]
+pub fn select_named_resources(lockfile: &LockFile, names: &[String]) -> HashSet<ResourceId> {
+    let mut selected = HashSet::new();
+    let mut pending: Vec<&LockedResource> = lockfile
+        .all_resources()
+        .into_iter()
+        .filter(|entry| {
+            names.iter().any(|name| {
+                entry.manifest_alias.as_deref() == Some(name.as_str()) || entry.name == *name
+            })
+        })
+        .collect();
+
+    while let Some(entry) = pending.pop() {
+        if !selected.insert(entry.id()) {
+            continue;
+        }
+        for dep in entry.parsed_dependencies() {
+            let candidates = lockfile.get_resources(&dep.resource_type);
+            let mut targets: Vec<_> = candidates
+                .iter()
+                .filter(|r| r.name == dep.path && r.source == dep.source)
+                .collect();
+            if targets.is_empty() {
+                targets = candidates.iter().filter(|r| r.name == dep.path).collect();
+            }
+            pending.extend(targets);
+        }
+    }
+    selected
+}
+
+/// Build the lockfile for a subset installation and the resources to install.
+///
+/// Selected entries come from the freshly resolved `lockfile`; all other
+/// entries, and the fast-path metadata, are kept from `baseline`. Previously
+/// selected entries that no longer resolve at all are dropped so their files
+/// are cleaned up.
+#[must_use]
+pub fn merge_subset(
+    lockfile: &LockFile,
+    baseline: Option<&LockFile>,
+    names: &[String],
+) -> (LockFile, ChangeSet) {
+    let selected = select_named_resources(lockfile, names);
+    let mut merged = baseline.cloned().unwrap_or_else(LockFile::new);
+    let stale = baseline.map(|b| select_named_resources(b, names)).unwrap_or_default();
+
+    let same_slot = |a: &LockedResource, b: &LockedResource| {
+        a.name == b.name && a.source == b.source && a.tool == b.tool
+    };
+
+    let mut changes = ChangeSet::default();
+    for resource_type in ResourceType::all() {
+        let resolved = lockfile.get_resources(resource_type);
+        let fresh: Vec<LockedResource> =
+            resolved.iter().filter(|entry| selected.contains(&entry.id())).cloned().collect();
+        let entries = merged.get_resources_mut(resource_type);
+        let before = entries.len();
+        entries.retain(|old| {
+            let replaced = fresh.iter().any(|new| same_slot(old, new));
+            let dropped =
+                stale.contains(&old.id()) && !resolved.iter().any(|new| same_slot(old, new));
+            !replaced && !dropped
+        });
+
+        let touched = !fresh.is_empty() || entries.len() < before;
+        match resource_type {
+            ResourceType::Hook => changes.hooks_changed |= touched,
+            ResourceType::McpServer => changes.mcp_servers_changed |= touched,
+            _ => {
+                changes.unchanged_count += entries.len();
+                changes.changed.extend(fresh.iter().map(LockedResource::id));
+            }
+        }
+        entries.extend(fresh);
+    }
+
+    // Selected entries may come from newly fetched sources
+    let used: HashSet<&str> = lockfile
+        .all_resources()
+        .into_iter()
+        .filter(|entry| selected.contains(&entry.id()))
+        .filter_map(|entry| entry.source.as_deref())
+        .collect();
+    for source in lockfile.sources.iter().filter(|s| used.contains(s.name.as_str())) {
+        match merged.sources.iter_mut().find(|s| s.name == source.name) {
+            Some(existing) => existing.clone_from(source),
+            None => merged.sources.push(source.clone()),
+        }
+    }
+    merged.resource_count = Some(merged.all_resources().len());
+
+    (merged, changes)
+}
@@ -0,0 +1,95 @@
+//! Aggregated progress for large installs.
+//!
+//! Instead of a message per resource, installation progress is summarized in
+//! one line grouped by resource type, e.g.
+//! `installed 120/500 (agents 80, snippets 40)`. On a terminal the line
+//! replaces the text of the live install counter. When stderr is not a
+//! terminal the live display is not drawn, so the summary is printed as a log
+//! line at every tenth of the install instead, keeping CI logs short while
+//! still showing that a long install is moving.
+//!
+//! Nothing is reported when progress is disabled (`--quiet`).
+
+use std::collections::BTreeMap;
+
+use crate::core::ResourceType;
+
+/// Running count of installed resources, grouped by type.
+#[derive(Debug)]
+pub(crate) struct InstallSummary {
+    total: usize,
+    installed: usize,
+    by_type: BTreeMap<ResourceType, usize>,
+    /// Print a log line every this many resources; `None` for a live display
+    every: Option<usize>,
+}
+
+impl InstallSummary {
+    /// Start a summary of `total` resources, updated live or logged periodically.
+    pub(crate) fn new(total: usize, live: bool) -> Self {
+        Self {
+            total,
+            installed: 0,
+            by_type: BTreeMap::new(),
+            every: (!live).then(|| total.div_ceil(10).max(1)),
+        }
+    }
+
+    /// Whether the summary updates a live display rather than printing log lines.
+    pub(crate) fn is_live(&self) -> bool {
+        self.every.is_none()
+    }
+
+    /// Count an installed resource and return the summary if it should be shown.
+    ///
+    /// A live summary is always returned. A logged one is returned at each
+    /// interval and for the last resource.
+    pub(crate) fn record(&mut self, resource_type: ResourceType) -> Option<String> {
+        self.installed += 1;
+        *self.by_type.entry(resource_type).or_insert(0) += 1;
+
+        match self.every {
+            Some(every) if self.installed % every != 0 && self.installed != self.total => None,
+            _ => Some(self.line()),
+        }
+    }
+
+    /// The summary line, e.g. `installed 120/500 (agents 80, snippets 40)`.
+    pub(crate) fn line(&self) -> String {
+        let by_type: Vec<String> = self
+            .by_type
+            .iter()
+            .map(|(resource_type, count)| format!("{} {count}", resource_type.to_plural()))
+            .collect();
+        format!("installed {}/{} ({})", self.installed, self.total, by_type.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_summary_logs_periodically() {
+        let mut summary = InstallSummary::new(25, false);
+        let lines: Vec<String> = (0..25)
+            .filter_map(|i| {
+                let resource_type = if i % 5 == 0 {
+                    ResourceType::Snippet
+                } else {
+                    ResourceType::Agent
+                };
+                summary.record(resource_type)
+            })
+            .collect();
+
+        // Every third resource, plus the last one
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[0], "installed 3/25 (agents 2, snippets 1)");
+        assert_eq!(lines.last().unwrap(), "installed 25/25 (agents 20, snippets 5)");
+
+        let mut live = InstallSummary::new(25, true);
+        assert!(live.is_live());
+        assert_eq!(live.record(ResourceType::Agent).as_deref(), Some("installed 1/25 (agents 1)"));
+    }
+}
@@ -0,0 +1,110 @@
+//! Symlink installation for local resources.
+//!
+//! With `installer.install_mode = "symlink"` in `agpm.toml` (or `agpm install
+//! --symlink`), resources from local path dependencies and local directory
+//! sources are installed as symlinks to their source files, so edits are
+//! visible without re-running `agpm install`.
+//!
+//! A symlink is only used when the installed content would be identical to the
+//! source file. Resources that are patched, rendered as templates or given a
+//! header banner are copied, as are all Git and archive sources. On Windows,
+//! where creating symlinks needs Developer Mode or elevated privileges, the
+//! installer falls back to copying with a warning.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::lockfile::LockedResource;
+
+/// Source file to link for a local `entry`, or `None` for remote sources.
+pub(super) fn local_source_path(entry: &LockedResource, project_dir: &Path) -> Option<PathBuf> {
+    if !entry.is_local() {
+        return None;
+    }
+
+    let path = match (&entry.source, &entry.url) {
+        (Some(_), Some(url)) => PathBuf::from(url).join(&entry.path),
+        (Some(_), None) => return None,
+        (None, _) if Path::new(&entry.path).is_absolute() => PathBuf::from(&entry.path),
+        (None, _) => project_dir.join(&entry.path),
+    };
+    // Link to an absolute path so the link keeps working wherever it is read from
+    path.canonicalize().ok()
+}
+
+/// Returns `true` if `dest` is a symlink pointing at `source`.
+pub(super) fn is_link_to(dest: &Path, source: &Path) -> bool {
+    std::fs::read_link(dest).is_ok_and(|target| target == source)
+}
+
+/// Replace `dest` with a symlink to `source`.
+///
+/// Returns `Ok(Some(changed))` when `dest` is a link to `source` afterwards, or
+/// `Ok(None)` when symlinks are unavailable and the caller should copy instead.
+pub(super) fn link_resource(dest: &Path, source: &Path) -> Result<Option<bool>> {
+    if is_link_to(dest, source) {
+        return Ok(Some(false));
+    }
+
+    if let Some(parent) = dest.parent() {
+        crate::utils::fs::ensure_dir(parent)?;
+    }
+
+    // Create the link next to the destination, then rename it over the old file
+    let file_name = dest.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let temp = dest.with_file_name(format!(".{file_name}.agpm-link"));
+    let _ = std::fs::remove_file(&temp);
+
+    if let Err(e) = create_symlink(source, &temp) {
+        if cfg!(windows) {
+            tracing::warn!(
+                "Cannot create symlink {} ({e}); copying instead. Enable Developer Mode to allow symlinks.",
+                dest.display()
+            );
+            return Ok(None);
+        }
+        return Err(e).with_context(|| {
+            format!("Failed to create symlink {} -> {}", dest.display(), source.display())
+        });
+    }
+
+    std::fs::rename(&temp, dest).with_context(|| {
+        let _ = std::fs::remove_file(&temp);
+        format!("Failed to install symlink at {}", dest.display())
+    })?;
+    Ok(Some(true))
+}
+
+#[cfg(unix)]
+fn create_symlink(source: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(source, link)
+}
+
+#[cfg(windows)]
+fn create_symlink(source: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(source, link)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_link_resource_replaces_copy() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source.md");
+        std::fs::write(&source, "# Source").unwrap();
+        let dest = temp.path().join("installed/agent.md");
+        std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
+        std::fs::write(&dest, "# Old copy").unwrap();
+
+        assert_eq!(link_resource(&dest, &source).unwrap(), Some(true));
+        assert!(is_link_to(&dest, &source));
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), "# Source");
+
+        // An existing link is left alone
+        assert_eq!(link_resource(&dest, &source).unwrap(), Some(false));
+    }
+}
@@ -2,8 +2,8 @@
 mod installer_tests {
     use crate::cache::Cache;
     use crate::installer::{
-        InstallContext, ResourceFilter, install_resource, install_resource_with_progress,
-        install_resources, install_updated_resources,
+        InstallContext, ResourceFilter, collect_install_entries, install_resource,
+        install_resource_with_progress, install_resources, install_updated_resources,
     };
     use crate::lockfile::{LockFile, LockedResource};
     use crate::manifest::Manifest;
@@ -35,6 +35,7 @@ mod installer_tests {
                 variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
                 is_private: false,
                 approximate_token_count: None,
+                deprecated: false,
             }
         } else {
             LockedResource {
@@ -56,6 +57,7 @@ mod installer_tests {
                 variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
                 is_private: false,
                 approximate_token_count: None,
+                deprecated: false,
             }
         }
     }
@@ -268,6 +270,45 @@ mod installer_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_collect_install_entries_orders_by_priority() -> Result<()> {
+        let manifest: Manifest = toml::from_str(
+            r#"
+[agents]
+a-agent = "agents/a-agent.md"
+
+[scripts]
+lint = { path = "scripts/lint.sh", priority = 10 }
+format = "scripts/format.sh"
+
+[hooks]
+pre-lint = "hooks/pre-lint.json"
+"#,
+        )?;
+
+        let mut lockfile = LockFile::new();
+        for (name, resource_type) in [
+            ("a-agent", crate::core::ResourceType::Agent),
+            ("format", crate::core::ResourceType::Script),
+            ("lint", crate::core::ResourceType::Script),
+            ("pre-lint", crate::core::ResourceType::Hook),
+        ] {
+            let mut entry = create_test_locked_resource(name, true);
+            entry.resource_type = resource_type;
+            entry.manifest_alias = Some(name.to_string());
+            lockfile.get_resources_mut(&resource_type).push(entry);
+        }
+
+        let order: Vec<_> = collect_install_entries(&ResourceFilter::All, &lockfile, &manifest)
+            .into_iter()
+            .map(|(entry, _)| entry.name)
+            .collect();
+        // The prioritized script comes first and the rest keep type-then-name
+        // order; hooks are merged into settings after all files are written
+        assert_eq!(order, ["lint", "a-agent", "format"]);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_install_updated_resources() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -544,6 +585,7 @@ mod should_skip_trusted_tests {
             variant_inputs: VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         }
     }
 
@@ -567,6 +609,7 @@ mod should_skip_trusted_tests {
             variant_inputs: VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         }
     }
 
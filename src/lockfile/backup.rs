@@ -0,0 +1,204 @@
+//! Timestamped backups of `agpm.lock` for `agpm update --rollback`.
+//!
+//! Whenever `agpm install` or `agpm update` is about to replace `agpm.lock`
+//! with different content, the previous file is copied to
+//! `.agpm/backups/lockfiles/agpm.lock.<UTC timestamp>`. Timestamps sort
+//! lexically, so the newest backup is the last file name. Only the newest
+//! `lockfile_backups` files (global config, default
+//! [`DEFAULT_LOCKFILE_BACKUPS`]) are kept; `0` disables backups.
+//!
+//! `agpm update --rollback` restores the newest backup and removes it, so
+//! rolling back repeatedly steps further back in history.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::LockFile;
+use crate::utils::fs::atomic_write;
+
+/// Backup directory, relative to the project root.
+pub const BACKUP_DIR: &str = ".agpm/backups/lockfiles";
+
+/// Number of lockfile backups kept when `lockfile_backups` is not configured.
+pub const DEFAULT_LOCKFILE_BACKUPS: usize = 5;
+
+const BACKUP_PREFIX: &str = "agpm.lock.";
+
+/// Lockfile backups in `project_dir`, oldest first.
+///
+/// # Errors
+///
+/// Returns an error if the backup directory exists but cannot be read.
+pub fn list_backups(project_dir: &Path) -> Result<Vec<PathBuf>> {
+    let dir = project_dir.join(BACKUP_DIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut backups: Vec<PathBuf> = fs::read_dir(&dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(BACKUP_PREFIX))
+        })
+        .collect();
+    backups.sort();
+    Ok(backups)
+}
+
+impl LockFile {
+    /// Back up the lockfile at `path` if saving `self` would change it.
+    ///
+    /// Formatting differences are ignored: the existing file is compared after
+    /// being rendered the way [`LockFile::save`] writes it. Keeps the newest
+    /// `keep` backups and returns the path of the new one, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backup cannot be written or old backups cannot
+    /// be removed.
+    pub fn backup_before_save(
+        &self,
+        path: &Path,
+        project_dir: &Path,
+        keep: usize,
+    ) -> Result<Option<PathBuf>> {
+        if keep == 0 {
+            return Ok(None);
+        }
+        let Ok(existing) = fs::read_to_string(path) else {
+            return Ok(None);
+        };
+        let new = self.to_file_content()?;
+        let render = |text: &str| {
+            toml::from_str::<LockFile>(text)
+                .ok()
+                .and_then(|lockfile| lockfile.to_file_content().ok())
+        };
+        if existing == new || render(&existing).is_some_and(|old| Some(old) == render(&new)) {
+            return Ok(None);
+        }
+
+        let dir = project_dir.join(BACKUP_DIR);
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        let timestamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.6fZ");
+        let backup = dir.join(format!("{BACKUP_PREFIX}{timestamp}"));
+        atomic_write(&backup, existing.as_bytes())
+            .with_context(|| format!("Failed to back up lockfile to {}", backup.display()))?;
+
+        let backups = list_backups(project_dir)?;
+        for old in &backups[..backups.len().saturating_sub(keep)] {
+            fs::remove_file(old)
+                .with_context(|| format!("Failed to remove old backup {}", old.display()))?;
+        }
+        Ok(Some(backup))
+    }
+}
+
+/// Replace `agpm.lock` in `project_dir` with the newest backup and remove it.
+///
+/// Returns the path the backup was restored from, or `None` if there is none.
+///
+/// # Errors
+///
+/// Returns an error if the backup cannot be read, is not a valid lockfile, or
+/// `agpm.lock` cannot be written.
+pub fn restore_latest_backup(project_dir: &Path) -> Result<Option<PathBuf>> {
+    let Some(latest) = list_backups(project_dir)?.pop() else {
+        return Ok(None);
+    };
+    LockFile::load(&latest)
+        .with_context(|| format!("Backup {} is not a valid lockfile", latest.display()))?;
+    let content =
+        fs::read(&latest).with_context(|| format!("Failed to read backup {}", latest.display()))?;
+    let lockfile_path = project_dir.join("agpm.lock");
+    atomic_write(&lockfile_path, &content)
+        .with_context(|| format!("Failed to restore {}", lockfile_path.display()))?;
+    fs::remove_file(&latest)
+        .with_context(|| format!("Failed to remove backup {}", latest.display()))?;
+    Ok(Some(latest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ResourceType;
+    use crate::lockfile::LockedResourceBuilder;
+    use tempfile::TempDir;
+
+    fn lockfile_at(commit: &str) -> LockFile {
+        let agent = LockedResourceBuilder::new(
+            "reviewer".to_string(),
+            "agents/reviewer.md".to_string(),
+            String::new(),
+            ".claude/agents/reviewer.md".to_string(),
+            ResourceType::Agent,
+        )
+        .source(Some("community".to_string()))
+        .resolved_commit(Some(commit.to_string()))
+        .build();
+        LockFile {
+            agents: vec![agent],
+            ..LockFile::new()
+        }
+    }
+
+    #[test]
+    fn test_backup_only_when_content_changes() -> Result<()> {
+        let temp = TempDir::new()?;
+        let path = temp.path().join("agpm.lock");
+
+        // Nothing to back up before the first save
+        let first = lockfile_at("a".repeat(40).as_str());
+        assert_eq!(first.backup_before_save(&path, temp.path(), 5)?, None);
+        first.save(&path)?;
+
+        assert_eq!(first.backup_before_save(&path, temp.path(), 5)?, None);
+
+        let second = lockfile_at("b".repeat(40).as_str());
+        let backup = second.backup_before_save(&path, temp.path(), 5)?.unwrap();
+        assert_eq!(fs::read_to_string(backup)?, fs::read_to_string(&path)?);
+
+        // Disabled with keep = 0
+        assert_eq!(second.backup_before_save(&path, temp.path(), 0)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_old_backups_are_pruned() -> Result<()> {
+        let temp = TempDir::new()?;
+        let path = temp.path().join("agpm.lock");
+        for (i, commit) in ["a", "b", "c", "d"].iter().enumerate() {
+            let lockfile = lockfile_at(commit.repeat(40).as_str());
+            lockfile.backup_before_save(&path, temp.path(), 2)?;
+            lockfile.save(&path)?;
+            assert!(list_backups(temp.path())?.len() <= 2, "iteration {i}");
+        }
+
+        let backups = list_backups(temp.path())?;
+        assert_eq!(backups.len(), 2);
+        assert!(fs::read_to_string(&backups[0])?.contains(&"b".repeat(40)));
+        assert!(fs::read_to_string(&backups[1])?.contains(&"c".repeat(40)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_restore_latest_backup_consumes_it() -> Result<()> {
+        let temp = TempDir::new()?;
+        let path = temp.path().join("agpm.lock");
+        assert_eq!(restore_latest_backup(temp.path())?, None);
+
+        let old = lockfile_at("a".repeat(40).as_str());
+        old.save(&path)?;
+        let new = lockfile_at("b".repeat(40).as_str());
+        new.backup_before_save(&path, temp.path(), 5)?;
+        new.save(&path)?;
+
+        assert!(restore_latest_backup(temp.path())?.is_some());
+        assert!(fs::read_to_string(&path)?.contains(&"a".repeat(40)));
+        assert!(list_backups(temp.path())?.is_empty());
+        Ok(())
+    }
+}
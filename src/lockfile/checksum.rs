@@ -56,7 +56,9 @@ impl LockFile {
     /// - Uses SHA-256, a cryptographically secure hash function
     /// - Suitable for integrity verification and tamper detection
     /// - Consistent across platforms (Windows, macOS, Linux)
-    /// - Not affected by line ending differences (hashes actual bytes)
+    /// - Hashes the file exactly as stored; see
+    ///   [`compute_checksum_with_eol`](Self::compute_checksum_with_eol) to ignore
+    ///   banners or line endings
     ///
     /// # Performance
     ///
@@ -64,19 +66,48 @@ impl LockFile {
     /// For very large files (>100MB), consider streaming implementations
     /// in future versions.
     pub fn compute_checksum(path: &Path) -> Result<String> {
+        Self::compute_checksum_with_eol(path, false, false)
+    }
+
+    /// Compute a file checksum, removing the banner and footer added by
+    /// `installer.header` / `installer.footer` when `strip_banners` is set, and
+    /// hashing CRLF line endings as LF when `normalize_eol` is set
+    /// (`integrity.normalize_eol`).
+    ///
+    /// Otherwise identical to [`compute_checksum`](Self::compute_checksum).
+    pub fn compute_checksum_with_eol(
+        path: &Path,
+        strip_banners: bool,
+        normalize_eol: bool,
+    ) -> Result<String> {
         use sha2::{Digest, Sha256};
 
-        let content = fs::read(path).with_context(|| {
-            format!(
-                "Cannot read file for checksum calculation: {}\n\n\
+        let content =
+            fs::read(crate::utils::platform::windows_long_path(path)).with_context(|| {
+                format!(
+                    "Cannot read file for checksum calculation: {}\n\n\
                     This error occurs when verifying file integrity.\n\
                     Check that the file exists and is readable.",
-                path.display()
-            )
-        })?;
+                    path.display()
+                )
+            })?;
 
         let mut hasher = Sha256::new();
-        hasher.update(&content);
+        match std::str::from_utf8(&content) {
+            Ok(text) => {
+                let text = if strip_banners {
+                    crate::installer::strip_banners(text)
+                } else {
+                    text.into()
+                };
+                if normalize_eol {
+                    hasher.update(crate::installer::normalize_eol(&text).as_bytes());
+                } else {
+                    hasher.update(text.as_bytes());
+                }
+            }
+            Err(_) => hasher.update(&content),
+        }
         let result = hasher.finalize();
 
         Ok(format!("sha256:{}", hex::encode(result)))
@@ -118,6 +149,14 @@ impl LockFile {
     /// # }
     /// ```
     pub fn compute_directory_checksum(path: &Path) -> Result<String> {
+        Self::compute_directory_checksum_with_eol(path, false)
+    }
+
+    /// Compute a directory checksum, hashing CRLF line endings in its files
+    /// as LF when `normalize_eol` is set (`integrity.normalize_eol`).
+    ///
+    /// Otherwise identical to [`compute_directory_checksum`](Self::compute_directory_checksum).
+    pub fn compute_directory_checksum_with_eol(path: &Path, normalize_eol: bool) -> Result<String> {
         use sha2::{Digest, Sha256};
         use walkdir::WalkDir;
 
@@ -135,7 +174,9 @@ impl LockFile {
                     file_path.strip_prefix(path).unwrap_or(file_path),
                 );
 
-                let file_checksum = Self::compute_checksum(file_path)?;
+                // Skill directories never get banners, so they are hashed as stored
+                let file_checksum =
+                    Self::compute_checksum_with_eol(file_path, false, normalize_eol)?;
                 file_hashes.push((relative_path, file_checksum));
             }
         }
@@ -0,0 +1,217 @@
+//! Differences between two lockfiles.
+//!
+//! Used by `agpm install --since <lockfile>` and `agpm update --since <lockfile>`
+//! to explain how the resolved lockfile differs from an older one, for example
+//! the version of `agpm.lock` on a pull request's base branch.
+
+use serde::Serialize;
+
+use crate::core::ResourceType;
+use crate::core::resource_iterator::ResourceIterator;
+use crate::lockfile::{LockFile, LockedResource};
+
+/// How a resource differs between the old and the new lockfile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// Only present in the new lockfile.
+    Added,
+    /// Only present in the old lockfile.
+    Removed,
+    /// Present in both, at a different version, commit or checksum.
+    Changed,
+}
+
+/// One resource that differs between two lockfiles.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LockfileChange {
+    /// Type of the resource.
+    pub resource_type: ResourceType,
+    /// Lockfile name of the resource.
+    pub name: String,
+    /// Source the resource comes from, `None` for local resources.
+    pub source: Option<String>,
+    /// Whether the resource was added, removed or changed.
+    pub kind: ChangeKind,
+    /// Version in the old lockfile.
+    pub old_version: Option<String>,
+    /// Version in the new lockfile.
+    pub new_version: Option<String>,
+    /// Resolved commit in the old lockfile.
+    pub old_commit: Option<String>,
+    /// Resolved commit in the new lockfile.
+    pub new_commit: Option<String>,
+}
+
+/// All differences between two lockfiles, ordered by resource type and name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LockfileDiff {
+    /// Added, removed and changed resources.
+    pub changes: Vec<LockfileChange>,
+}
+
+impl LockfileDiff {
+    /// Compare `new` against `old`.
+    ///
+    /// Resources are matched by type, name and source. A matched resource is
+    /// changed when its version or resolved commit differs, or, for local
+    /// resources without a commit, when both checksums are known and differ.
+    #[must_use]
+    pub fn between(old: &LockFile, new: &LockFile) -> Self {
+        let mut changes = Vec::new();
+
+        ResourceIterator::for_each_resource(new, |resource_type, new_entry| {
+            match find_entry(old, resource_type, new_entry) {
+                Some(old_entry) if is_changed(old_entry, new_entry) => {
+                    changes.push(change(resource_type, ChangeKind::Changed, old_entry, new_entry));
+                }
+                Some(_) => {}
+                None => {
+                    changes.push(change(resource_type, ChangeKind::Added, new_entry, new_entry));
+                }
+            }
+        });
+        ResourceIterator::for_each_resource(old, |resource_type, old_entry| {
+            if find_entry(new, resource_type, old_entry).is_none() {
+                changes.push(change(resource_type, ChangeKind::Removed, old_entry, old_entry));
+            }
+        });
+
+        changes.sort_by(|a, b| {
+            a.resource_type
+                .to_plural()
+                .cmp(b.resource_type.to_plural())
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.source.cmp(&b.source))
+        });
+        Self {
+            changes,
+        }
+    }
+
+    /// Whether the two lockfiles have the same resources.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+fn find_entry<'a>(
+    lockfile: &'a LockFile,
+    resource_type: ResourceType,
+    entry: &LockedResource,
+) -> Option<&'a LockedResource> {
+    lockfile
+        .get_resources(&resource_type)
+        .iter()
+        .find(|e| e.name == entry.name && e.source == entry.source && e.tool == entry.tool)
+}
+
+fn is_changed(old: &LockedResource, new: &LockedResource) -> bool {
+    if old.version != new.version || old.resolved_commit != new.resolved_commit {
+        return true;
+    }
+    new.resolved_commit.is_none()
+        && !old.checksum.is_empty()
+        && !new.checksum.is_empty()
+        && old.checksum != new.checksum
+}
+
+fn change(
+    resource_type: ResourceType,
+    kind: ChangeKind,
+    old: &LockedResource,
+    new: &LockedResource,
+) -> LockfileChange {
+    let (old, new) = match kind {
+        ChangeKind::Added => (None, Some(new)),
+        ChangeKind::Removed => (Some(old), None),
+        ChangeKind::Changed => (Some(old), Some(new)),
+    };
+    let entry = new.or(old).expect("a change has at least one side");
+    LockfileChange {
+        resource_type,
+        name: entry.name.clone(),
+        source: entry.source.clone(),
+        kind,
+        old_version: old.and_then(|e| e.version.clone()),
+        new_version: new.and_then(|e| e.version.clone()),
+        old_commit: old.and_then(|e| e.resolved_commit.clone()),
+        new_commit: new.and_then(|e| e.resolved_commit.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::LockedResourceBuilder;
+
+    fn locked(resource_type: ResourceType, name: &str, version: &str) -> LockedResource {
+        LockedResourceBuilder::new(
+            name.to_string(),
+            format!("{name}.md"),
+            String::new(),
+            format!(".claude/{name}.md"),
+            resource_type,
+        )
+        .source(Some("community".to_string()))
+        .version(Some(version.to_string()))
+        .resolved_commit(Some(format!("{version}-sha")))
+        .build()
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_and_changed() {
+        let mut old = LockFile::new();
+        old.agents.push(locked(ResourceType::Agent, "agents/reviewer", "v1.0.0"));
+        old.agents.push(locked(ResourceType::Agent, "agents/stable", "v1.0.0"));
+        old.commands.push(locked(ResourceType::Command, "commands/deploy", "v1.0.0"));
+
+        let mut new = LockFile::new();
+        new.agents.push(locked(ResourceType::Agent, "agents/reviewer", "v1.1.0"));
+        new.agents.push(locked(ResourceType::Agent, "agents/stable", "v1.0.0"));
+        new.snippets.push(locked(ResourceType::Snippet, "snippets/style", "v2.0.0"));
+
+        let diff = LockfileDiff::between(&old, &new);
+        let summary: Vec<_> = diff
+            .changes
+            .iter()
+            .map(|c| (c.kind, c.name.as_str(), c.old_version.as_deref(), c.new_version.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ChangeKind::Changed, "agents/reviewer", Some("v1.0.0"), Some("v1.1.0")),
+                (ChangeKind::Removed, "commands/deploy", Some("v1.0.0"), None),
+                (ChangeKind::Added, "snippets/style", None, Some("v2.0.0")),
+            ]
+        );
+        assert!(LockfileDiff::between(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_diff_compares_checksums_of_local_resources() {
+        let local = |checksum: &str| {
+            LockedResourceBuilder::new(
+                "snippets/local".to_string(),
+                "snippets/local.md".to_string(),
+                checksum.to_string(),
+                ".claude/snippets/local.md".to_string(),
+                ResourceType::Snippet,
+            )
+            .build()
+        };
+        let lockfile = |checksum: &str| {
+            let mut lockfile = LockFile::new();
+            lockfile.snippets.push(local(checksum));
+            lockfile
+        };
+
+        let diff = LockfileDiff::between(&lockfile("sha256:aaa"), &lockfile("sha256:bbb"));
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].kind, ChangeKind::Changed);
+
+        // An unknown checksum is not a change
+        assert!(LockfileDiff::between(&lockfile("sha256:aaa"), &lockfile("")).is_empty());
+    }
+}
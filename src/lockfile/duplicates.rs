@@ -0,0 +1,111 @@
+//! Installed resources with byte-identical content.
+//!
+//! Used by `agpm install --report-duplicates` to point out dependencies that
+//! install the same content under different names, which are candidates for
+//! consolidation. The checksums recorded in the lockfile are compared, so no
+//! installed file is read again.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::core::ResourceType;
+use crate::core::resource_iterator::ResourceIterator;
+use crate::lockfile::LockFile;
+
+/// One installed resource in a [`DuplicateGroup`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DuplicateResource {
+    /// Type of the resource.
+    pub resource_type: ResourceType,
+    /// Manifest name of the resource, or its lockfile name for transitive dependencies.
+    pub name: String,
+    /// Source the resource comes from, `None` for local resources.
+    pub source: Option<String>,
+    /// Path the resource is installed to, relative to the project.
+    pub installed_at: String,
+}
+
+/// Installed resources that share a checksum.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DuplicateGroup {
+    /// Checksum shared by every resource in the group.
+    pub checksum: String,
+    /// The resources, ordered by install path.
+    pub resources: Vec<DuplicateResource>,
+}
+
+/// Group the installed resources of `lockfile` by checksum.
+///
+/// Only groups with at least two install paths are returned, ordered by
+/// checksum. Entries without a checksum, entries not installed
+/// (`install = false` or no install path), and hooks and MCP servers, which
+/// are merged into shared configuration files, are ignored.
+#[must_use]
+pub fn find_duplicates(lockfile: &LockFile) -> Vec<DuplicateGroup> {
+    let mut by_checksum: BTreeMap<String, Vec<DuplicateResource>> = BTreeMap::new();
+
+    ResourceIterator::for_each_resource(lockfile, |resource_type, entry| {
+        if matches!(resource_type, ResourceType::Hook | ResourceType::McpServer)
+            || entry.checksum.is_empty()
+            || entry.installed_at.is_empty()
+            || entry.install == Some(false)
+        {
+            return;
+        }
+        by_checksum.entry(entry.checksum.clone()).or_default().push(DuplicateResource {
+            resource_type,
+            name: entry.manifest_alias.clone().unwrap_or_else(|| entry.name.clone()),
+            source: entry.source.clone(),
+            installed_at: entry.installed_at.clone(),
+        });
+    });
+
+    by_checksum
+        .into_iter()
+        .filter_map(|(checksum, mut resources)| {
+            resources.sort_by(|a, b| a.installed_at.cmp(&b.installed_at));
+            resources.dedup_by(|a, b| a.installed_at == b.installed_at);
+            (resources.len() > 1).then_some(DuplicateGroup {
+                checksum,
+                resources,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::{LockedResource, LockedResourceBuilder};
+
+    fn locked(resource_type: ResourceType, name: &str, checksum: &str) -> LockedResource {
+        LockedResourceBuilder::new(
+            name.to_string(),
+            format!("{name}.md"),
+            checksum.to_string(),
+            format!(".claude/{name}.md"),
+            resource_type,
+        )
+        .build()
+    }
+
+    #[test]
+    fn test_find_duplicates_groups_by_checksum() {
+        let mut lockfile = LockFile::new();
+        lockfile.agents.push(locked(ResourceType::Agent, "agents/reviewer", "sha256:aaa"));
+        lockfile.agents.push(locked(ResourceType::Agent, "agents/unique", "sha256:bbb"));
+        lockfile.commands.push(locked(ResourceType::Command, "commands/review", "sha256:aaa"));
+        lockfile.snippets.push(locked(ResourceType::Snippet, "snippets/pending", ""));
+        lockfile.snippets.push(locked(ResourceType::Snippet, "snippets/other", ""));
+        let mut skipped = locked(ResourceType::Snippet, "snippets/skipped", "sha256:aaa");
+        skipped.install = Some(false);
+        lockfile.snippets.push(skipped);
+
+        let groups = find_duplicates(&lockfile);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].checksum, "sha256:aaa");
+        let paths: Vec<_> = groups[0].resources.iter().map(|r| r.installed_at.as_str()).collect();
+        assert_eq!(paths, vec![".claude/agents/reviewer.md", ".claude/commands/review.md"]);
+    }
+}
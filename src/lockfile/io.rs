@@ -240,13 +240,7 @@ impl LockFile {
     /// fetched_at = "2024-01-15T10:30:00Z"
     /// ```
     pub fn save(&self, path: &Path) -> Result<()> {
-        // Normalize lockfile for backward compatibility before saving
-        let normalized = self.normalize();
-
-        // Use toml_edit to ensure applied_patches are formatted as inline tables
-        let mut content = String::from("# Auto-generated lockfile - DO NOT EDIT\n");
-        let toml_content = serialize_lockfile_with_inline_patches(&normalized)?;
-        content.push_str(&toml_content);
+        let content = self.to_file_content()?;
 
         atomic_write(path, content.as_bytes()).with_context(|| {
             format!(
@@ -262,4 +256,33 @@ impl LockFile {
 
         Ok(())
     }
+
+    /// Save the lockfile only if its content differs from the file on disk.
+    ///
+    /// Returns `true` if the file was written. Used by `install --changed-only`
+    /// to leave an up-to-date lockfile untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`LockFile::save`].
+    pub fn save_if_changed(&self, path: &Path) -> Result<bool> {
+        let content = self.to_file_content()?;
+        if fs::read_to_string(path).is_ok_and(|existing| existing == content) {
+            return Ok(false);
+        }
+        self.save(path)?;
+        Ok(true)
+    }
+
+    /// Render the lockfile exactly as [`LockFile::save`] writes it.
+    pub(super) fn to_file_content(&self) -> Result<String> {
+        // Normalize lockfile for backward compatibility before saving
+        let normalized = self.normalize();
+
+        // Use toml_edit to ensure applied_patches are formatted as inline tables
+        let mut content = String::from("# Auto-generated lockfile - DO NOT EDIT\n");
+        let toml_content = serialize_lockfile_with_inline_patches(&normalized)?;
+        content.push_str(&toml_content);
+        Ok(content)
+    }
 }
@@ -1042,6 +1042,16 @@ pub struct LockedResource {
     /// Omitted from TOML serialization when `None`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub approximate_token_count: Option<u64>,
+
+    /// Whether the installed resource is marked deprecated in its frontmatter.
+    ///
+    /// Set during installation from the `deprecated` frontmatter field so that
+    /// `agpm list` and `agpm validate` can surface resources that should be
+    /// migrated away from.
+    ///
+    /// Omitted from TOML serialization when false (the default).
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub deprecated: bool,
 }
 
 /// Helper function for serde skip_serializing_if on bool fields.
@@ -1072,6 +1082,7 @@ pub struct LockedResourceBuilder {
     variant_inputs: crate::resolver::lockfile_builder::VariantInputs,
     is_private: bool,
     approximate_token_count: Option<u64>,
+    deprecated: bool,
 }
 
 impl LockedResourceBuilder {
@@ -1102,6 +1113,7 @@ impl LockedResourceBuilder {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         }
     }
 
@@ -1186,6 +1198,12 @@ impl LockedResourceBuilder {
         self
     }
 
+    /// Set the deprecated flag.
+    pub fn deprecated(mut self, deprecated: bool) -> Self {
+        self.deprecated = deprecated;
+        self
+    }
+
     /// Build the LockedResource.
     pub fn build(self) -> LockedResource {
         LockedResource {
@@ -1207,6 +1225,7 @@ impl LockedResourceBuilder {
             variant_inputs: self.variant_inputs,
             is_private: self.is_private,
             approximate_token_count: self.approximate_token_count,
+            deprecated: self.deprecated,
         }
     }
 }
@@ -1436,10 +1455,14 @@ impl LockedResource {
 }
 
 // Submodules for organized implementation
+pub mod backup;
 mod checksum;
+pub mod diff;
+pub mod duplicates;
 mod helpers;
 mod io;
 pub mod lockfile_dependency_ref;
+mod preserve;
 pub mod private_lock;
 mod resource_ops;
 mod validation;
@@ -1530,6 +1553,8 @@ impl LockFile {
         normalized.scripts.sort_by(Self::compare_resources);
         normalized.hooks.sort_by(Self::compare_resources);
         normalized.mcp_servers.sort_by(Self::compare_resources);
+        normalized.skills.sort_by(Self::compare_resources);
+        normalized.sources.sort_by(|a, b| a.name.cmp(&b.name));
 
         normalized
     }
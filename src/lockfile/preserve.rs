@@ -0,0 +1,333 @@
+//! Structure-preserving lockfile writes for targeted updates.
+//!
+//! [`LockFile::save`] renders the whole lockfile from scratch, which is the
+//! right thing for installs but turns `agpm update <name>` on a large lockfile
+//! into a noisy diff whenever entries come back in a different order. The
+//! writer in this module instead edits the existing `agpm.lock` in place:
+//!
+//! - Entries whose content is unchanged keep their exact bytes, including any
+//!   surrounding comments and their position.
+//! - Changed entries are updated where they stand, keeping their comments.
+//! - Removed entries are dropped and new entries are appended to their section.
+//!
+//! Entries are matched by `name`, `source` and `tool`; contents are compared
+//! as TOML values, so formatting differences alone never cause a rewrite.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table};
+
+use super::LockFile;
+
+/// Keys identifying an entry within its section.
+const IDENTITY_KEYS: &[&str] = &["name", "source", "tool"];
+
+impl LockFile {
+    /// Save the lockfile, rewriting only the entries that differ from the
+    /// lockfile already at `path`.
+    ///
+    /// Falls back to [`LockFile::save`] when there is no readable lockfile to
+    /// preserve.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`LockFile::save`].
+    pub fn save_preserving(&self, path: &Path) -> Result<()> {
+        let content = self.to_file_content()?;
+        let Ok(existing) = fs::read_to_string(path) else {
+            return self.save(path);
+        };
+        let Ok(merged) = merge_preserving(&existing, &content) else {
+            return self.save(path);
+        };
+        if merged == existing {
+            return Ok(());
+        }
+        crate::utils::fs::atomic_write(path, merged.as_bytes())
+            .with_context(|| format!("Cannot write lockfile: {}", path.display()))
+    }
+}
+
+/// Apply the content of the lockfile text `new` to the lockfile text `old`,
+/// keeping everything in `old` that did not change.
+pub(super) fn merge_preserving(old: &str, new: &str) -> Result<String> {
+    let mut doc: DocumentMut = old.parse().context("Failed to parse existing lockfile")?;
+    let new_doc: DocumentMut = new.parse().context("Failed to parse new lockfile")?;
+
+    let removed: Vec<String> = doc
+        .iter()
+        .filter(|(key, _)| !new_doc.contains_key(key))
+        .map(|(key, _)| key.to_string())
+        .collect();
+    for key in removed {
+        doc.remove(&key);
+    }
+
+    for (key, new_item) in new_doc.iter() {
+        match doc.get_mut(key) {
+            Some(Item::ArrayOfTables(entries)) if new_item.is_array_of_tables() => {
+                let new_entries = new_item.as_array_of_tables().expect("checked above");
+                merge_entries(entries, new_entries)?;
+            }
+            Some(item) => update_item(item, new_item, max_position(item))?,
+            None => {
+                let end = max_position(doc.as_item());
+                doc.insert(key, positioned(new_item.clone(), end));
+            }
+        }
+    }
+
+    Ok(doc.to_string())
+}
+
+/// Merge one `[[section]]` array: keep unchanged entries, update changed
+/// ones in place, drop removed ones and append new ones.
+fn merge_entries(entries: &mut ArrayOfTables, new_entries: &ArrayOfTables) -> Result<()> {
+    let old_ids: Vec<Vec<String>> = entries.iter().map(identity).collect();
+    let mut matched = vec![None; old_ids.len()];
+    let mut appended = Vec::new();
+
+    for new_entry in new_entries.iter() {
+        let id = identity(new_entry);
+        match (0..old_ids.len()).find(|&i| matched[i].is_none() && old_ids[i] == id) {
+            Some(i) => matched[i] = Some(new_entry),
+            None => appended.push(new_entry),
+        }
+    }
+
+    // New entries are rendered after everything already in the section
+    let end = entries.iter().map(|entry| max_position(&Item::Table(entry.clone()))).max();
+    let mut merged = ArrayOfTables::new();
+    for (mut entry, new_entry) in entries.iter().cloned().zip(matched) {
+        let Some(new_entry) = new_entry else {
+            continue;
+        };
+        let position = entry.position().unwrap_or_default();
+        update_table(&mut entry, new_entry, position)?;
+        merged.push(entry);
+    }
+    for new_entry in appended {
+        let position = end.unwrap_or_default();
+        merged.push(positioned_table(new_entry.clone(), position));
+    }
+
+    *entries = merged;
+    Ok(())
+}
+
+fn identity(entry: &Table) -> Vec<String> {
+    IDENTITY_KEYS
+        .iter()
+        .map(|key| entry.get(key).and_then(Item::as_str).unwrap_or_default().to_string())
+        .collect()
+}
+
+/// Make `table` hold the data of `new`, touching only keys that differ.
+///
+/// Sub-tables added to `table` are rendered at `position`.
+fn update_table(table: &mut Table, new: &Table, position: isize) -> Result<()> {
+    let removed: Vec<String> = table
+        .iter()
+        .filter(|(key, _)| !new.contains_key(key))
+        .map(|(key, _)| key.to_string())
+        .collect();
+    for key in removed {
+        table.remove(&key);
+    }
+    for (key, new_item) in new.iter() {
+        match table.get_mut(key) {
+            Some(item) => update_item(item, new_item, position)?,
+            None => {
+                table.insert(key, positioned(new_item.clone(), position));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn update_item(item: &mut Item, new: &Item, position: isize) -> Result<()> {
+    if same_item(item, new)? {
+        return Ok(());
+    }
+    match (item, new) {
+        (Item::Value(value), Item::Value(new_value)) => {
+            // Keep the whitespace and comments around the replaced value
+            let decor = value.decor().clone();
+            *value = new_value.clone();
+            *value.decor_mut() = decor;
+        }
+        (Item::Table(table), Item::Table(new_table)) => {
+            let position = table.position().unwrap_or(position);
+            update_table(table, new_table, position)?;
+        }
+        (item, new) => *item = positioned(new.clone(), position),
+    }
+    Ok(())
+}
+
+/// Compare two items as data, ignoring formatting and comments.
+fn same_item(a: &Item, b: &Item) -> Result<bool> {
+    let as_value = |item: &Item| -> Result<toml::Value> {
+        let mut doc = DocumentMut::new();
+        doc.insert("item", item.clone());
+        toml::from_str(&doc.to_string()).context("Failed to compare lockfile entries")
+    };
+    Ok(as_value(a)? == as_value(b)?)
+}
+
+/// The highest document position of any table in `item`.
+fn max_position(item: &Item) -> isize {
+    match item {
+        Item::Table(table) => table
+            .iter()
+            .map(|(_, child)| max_position(child))
+            .fold(table.position().unwrap_or_default(), isize::max),
+        Item::ArrayOfTables(array) => array
+            .iter()
+            .map(|table| max_position(&Item::Table(table.clone())))
+            .max()
+            .unwrap_or_default(),
+        _ => 0,
+    }
+}
+
+/// Place every table in an item taken from another document at `position`.
+///
+/// Tables sharing a position keep their order, so the item is rendered
+/// right after the tables already at that position.
+fn positioned(item: Item, position: isize) -> Item {
+    match item {
+        Item::Table(table) => Item::Table(positioned_table(table, position)),
+        Item::ArrayOfTables(array) => {
+            let mut positioned_array = ArrayOfTables::new();
+            for table in array {
+                positioned_array.push(positioned_table(table, position));
+            }
+            Item::ArrayOfTables(positioned_array)
+        }
+        other => other,
+    }
+}
+
+fn positioned_table(mut table: Table, position: isize) -> Table {
+    table.set_position(position);
+    for (_, item) in table.iter_mut() {
+        let owned = std::mem::replace(item, Item::None);
+        *item = positioned(owned, position);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = r#"# Auto-generated lockfile - DO NOT EDIT
+version = 1
+
+[[sources]]
+name = "community"
+url = "https://github.com/org/community.git"
+fetched_at = "2025-01-01T00:00:00Z"
+
+# Pinned for the release
+[[agents]]
+name = "reviewer"
+source = "community"
+version = "v1.0.0"
+resolved_commit = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+applied_patches = {}
+
+[agents.variant_inputs]
+
+[[agents]]
+name = "writer"
+source = "community"
+version = "v1.0.0"
+resolved_commit = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+applied_patches = {}
+
+[agents.variant_inputs]
+
+[[snippets]]
+name = "style"
+source = "community"
+version = "v1.0.0"
+"#;
+
+    #[test]
+    fn test_merge_preserving_updates_only_changed_entry() {
+        // Canonical rendering with entries in a different order and one update
+        let new = r#"version = 1
+
+[[sources]]
+name = "community"
+url = "https://github.com/org/community.git"
+fetched_at = "2025-01-01T00:00:00Z"
+
+[[agents]]
+name = "writer"
+source = "community"
+version = "v2.0.0"
+resolved_commit = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
+applied_patches = {}
+
+[agents.variant_inputs]
+
+[[agents]]
+name = "reviewer"
+source = "community"
+version = "v1.0.0"
+resolved_commit = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+applied_patches = {}
+
+[agents.variant_inputs]
+
+[[snippets]]
+name = "style"
+source = "community"
+version = "v1.0.0"
+"#;
+        let merged = merge_preserving(OLD, new).unwrap();
+        let expected = OLD.replacen(
+            "name = \"writer\"\nsource = \"community\"\nversion = \"v1.0.0\"\nresolved_commit = \"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\"",
+            "name = \"writer\"\nsource = \"community\"\nversion = \"v2.0.0\"\nresolved_commit = \"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\"",
+            1,
+        );
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn test_merge_preserving_adds_and_removes_entries() {
+        let new = r#"version = 1
+
+[[agents]]
+name = "reviewer"
+source = "community"
+version = "v1.0.0"
+resolved_commit = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+applied_patches = {}
+
+[agents.variant_inputs]
+
+[[agents]]
+name = "planner"
+source = "community"
+version = "v1.0.0"
+applied_patches = {}
+"#;
+        let merged = merge_preserving(OLD, new).unwrap();
+        assert!(merged.starts_with("# Auto-generated lockfile - DO NOT EDIT\n"));
+        assert!(merged.contains("# Pinned for the release\n[[agents]]\nname = \"reviewer\""));
+        assert!(!merged.contains("writer"));
+        assert!(!merged.contains("[[sources]]"));
+        assert!(!merged.contains("[[snippets]]"));
+
+        let parsed: toml::Value = toml::from_str(&merged).unwrap();
+        let expected: toml::Value = toml::from_str(new).unwrap();
+        assert_eq!(parsed, expected);
+        let planner = merged.find("name = \"planner\"").unwrap();
+        assert!(planner > merged.find("name = \"reviewer\"").unwrap());
+    }
+}
@@ -227,6 +227,26 @@ impl PrivateLockFile {
         Ok(())
     }
 
+    /// Save to disk only if the content differs from the existing file.
+    ///
+    /// Returns `true` if the file was written or removed.
+    pub fn save_if_changed(&self, project_dir: &Path) -> Result<bool> {
+        let path = project_dir.join(PRIVATE_LOCK_FILENAME);
+
+        let unchanged = if self.is_empty() {
+            !path.exists()
+        } else {
+            let content = serialize_private_lockfile(self)?;
+            std::fs::read_to_string(&path).is_ok_and(|existing| existing == content)
+        };
+        if unchanged {
+            return Ok(false);
+        }
+
+        self.save(project_dir)?;
+        Ok(true)
+    }
+
     /// Check if the lockfile has any private resources.
     pub fn is_empty(&self) -> bool {
         self.agents.is_empty()
@@ -358,6 +378,7 @@ mod tests {
             variant_inputs: VariantInputs::default(),
             is_private: true,
             approximate_token_count: None,
+            deprecated: false,
         }
     }
 
@@ -466,6 +487,7 @@ mod tests {
             variant_inputs: VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         };
 
         // Add private agent
@@ -488,6 +510,7 @@ mod tests {
             variant_inputs: VariantInputs::default(),
             is_private: true,
             approximate_token_count: None,
+            deprecated: false,
         };
 
         lockfile.agents.push(public_agent);
@@ -532,6 +555,7 @@ mod tests {
             variant_inputs: VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
         public_lock.resource_count = Some(1);
 
@@ -578,6 +602,7 @@ mod tests {
             variant_inputs: VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
         original.agents.push(LockedResource {
             name: "private".to_string(),
@@ -598,6 +623,7 @@ mod tests {
             variant_inputs: VariantInputs::default(),
             is_private: true,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         // Split
@@ -4,7 +4,7 @@
 //! manifests, detect corruption, and identify when lockfiles need regeneration.
 
 use anyhow::Result;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use super::{LockFile, StalenessReason};
@@ -99,7 +99,9 @@ impl LockFile {
                         // Find matching resource in lockfile
                         let locked_resource = self.get_resource(name);
 
-                        if locked_resource.is_none() {
+                        // Types disabled in [install] may have been pruned from the lockfile
+                        if locked_resource.is_none() && manifest.install.is_enabled(*resource_type)
+                        {
                             // Dependency is in manifest but not in lockfile
                             return Ok(Some(StalenessReason::MissingDependency {
                                 name: name.clone(),
@@ -122,7 +124,18 @@ impl LockFile {
                             }
 
                             // Check for path changes
-                            if dep.get_path() != locked.path {
+                            // Paths redirected by `[path-aliases]` are locked at their actual path
+                            let expected_path = dep
+                                .get_source()
+                                .and_then(|source| {
+                                    manifest.aliased_path(
+                                        source,
+                                        dep.get_path(),
+                                        locked.version.as_deref(),
+                                    )
+                                })
+                                .unwrap_or(dep.get_path());
+                            if expected_path != locked.path {
                                 return Ok(Some(StalenessReason::PathChanged {
                                     name: name.clone(),
                                     resource_type: *resource_type,
@@ -138,7 +151,10 @@ impl LockFile {
                                 .unwrap_or_else(|| manifest.get_default_tool(*resource_type));
                             let manifest_tool = manifest_tool_string.as_str();
                             let locked_tool = locked.tool.as_deref().unwrap_or("claude-code");
-                            if manifest_tool != locked_tool {
+                            // A defaulted tool may have been replaced by the resource's frontmatter
+                            if manifest_tool != locked_tool
+                                && !manifest.is_tool_defaulted(*resource_type, name)
+                            {
                                 return Ok(Some(StalenessReason::ToolChanged {
                                     name: name.clone(),
                                     resource_type: *resource_type,
@@ -193,22 +209,25 @@ impl LockFile {
 
     /// Detect duplicate entries indicating corruption.
     ///
-    /// Scans all resource types for duplicate names.
+    /// Scans all resource types for entries with the same identity (name, source,
+    /// tool and variant inputs). Same-named resources from different sources are
+    /// distinct, e.g. when `on_conflict = "rename"` installs both.
     pub(crate) fn detect_duplicate_entries(&self) -> Result<Option<StalenessReason>> {
         // Check each resource type for duplicates
         for resource_type in crate::core::ResourceType::all() {
             let resources = self.get_resources(resource_type);
-            let mut seen_names = HashMap::new();
+            let mut seen_ids = HashSet::new();
 
             for resource in resources {
-                if seen_names.contains_key(&resource.name) {
+                let id = resource.id();
+                if seen_ids.contains(&id) {
                     return Ok(Some(StalenessReason::DuplicateEntries {
                         name: resource.name.clone(),
                         resource_type: *resource_type,
-                        count: resources.iter().filter(|r| r.name == resource.name).count(),
+                        count: resources.iter().filter(|r| r.matches_id(&id)).count(),
                     }));
                 }
-                seen_names.insert(&resource.name, 0);
+                seen_ids.insert(id);
             }
         }
 
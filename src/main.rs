@@ -15,10 +15,12 @@
 //! - `remove` - Remove sources or dependencies from manifest
 
 use agpm_cli::cli;
-use agpm_cli::core::error_formatting::user_friendly_error;
+use agpm_cli::core::error_formatting::{json_error, user_friendly_error};
+use agpm_cli::utils::log_file::{Redacting, RotatingFileWriter};
 use anyhow::Result;
 use clap::Parser;
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::prelude::*;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -49,23 +51,51 @@ async fn main() -> Result<()> {
         EnvFilter::new("off")
     };
 
-    tracing_subscriber::fmt()
-        .with_env_filter(filter)
+    let console_layer = tracing_subscriber::fmt::layer()
+        .with_writer(Redacting(std::io::stdout))
         .with_target(false) // Don't show the module path in logs
         .with_thread_ids(false) // Don't show thread IDs
-        .init();
+        .with_filter(filter);
 
-    // Set up colored output for Windows
-    #[cfg(windows)]
-    colored::control::set_virtual_terminal(true).ok();
+    // --log-file adds a second layer that logs at debug level (or RUST_LOG)
+    // regardless of the console verbosity
+    let file_layer = config.log_file.as_ref().and_then(|path| {
+        match RotatingFileWriter::open(path, config.log_rotate) {
+            Ok(writer) => {
+                let file_filter = if rust_log_exists {
+                    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug"))
+                } else {
+                    EnvFilter::new("debug")
+                };
+                Some(
+                    tracing_subscriber::fmt::layer()
+                        .with_writer(Redacting(writer))
+                        .with_ansi(false)
+                        .with_filter(file_filter),
+                )
+            }
+            Err(e) => {
+                eprintln!("Warning: cannot open log file {}: {e}", path.display());
+                None
+            }
+        }
+    });
+
+    tracing_subscriber::registry().with(console_layer).with(file_layer).init();
 
-    // Execute the command (execute_with_config will apply the rest of the config)
+    // Execute the command (execute_with_config will apply the rest of the config,
+    // including --color, which also enables ANSI support on Windows consoles)
+    let json_errors = config.json_errors;
     match cli.execute_with_config(config).await {
         Ok(()) => Ok(()),
         Err(e) => {
-            // Convert to user-friendly error with context and suggestions
-            let error_ctx = user_friendly_error(e);
-            error_ctx.display();
+            if json_errors {
+                eprintln!("{}", json_error(e));
+            } else {
+                // Convert to user-friendly error with context and suggestions
+                let error_ctx = user_friendly_error(e);
+                error_ctx.display();
+            }
             std::process::exit(1);
         }
     }
@@ -3,6 +3,7 @@
 //! This module defines the structures used to represent transitive dependencies
 //! that resources can declare within their files (via YAML frontmatter or JSON fields).
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -11,7 +12,7 @@ use std::collections::BTreeMap;
 /// Used within resource files to declare dependencies on other resources
 /// from the same source repository. The source is implicit and inherited
 /// from the resource that declares the dependency.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct DependencySpec {
     /// Path to the dependency file within the source repository.
@@ -0,0 +1,162 @@
+//! Environment-specific manifest overlays (`agpm.<env>.toml`).
+//!
+//! An environment overlay lives next to `agpm.toml` and is selected with the
+//! global `--env` flag or the `AGPM_ENV` environment variable. Overlays follow
+//! the same rules as private manifests:
+//!
+//! - **Sources** are merged (overlay sources shadow project sources with the same name)
+//! - **Dependencies** are merged (overlay entries replace project entries with the same name)
+//! - **Default tools** are merged before tool defaults are applied
+//! - **Patches** are merged (overlay patches take precedence over project patches)
+//! - **Tools** cannot be defined in an overlay
+//!
+//! Private configuration (`agpm.private.toml`) is applied after the overlay and
+//! therefore still takes precedence.
+
+use crate::manifest::{Manifest, PatchConflict};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+impl Manifest {
+    /// Returns the overlay path for an environment, next to the project manifest.
+    ///
+    /// For `env = "staging"` and `path = "/project/agpm.toml"` this is
+    /// `/project/agpm.staging.toml`.
+    #[must_use]
+    pub fn env_overlay_path(path: &Path, env: &str) -> PathBuf {
+        let file_name = format!("agpm.{env}.toml");
+        match path.parent() {
+            Some(parent) => parent.join(file_name),
+            None => PathBuf::from(file_name),
+        }
+    }
+
+    /// Load the project manifest, merging the overlay for `env` when one is selected.
+    ///
+    /// Unlike [`Self::load_with_overlays`], `agpm.private.toml` is not merged,
+    /// so read-only commands that inspect the project manifest see the same
+    /// dependencies as before, plus the selected environment's.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be loaded, or if `env` is set
+    /// and [`Self::load_with_env`] fails.
+    pub fn load_for_env(path: &Path, env: Option<&str>) -> Result<Self> {
+        match env {
+            Some(env) => Ok(Self::load_with_env(path, env)?.0),
+            None => Self::load(path),
+        }
+    }
+
+    /// Load the project manifest with an environment overlay merged on top.
+    ///
+    /// The overlay must exist: selecting an environment without a matching
+    /// `agpm.<env>.toml` is an error rather than a silent no-op. Project patches
+    /// and overlay patches are tracked separately in `project_patches` and
+    /// `env_patches`, and overlay dependency names in `env_dependency_names`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the project manifest file (`agpm.toml`)
+    /// * `env` - Environment name (letters, digits, `-` and `_`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The environment name is invalid or reserved (`private`)
+    /// - The overlay file does not exist or cannot be parsed
+    /// - The overlay contains a `[tools]` section
+    /// - The merged manifest fails validation
+    pub fn load_with_env(path: &Path, env: &str) -> Result<(Self, Vec<PatchConflict>)> {
+        validate_env_name(env)?;
+
+        let overlay_path = Self::env_overlay_path(path, env);
+        if !overlay_path.exists() {
+            anyhow::bail!(
+                "Environment '{env}' was selected but {} does not exist",
+                overlay_path.display()
+            );
+        }
+
+        let mut manifest = Self::parse_file(path, "manifest")?;
+        let overlay = Self::parse_file(&overlay_path, "environment manifest")?;
+
+        if overlay.tools.is_some() {
+            anyhow::bail!(
+                "Environment manifest file ({}) cannot contain [tools] section. \
+                 Tool configuration must be defined in the project manifest (agpm.toml).",
+                overlay_path.display()
+            );
+        }
+
+        // Store project patches before merging
+        manifest.project_patches = manifest.patches.clone();
+
+        for (name, url) in overlay.sources {
+            manifest.sources.insert(name, url);
+        }
+        manifest.path_overrides.extend(overlay.path_overrides);
+        manifest.default_versions.extend(overlay.default_versions);
+        manifest.tag_patterns.extend(overlay.tag_patterns);
+        manifest.path_aliases.extend(overlay.path_aliases);
+
+        // Merge default tools before applying them so overlays can retarget project deps
+        for (resource_type, tool) in overlay.default_tools {
+            manifest.default_tools.insert(resource_type, tool);
+        }
+
+        let mut env_names = manifest.merge_overlay_dependencies(
+            overlay.agents,
+            overlay.snippets,
+            overlay.commands,
+            overlay.scripts,
+            overlay.hooks,
+            overlay.mcp_servers,
+        );
+        for (name, dep) in overlay.skills {
+            env_names.insert(("skills".to_string(), name.clone()));
+            manifest.skills.insert(name, dep);
+        }
+        manifest.env_dependency_names = env_names;
+
+        manifest.env_patches = overlay.patches.clone();
+        let (merged_patches, conflicts) = manifest.patches.merge_with(&overlay.patches);
+        manifest.patches = merged_patches;
+
+        manifest.env = Some(env.to_string());
+        manifest.apply_tool_defaults();
+
+        manifest.validate().with_context(|| {
+            format!(
+                "Validation failed after merging environment manifest: {}",
+                overlay_path.display()
+            )
+        })?;
+
+        Ok((manifest, conflicts))
+    }
+
+    /// Check if a dependency was added or overridden by the environment overlay.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource_type` - Plural resource type as used in TOML sections (e.g. `"agents"`)
+    /// * `name` - The dependency name as specified in the manifest
+    #[must_use]
+    pub fn is_env_dependency(&self, resource_type: &str, name: &str) -> bool {
+        self.env_dependency_names.contains(&(resource_type.to_string(), name.to_string()))
+    }
+}
+
+/// Ensure an environment name maps to a safe, unambiguous overlay filename.
+fn validate_env_name(env: &str) -> Result<()> {
+    if env.is_empty() || !env.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        anyhow::bail!("Invalid environment name '{env}': use only letters, digits, '-' and '_'");
+    }
+    if env == "private" {
+        anyhow::bail!(
+            "Environment name 'private' is reserved for agpm.private.toml, choose another name"
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,183 @@
+//! Tests for environment-specific manifest overlays (`agpm.<env>.toml`).
+
+use crate::manifest::Manifest;
+use anyhow::Result;
+use tempfile::tempdir;
+
+const PROJECT_TOML: &str = r#"
+[sources]
+community = "https://github.com/example/community.git"
+
+[agents]
+reviewer = { source = "community", path = "agents/reviewer.md", version = "v1.0.0" }
+
+[patch.agents.reviewer]
+model = "claude-3-haiku"
+"#;
+
+#[test]
+fn test_env_overlay_changes_version_and_adds_source() -> Result<()> {
+    let temp = tempdir()?;
+    let manifest_path = temp.path().join("agpm.toml");
+    std::fs::write(&manifest_path, PROJECT_TOML)?;
+    std::fs::write(
+        temp.path().join("agpm.staging.toml"),
+        r#"
+[sources]
+staging = "https://github.com/example/staging.git"
+
+[agents]
+reviewer = { source = "community", path = "agents/reviewer.md", version = "v2.0.0" }
+canary = { source = "staging", path = "agents/canary.md", version = "v0.1.0" }
+
+[patch.agents.reviewer]
+model = "claude-3-opus"
+"#,
+    )?;
+
+    let (manifest, conflicts) = Manifest::load_with_overlays(&manifest_path, Some("staging"))?;
+
+    assert_eq!(manifest.env.as_deref(), Some("staging"));
+    assert!(manifest.sources.contains_key("community"));
+    assert!(manifest.sources.contains_key("staging"));
+    assert_eq!(manifest.agents["reviewer"].get_version(), Some("v2.0.0"));
+    assert!(manifest.has_dependency("canary"));
+
+    // Provenance tracking
+    assert!(manifest.is_env_dependency("agents", "reviewer"));
+    assert!(manifest.is_env_dependency("agents", "canary"));
+    assert!(!manifest.is_private_dependency("agents", "canary"));
+
+    // Overlay patches win and both layers are kept separately
+    let model = |patches: &crate::manifest::ManifestPatches| {
+        patches.get("agents", "reviewer").and_then(|p| p.get("model")).cloned()
+    };
+    assert_eq!(model(&manifest.patches), Some(toml::Value::from("claude-3-opus")));
+    assert_eq!(model(&manifest.project_patches), Some(toml::Value::from("claude-3-haiku")));
+    assert_eq!(model(&manifest.env_patches), Some(toml::Value::from("claude-3-opus")));
+    assert_eq!(conflicts.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_env_overlay_default_tools_apply_to_project_deps() -> Result<()> {
+    let temp = tempdir()?;
+    let manifest_path = temp.path().join("agpm.toml");
+    std::fs::write(&manifest_path, PROJECT_TOML)?;
+    std::fs::write(
+        temp.path().join("agpm.dev.toml"),
+        r#"
+[default-tools]
+agents = "opencode"
+"#,
+    )?;
+
+    let (manifest, _) = Manifest::load_with_overlays(&manifest_path, Some("dev"))?;
+    assert_eq!(manifest.agents["reviewer"].get_tool(), Some("opencode"));
+    Ok(())
+}
+
+#[test]
+fn test_env_overlay_precedes_private() -> Result<()> {
+    let temp = tempdir()?;
+    let manifest_path = temp.path().join("agpm.toml");
+    std::fs::write(&manifest_path, PROJECT_TOML)?;
+    std::fs::write(
+        temp.path().join("agpm.prod.toml"),
+        r#"
+[sources]
+community = "https://github.com/example/prod-mirror.git"
+"#,
+    )?;
+    std::fs::write(
+        temp.path().join("agpm.private.toml"),
+        r#"
+[sources]
+community = "https://github.com/me/fork.git"
+"#,
+    )?;
+
+    let (manifest, _) = Manifest::load_with_overlays(&manifest_path, Some("prod"))?;
+    assert_eq!(manifest.sources["community"], "https://github.com/me/fork.git");
+    Ok(())
+}
+
+#[test]
+fn test_env_overlay_without_env_is_ignored() -> Result<()> {
+    let temp = tempdir()?;
+    let manifest_path = temp.path().join("agpm.toml");
+    std::fs::write(&manifest_path, PROJECT_TOML)?;
+    std::fs::write(
+        temp.path().join("agpm.staging.toml"),
+        "[sources]\nstaging = \"https://github.com/example/staging.git\"\n",
+    )?;
+
+    let (manifest, _) = Manifest::load_with_private(&manifest_path)?;
+    assert!(manifest.env.is_none());
+    assert!(!manifest.sources.contains_key("staging"));
+    Ok(())
+}
+
+#[test]
+fn test_env_overlay_cannot_have_tools() -> Result<()> {
+    let temp = tempdir()?;
+    let manifest_path = temp.path().join("agpm.toml");
+    std::fs::write(&manifest_path, PROJECT_TOML)?;
+    std::fs::write(
+        temp.path().join("agpm.dev.toml"),
+        r#"
+[tools.claude-code]
+path = ".claude"
+
+[tools.claude-code.resources.agents]
+path = "agents"
+"#,
+    )?;
+
+    let err = Manifest::load_with_overlays(&manifest_path, Some("dev")).unwrap_err();
+    assert!(err.to_string().contains("[tools]"), "unexpected error: {err}");
+    Ok(())
+}
+
+#[test]
+fn test_env_overlay_missing_or_invalid_name() -> Result<()> {
+    let temp = tempdir()?;
+    let manifest_path = temp.path().join("agpm.toml");
+    std::fs::write(&manifest_path, PROJECT_TOML)?;
+
+    let err = Manifest::load_with_overlays(&manifest_path, Some("qa")).unwrap_err();
+    assert!(err.to_string().contains("agpm.qa.toml"), "unexpected error: {err}");
+
+    assert!(Manifest::load_with_overlays(&manifest_path, Some("../evil")).is_err());
+    assert!(Manifest::load_with_overlays(&manifest_path, Some("private")).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_load_for_env_skips_private_overlay() -> Result<()> {
+    let temp = tempdir()?;
+    let manifest_path = temp.path().join("agpm.toml");
+    std::fs::write(&manifest_path, PROJECT_TOML)?;
+    std::fs::write(
+        temp.path().join("agpm.staging.toml"),
+        r#"
+[agents]
+canary = { source = "community", path = "agents/canary.md", version = "v0.1.0" }
+"#,
+    )?;
+    std::fs::write(
+        temp.path().join("agpm.private.toml"),
+        r#"
+[agents]
+mine = { source = "community", path = "agents/mine.md", version = "v1.0.0" }
+"#,
+    )?;
+
+    let manifest = Manifest::load_for_env(&manifest_path, Some("staging"))?;
+    assert!(manifest.has_dependency("canary"));
+    assert!(!manifest.has_dependency("mine"));
+
+    let manifest = Manifest::load_for_env(&manifest_path, None)?;
+    assert!(!manifest.has_dependency("canary"));
+    Ok(())
+}
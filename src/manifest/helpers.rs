@@ -134,11 +134,17 @@ pub fn find_manifest() -> Result<PathBuf> {
 pub fn find_manifest_with_optional(explicit_path: Option<PathBuf>) -> Result<PathBuf> {
     match explicit_path {
         Some(path) => {
-            if path.exists() {
-                Ok(path)
-            } else {
-                Err(crate::core::AgpmError::ManifestNotFound.into())
+            // Make the path absolute so the project directory (lockfile, private
+            // manifest, installed resources) never depends on the working directory
+            let mut path = std::path::absolute(&path)
+                .with_context(|| format!("Invalid manifest path: {}", path.display()))?;
+            if path.is_dir() {
+                path.push("agpm.toml");
             }
+            if !path.is_file() {
+                anyhow::bail!("Manifest not found at {}", path.display());
+            }
+            Ok(path)
         }
         None => find_manifest(),
     }
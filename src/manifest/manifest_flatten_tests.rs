@@ -23,6 +23,11 @@ fn test_flatten_field_agents() -> Result<()> {
         flatten: Some(false), // Override default
         install: None,
         template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+        sha256: None,
+        on_conflict: None,
+        preserve_structure: None,
+        channel: None,
+        priority: None,
     }));
 
     assert_eq!(dep.get_flatten(), Some(false));
@@ -46,6 +51,11 @@ fn test_flatten_field_snippets() -> Result<()> {
         flatten: Some(true), // Override default
         install: None,
         template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+        sha256: None,
+        on_conflict: None,
+        preserve_structure: None,
+        channel: None,
+        priority: None,
     }));
 
     assert_eq!(dep.get_flatten(), Some(true));
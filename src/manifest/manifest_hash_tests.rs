@@ -23,6 +23,11 @@ fn detailed_dep(source: Option<&str>, path: &str, version: Option<&str>) -> Deta
         flatten: None,
         install: None,
         template_vars: None,
+        sha256: None,
+        on_conflict: None,
+        preserve_structure: None,
+        channel: None,
+        priority: None,
     }
 }
 
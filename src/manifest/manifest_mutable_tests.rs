@@ -29,6 +29,11 @@ fn detailed_dep_with_branch(
         flatten: None,
         install: None,
         template_vars: None,
+        sha256: None,
+        on_conflict: None,
+        preserve_structure: None,
+        channel: None,
+        priority: None,
     }
 }
 
@@ -286,6 +291,11 @@ fn test_compute_dependency_hash_is_deterministic() {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
     );
 
@@ -306,6 +316,11 @@ fn test_compute_dependency_hash_is_deterministic() {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
     );
 
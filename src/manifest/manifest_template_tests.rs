@@ -33,6 +33,11 @@ fn test_template_vars_in_dependency() {
         flatten: None,
         install: None,
         template_vars: Some(vars.clone()),
+        sha256: None,
+        on_conflict: None,
+        preserve_structure: None,
+        channel: None,
+        priority: None,
     }));
 
     assert_eq!(dep.get_template_vars(), Some(&vars));
@@ -90,6 +95,11 @@ fn test_template_vars_empty() {
         flatten: None,
         install: None,
         template_vars: None,
+        sha256: None,
+        on_conflict: None,
+        preserve_structure: None,
+        channel: None,
+        priority: None,
     }));
 
     assert_eq!(dep.get_template_vars(), None);
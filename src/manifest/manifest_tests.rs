@@ -3,7 +3,7 @@ mod tests {
 
     use crate::manifest::{
         DetailedDependency, Manifest, ProjectConfig, ResourceDependency, expand_url,
-        find_manifest_from, json_value_to_toml, toml_value_to_json,
+        find_manifest_from, find_manifest_with_optional, json_value_to_toml, toml_value_to_json,
     };
     use anyhow::Result;
 
@@ -47,6 +47,11 @@ mod tests {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
             true,
         );
@@ -91,6 +96,11 @@ mod tests {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
             true,
         );
@@ -126,6 +136,11 @@ mod tests {
             install: None,
 
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
         assert!(!detailed_dep.is_local());
         assert_eq!(detailed_dep.get_path(), "agents/test.md");
@@ -163,6 +178,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_find_manifest_explicit_path() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+        std::fs::write(&manifest_path, "[sources]\n")?;
+
+        let found = find_manifest_with_optional(Some(manifest_path.clone()))?;
+        assert_eq!(found, manifest_path);
+        // A directory means the manifest inside it
+        let found = find_manifest_with_optional(Some(temp.path().to_path_buf()))?;
+        assert_eq!(found, manifest_path);
+
+        let missing = find_manifest_with_optional(Some(temp.path().join("other.toml")));
+        assert!(format!("{:#}", missing.unwrap_err()).contains("other.toml"));
+        Ok(())
+    }
+
     #[test]
     fn test_expand_url_basic() -> Result<()> {
         let url = "https://github.com/example/repo.git";
@@ -529,6 +561,11 @@ agents = "opencode"
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
         assert!(detailed_dep.is_pattern());
     }
@@ -550,6 +587,11 @@ agents = "opencode"
             flatten: None, // Not specified
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
         // When not specified, get_flatten returns None
         assert_eq!(dep_with_default.get_flatten(), None);
@@ -572,6 +614,11 @@ agents = "opencode"
             flatten: Some(true),
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
         assert_eq!(dep_flatten_true.get_flatten(), Some(true));
 
@@ -590,6 +637,11 @@ agents = "opencode"
             flatten: Some(false),
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
         assert_eq!(dep_flatten_false.get_flatten(), Some(false));
     }
@@ -611,6 +663,11 @@ agents = "opencode"
             flatten: None,
             install: None, // Not specified - defaults to true
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
         assert_eq!(dep.get_install(), None); // Returns None when not specified
     }
@@ -632,6 +689,11 @@ agents = "opencode"
             flatten: None,
             install: Some(false), // Explicitly disabled
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
         assert_eq!(dep_install_false.get_install(), Some(false));
 
@@ -650,10 +712,223 @@ agents = "opencode"
             flatten: None,
             install: Some(true), // Explicitly enabled
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
         assert_eq!(dep_install_true.get_install(), Some(true));
     }
 
+    #[test]
+    fn test_install_toggles_filter_resource_types() {
+        let mut manifest: Manifest = toml::from_str(
+            r#"
+[install]
+hooks = false
+mcp-servers = false
+
+[agents]
+helper = "agents/helper.md"
+
+[hooks]
+guard = "hooks/guard.json"
+
+[mcp-servers]
+db = { path = "mcp/db.json" }
+"#,
+        )
+        .unwrap();
+        assert!(!manifest.install.is_default());
+        assert!(manifest.install.is_enabled(crate::core::ResourceType::Agent));
+        assert!(!manifest.install.is_enabled(crate::core::ResourceType::Hook));
+
+        // Disabled types are kept but marked install=false
+        let installs: Vec<_> = manifest
+            .all_dependencies_with_types()
+            .iter()
+            .map(|(name, dep, _)| (name.to_string(), dep.get_install()))
+            .collect();
+        assert_eq!(
+            installs,
+            vec![
+                ("helper".to_string(), None),
+                ("db".to_string(), Some(false)),
+                ("guard".to_string(), Some(false)),
+            ]
+        );
+        let hash = manifest.compute_dependency_hash();
+
+        // --prune-disabled drops them entirely
+        manifest.install.prune_disabled = true;
+        let names: Vec<_> =
+            manifest.all_dependencies_with_types().iter().map(|(name, _, _)| *name).collect();
+        assert_eq!(names, vec!["helper"]);
+        assert_ne!(manifest.compute_dependency_hash(), hash);
+
+        // Re-enabling everything leaves the hash as if [install] were absent
+        manifest.install = Default::default();
+        let mut plain = manifest.clone();
+        plain.install = toml::from_str("").unwrap();
+        assert_eq!(plain.compute_dependency_hash(), manifest.compute_dependency_hash());
+        assert!(!toml::to_string(&manifest).unwrap().contains("[install]"));
+    }
+
+    #[test]
+    fn test_transitive_policy_per_resource_type() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+[transitive]
+snippets = false
+"#,
+        )
+        .unwrap();
+        assert!(manifest.transitive.is_enabled(crate::core::ResourceType::Agent));
+        assert!(!manifest.transitive.is_enabled(crate::core::ResourceType::Snippet));
+
+        // Disabling a type changes the hash; the default policy is never serialized
+        let mut plain = manifest.clone();
+        plain.transitive = Default::default();
+        assert_ne!(plain.compute_dependency_hash(), manifest.compute_dependency_hash());
+        assert!(toml::to_string(&manifest).unwrap().contains("[transitive]"));
+        assert!(!toml::to_string(&plain).unwrap().contains("[transitive]"));
+    }
+
+    #[test]
+    fn test_transitive_dependency_field() {
+        let mut manifest: Manifest = toml::from_str(
+            r#"
+[transitive]
+field = "agpm_dependencies"
+"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.transitive.dependency_field(), "agpm_dependencies");
+        assert!(manifest.validate().is_ok());
+        assert_eq!(Manifest::new().transitive.dependency_field(), "dependencies");
+
+        let plain = Manifest::new();
+        assert_ne!(plain.compute_dependency_hash(), manifest.compute_dependency_hash());
+
+        for invalid in ["", "agpm", "my deps", "agpm.dependencies"] {
+            manifest.transitive.field = Some(invalid.to_string());
+            let err = manifest.validate().unwrap_err().to_string();
+            assert!(err.contains("Invalid [transitive] field"), "{invalid}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_transitive_restrict_sources() {
+        let mut manifest: Manifest = toml::from_str(
+            r#"
+[sources]
+community = "https://github.com/example/community.git"
+
+[transitive]
+restrict_sources = true
+"#,
+        )
+        .unwrap();
+        let policy = &manifest.transitive;
+        assert!(policy.permits_source("community", &manifest.sources));
+        assert!(!policy.permits_source("personal", &manifest.sources));
+        assert!(Manifest::new().transitive.permits_source("personal", &manifest.sources));
+
+        // An allowlist replaces the manifest's sources
+        manifest.transitive.allowed_sources = Some(vec!["personal".to_string()]);
+        let policy = &manifest.transitive;
+        assert!(policy.permits_source("personal", &manifest.sources));
+        assert!(!policy.permits_source("community", &manifest.sources));
+
+        let mut plain = manifest.clone();
+        plain.transitive = Default::default();
+        assert_ne!(plain.compute_dependency_hash(), manifest.compute_dependency_hash());
+        assert!(toml::to_string(&manifest).unwrap().contains("restrict_sources = true"));
+    }
+
+    #[test]
+    fn test_source_default_versions() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+[sources]
+community = "https://github.com/example/community.git"
+other = "https://github.com/example/other.git"
+
+[default-versions]
+community = "^2.0.0"
+
+[agents]
+unversioned = { source = "community", path = "agents/a.md" }
+pinned = { source = "community", path = "agents/b.md", version = "v1.0.0" }
+tracking = { source = "community", path = "agents/c.md", branch = "main" }
+elsewhere = { source = "other", path = "agents/d.md" }
+local = "../local/agent.md"
+"#,
+        )
+        .unwrap();
+        manifest.validate().unwrap();
+
+        let versions: std::collections::HashMap<_, _> = manifest
+            .all_dependencies_with_types()
+            .into_iter()
+            .map(|(name, dep, _)| (name, dep.get_version().map(str::to_string)))
+            .collect();
+        assert_eq!(versions["unversioned"].as_deref(), Some("^2.0.0"));
+        assert_eq!(versions["pinned"].as_deref(), Some("v1.0.0"));
+        assert_eq!(versions["tracking"].as_deref(), Some("main"));
+        assert_eq!(versions["elsewhere"], None);
+        assert_eq!(versions["local"], None);
+
+        // The manifest itself keeps the dependency unversioned
+        assert_eq!(manifest.agents["unversioned"].get_version(), None);
+
+        let mut plain = manifest.clone();
+        plain.default_versions.clear();
+        assert_ne!(plain.compute_dependency_hash(), manifest.compute_dependency_hash());
+    }
+
+    #[test]
+    fn test_source_default_versions_validation() {
+        let mut manifest = Manifest::new();
+        manifest.sources.insert("local".to_string(), "../shared".to_string());
+        manifest.sources.insert("git".to_string(), "file:///repos/shared.git".to_string());
+
+        manifest.default_versions.insert("git".to_string(), "^1.0.0".to_string());
+        manifest.validate().unwrap();
+
+        manifest.default_versions.insert("local".to_string(), "^1.0.0".to_string());
+        let err = manifest.validate().unwrap_err().to_string();
+        assert!(err.contains("local path source"), "{err}");
+
+        manifest.default_versions.clear();
+        manifest.default_versions.insert("missing".to_string(), "^1.0.0".to_string());
+        let err = manifest.validate().unwrap_err().to_string();
+        assert!(err.contains("does not match any source"), "{err}");
+    }
+
+    #[test]
+    fn test_tag_patterns_validation() {
+        let mut manifest = Manifest::new();
+        manifest.sources.insert("local".to_string(), "../shared".to_string());
+        manifest.sources.insert("git".to_string(), "file:///repos/shared.git".to_string());
+
+        manifest.tag_patterns.insert("git".to_string(), "release/{version}".to_string());
+        manifest.validate().unwrap();
+        let mut plain = manifest.clone();
+        plain.tag_patterns.clear();
+        assert_ne!(plain.compute_dependency_hash(), manifest.compute_dependency_hash());
+
+        manifest.tag_patterns.insert("git".to_string(), "release/".to_string());
+        let err = manifest.validate().unwrap_err().to_string();
+        assert!(err.contains("must contain {version}"), "{err}");
+
+        manifest.tag_patterns.clear();
+        manifest.tag_patterns.insert("local".to_string(), "release/{version}".to_string());
+        let err = manifest.validate().unwrap_err().to_string();
+        assert!(err.contains("local path source"), "{err}");
+    }
+
     #[test]
     fn test_get_template_vars() {
         let dep_no_vars = ResourceDependency::Detailed(Box::new(DetailedDependency {
@@ -671,6 +946,11 @@ agents = "opencode"
             flatten: None,
             install: None,
             template_vars: None,
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
         assert_eq!(dep_no_vars.get_template_vars(), None);
 
@@ -695,6 +975,11 @@ agents = "opencode"
             flatten: None,
             install: None,
             template_vars: Some(vars.clone()),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
         assert_eq!(dep_with_vars.get_template_vars(), Some(&vars));
     }
@@ -796,6 +1081,104 @@ shared = "git@github.com:me/private.git"
         Ok(())
     }
 
+    #[test]
+    fn test_load_with_private_merges_default_versions() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+        std::fs::write(
+            &manifest_path,
+            r#"
+[sources]
+community = "https://github.com/example/community.git"
+other = "https://github.com/example/other.git"
+
+[default-versions]
+community = "^1.0.0"
+"#,
+        )?;
+        std::fs::write(
+            temp.path().join("agpm.private.toml"),
+            r#"
+[default-versions]
+community = "^2.0.0"
+other = "main"
+"#,
+        )?;
+
+        let (manifest, _conflicts) = Manifest::load_with_private(&manifest_path)?;
+        assert_eq!(manifest.default_versions["community"], "^2.0.0");
+        assert_eq!(manifest.default_versions["other"], "main");
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_with_private_merges_tag_patterns() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+        std::fs::write(
+            &manifest_path,
+            r#"
+[sources]
+community = "https://github.com/example/community.git"
+other = "https://github.com/example/other.git"
+
+[tag-patterns]
+community = "release/{version}"
+"#,
+        )?;
+        std::fs::write(
+            temp.path().join("agpm.private.toml"),
+            r#"
+[tag-patterns]
+community = "rel-{version}"
+other = "v{version}-stable"
+"#,
+        )?;
+
+        let (manifest, _conflicts) = Manifest::load_with_private(&manifest_path)?;
+        assert_eq!(manifest.tag_patterns["community"], "rel-{version}");
+        assert_eq!(manifest.tag_patterns["other"], "v{version}-stable");
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_with_private_merges_path_aliases() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+        std::fs::write(
+            &manifest_path,
+            r#"
+[sources]
+community = "https://github.com/example/community.git"
+other = "https://github.com/example/other.git"
+
+[path-aliases.community]
+"agents/reviewer.md" = "agents/review/reviewer.md"
+"#,
+        )?;
+        std::fs::write(
+            temp.path().join("agpm.private.toml"),
+            r#"
+[path-aliases.community]
+"agents/reviewer.md" = "agents/quality/reviewer.md"
+
+[path-aliases.other]
+"agents/linter.md" = "agents/lint/linter.md"
+"#,
+        )?;
+
+        let (manifest, _conflicts) = Manifest::load_with_private(&manifest_path)?;
+        assert_eq!(
+            manifest.aliased_path("community", "agents/reviewer.md", None),
+            Some("agents/quality/reviewer.md")
+        );
+        assert_eq!(
+            manifest.aliased_path("other", "agents/linter.md", None),
+            Some("agents/lint/linter.md")
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_private_manifest_cannot_have_tools() {
         let temp = tempdir().unwrap();
@@ -128,6 +128,35 @@ fn test_get_merge_target_mcp_servers() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_install_root_remaps_tool_paths() -> Result<()> {
+    let mut manifest: Manifest = toml::from_str(
+        r#"
+[tools.claude-code]
+path = ".claude-custom"
+resources = { agents = { path = "agents/agpm" }, hooks = { merge-target = ".claude-custom/settings.local.json" }, mcp-servers = { merge-target = ".mcp.json" } }
+"#,
+    )?;
+    manifest.install_root = Some(PathBuf::from("/build/out"));
+
+    let agents = manifest
+        .get_artifact_resource_path("claude-code", crate::core::ResourceType::Agent)
+        .ok_or_else(|| anyhow::anyhow!("Path should exist for claude-code agents"))?;
+    assert_eq!(agents, PathBuf::from("/build/out/.claude-custom/agents/agpm"));
+
+    let hooks = manifest.get_merge_target("claude-code", crate::core::ResourceType::Hook);
+    assert_eq!(hooks, Some(PathBuf::from("/build/out/.claude-custom/settings.local.json")));
+    let mcp = manifest.get_merge_target("claude-code", crate::core::ResourceType::McpServer);
+    assert_eq!(mcp, Some(PathBuf::from("/build/out/.mcp.json")));
+
+    assert_eq!(
+        manifest.get_tool_base_path("claude-code"),
+        Some(PathBuf::from("/build/out/.claude-custom"))
+    );
+    assert_eq!(manifest.get_tool_base_path("unknown"), None);
+    Ok(())
+}
+
 #[test]
 fn test_get_merge_target_non_mergeable() {
     let manifest = Manifest::new();
@@ -87,6 +87,137 @@ impl Manifest {
             }
         }
 
+        // The frontmatter dependency key must be a plain key other than AGPM's own section
+        if let Some(field) = &self.transitive.field {
+            let is_plain_key = !field.is_empty()
+                && field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+            if !is_plain_key || field == "agpm" {
+                return Err(crate::core::AgpmError::ManifestValidationError {
+                    reason: format!(
+                        "Invalid [transitive] field '{field}'. \n\
+                        Use a frontmatter key made of letters, digits, '_' or '-', other than 'agpm'."
+                    ),
+                }
+                .into());
+            }
+        }
+
+        // Transitive constraints must be usable versions
+        for (name, version) in &self.constraints {
+            if version.trim().is_empty()
+                || crate::version::constraints::VersionConstraint::parse(version).is_err()
+            {
+                return Err(crate::core::AgpmError::ManifestValidationError {
+                    reason: format!(
+                        "Invalid [constraints] entry '{name}' = '{version}'. \n\
+                        Use a tag, branch, commit or semver constraint such as 'v1.2.0' or '^1.2'."
+                    ),
+                }
+                .into());
+            }
+        }
+
+        // Source default versions must name a Git source and be usable versions
+        for (source, version) in &self.default_versions {
+            let Some(url) = self.sources.get(source) else {
+                return Err(crate::core::AgpmError::ManifestValidationError {
+                    reason: format!(
+                        "[default-versions] entry '{source}' does not match any source in [sources]"
+                    ),
+                }
+                .into());
+            };
+            if crate::utils::is_local_path(url) {
+                return Err(crate::core::AgpmError::ManifestValidationError {
+                    reason: format!(
+                        "[default-versions] entry '{source}' names a local path source. \n\
+                        Local sources do not support versions; remove the entry or use a git source."
+                    ),
+                }
+                .into());
+            }
+            if version.trim().is_empty()
+                || crate::version::constraints::VersionConstraint::parse(version).is_err()
+            {
+                return Err(crate::core::AgpmError::ManifestValidationError {
+                    reason: format!(
+                        "Invalid [default-versions] entry '{source}' = '{version}'. \n\
+                        Use a tag, branch or semver constraint such as 'v1.2.0' or '^1.2'."
+                    ),
+                }
+                .into());
+            }
+        }
+
+        // Tag patterns must name a Git source and contain {version} once
+        for (source, pattern) in &self.tag_patterns {
+            let Some(url) = self.sources.get(source) else {
+                return Err(crate::core::AgpmError::ManifestValidationError {
+                    reason: format!(
+                        "[tag-patterns] entry '{source}' does not match any source in [sources]"
+                    ),
+                }
+                .into());
+            };
+            if crate::utils::is_local_path(url) {
+                return Err(crate::core::AgpmError::ManifestValidationError {
+                    reason: format!(
+                        "[tag-patterns] entry '{source}' names a local path source. \n\
+                        Local sources have no tags; remove the entry or use a git source."
+                    ),
+                }
+                .into());
+            }
+            if let Err(e) = crate::version::tag_pattern::TagPattern::parse(pattern) {
+                return Err(crate::core::AgpmError::ManifestValidationError {
+                    reason: format!(
+                        "Invalid [tag-patterns] entry '{source}': {e}. \n\
+                        Use a pattern such as 'release/{{version}}'."
+                    ),
+                }
+                .into());
+            }
+        }
+
+        // Path aliases must name a source and use valid version ranges
+        for (source, aliases) in &self.path_aliases {
+            if !self.sources.contains_key(source) {
+                return Err(crate::core::AgpmError::ManifestValidationError {
+                    reason: format!(
+                        "[path-aliases] entry '{source}' does not match any source in [sources]"
+                    ),
+                }
+                .into());
+            }
+            for (path, alias) in aliases {
+                if path.is_empty() || alias.targets().any(str::is_empty) {
+                    return Err(crate::core::AgpmError::ManifestValidationError {
+                        reason: format!(
+                            "Invalid [path-aliases.{source}] entry '{path}': paths cannot be empty"
+                        ),
+                    }
+                    .into());
+                }
+                let crate::manifest::PathAlias::Versioned(entries) = alias else {
+                    continue;
+                };
+                for range in entries.iter().filter_map(|entry| entry.versions.as_deref()) {
+                    if range
+                        .split("||")
+                        .any(|part| crate::version::parse_version_req(part.trim()).is_err())
+                    {
+                        return Err(crate::core::AgpmError::ManifestValidationError {
+                            reason: format!(
+                                "Invalid [path-aliases.{source}] entry '{path}': '{range}' is not a version range. \n\
+                                Use a semver range such as '<2.0.0' or '>=2.0.0'."
+                            ),
+                        }
+                        .into());
+                    }
+                }
+            }
+        }
+
         // Check that all referenced sources exist and dependencies have required fields
         for (name, dep) in self.all_dependencies() {
             // Check for empty path
@@ -106,6 +237,29 @@ impl Manifest {
                 })?;
             }
 
+            // A content pin names exactly one file and must be a full SHA-256 digest
+            if let Some(sha256) = dep.get_sha256() {
+                if dep.is_pattern() {
+                    return Err(crate::core::AgpmError::ManifestValidationError {
+                        reason: format!(
+                            "Dependency '{name}' uses 'sha256' with a pattern path. \
+                             Content pins apply to a single file; list the files individually."
+                        ),
+                    }
+                    .into());
+                }
+                let digest = sha256.strip_prefix("sha256:").unwrap_or(sha256);
+                if digest.len() != 64 || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(crate::core::AgpmError::ManifestValidationError {
+                        reason: format!(
+                            "Invalid sha256 '{sha256}' for dependency '{name}': \
+                             expected 64 hex digits, optionally prefixed with 'sha256:'"
+                        ),
+                    }
+                    .into());
+                }
+            }
+
             // Check for version when source is specified (non-local dependencies)
             if let Some(source) = dep.get_source() {
                 if !self.sources.contains_key(source) {
@@ -410,11 +564,13 @@ impl Manifest {
     /// Validate that patches reference valid manifest aliases.
     ///
     /// This method checks that all patch aliases correspond to actual dependencies
-    /// defined in the manifest. Patches for non-existent aliases are rejected.
+    /// defined in the manifest. Patches for non-existent aliases are rejected, as are
+    /// `when` conditions that are not valid version constraints.
     ///
     /// # Errors
     ///
-    /// Returns an error if a patch references an alias that doesn't exist in the manifest.
+    /// Returns an error if a patch references an alias that doesn't exist in the manifest
+    /// or carries an invalid `when` constraint.
     fn validate_patches(&self) -> Result<()> {
         use crate::core::ResourceType;
 
@@ -424,7 +580,19 @@ impl Manifest {
          -> Result<()> {
             let deps = self.get_dependencies(resource_type);
 
-            for alias in patches.keys() {
+            for (alias, patch) in patches {
+                if let Some(when) = patch.get(crate::manifest::patches::WHEN_KEY)
+                    && let Err(e) = crate::manifest::patches::validate_when(when)
+                {
+                    return Err(crate::core::AgpmError::ManifestValidationError {
+                        reason: format!(
+                            "Invalid patch for '{alias}' in [patch.{}] section: {e}",
+                            resource_type.to_plural()
+                        ),
+                    }
+                    .into());
+                }
+
                 // Check if this alias exists in the manifest
                 let exists = if let Some(deps) = deps {
                     deps.contains_key(alias)
@@ -26,6 +26,11 @@ fn make_detailed_dep(source: &str, path: &str, version: &str) -> ResourceDepende
         flatten: None,
         install: None,
         template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+        sha256: None,
+        on_conflict: None,
+        preserve_structure: None,
+        channel: None,
+        priority: None,
     }))
 }
 
@@ -108,6 +113,11 @@ fn test_validate_sources() -> Result<()> {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
         true,
     );
@@ -142,6 +152,11 @@ fn test_validate_version_constraints() -> Result<()> {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
         true,
     );
@@ -166,6 +181,11 @@ fn test_validate_version_constraints() -> Result<()> {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
         true,
     );
@@ -237,3 +257,34 @@ fn test_case_different_sections_allowed() -> Result<()> {
     manifest.validate()?;
     Ok(())
 }
+
+#[test]
+fn test_validate_sha256_pins() -> Result<()> {
+    let digest = "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08";
+    let with_sha = |path: &str, sha256: &str| {
+        let mut dep = make_detailed_dep("test", path, "v1.0.0");
+        if let ResourceDependency::Detailed(detailed) = &mut dep {
+            detailed.sha256 = Some(sha256.to_string());
+        }
+        dep
+    };
+    let mut manifest = Manifest::new();
+    manifest.add_source("test".to_string(), "https://github.com/test/repo.git".to_string());
+
+    // Bare and prefixed digests are accepted, in either case
+    manifest.agents.insert("bare".to_string(), with_sha("agents/a.md", digest));
+    manifest
+        .agents
+        .insert("prefixed".to_string(), with_sha("agents/b.md", &format!("sha256:{digest}")));
+    manifest.validate()?;
+
+    manifest.agents.insert("short".to_string(), with_sha("agents/c.md", "abc123"));
+    let err = manifest.validate().unwrap_err().to_string();
+    assert!(err.contains("Invalid sha256 'abc123'"), "{err}");
+    manifest.agents.remove("short");
+
+    manifest.agents.insert("all".to_string(), with_sha("agents/*.md", digest));
+    let err = manifest.validate().unwrap_err().to_string();
+    assert!(err.contains("pattern"), "{err}");
+    Ok(())
+}
@@ -58,11 +58,19 @@
 //! reproducible installations, and [`crate::git`] for source management.
 
 pub mod dependency_spec;
+mod env_overlay;
 pub mod helpers;
 pub mod patches;
+pub mod path_alias;
+pub mod pin;
+pub mod promote;
 pub mod resource_dependency;
+pub mod schema;
 pub mod tool_config;
+pub mod workspace;
 
+#[cfg(test)]
+mod env_overlay_tests;
 #[cfg(test)]
 mod manifest_flatten_tests;
 #[cfg(test)]
@@ -85,15 +93,18 @@ mod tool_config_tests;
 
 use crate::core::file_error::{FileOperation, FileResultExt};
 use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
 pub use dependency_spec::{DependencyMetadata, DependencySpec};
 pub use helpers::{expand_url, find_manifest, find_manifest_from, find_manifest_with_optional};
 pub use patches::{ManifestPatches, PatchConflict, PatchData, PatchOrigin};
-pub use resource_dependency::{DetailedDependency, ResourceDependency};
+pub use path_alias::{PathAlias, VersionedPath};
+pub use resource_dependency::{ConflictPolicy, DetailedDependency, ResourceDependency};
 pub use tool_config::{ArtifactTypeConfig, ResourceConfig, ToolsConfig, WellKnownTool};
+pub use workspace::WorkspaceConfig;
 
 /// The main manifest file structure representing a complete `agpm.toml` file.
 ///
@@ -157,8 +168,11 @@ pub use tool_config::{ArtifactTypeConfig, ResourceConfig, ToolsConfig, WellKnown
 /// Max line length: {{ agpm.project.max_line_length }}
 /// Architecture: {{ agpm.project.paths.architecture }}
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct ProjectConfig(toml::map::Map<String, toml::Value>);
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+pub struct ProjectConfig(
+    #[schemars(with = "serde_json::Map<String, serde_json::Value>")]
+    toml::map::Map<String, toml::Value>,
+);
 
 impl ProjectConfig {
     /// Convert this ProjectConfig to a serde_json::Value for template rendering.
@@ -243,7 +257,329 @@ pub(crate) fn json_value_to_toml(value: &serde_json::Value) -> toml::Value {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Installer options from the `[installer]` section of `agpm.toml`.
+///
+/// ```toml
+/// [installer]
+/// header = true  # Prepend a "Managed by AGPM — do not edit" banner to installed files
+/// footer = true  # Append the resolved version and commit to installed Markdown files
+/// install_mode = "symlink"  # Link local resources to their source files
+/// min_version_policy = "warn"  # Install resources that need a newer AGPM with a warning
+/// emit_checksums = true  # Write CHECKSUMS.txt for `sha256sum -c`
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(default)]
+pub struct InstallerConfig {
+    /// Prepend a banner naming the source and version to installed Markdown
+    /// and JSON files. The banner is excluded from checksums.
+    pub header: bool,
+
+    /// Append a machine-parsable block with the resolved source, version and
+    /// commit to installed Markdown files. Also excluded from checksums.
+    pub footer: bool,
+
+    /// How resources from local sources are placed in the project.
+    pub install_mode: InstallMode,
+
+    /// What to do when a resource's `min_agpm_version` is newer than the
+    /// running AGPM.
+    pub min_version_policy: MinVersionPolicy,
+
+    /// Write `CHECKSUMS.txt` to the project directory after installation,
+    /// listing every installed file in `sha256sum -c` format.
+    pub emit_checksums: bool,
+}
+
+/// Handling of resources that require a newer AGPM than the one running.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MinVersionPolicy {
+    /// Fail the installation, naming the resource.
+    #[default]
+    Error,
+    /// Install the resource anyway and print a warning.
+    Warn,
+}
+
+/// How installed files are created from their source.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallMode {
+    /// Write a copy of the (patched and rendered) content.
+    #[default]
+    Copy,
+    /// Symlink to the source file for local dependencies and local directory
+    /// sources whose content is installed unchanged, so edits show up without
+    /// reinstalling. Git sources and transformed content are always copied.
+    Symlink,
+}
+
+impl InstallerConfig {
+    /// Returns `true` when all options have their default values.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Per-resource-type install switches from the `[install]` section of `agpm.toml`.
+///
+/// Disabled types are still resolved and recorded in the lockfile with
+/// `install = false`, so the project stays reproducible, but nothing is written
+/// to disk or merged into tool configuration. With `--prune-disabled` they are
+/// left out of the lockfile instead.
+///
+/// ```toml
+/// [install]
+/// hooks = false        # Never merge hooks into settings.local.json
+/// mcp-servers = false  # Never merge MCP servers into .mcp.json
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct InstallToggles {
+    /// Install agents.
+    pub agents: bool,
+    /// Install snippets.
+    pub snippets: bool,
+    /// Install commands.
+    pub commands: bool,
+    /// Install scripts.
+    pub scripts: bool,
+    /// Configure hooks.
+    pub hooks: bool,
+    /// Configure MCP servers.
+    pub mcp_servers: bool,
+    /// Install skills.
+    pub skills: bool,
+    /// Omit disabled types from the lockfile instead of recording them as
+    /// not installed. Set from the `--prune-disabled` flag, never from TOML.
+    #[serde(skip)]
+    pub prune_disabled: bool,
+}
+
+impl Default for InstallToggles {
+    fn default() -> Self {
+        Self {
+            agents: true,
+            snippets: true,
+            commands: true,
+            scripts: true,
+            hooks: true,
+            mcp_servers: true,
+            skills: true,
+            prune_disabled: false,
+        }
+    }
+}
+
+impl InstallToggles {
+    /// Returns `true` if resources of `resource_type` should be installed.
+    #[must_use]
+    pub fn is_enabled(&self, resource_type: crate::core::ResourceType) -> bool {
+        use crate::core::ResourceType;
+        match resource_type {
+            ResourceType::Agent => self.agents,
+            ResourceType::Snippet => self.snippets,
+            ResourceType::Command => self.commands,
+            ResourceType::Script => self.scripts,
+            ResourceType::Hook => self.hooks,
+            ResourceType::McpServer => self.mcp_servers,
+            ResourceType::Skill => self.skills,
+        }
+    }
+
+    /// Returns `true` if resources of `resource_type` are left out of the lockfile.
+    #[must_use]
+    pub fn is_pruned(&self, resource_type: crate::core::ResourceType) -> bool {
+        self.prune_disabled && !self.is_enabled(resource_type)
+    }
+
+    /// Returns `true` when all options have their default values.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Per-resource-type transitive dependency policy from the `[transitive]`
+/// section of `agpm.toml`.
+///
+/// When a type is set to `false`, the `dependencies` declared in the
+/// frontmatter of resources of that type are ignored, so those resources are
+/// always leaves of the dependency graph. Dependencies declared by other types
+/// are still followed.
+///
+/// `field` changes the frontmatter key dependencies are read from, for
+/// resources whose `dependencies` key belongs to another tool.
+///
+/// Transitive dependencies come from the source of the resource declaring
+/// them. `restrict_sources` only lets sources declared in `[sources]` (or
+/// listed in `allowed_sources`) contribute them, so a dependency from a source
+/// defined only in the global config cannot pull further resources in.
+///
+/// ```toml
+/// [transitive]
+/// snippets = false  # Snippets are leaves; ignore their frontmatter dependencies
+/// field = "agpm_dependencies"
+/// restrict_sources = true
+/// allowed_sources = ["company"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct TransitivePolicy {
+    /// Follow dependencies declared by agents.
+    pub agents: bool,
+    /// Follow dependencies declared by snippets.
+    pub snippets: bool,
+    /// Follow dependencies declared by commands.
+    pub commands: bool,
+    /// Follow dependencies declared by scripts.
+    pub scripts: bool,
+    /// Follow dependencies declared by hooks.
+    pub hooks: bool,
+    /// Follow dependencies declared by MCP servers.
+    pub mcp_servers: bool,
+    /// Follow dependencies declared by skills.
+    pub skills: bool,
+    /// Frontmatter (or JSON) key that declares dependencies, if not `dependencies`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    /// Fail when a resource from a source that is not permitted declares dependencies.
+    #[serde(rename = "restrict_sources", skip_serializing_if = "std::ops::Not::not")]
+    pub restrict_sources: bool,
+    /// Sources permitted under `restrict_sources`, instead of those in `[sources]`.
+    #[serde(rename = "allowed_sources", skip_serializing_if = "Option::is_none")]
+    pub allowed_sources: Option<Vec<String>>,
+}
+
+impl Default for TransitivePolicy {
+    fn default() -> Self {
+        Self {
+            agents: true,
+            snippets: true,
+            commands: true,
+            scripts: true,
+            hooks: true,
+            mcp_servers: true,
+            skills: true,
+            field: None,
+            restrict_sources: false,
+            allowed_sources: None,
+        }
+    }
+}
+
+impl TransitivePolicy {
+    /// The key dependencies are declared under when no `field` is configured.
+    pub const DEFAULT_FIELD: &'static str = "dependencies";
+
+    /// The frontmatter key to read transitive dependencies from.
+    #[must_use]
+    pub fn dependency_field(&self) -> &str {
+        self.field.as_deref().unwrap_or(Self::DEFAULT_FIELD)
+    }
+
+    /// Returns `true` if dependencies declared by resources of `resource_type` are followed.
+    #[must_use]
+    pub fn is_enabled(&self, resource_type: crate::core::ResourceType) -> bool {
+        use crate::core::ResourceType;
+        match resource_type {
+            ResourceType::Agent => self.agents,
+            ResourceType::Snippet => self.snippets,
+            ResourceType::Command => self.commands,
+            ResourceType::Script => self.scripts,
+            ResourceType::Hook => self.hooks,
+            ResourceType::McpServer => self.mcp_servers,
+            ResourceType::Skill => self.skills,
+        }
+    }
+
+    /// Returns `true` if resources from `source` may declare transitive dependencies.
+    ///
+    /// Always `true` without `restrict_sources`. Otherwise `source` must be in
+    /// `allowed_sources` if set, or else be one of the manifest's `sources`.
+    #[must_use]
+    pub fn permits_source(&self, source: &str, sources: &HashMap<String, String>) -> bool {
+        if !self.restrict_sources {
+            return true;
+        }
+        match &self.allowed_sources {
+            Some(allowed) => allowed.iter().any(|name| name == source),
+            None => sources.contains_key(source),
+        }
+    }
+
+    /// Returns `true` when all options have their default values.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Resolver policies from the `[resolution]` section of `agpm.toml`.
+///
+/// `duplicate_name` decides what happens when transitive dependencies bring in
+/// resources with the same name from different sources, which would install
+/// to the same path. By default (`"strict"`) this is a target path conflict;
+/// `"newest"` keeps the copy with the highest resolved version.
+///
+/// ```toml
+/// [resolution]
+/// duplicate_name = "newest"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(default)]
+pub struct ResolutionPolicy {
+    /// Policy for same-named resources reached through several sources.
+    pub duplicate_name: DuplicateNamePolicy,
+}
+
+impl ResolutionPolicy {
+    /// Returns `true` when all options have their default values.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Policy for same-named resources reached through several sources.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateNamePolicy {
+    /// Fail resolution with a target path conflict error.
+    #[default]
+    Strict,
+    /// Keep the copy with the highest resolved version.
+    Newest,
+}
+
+/// Checksum options from the `[integrity]` section of `agpm.toml`.
+///
+/// With `normalize_eol`, checksums are computed as if every CRLF line ending
+/// were LF, so a checkout that converted line endings (Git's `core.autocrlf`
+/// on Windows) records the same checksums in `agpm.lock` as a Unix checkout.
+/// Installed files keep their line endings.
+///
+/// ```toml
+/// [integrity]
+/// normalize_eol = true
+/// ```
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(default)]
+pub struct IntegrityConfig {
+    /// Hash CRLF line endings as LF.
+    pub normalize_eol: bool,
+}
+
+impl IntegrityConfig {
+    /// Returns `true` when all options have their default values.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Manifest {
     /// Named source repositories mapped to their Git URLs.
     ///
@@ -373,6 +709,65 @@ pub struct Manifest {
     #[serde(default, skip_serializing_if = "HashMap::is_empty", rename = "default-tools")]
     pub default_tools: HashMap<String, String>,
 
+    /// Default version constraints for dependencies of a source.
+    ///
+    /// Maps a source name to the version constraint used by its dependencies
+    /// that set none of `version`, `branch` or `rev`, so a source known to
+    /// follow semver can be tracked without repeating the constraint. Local
+    /// path sources have no versions and cannot be listed.
+    ///
+    /// ```toml
+    /// [default-versions]
+    /// community = "^2.0.0"
+    /// ```
+    #[serde(default, skip_serializing_if = "HashMap::is_empty", rename = "default-versions")]
+    pub default_versions: HashMap<String, String>,
+
+    /// Tag formats for sources whose tags are not `v1.2.3`-style.
+    ///
+    /// Maps a source name to a pattern containing `{version}`. Version
+    /// constraints for that source are matched against the part of each tag
+    /// in place of `{version}`; tags that don't fit the pattern are ignored.
+    /// Local path sources have no tags and cannot be listed.
+    ///
+    /// ```toml
+    /// [tag-patterns]
+    /// community = "release/{version}"
+    /// ```
+    #[serde(default, skip_serializing_if = "HashMap::is_empty", rename = "tag-patterns")]
+    pub tag_patterns: HashMap<String, String>,
+
+    /// Actual paths of resources that moved between releases of a source.
+    ///
+    /// Maps a source name to a table from the path used in the manifest to the
+    /// path in the source, either for every version or per version range. See
+    /// [`path_alias`] for the format.
+    ///
+    /// ```toml
+    /// [path-aliases.community]
+    /// "agents/linter.md" = [
+    ///     { versions = "<2.0.0", path = "agents/linter.md" },
+    ///     { versions = ">=2.0.0", path = "agents/quality/linter.md" },
+    /// ]
+    /// ```
+    #[serde(default, skip_serializing_if = "HashMap::is_empty", rename = "path-aliases")]
+    pub path_aliases: HashMap<String, BTreeMap<String, PathAlias>>,
+
+    /// Local checkouts used in place of Git sources during development.
+    ///
+    /// Maps a source name or URL to a local directory, relative to the
+    /// manifest directory unless absolute. Versions are still resolved against
+    /// the remote source and recorded in the lockfile, but resource content is
+    /// read from the local directory. Usually kept in `agpm.private.toml` so it
+    /// is never committed.
+    ///
+    /// ```toml
+    /// [path-overrides]
+    /// community = "../agpm-community"
+    /// ```
+    #[serde(default, skip_serializing_if = "HashMap::is_empty", rename = "path-overrides")]
+    pub path_overrides: HashMap<String, PathBuf>,
+
     /// Project-specific template variables.
     ///
     /// Custom project configuration that can be referenced in resource templates
@@ -405,6 +800,43 @@ pub struct Manifest {
     #[serde(skip)]
     pub private_dependency_names: std::collections::HashSet<(String, String)>,
 
+    /// Name of the environment overlay merged into this manifest, if any.
+    ///
+    /// Set by `load_with_overlays()` when `--env` or `AGPM_ENV` selects an
+    /// `agpm.<env>.toml` overlay.
+    #[serde(skip)]
+    pub env: Option<String>,
+
+    /// Patches from the environment overlay (`agpm.<env>.toml`).
+    ///
+    /// This field is not serialized - it's populated during loading to track
+    /// which patches came from the environment overlay, mirroring `private_patches`.
+    #[serde(skip)]
+    pub env_patches: ManifestPatches,
+
+    /// Names of dependencies added or overridden by the environment overlay.
+    ///
+    /// Contains `(resource_type, name)` pairs, using the same plural resource
+    /// type names as `private_dependency_names`.
+    #[serde(skip)]
+    pub env_dependency_names: std::collections::HashSet<(String, String)>,
+
+    /// Names of dependencies whose `tool` was filled in from the resource-type default.
+    ///
+    /// Contains `(resource_type, name)` pairs with plural resource type names.
+    /// A tool declared in the resource's frontmatter takes precedence over the
+    /// default for these dependencies; see [`Manifest::is_tool_defaulted`].
+    #[serde(skip)]
+    pub defaulted_tool_names: std::collections::HashSet<(String, String)>,
+
+    /// Directory every tool base directory is remapped under, from `--install-root`.
+    ///
+    /// When set, tool paths such as `.claude` and merge targets such as
+    /// `.mcp.json` are placed inside this directory instead of the project
+    /// directory. Relative roots are resolved against the project directory.
+    #[serde(skip)]
+    pub install_root: Option<PathBuf>,
+
     /// Token count warning threshold (project-level override).
     ///
     /// Overrides the global `token_warning_threshold` for this project.
@@ -429,6 +861,90 @@ pub struct Manifest {
     /// ```
     #[serde(default = "default_gitignore")]
     pub gitignore: bool,
+
+    /// Require every Git dependency to resolve to a tag.
+    ///
+    /// When true, dependencies that track a branch (`branch`, a branch name in
+    /// `version`, or no version at all) or name a commit (`rev`, a SHA in
+    /// `version`) are rejected during resolution. Exact tags and semver ranges
+    /// are allowed. Local dependencies and local directory or archive sources
+    /// are not affected.
+    ///
+    /// Example:
+    /// ```toml
+    /// require_tag = true  # Forbid branch and commit pins
+    /// ```
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub require_tag: bool,
+
+    /// Only resolve versions that upstream marked as validated.
+    ///
+    /// When true, version ranges and `latest-by-date` only consider a tag if a
+    /// companion `<tag>-validated` marker tag exists in the same source, for
+    /// example `v1.2.0-validated` next to `v1.2.0`. Exact tags are used as
+    /// written.
+    ///
+    /// Example:
+    /// ```toml
+    /// require_validated = true  # Skip releases that did not pass upstream CI
+    /// ```
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub require_validated: bool,
+
+    /// Which tag is resolved and recorded when several tags name one release.
+    ///
+    /// With `"semver"` (default), pinning a non-semver alias such as `stable`
+    /// records the semver tag on the same commit (e.g. `v1.2.0`) in
+    /// `agpm.lock`, and a range matching tags of equal precedence (`v1.2.0`,
+    /// `v1.2.0+build.7`) picks the one without build metadata. `"requested"`
+    /// records tags exactly as written.
+    ///
+    /// Example:
+    /// ```toml
+    /// tag_preference = "requested"  # Keep `stable` in the lockfile
+    /// ```
+    #[serde(default, skip_serializing_if = "crate::version::TagPreference::is_default")]
+    pub tag_preference: crate::version::TagPreference,
+
+    /// Installer options (`[installer]` section).
+    #[serde(default, skip_serializing_if = "InstallerConfig::is_default")]
+    pub installer: InstallerConfig,
+
+    /// Per-resource-type install switches (`[install]` section).
+    #[serde(default, skip_serializing_if = "InstallToggles::is_default")]
+    pub install: InstallToggles,
+
+    /// Per-resource-type transitive dependency policy (`[transitive]` section).
+    #[serde(default, skip_serializing_if = "TransitivePolicy::is_default")]
+    pub transitive: TransitivePolicy,
+
+    /// Resolver policies (`[resolution]` section).
+    #[serde(default, skip_serializing_if = "ResolutionPolicy::is_default")]
+    pub resolution: ResolutionPolicy,
+
+    /// Checksum options (`[integrity]` section).
+    #[serde(default, skip_serializing_if = "IntegrityConfig::is_default")]
+    pub integrity: IntegrityConfig,
+
+    /// Version constraints for transitive dependencies (`[constraints]` section).
+    ///
+    /// Maps a resource name, as shown in `agpm.lock` (its repository path without
+    /// the extension, e.g. `snippets/rust-style`), to the version it resolves to
+    /// whenever it appears transitively. The version a parent declares for it
+    /// must accept the constraint, otherwise resolution fails. Resources declared
+    /// directly in the manifest are not affected.
+    ///
+    /// Example:
+    /// ```toml
+    /// [constraints]
+    /// "snippets/rust-style" = "v1.2.0"
+    /// ```
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub constraints: BTreeMap<String, String>,
+
+    /// Member projects installed along with this one (`[workspace]` section).
+    #[serde(default, skip_serializing_if = "WorkspaceConfig::is_default")]
+    pub workspace: WorkspaceConfig,
 }
 
 /// Default value for gitignore field (true = enabled).
@@ -497,11 +1013,30 @@ impl Manifest {
             project_patches: ManifestPatches::new(),
             private_patches: ManifestPatches::new(),
             default_tools: HashMap::new(),
+            default_versions: HashMap::new(),
+            tag_patterns: HashMap::new(),
+            path_aliases: HashMap::new(),
+            path_overrides: HashMap::new(),
             project: None,
             manifest_dir: None,
             private_dependency_names: std::collections::HashSet::new(),
+            env: None,
+            env_patches: ManifestPatches::new(),
+            env_dependency_names: std::collections::HashSet::new(),
+            defaulted_tool_names: std::collections::HashSet::new(),
+            install_root: None,
             token_warning_threshold: None,
             gitignore: true,
+            require_tag: false,
+            require_validated: false,
+            tag_preference: crate::version::TagPreference::default(),
+            installer: InstallerConfig::default(),
+            install: InstallToggles::default(),
+            transitive: TransitivePolicy::default(),
+            resolution: ResolutionPolicy::default(),
+            integrity: IntegrityConfig::default(),
+            constraints: BTreeMap::new(),
+            workspace: WorkspaceConfig::default(),
         }
     }
 
@@ -537,10 +1072,26 @@ impl Manifest {
     /// Expects a valid TOML file following the AGPM manifest format.
     /// See the module-level documentation for complete format specification.
     pub fn load(path: &Path) -> Result<Self> {
+        let mut manifest = Self::parse_file(path, "manifest")?;
+
+        // Apply resource-type-specific defaults for tool
+        // Snippets default to "agpm" (shared infrastructure) instead of "claude-code"
+        manifest.apply_tool_defaults();
+
+        manifest.validate()?;
+
+        Ok(manifest)
+    }
+
+    /// Read and deserialize a manifest-format TOML file without defaults or validation.
+    ///
+    /// Shared by the project, private, and environment manifest loaders. `kind`
+    /// describes the file in error messages (e.g. `"private manifest"`).
+    fn parse_file(path: &Path, kind: &str) -> Result<Self> {
         let content = std::fs::read_to_string(path).with_file_context(
             FileOperation::Read,
             path,
-            "reading manifest file",
+            format!("reading {kind} file"),
             "manifest_module",
         )?;
 
@@ -551,7 +1102,7 @@ impl Manifest {
             })
             .with_context(|| {
                 format!(
-                    "Invalid TOML syntax in manifest file: {}\n\n\
+                    "Invalid TOML syntax in {kind} file: {}\n\n\
                     Common TOML syntax errors:\n\
                     - Missing quotes around strings\n\
                     - Unmatched brackets [ ] or braces {{ }}\n\
@@ -561,19 +1112,13 @@ impl Manifest {
                 )
             })?;
 
-        // Apply resource-type-specific defaults for tool
-        // Snippets default to "agpm" (shared infrastructure) instead of "claude-code"
-        manifest.apply_tool_defaults();
-
         // Store the manifest directory for resolving relative paths
         manifest.manifest_dir = Some(
             path.parent()
-                .ok_or_else(|| anyhow::anyhow!("Manifest path has no parent directory"))?
+                .ok_or_else(|| anyhow::anyhow!("{kind} path has no parent directory"))?
                 .to_path_buf(),
         );
 
-        manifest.validate()?;
-
         Ok(manifest)
     }
 
@@ -582,6 +1127,9 @@ impl Manifest {
     /// Loads the project manifest from `agpm.toml` and then attempts to load
     /// `agpm.private.toml` from the same directory. If a private config exists:
     /// - **Sources** are merged (private sources can use same names, which shadows project sources)
+    /// - **Path overrides** are merged (private overrides take precedence)
+    /// - **Default versions**, **tag patterns** and **path aliases** are merged per source
+    ///   (private entries take precedence)
     /// - **Dependencies** are merged (private deps tracked via `private_dependency_names`)
     /// - **Patches** are merged (private patches take precedence)
     ///
@@ -598,11 +1146,37 @@ impl Manifest {
     /// A manifest with merged sources, dependencies, patches, and a list of any
     /// patch conflicts detected (for informational/debugging purposes).
     pub fn load_with_private(path: &Path) -> Result<(Self, Vec<PatchConflict>)> {
-        // Load the main project manifest
-        let mut manifest = Self::load(path)?;
+        Self::load_with_overlays(path, None)
+    }
 
-        // Store project patches before merging
-        manifest.project_patches = manifest.patches.clone();
+    /// Load manifest with an optional environment overlay and private config merged.
+    ///
+    /// Layers are applied in increasing precedence:
+    /// 1. `agpm.toml` (project)
+    /// 2. `agpm.<env>.toml` (environment overlay, only when `env` is set)
+    /// 3. `agpm.private.toml` (private, user-level)
+    ///
+    /// See [`Self::load_with_private`] for private merge semantics and
+    /// [`Self::load_with_env`] for environment overlay semantics.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the project manifest file (`agpm.toml`)
+    /// * `env` - Environment name selected via `--env` or `AGPM_ENV`
+    pub fn load_with_overlays(
+        path: &Path,
+        env: Option<&str>,
+    ) -> Result<(Self, Vec<PatchConflict>)> {
+        // Load the main project manifest (with environment overlay if requested)
+        let (mut manifest, mut conflicts) = match env {
+            Some(env) => Self::load_with_env(path, env)?,
+            None => {
+                let mut manifest = Self::load(path)?;
+                // Store project patches before merging
+                manifest.project_patches = manifest.patches.clone();
+                (manifest, Vec::new())
+            }
+        };
 
         // Try to load private config
         let private_path = if let Some(parent) = path.parent() {
@@ -619,54 +1193,33 @@ impl Manifest {
                 manifest.sources.insert(name, url);
             }
 
-            // Track which dependencies are from private manifest and merge them
-            let mut private_names = std::collections::HashSet::new();
-
-            // Merge agents
-            for (name, dep) in private_manifest.agents {
-                private_names.insert(("agents".to_string(), name.clone()));
-                manifest.agents.insert(name, dep);
-            }
-
-            // Merge snippets
-            for (name, dep) in private_manifest.snippets {
-                private_names.insert(("snippets".to_string(), name.clone()));
-                manifest.snippets.insert(name, dep);
-            }
-
-            // Merge commands
-            for (name, dep) in private_manifest.commands {
-                private_names.insert(("commands".to_string(), name.clone()));
-                manifest.commands.insert(name, dep);
-            }
-
-            // Merge scripts
-            for (name, dep) in private_manifest.scripts {
-                private_names.insert(("scripts".to_string(), name.clone()));
-                manifest.scripts.insert(name, dep);
-            }
-
-            // Merge hooks
-            for (name, dep) in private_manifest.hooks {
-                private_names.insert(("hooks".to_string(), name.clone()));
-                manifest.hooks.insert(name, dep);
-            }
+            // Merge path overrides and per-source version settings (private takes precedence)
+            manifest.path_overrides.extend(private_manifest.path_overrides);
+            manifest.default_versions.extend(private_manifest.default_versions);
+            manifest.tag_patterns.extend(private_manifest.tag_patterns);
+            manifest.path_aliases.extend(private_manifest.path_aliases);
 
-            // Merge MCP servers
-            for (name, dep) in private_manifest.mcp_servers {
-                private_names.insert(("mcp-servers".to_string(), name.clone()));
-                manifest.mcp_servers.insert(name, dep);
-            }
-
-            manifest.private_dependency_names = private_names;
+            // Track which dependencies are from private manifest and merge them
+            manifest.private_dependency_names = manifest.merge_overlay_dependencies(
+                private_manifest.agents,
+                private_manifest.snippets,
+                private_manifest.commands,
+                private_manifest.scripts,
+                private_manifest.hooks,
+                private_manifest.mcp_servers,
+            );
+            let private_names = &manifest.private_dependency_names;
+            manifest.defaulted_tool_names.retain(|key| !private_names.contains(key));
+            manifest.defaulted_tool_names.extend(private_manifest.defaulted_tool_names);
 
             // Store private patches
             manifest.private_patches = private_manifest.patches.clone();
 
             // Merge patches (private takes precedence)
-            let (merged_patches, conflicts) =
+            let (merged_patches, private_conflicts) =
                 manifest.patches.merge_with(&private_manifest.patches);
             manifest.patches = merged_patches;
+            conflicts.extend(private_conflicts);
 
             // Re-validate after merge to ensure private dependencies reference valid sources
             manifest.validate().with_context(|| {
@@ -675,13 +1228,44 @@ impl Manifest {
                     private_path.display()
                 )
             })?;
-
-            Ok((manifest, conflicts))
         } else {
             // No private config, keep private_patches empty
             manifest.private_patches = ManifestPatches::new();
-            Ok((manifest, Vec::new()))
         }
+
+        Ok((manifest, conflicts))
+    }
+
+    /// Merge overlay dependencies into this manifest, returning the merged names.
+    ///
+    /// Overlay entries replace project entries with the same name. The returned
+    /// set contains `(resource_type, name)` pairs for provenance tracking.
+    fn merge_overlay_dependencies(
+        &mut self,
+        agents: HashMap<String, ResourceDependency>,
+        snippets: HashMap<String, ResourceDependency>,
+        commands: HashMap<String, ResourceDependency>,
+        scripts: HashMap<String, ResourceDependency>,
+        hooks: HashMap<String, ResourceDependency>,
+        mcp_servers: HashMap<String, ResourceDependency>,
+    ) -> std::collections::HashSet<(String, String)> {
+        let mut names = std::collections::HashSet::new();
+
+        for (resource_type, overlay, target) in [
+            ("agents", agents, &mut self.agents),
+            ("snippets", snippets, &mut self.snippets),
+            ("commands", commands, &mut self.commands),
+            ("scripts", scripts, &mut self.scripts),
+            ("hooks", hooks, &mut self.hooks),
+            ("mcp-servers", mcp_servers, &mut self.mcp_servers),
+        ] {
+            for (name, dep) in overlay {
+                names.insert((resource_type.to_string(), name.clone()));
+                target.insert(name, dep);
+            }
+        }
+
+        names
     }
 
     /// Load a private manifest file.
@@ -690,6 +1274,7 @@ impl Manifest {
     /// - **Sources**: Private Git repositories with authentication
     /// - **Dependencies**: User-only resources (agents, snippets, commands, etc.)
     /// - **Patches**: Customizations to project or private dependencies
+    /// - **Path overrides**: Local checkouts used in place of sources
     ///
     /// Private manifests **cannot** contain:
     /// - **Tools**: Tool configuration must be in the main manifest
@@ -705,29 +1290,7 @@ impl Manifest {
     /// - The TOML syntax is invalid
     /// - The private config contains tools configuration
     fn load_private(path: &Path) -> Result<Self> {
-        let content = std::fs::read_to_string(path).with_file_context(
-            FileOperation::Read,
-            path,
-            "reading private manifest file",
-            "manifest_module",
-        )?;
-
-        let mut manifest: Self = toml::from_str(&content)
-            .map_err(|e| crate::core::AgpmError::ManifestParseError {
-                file: path.display().to_string(),
-                reason: e.to_string(),
-            })
-            .with_context(|| {
-                format!(
-                    "Invalid TOML syntax in private manifest file: {}\n\n\
-                    Common TOML syntax errors:\n\
-                    - Missing quotes around strings\n\
-                    - Unmatched brackets [ ] or braces {{ }}\n\
-                    - Invalid characters in keys or values\n\
-                    - Incorrect indentation or structure",
-                    path.display()
-                )
-            })?;
+        let mut manifest = Self::parse_file(path, "private manifest")?;
 
         // Validate that private config doesn't contain tools
         if manifest.tools.is_some() {
@@ -741,13 +1304,6 @@ impl Manifest {
         // Apply resource-type-specific defaults for tool
         manifest.apply_tool_defaults();
 
-        // Store the manifest directory for resolving relative paths
-        manifest.manifest_dir = Some(
-            path.parent()
-                .ok_or_else(|| anyhow::anyhow!("Private manifest path has no parent directory"))?
-                .to_path_buf(),
-        );
-
         Ok(manifest)
     }
 
@@ -801,16 +1357,22 @@ impl Manifest {
         ] {
             // Get the default tool before the mutable borrow to avoid borrow conflicts
             let default_tool = self.get_default_tool(resource_type);
+            let mut defaulted = Vec::new();
 
             if let Some(deps) = self.get_dependencies_mut(resource_type) {
-                for dependency in deps.values_mut() {
+                for (name, dependency) in deps.iter_mut() {
                     if let ResourceDependency::Detailed(details) = dependency {
                         if details.tool.is_none() {
                             details.tool = Some(default_tool.clone());
+                            defaulted.push(name.clone());
                         }
                     }
                 }
             }
+
+            let plural = resource_type.to_plural();
+            self.defaulted_tool_names
+                .extend(defaulted.into_iter().map(|name| (plural.to_string(), name)));
         }
     }
 
@@ -955,9 +1517,42 @@ impl Manifest {
         self.get_tools_config().types.get(tool)
     }
 
+    /// Get the base directory of a tool (e.g., ".claude"), remapped under
+    /// [`Self::install_root`] when set.
+    ///
+    /// Returns None if the tool is not configured.
+    pub fn get_tool_base_path(&self, tool: &str) -> Option<PathBuf> {
+        self.get_tool_config(tool).map(|config| self.under_install_root(config.path.clone()))
+    }
+
+    /// Prefix a project-relative install path with [`Self::install_root`], if set.
+    fn under_install_root(&self, path: PathBuf) -> PathBuf {
+        match &self.install_root {
+            Some(root) => root.join(path),
+            None => path,
+        }
+    }
+
+    /// The `[path-overrides]` table with relative directories resolved against
+    /// the manifest directory.
+    #[must_use]
+    pub fn resolved_path_overrides(&self) -> HashMap<String, PathBuf> {
+        self.path_overrides
+            .iter()
+            .map(|(source, dir)| {
+                let dir = match &self.manifest_dir {
+                    Some(base) if dir.is_relative() => base.join(dir),
+                    _ => dir.clone(),
+                };
+                (source.clone(), dir)
+            })
+            .collect()
+    }
+
     /// Get the installation path for a resource within a tool.
     ///
     /// Returns the full installation directory path by combining:
+    /// - `--install-root`, if set
     /// - Tool's base directory (e.g., ".claude", ".opencode")
     /// - Resource type's subdirectory (e.g., "agents", "command")
     ///
@@ -980,15 +1575,16 @@ impl Manifest {
             for component in subdir.split('/') {
                 result = result.join(component);
             }
-            result
+            self.under_install_root(result)
         })
     }
 
     /// Get the merge target configuration file path for a resource type.
     ///
     /// Returns the path to the configuration file where resources of this type
-    /// should be merged (e.g., hooks, MCP servers). Returns None if the resource
-    /// type doesn't use merge targets or if the tool doesn't support this resource type.
+    /// should be merged (e.g., hooks, MCP servers), remapped under `--install-root`
+    /// when set. Returns None if the resource type doesn't use merge targets or if
+    /// the tool doesn't support this resource type.
     ///
     /// # Arguments
     ///
@@ -1007,7 +1603,7 @@ impl Manifest {
         let artifact_config = self.get_tool_config(tool)?;
         let resource_config = artifact_config.resources.get(resource_type.to_plural())?;
 
-        resource_config.merge_target.as_ref().map(PathBuf::from)
+        resource_config.merge_target.as_ref().map(|target| self.under_install_root(target.into()))
     }
 
     /// Check if a resource type is supported by a tool.
@@ -1108,7 +1704,9 @@ impl Manifest {
     /// This is used by the resolver to correctly type transitive dependencies without
     /// falling back to manifest section order lookups.
     ///
-    /// Dependencies for disabled tools are automatically filtered out.
+    /// Dependencies for disabled tools are automatically filtered out. Resource
+    /// types disabled in `[install]` are marked `install = false`, or filtered out
+    /// as well when `--prune-disabled` is set.
     pub fn all_dependencies_with_types(
         &self,
     ) -> Vec<(&str, std::borrow::Cow<'_, ResourceDependency>, crate::core::ResourceType)> {
@@ -1116,6 +1714,12 @@ impl Manifest {
 
         // Use ResourceType::all() to iterate through all resource types
         for resource_type in crate::core::ResourceType::all() {
+            if self.install.is_pruned(*resource_type) {
+                tracing::debug!("Skipping {} dependencies disabled in [install]", resource_type);
+                continue;
+            }
+            let disabled = !self.install.is_enabled(*resource_type);
+
             if let Some(type_deps) = self.get_dependencies(*resource_type) {
                 // CRITICAL: Sort dependencies for deterministic iteration order!
                 // HashMap iteration is non-deterministic, so we must sort by name
@@ -1160,6 +1764,28 @@ impl Manifest {
                         std::borrow::Cow::Borrowed(dep)
                     };
 
+                    // Fill in the source's [default-versions] constraint for unversioned deps
+                    let dep_with_tool = match self.default_version_for(&dep_with_tool) {
+                        Some(version) => {
+                            let version = version.to_string();
+                            let mut dep_owned = dep_with_tool.into_owned();
+                            if let ResourceDependency::Detailed(details) = &mut dep_owned {
+                                details.version = Some(version);
+                            }
+                            std::borrow::Cow::Owned(dep_owned)
+                        }
+                        None => dep_with_tool,
+                    };
+
+                    // Record dependencies of types disabled in [install] without installing them
+                    let dep_with_tool = if disabled {
+                        let mut dep_owned = dep_with_tool.into_owned();
+                        dep_owned.set_install(Some(false));
+                        std::borrow::Cow::Owned(dep_owned)
+                    } else {
+                        dep_with_tool
+                    };
+
                     deps.push((name.as_str(), dep_with_tool, *resource_type));
                 }
             }
@@ -1168,6 +1794,40 @@ impl Manifest {
         deps
     }
 
+    /// The `[default-versions]` constraint that applies to `dep`.
+    ///
+    /// Only dependencies from a Git source that set none of `version`, `branch`
+    /// or `rev` take their source's default.
+    #[must_use]
+    pub fn default_version_for(&self, dep: &ResourceDependency) -> Option<&str> {
+        let ResourceDependency::Detailed(details) = dep else {
+            return None;
+        };
+        if details.version.is_some() || details.branch.is_some() || details.rev.is_some() {
+            return None;
+        }
+        self.default_versions.get(details.source.as_deref()?).map(String::as_str)
+    }
+
+    /// The actual path of `path` in `source` at the resolved `tag`, per `[path-aliases]`.
+    ///
+    /// Version ranges are matched against the version part of the tag, taken
+    /// from the source's `[tag-patterns]` entry if it has one. Returns `None`
+    /// if `path` has no alias or no entry matches.
+    #[must_use]
+    pub fn aliased_path(&self, source: &str, path: &str, tag: Option<&str>) -> Option<&str> {
+        let alias = self.path_aliases.get(source)?.get(path)?;
+        let pattern = self
+            .tag_patterns
+            .get(source)
+            .and_then(|pattern| crate::version::tag_pattern::TagPattern::parse(pattern).ok());
+        let version = match (tag, &pattern) {
+            (Some(tag), Some(pattern)) => pattern.extract(tag),
+            (tag, _) => tag,
+        };
+        alias.resolve(version)
+    }
+
     /// Check if a dependency with the given name exists in any section.
     ///
     /// Searches the `[agents]`, `[snippets]`, and `[commands]` sections for a dependency
@@ -1517,6 +2177,75 @@ impl Manifest {
             }
         }
 
+        // Hash installer options only when set, keeping existing hashes stable
+        if self.installer.header {
+            hasher.update(b"installer.header=true\n");
+        }
+        if self.installer.footer {
+            hasher.update(b"installer.footer=true\n");
+        }
+        if self.integrity.normalize_eol {
+            hasher.update(b"integrity.normalize_eol=true\n");
+        }
+
+        if let Some(root) = &self.install_root {
+            hasher.update(format!("install-root={}\n", root.display()).as_bytes());
+        }
+
+        // Same for [install] toggles: only disabled types and pruning affect the hash
+        for resource_type in crate::core::ResourceType::all() {
+            if !self.install.is_enabled(*resource_type) {
+                hasher.update(format!("install.{resource_type}=false\n").as_bytes());
+            }
+        }
+        if self.install.prune_disabled {
+            hasher.update(b"install.prune-disabled=true\n");
+        }
+
+        for resource_type in crate::core::ResourceType::all() {
+            if !self.transitive.is_enabled(*resource_type) {
+                hasher.update(format!("transitive.{resource_type}=false\n").as_bytes());
+            }
+        }
+        if let Some(field) = &self.transitive.field {
+            hasher.update(format!("transitive.field={field}\n").as_bytes());
+        }
+        if self.transitive.restrict_sources {
+            hasher.update(b"transitive.restrict_sources=true\n");
+            if let Some(allowed) = &self.transitive.allowed_sources {
+                hasher.update(format!("transitive.allowed_sources={allowed:?}\n").as_bytes());
+            }
+        }
+        for (name, version) in &self.constraints {
+            hasher.update(format!("constraints.{name}={version}\n").as_bytes());
+        }
+        if self.resolution.duplicate_name != DuplicateNamePolicy::default() {
+            hasher.update(
+                format!("resolution.duplicate_name={:?}\n", self.resolution.duplicate_name)
+                    .as_bytes(),
+            );
+        }
+        if self.tag_preference != crate::version::TagPreference::default() {
+            hasher.update(format!("tag_preference={:?}\n", self.tag_preference).as_bytes());
+        }
+        let mut default_versions: Vec<_> = self.default_versions.iter().collect();
+        default_versions.sort();
+        for (source, version) in default_versions {
+            hasher.update(format!("default-versions.{source}={version}\n").as_bytes());
+        }
+        let mut tag_patterns: Vec<_> = self.tag_patterns.iter().collect();
+        tag_patterns.sort();
+        for (source, pattern) in tag_patterns {
+            hasher.update(format!("tag-patterns.{source}={pattern}\n").as_bytes());
+        }
+        let mut path_aliases: Vec<_> = self.path_aliases.iter().collect();
+        path_aliases.sort_by_key(|(source, _)| source.as_str());
+        for (source, aliases) in path_aliases {
+            for (path, alias) in aliases {
+                hasher.update(format!("path-aliases.{source}.{path}={alias:?}\n").as_bytes());
+            }
+        }
+
         // Hash tools configuration (affects installation paths)
         // Convert to Value first for deterministic HashMap serialization
         if let Some(tools) = &self.tools {
@@ -1585,6 +2314,20 @@ impl Manifest {
         };
         self.private_dependency_names.contains(&(plural_type.to_string(), name.to_string()))
     }
+
+    /// Check if a dependency's tool comes from the resource-type default.
+    ///
+    /// This is the case when the dependency omits `tool`, including
+    /// shorthand path dependencies, which never set one.
+    #[must_use]
+    pub fn is_tool_defaulted(&self, resource_type: crate::core::ResourceType, name: &str) -> bool {
+        let key = (resource_type.to_plural().to_string(), name.to_string());
+        self.defaulted_tool_names.contains(&key)
+            || self
+                .get_dependencies(resource_type)
+                .and_then(|deps| deps.get(name))
+                .is_some_and(|dep| dep.get_tool().is_none())
+    }
 }
 
 impl Default for Manifest {
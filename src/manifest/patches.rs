@@ -14,8 +14,14 @@
 //!
 //! [patch.commands.deploy]
 //! timeout = "300"
+//!
+//! # Only applied while the resolved version is below v2.0.0
+//! [patch.agents.legacy-agent]
+//! when = "<v2.0.0"
+//! model = "claude-3-opus"
 //! ```
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -42,37 +48,63 @@ use std::collections::BTreeMap;
 /// [patch.commands.deploy]
 /// timeout = "300"
 /// ```
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct ManifestPatches {
     /// Patches for agent resources.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(schema_with = "patch_table_schema")]
     pub agents: BTreeMap<String, PatchData>,
 
     /// Patches for snippet resources.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(schema_with = "patch_table_schema")]
     pub snippets: BTreeMap<String, PatchData>,
 
     /// Patches for command resources.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(schema_with = "patch_table_schema")]
     pub commands: BTreeMap<String, PatchData>,
 
     /// Patches for script resources.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(schema_with = "patch_table_schema")]
     pub scripts: BTreeMap<String, PatchData>,
 
     /// Patches for MCP server resources.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty", rename = "mcp-servers")]
+    #[schemars(schema_with = "patch_table_schema")]
     pub mcp_servers: BTreeMap<String, PatchData>,
 
     /// Patches for hook resources.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(schema_with = "patch_table_schema")]
     pub hooks: BTreeMap<String, PatchData>,
 
     /// Patches for skill resources.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(schema_with = "patch_table_schema")]
     pub skills: BTreeMap<String, PatchData>,
 }
 
+/// JSON Schema for one `[patch.<type>]` table: alias -> free-form patch fields.
+///
+/// Patch values are arbitrary TOML, so only the reserved [`WHEN_KEY`] is described.
+fn patch_table_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::json_schema!({
+        "type": "object",
+        "additionalProperties": {
+            "type": "object",
+            "description": "Fields to override in the resource's frontmatter or JSON",
+            "properties": {
+                WHEN_KEY: {
+                    "type": "string",
+                    "description": "Only apply the patch when the resolved version matches this constraint"
+                }
+            }
+        }
+    })
+}
+
 /// Arbitrary key-value pairs to override in a resource's metadata.
 ///
 /// This is a free-form map that can contain any valid TOML values (strings, numbers,
@@ -89,6 +121,62 @@ pub struct ManifestPatches {
 /// ```
 pub type PatchData = BTreeMap<String, toml::Value>;
 
+/// Reserved patch key holding a version constraint that gates the patch.
+///
+/// When present, the patch only applies if the resource's resolved version
+/// satisfies the constraint. The key itself is never written to the resource.
+pub const WHEN_KEY: &str = "when";
+
+/// Select the fields of `patch` that apply to a resource at `version`.
+///
+/// Patches without a [`WHEN_KEY`] entry apply unconditionally. Conditional
+/// patches apply only when `version` is a semantic version matching the
+/// constraint; resources without a version (local paths, branches, commits)
+/// never match. The `when` key is stripped from the returned data.
+///
+/// Returns `None` when the patch should be skipped.
+pub fn patch_for_version(patch: &PatchData, version: Option<&str>) -> Option<PatchData> {
+    let Some(when) = patch.get(WHEN_KEY) else {
+        return Some(patch.clone());
+    };
+
+    let matches = match (when.as_str(), version) {
+        (Some(constraint), Some(version)) => {
+            crate::version::matches_requirement(version, constraint).unwrap_or_else(|e| {
+                tracing::debug!(
+                    "Skipping patch: cannot match '{version}' against '{constraint}': {e}"
+                );
+                false
+            })
+        }
+        _ => false,
+    };
+
+    matches.then(|| {
+        let mut data = patch.clone();
+        data.remove(WHEN_KEY);
+        data
+    })
+}
+
+/// Check that a `when` value is a valid version constraint.
+///
+/// # Errors
+///
+/// Returns an error describing the problem if `when` is not a string or does
+/// not parse as a version requirement.
+pub fn validate_when(when: &toml::Value) -> anyhow::Result<()> {
+    let constraint =
+        when.as_str().ok_or_else(|| anyhow::anyhow!("'{WHEN_KEY}' must be a version string"))?;
+    for alternative in constraint.split("||") {
+        let (_, requirement) = crate::version::split_prefix_and_version(alternative.trim());
+        crate::version::parse_version_req(requirement).map_err(|e| {
+            anyhow::anyhow!("'{WHEN_KEY}' has invalid version constraint '{constraint}': {e}")
+        })?;
+    }
+    Ok(())
+}
+
 /// Result of applying patches, separated by origin.
 ///
 /// This structure tracks which patches came from project-level configuration
@@ -204,6 +292,19 @@ impl ManifestPatches {
         }
     }
 
+    /// Gets the patch data that applies to a resource at the given resolved version.
+    ///
+    /// Like [`get`](Self::get), but honours the patch's `when` constraint and strips
+    /// it from the result. See [`patch_for_version`].
+    pub fn get_for_version(
+        &self,
+        resource_type: &str,
+        alias: &str,
+        version: Option<&str>,
+    ) -> Option<PatchData> {
+        self.get(resource_type, alias).and_then(|patch| patch_for_version(patch, version))
+    }
+
     /// Merges another patches collection into this one.
     ///
     /// Fields from `other` take precedence over fields in `self`. This is used to
@@ -629,6 +730,54 @@ mod tests {
         assert_eq!(patches.get("snippets", "test-agent"), None);
     }
 
+    fn conditional_patch(when: &str) -> PatchData {
+        BTreeMap::from([
+            (WHEN_KEY.to_string(), toml::Value::String(when.to_string())),
+            ("model".to_string(), toml::Value::String("claude-3-opus".to_string())),
+        ])
+    }
+
+    #[test]
+    fn test_patch_for_version_applies_when_constraint_matches() {
+        let patch = conditional_patch("<v2.0.0");
+        let applied = patch_for_version(&patch, Some("v1.5.0")).unwrap();
+        assert_eq!(applied.get("model"), Some(&toml::Value::String("claude-3-opus".into())));
+        assert!(!applied.contains_key(WHEN_KEY), "when is not a resource field");
+    }
+
+    #[test]
+    fn test_patch_for_version_skips_when_constraint_fails() {
+        let patch = conditional_patch("<v2.0.0");
+        assert_eq!(patch_for_version(&patch, Some("v2.1.0")), None);
+        assert_eq!(patch_for_version(&patch, Some("main")), None);
+        assert_eq!(patch_for_version(&patch, None), None);
+    }
+
+    #[test]
+    fn test_patch_for_version_without_when_always_applies() {
+        let patch =
+            BTreeMap::from([("model".to_string(), toml::Value::String("haiku".to_string()))]);
+        assert_eq!(patch_for_version(&patch, None), Some(patch.clone()));
+        assert_eq!(patch_for_version(&patch, Some("v3.0.0")), Some(patch));
+    }
+
+    #[test]
+    fn test_get_for_version() {
+        let mut patches = ManifestPatches::new();
+        patches.agents.insert("legacy".to_string(), conditional_patch(">=v1.0.0, <v2.0.0"));
+        assert!(patches.get_for_version("agents", "legacy", Some("v1.2.3")).is_some());
+        assert!(patches.get_for_version("agents", "legacy", Some("v2.0.0")).is_none());
+        assert!(patches.get_for_version("agents", "other", Some("v1.2.3")).is_none());
+    }
+
+    #[test]
+    fn test_validate_when() {
+        assert!(validate_when(&toml::Value::String("<v2.0.0".into())).is_ok());
+        assert!(validate_when(&toml::Value::String("^1.0 || ^2.0".into())).is_ok());
+        assert!(validate_when(&toml::Value::String("not a version".into())).is_err());
+        assert!(validate_when(&toml::Value::Integer(2)).is_err());
+    }
+
     #[test]
     fn test_merge_no_conflict() {
         let mut base = ManifestPatches::new();
@@ -0,0 +1,107 @@
+//! Path aliases for sources that move files between releases (`[path-aliases]`).
+//!
+//! When an upstream repository reorganizes its directories, the same logical
+//! resource lives at different paths depending on the tag. A `[path-aliases]`
+//! table per source maps the path written in the manifest to the actual path,
+//! either for every version or per version range:
+//!
+//! ```toml
+//! [path-aliases.community]
+//! "agents/reviewer.md" = "agents/review/reviewer.md"
+//! "agents/linter.md" = [
+//!     { versions = "<2.0.0", path = "agents/linter.md" },
+//!     { versions = ">=2.0.0", path = "agents/quality/linter.md" },
+//! ]
+//! ```
+//!
+//! The alias is chosen for the version each manifest dependency resolves to.
+//! Entries are tried in order and the first whose `versions` range matches the
+//! resolved tag wins; an entry without `versions` matches any version. When no
+//! entry matches, the path is used as written. The lockfile records the actual
+//! path.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::version::matches_requirement;
+
+/// Actual path of an aliased resource, for all versions or per version range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum PathAlias {
+    /// The same actual path for every version.
+    Path(String),
+    /// Actual paths by version range; the first matching entry wins.
+    Versioned(Vec<VersionedPath>),
+}
+
+/// One version-specific entry of a [`PathAlias`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct VersionedPath {
+    /// Semver range of the versions this path applies to, such as `"<2.0.0"`.
+    /// Omit it to match any version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub versions: Option<String>,
+    /// Path within the source at those versions.
+    pub path: String,
+}
+
+impl PathAlias {
+    /// The actual path at `version`, the version part of the resolved tag.
+    ///
+    /// Ranges never match when the dependency did not resolve to a version,
+    /// for example when it tracks a branch.
+    #[must_use]
+    pub fn resolve(&self, version: Option<&str>) -> Option<&str> {
+        match self {
+            Self::Path(path) => Some(path),
+            Self::Versioned(entries) => entries
+                .iter()
+                .find(|entry| match &entry.versions {
+                    None => true,
+                    Some(range) => {
+                        version.is_some_and(|v| matches_requirement(v, range).unwrap_or(false))
+                    }
+                })
+                .map(|entry| entry.path.as_str()),
+        }
+    }
+
+    /// Every actual path this alias can resolve to.
+    pub fn targets(&self) -> impl Iterator<Item = &str> {
+        let (single, entries) = match self {
+            Self::Path(path) => (Some(path.as_str()), &[][..]),
+            Self::Versioned(entries) => (None, entries.as_slice()),
+        };
+        single.into_iter().chain(entries.iter().map(|entry| entry.path.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_by_version_range() {
+        let alias: PathAlias = toml::from_str::<toml::Table>(
+            r#"alias = [
+                { versions = "<2.0.0", path = "agents/linter.md" },
+                { versions = ">=2.0.0", path = "agents/quality/linter.md" },
+            ]"#,
+        )
+        .unwrap()["alias"]
+            .clone()
+            .try_into()
+            .unwrap();
+
+        assert_eq!(alias.resolve(Some("v1.4.0")), Some("agents/linter.md"));
+        assert_eq!(alias.resolve(Some("2.0.0")), Some("agents/quality/linter.md"));
+        assert_eq!(alias.resolve(Some("main")), None);
+        assert_eq!(alias.resolve(None), None);
+        assert_eq!(alias.targets().count(), 2);
+
+        let plain = PathAlias::Path("agents/review/reviewer.md".to_string());
+        assert_eq!(plain.resolve(None), Some("agents/review/reviewer.md"));
+    }
+}
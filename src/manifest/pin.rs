@@ -0,0 +1,242 @@
+//! Pinning manifest dependencies to the versions recorded in the lockfile.
+//!
+//! `agpm pin` rewrites every Git dependency in `agpm.toml` so that it resolves
+//! to exactly what `agpm.lock` currently holds. Range constraints such as
+//! `^1.0.0` become the resolved tag (`v1.2.3`). Branch-tracked dependencies
+//! keep their `branch` (or `version`) for reference and gain a `rev` with the
+//! resolved commit, which takes precedence during resolution; removing the
+//! `rev` resumes tracking the branch.
+//!
+//! The manifest is edited with `toml_edit`, leaving existing comments, ordering
+//! and formatting untouched. Local dependencies and dependencies that already
+//! name a commit are left alone.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use toml_edit::DocumentMut;
+
+use crate::core::ResourceType;
+use crate::lockfile::LockFile;
+use crate::manifest::{Manifest, ResourceDependency};
+use crate::version::{VersionConstraint, parse_version_constraint};
+
+/// How a dependency is pinned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PinChange {
+    /// Replace the `version` constraint with the resolved tag.
+    Version {
+        /// Constraint currently in the manifest.
+        from: String,
+        /// Resolved tag from the lockfile.
+        to: String,
+    },
+    /// Record the resolved commit of a branch-tracked dependency as `rev`.
+    Rev {
+        /// Branch the dependency tracks.
+        branch: String,
+        /// Resolved commit SHA from the lockfile.
+        sha: String,
+    },
+}
+
+/// A manifest dependency to pin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedDependency {
+    /// Manifest key of the dependency.
+    pub name: String,
+    /// Type of the resource, which selects the manifest section.
+    pub resource_type: ResourceType,
+    /// The edit to apply.
+    pub change: PinChange,
+}
+
+/// Collect the dependencies in `manifest` whose constraint is looser than what `lockfile` holds.
+///
+/// Dependencies that are already exact, local, or missing from the lockfile are skipped.
+#[must_use]
+pub fn find_pins(manifest: &Manifest, lockfile: &LockFile) -> Vec<PinnedDependency> {
+    let mut pins = Vec::new();
+
+    for resource_type in ResourceType::all() {
+        let Some(deps) = manifest.get_dependencies(*resource_type) else {
+            continue;
+        };
+        let mut names: Vec<&String> = deps.keys().collect();
+        names.sort();
+
+        for name in names {
+            let ResourceDependency::Detailed(dep) = &deps[name] else {
+                continue;
+            };
+            if dep.source.is_none() || dep.rev.is_some() {
+                continue;
+            }
+
+            // Pattern and multi-tool dependencies lock several entries at one version
+            let Some(entry) = lockfile
+                .get_resources(resource_type)
+                .iter()
+                .find(|entry| entry.manifest_alias.as_deref() == Some(name.as_str()))
+            else {
+                continue;
+            };
+            if entry.is_local() {
+                continue;
+            }
+
+            // Without a version the dependency tracks the default branch (HEAD)
+            let constraint = dep.version.as_deref().unwrap_or("HEAD");
+            let change = match (dep.branch.clone(), parse_version_constraint(constraint)) {
+                (Some(branch), _) | (None, VersionConstraint::Branch(branch)) => {
+                    let Some(sha) = entry.resolved_commit.clone() else {
+                        continue;
+                    };
+                    PinChange::Rev {
+                        branch,
+                        sha,
+                    }
+                }
+                (None, VersionConstraint::Commit(_)) => continue,
+                (None, VersionConstraint::Tag(_)) => {
+                    let Some(resolved) = entry.version.clone().filter(|v| v != constraint) else {
+                        continue;
+                    };
+                    PinChange::Version {
+                        from: constraint.to_string(),
+                        to: resolved,
+                    }
+                }
+            };
+
+            pins.push(PinnedDependency {
+                name: name.clone(),
+                resource_type: *resource_type,
+                change,
+            });
+        }
+    }
+
+    pins
+}
+
+/// Apply `pins` to the manifest at `manifest_path`, preserving its formatting.
+pub fn write_pins(manifest_path: &Path, pins: &[PinnedDependency]) -> Result<()> {
+    if pins.is_empty() {
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(manifest_path)
+        .with_context(|| format!("Failed to read manifest: {}", manifest_path.display()))?;
+    let mut doc: DocumentMut = content
+        .parse()
+        .with_context(|| format!("Failed to parse manifest: {}", manifest_path.display()))?;
+
+    for pin in pins {
+        let section_name = pin.resource_type.to_plural();
+        let item = doc
+            .get_mut(section_name)
+            .and_then(|section| section.get_mut(&pin.name))
+            .with_context(|| format!("Dependency '{}' not found in [{section_name}]", pin.name))?;
+        let (key, value) = match &pin.change {
+            PinChange::Version {
+                to,
+                ..
+            } => ("version", to),
+            PinChange::Rev {
+                sha,
+                ..
+            } => ("rev", sha),
+        };
+
+        if let Some(inline) = item.as_inline_table_mut() {
+            inline.insert(key, toml_edit::Value::from(value.as_str()));
+            // Keep `{ a = 1, b = 2 }` spacing when a key is appended
+            inline.fmt();
+        } else if let Some(table) = item.as_table_like_mut() {
+            table.insert(key, toml_edit::value(value.as_str()));
+        } else {
+            anyhow::bail!("Dependency '{}' in [{section_name}] is not a table", pin.name);
+        }
+    }
+
+    crate::utils::fs::atomic_write(manifest_path, doc.to_string().as_bytes())
+        .with_context(|| format!("Failed to write manifest: {}", manifest_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::LockedResourceBuilder;
+    use tempfile::TempDir;
+
+    fn locked(alias: &str, version: &str, sha: &str) -> crate::lockfile::LockedResource {
+        let mut entry = LockedResourceBuilder::new(
+            format!("agents/{alias}"),
+            format!("agents/{alias}.md"),
+            String::new(),
+            format!(".claude/agents/agpm/{alias}.md"),
+            ResourceType::Agent,
+        )
+        .source(Some("community".to_string()))
+        .version(Some(version.to_string()))
+        .resolved_commit(Some(sha.to_string()))
+        .build();
+        entry.manifest_alias = Some(alias.to_string());
+        entry
+    }
+
+    #[test]
+    fn test_pin_ranges_and_branches() {
+        let temp = TempDir::new().unwrap();
+        let manifest_path = temp.path().join("agpm.toml");
+        std::fs::write(
+            &manifest_path,
+            r#"[sources]
+community = "https://example.com/repo.git"
+
+[agents]
+# Reviewer agent
+reviewer = { source = "community", path = "agents/reviewer.md", version = "^1.0.0" }
+exact = { source = "community", path = "agents/exact.md", version = "v2.0.0" }
+nightly = { source = "community", path = "agents/nightly.md", branch = "main" }
+local = { path = "local/agent.md" }
+"#,
+        )
+        .unwrap();
+        let manifest = Manifest::load(&manifest_path).unwrap();
+
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+        let lockfile = LockFile {
+            agents: vec![
+                locked("reviewer", "v1.4.2", sha),
+                locked("exact", "v2.0.0", sha),
+                locked("nightly", "main", sha),
+            ],
+            ..Default::default()
+        };
+
+        let pins = find_pins(&manifest, &lockfile);
+        assert_eq!(pins.len(), 2);
+        assert_eq!(pins[0].name, "nightly");
+        assert_eq!(
+            pins[1].change,
+            PinChange::Version {
+                from: "^1.0.0".to_string(),
+                to: "v1.4.2".to_string()
+            }
+        );
+
+        write_pins(&manifest_path, &pins).unwrap();
+        let updated = std::fs::read_to_string(&manifest_path).unwrap();
+        assert!(updated.contains(
+            "# Reviewer agent\nreviewer = { source = \"community\", path = \"agents/reviewer.md\", version = \"v1.4.2\" }"
+        ));
+        assert!(updated.contains(&format!(
+            "nightly = {{ source = \"community\", path = \"agents/nightly.md\", branch = \"main\", rev = \"{sha}\" }}"
+        )));
+
+        // Everything is exact after pinning
+        let manifest = Manifest::load(&manifest_path).unwrap();
+        assert!(find_pins(&manifest, &lockfile).is_empty());
+    }
+}
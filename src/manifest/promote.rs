@@ -0,0 +1,255 @@
+//! Promotion of transitive dependencies to direct manifest entries.
+//!
+//! `agpm install --save-transitive` writes every Git-sourced transitive
+//! dependency found during resolution into the matching manifest section,
+//! pinned to its resolved version, so it can be patched or pinned like any
+//! other dependency. The manifest is edited with `toml_edit`, leaving existing
+//! comments, ordering and formatting untouched.
+//!
+//! Promotion is idempotent: a dependency already declared in its section with
+//! the same source and path is never added twice. Local transitive
+//! dependencies are skipped, as their paths are relative to the file that
+//! declared them rather than to the project.
+//!
+//! Only `agpm.toml` itself is considered: the manifest passed in must be
+//! loaded without the private or environment overlays, and dependencies that
+//! are private or come from a source `agpm.toml` does not define are never
+//! promoted, so personal and per-environment entries stay out of the shared
+//! manifest.
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::Path;
+use toml_edit::{DocumentMut, InlineTable, Item, Table};
+
+use crate::core::ResourceType;
+use crate::lockfile::LockFile;
+use crate::manifest::Manifest;
+
+/// A transitive dependency to be added to the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromotedDependency {
+    /// Manifest key, derived from the file name of the resource.
+    pub name: String,
+    /// Type of the resource, which selects the manifest section.
+    pub resource_type: ResourceType,
+    /// Source repository name.
+    pub source: String,
+    /// Path of the resource within the source repository.
+    pub path: String,
+    /// Resolved version (tag, branch, or commit) to pin.
+    pub version: String,
+    /// Tool override, only set when it differs from the section default.
+    pub tool: Option<String>,
+    /// Whether the resource is content-only (`install = false`).
+    pub content_only: bool,
+}
+
+/// Collect the transitive dependencies in `lockfile` that `manifest` does not declare.
+///
+/// `manifest` is the project manifest as returned by [`Manifest::load`].
+/// Manifest keys are unique within each section; when the file name is already
+/// taken a numeric suffix is appended.
+#[must_use]
+pub fn find_transitive_dependencies(
+    manifest: &Manifest,
+    lockfile: &LockFile,
+) -> Vec<PromotedDependency> {
+    let mut promoted = Vec::new();
+
+    for resource_type in ResourceType::all() {
+        let declared = manifest.get_dependencies(*resource_type);
+        let mut taken: HashSet<String> =
+            declared.map(|deps| deps.keys().cloned().collect()).unwrap_or_default();
+        let mut seen = HashSet::new();
+
+        for entry in lockfile.get_resources(resource_type) {
+            // Direct and pattern-expanded entries carry the manifest key they came from
+            if entry.manifest_alias.is_some() {
+                continue;
+            }
+            let Some(source) = &entry.source else {
+                continue;
+            };
+            // Sources from agpm.private.toml or agpm.<env>.toml cannot be written to agpm.toml
+            if entry.is_private || !manifest.sources.contains_key(source) {
+                continue;
+            }
+            let Some(version) = entry.version.clone().or_else(|| entry.resolved_commit.clone())
+            else {
+                continue;
+            };
+
+            let path = entry.path.trim_start_matches("./").to_string();
+            let already_declared = declared.is_some_and(|deps| {
+                deps.values().any(|dep| {
+                    dep.get_source() == Some(source.as_str())
+                        && dep.get_path().trim_start_matches("./") == path
+                })
+            });
+            // The same resource may be locked once per tool; promote it only once
+            if already_declared || !seen.insert((source.clone(), path.clone())) {
+                continue;
+            }
+
+            let stem = Path::new(&path)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or(entry.name.as_str())
+                .to_string();
+            let mut name = stem.clone();
+            let mut suffix = 2;
+            while taken.contains(&name) {
+                name = format!("{stem}-{suffix}");
+                suffix += 1;
+            }
+            taken.insert(name.clone());
+
+            let tool = entry
+                .tool
+                .clone()
+                .filter(|tool| *tool != manifest.get_default_tool(*resource_type));
+
+            promoted.push(PromotedDependency {
+                name,
+                resource_type: *resource_type,
+                source: source.clone(),
+                path,
+                version,
+                tool,
+                content_only: entry.install == Some(false),
+            });
+        }
+    }
+
+    promoted
+}
+
+/// Add `dependencies` to the manifest at `manifest_path`, preserving its formatting.
+///
+/// Missing sections are appended to the end of the file. Each dependency is
+/// written as an inline table, matching the style of `agpm add dep`.
+pub fn write_promoted_dependencies(
+    manifest_path: &Path,
+    dependencies: &[PromotedDependency],
+) -> Result<()> {
+    if dependencies.is_empty() {
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(manifest_path)
+        .with_context(|| format!("Failed to read manifest: {}", manifest_path.display()))?;
+    let mut doc: DocumentMut = content
+        .parse()
+        .with_context(|| format!("Failed to parse manifest: {}", manifest_path.display()))?;
+
+    for dep in dependencies {
+        let section_name = dep.resource_type.to_plural();
+        let section = doc
+            .entry(section_name)
+            .or_insert_with(|| Item::Table(Table::new()))
+            .as_table_like_mut()
+            .with_context(|| format!("[{section_name}] in the manifest is not a table"))?;
+
+        let mut inline = InlineTable::new();
+        inline.insert("source", toml_edit::Value::from(dep.source.as_str()));
+        inline.insert("path", toml_edit::Value::from(dep.path.as_str()));
+        inline.insert("version", toml_edit::Value::from(dep.version.as_str()));
+        if let Some(tool) = &dep.tool {
+            inline.insert("tool", toml_edit::Value::from(tool.as_str()));
+        }
+        if dep.content_only {
+            inline.insert("install", toml_edit::Value::from(false));
+        }
+        section.insert(&dep.name, toml_edit::value(inline));
+    }
+
+    crate::utils::fs::atomic_write(manifest_path, doc.to_string().as_bytes())
+        .with_context(|| format!("Failed to write manifest: {}", manifest_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::LockedResourceBuilder;
+    use tempfile::TempDir;
+
+    fn locked(name: &str, path: &str, alias: Option<&str>) -> crate::lockfile::LockedResource {
+        let mut entry = LockedResourceBuilder::new(
+            name.to_string(),
+            path.to_string(),
+            String::new(),
+            format!(".claude/agents/agpm/{name}.md"),
+            ResourceType::Agent,
+        )
+        .source(Some("community".to_string()))
+        .version(Some("v1.0.0".to_string()))
+        .tool(Some("claude-code".to_string()))
+        .build();
+        entry.manifest_alias = alias.map(str::to_string);
+        entry
+    }
+
+    #[test]
+    fn test_promote_transitive_dependencies() {
+        let temp = TempDir::new().unwrap();
+        let manifest_path = temp.path().join("agpm.toml");
+        let original = "# Team manifest\n[sources]\ncommunity = \"https://example.com/repo.git\"\n\n[agents]\n# The main entry point\nhelper = { source = \"community\", path = \"agents/main.md\", version = \"v1.0.0\" }\n";
+        std::fs::write(&manifest_path, original).unwrap();
+        let manifest = Manifest::load(&manifest_path).unwrap();
+
+        let lockfile = LockFile {
+            agents: vec![
+                locked("helper", "agents/main.md", Some("helper")),
+                locked("agents/helper", "agents/helper.md", None),
+            ],
+            ..Default::default()
+        };
+
+        let promoted = find_transitive_dependencies(&manifest, &lockfile);
+        assert_eq!(promoted.len(), 1);
+        // "helper" is already used as a key, so the new entry gets a suffix
+        assert_eq!(promoted[0].name, "helper-2");
+        assert_eq!(promoted[0].path, "agents/helper.md");
+        assert_eq!(promoted[0].tool, None);
+
+        write_promoted_dependencies(&manifest_path, &promoted).unwrap();
+        let updated = std::fs::read_to_string(&manifest_path).unwrap();
+        assert!(updated.starts_with(original), "existing content must be preserved:\n{updated}");
+        assert!(updated.ends_with(
+            "helper-2 = { source = \"community\", path = \"agents/helper.md\", version = \"v1.0.0\" }\n"
+        ));
+
+        // A second pass finds nothing left to promote
+        let manifest = Manifest::load(&manifest_path).unwrap();
+        assert!(find_transitive_dependencies(&manifest, &lockfile).is_empty());
+    }
+
+    #[test]
+    fn test_skip_private_and_overlay_sources() {
+        let temp = TempDir::new().unwrap();
+        let manifest_path = temp.path().join("agpm.toml");
+        std::fs::write(&manifest_path, "[sources]\ncommunity = \"https://example.com/repo.git\"\n")
+            .unwrap();
+        // A staging source defined only by an overlay
+        std::fs::write(
+            temp.path().join("agpm.staging.toml"),
+            "[sources]\nstaging = \"https://example.com/staging.git\"\n",
+        )
+        .unwrap();
+        let manifest = Manifest::load(&manifest_path).unwrap();
+
+        let mut from_overlay = locked("agents/canary", "agents/canary.md", None);
+        from_overlay.source = Some("staging".to_string());
+        let mut private = locked("agents/mine", "agents/mine.md", None);
+        private.is_private = true;
+        let lockfile = LockFile {
+            agents: vec![from_overlay, private, locked("agents/shared", "agents/shared.md", None)],
+            ..Default::default()
+        };
+
+        let promoted = find_transitive_dependencies(&manifest, &lockfile);
+        assert_eq!(promoted.len(), 1);
+        assert_eq!(promoted[0].name, "shared");
+    }
+}
@@ -4,7 +4,9 @@
 //! - `ResourceDependency`: Enum supporting both simple path-only and detailed specifications
 //! - `DetailedDependency`: Full dependency specification with all configuration options
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use crate::manifest::dependency_spec::DependencySpec;
@@ -14,7 +16,7 @@ use crate::manifest::dependency_spec::DependencySpec;
 /// Dependencies can be specified in two main formats to balance simplicity
 /// with flexibility. The enum uses Serde's `untagged` attribute to automatically
 /// deserialize the correct variant based on the TOML structure.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum ResourceDependency {
     /// Simple path-only dependency, typically for local files.
@@ -119,7 +121,7 @@ pub enum ResourceDependency {
 /// Paths are interpreted differently based on context:
 /// - **Remote dependencies**: Path within the Git repository
 /// - **Local dependencies**: Filesystem path relative to manifest directory
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DetailedDependency {
     /// Source repository name referencing the `[sources]` section.
     ///
@@ -487,6 +489,111 @@ pub struct DetailedDependency {
     /// `python-best-practices.md`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub template_vars: Option<serde_json::Value>,
+
+    /// Expected SHA-256 of the installed content.
+    ///
+    /// Pins the resource to exact content independently of the Git commit, so a
+    /// re-tagged upstream release cannot change it silently. The installer hashes
+    /// the final content (after patches and templating) and fails without writing
+    /// it if the hash differs. Takes the value recorded as `checksum` in
+    /// `agpm.lock`, with or without the `sha256:` prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [agents]
+    /// reviewer = {
+    ///     source = "community",
+    ///     path = "agents/reviewer.md",
+    ///     version = "v1.0.0",
+    ///     sha256 = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+    /// }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+
+    /// What to do when this dependency installs to the same path as another.
+    ///
+    /// By default a target path collision fails resolution. With
+    /// `on_conflict = "rename"`, a colliding file from a Git source is installed
+    /// as `<source>-<filename>` instead, and the renamed path is recorded in
+    /// `agpm.lock`. Mostly useful for pattern dependencies that pull similarly
+    /// named files from several sources.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [agents]
+    /// official = { source = "official", path = "agents/*.md", on_conflict = "rename" }
+    /// community = { source = "community", path = "agents/*.md", on_conflict = "rename" }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_conflict: Option<ConflictPolicy>,
+
+    /// Install pattern matches into subdirectories mirroring their source layout.
+    ///
+    /// Only applies to pattern dependencies. When `true`, each matched file is
+    /// installed at its path relative to the pattern's fixed base (the leading
+    /// components before the first glob), so `agents/team/review/lint.md` matched
+    /// by `agents/**/*.md` installs as `team/review/lint.md`. Takes precedence
+    /// over `flatten`. Matches that would resolve outside the install directory
+    /// are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [agents]
+    /// team = { source = "community", path = "agents/**/*.md", version = "v1.0.0", preserve_structure = true }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preserve_structure: Option<bool>,
+
+    /// Release channel: a regex over the annotation message of candidate tags.
+    ///
+    /// Only applies to version ranges and `latest-by-date`. Tags whose
+    /// annotation message (as shown by `git cat-file tag`) does not match are
+    /// not considered, and lightweight tags never match, so upstreams that mark
+    /// releases as `stable` or `canary` in their tag messages can be followed
+    /// per channel.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [agents]
+    /// reviewer = { source = "community", path = "agents/reviewer.md", version = "^1.0.0", channel = "stable" }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+
+    /// Install order relative to other dependencies; higher values install first.
+    ///
+    /// Resources are written in priority tiers: every resource of a higher
+    /// priority is on disk before any resource of a lower one is installed.
+    /// Within a tier the order is by resource type, then name. Defaults to `0`,
+    /// so manifests without priorities keep the alphabetical order. Applies to
+    /// the dependency and the matches of a pattern; transitive dependencies
+    /// install at the default priority.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [scripts]
+    /// # Written before the hook that invokes it
+    /// lint = { source = "community", path = "scripts/lint.sh", version = "v1.0.0", priority = 10 }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i32>,
+}
+
+/// Policy for a dependency whose target path collides with another dependency.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    /// Fail resolution with a target path conflict error.
+    #[default]
+    Error,
+    /// Prefix the installed filename with the source name.
+    Rename,
 }
 
 impl ResourceDependency {
@@ -521,6 +628,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert_eq!(remote.get_source(), Some("official"));
     /// assert_eq!(remote.get_source(), Some("official"));
@@ -567,6 +679,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert_eq!(custom.get_target(), Some("custom/tools"));
     ///
@@ -634,6 +751,11 @@ impl ResourceDependency {
     ///     install: None,
     ///     flatten: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert_eq!(custom.get_filename(), Some("ai-assistant.md"));
     ///
@@ -693,6 +815,39 @@ impl ResourceDependency {
         }
     }
 
+    /// Set the install flag for this dependency.
+    ///
+    /// A simple path dependency is converted to the detailed form, since only
+    /// that form can carry the flag.
+    pub fn set_install(&mut self, install: Option<bool>) {
+        match self {
+            Self::Detailed(d) => d.install = install,
+            Self::Simple(path) => {
+                *self = Self::Detailed(Box::new(DetailedDependency {
+                    source: None,
+                    path: std::mem::take(path),
+                    version: None,
+                    branch: None,
+                    rev: None,
+                    command: None,
+                    args: None,
+                    target: None,
+                    filename: None,
+                    dependencies: None,
+                    tool: None,
+                    flatten: None,
+                    install,
+                    template_vars: None,
+                    sha256: None,
+                    on_conflict: None,
+                    preserve_structure: None,
+                    channel: None,
+                    priority: None,
+                }));
+            }
+        }
+    }
+
     /// Get the template variable overrides for this resource.
     ///
     /// Returns the resource-specific template variables that override the global
@@ -724,6 +879,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(json!({ "project": { "language": "golang" } })),
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     ///
     /// assert!(resource.get_template_vars().is_some());
@@ -735,6 +895,46 @@ impl ResourceDependency {
         }
     }
 
+    /// Get the expected SHA-256 of the installed content, if pinned.
+    pub fn get_sha256(&self) -> Option<&str> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Detailed(d) => d.sha256.as_deref(),
+        }
+    }
+
+    /// Get the target path conflict policy, defaulting to [`ConflictPolicy::Error`].
+    pub fn get_on_conflict(&self) -> ConflictPolicy {
+        match self {
+            Self::Simple(_) => ConflictPolicy::Error,
+            Self::Detailed(d) => d.on_conflict.unwrap_or_default(),
+        }
+    }
+
+    /// Whether pattern matches keep their directory layout below the pattern base.
+    pub fn get_preserve_structure(&self) -> bool {
+        match self {
+            Self::Simple(_) => false,
+            Self::Detailed(d) => d.preserve_structure.unwrap_or(false),
+        }
+    }
+
+    /// Get the release channel pattern that candidate tag messages must match.
+    pub fn get_channel(&self) -> Option<&str> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Detailed(d) => d.channel.as_deref(),
+        }
+    }
+
+    /// Get the install priority, defaulting to `0`.
+    pub fn get_priority(&self) -> i32 {
+        match self {
+            Self::Simple(_) => 0,
+            Self::Detailed(d) => d.priority.unwrap_or(0),
+        }
+    }
+
     /// Get the path to the resource file.
     ///
     /// Returns the path component of the dependency, which is interpreted
@@ -768,6 +968,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert_eq!(remote.get_path(), "agents/code-reviewer.md");
     /// ```
@@ -792,11 +997,47 @@ impl ResourceDependency {
     /// multiple resources, `false` if it specifies a single resource path.
     ///
     /// Patterns are detected by the presence of glob characters (`*`, `?`, `[`)
-    /// in the path field.
+    /// in the path field. Directory references (see
+    /// [`is_directory_reference`](Self::is_directory_reference)) are patterns too.
     #[must_use]
     pub fn is_pattern(&self) -> bool {
         let path = self.get_path();
-        path.contains('*') || path.contains('?') || path.contains('[')
+        path.contains('*')
+            || path.contains('?')
+            || path.contains('[')
+            || self.is_directory_reference()
+    }
+
+    /// Check if this dependency references a whole directory.
+    ///
+    /// A path ending in `/` (e.g. `agents/review/`) selects every supported
+    /// resource directly inside that directory. Unlike `*` and `**` globs,
+    /// subdirectories are never searched.
+    #[must_use]
+    pub fn is_directory_reference(&self) -> bool {
+        self.get_path().ends_with('/')
+    }
+
+    /// The glob a pattern dependency is expanded with.
+    ///
+    /// Directory references become a glob over the resource files of
+    /// `resource_type` in that directory: `*.md` for agents, snippets and
+    /// commands, `*.json` for hooks and MCP servers, and every entry for
+    /// scripts and skills. Other paths are returned unchanged.
+    #[must_use]
+    pub fn expansion_pattern(&self, resource_type: crate::core::ResourceType) -> Cow<'_, str> {
+        use crate::core::ResourceType;
+
+        let path = self.get_path();
+        if !self.is_directory_reference() {
+            return Cow::Borrowed(path);
+        }
+        let glob = match resource_type {
+            ResourceType::Agent | ResourceType::Snippet | ResourceType::Command => "*.md",
+            ResourceType::Hook | ResourceType::McpServer => "*.json",
+            ResourceType::Script | ResourceType::Skill => "*",
+        };
+        Cow::Owned(format!("{path}{glob}"))
     }
 
     /// Get the version constraint for dependency resolution.
@@ -827,6 +1068,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     ///
     /// assert_eq!(dep.get_version(), Some("develop"));
@@ -857,6 +1103,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert_eq!(versioned.get_version(), Some("v1.0.0"));
     ///
@@ -876,6 +1127,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert_eq!(branch_ref.get_version(), Some("main"));
     /// ```
@@ -935,6 +1191,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert!(!remote.is_local());
     ///
@@ -954,6 +1215,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert!(local_detailed.is_local());
     /// ```
@@ -1002,6 +1268,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: None,
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert_eq!(versioned.resolution_mode(), ResolutionMode::Version);
     ///
@@ -1021,6 +1292,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: None,
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert_eq!(git_ref.resolution_mode(), ResolutionMode::GitRef);
     /// ```
@@ -1060,6 +1336,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: None,
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert_eq!(versioned.get_version_constraint(), Some("^1.0.0"));
     ///
@@ -1079,6 +1360,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: None,
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert_eq!(git_ref.get_version_constraint(), None);
     /// ```
@@ -1125,6 +1411,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: None,
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert_eq!(branch_ref.get_git_ref(), Some("main"));
     ///
@@ -1144,6 +1435,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: None,
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert_eq!(versioned.get_git_ref(), None);
     /// ```
@@ -1202,6 +1498,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: None,
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert!(branch.is_mutable());
     ///
@@ -1221,6 +1522,11 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: None,
+    ///     sha256: None,
+    ///     on_conflict: None,
+    ///     preserve_structure: None,
+    ///     channel: None,
+    ///     priority: None,
     /// }));
     /// assert!(!versioned.is_mutable());
     /// ```
@@ -1279,6 +1585,11 @@ impl ResourceDependency {
             return true;
         }
 
+        // Resolves to a tag and stays locked until `agpm update`, like a range
+        if version == crate::version::LATEST_BY_DATE {
+            return false;
+        }
+
         // Full SHA (40 hex chars) is immutable - it points to a specific commit
         if version.len() == 40 && version.chars().all(|c| c.is_ascii_hexdigit()) {
             return false;
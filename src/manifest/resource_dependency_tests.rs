@@ -28,6 +28,11 @@ fn detailed_dep(
         flatten: None,
         install: None,
         template_vars: None,
+        sha256: None,
+        on_conflict: None,
+        preserve_structure: None,
+        channel: None,
+        priority: None,
     }
 }
 
@@ -142,6 +147,12 @@ mod branch_detection_tests {
         assert!(!ResourceDependency::is_branch_like_version("^v1.0.0"));
     }
 
+    #[test]
+    fn test_latest_by_date_keyword() {
+        assert!(!ResourceDependency::is_branch_like_version("latest-by-date"));
+        assert!(ResourceDependency::is_branch_like_version("latest"));
+    }
+
     #[test]
     fn test_semver_tilde() {
         assert!(!ResourceDependency::is_branch_like_version("~1.0.0"));
@@ -226,3 +237,36 @@ mod branch_detection_tests {
         )); // 39 chars
     }
 }
+
+mod directory_reference_tests {
+    use super::*;
+    use crate::core::ResourceType;
+
+    #[test]
+    fn test_directory_reference_is_pattern() {
+        let dep = ResourceDependency::Detailed(Box::new(detailed_dep(
+            Some("repo"),
+            "agents/review/",
+            Some("v1.0.0"),
+            None,
+            None,
+        )));
+        assert!(dep.is_directory_reference());
+        assert!(dep.is_pattern());
+
+        let file = ResourceDependency::Simple("agents/review.md".to_string());
+        assert!(!file.is_directory_reference());
+        assert!(!file.is_pattern());
+    }
+
+    #[test]
+    fn test_expansion_pattern_by_resource_type() {
+        let dep = ResourceDependency::Simple("shared/".to_string());
+        assert_eq!(dep.expansion_pattern(ResourceType::Agent), "shared/*.md");
+        assert_eq!(dep.expansion_pattern(ResourceType::Hook), "shared/*.json");
+        assert_eq!(dep.expansion_pattern(ResourceType::Script), "shared/*");
+
+        let glob = ResourceDependency::Simple("agents/**/*.md".to_string());
+        assert_eq!(glob.expansion_pattern(ResourceType::Agent), "agents/**/*.md");
+    }
+}
@@ -0,0 +1,183 @@
+//! JSON Schema for `agpm.toml`.
+//!
+//! The schema is generated from the serde types that parse the manifest, so it
+//! describes the same sources, resource sections, `[tools]`, `[patch]` and
+//! configuration tables the parser accepts. It targets draft-07, which most
+//! TOML-aware editors understand, and is printed by `agpm schema`.
+//!
+//! Private manifests (`agpm.private.toml`) and environment overlays use the
+//! same structure and can be validated against the same schema.
+
+use schemars::generate::SchemaSettings;
+
+use super::Manifest;
+
+/// Title used for the generated schema.
+pub const SCHEMA_TITLE: &str = "agpm.toml";
+
+/// Generate the JSON Schema describing the manifest format.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use agpm_cli::manifest::schema::manifest_schema;
+///
+/// let schema = manifest_schema();
+/// assert_eq!(schema["title"], "agpm.toml");
+/// assert!(schema["properties"]["agents"].is_object());
+/// ```
+#[must_use]
+pub fn manifest_schema() -> serde_json::Value {
+    let mut schema = SchemaSettings::draft07().into_generator().into_root_schema_for::<Manifest>();
+    schema.insert("title".to_string(), SCHEMA_TITLE.into());
+    schema.insert(
+        "description".to_string(),
+        "AGPM project manifest: sources, dependencies and project configuration".into(),
+    );
+    schema.to_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator() -> jsonschema::Validator {
+        jsonschema::validator_for(&manifest_schema()).expect("schema should compile")
+    }
+
+    fn errors(toml_text: &str) -> Vec<String> {
+        let value: toml::Value = toml::from_str(toml_text).unwrap();
+        let instance = serde_json::to_value(value).unwrap();
+        validator()
+            .iter_errors(&instance)
+            .map(|e| format!("{} at {}", e, e.instance_path))
+            .collect()
+    }
+
+    #[test]
+    fn test_sample_manifest_validates_against_schema() {
+        let sample = r#"
+gitignore = false
+require_tag = true
+require_validated = true
+tag_preference = "requested"
+token_warning_threshold = 50000
+
+[sources]
+community = "https://github.com/aig787/agpm-community.git"
+
+[tools.claude-code]
+path = ".claude"
+resources = { agents = { path = "agents/agpm", flatten = true } }
+
+[tools.custom]
+path = ".custom"
+enabled = false
+resources = { hooks = { merge-target = ".custom/hooks.json" } }
+
+[default-tools]
+snippets = "agpm"
+
+[default-versions]
+community = "^1.0.0"
+
+[tag-patterns]
+community = "release/{version}"
+
+[project]
+style_guide = "docs/STYLE.md"
+paths = { architecture = "docs/ARCH.md" }
+
+[installer]
+header = true
+install_mode = "symlink"
+
+[install]
+hooks = false
+
+[transitive]
+field = "requires"
+restrict_sources = true
+allowed_sources = ["community"]
+
+[constraints]
+"snippets/helper" = "v1.2.0"
+
+[resolution]
+duplicate_name = "newest"
+
+[integrity]
+normalize_eol = true
+
+[workspace]
+members = ["packages/app"]
+
+[agents]
+simple = "../local/agents/simple.md"
+reviewer = { source = "community", path = "agents/reviewer.md", version = "^1.0.0", tool = "claude-code", on_conflict = "rename" }
+all = { source = "community", path = "agents/ai/*.md", version = "v1.0.0", template_vars = { lang = "rust" } }
+
+[snippets]
+style = { source = "community", path = "snippets/style.md", branch = "main", install = false }
+
+[mcp-servers]
+files = { source = "community", path = "mcp-servers/files.json", rev = "abc1234" }
+
+[patch.agents.reviewer]
+when = "<v2.0.0"
+model = "claude-3-haiku"
+max_tokens = 2000
+"#;
+        assert_eq!(errors(sample), Vec::<String>::new());
+        // The sample must also be a manifest the parser accepts
+        toml::from_str::<Manifest>(sample).unwrap();
+    }
+
+    #[test]
+    fn test_example_manifests_validate_against_schema() {
+        let examples = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("docs/examples");
+        let mut checked = 0;
+        for entry in std::fs::read_dir(examples).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|ext| ext == "toml") {
+                let text = std::fs::read_to_string(&path).unwrap();
+                assert_eq!(errors(&text), Vec::<String>::new(), "{}", path.display());
+                checked += 1;
+            }
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn test_schema_rejects_invalid_manifest() {
+        assert!(!errors("[sources]\ncommunity = 1\n").is_empty());
+        assert!(!errors("[agents]\nbad = { source = \"community\" }\n").is_empty());
+        assert!(!errors("[installer]\ninstall_mode = \"hardlink\"\n").is_empty());
+        assert!(!errors("[patch.agents.reviewer]\nwhen = 2\n").is_empty());
+    }
+
+    #[test]
+    fn test_schema_describes_manifest_sections() {
+        let schema = manifest_schema();
+        assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+        let properties = schema["properties"].as_object().unwrap();
+        for section in [
+            "sources",
+            "tools",
+            "agents",
+            "snippets",
+            "commands",
+            "scripts",
+            "hooks",
+            "mcp-servers",
+            "skills",
+            "patch",
+            "default-tools",
+        ] {
+            assert!(properties.contains_key(section), "missing {section}");
+        }
+        // Internal state is not part of the file format
+        assert!(!properties.contains_key("manifest_dir"));
+        assert!(!properties.contains_key("project_patches"));
+    }
+}
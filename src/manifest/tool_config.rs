@@ -47,9 +47,10 @@
 //! flatten = true
 //! ```
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 // Cached default configuration to avoid repeated allocations
@@ -64,7 +65,7 @@ static DEFAULT_TOOLS_CONFIG: OnceLock<ToolsConfig> = OnceLock::new();
 ///
 /// At least one of `path` or `merge_target` should be set for a resource type
 /// to be considered supported by a tool.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 pub struct ResourceConfig {
     /// Subdirectory path for this resource type relative to the tool's base directory.
     ///
@@ -160,7 +161,8 @@ impl WellKnownTool {
 ///
 /// This is used during deserialization to capture optional fields.
 /// The public API uses `ArtifactTypeConfig` with required `enabled` field.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[schemars(rename = "ToolConfig")]
 struct ArtifactTypeConfigRaw {
     /// Base directory for this tool (e.g., ".claude", ".opencode", ".agpm")
     path: PathBuf,
@@ -265,6 +267,15 @@ impl<'de> serde::Deserialize<'de> for ToolsConfig {
                 let merged_resources = if let Some(default_config) = defaults.types.get(&tool_name)
                 {
                     let mut resources = default_config.resources.clone();
+                    // Default merge targets inside the default base directory follow a
+                    // relocated base (e.g. `.claude/settings.local.json`)
+                    if raw_config.path != default_config.path {
+                        for resource in resources.values_mut() {
+                            resource.merge_target = resource.merge_target.take().map(|target| {
+                                rebase_merge_target(target, &default_config.path, &raw_config.path)
+                            });
+                        }
+                    }
                     // User-provided resources override defaults
                     resources.extend(raw_config.resources);
                     resources
@@ -289,6 +300,28 @@ impl<'de> serde::Deserialize<'de> for ToolsConfig {
     }
 }
 
+/// The schema describes the TOML input, which is the raw per-tool configuration.
+impl JsonSchema for ToolsConfig {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "ToolsConfig".into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        generator.subschema_for::<HashMap<String, ArtifactTypeConfigRaw>>()
+    }
+}
+
+/// Move a default merge target that lives under `default_base` to `base`.
+///
+/// Targets outside the default base directory (such as `.mcp.json` at the
+/// project root) are returned unchanged.
+fn rebase_merge_target(target: String, default_base: &Path, base: &Path) -> String {
+    match Path::new(&target).strip_prefix(default_base) {
+        Ok(rest) => crate::utils::normalize_path_for_storage(base.join(rest)),
+        Err(_) => target,
+    }
+}
+
 impl Default for ToolsConfig {
     fn default() -> Self {
         use crate::core::ResourceType;
@@ -448,4 +448,24 @@ test-tool = { path = ".test", enabled = true, resources = { agents = { path = "a
             assert!(resource.merge_target.is_some());
         }
     }
+
+    #[test]
+    fn test_relocated_base_moves_default_merge_targets() {
+        let toml = r#"
+claude-code = { path = "config/.claude" }
+opencode = { path = ".opencode", resources = { mcp-servers = { merge-target = "custom.json" } } }
+"#;
+
+        let config: ToolsConfig = toml::from_str(toml).unwrap();
+        let claude = config.types.get("claude-code").unwrap();
+        let merge_target = |name: &str| claude.resources[name].merge_target.clone();
+        assert_eq!(merge_target("hooks").as_deref(), Some("config/.claude/settings.local.json"));
+        // Targets outside the base directory stay put
+        assert_eq!(merge_target("mcp-servers").as_deref(), Some(".mcp.json"));
+        assert_eq!(claude.resources["agents"].path.as_deref(), Some("agents/agpm"));
+
+        // Explicit targets are never rewritten
+        let opencode = config.types.get("opencode").unwrap();
+        assert_eq!(opencode.resources["mcp-servers"].merge_target.as_deref(), Some("custom.json"));
+    }
 }
@@ -0,0 +1,184 @@
+//! Workspaces of several AGPM projects installed together.
+//!
+//! A root `agpm.toml` can list member projects in a `[workspace]` section:
+//!
+//! ```toml
+//! [workspace]
+//! members = ["services/api", "services/web"]
+//! ```
+//!
+//! Each member is an ordinary project with its own `agpm.toml` and `agpm.lock`,
+//! so it can still be installed on its own. Running `agpm install` in the root
+//! installs every member, followed by the root's own dependencies if it has any.
+//! Members resolve through the same Git cache, so a source shared by several
+//! members is only fetched once.
+//!
+//! After installing, the member lockfiles are compared: a resource name that
+//! resolves to different content in different members is reported as a
+//! conflict, since it usually means the members drifted apart by accident.
+
+use anyhow::{Result, bail};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::lockfile::{LockFile, LockedResource};
+
+/// Member projects from the `[workspace]` section of `agpm.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(default)]
+pub struct WorkspaceConfig {
+    /// Member project directories (or their `agpm.toml` files), relative to
+    /// the root manifest directory unless absolute.
+    pub members: Vec<PathBuf>,
+}
+
+impl WorkspaceConfig {
+    /// Whether the section is absent or lists no members.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Paths of the member manifests, resolved against `root_dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a member has no `agpm.toml`.
+    pub fn member_manifests(&self, root_dir: &Path) -> Result<Vec<PathBuf>> {
+        self.members
+            .iter()
+            .map(|member| {
+                let path = root_dir.join(member);
+                let manifest_path = if path.is_dir() {
+                    path.join("agpm.toml")
+                } else {
+                    path
+                };
+                if !manifest_path.is_file() {
+                    bail!(
+                        "Workspace member '{}' has no agpm.toml (expected {})",
+                        member.display(),
+                        manifest_path.display()
+                    );
+                }
+                Ok(manifest_path)
+            })
+            .collect()
+    }
+}
+
+/// A resource name that resolves differently in two or more members.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceConflict {
+    /// Plural resource type, e.g. `agents`.
+    pub resource_type: String,
+    /// Manifest name of the resource.
+    pub name: String,
+    /// Member and what the resource resolves to there, one per member.
+    pub resolutions: Vec<(String, String)>,
+}
+
+impl fmt::Display for WorkspaceConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}:", self.resource_type, self.name)?;
+        for (member, resolution) in &self.resolutions {
+            write!(f, "\n    {member}: {resolution}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Find resource names that resolve to different content across member lockfiles.
+///
+/// Resources are keyed by type and manifest name; only direct dependencies are
+/// compared, as transitive ones follow from them. `members` pairs each member's
+/// display name with its lockfile.
+#[must_use]
+pub fn find_conflicts(members: &[(String, LockFile)]) -> Vec<WorkspaceConflict> {
+    let mut resolutions: BTreeMap<(String, String), Vec<(String, String)>> = BTreeMap::new();
+    for (member, lockfile) in members {
+        for resource in lockfile.all_resources() {
+            let Some(name) = resource.manifest_alias.as_deref() else {
+                continue;
+            };
+            resolutions
+                .entry((resource.resource_type.to_plural().to_string(), name.to_string()))
+                .or_default()
+                .push((member.clone(), describe(resource)));
+        }
+    }
+
+    resolutions
+        .into_iter()
+        .filter(|(_, found)| found.iter().any(|(_, resolution)| *resolution != found[0].1))
+        .map(|((resource_type, name), resolutions)| WorkspaceConflict {
+            resource_type,
+            name,
+            resolutions,
+        })
+        .collect()
+}
+
+/// Describe what a resource resolves to, e.g. `community:agents/a.md@v1.0.0 (abc1234)`.
+fn describe(resource: &LockedResource) -> String {
+    let mut description = match &resource.source {
+        Some(source) => format!("{source}:{}", resource.path),
+        None => resource.path.clone(),
+    };
+    if let Some(version) = &resource.version {
+        description.push_str(&format!("@{version}"));
+    }
+    if let Some(commit) = &resource.resolved_commit {
+        description.push_str(&format!(" ({})", &commit[..commit.len().min(7)]));
+    }
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ResourceType;
+    use crate::lockfile::LockedResourceBuilder;
+
+    fn lockfile(version: &str, commit: &str) -> LockFile {
+        let mut lockfile = LockFile::new();
+        let mut agent = LockedResourceBuilder::new(
+            "agents/reviewer".to_string(),
+            "agents/reviewer.md".to_string(),
+            String::new(),
+            ".claude/agents/agpm/reviewer.md".to_string(),
+            ResourceType::Agent,
+        )
+        .source(Some("community".to_string()))
+        .version(Some(version.to_string()))
+        .resolved_commit(Some(commit.to_string()))
+        .build();
+        agent.manifest_alias = Some("reviewer".to_string());
+        lockfile.agents.push(agent);
+        lockfile
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let same = [
+            ("api".to_string(), lockfile("v1.0.0", "aaaaaaaaaa")),
+            ("web".to_string(), lockfile("v1.0.0", "aaaaaaaaaa")),
+        ];
+        assert!(find_conflicts(&same).is_empty());
+
+        let drifted = [
+            ("api".to_string(), lockfile("v1.0.0", "aaaaaaaaaa")),
+            ("web".to_string(), lockfile("v2.0.0", "bbbbbbbbbb")),
+        ];
+        let conflicts = find_conflicts(&drifted);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            conflicts[0].to_string(),
+            "agents/reviewer:\n    api: community:agents/reviewer.md@v1.0.0 (aaaaaaa)\n    \
+             web: community:agents/reviewer.md@v2.0.0 (bbbbbbb)"
+        );
+    }
+}
@@ -606,14 +606,14 @@ The document will be processed without metadata.",
         })?;
 
         // Get raw frontmatter for line number tracking
-        let rendered_frontmatter = if matter_result.data.is_some() {
+        let rendered_frontmatter = if let Some(data) = &matter_result.data {
             // Count lines before frontmatter to get accurate line numbers
             let frontmatter_start = rendered_content.find("---").unwrap_or(0);
             let lines_before = rendered_content[..frontmatter_start].lines().count();
 
             // Store the raw frontmatter with line offset info
             Some(RenderedFrontmatter {
-                content: serde_yaml::to_string(&matter_result.data.as_ref().unwrap())?,
+                content: serde_yaml::to_string(data)?,
                 line_offset: lines_before,
             })
         } else {
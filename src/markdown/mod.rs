@@ -97,6 +97,9 @@
 //! | type | string | Resource type ("agent" or "snippet") | No |
 //! | tags | array | Tags for categorization | No |
 //! | dependencies | object | Structured dependencies by resource type | No |
+//! | deprecated | bool or string | Marks the resource as deprecated | No |
+//! | deprecated_message | string | Explanation shown in deprecation warnings | No |
+//! | replacement | string | Suggested resource to migrate to | No |
 //!
 //! Additional custom fields are preserved in the extra map.
 //!
@@ -370,6 +373,65 @@ impl MarkdownMetadata {
     pub fn get_agpm_metadata(&self) -> Option<AgpmMetadata> {
         self.extra.get("agpm").and_then(|value| serde_json::from_value(value.clone()).ok())
     }
+
+    /// Get the deprecation notice declared in the frontmatter, if any.
+    ///
+    /// A resource is deprecated when `deprecated` is `true` or a non-empty
+    /// string, which is then used as the message. `deprecated_message` and
+    /// `replacement` are optional and only read for deprecated resources:
+    ///
+    /// ```yaml
+    /// deprecated: true
+    /// deprecated_message: Superseded by the new reviewer agent
+    /// replacement: agents/reviewer.md
+    /// ```
+    pub fn deprecation(&self) -> Option<Deprecation> {
+        let string_field = |key: &str| {
+            self.extra
+                .get(key)
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+
+        let inline_message = match self.extra.get("deprecated")? {
+            serde_json::Value::Bool(true) => None,
+            serde_json::Value::String(_) => Some(string_field("deprecated")?),
+            _ => return None,
+        };
+
+        Some(Deprecation {
+            message: string_field("deprecated_message").or(inline_message),
+            replacement: string_field("replacement"),
+        })
+    }
+
+    /// Get the oldest AGPM release the resource supports, if declared.
+    ///
+    /// Resources that rely on newer features (templating, custom tools) set
+    /// `min_agpm_version: 0.5.0` in their frontmatter. The value is returned as
+    /// written (unquoted YAML numbers such as `1.0` included); see
+    /// [`crate::installer::check_min_agpm_version`].
+    pub fn min_agpm_version(&self) -> Option<String> {
+        let declared = match self.extra.get("min_agpm_version")? {
+            serde_json::Value::String(s) => s.trim().to_string(),
+            serde_json::Value::Number(n) => n.to_string(),
+            _ => return None,
+        };
+        (!declared.is_empty()).then_some(declared)
+    }
+}
+
+/// Deprecation notice read from a resource's frontmatter.
+///
+/// See [`MarkdownMetadata::deprecation`] for the supported fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Deprecation {
+    /// Explanation shown to users, from `deprecated_message`.
+    pub message: Option<String>,
+    /// Suggested resource to migrate to, from `replacement`.
+    pub replacement: Option<String>,
 }
 
 /// A parsed Markdown document representing a Claude Code resource.
@@ -1414,4 +1476,37 @@ Content here"#;
             }
         }
     }
+
+    #[test]
+    fn test_deprecation_from_frontmatter() {
+        let input = r"---
+deprecated: true
+deprecated_message: Superseded by the new reviewer
+replacement: agents/reviewer.md
+---
+
+# Old Reviewer";
+
+        let metadata = MarkdownDocument::parse(input).unwrap().metadata.unwrap();
+        assert_eq!(
+            metadata.deprecation(),
+            Some(Deprecation {
+                message: Some("Superseded by the new reviewer".to_string()),
+                replacement: Some("agents/reviewer.md".to_string()),
+            })
+        );
+
+        // A string value doubles as the message
+        let input = "---\ndeprecated: Use the v2 agent instead\n---\n\n# Old";
+        let deprecation = MarkdownDocument::parse(input).unwrap().metadata.unwrap().deprecation();
+        assert_eq!(deprecation.unwrap().message.as_deref(), Some("Use the v2 agent instead"));
+
+        for input in [
+            "---\ndeprecated: false\nreplacement: agents/other.md\n---\n\n# Current",
+            "---\ntitle: Current\n---\n\n# Current",
+        ] {
+            let metadata = MarkdownDocument::parse(input).unwrap().metadata.unwrap();
+            assert_eq!(metadata.deprecation(), None);
+        }
+    }
 }
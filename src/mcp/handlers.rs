@@ -166,16 +166,25 @@ impl McpHandler for ClaudeCodeMcpHandler {
                 let (patched_content, applied_patches) = {
                     // Look up patches for this MCP server
                     let lookup_name = entry.lookup_name();
-                    let project_patches = manifest.project_patches.get("mcp-servers", lookup_name);
-                    let private_patches = manifest.private_patches.get("mcp-servers", lookup_name);
+                    let version = entry.version.as_deref();
+                    let project_patches = manifest.project_patches.get_for_version(
+                        "mcp-servers",
+                        lookup_name,
+                        version,
+                    );
+                    let private_patches = manifest.private_patches.get_for_version(
+                        "mcp-servers",
+                        lookup_name,
+                        version,
+                    );
 
                     if project_patches.is_some() || private_patches.is_some() {
                         use crate::manifest::patches::apply_patches_to_content_with_origin;
                         apply_patches_to_content_with_origin(
                             &json_content,
                             &source_path.display().to_string(),
-                            project_patches.unwrap_or(&std::collections::BTreeMap::new()),
-                            private_patches.unwrap_or(&std::collections::BTreeMap::new()),
+                            &project_patches.unwrap_or_default(),
+                            &private_patches.unwrap_or_default(),
                         )?
                     } else {
                         (json_content, crate::manifest::patches::AppliedPatches::default())
@@ -204,8 +213,12 @@ impl McpHandler for ClaudeCodeMcpHandler {
                 mcp_servers.insert(entry.lookup_name().to_string(), config);
             }
 
-            // Configure MCP servers by merging into .mcp.json
-            let mcp_config_path = project_root.join(".mcp.json");
+            // Configure MCP servers by merging into .mcp.json (or the configured merge target)
+            let mcp_config_path = project_root.join(
+                manifest
+                    .get_merge_target("claude-code", crate::core::ResourceType::McpServer)
+                    .unwrap_or_else(|| ".mcp.json".into()),
+            );
             let changed_count = super::merge_mcp_servers(&mcp_config_path, mcp_servers).await?;
 
             Ok((all_applied_patches, changed_count))
@@ -315,16 +328,25 @@ impl McpHandler for OpenCodeMcpHandler {
                 let (patched_content, applied_patches) = {
                     // Look up patches for this MCP server
                     let lookup_name = entry.lookup_name();
-                    let project_patches = manifest.project_patches.get("mcp-servers", lookup_name);
-                    let private_patches = manifest.private_patches.get("mcp-servers", lookup_name);
+                    let version = entry.version.as_deref();
+                    let project_patches = manifest.project_patches.get_for_version(
+                        "mcp-servers",
+                        lookup_name,
+                        version,
+                    );
+                    let private_patches = manifest.private_patches.get_for_version(
+                        "mcp-servers",
+                        lookup_name,
+                        version,
+                    );
 
                     if project_patches.is_some() || private_patches.is_some() {
                         use crate::manifest::patches::apply_patches_to_content_with_origin;
                         apply_patches_to_content_with_origin(
                             &json_content,
                             &source_path.display().to_string(),
-                            project_patches.unwrap_or(&std::collections::BTreeMap::new()),
-                            private_patches.unwrap_or(&std::collections::BTreeMap::new()),
+                            &project_patches.unwrap_or_default(),
+                            &private_patches.unwrap_or_default(),
                         )?
                     } else {
                         (json_content, crate::manifest::patches::AppliedPatches::default())
@@ -20,7 +20,7 @@ use serde_json::Value as JsonValue;
 use std::path::Path;
 
 use crate::core::OperationContext;
-use crate::manifest::{DependencyMetadata, dependency_spec::AgpmMetadata};
+use crate::manifest::{DependencyMetadata, TransitivePolicy, dependency_spec::AgpmMetadata};
 use crate::markdown::frontmatter::FrontmatterParser;
 
 /// Metadata extractor for resource files.
@@ -74,12 +74,36 @@ impl MetadataExtractor {
         content: &str,
         variant_inputs: Option<&serde_json::Value>,
         context: Option<&OperationContext>,
+    ) -> Result<DependencyMetadata> {
+        Self::extract_with_field(
+            path,
+            content,
+            variant_inputs,
+            context,
+            TransitivePolicy::DEFAULT_FIELD,
+        )
+    }
+
+    /// Extract dependency metadata declared under a custom key.
+    ///
+    /// Same as [`extract`](Self::extract), but reads root-level dependencies from
+    /// `field` instead of `dependencies` (see `[transitive] field` in the
+    /// manifest). Dependencies nested under `agpm.dependencies` are always read.
+    pub fn extract_with_field(
+        path: &Path,
+        content: &str,
+        variant_inputs: Option<&serde_json::Value>,
+        context: Option<&OperationContext>,
+        field: &str,
     ) -> Result<DependencyMetadata> {
         let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
 
         match extension {
-            "md" => Self::extract_markdown_frontmatter(content, variant_inputs, path, context),
-            "json" => Self::extract_json_field(content, variant_inputs, path, context),
+            "md" if field == TransitivePolicy::DEFAULT_FIELD => {
+                Self::extract_markdown_frontmatter(content, variant_inputs, path, context)
+            }
+            "md" => Self::extract_markdown_field(content, variant_inputs, path, context, field),
+            "json" => Self::extract_json_field(content, variant_inputs, path, context, field),
             _ => {
                 // Scripts and other files don't support embedded dependencies
                 Ok(DependencyMetadata::default())
@@ -130,15 +154,73 @@ impl MetadataExtractor {
         }
     }
 
+    /// Extract dependencies declared under a custom frontmatter key.
+    ///
+    /// The frontmatter is parsed as untyped YAML so that a `dependencies` key
+    /// owned by another tool, in whatever shape, does not hide the metadata.
+    fn extract_markdown_field(
+        content: &str,
+        variant_inputs: Option<&serde_json::Value>,
+        path: &Path,
+        context: Option<&OperationContext>,
+        field: &str,
+    ) -> Result<DependencyMetadata> {
+        let mut parser = FrontmatterParser::new();
+        let result =
+            parser.parse_with_templating::<JsonValue>(content, variant_inputs, path, context)?;
+
+        let Some(frontmatter) = result.data else {
+            return Ok(DependencyMetadata::default());
+        };
+        let root_dependencies = frontmatter
+            .get(field)
+            .and_then(|deps| Self::parse_dependencies_field(deps, field, path, context));
+        let agpm_metadata = frontmatter
+            .get("agpm")
+            .and_then(|agpm| serde_json::from_value::<AgpmMetadata>(agpm.clone()).ok());
+
+        let dependency_metadata = DependencyMetadata::new(root_dependencies, agpm_metadata);
+        Self::validate_resource_types(&dependency_metadata, path)?;
+        Ok(dependency_metadata)
+    }
+
+    /// Parse a custom dependencies key, warning once per file if it is malformed.
+    fn parse_dependencies_field(
+        deps: &JsonValue,
+        field: &str,
+        path: &Path,
+        context: Option<&OperationContext>,
+    ) -> Option<std::collections::BTreeMap<String, Vec<crate::manifest::DependencySpec>>> {
+        match serde_json::from_value(deps.clone()) {
+            Ok(dependencies) => Some(dependencies),
+            Err(e) => {
+                if let Some(ctx) = context
+                    && ctx.should_warn_file(path)
+                {
+                    eprintln!(
+                        "Warning: Unable to parse '{}' field in '{}'. Declared dependencies \
+                         will NOT be resolved or installed.\n\nParse error: {}",
+                        field,
+                        path.display(),
+                        e
+                    );
+                }
+                None
+            }
+        }
+    }
+
     /// Extract dependencies field from JSON content.
     ///
-    /// Looks for a `dependencies` field in the top-level JSON object.
-    /// Uses unified templating logic to respect per-resource templating settings.
+    /// Looks for the dependencies `field` (normally `dependencies`) in the
+    /// top-level JSON object. Uses unified templating logic to respect
+    /// per-resource templating settings.
     fn extract_json_field(
         content: &str,
         variant_inputs: Option<&serde_json::Value>,
         path: &Path,
         context: Option<&OperationContext>,
+        field: &str,
     ) -> Result<DependencyMetadata> {
         // Use unified templating logic - always template to catch syntax errors
         let mut parser = FrontmatterParser::new();
@@ -147,7 +229,7 @@ impl MetadataExtractor {
         let json: JsonValue = serde_json::from_str(&templated_content)
             .with_context(|| "Failed to parse JSON content")?;
 
-        if let Some(deps) = json.get("dependencies") {
+        if let Some(deps) = json.get(field) {
             // The dependencies field should match our expected structure
             match serde_json::from_value::<
                 std::collections::BTreeMap<String, Vec<crate::manifest::DependencySpec>>,
@@ -164,7 +246,7 @@ impl MetadataExtractor {
                     if let Some(ctx) = context {
                         if ctx.should_warn_file(path) {
                             eprintln!(
-                                "Warning: Unable to parse dependencies field in '{}'.
+                                "Warning: Unable to parse {} field in '{}'.
 
 The document will be processed without metadata, and any declared dependencies
 will NOT be resolved or installed.
@@ -173,6 +255,7 @@ Parse error: {}
 
 For the correct dependency format, see:
 https://github.com/aig787/agpm#transitive-dependencies",
+                                field,
                                 path.display(),
                                 e
                             );
@@ -258,8 +341,13 @@ https://github.com/aig787/agpm#transitive-dependencies",
 
         // Try JSON format
         if content.trim_start().starts_with('{')
-            && let Ok(metadata) =
-                Self::extract_json_field(content, None, &PathBuf::from("unknown.json"), None)
+            && let Ok(metadata) = Self::extract_json_field(
+                content,
+                None,
+                &PathBuf::from("unknown.json"),
+                None,
+                TransitivePolicy::DEFAULT_FIELD,
+            )
             && metadata.has_dependencies()
         {
             return Ok(metadata);
@@ -302,6 +390,39 @@ This is the command documentation."#;
         assert_eq!(deps["agents"][0].version, Some("v1.0.0".to_string()));
     }
 
+    #[test]
+    fn test_extract_with_custom_field() {
+        // Another tool owns `dependencies`, in a shape AGPM does not understand
+        let content = r#"---
+dependencies:
+  - some-package
+agpm_dependencies:
+  snippets:
+    - path: snippets/utils.md
+---
+
+# My Command"#;
+
+        let path = Path::new("command.md");
+        let metadata =
+            MetadataExtractor::extract_with_field(path, content, None, None, "agpm_dependencies")
+                .unwrap();
+        let deps = metadata.dependencies.unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps["snippets"][0].path, "snippets/utils.md");
+
+        let json = r#"{"dependencies": ["x"], "agpm_dependencies": {"agents": [{"path": "agents/a.md"}]}}"#;
+        let metadata = MetadataExtractor::extract_with_field(
+            Path::new("config.json"),
+            json,
+            None,
+            None,
+            "agpm_dependencies",
+        )
+        .unwrap();
+        assert_eq!(metadata.dependencies.unwrap()["agents"][0].path, "agents/a.md");
+    }
+
     #[test]
     fn test_extract_markdown_no_frontmatter() {
         let content = r#"# My Command
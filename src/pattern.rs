@@ -32,6 +32,18 @@
 //! python-agents = { source = "community", path = "agents/python-*.md", version = "v1.0.0" }
 //! ```
 //!
+//! ## Ignore File
+//!
+//! A project-level `.agpmignore` file next to `agpm.toml` removes paths from
+//! the results of every pattern dependency. It uses `.gitignore` syntax; see
+//! [`IgnoreRules`] for the supported subset.
+//!
+//! ```text
+//! # Never install work-in-progress resources
+//! WIP/
+//! *.draft.md
+//! ```
+//!
 //! ## Security Considerations
 //!
 //! Pattern matching includes several security measures:
@@ -51,7 +63,7 @@
 //! - **Memory Efficient**: Streaming approach for large directory trees
 
 use anyhow::{Context, Result};
-use glob::Pattern;
+use glob::{MatchOptions, Pattern};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use tracing::{debug, trace};
@@ -285,6 +297,7 @@ impl PatternMatcher {
 /// # Features
 ///
 /// - **Exclusion Patterns**: Support for excluding specific patterns from results
+/// - **Ignore Rules**: Honors the project's `.agpmignore` via [`with_ignore_rules`]
 /// - **Deterministic Ordering**: Results are always returned in sorted order
 /// - **Deduplication**: Automatically removes duplicate paths from results
 /// - **Multiple Pattern Support**: Can resolve multiple patterns in one operation
@@ -308,9 +321,15 @@ impl PatternMatcher {
 /// # Ok(())
 /// # }
 /// ```
+///
+/// [`with_ignore_rules`]: PatternResolver::with_ignore_rules
 pub struct PatternResolver {
     /// Patterns to exclude from matching
     exclude_patterns: Vec<Pattern>,
+    /// Project-wide rules from `.agpmignore`
+    ignore_rules: IgnoreRules,
+    /// Only return files directly in the pattern's directory
+    direct_children_only: bool,
 }
 
 impl PatternResolver {
@@ -332,9 +351,47 @@ impl PatternResolver {
     pub const fn new() -> Self {
         Self {
             exclude_patterns: Vec::new(),
+            ignore_rules: IgnoreRules::new(),
+            direct_children_only: false,
         }
     }
 
+    /// Applies project-wide ignore rules to every resolution.
+    ///
+    /// Ignore rules and exclusion patterns are independent filters: a path is
+    /// returned only if neither of them removes it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use agpm_cli::pattern::{IgnoreRules, PatternResolver};
+    /// use std::path::Path;
+    ///
+    /// # fn example() -> anyhow::Result<()> {
+    /// let rules = IgnoreRules::load(Some(Path::new("/project")))?;
+    /// let resolver = PatternResolver::new().with_ignore_rules(rules);
+    /// let matches = resolver.resolve("agents/*.md", Path::new("/repo"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_ignore_rules(mut self, rules: IgnoreRules) -> Self {
+        self.ignore_rules = rules;
+        self
+    }
+
+    /// Restricts results to files directly in the pattern's directory.
+    ///
+    /// Glob wildcards match across `/`, so `agents/*.md` also finds
+    /// `agents/nested/helper.md`. With this set, only regular files whose
+    /// parent is the pattern's directory (`agents`) are returned. Used for
+    /// directory references such as `agents/`.
+    #[must_use]
+    pub const fn direct_children_only(mut self, enabled: bool) -> Self {
+        self.direct_children_only = enabled;
+        self
+    }
+
     /// Adds an exclusion pattern to filter out unwanted results.
     ///
     /// Files matching exclusion patterns will be removed from resolution
@@ -387,7 +444,7 @@ impl PatternResolver {
     /// # Algorithm
     ///
     /// 1. Use `PatternMatcher` to find all files matching the pattern
-    /// 2. Filter out any files matching exclusion patterns
+    /// 2. Filter out any files matching exclusion patterns or ignore rules
     /// 3. Remove duplicates (though unlikely with file paths)
     /// 4. Sort results for deterministic ordering
     ///
@@ -437,6 +494,18 @@ impl PatternResolver {
             });
         }
 
+        if self.direct_children_only {
+            let directory = Path::new(pattern).parent();
+            matched_paths
+                .retain(|path| path.parent() == directory && base_path.join(path).is_file());
+        }
+
+        // Apply project-wide ignore rules
+        if !self.ignore_rules.is_empty() {
+            matched_paths
+                .retain(|path| !self.ignore_rules.is_ignored(path, base_path.join(path).is_dir()));
+        }
+
         // Sort for deterministic ordering
         matched_paths.sort();
 
@@ -503,6 +572,193 @@ impl Default for PatternResolver {
     }
 }
 
+/// Name of the project-level ignore file consulted by pattern dependencies.
+pub const IGNORE_FILE_NAME: &str = ".agpmignore";
+
+/// Match options for ignore rules: `*` never crosses a `/`, only `**` does.
+const IGNORE_MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// A single compiled line of an ignore file.
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    pattern: Pattern,
+    negated: bool,
+    dir_only: bool,
+}
+
+/// Project-wide ignore rules loaded from `.agpmignore`.
+///
+/// The file uses the familiar `.gitignore` syntax and applies to every pattern
+/// dependency. Paths are matched relative to the directory a pattern is
+/// searched in: the repository root for Git sources, or the directory a local
+/// pattern is resolved against.
+///
+/// # Syntax
+///
+/// - Blank lines and lines starting with `#` are skipped
+/// - A leading `!` re-includes paths excluded by an earlier line
+/// - A trailing `/` only matches directories
+/// - A leading `/` (or any `/` in the middle) anchors the pattern to the root;
+///   otherwise it matches at any depth
+/// - Ignoring a directory ignores everything inside it, and files inside an
+///   ignored directory cannot be re-included
+///
+/// Later lines take precedence over earlier ones.
+///
+/// # Examples
+///
+/// ```rust
+/// use agpm_cli::pattern::IgnoreRules;
+/// use std::path::Path;
+///
+/// # fn example() -> anyhow::Result<()> {
+/// let rules = IgnoreRules::parse("WIP/\n*.draft.md\n!keep.draft.md\n")?;
+///
+/// assert!(rules.is_ignored(Path::new("agents/WIP/helper.md"), false));
+/// assert!(rules.is_ignored(Path::new("agents/review.draft.md"), false));
+/// assert!(!rules.is_ignored(Path::new("agents/keep.draft.md"), false));
+/// assert!(!rules.is_ignored(Path::new("agents/review.md"), false));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreRules {
+    /// Creates an empty rule set that ignores nothing.
+    pub const fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+        }
+    }
+
+    /// Parses ignore rules from the contents of an ignore file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the offending line if a rule is not a valid
+    /// glob pattern.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut rules = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (negated, line) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line.strip_prefix('\\').unwrap_or(line)),
+            };
+            let (dir_only, line) = match line.strip_suffix('/') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            let glob = match line.strip_prefix('/') {
+                Some(anchored) => anchored.to_string(),
+                None if line.contains('/') => line.to_string(),
+                None => format!("**/{line}"),
+            };
+            if glob.is_empty() {
+                continue;
+            }
+
+            let pattern = Pattern::new(&glob).with_context(|| {
+                format!("Invalid pattern on line {} of {IGNORE_FILE_NAME}: {line}", index + 1)
+            })?;
+            rules.push(IgnoreRule {
+                pattern,
+                negated,
+                dir_only,
+            });
+        }
+
+        Ok(Self {
+            rules,
+        })
+    }
+
+    /// Loads the `.agpmignore` file from a project directory.
+    ///
+    /// A missing file, or no project directory at all, yields an empty rule set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(project_dir: Option<&Path>) -> Result<Self> {
+        let Some(project_dir) = project_dir else {
+            return Ok(Self::new());
+        };
+        let path = project_dir.join(IGNORE_FILE_NAME);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => {
+                let rules = Self::parse(&content)?;
+                debug!("Loaded {} ignore rules from {}", rules.rules.len(), path.display());
+                Ok(rules)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// Returns `true` if the rule set contains no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Checks whether a relative path, or any directory containing it, is ignored.
+    ///
+    /// `is_dir` tells directory-only rules (`name/`) whether the final path
+    /// component is a directory.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        if self.rules.is_empty() {
+            return false;
+        }
+
+        let components: Vec<_> = path
+            .components()
+            .filter_map(|c| match c {
+                std::path::Component::Normal(part) => Some(part.to_string_lossy()),
+                _ => None,
+            })
+            .collect();
+
+        let mut prefix = String::new();
+        for (index, component) in components.iter().enumerate() {
+            if index > 0 {
+                prefix.push('/');
+            }
+            prefix.push_str(component);
+            let prefix_is_dir = is_dir || index + 1 < components.len();
+            if self.matches(&prefix, prefix_is_dir) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Applies the rules to a single path; the last matching rule wins.
+    fn matches(&self, path: &str, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            if rule.pattern.matches_with(path, IGNORE_MATCH_OPTIONS) {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
 /// Extracts a resource name from a file path.
 ///
 /// This function determines an appropriate resource name by extracting
@@ -695,6 +951,75 @@ mod tests {
         assert!(matches.contains(&PathBuf::from("agents/helper.md")));
     }
 
+    #[test]
+    fn test_pattern_resolver_direct_children_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path();
+        fs::create_dir_all(base_path.join("scripts/lib.d")).unwrap();
+        fs::write(base_path.join("scripts/build.sh"), "").unwrap();
+        fs::write(base_path.join("scripts/lib.d/util.sh"), "").unwrap();
+
+        let recursive = PatternResolver::new().resolve("scripts/*", base_path).unwrap();
+        assert_eq!(recursive.len(), 3);
+
+        let direct =
+            PatternResolver::new().direct_children_only(true).resolve("scripts/*", base_path);
+        assert_eq!(direct.unwrap(), vec![PathBuf::from("scripts/build.sh")]);
+    }
+
+    #[test]
+    fn test_ignore_rules_gitignore_semantics() {
+        let rules = IgnoreRules::parse(
+            "# comment\n\nWIP/\n*.draft.md\n!keep.draft.md\n/agents/legacy.md\ndocs/*.md\n",
+        )
+        .unwrap();
+
+        // Unanchored rules match at any depth
+        assert!(rules.is_ignored(Path::new("a/b/note.draft.md"), false));
+        assert!(!rules.is_ignored(Path::new("a/b/keep.draft.md"), false));
+
+        // Directory rules ignore everything inside, but not files of that name
+        assert!(rules.is_ignored(Path::new("agents/WIP/helper.md"), false));
+        assert!(rules.is_ignored(Path::new("WIP"), true));
+        assert!(!rules.is_ignored(Path::new("WIP"), false));
+
+        // Anchored rules only match from the root
+        assert!(rules.is_ignored(Path::new("agents/legacy.md"), false));
+        assert!(!rules.is_ignored(Path::new("nested/agents/legacy.md"), false));
+        assert!(rules.is_ignored(Path::new("docs/intro.md"), false));
+        assert!(!rules.is_ignored(Path::new("docs/deep/intro.md"), false));
+
+        assert!(!rules.is_ignored(Path::new("agents/helper.md"), false));
+    }
+
+    #[test]
+    fn test_ignore_rules_load_missing_and_invalid() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(IgnoreRules::load(Some(temp_dir.path())).unwrap().is_empty());
+        assert!(IgnoreRules::load(None).unwrap().is_empty());
+
+        fs::write(temp_dir.path().join(IGNORE_FILE_NAME), "ok.md\n[invalid\n").unwrap();
+        let err = IgnoreRules::load(Some(temp_dir.path())).unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{err}");
+    }
+
+    #[test]
+    fn test_pattern_resolver_applies_ignore_rules_with_exclusions() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path();
+        fs::create_dir_all(base_path.join("agents/WIP")).unwrap();
+        fs::write(base_path.join("agents/helper.md"), "").unwrap();
+        fs::write(base_path.join("agents/test.md"), "").unwrap();
+        fs::write(base_path.join("agents/WIP/draft.md"), "").unwrap();
+
+        let mut resolver =
+            PatternResolver::new().with_ignore_rules(IgnoreRules::parse("WIP/").unwrap());
+        resolver.exclude("*/test.md").unwrap();
+
+        let matches = resolver.resolve("agents/**/*.md", base_path).unwrap();
+        assert_eq!(matches, vec![PathBuf::from("agents/helper.md")]);
+    }
+
     #[test]
     fn test_resolve_multiple_patterns_with_deduplication() {
         let temp_dir = TempDir::new().unwrap();
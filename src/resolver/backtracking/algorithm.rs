@@ -340,6 +340,7 @@ pub async fn find_alternative_for_transitive(
             &worktree_path,
             &parent_resource_path,
             parent_variant_inputs_cloned.as_ref(),
+            core.manifest().transitive.dependency_field(),
         )
         .await
         {
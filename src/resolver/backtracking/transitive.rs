@@ -75,8 +75,13 @@ pub async fn reextract_transitive_deps(
 
         let variant_inputs = get_variant_inputs_for_resource(change_tracker, &resource_id);
 
-        let transitive_deps =
-            extract_transitive_deps(&worktree_path, resource_path, variant_inputs.as_ref()).await?;
+        let transitive_deps = extract_transitive_deps(
+            &worktree_path,
+            resource_path,
+            variant_inputs.as_ref(),
+            core.manifest().transitive.dependency_field(),
+        )
+        .await?;
 
         for (_resource_type, specs) in transitive_deps {
             for spec in specs {
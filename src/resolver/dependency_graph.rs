@@ -5,11 +5,12 @@
 //! ordering for correct installation order.
 
 use anyhow::{Result, anyhow};
-use petgraph::algo::toposort;
+use petgraph::Direction;
 use petgraph::graph::{DiGraph, NodeIndex};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::fmt;
 
+use crate::core::AgpmError;
 use crate::lockfile::lockfile_dependency_ref::LockfileDependencyRef;
 
 /// Represents a dependency node in the graph.
@@ -17,7 +18,7 @@ use crate::lockfile::lockfile_dependency_ref::LockfileDependencyRef;
 /// Each node represents a unique resource that can be installed.
 /// Nodes are distinguished by name, resource type, and source to prevent
 /// false cycle detection when the same resource name appears in multiple sources.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct DependencyNode {
     /// Resource type: Agent, Snippet, Command, etc.
     pub resource_type: crate::core::ResourceType,
@@ -52,11 +53,22 @@ impl DependencyNode {
 
     /// Get a display name for this node.
     pub fn display_name(&self) -> String {
+        self.display_name_with_version(None)
+    }
+
+    /// Get a display name for this node, including the version when known.
+    pub fn display_name_with_version(&self, version: Option<&str>) -> String {
+        let version = version.map(str::to_string);
         if let Some(ref source) = self.source {
-            LockfileDependencyRef::git(source.clone(), self.resource_type, self.name.clone(), None)
-                .to_string()
+            LockfileDependencyRef::git(
+                source.clone(),
+                self.resource_type,
+                self.name.clone(),
+                version,
+            )
+            .to_string()
         } else {
-            LockfileDependencyRef::local(self.resource_type, self.name.clone(), None).to_string()
+            LockfileDependencyRef::local(self.resource_type, self.name.clone(), version).to_string()
         }
     }
 }
@@ -87,6 +99,8 @@ pub struct DependencyGraph {
     graph: DiGraph<DependencyNode, ()>,
     /// Map from dependency nodes to their graph indices.
     node_map: HashMap<DependencyNode, NodeIndex>,
+    /// Requested version of each node, when known. Only used for reporting.
+    versions: HashMap<DependencyNode, String>,
 }
 
 impl DependencyGraph {
@@ -95,6 +109,7 @@ impl DependencyGraph {
         Self {
             graph: DiGraph::new(),
             node_map: HashMap::new(),
+            versions: HashMap::new(),
         }
     }
 
@@ -111,6 +126,11 @@ impl DependencyGraph {
         }
     }
 
+    /// Add a node with no dependencies, if it doesn't already exist.
+    pub fn add_node(&mut self, node: DependencyNode) {
+        self.ensure_node(node);
+    }
+
     /// Add a dependency relationship to the graph.
     ///
     /// `from` depends on `to`, meaning `to` must be installed before `from`.
@@ -124,9 +144,15 @@ impl DependencyGraph {
         }
     }
 
+    /// Record the version a node was requested at, so cycle errors can show it.
+    pub fn set_version(&mut self, node: &DependencyNode, version: impl Into<String>) {
+        self.versions.insert(node.clone(), version.into());
+    }
+
     /// Detect cycles in the dependency graph using DFS with colors.
     ///
-    /// Returns an error containing the cycle path if a cycle is detected.
+    /// Returns an [`AgpmError::CircularDependency`] listing every resource in
+    /// the cycle (with source and version when known) if a cycle is detected.
     pub fn detect_cycles(&self) -> Result<()> {
         let mut colors: HashMap<NodeIndex, Color> = HashMap::new();
         let mut path: Vec<DependencyNode> = Vec::new();
@@ -141,9 +167,17 @@ impl DependencyGraph {
             if matches!(colors.get(&node), Some(Color::White))
                 && let Some(cycle) = self.dfs_visit(node, &mut colors, &mut path)
             {
-                let cycle_str =
-                    cycle.iter().map(DependencyNode::display_name).collect::<Vec<_>>().join(" → ");
-                return Err(anyhow!("Circular dependency detected: {cycle_str}"));
+                let chain = cycle
+                    .iter()
+                    .map(|node| {
+                        node.display_name_with_version(self.versions.get(node).map(String::as_str))
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" → ");
+                return Err(AgpmError::CircularDependency {
+                    chain,
+                }
+                .into());
             }
         }
 
@@ -189,26 +223,43 @@ impl DependencyGraph {
     /// Get the topological order for installation.
     ///
     /// Returns nodes in an order where all dependencies come before their dependents.
-    /// This ensures that resources are installed in the correct order.
+    /// This ensures that resources are installed in the correct order. Among nodes
+    /// whose dependencies are all placed, the smallest node comes first, so the order
+    /// does not depend on the order in which nodes were discovered.
     pub fn topological_order(&self) -> Result<Vec<DependencyNode>> {
         // First check for cycles
         self.detect_cycles()?;
 
-        // Perform topological sort
-        match toposort(&self.graph, None) {
-            Ok(indices) => {
-                let mut order = Vec::new();
-                // Reverse the order so dependencies come first
-                for idx in indices.into_iter().rev() {
-                    order.push(self.graph[idx].clone());
+        // Kahn's algorithm over "remaining dependencies" counts
+        let mut remaining: HashMap<NodeIndex, usize> = self
+            .graph
+            .node_indices()
+            .map(|idx| (idx, self.graph.neighbors_directed(idx, Direction::Outgoing).count()))
+            .collect();
+        let mut ready: BTreeSet<(&DependencyNode, NodeIndex)> = remaining
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(idx, _)| (&self.graph[*idx], *idx))
+            .collect();
+
+        let mut order = Vec::with_capacity(self.graph.node_count());
+        while let Some((node, idx)) = ready.pop_first() {
+            order.push(node.clone());
+            for dependent in self.graph.neighbors_directed(idx, Direction::Incoming) {
+                if let Some(count) = remaining.get_mut(&dependent) {
+                    *count -= 1;
+                    if *count == 0 {
+                        ready.insert((&self.graph[dependent], dependent));
+                    }
                 }
-                Ok(order)
-            }
-            Err(_) => {
-                // This shouldn't happen as we already checked for cycles
-                Err(anyhow!("Failed to determine installation order"))
             }
         }
+
+        if order.len() != self.graph.node_count() {
+            // This shouldn't happen as we already checked for cycles
+            return Err(anyhow!("Failed to determine installation order"));
+        }
+        Ok(order)
     }
 
     /// Get all transitive dependencies for a given node.
@@ -269,6 +320,15 @@ impl DependencyGraph {
         self.graph.node_indices().map(|idx| self.graph[idx].clone()).collect()
     }
 
+    /// Get all dependency relationships as `(dependent, dependency)` pairs.
+    pub fn edges(&self) -> Vec<(DependencyNode, DependencyNode)> {
+        self.graph
+            .edge_indices()
+            .filter_map(|edge| self.graph.edge_endpoints(edge))
+            .map(|(from, to)| (self.graph[from].clone(), self.graph[to].clone()))
+            .collect()
+    }
+
     /// Build a human-readable dependency tree representation.
     ///
     /// Returns a string showing the dependency hierarchy.
@@ -538,6 +598,32 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_topological_order_ignores_insertion_order() -> Result<()> {
+        let agent = |name: &str| DependencyNode::new(crate::core::ResourceType::Agent, name);
+        let snippet = |name: &str| DependencyNode::new(crate::core::ResourceType::Snippet, name);
+        let edges = [
+            (agent("root"), snippet("c")),
+            (agent("root"), snippet("a")),
+            (agent("root"), snippet("b")),
+            (snippet("b"), snippet("a")),
+        ];
+
+        let mut forward = DependencyGraph::new();
+        for (from, to) in edges.iter().cloned() {
+            forward.add_dependency(from, to);
+        }
+        let mut backward = DependencyGraph::new();
+        for (from, to) in edges.iter().rev().cloned() {
+            backward.add_dependency(from, to);
+        }
+
+        let order = forward.topological_order()?;
+        assert_eq!(order, backward.topological_order()?);
+        assert_eq!(order, vec![snippet("a"), snippet("b"), snippet("c"), agent("root")]);
+        Ok(())
+    }
+
     #[test]
     fn test_cross_source_real_cycle() {
         let mut graph = DependencyGraph::new();
@@ -573,4 +659,32 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Circular dependency"));
     }
+
+    #[test]
+    fn test_cycle_error_lists_sources_and_versions() {
+        let node = |resource_type, name: &str| {
+            DependencyNode::with_source(resource_type, name, Some("community".to_string()))
+        };
+        let agent = node(crate::core::ResourceType::Agent, "agents/a");
+        let snippet = node(crate::core::ResourceType::Snippet, "snippets/b");
+
+        let mut graph = DependencyGraph::new();
+        graph.add_dependency(agent.clone(), snippet.clone());
+        graph.add_dependency(snippet.clone(), agent.clone());
+        graph.set_version(&agent, "v1.0.0");
+        graph.set_version(&snippet, "v2.0.0");
+
+        let error = graph.detect_cycles().unwrap_err();
+        let Some(AgpmError::CircularDependency {
+            chain,
+        }) = error.downcast_ref::<AgpmError>()
+        else {
+            panic!("expected CircularDependency, got {error}");
+        };
+        assert_eq!(
+            chain,
+            "community/agent:agents/a@v1.0.0 → community/snippet:snippets/b@v2.0.0 → \
+             community/agent:agents/a@v1.0.0"
+        );
+    }
 }
@@ -126,6 +126,7 @@ impl DependencyResolver {
             resource_type,
             name,
             manifest_alias.as_deref(),
+            None,
         );
 
         // Generate canonical name for local dependencies
@@ -176,6 +177,7 @@ impl DependencyResolver {
             context_checksum: None,
             is_private,
             approximate_token_count: None,
+            deprecated: false,
         })
     }
 
@@ -273,6 +275,7 @@ impl DependencyResolver {
             resource_type,
             name,
             manifest_alias.as_deref(),
+            prepared.resolved_version.as_deref(),
         );
 
         let variant_inputs = lockfile_builder::VariantInputs::new(
@@ -325,6 +328,7 @@ impl DependencyResolver {
             context_checksum: None,
             is_private,
             approximate_token_count: None,
+            deprecated: false,
         })
     }
 
@@ -357,11 +361,14 @@ impl DependencyResolver {
         dep: &ResourceDependency,
         resource_type: ResourceType,
     ) -> Result<Vec<LockedResource>> {
-        use crate::pattern::PatternResolver;
-
-        let pattern = dep.get_path();
-        let (base_path, pattern_str) = install_path_resolver::parse_pattern_base_path(pattern);
-        let pattern_resolver = PatternResolver::new();
+        use crate::pattern::{IgnoreRules, PatternResolver};
+
+        let pattern = dep.expansion_pattern(resource_type);
+        let (base_path, pattern_str) = install_path_resolver::parse_pattern_base_path(&pattern);
+        let ignore_rules = IgnoreRules::load(self.core.manifest().manifest_dir.as_deref())?;
+        let pattern_resolver = PatternResolver::new()
+            .with_ignore_rules(ignore_rules)
+            .direct_children_only(dep.is_directory_reference());
         let matches = pattern_resolver.resolve(&pattern_str, &base_path)?;
 
         let artifact_type_string = self.resolve_tool(dep, resource_type);
@@ -384,13 +391,23 @@ impl DependencyResolver {
             let filename =
                 install_path_resolver::extract_pattern_filename(&base_path, &matched_path);
 
-            let installed_at = install_path_resolver::resolve_install_path(
-                self.core.manifest(),
-                dep,
-                artifact_type,
-                resource_type,
-                &filename,
-            )?;
+            let installed_at = if dep.get_preserve_structure() {
+                install_path_resolver::resolve_preserved_install_path(
+                    self.core.manifest(),
+                    dep,
+                    artifact_type,
+                    resource_type,
+                    Path::new(&full_relative_path),
+                )?
+            } else {
+                install_path_resolver::resolve_install_path(
+                    self.core.manifest(),
+                    dep,
+                    artifact_type,
+                    resource_type,
+                    &filename,
+                )?
+            };
 
             // Transform path for private dependencies
             let final_installed_at = if is_private {
@@ -417,12 +434,14 @@ impl DependencyResolver {
                     resource_type,
                     &resource_name, // Use canonical resource name
                     Some(name),     // Use manifest_alias for patch lookups
+                    None,
                 ),
                 install: dep.get_install(),
                 variant_inputs: variant_inputs.clone(),
                 context_checksum: None,
                 is_private,
                 approximate_token_count: None,
+                deprecated: false,
             });
         }
 
@@ -436,10 +455,10 @@ impl DependencyResolver {
         dep: &ResourceDependency,
         resource_type: ResourceType,
     ) -> Result<Vec<LockedResource>> {
-        use crate::pattern::PatternResolver;
+        use crate::pattern::{IgnoreRules, PatternResolver};
         use crate::utils::{compute_relative_install_path, normalize_path_for_storage};
 
-        let pattern = dep.get_path();
+        let pattern = dep.expansion_pattern(resource_type);
         let pattern_name = name;
 
         let source_name = dep.get_source().ok_or_else(|| {
@@ -469,8 +488,11 @@ impl DependencyResolver {
         let resolved_commit = prepared.resolved_commit.clone();
 
         let repo_path = Path::new(&worktree_path);
-        let pattern_resolver = PatternResolver::new();
-        let matches = pattern_resolver.resolve(pattern, repo_path)?;
+        let ignore_rules = IgnoreRules::load(self.core.manifest().manifest_dir.as_deref())?;
+        let pattern_resolver = PatternResolver::new()
+            .with_ignore_rules(ignore_rules)
+            .direct_children_only(dep.is_directory_reference());
+        let matches = pattern_resolver.resolve(&pattern, repo_path)?;
 
         let artifact_type_string = self.resolve_tool(dep, resource_type);
         let artifact_type = artifact_type_string.as_str();
@@ -490,6 +512,15 @@ impl DependencyResolver {
 
             // Compute installation path
             let installed_at = match resource_type {
+                _ if dep.get_preserve_structure() => {
+                    install_path_resolver::resolve_preserved_install_path(
+                        self.core.manifest(),
+                        dep,
+                        artifact_type,
+                        resource_type,
+                        &matched_path,
+                    )?
+                }
                 ResourceType::Hook | ResourceType::McpServer => {
                     install_path_resolver::resolve_merge_target_path(
                         self.core.manifest(),
@@ -570,12 +601,14 @@ impl DependencyResolver {
                     resource_type,
                     &resource_name,     // Use canonical resource name
                     Some(pattern_name), // Use manifest_alias for patch lookups
+                    resolved_version.as_deref(),
                 ),
                 install: dep.get_install(),
                 variant_inputs: variant_inputs.clone(),
                 context_checksum: None,
                 is_private,
                 approximate_token_count: None,
+                deprecated: false,
             });
         }
 
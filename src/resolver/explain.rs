@@ -0,0 +1,317 @@
+//! Resolution explanations for `--explain`.
+//!
+//! When explanation is enabled on the [`VersionResolver`](super::VersionResolver),
+//! every `(source, version)` pair it resolves records which tags were considered,
+//! why each rejected candidate was filtered out, and why the final ref was chosen.
+//! Recording is opt-in so normal runs pay nothing for it.
+//!
+//! Explanations are keyed by `(source, version)` like the resolver itself; use
+//! [`group_by_dependency`] to attach them to the manifest dependencies that
+//! requested them for display.
+
+use semver::Version;
+use serde::Serialize;
+
+use crate::manifest::Manifest;
+use crate::version::constraints::{ConstraintSet, VersionConstraint};
+
+/// Why a candidate tag was or was not selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CandidateStatus {
+    /// The tag was chosen for this constraint.
+    Selected,
+    /// The tag satisfies the constraint but the resolution strategy preferred
+    /// another version (a higher one by default, a lower one with `lowest`).
+    Superseded,
+    /// The tag is a prerelease and the constraint does not allow prereleases.
+    Prerelease,
+    /// The tag prefix (e.g. `agents-`) does not match the constraint prefix.
+    PrefixMismatch,
+    /// The tag is not a semantic version.
+    NotSemver,
+    /// The tag's version does not satisfy the constraint.
+    Unsatisfied,
+}
+
+impl CandidateStatus {
+    /// Short human-readable description used in text output.
+    #[must_use]
+    pub const fn describe(self) -> &'static str {
+        match self {
+            Self::Selected => "selected",
+            Self::Superseded => "matches, but another version was preferred",
+            Self::Prerelease => "prerelease excluded by constraint",
+            Self::PrefixMismatch => "prefix mismatch",
+            Self::NotSemver => "not a semantic version",
+            Self::Unsatisfied => "does not satisfy constraint",
+        }
+    }
+}
+
+/// A single tag considered while resolving a version constraint.
+#[derive(Debug, Clone, Serialize)]
+pub struct CandidateTag {
+    /// Tag name as it appears in the repository.
+    pub tag: String,
+    /// Parsed semantic version, if the tag is one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Outcome for this tag.
+    pub status: CandidateStatus,
+}
+
+/// Explanation of how one `(source, version)` pair was resolved.
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionExplanation {
+    /// Source name from the manifest.
+    pub source: String,
+    /// Requested version specification (`HEAD` when none was given).
+    pub requested: String,
+    /// Ref that was ultimately resolved (tag, branch, or SHA).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_ref: Option<String>,
+    /// Commit SHA the ref resolved to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_sha: Option<String>,
+    /// Why the resolved ref was chosen.
+    pub reason: String,
+    /// Tags considered for version constraints, highest version first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<CandidateTag>,
+}
+
+impl VersionExplanation {
+    pub(crate) fn new(source: &str, requested: &str, reason: impl Into<String>) -> Self {
+        Self {
+            source: source.to_string(),
+            requested: requested.to_string(),
+            resolved_ref: None,
+            resolved_sha: None,
+            reason: reason.into(),
+            candidates: Vec::new(),
+        }
+    }
+}
+
+/// A [`VersionExplanation`] attributed to the dependency that requested it.
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyExplanation {
+    /// Dependency name from the manifest, or `None` for transitive-only versions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Plural resource type (e.g. `agents`) for manifest dependencies.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_type: Option<String>,
+    /// How the dependency's version was resolved.
+    #[serde(flatten)]
+    pub explanation: VersionExplanation,
+}
+
+/// Classify every tag against a constraint, mirroring [`find_best_matching_tag`].
+///
+/// `selected` is the tag returned by the real resolution, so the explanation can
+/// never disagree with what was installed. Candidates are returned highest
+/// version first, with non-semver and mismatched-prefix tags last.
+///
+/// [`find_best_matching_tag`]: super::version_resolver::find_best_matching_tag
+pub(crate) fn explain_tag_selection(
+    constraint_str: &str,
+    tags: &[String],
+    selected: Option<&str>,
+) -> Vec<CandidateTag> {
+    let (constraint_prefix, version_str) = crate::version::split_prefix_and_version(constraint_str);
+
+    let constraint_set = if version_str == "*" {
+        None
+    } else {
+        VersionConstraint::parse(constraint_str).ok().and_then(|constraint| {
+            let mut set = ConstraintSet::new();
+            set.add(constraint).ok().map(|()| set)
+        })
+    };
+
+    let mut semver_candidates: Vec<(String, Version)> = Vec::new();
+    let mut rejected = Vec::new();
+
+    for tag in tags {
+        let (tag_prefix, tag_version) = crate::version::split_prefix_and_version(tag);
+        if tag_prefix != constraint_prefix {
+            rejected.push(CandidateTag {
+                tag: tag.clone(),
+                version: None,
+                status: CandidateStatus::PrefixMismatch,
+            });
+            continue;
+        }
+
+        let cleaned = tag_version.trim_start_matches('v').trim_start_matches('V');
+        match Version::parse(cleaned) {
+            Ok(version) => semver_candidates.push((tag.clone(), version)),
+            Err(_) => rejected.push(CandidateTag {
+                tag: tag.clone(),
+                version: None,
+                status: CandidateStatus::NotSemver,
+            }),
+        }
+    }
+
+    super::version_resolver::sort_versions_deterministic(&mut semver_candidates);
+
+    let mut candidates: Vec<CandidateTag> = semver_candidates
+        .into_iter()
+        .map(|(tag, version)| {
+            let status = if selected == Some(tag.as_str()) {
+                CandidateStatus::Selected
+            } else {
+                classify(&version, constraint_set.as_ref())
+            };
+            CandidateTag {
+                tag,
+                version: Some(version.to_string()),
+                status,
+            }
+        })
+        .collect();
+
+    candidates.extend(rejected);
+    candidates
+}
+
+/// Classify a non-selected semver tag against the constraint set.
+fn classify(version: &Version, constraint_set: Option<&ConstraintSet>) -> CandidateStatus {
+    // Wildcard: every version matches, so anything not selected was superseded
+    let Some(set) = constraint_set else {
+        return CandidateStatus::Superseded;
+    };
+
+    let is_prerelease = !version.pre.is_empty();
+    if set.satisfies(version) {
+        if is_prerelease && !set.allows_prerelease() {
+            CandidateStatus::Prerelease
+        } else {
+            CandidateStatus::Superseded
+        }
+    } else if is_prerelease {
+        // semver requirements reject prereleases outright; report that when the
+        // release version itself would have matched
+        let mut release = version.clone();
+        release.pre = semver::Prerelease::EMPTY;
+        if set.satisfies(&release) {
+            CandidateStatus::Prerelease
+        } else {
+            CandidateStatus::Unsatisfied
+        }
+    } else {
+        CandidateStatus::Unsatisfied
+    }
+}
+
+/// Attach explanations to the manifest dependencies that requested them.
+///
+/// Each direct dependency with a source gets its own entry (several dependencies
+/// may share one explanation). Explanations not requested by any manifest
+/// dependency come from transitive dependencies and are listed afterwards
+/// without a name.
+#[must_use]
+pub fn group_by_dependency(
+    manifest: &Manifest,
+    explanations: &[VersionExplanation],
+) -> Vec<DependencyExplanation> {
+    let mut grouped = Vec::new();
+    let mut used = vec![false; explanations.len()];
+
+    for (name, dep, resource_type) in manifest.all_dependencies_with_types() {
+        let Some(source) = dep.get_source() else {
+            continue;
+        };
+        let requested = dep.get_version().unwrap_or("HEAD");
+        if let Some(idx) =
+            explanations.iter().position(|e| e.source == source && e.requested == requested)
+        {
+            used[idx] = true;
+            grouped.push(DependencyExplanation {
+                name: Some(name.to_string()),
+                resource_type: Some(resource_type.to_plural().to_string()),
+                explanation: explanations[idx].clone(),
+            });
+        }
+    }
+
+    for (explanation, _) in explanations.iter().zip(&used).filter(|(_, used)| !**used) {
+        grouped.push(DependencyExplanation {
+            name: None,
+            resource_type: None,
+            explanation: explanation.clone(),
+        });
+    }
+
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    fn status_of(candidates: &[CandidateTag], tag: &str) -> CandidateStatus {
+        candidates.iter().find(|c| c.tag == tag).unwrap().status
+    }
+
+    #[test]
+    fn test_explain_caret_constraint() {
+        let all = tags(&["v1.0.0", "v1.2.0", "v1.3.0-beta.1", "v2.0.0", "agents-v1.5.0", "latest"]);
+        let candidates = explain_tag_selection("^1.0.0", &all, Some("v1.2.0"));
+
+        assert_eq!(candidates[0].tag, "v2.0.0");
+        assert_eq!(status_of(&candidates, "v1.2.0"), CandidateStatus::Selected);
+        assert_eq!(status_of(&candidates, "v1.0.0"), CandidateStatus::Superseded);
+        assert_eq!(status_of(&candidates, "v1.3.0-beta.1"), CandidateStatus::Prerelease);
+        assert_eq!(status_of(&candidates, "v2.0.0"), CandidateStatus::Unsatisfied);
+        assert_eq!(status_of(&candidates, "agents-v1.5.0"), CandidateStatus::PrefixMismatch);
+        assert_eq!(status_of(&candidates, "latest"), CandidateStatus::NotSemver);
+    }
+
+    #[test]
+    fn test_explain_prefixed_constraint() {
+        let all = tags(&["agents-v1.0.0", "agents-v1.1.0", "snippets-v1.1.0", "v1.1.0"]);
+        let candidates = explain_tag_selection("agents-^v1.0.0", &all, Some("agents-v1.1.0"));
+
+        assert_eq!(status_of(&candidates, "agents-v1.1.0"), CandidateStatus::Selected);
+        assert_eq!(status_of(&candidates, "agents-v1.0.0"), CandidateStatus::Superseded);
+        assert_eq!(status_of(&candidates, "snippets-v1.1.0"), CandidateStatus::PrefixMismatch);
+        assert_eq!(status_of(&candidates, "v1.1.0"), CandidateStatus::PrefixMismatch);
+    }
+
+    #[test]
+    fn test_explain_serializes_kebab_case() {
+        let mut explanation = VersionExplanation::new("community", "^1.0.0", "highest match");
+        explanation.candidates =
+            explain_tag_selection("^1.0.0", &tags(&["v1.0.0", "other-v1.0.0"]), Some("v1.0.0"));
+
+        let json = serde_json::to_value(&explanation).unwrap();
+        assert_eq!(json["candidates"][0]["status"], "selected");
+        assert_eq!(json["candidates"][1]["status"], "prefix-mismatch");
+        assert!(json.get("resolved_sha").is_none());
+    }
+
+    #[test]
+    fn test_explain_lowest_strategy() {
+        use crate::resolver::{ResolutionStrategy, version_resolver::find_matching_tag};
+
+        let all = tags(&["v1.0.0", "v1.2.0", "v2.0.0"]);
+        let selected =
+            find_matching_tag("^1.0.0", all.clone(), ResolutionStrategy::Lowest).unwrap();
+        assert_eq!(selected, "v1.0.0");
+
+        let candidates = explain_tag_selection("^1.0.0", &all, Some(&selected));
+        assert_eq!(status_of(&candidates, "v1.0.0"), CandidateStatus::Selected);
+        // The higher match was passed over, so it must not claim a newer one won
+        assert_eq!(status_of(&candidates, "v1.2.0"), CandidateStatus::Superseded);
+        assert!(!CandidateStatus::Superseded.describe().contains("newer"));
+        assert_eq!(status_of(&candidates, "v2.0.0"), CandidateStatus::Unsatisfied);
+    }
+}
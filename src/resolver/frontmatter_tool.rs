@@ -0,0 +1,194 @@
+//! Tool detection from resource frontmatter.
+//!
+//! A markdown resource can declare the tool it is written for with a top-level
+//! `tool` key in its frontmatter:
+//!
+//! ```markdown
+//! ---
+//! tool: opencode
+//! ---
+//! ```
+//!
+//! For manifest dependencies that omit `tool`, the declared tool takes
+//! precedence over the resource-type default (`[default-tools]` or the built-in
+//! default), as long as it is enabled and supports the resource type. A
+//! dependency whose explicit `tool` differs from the declared one is rejected,
+//! since the resource would be installed for a tool it was not written for.
+//!
+//! Patterns, skills and non-markdown resources keep the usual defaults, and so
+//! do transitive dependencies, which inherit their parent's tool.
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::core::ResourceType;
+use crate::manifest::{DetailedDependency, ResourceDependency};
+use crate::markdown::frontmatter::FrontmatterParser;
+
+use super::DependencyResolver;
+use super::resource_service::ResourceFetchingService;
+
+#[derive(Deserialize)]
+struct ToolFrontmatter {
+    tool: Option<String>,
+}
+
+impl DependencyResolver {
+    /// Applies tools declared in frontmatter to manifest dependencies.
+    ///
+    /// Dependencies without an explicit `tool` switch to the declared tool;
+    /// conflicts with an explicit `tool` are reported together.
+    pub(super) async fn apply_frontmatter_tools(
+        &self,
+        base_deps: &mut [(String, ResourceDependency, ResourceType)],
+    ) -> Result<()> {
+        // Unreadable resources are skipped here and reported by the later phases
+        let declared =
+            futures::future::join_all(base_deps.iter().map(|(_, dep, resource_type)| async move {
+                if dep.is_pattern()
+                    || *resource_type == ResourceType::Skill
+                    || !dep.get_path().ends_with(".md")
+                {
+                    return None;
+                }
+                let content =
+                    ResourceFetchingService::fetch_content(&self.core, dep, &self.version_service)
+                        .await
+                        .ok()?;
+                frontmatter_tool(&content)
+            }))
+            .await;
+
+        let manifest = self.core.manifest();
+        let mut conflicts = Vec::new();
+        for ((name, dep, resource_type), declared) in base_deps.iter_mut().zip(declared) {
+            let Some(declared) = declared else {
+                continue;
+            };
+            let current = dep
+                .get_tool()
+                .map(str::to_string)
+                .unwrap_or_else(|| manifest.get_default_tool(*resource_type));
+            if current == declared {
+                continue;
+            }
+
+            if !manifest.is_tool_defaulted(*resource_type, name) {
+                conflicts.push(format!(
+                    "  {resource_type} '{name}' sets tool '{current}', but its frontmatter declares '{declared}'"
+                ));
+                continue;
+            }
+
+            let enabled = manifest.get_tool_config(&declared).is_some_and(|config| config.enabled);
+            if !enabled || !manifest.is_resource_supported(&declared, *resource_type) {
+                tracing::debug!(
+                    "Ignoring frontmatter tool '{}' for {} '{}': not enabled for {}",
+                    declared,
+                    resource_type,
+                    name,
+                    resource_type.to_plural()
+                );
+                continue;
+            }
+
+            tracing::debug!(
+                "Using frontmatter tool '{}' for {} '{}' instead of default '{}'",
+                declared,
+                resource_type,
+                name,
+                current
+            );
+            *dep = with_tool(dep, declared);
+        }
+
+        if conflicts.is_empty() {
+            return Ok(());
+        }
+        conflicts.sort();
+        anyhow::bail!(
+            "{} a different tool than {} frontmatter:\n{}\n\n\
+             Remove `tool` from the dependency in agpm.toml to use the declared tool.",
+            if conflicts.len() == 1 {
+                "This dependency sets"
+            } else {
+                "These dependencies set"
+            },
+            if conflicts.len() == 1 {
+                "its"
+            } else {
+                "their"
+            },
+            conflicts.join("\n")
+        )
+    }
+}
+
+/// The tool declared by a top-level `tool` key in `content`'s frontmatter.
+///
+/// Frontmatter that is not valid YAML, for example because it still contains
+/// template syntax, declares no tool.
+pub(crate) fn frontmatter_tool(content: &str) -> Option<String> {
+    let raw = FrontmatterParser::new().extract_raw_frontmatter(content)?;
+    serde_yaml::from_str::<ToolFrontmatter>(&raw).ok()?.tool.filter(|tool| !tool.is_empty())
+}
+
+/// `dep` with its tool set to `tool`, expanding shorthand path dependencies.
+fn with_tool(dep: &ResourceDependency, tool: String) -> ResourceDependency {
+    match dep {
+        ResourceDependency::Simple(path) => {
+            ResourceDependency::Detailed(Box::new(DetailedDependency {
+                path: path.clone(),
+                source: None,
+                version: None,
+                branch: None,
+                rev: None,
+                command: None,
+                args: None,
+                target: None,
+                filename: None,
+                dependencies: None,
+                tool: Some(tool),
+                flatten: None,
+                install: None,
+                template_vars: None,
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
+            }))
+        }
+        ResourceDependency::Detailed(_) => {
+            let mut dep = dep.clone();
+            dep.set_tool(Some(tool));
+            dep
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frontmatter_tool() {
+        let content = "---\nname: reviewer\ntool: opencode\n---\n\n# Reviewer\n";
+        assert_eq!(frontmatter_tool(content).as_deref(), Some("opencode"));
+
+        assert_eq!(frontmatter_tool("---\nname: reviewer\n---\n\n# Reviewer\n"), None);
+        assert_eq!(frontmatter_tool("# Reviewer\n\ntool: opencode\n"), None);
+        assert_eq!(frontmatter_tool("---\ntool: [unclosed\n---\n"), None);
+    }
+
+    #[test]
+    fn test_with_tool_expands_shorthand() {
+        let dep = with_tool(
+            &ResourceDependency::Simple("agents/reviewer.md".to_string()),
+            "opencode".to_string(),
+        );
+        assert_eq!(dep.get_path(), "agents/reviewer.md");
+        assert_eq!(dep.get_tool(), Some("opencode"));
+        assert!(dep.is_local());
+    }
+}
@@ -26,6 +26,18 @@ impl DependencyResolver {
             project_patches: self.core.manifest.project_patches.clone(),
             private_patches: self.core.manifest.private_patches.clone(),
             manifest_dir: self.core.manifest.manifest_dir.clone(),
+            install: self.core.manifest.install,
+            transitive: self.core.manifest.transitive.clone(),
+            resolution: self.core.manifest.resolution.clone(),
+            integrity: self.core.manifest.integrity,
+            constraints: self.core.manifest.constraints.clone(),
+            default_versions: self.core.manifest.default_versions.clone(),
+            tag_patterns: self.core.manifest.tag_patterns.clone(),
+            path_aliases: self.core.manifest.path_aliases.clone(),
+            require_tag: self.core.manifest.require_tag,
+            require_validated: self.core.manifest.require_validated,
+            tag_preference: self.core.manifest.tag_preference,
+            install_root: self.core.manifest.install_root.clone(),
             ..Default::default()
         };
 
@@ -5,10 +5,11 @@
 
 use crate::core::ResourceType;
 use crate::lockfile::{LockFile, LockedResource, lockfile_dependency_ref::LockfileDependencyRef};
-use crate::manifest::{Manifest, ResourceDependency};
+use crate::manifest::{ConflictPolicy, DuplicateNamePolicy, Manifest, ResourceDependency};
 use crate::resolver::types as dependency_helpers;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
 use std::str::FromStr;
 
 // Type aliases for internal lookups
@@ -706,15 +707,18 @@ fn rewrite_dependency_string(
 /// * `resource_type` - Type of the resource (agent, snippet, command, etc.)
 /// * `name` - Resource name to look up patches for
 /// * `manifest_alias` - Optional manifest alias for pattern-expanded resources
+/// * `version` - Resolved version, checked against the patch's `when` constraint
 ///
 /// # Returns
 ///
-/// BTreeMap of patch key-value pairs, or empty BTreeMap if no patches defined
+/// BTreeMap of patch key-value pairs, or empty BTreeMap if no patches defined or
+/// the patch's `when` constraint excludes `version`
 pub(super) fn get_patches_for_resource(
     manifest: &Manifest,
     resource_type: ResourceType,
     name: &str,
     manifest_alias: Option<&str>,
+    version: Option<&str>,
 ) -> BTreeMap<String, toml::Value> {
     // Use manifest_alias for pattern-expanded resources, name for regular resources
     let lookup_name = manifest_alias.unwrap_or(name);
@@ -729,7 +733,10 @@ pub(super) fn get_patches_for_resource(
         ResourceType::Skill => &manifest.patches.skills,
     };
 
-    patches.get(lookup_name).cloned().unwrap_or_default()
+    patches
+        .get(lookup_name)
+        .and_then(|patch| crate::manifest::patches::patch_for_version(patch, version))
+        .unwrap_or_default()
 }
 
 /// Build the complete merged template variable context for a dependency.
@@ -904,6 +911,190 @@ impl VariantInputs {
     }
 }
 
+/// Renames colliding targets for dependencies that opt in with `on_conflict = "rename"`.
+///
+/// An entry whose `installed_at` path is shared with an entry from a different
+/// source is moved to `<source>-<filename>` in the same directory when its
+/// manifest dependency uses [`ConflictPolicy::Rename`]. Local and transitive
+/// entries are never renamed, and any collision left over is reported by
+/// [`detect_target_conflicts`].
+pub(super) fn rename_conflicting_targets(lockfile: &mut LockFile, manifest: &Manifest) {
+    // Hooks, MCP servers and skills are excluded for the same reasons as in
+    // `detect_target_conflicts`
+    const FILE_TYPES: [ResourceType; 4] =
+        [ResourceType::Agent, ResourceType::Snippet, ResourceType::Command, ResourceType::Script];
+
+    let mut sources_by_path: HashMap<String, HashSet<Option<String>>> = HashMap::new();
+    for resource_type in &FILE_TYPES {
+        for entry in lockfile.get_resources(resource_type) {
+            if entry.install != Some(false) {
+                sources_by_path
+                    .entry(entry.installed_at.clone())
+                    .or_default()
+                    .insert(entry.source.clone());
+            }
+        }
+    }
+
+    for resource_type in &FILE_TYPES {
+        let deps = manifest.get_resources(resource_type);
+        for entry in lockfile.get_resources_mut(resource_type) {
+            let Some(source) = entry.source.as_deref() else {
+                continue;
+            };
+            let collides = sources_by_path.get(&entry.installed_at).is_some_and(|s| s.len() > 1);
+            let renames = entry
+                .manifest_alias
+                .as_ref()
+                .and_then(|alias| deps.get(alias))
+                .is_some_and(|dep| dep.get_on_conflict() == ConflictPolicy::Rename);
+            if entry.install == Some(false) || !collides || !renames {
+                continue;
+            }
+
+            let renamed = match entry.installed_at.rsplit_once('/') {
+                Some((dir, file)) => format!("{dir}/{source}-{file}"),
+                None => format!("{source}-{}", entry.installed_at),
+            };
+            tracing::debug!("Renaming conflicting target {} to {}", entry.installed_at, renamed);
+            entry.installed_at = renamed;
+        }
+    }
+}
+
+/// Keeps only the newest copy of transitive resources reached through several sources.
+///
+/// Applies with `resolution.duplicate_name = "newest"`. Entries of one type
+/// with the same name and target path but different sources are compared by
+/// their resolved version, the entry with the highest version stays, and
+/// dependency references to the others are pointed at it. Groups that include
+/// a direct dependency, or whose highest version cannot be told apart, are left
+/// for [`detect_target_conflicts`] to report.
+pub(super) fn select_newest_duplicates(lockfile: &mut LockFile, manifest: &Manifest) {
+    // Hooks, MCP servers and skills are excluded for the same reasons as in
+    // `detect_target_conflicts`
+    const FILE_TYPES: [ResourceType; 4] =
+        [ResourceType::Agent, ResourceType::Snippet, ResourceType::Command, ResourceType::Script];
+
+    if manifest.resolution.duplicate_name != DuplicateNamePolicy::Newest {
+        return;
+    }
+
+    // (type, source, normalized path) of a dropped entry -> (source, path) of the winner
+    let mut replaced: HashMap<ResourceKey, (String, String)> = HashMap::new();
+    for resource_type in FILE_TYPES {
+        let resources = lockfile.get_resources_mut(&resource_type);
+        let mut groups: BTreeMap<(&str, &str), Vec<usize>> = BTreeMap::new();
+        for (index, entry) in resources.iter().enumerate() {
+            if entry.source.is_some() {
+                groups
+                    .entry((entry.name.as_str(), entry.installed_at.as_str()))
+                    .or_default()
+                    .push(index);
+            }
+        }
+
+        let mut dropped = Vec::new();
+        for indices in groups.values() {
+            let entries: Vec<&LockedResource> = indices.iter().map(|&i| &resources[i]).collect();
+            let sources: HashSet<_> = entries.iter().map(|e| &e.source).collect();
+            if sources.len() < 2 || entries.iter().any(|e| e.manifest_alias.is_some()) {
+                continue;
+            }
+            let Some(versions) = entries
+                .iter()
+                .map(|e| entry_semver(e, manifest))
+                .collect::<Option<Vec<semver::Version>>>()
+            else {
+                continue;
+            };
+            let newest = versions.iter().max().expect("group has entries");
+            let winners: Vec<&LockedResource> = entries
+                .iter()
+                .zip(&versions)
+                .filter(|(_, version)| *version == newest)
+                .map(|(entry, _)| *entry)
+                .collect();
+            let winner = winners[0];
+            if winners.iter().any(|e| e.source != winner.source) {
+                continue;
+            }
+
+            let winner_source = winner.source.clone().unwrap_or_default();
+            tracing::debug!(
+                "Using {} '{}' from source '{}' ({}) for duplicate name across sources",
+                resource_type,
+                winner.name,
+                winner_source,
+                newest
+            );
+            for (&index, entry) in indices.iter().zip(&entries) {
+                if entry.source != winner.source {
+                    replaced.insert(
+                        (
+                            resource_type,
+                            dependency_helpers::normalize_lookup_path(&entry.path),
+                            entry.source.clone(),
+                        ),
+                        (winner_source.clone(), winner.path.clone()),
+                    );
+                    dropped.push(index);
+                }
+            }
+        }
+
+        dropped.sort_unstable();
+        for index in dropped.into_iter().rev() {
+            resources.remove(index);
+        }
+    }
+
+    if replaced.is_empty() {
+        return;
+    }
+    for resource_type in ResourceType::all() {
+        for entry in lockfile.get_resources_mut(resource_type) {
+            let parent_source = entry.source.clone();
+            for dep in &mut entry.dependencies {
+                let Ok(dep_ref) = LockfileDependencyRef::from_str(dep) else {
+                    continue;
+                };
+                let key = (
+                    dep_ref.resource_type,
+                    dependency_helpers::normalize_lookup_path(&dep_ref.path),
+                    dep_ref.source.clone().or_else(|| parent_source.clone()),
+                );
+                if let Some((source, path)) = replaced.get(&key) {
+                    *dep = LockfileDependencyRef::git(
+                        source.clone(),
+                        dep_ref.resource_type,
+                        path.clone(),
+                        None,
+                    )
+                    .to_string();
+                }
+            }
+            let mut seen = HashSet::new();
+            entry.dependencies.retain(|dep| seen.insert(dep.clone()));
+        }
+    }
+}
+
+/// The resolved version of `entry` as semver, using the source's `[tag-patterns]` entry.
+fn entry_semver(entry: &LockedResource, manifest: &Manifest) -> Option<semver::Version> {
+    let tag = entry.version.as_deref()?;
+    let pattern = entry
+        .source
+        .as_ref()
+        .and_then(|source| manifest.tag_patterns.get(source))
+        .and_then(|pattern| crate::version::tag_pattern::TagPattern::parse(pattern).ok());
+    let version = match &pattern {
+        Some(pattern) => pattern.extract(tag)?,
+        None => crate::version::split_prefix_and_version(tag).1,
+    };
+    semver::Version::parse(version.strip_prefix('v').unwrap_or(version)).ok()
+}
+
 /// Detects conflicts where multiple dependencies resolve to the same installation path.
 ///
 /// This method validates that no two dependencies will overwrite each other during
@@ -1056,6 +1247,21 @@ pub(super) fn detect_target_conflicts(lockfile: &LockFile) -> Result<()> {
     Ok(())
 }
 
+/// Check that every installed resource fits within the platform's path limits.
+///
+/// Runs during resolution so that an overly deep install target is reported
+/// with its dependency name before any files are written.
+pub(super) fn validate_install_path_lengths(lockfile: &LockFile, project_dir: &Path) -> Result<()> {
+    for resource in lockfile.all_resources() {
+        if resource.install == Some(false) {
+            continue;
+        }
+        crate::utils::validate_install_path_length(project_dir, &resource.installed_at)
+            .with_context(|| format!("Cannot install '{}'", resource.name))?;
+    }
+    Ok(())
+}
+
 /// Add version information to dependency references in all lockfile entries.
 ///
 /// This post-processing step updates the `dependencies` field of each locked resource
@@ -1131,6 +1337,40 @@ pub(super) fn add_version_to_all_dependencies(lockfile: &mut LockFile) {
     }
 }
 
+/// Apply the manifest's `[install]` toggles to every lockfile entry.
+///
+/// Direct dependencies are already marked in [`Manifest::all_dependencies_with_types`];
+/// this also covers transitive ones. Entries of disabled types get `install = false`,
+/// or are removed together with references to them when pruning.
+pub(super) fn apply_install_toggles(lockfile: &mut LockFile, manifest: &Manifest) {
+    let toggles = &manifest.install;
+    if toggles.is_default() {
+        return;
+    }
+
+    for resource_type in ResourceType::all() {
+        let resources = lockfile.get_resources_mut(resource_type);
+        if toggles.is_pruned(*resource_type) {
+            resources.clear();
+        } else if !toggles.is_enabled(*resource_type) {
+            for entry in resources {
+                entry.install = Some(false);
+            }
+        }
+    }
+
+    if toggles.prune_disabled {
+        for resource_type in ResourceType::all() {
+            for entry in lockfile.get_resources_mut(resource_type) {
+                entry.dependencies.retain(|dep| {
+                    LockfileDependencyRef::from_str(dep)
+                        .map_or(true, |dep_ref| !toggles.is_pruned(dep_ref.resource_type))
+                });
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1174,6 +1414,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         lockfile.snippets.push(LockedResource {
@@ -1195,6 +1436,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         });
 
         lockfile
@@ -1225,6 +1467,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         };
 
         builder.add_or_update_lockfile_entry(&mut lockfile, entry);
@@ -1258,6 +1501,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         };
 
         builder.add_or_update_lockfile_entry(&mut lockfile, updated_entry);
@@ -1326,6 +1570,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         };
 
         LockfileBuilder::collect_transitive_children(&lockfile, &parent, &mut entries_to_remove);
@@ -1368,6 +1613,11 @@ test-repo = "https://example.com/repo.git"
                 "project": { "name": "Production" },
                 "config": { "model": "claude-3-opus", "temperature": 0.5 }
             })),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
 
         // Call build_merged_variant_inputs
@@ -1412,6 +1662,7 @@ test-repo = "https://example.com/repo.git"
             variant_inputs: VariantInputs::new(json!({"lang": "rust"})),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         };
 
         // Create transitive dependency with template_vars = {lang: "python"}
@@ -1434,6 +1685,7 @@ test-repo = "https://example.com/repo.git"
             variant_inputs: VariantInputs::new(json!({"lang": "python"})),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         };
 
         // According to the CRITICAL note in the code:
@@ -1450,4 +1702,58 @@ test-repo = "https://example.com/repo.git"
              They represent distinct resources that both need to exist in the lockfile."
         );
     }
+
+    #[test]
+    fn test_rename_conflicting_targets_only_renames_opted_in_entries() {
+        let mut manifest = Manifest::default();
+        for (alias, policy) in [("official", "rename"), ("community", "error")] {
+            let dep: ResourceDependency = toml::from_str(&format!(
+                "source = \"{alias}\"\npath = \"agents/*.md\"\non_conflict = \"{policy}\""
+            ))
+            .unwrap();
+            manifest.agents.insert(alias.to_string(), dep);
+        }
+
+        let mut lockfile = create_test_lockfile();
+        lockfile.agents[0].manifest_alias = Some("community".to_string());
+        let mut official = lockfile.agents[0].clone();
+        official.source = Some("official".to_string());
+        official.manifest_alias = Some("official".to_string());
+        official.resolved_commit = Some("fed321".to_string());
+        lockfile.agents.push(official);
+
+        rename_conflicting_targets(&mut lockfile, &manifest);
+
+        assert_eq!(lockfile.agents[0].installed_at, ".claude/agents/test-agent.md");
+        assert_eq!(lockfile.agents[1].installed_at, ".claude/agents/official-test-agent.md");
+        assert_eq!(lockfile.snippets[0].installed_at, ".claude/snippets/test-snippet.md");
+        assert!(detect_target_conflicts(&lockfile).is_ok());
+    }
+
+    #[test]
+    fn test_select_newest_duplicates() {
+        let mut manifest = Manifest::default();
+        manifest.resolution.duplicate_name = DuplicateNamePolicy::Newest;
+
+        let mut lockfile = create_test_lockfile();
+        let mut official = lockfile.agents[0].clone();
+        official.source = Some("official".to_string());
+        official.version = Some("v1.2.0".to_string());
+        official.resolved_commit = Some("fed321".to_string());
+        lockfile.agents.push(official);
+        lockfile.snippets[0].dependencies = vec!["agent:agents/test-agent".to_string()];
+
+        // Equal versions from different sources stay ambiguous
+        let mut tied = lockfile.clone();
+        tied.agents[1].version = Some("v1.0.0".to_string());
+        select_newest_duplicates(&mut tied, &manifest);
+        assert_eq!(tied.agents.len(), 2);
+        assert!(detect_target_conflicts(&tied).is_err());
+
+        select_newest_duplicates(&mut lockfile, &manifest);
+        assert_eq!(lockfile.agents.len(), 1);
+        assert_eq!(lockfile.agents[0].source.as_deref(), Some("official"));
+        assert_eq!(lockfile.snippets[0].dependencies, ["official/agent:agents/test-agent.md"]);
+        assert!(detect_target_conflicts(&lockfile).is_ok());
+    }
 }
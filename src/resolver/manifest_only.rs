@@ -0,0 +1,477 @@
+//! Manifest-only resolution, without worktrees (`agpm resolve --manifest-only`).
+//!
+//! Answers "will this manifest resolve?" faster than an install or a dry run.
+//! Versions are resolved from the tags and branches of each source's bare
+//! repository, exactly as for installation, but nothing is checked out:
+//! frontmatter is read with `git show` and patterns are matched against the
+//! commit's file list. Local directories and archives are read in place.
+//!
+//! Transitive dependencies follow the rules of the full resolver for paths
+//! (file-relative or relative to the source root), inherited versions,
+//! `[constraints]` pins and the `[transitive]` policy. The result is a version
+//! map, not a lockfile: frontmatter is not rendered with template variables,
+//! installation targets are not computed, and a resource required at two
+//! different commits is reported as a conflict without backtracking.
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::cache::Cache;
+use crate::core::ResourceType;
+use crate::git::GitRepo;
+use crate::manifest::Manifest;
+use crate::metadata::MetadataExtractor;
+use crate::source::SourceManager;
+use crate::utils::{normalize_path, normalize_path_for_storage};
+use crate::version::tag_pattern::TagPattern;
+
+use super::path_resolver::is_file_relative_path;
+use super::pattern_expander::generate_dependency_name;
+use super::source_context::SourceContext;
+use super::transitive_resolver::check_transitive_constraint;
+use super::types::ResolutionMode;
+use super::version_resolver::VersionResolver;
+
+/// A resource in the resolved version map.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResolvedResource {
+    /// Resource type.
+    #[serde(rename = "type")]
+    pub resource_type: ResourceType,
+    /// Manifest name, or the generated name of a transitive dependency or pattern match.
+    pub name: String,
+    /// Source name; `None` for local dependencies.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Path within the source.
+    pub path: String,
+    /// Resolved tag or branch; `None` for local resources.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Resolved commit; `None` for local resources.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+/// Resolves every dependency of `manifest` and its transitive dependencies
+/// without creating worktrees.
+///
+/// Returns the resolved resources sorted by type and name.
+///
+/// # Errors
+///
+/// Returns an error if a source cannot be fetched, a version does not resolve,
+/// a resource does not exist at its resolved commit, or a resource is required
+/// at two different commits.
+pub async fn resolve_manifest_only(
+    manifest: &Manifest,
+    cache: Cache,
+) -> Result<Vec<ResolvedResource>> {
+    let mut versions = VersionResolver::new(cache);
+    versions.set_tag_preference(manifest.tag_preference);
+    versions.set_require_validated(manifest.require_validated);
+    for (source, pattern) in &manifest.tag_patterns {
+        versions.set_tag_pattern(source, TagPattern::parse(pattern)?);
+    }
+
+    let mut pending = Vec::new();
+    for (name, dep, resource_type) in manifest.all_dependencies_with_types() {
+        if let (Some(source), Some(channel)) = (dep.get_source(), dep.get_channel()) {
+            versions.set_channel(source, dep.get_version(), channel)?;
+        }
+        pending.push(Request {
+            resource_type,
+            name: (!dep.is_pattern()).then(|| name.to_string()),
+            source: dep.get_source().map(str::to_string),
+            path: dep.get_path().to_string(),
+            version: dep.get_version().map(str::to_string),
+            mode: dep.resolution_mode(),
+        });
+    }
+
+    let mut resolver = ManifestOnlyResolver {
+        manifest,
+        sources: SourceManager::from_manifest_with_global(manifest).await?,
+        versions,
+        files: HashMap::new(),
+        resolved: BTreeMap::new(),
+    };
+
+    // Each round resolves the versions of one level of the graph in a batch
+    while !pending.is_empty() {
+        resolver.resolve_versions(&pending).await?;
+        let mut next = Vec::new();
+        for request in pending {
+            next.extend(resolver.visit(request).await?);
+        }
+        pending = next;
+    }
+
+    let mut resources: Vec<ResolvedResource> = resolver.resolved.into_values().collect();
+    resources.sort_by(|a, b| (a.resource_type, &a.name).cmp(&(b.resource_type, &b.name)));
+    Ok(resources)
+}
+
+/// A resource to resolve: a manifest dependency or a declared transitive dependency.
+struct Request {
+    resource_type: ResourceType,
+    /// Manifest name; `None` for patterns and transitive dependencies.
+    name: Option<String>,
+    source: Option<String>,
+    path: String,
+    version: Option<String>,
+    mode: ResolutionMode,
+}
+
+/// Where the files of a resolved resource are read from.
+enum Tree {
+    /// A commit of a bare repository.
+    Commit {
+        repo: GitRepo,
+        commit: String,
+    },
+    /// A directory on disk: the project, a local source or an extracted archive.
+    Dir(PathBuf),
+}
+
+struct ManifestOnlyResolver<'a> {
+    manifest: &'a Manifest,
+    sources: SourceManager,
+    versions: VersionResolver,
+    /// Files of each commit, listed once.
+    files: HashMap<String, Vec<String>>,
+    /// Resolved resources by type, source and path.
+    resolved: BTreeMap<(ResourceType, Option<String>, String), ResolvedResource>,
+}
+
+impl ManifestOnlyResolver<'_> {
+    fn source_url(&self, source: &str) -> Result<String> {
+        self.sources
+            .get_source_url(source)
+            .ok_or_else(|| anyhow::anyhow!("Source '{source}' not found"))
+    }
+
+    /// Fetches the sources of `requests` and resolves their versions.
+    async fn resolve_versions(&self, requests: &[Request]) -> Result<()> {
+        for request in requests {
+            if let Some(source) = &request.source {
+                let url = self.source_url(source)?;
+                self.versions.add_version(source, &url, request.version.as_deref(), request.mode);
+            }
+        }
+        self.versions.pre_sync_sources(None).await?;
+        self.versions.resolve_all(None).await
+    }
+
+    /// Records the resources `request` resolves to and returns their dependencies.
+    async fn visit(&mut self, request: Request) -> Result<Vec<Request>> {
+        let (tree, version, commit) = self.locate(&request)?;
+        let path = match &request.source {
+            Some(source) => self
+                .manifest
+                .aliased_path(source, &request.path, version.as_deref())
+                .unwrap_or(&request.path)
+                .to_string(),
+            None => request.path.clone(),
+        };
+
+        let is_pattern = path.contains(['*', '?', '[']);
+        let paths = if is_pattern {
+            self.expand(&tree, request.resource_type, &path).await?
+        } else if self.exists(&tree, request.resource_type, &path).await? {
+            vec![path]
+        } else {
+            let at = match (&request.source, &version) {
+                (Some(source), Some(version)) => format!(" in source '{source}' at {version}"),
+                (Some(source), None) => format!(" in source '{source}'"),
+                (None, _) => String::new(),
+            };
+            bail!(
+                "{} '{}' not found: '{path}' does not exist{at}",
+                request.resource_type,
+                request.name.as_deref().unwrap_or(&request.path)
+            );
+        };
+
+        let mut dependencies = Vec::new();
+        for path in paths {
+            let name = match (&request.name, is_pattern) {
+                (Some(name), false) => name.clone(),
+                _ => generate_dependency_name(&path, &self.source_context(&request)),
+            };
+            let key = (request.resource_type, request.source.clone(), path.clone());
+            if let Some(existing) = self.resolved.get(&key) {
+                if existing.commit != commit {
+                    bail!(
+                        "{} '{}' from source '{}' is required at two different commits: {} ({}) and {} ({})",
+                        request.resource_type,
+                        path,
+                        request.source.as_deref().unwrap_or("local"),
+                        existing.version.as_deref().unwrap_or("HEAD"),
+                        existing.commit.as_deref().unwrap_or("local"),
+                        version.as_deref().unwrap_or("HEAD"),
+                        commit.as_deref().unwrap_or("local"),
+                    );
+                }
+                continue;
+            }
+
+            dependencies.extend(self.dependencies_of(&tree, &request, &name, &path).await?);
+            self.resolved.insert(
+                key,
+                ResolvedResource {
+                    resource_type: request.resource_type,
+                    name,
+                    source: request.source.clone(),
+                    path,
+                    version: version.clone(),
+                    commit: commit.clone(),
+                },
+            );
+        }
+        Ok(dependencies)
+    }
+
+    /// The tree of a resolved request, with its resolved version and commit.
+    fn locate(&self, request: &Request) -> Result<(Tree, Option<String>, Option<String>)> {
+        let Some(source) = &request.source else {
+            let dir = self.manifest.manifest_dir.clone().unwrap_or_else(|| PathBuf::from("."));
+            return Ok((Tree::Dir(dir), None, None));
+        };
+        let url = self.source_url(source)?;
+        let repo = self
+            .versions
+            .get_bare_repo_path(source)
+            .ok_or_else(|| anyhow::anyhow!("Source '{source}' was not synced"))?;
+        if crate::utils::is_local_path(&url) {
+            return Ok((Tree::Dir(repo), None, None));
+        }
+
+        let version = request.version.as_deref().unwrap_or("HEAD");
+        let resolved = self.versions.get_resolved(source, version).ok_or_else(|| {
+            anyhow::anyhow!("Version '{version}' of source '{source}' was not resolved")
+        })?;
+        let tree = if crate::source::archive::is_archive_url(&url) {
+            Tree::Dir(repo)
+        } else {
+            Tree::Commit {
+                repo: GitRepo::new(repo),
+                commit: resolved.sha.clone(),
+            }
+        };
+        Ok((tree, Some(resolved.resolved_ref), Some(resolved.sha)))
+    }
+
+    fn source_context(&self, request: &Request) -> SourceContext {
+        match (&request.source, &self.manifest.manifest_dir) {
+            (Some(source), _) => SourceContext::remote(source),
+            (None, Some(dir)) => SourceContext::local(dir),
+            (None, None) => SourceContext::local("."),
+        }
+    }
+
+    /// Files of a commit, listed on first use.
+    async fn files(&mut self, repo: &GitRepo, commit: &str) -> Result<&[String]> {
+        if !self.files.contains_key(commit) {
+            let files = repo.list_files_at(commit).await?;
+            self.files.insert(commit.to_string(), files);
+        }
+        Ok(&self.files[commit])
+    }
+
+    /// Whether the resource at `path` exists; skills are directories with a `SKILL.md`.
+    async fn exists(
+        &mut self,
+        tree: &Tree,
+        resource_type: ResourceType,
+        path: &str,
+    ) -> Result<bool> {
+        let file = resource_file(resource_type, path);
+        Ok(match tree {
+            Tree::Commit {
+                repo,
+                commit,
+            } => self.files(repo, commit).await?.contains(&file),
+            Tree::Dir(dir) => dir.join(&file).is_file(),
+        })
+    }
+
+    /// Paths of the resources matching `pattern`.
+    async fn expand(
+        &mut self,
+        tree: &Tree,
+        resource_type: ResourceType,
+        pattern: &str,
+    ) -> Result<Vec<String>> {
+        let mut paths: Vec<String> = match tree {
+            Tree::Commit {
+                repo,
+                commit,
+            } => {
+                let matcher = glob::Pattern::new(pattern)
+                    .with_context(|| format!("Invalid pattern '{pattern}'"))?;
+                let options = glob::MatchOptions {
+                    require_literal_separator: true,
+                    ..glob::MatchOptions::default()
+                };
+                self.files(repo, commit)
+                    .await?
+                    .iter()
+                    .filter_map(|file| match resource_type {
+                        ResourceType::Skill => file.strip_suffix("/SKILL.md"),
+                        _ => Some(file.as_str()),
+                    })
+                    .filter(|path| matcher.matches_with(path, options))
+                    .map(str::to_string)
+                    .collect()
+            }
+            Tree::Dir(dir) => glob::glob(&dir.join(pattern).to_string_lossy())
+                .with_context(|| format!("Invalid pattern '{pattern}'"))?
+                .filter_map(std::result::Result::ok)
+                .filter(|path| {
+                    path.join("SKILL.md").is_file() || resource_type != ResourceType::Skill
+                })
+                .filter(|path| path.is_file() || resource_type == ResourceType::Skill)
+                .map(|path| match path.strip_prefix(dir) {
+                    Ok(relative) => normalize_path_for_storage(relative),
+                    Err(_) => normalize_path_for_storage(&path),
+                })
+                .collect(),
+        };
+        paths.sort();
+        Ok(paths)
+    }
+
+    async fn read(&self, tree: &Tree, file: &str) -> Result<String> {
+        match tree {
+            Tree::Commit {
+                repo,
+                commit,
+            } => repo.read_file_at(commit, file).await,
+            Tree::Dir(dir) => {
+                let path = dir.join(file);
+                tokio::fs::read_to_string(&path)
+                    .await
+                    .with_context(|| format!("Failed to read resource file: {}", path.display()))
+            }
+        }
+    }
+
+    /// The transitive dependencies declared by the resource at `path`.
+    async fn dependencies_of(
+        &self,
+        tree: &Tree,
+        request: &Request,
+        name: &str,
+        path: &str,
+    ) -> Result<Vec<Request>> {
+        let manifest = self.manifest;
+        let file = resource_file(request.resource_type, path);
+        if !manifest.transitive.is_enabled(request.resource_type)
+            || !(file.ends_with(".md") || file.ends_with(".json"))
+        {
+            return Ok(Vec::new());
+        }
+
+        let content = self.read(tree, &file).await?;
+        let metadata = MetadataExtractor::extract_with_field(
+            Path::new(&file),
+            &content,
+            None,
+            None,
+            manifest.transitive.dependency_field(),
+        )
+        .with_context(|| format!("Failed to extract metadata from: {file}"))?;
+        let Some(declared) = metadata.get_dependencies_typed() else {
+            return Ok(Vec::new());
+        };
+        let declared: BTreeMap<_, _> = declared.into_iter().collect();
+
+        let mut dependencies = Vec::new();
+        for (resource_type, specs) in declared {
+            for spec in specs {
+                if let Some(source) = &request.source
+                    && !manifest.transitive.permits_source(source, &manifest.sources)
+                {
+                    bail!(
+                        "'{name}' declares a transitive dependency on '{}' from source '{source}', \
+                        which [transitive] restrict_sources does not permit.\n\
+                        Add '{source}' to [transitive] allowed_sources (or to [sources] if no allowlist is set) \
+                        to trust its transitive dependencies.",
+                        spec.path,
+                    );
+                }
+
+                let dep_path = transitive_path(&file, &spec.path);
+                let version = match &request.source {
+                    Some(source) => {
+                        let dep_name =
+                            generate_dependency_name(&dep_path, &SourceContext::remote(source));
+                        match manifest.constraints.get(&dep_name) {
+                            Some(pinned) => {
+                                check_transitive_constraint(
+                                    &dep_name,
+                                    pinned,
+                                    spec.version.as_deref(),
+                                    name,
+                                )?;
+                                Some(pinned.clone())
+                            }
+                            None => spec.version.clone().or_else(|| request.version.clone()),
+                        }
+                    }
+                    None => None,
+                };
+                dependencies.push(Request {
+                    resource_type,
+                    name: None,
+                    source: request.source.clone(),
+                    path: dep_path,
+                    version,
+                    mode: ResolutionMode::Version,
+                });
+            }
+        }
+        Ok(dependencies)
+    }
+}
+
+/// The file holding a resource's content and frontmatter.
+fn resource_file(resource_type: ResourceType, path: &str) -> String {
+    match resource_type {
+        ResourceType::Skill => format!("{}/SKILL.md", path.trim_end_matches('/')),
+        _ => path.to_string(),
+    }
+}
+
+/// Path of a transitive dependency within the tree of the file declaring it.
+///
+/// Paths starting with `./` or `../` and bare file names are relative to the
+/// declaring file; other paths are relative to the root of the source.
+fn transitive_path(parent_file: &str, dep_path: &str) -> String {
+    let path = if is_file_relative_path(dep_path) || !dep_path.contains('/') {
+        Path::new(parent_file).parent().unwrap_or_else(|| Path::new("")).join(dep_path)
+    } else {
+        PathBuf::from(dep_path)
+    };
+    normalize_path_for_storage(normalize_path(&path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transitive_path() {
+        assert_eq!(transitive_path("agents/reviewer.md", "./helper.md"), "agents/helper.md");
+        assert_eq!(transitive_path("agents/reviewer.md", "helper.md"), "agents/helper.md");
+        assert_eq!(
+            transitive_path("agents/review/reviewer.md", "../../snippets/style.md"),
+            "snippets/style.md"
+        );
+        assert_eq!(transitive_path("agents/reviewer.md", "snippets/style.md"), "snippets/style.md");
+        assert_eq!(resource_file(ResourceType::Skill, "skills/pdf/"), "skills/pdf/SKILL.md");
+    }
+}
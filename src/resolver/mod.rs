@@ -21,14 +21,19 @@ pub mod conflict_service;
 pub mod dependency_graph;
 mod dependency_processing;
 mod entry_builder;
+pub mod explain;
+mod frontmatter_tool;
 mod incremental_update;
 pub mod lockfile_builder;
+pub mod manifest_only;
+mod path_aliases;
 pub mod path_resolver;
 pub mod pattern_expander;
 pub mod resource_service;
 pub mod sha_conflict_detector;
 pub mod skills;
 pub mod source_context;
+pub(crate) mod tag_policy;
 pub mod transitive_extractor;
 pub mod transitive_resolver;
 pub mod types;
@@ -60,7 +65,7 @@ pub use resource_service::ResourceFetchingService;
 pub use types::ResolutionCore;
 pub use version_resolver::{
     VersionResolutionService, VersionResolver as VersionResolverExport, find_best_matching_tag,
-    is_version_constraint, parse_tags_to_versions,
+    find_matching_tag, is_version_constraint, parse_tags_to_versions,
 };
 
 // Legacy re-exports for compatibility
@@ -69,7 +74,8 @@ pub use lockfile_builder::LockfileBuilder;
 pub use pattern_expander::{expand_pattern_to_concrete_deps, generate_dependency_name};
 pub use types::{
     ConflictDetectionKey, DependencyKey, ManifestOverride, ManifestOverrideIndex, OverrideKey,
-    ResolutionContext, ResolvedDependenciesMap, ResolvedDependencyInfo, TransitiveContext,
+    ResolutionContext, ResolutionStrategy, ResolvedDependenciesMap, ResolvedDependencyInfo,
+    TransitiveContext,
 };
 
 pub use version_resolver::{PreparedSourceVersion, VersionResolver, WorktreeManager};
@@ -193,9 +199,15 @@ impl DependencyResolver {
     /// * `pattern_service` - Pattern expansion service
     fn init_dependencies(
         core: ResolutionCore,
-        version_service: VersionResolutionService,
+        mut version_service: VersionResolutionService,
         pattern_service: PatternExpansionService,
     ) -> Result<Self> {
+        version_service.set_tag_preference(core.manifest.tag_preference);
+        version_service.set_require_validated(core.manifest.require_validated);
+        for (source, pattern) in &core.manifest.tag_patterns {
+            version_service
+                .set_tag_pattern(source, crate::version::tag_pattern::TagPattern::parse(pattern)?);
+        }
         Ok(Self {
             core,
             version_service,
@@ -367,10 +379,13 @@ impl DependencyResolver {
         progress: Option<std::sync::Arc<crate::utils::MultiPhaseProgress>>,
     ) -> Result<LockFile> {
         // Phase 1: Preparation and manifest loading
-        let (base_deps, mut lockfile) = self.prepare_resolution(&progress).await?;
+        let (mut base_deps, mut lockfile) = self.prepare_resolution(&progress).await?;
 
         // Phase 2: Pre-sync sources
         self.pre_sync_sources_if_needed(&base_deps, progress.clone()).await?;
+        self.enforce_tag_policy(&base_deps).await?;
+        self.apply_path_aliases(&mut base_deps).await?;
+        self.apply_frontmatter_tools(&mut base_deps).await?;
 
         // Phase 3: Resolve transitive dependencies
         let all_deps = self
@@ -845,8 +860,14 @@ impl DependencyResolver {
         progress: &Option<std::sync::Arc<crate::utils::MultiPhaseProgress>>,
     ) -> Result<()> {
         // Post-process dependencies and detect target conflicts
+        lockfile_builder::apply_install_toggles(lockfile, self.core.manifest());
+        lockfile_builder::select_newest_duplicates(lockfile, self.core.manifest());
         self.add_version_to_dependencies(lockfile)?;
+        lockfile_builder::rename_conflicting_targets(lockfile, self.core.manifest());
         self.detect_target_conflicts(lockfile)?;
+        if let Some(project_dir) = self.core.manifest().manifest_dir.as_deref() {
+            lockfile_builder::validate_install_path_lengths(lockfile, project_dir)?;
+        }
 
         // Complete the resolution phase (includes all phases: version resolution,
         // transitive deps, conflict detection)
@@ -972,7 +993,8 @@ impl DependencyResolver {
                 let updated = temp_resolver.resolve_with_options(true, progress).await?;
 
                 // Phase 5: Merge unchanged and updated lockfiles
-                let merged = Self::merge_lockfiles(unchanged, updated);
+                let mut merged = Self::merge_lockfiles(unchanged, updated);
+                lockfile_builder::apply_install_toggles(&mut merged, self.core.manifest());
 
                 tracing::debug!(
                     "Incremental update complete: merged lockfile has {} total entries",
@@ -1017,6 +1039,25 @@ impl DependencyResolver {
         Ok(())
     }
 
+    /// Record why each version was resolved the way it was (`--explain`).
+    ///
+    /// Must be called before resolution; has no effect on already-resolved versions.
+    pub fn set_explain(&mut self, explain: bool) {
+        self.version_service.set_explain(explain);
+    }
+
+    /// Choose whether version constraints resolve to the highest or lowest matching tag.
+    ///
+    /// Must be called before resolution; has no effect on already-resolved versions.
+    pub fn set_resolution_strategy(&mut self, strategy: ResolutionStrategy) {
+        self.version_service.set_strategy(strategy);
+    }
+
+    /// Get the resolution explanations recorded since [`set_explain`](Self::set_explain).
+    pub fn explanations(&self) -> Vec<explain::VersionExplanation> {
+        self.version_service.explanations()
+    }
+
     /// Get current operation context if available.
     pub fn operation_context(&self) -> Option<&Arc<OperationContext>> {
         self.core.operation_context()
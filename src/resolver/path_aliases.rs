@@ -0,0 +1,76 @@
+//! Redirection of manifest paths through `[path-aliases]`.
+//!
+//! Before anything is fetched, each Git dependency whose path has an alias for
+//! its source is prepared at its version, and its path is replaced with the
+//! alias for the tag it resolved to. Later phases, the lockfile and the
+//! installed file name all use the actual path. See
+//! [`crate::manifest::path_alias`] for the manifest format.
+
+use anyhow::{Context, Result};
+
+use crate::core::ResourceType;
+use crate::manifest::ResourceDependency;
+
+use super::DependencyResolver;
+
+impl DependencyResolver {
+    /// Replaces aliased paths of manifest dependencies with their actual paths.
+    pub(super) async fn apply_path_aliases(
+        &self,
+        base_deps: &mut [(String, ResourceDependency, ResourceType)],
+    ) -> Result<()> {
+        let manifest = self.core.manifest();
+        if manifest.path_aliases.is_empty() {
+            return Ok(());
+        }
+
+        for (name, dep, _) in base_deps.iter_mut() {
+            let Some(source) = dep.get_source() else {
+                continue;
+            };
+            if dep.is_pattern()
+                || !manifest
+                    .path_aliases
+                    .get(source)
+                    .is_some_and(|aliases| aliases.contains_key(dep.get_path()))
+            {
+                continue;
+            }
+
+            let prepared = self
+                .version_service
+                .get_or_prepare_version(&self.core, source, dep.get_version())
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to prepare source '{}' @ '{}' for path alias of '{}'",
+                        source,
+                        dep.get_version().unwrap_or("HEAD"),
+                        name
+                    )
+                })?;
+
+            let Some(actual) =
+                manifest.aliased_path(source, dep.get_path(), prepared.resolved_version.as_deref())
+            else {
+                continue;
+            };
+            if actual == dep.get_path() {
+                continue;
+            }
+
+            tracing::debug!(
+                "Path alias for '{}' at {:?}: {} -> {}",
+                name,
+                prepared.resolved_version,
+                dep.get_path(),
+                actual
+            );
+            let actual = actual.to_string();
+            if let ResourceDependency::Detailed(detailed) = dep {
+                detailed.path = actual;
+            }
+        }
+        Ok(())
+    }
+}
@@ -329,6 +329,102 @@ pub fn extract_pattern_filename(base_path: &Path, matched_path: &Path) -> String
     extract_meaningful_path(&full_path)
 }
 
+/// Returns the fixed base of a glob pattern: its components before the first glob.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use agpm_cli::resolver::path_resolver::pattern_fixed_base;
+///
+/// assert_eq!(pattern_fixed_base("agents/**/*.md"), PathBuf::from("agents"));
+/// assert_eq!(pattern_fixed_base("./shared/agents/*.md"), PathBuf::from("shared/agents"));
+/// assert_eq!(pattern_fixed_base("*.md"), PathBuf::new());
+/// ```
+pub fn pattern_fixed_base(pattern: &str) -> PathBuf {
+    use std::path::Component;
+
+    let normalized = normalize_path_for_storage(pattern);
+    let components: Vec<Component> = Path::new(&normalized).components().collect();
+    components[..components.len().saturating_sub(1)]
+        .iter()
+        .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[', '{']))
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
+}
+
+/// Computes a matched file's path relative to the fixed base of its pattern.
+///
+/// Used for `preserve_structure` pattern dependencies. `matched_path` must be
+/// expressed relative to the same root as `pattern`.
+///
+/// # Errors
+///
+/// Returns an error if the match does not lie below the pattern base, or if the
+/// relative path contains anything other than plain components (such as `..`),
+/// which could place the installed file outside its install directory.
+pub fn pattern_relative_path(pattern: &str, matched_path: &Path) -> Result<PathBuf> {
+    use std::path::Component;
+
+    let base = pattern_fixed_base(pattern);
+    let matched: PathBuf = Path::new(&normalize_path_for_storage(matched_path))
+        .components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect();
+    let relative = matched.strip_prefix(&base).map_err(|_| {
+        anyhow::anyhow!(
+            "Matched path '{}' is not below the base '{}' of pattern '{}'",
+            matched_path.display(),
+            base.display(),
+            pattern
+        )
+    })?;
+
+    if relative.as_os_str().is_empty()
+        || !relative.components().all(|c| matches!(c, Component::Normal(_)))
+    {
+        anyhow::bail!(
+            "Matched path '{}' would be installed outside the install directory",
+            matched_path.display()
+        );
+    }
+    Ok(relative.to_path_buf())
+}
+
+/// Resolves the installation path for a `preserve_structure` pattern match.
+///
+/// The match is installed at its path below the pattern base, inside the
+/// artifact directory (and custom target, if any). Merge-target resources are
+/// unaffected.
+///
+/// # Errors
+///
+/// Returns an error if the resource type is not supported by the tool or the
+/// matched path cannot be placed safely below the install directory.
+pub fn resolve_preserved_install_path(
+    manifest: &Manifest,
+    dep: &ResourceDependency,
+    artifact_type: &str,
+    resource_type: ResourceType,
+    matched_path: &Path,
+) -> Result<String> {
+    if matches!(resource_type, ResourceType::Hook | ResourceType::McpServer) {
+        return Ok(resolve_merge_target_path(manifest, artifact_type, resource_type));
+    }
+
+    let artifact_path =
+        manifest.get_artifact_resource_path(artifact_type, resource_type).ok_or_else(|| {
+            create_unsupported_resource_error(artifact_type, resource_type, dep.get_path())
+        })?;
+    let relative = pattern_relative_path(&dep.expansion_pattern(resource_type), matched_path)?;
+
+    let base_target = match dep.get_target() {
+        Some(custom_target) => artifact_path.join(custom_target.trim_start_matches(['/', '\\'])),
+        None => artifact_path,
+    };
+    Ok(normalize_path_for_storage(base_target.join(relative)))
+}
+
 /// Extracts the meaningful path by removing redundant directory prefixes.
 ///
 /// This prevents paths like `.claude/agents/agents/file.md` by eliminating
@@ -772,4 +868,32 @@ mod tests {
         let filename = extract_pattern_filename(&base, matched);
         assert_eq!(filename, "agents/helper.md");
     }
+
+    #[test]
+    fn test_pattern_fixed_base() {
+        assert_eq!(pattern_fixed_base("agents/**/*.md"), PathBuf::from("agents"));
+        assert_eq!(pattern_fixed_base("agents/team/*.md"), PathBuf::from("agents/team"));
+        assert_eq!(pattern_fixed_base("../shared/agents/*.md"), PathBuf::from("../shared/agents"));
+        assert_eq!(pattern_fixed_base("agents/{a,b}/*.md"), PathBuf::from("agents"));
+        assert_eq!(pattern_fixed_base("**/*.md"), PathBuf::new());
+    }
+
+    #[test]
+    fn test_pattern_relative_path() {
+        let relative =
+            pattern_relative_path("agents/**/*.md", Path::new("agents/team/review/lint.md"))
+                .unwrap();
+        assert_eq!(relative, PathBuf::from("team/review/lint.md"));
+
+        let relative =
+            pattern_relative_path("./agents/*.md", Path::new("agents/helper.md")).unwrap();
+        assert_eq!(relative, PathBuf::from("helper.md"));
+
+        // Matches outside the base or escaping it are rejected
+        assert!(pattern_relative_path("agents/**/*.md", Path::new("snippets/a.md")).is_err());
+        assert!(
+            pattern_relative_path("agents/**/*.md", Path::new("agents/../../etc/passwd")).is_err()
+        );
+        assert!(pattern_relative_path("agents/*.md", Path::new("agents")).is_err());
+    }
 }
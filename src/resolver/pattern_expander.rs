@@ -1,13 +1,14 @@
 //! Pattern expansion for AGPM dependencies.
 //!
 //! This module handles expansion of glob patterns to concrete file paths,
-//! converting pattern dependencies (like "agents/*.md") into individual file
-//! dependencies. It supports both local and remote pattern resolution with
-//! proper path handling, dependency naming, and locked resource generation.
+//! converting pattern dependencies (like "agents/*.md", or the directory
+//! reference "agents/") into individual file dependencies. It supports both
+//! local and remote pattern resolution with proper path handling, dependency
+//! naming, and locked resource generation.
 
 use crate::git::GitRepo;
 use crate::manifest::{DetailedDependency, ResourceDependency};
-use crate::pattern::PatternResolver;
+use crate::pattern::{IgnoreRules, PatternResolver};
 use crate::resolver::version_resolver::PreparedSourceVersion;
 use crate::utils::normalize_path_for_storage;
 use anyhow::{Context, Result};
@@ -83,6 +84,11 @@ use tracing::debug;
 /// #     flatten: None,
 /// #     install: None,
 /// #     template_vars: None,
+/// #     sha256: None,
+/// #     on_conflict: None,
+/// #     preserve_structure: None,
+/// #     channel: None,
+/// #     priority: None,
 /// # }));
 /// let deps = expand_pattern_to_concrete_deps(
 ///     &pattern_dep,           // Pattern dependency
@@ -110,13 +116,22 @@ pub async fn expand_pattern_to_concrete_deps(
     manifest_dir: Option<&Path>,
     prepared_versions: Option<&DashMap<String, PreparedSourceVersion>>,
 ) -> Result<Vec<(String, ResourceDependency)>> {
-    let pattern = dep.get_path();
+    let pattern = dep.expansion_pattern(resource_type);
+    let ignore_rules = IgnoreRules::load(manifest_dir)?;
 
     if dep.is_local() {
-        expand_local_pattern(dep, pattern, resource_type, manifest_dir).await
+        expand_local_pattern(dep, &pattern, resource_type, manifest_dir, ignore_rules).await
     } else {
-        expand_remote_pattern(dep, pattern, resource_type, source_manager, cache, prepared_versions)
-            .await
+        expand_remote_pattern(
+            dep,
+            &pattern,
+            resource_type,
+            source_manager,
+            cache,
+            prepared_versions,
+            ignore_rules,
+        )
+        .await
     }
 }
 
@@ -126,6 +141,7 @@ async fn expand_local_pattern(
     pattern: &str,
     resource_type: crate::core::ResourceType,
     manifest_dir: Option<&Path>,
+    ignore_rules: IgnoreRules,
 ) -> Result<Vec<(String, ResourceDependency)>> {
     // For absolute patterns, use the parent directory as base and strip the pattern to just the filename part
     // For relative patterns, use manifest directory
@@ -169,6 +185,7 @@ async fn expand_local_pattern(
         ResourceDependency::Detailed(d) => (d.tool.clone(), d.target.clone(), d.flatten),
         _ => (None, None, None),
     };
+    let preserve_structure = dep.get_preserve_structure();
 
     let mut concrete_deps = Vec::new();
 
@@ -179,7 +196,10 @@ async fn expand_local_pattern(
             &search_pattern,
             None, // No strip prefix for local patterns
         )
-        .await?;
+        .await?
+        .into_iter()
+        .filter(|(skill_name, _)| !is_ignored_skill(&ignore_rules, skill_name))
+        .collect::<Vec<_>>();
 
         debug!("Local skill pattern '{}' matched {} directories", pattern, skill_matches.len());
 
@@ -200,13 +220,20 @@ async fn expand_local_pattern(
                 flatten,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             }));
 
             concrete_deps.push((skill_name, concrete_dep));
         }
     } else {
         // For file-based resources, use the pattern resolver
-        let pattern_resolver = PatternResolver::new();
+        let pattern_resolver = PatternResolver::new()
+            .with_ignore_rules(ignore_rules)
+            .direct_children_only(dep.is_directory_reference());
         let matches = pattern_resolver.resolve(&search_pattern, &base_path)?;
 
         debug!("Pattern '{}' matched {} files", pattern, matches.len());
@@ -228,6 +255,17 @@ async fn expand_local_pattern(
 
             let dep_name = generate_dependency_name(&concrete_path, &source_context);
 
+            let relative_to_pattern = if pattern_path.is_absolute() {
+                &absolute_path
+            } else {
+                &matched_path
+            };
+            let (target, flatten) = if preserve_structure {
+                preserved_placement(pattern, relative_to_pattern, target.as_deref())?
+            } else {
+                (target.clone(), flatten)
+            };
+
             // Create a concrete dependency for the matched file, inheriting tool, target, and flatten from parent
             let concrete_dep = ResourceDependency::Detailed(Box::new(DetailedDependency {
                 path: concrete_path,
@@ -237,13 +275,18 @@ async fn expand_local_pattern(
                 rev: None,
                 command: None,
                 args: None,
-                target: target.clone(),
+                target,
                 filename: None,
                 dependencies: None,
                 tool: tool.clone(),
                 flatten,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             }));
 
             concrete_deps.push((dep_name, concrete_dep));
@@ -261,6 +304,7 @@ async fn expand_remote_pattern(
     source_manager: &crate::source::SourceManager,
     cache: &crate::cache::Cache,
     prepared_versions: Option<&DashMap<String, PreparedSourceVersion>>,
+    ignore_rules: IgnoreRules,
 ) -> Result<Vec<(String, ResourceDependency)>> {
     let source_name = dep
         .get_source()
@@ -314,6 +358,7 @@ async fn expand_remote_pattern(
         ResourceDependency::Detailed(d) => (d.tool.clone(), d.target.clone(), d.flatten),
         _ => (None, None, None),
     };
+    let preserve_structure = dep.get_preserve_structure();
 
     let mut concrete_deps = Vec::new();
 
@@ -324,7 +369,10 @@ async fn expand_remote_pattern(
             pattern,
             Some(&worktree_path),
         )
-        .await?;
+        .await?
+        .into_iter()
+        .filter(|(skill_name, _)| !is_ignored_skill(&ignore_rules, skill_name))
+        .collect::<Vec<_>>();
 
         debug!(
             "Remote skill pattern '{}' in {} matched {} directories",
@@ -352,13 +400,20 @@ async fn expand_remote_pattern(
                 flatten,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             }));
 
             concrete_deps.push((skill_name, concrete_dep));
         }
     } else {
         // For file-based resources, use the pattern resolver
-        let pattern_resolver = PatternResolver::new();
+        let pattern_resolver = PatternResolver::new()
+            .with_ignore_rules(ignore_rules)
+            .direct_children_only(dep.is_directory_reference());
         let matches = pattern_resolver.resolve(pattern, &worktree_path)?;
 
         debug!("Remote pattern '{}' in {} matched {} files", pattern, source_name, matches.len());
@@ -370,6 +425,11 @@ async fn expand_remote_pattern(
                 crate::resolver::source_context::SourceContext::git(&worktree_path);
             let dep_name =
                 generate_dependency_name(&matched_path.to_string_lossy(), &source_context);
+            let (target, flatten) = if preserve_structure {
+                preserved_placement(pattern, &matched_path, target.as_deref())?
+            } else {
+                (target.clone(), flatten)
+            };
 
             // matched_path is already relative to worktree root (from PatternResolver)
             // Create a concrete dependency for the matched file, inheriting tool, target, and flatten from parent
@@ -383,13 +443,18 @@ async fn expand_remote_pattern(
                 rev: None,
                 command: None,
                 args: None,
-                target: target.clone(),
+                target,
                 filename: None,
                 dependencies: None,
                 tool: tool.clone(),
                 flatten,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             }));
 
             concrete_deps.push((dep_name, concrete_dep));
@@ -399,6 +464,30 @@ async fn expand_remote_pattern(
     Ok(concrete_deps)
 }
 
+/// Target and flatten setting that install a `preserve_structure` match at its
+/// path below the pattern base.
+///
+/// The match's parent directories (relative to the pattern base) are appended to
+/// the inherited target and the file itself is installed flattened into it.
+fn preserved_placement(
+    pattern: &str,
+    matched_path: &Path,
+    target: Option<&str>,
+) -> Result<(Option<String>, Option<bool>)> {
+    let relative = crate::resolver::path_resolver::pattern_relative_path(pattern, matched_path)?;
+    let mut placement = PathBuf::from(target.unwrap_or_default());
+    if let Some(parent) = relative.parent() {
+        placement.push(parent);
+    }
+    let placement = normalize_path_for_storage(&placement);
+    Ok(((!placement.is_empty()).then_some(placement), Some(true)))
+}
+
+/// Whether a matched skill directory (`skills/<name>`) is excluded by `.agpmignore`.
+fn is_ignored_skill(ignore_rules: &IgnoreRules, skill_name: &str) -> bool {
+    ignore_rules.is_ignored(&Path::new("skills").join(skill_name), true)
+}
+
 /// Generates a dependency name from a path using source context.
 /// Creates collision-resistant names by preserving directory structure relative to source.
 pub fn generate_dependency_name(
@@ -619,6 +708,11 @@ mod tests {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
 
         // Test pattern expansion with local source context
@@ -627,6 +721,7 @@ mod tests {
             "agents/*.md",
             crate::core::ResourceType::Agent,
             Some(manifest_dir),
+            IgnoreRules::new(),
         )
         .await
         .unwrap();
@@ -184,6 +184,11 @@ pub fn create_skill_dependency(
             flatten,
             install: None,
             template_vars: None,
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
     )
 }
@@ -273,6 +278,11 @@ mod tests {
             filename: None,
             dependencies: None,
             template_vars: None,
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
             tool: Some("claude-code".to_string()),
             flatten: Some(true),
             install: None,
@@ -280,6 +280,11 @@ mod tests {
             flatten: None,
             install: None,
             template_vars: None,
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
 
         let manifest_dir = Path::new("/project");
@@ -302,6 +307,11 @@ mod tests {
             flatten: None,
             install: None,
             template_vars: None,
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
 
         let repo_root = Path::new("/repo");
@@ -338,6 +348,7 @@ mod tests {
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
             is_private: false,
             approximate_token_count: None,
+            deprecated: false,
         };
 
         let manifest_dir = Path::new("/project");
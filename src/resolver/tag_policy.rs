@@ -0,0 +1,160 @@
+//! Enforcement of the `require_tag` manifest policy.
+//!
+//! With `require_tag = true` in `agpm.toml`, every Git dependency in the
+//! manifest must resolve to a tag: an exact tag name, a semver range such as
+//! `^1.0.0`, or `latest-by-date`. Branch tracking (`branch`, a branch name in
+//! `version`, or no version at all), commit pins (`rev`, a SHA in `version`)
+//! and raw refs (`ref:refs/pull/42/head`) are rejected before anything is
+//! resolved, so non-reproducible pins cannot reach a production lockfile.
+//!
+//! The check runs after sources are synced. A plain name in `version` is only
+//! accepted if the source actually has a tag with that name, so exact tags that
+//! do not look like versions (`stable`, `release-2024-06`) are allowed while
+//! branches with the same shape are not.
+
+use anyhow::Result;
+use std::collections::HashMap;
+
+use crate::core::ResourceType;
+use crate::git::GitRepo;
+use crate::git::raw_ref::raw_ref_spec;
+use crate::manifest::ResourceDependency;
+use crate::version::{LATEST_BY_DATE, VersionConstraint, parse_version_constraint};
+
+use super::DependencyResolver;
+use super::version_resolver::is_version_constraint;
+
+impl DependencyResolver {
+    /// Rejects manifest dependencies that do not resolve to a tag when `require_tag` is set.
+    ///
+    /// All violations are reported together.
+    pub(super) async fn enforce_tag_policy(
+        &self,
+        base_deps: &[(String, ResourceDependency, ResourceType)],
+    ) -> Result<()> {
+        let manifest = self.core.manifest();
+        if !manifest.require_tag {
+            return Ok(());
+        }
+
+        let mut tags_by_source: HashMap<&str, Vec<String>> = HashMap::new();
+        let mut violations = Vec::new();
+
+        for (name, dep, resource_type) in base_deps {
+            let Some(source) = dep.get_source() else {
+                continue;
+            };
+            let Some(url) = manifest.sources.get(source) else {
+                continue;
+            };
+            if crate::utils::is_local_path(url) || crate::source::archive::is_archive_url(url) {
+                continue;
+            }
+
+            if !tags_by_source.contains_key(source) {
+                let tags = match self.version_service.get_bare_repo_path(source) {
+                    Some(path) => GitRepo::new(path).list_tags().await.unwrap_or_default(),
+                    None => Vec::new(),
+                };
+                tags_by_source.insert(source, tags);
+            }
+
+            let tags = &tags_by_source[source];
+            if let Some(reason) =
+                tag_policy_violation(dep, |version| tags.iter().any(|t| t == version))
+            {
+                violations.push(format!("  {resource_type} '{name}' {reason}"));
+            }
+        }
+
+        if violations.is_empty() {
+            return Ok(());
+        }
+        violations.sort();
+        anyhow::bail!(
+            "require_tag is set in agpm.toml, but {} not pinned to a tag:\n{}\n\n\
+             Use an exact tag or a semver range such as \"^1.0.0\" in `version`.",
+            if violations.len() == 1 {
+                "this dependency is"
+            } else {
+                "these dependencies are"
+            },
+            violations.join("\n")
+        )
+    }
+}
+
+/// Why `dep` does not resolve to a tag, or `None` if it does.
+///
+/// `is_tag` tells whether a plain name in `version` is a tag of the
+/// dependency's source.
+pub(crate) fn tag_policy_violation(
+    dep: &ResourceDependency,
+    is_tag: impl Fn(&str) -> bool,
+) -> Option<String> {
+    let ResourceDependency::Detailed(detailed) = dep else {
+        return None;
+    };
+
+    if let Some(rev) = &detailed.rev {
+        return Some(format!("pins commit '{rev}'"));
+    }
+    if let Some(branch) = &detailed.branch {
+        return Some(format!("tracks branch '{branch}'"));
+    }
+    let Some(version) = &detailed.version else {
+        return Some("has no version and tracks the default branch".to_string());
+    };
+    if let Some(spec) = raw_ref_spec(version) {
+        return Some(format!("tracks ref '{spec}', which can be force-pushed"));
+    }
+    if version == LATEST_BY_DATE || is_version_constraint(version) || is_tag(version) {
+        return None;
+    }
+    Some(match parse_version_constraint(version) {
+        VersionConstraint::Commit(_) => format!("pins commit '{version}'"),
+        VersionConstraint::Branch(_) => format!("tracks branch '{version}'"),
+        VersionConstraint::Tag(_) => format!("uses '{version}', which is not a tag"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::DetailedDependency;
+
+    fn dep(version: Option<&str>, branch: Option<&str>, rev: Option<&str>) -> ResourceDependency {
+        let mut dep: DetailedDependency =
+            toml::from_str("source = \"community\"\npath = \"agents/reviewer.md\"\n").unwrap();
+        dep.version = version.map(str::to_string);
+        dep.branch = branch.map(str::to_string);
+        dep.rev = rev.map(str::to_string);
+        ResourceDependency::Detailed(Box::new(dep))
+    }
+
+    #[test]
+    fn test_tag_policy_violation() {
+        let tags = ["v1.0.0", "stable"];
+        let is_tag = |version: &str| tags.contains(&version);
+
+        assert_eq!(tag_policy_violation(&dep(Some("v1.0.0"), None, None), is_tag), None);
+        assert_eq!(tag_policy_violation(&dep(Some("^1.0.0"), None, None), is_tag), None);
+        assert_eq!(tag_policy_violation(&dep(Some("stable"), None, None), is_tag), None);
+        assert_eq!(tag_policy_violation(&dep(Some(LATEST_BY_DATE), None, None), is_tag), None);
+
+        let violation = |d| tag_policy_violation(&d, is_tag).unwrap();
+        assert_eq!(violation(dep(Some("main"), None, None)), "tracks branch 'main'");
+        assert_eq!(violation(dep(Some("v1.0.0"), Some("main"), None)), "tracks branch 'main'");
+        assert_eq!(violation(dep(None, None, Some("abc1234"))), "pins commit 'abc1234'");
+        assert_eq!(violation(dep(Some("abc1234"), None, None)), "pins commit 'abc1234'");
+        assert_eq!(violation(dep(Some("v9.0.0"), None, None)), "uses 'v9.0.0', which is not a tag");
+        assert!(violation(dep(None, None, None)).contains("default branch"));
+        assert_eq!(
+            violation(dep(Some("ref:refs/pull/42/head"), None, None)),
+            "tracks ref 'refs/pull/42/head', which can be force-pushed"
+        );
+
+        let local = ResourceDependency::Simple("agents/local.md".to_string());
+        assert_eq!(tag_policy_violation(&local, is_tag), None);
+    }
+}
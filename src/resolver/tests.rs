@@ -93,6 +93,11 @@ async fn pre_sync_sources() -> Result<(), Box<dyn std::error::Error>> {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
         ResourceType::Agent,
     );
@@ -303,6 +308,7 @@ async fn update_specific_dependency() -> Result<(), Box<dyn std::error::Error>>
         variant_inputs: VariantInputs::default(),
         is_private: false,
         approximate_token_count: None,
+        deprecated: false,
     });
 
     // Create the agent file
@@ -380,6 +386,11 @@ async fn version_constraint_resolution() -> Result<(), Box<dyn std::error::Error
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
         ResourceType::Agent,
     );
@@ -433,6 +444,11 @@ async fn multi_tool_support() -> Result<(), Box<dyn std::error::Error>> {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
         ResourceType::Agent,
     );
@@ -454,6 +470,11 @@ async fn multi_tool_support() -> Result<(), Box<dyn std::error::Error>> {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
         ResourceType::Agent,
     );
@@ -573,6 +594,11 @@ async fn template_variable_inheritance() -> Result<(), Box<dyn std::error::Error
             flatten: None,
             install: None,
             template_vars: Some(json!({"local_var": "local_value"})),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
         ResourceType::Agent,
     );
@@ -13,6 +13,11 @@
 //! 3. Process batch concurrently using join_all
 //! 4. Repeat until queue empty
 //!
+//! Frontmatter parsing is CPU-bound, so each worker hands it to the blocking
+//! pool, bounded by a semaphore sized to the CPU core count. Because batches
+//! complete in scheduling order, the final result is built from a deterministic
+//! topological order and key-sorted entries rather than discovery order.
+//!
 //! Concurrent safety is ensured via `Arc<DashMap>` for shared state.
 //! Each batch processes dependencies independently, with coordination
 //! happening through the shared DashMap-backed registries.
@@ -25,10 +30,12 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use anyhow::{Context, Result};
 use dashmap::DashMap;
 use futures::future::join_all;
-use tokio::sync::{Mutex, MutexGuard};
+use tokio::sync::{Mutex, MutexGuard, Semaphore};
 
 use crate::core::ResourceType;
+use crate::core::operation_context::OperationContext;
 use crate::lockfile::lockfile_dependency_ref::LockfileDependencyRef;
+use crate::manifest::DependencyMetadata;
 use crate::manifest::{DetailedDependency, ResourceDependency};
 use crate::metadata::MetadataExtractor;
 use crate::utils;
@@ -225,6 +232,8 @@ struct TransitiveSharedState<'a> {
     /// When a transitive dep has the same canonical path as a manifest dep, the
     /// manifest dep takes precedence (it may have customizations like filename).
     canonical_path_index: Arc<DashMap<CanonicalPathKey, String>>,
+    /// Bounds how many frontmatter scans run on the blocking pool at once.
+    scan_permits: Arc<Semaphore>,
 }
 
 /// Resolution context and services.
@@ -243,6 +252,21 @@ struct TransitiveResolutionContext<'a> {
 async fn process_transitive_dependency_spec(
     params: TransitiveDepProcessingParams<'_>,
 ) -> Result<(ResourceDependency, String)> {
+    // Transitive dependencies come from the parent's source, which the policy may not trust
+    let manifest = params.ctx.base.manifest;
+    if let Some(source) = params.parent_dep.get_source()
+        && !manifest.transitive.permits_source(source, &manifest.sources)
+    {
+        anyhow::bail!(
+            "'{}' declares a transitive dependency on '{}' from source '{source}', \
+            which [transitive] restrict_sources does not permit.\n\
+            Add '{source}' to [transitive] allowed_sources (or to [sources] if no allowlist is set) \
+            to trust its transitive dependencies.",
+            params.parent_name,
+            params.dep_spec.path,
+        );
+    }
+
     // Get the canonical path to the parent resource file
     let parent_file_path = ResourceFetchingService::get_canonical_path(
         params.core,
@@ -405,7 +429,7 @@ async fn create_transitive_dependency(
     parent_dep: &ResourceDependency,
     dep_resource_type: ResourceType,
     parent_resource_type: ResourceType,
-    _parent_name: &str,
+    parent_name: &str,
     dep_spec: &crate::manifest::DependencySpec,
     parent_file_path: &Path,
     trans_canonical: &Path,
@@ -437,6 +461,15 @@ async fn create_transitive_dependency(
         .await?
     };
 
+    // Pin the version if the manifest constrains this transitive dependency
+    if let Some(source) = dep.get_source() {
+        let source_context = crate::resolver::source_context::SourceContext::remote(source);
+        let name = generate_dependency_name(dep.get_path(), &source_context);
+        if let Some(pinned) = ctx.base.manifest.constraints.get(&name) {
+            apply_transitive_constraint(&mut dep, &name, pinned, dep_spec, parent_name)?;
+        }
+    }
+
     // Check for manifest override
     let normalized_path = normalize_lookup_path(dep.get_path());
     let source = dep.get_source().map(std::string::ToString::to_string);
@@ -466,6 +499,44 @@ async fn create_transitive_dependency(
     Ok(dep)
 }
 
+/// Replace a transitive dependency's version with its `[constraints]` pin.
+///
+/// Fails when the parent declares a version that the pin does not satisfy.
+fn apply_transitive_constraint(
+    dep: &mut ResourceDependency,
+    name: &str,
+    pinned: &str,
+    dep_spec: &crate::manifest::DependencySpec,
+    parent_name: &str,
+) -> Result<()> {
+    check_transitive_constraint(name, pinned, dep_spec.version.as_deref(), parent_name)?;
+    if let ResourceDependency::Detailed(detailed) = dep {
+        tracing::debug!("[TRANSITIVE] Pinning '{}' to '{}' via [constraints]", name, pinned);
+        detailed.version = Some(pinned.to_string());
+    }
+    Ok(())
+}
+
+/// Check that the version `parent_name` declares for `name`, if any, allows its `[constraints]` pin.
+pub(super) fn check_transitive_constraint(
+    name: &str,
+    pinned: &str,
+    declared: Option<&str>,
+    parent_name: &str,
+) -> Result<()> {
+    if let Some(declared) = declared {
+        let satisfied = VersionConstraint::parse(declared)
+            .and_then(|d| Ok(d.is_satisfied_by(&VersionConstraint::parse(pinned)?)))
+            .unwrap_or(false);
+        if !satisfied {
+            anyhow::bail!(
+                "'{parent_name}' requires {name} at '{declared}', which the [constraints] pin '{pinned}' does not satisfy"
+            );
+        }
+    }
+    Ok(())
+}
+
 /// Create a path-only transitive dependency (parent is path-only).
 fn create_path_only_transitive_dep(
     ctx: &TransitiveContext<'_>,
@@ -521,6 +592,11 @@ fn create_path_only_transitive_dep(
             ctx.base.manifest,
             parent_dep,
         )),
+        sha256: None,
+        on_conflict: None,
+        preserve_structure: None,
+        channel: None,
+        priority: None,
     })))
 }
 
@@ -548,6 +624,10 @@ async fn create_git_backed_transitive_dep(
     // Get repo-relative path by stripping the appropriate prefix
     let repo_relative = if utils::is_local_path(&source_url) {
         strip_local_source_prefix(&source_url, trans_canonical)?
+    } else if crate::source::archive::is_archive_url(&source_url) {
+        // Extracted archives have no .git marker, so strip the extraction root
+        let root = ctx.base.cache.get_worktree_path(&source_url, "")?;
+        strip_local_source_prefix(&root.to_string_lossy(), trans_canonical)?
     } else {
         // For remote Git sources, derive the worktree root from the parent file path
         strip_git_worktree_prefix_from_parent(parent_file_path, trans_canonical)?
@@ -583,6 +663,11 @@ async fn create_git_backed_transitive_dep(
             ctx.base.manifest,
             parent_dep,
         )),
+        sha256: None,
+        on_conflict: None,
+        preserve_structure: None,
+        channel: None,
+        priority: None,
     })))
 }
 
@@ -772,6 +857,10 @@ fn determine_transitive_tool(
 }
 
 /// Build the final ordered result from the dependency graph.
+///
+/// Batches finish in whatever order their tasks complete, so the entries are
+/// sorted by key first; together with the graph's deterministic topological
+/// order this makes the result independent of scheduling.
 fn build_ordered_result(
     all_deps: Arc<DashMap<DependencyKey, ResourceDependency>>,
     ordered_nodes: Vec<DependencyNode>,
@@ -785,6 +874,10 @@ fn build_ordered_result(
         all_deps.len()
     );
 
+    let mut entries: Vec<(DependencyKey, ResourceDependency)> =
+        all_deps.iter().map(|entry| (entry.key().clone(), entry.value().clone())).collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
     for node in ordered_nodes {
         tracing::debug!(
             "Processing ordered node: {}/{} (source: {:?})",
@@ -794,23 +887,20 @@ fn build_ordered_result(
         );
 
         // Find matching dependency
-        for entry in all_deps.iter() {
-            let (key, dep) = (entry.key(), entry.value());
-            if key.0 == node.resource_type && key.1 == node.name && key.2 == node.source {
-                tracing::debug!(
-                    "  -> Found match in all_deps, adding to result with type {:?}",
-                    node.resource_type
-                );
-                result.push((node.name.clone(), dep.clone(), node.resource_type));
-                added_keys.insert(key.clone());
-                break;
-            }
+        if let Some((key, dep)) = entries.iter().find(|(key, _)| {
+            key.0 == node.resource_type && key.1 == node.name && key.2 == node.source
+        }) {
+            tracing::debug!(
+                "  -> Found match in all_deps, adding to result with type {:?}",
+                node.resource_type
+            );
+            result.push((node.name.clone(), dep.clone(), node.resource_type));
+            added_keys.insert(key.clone());
         }
     }
 
     // Add remaining dependencies that weren't in the graph (no transitive deps)
-    for entry in all_deps.iter() {
-        let (key, dep) = (entry.key(), entry.value());
+    for (key, dep) in &entries {
         if !added_keys.contains(key) && !dep.is_pattern() {
             tracing::debug!(
                 "Adding non-graph dependency: {}/{} (source: {:?}) with type {:?}",
@@ -833,6 +923,33 @@ pub fn group_key(source: &str, version: &str) -> String {
     format!("{source}::{version}")
 }
 
+/// Parse a resource's frontmatter on the blocking pool.
+///
+/// Template rendering and YAML/JSON parsing are CPU-bound, so running them inline
+/// would serialize a whole batch on the tasks driving it. `permits` caps how many
+/// scans occupy blocking threads at once.
+async fn scan_metadata(
+    permits: &Semaphore,
+    path: PathBuf,
+    content: String,
+    variant_inputs: serde_json::Value,
+    context: Option<Arc<OperationContext>>,
+    field: String,
+) -> Result<DependencyMetadata> {
+    let _permit = permits.acquire().await.context("Frontmatter scan pool was closed")?;
+    tokio::task::spawn_blocking(move || {
+        MetadataExtractor::extract_with_field(
+            &path,
+            &content,
+            Some(&variant_inputs),
+            context.as_deref(),
+            &field,
+        )
+    })
+    .await
+    .context("Frontmatter scan task panicked")?
+}
+
 /// Process a single transitive dependency from the queue.
 ///
 /// This function extracts the core loop body logic into a standalone async function
@@ -1103,7 +1220,6 @@ async fn process_single_transitive_dependency<'a>(
         ctx.resolution.ctx_base.manifest,
         &ctx.input.dep,
     );
-    let variant_inputs = Some(&variant_inputs_value);
 
     // Extract metadata from the resource with complete variant_inputs
     // For skills, use SKILL.md path so extractor recognizes it as markdown
@@ -1112,12 +1228,15 @@ async fn process_single_transitive_dependency<'a>(
     } else {
         PathBuf::from(ctx.input.dep.get_path())
     };
-    let metadata = MetadataExtractor::extract(
-        &path,
-        &content,
-        variant_inputs,
-        ctx.resolution.ctx_base.operation_context.map(|arc| arc.as_ref()),
-    )?;
+    let metadata = scan_metadata(
+        &ctx.shared.scan_permits,
+        path,
+        content,
+        variant_inputs_value,
+        ctx.resolution.ctx_base.operation_context.cloned(),
+        ctx.resolution.ctx_base.manifest.transitive.dependency_field().to_string(),
+    )
+    .await?;
 
     tracing::debug!(
         "[DEBUG] Extracted metadata for '{}': has_deps={}",
@@ -1125,8 +1244,19 @@ async fn process_single_transitive_dependency<'a>(
         metadata.get_dependencies().is_some()
     );
 
+    // Resource types disabled in [transitive] are leaves: ignore their declared dependencies
+    let follow_dependencies =
+        ctx.resolution.ctx_base.manifest.transitive.is_enabled(ctx.input.resource_type);
+    if !follow_dependencies && metadata.has_dependencies() {
+        tracing::debug!(
+            "Ignoring dependencies of {} '{}' disabled in [transitive]",
+            ctx.input.resource_type,
+            ctx.input.name
+        );
+    }
+
     // Process transitive dependencies if present
-    if let Some(deps_map) = metadata.get_dependencies() {
+    if let Some(deps_map) = metadata.get_dependencies().filter(|_| follow_dependencies) {
         tracing::debug!(
             "[DEBUG] Found {} dependency type(s) for '{}': {:?}",
             deps_map.len(),
@@ -1142,7 +1272,7 @@ async fn process_single_transitive_dependency<'a>(
         // CRITICAL: Collect graph edges to batch-insert AFTER the loop.
         // Acquiring the graph mutex inside the loop creates high contention
         // and potential deadlocks with DashMap operations.
-        let mut graph_edges: Vec<(DependencyNode, DependencyNode)> = Vec::new();
+        let mut graph_edges: Vec<(DependencyNode, DependencyNode, Option<String>)> = Vec::new();
 
         // Track declared dependencies for validation
         let declared_count = metadata.dependency_count();
@@ -1266,7 +1396,8 @@ async fn process_single_transitive_dependency<'a>(
                     &graph_dep_name,
                     trans_source.clone(),
                 );
-                graph_edges.push((from_node, to_node));
+                let to_version = trans_dep.get_version().map(str::to_string);
+                graph_edges.push((from_node, to_node, to_version));
 
                 // Track in dependency map
                 let from_key = (
@@ -1353,7 +1484,14 @@ async fn process_single_transitive_dependency<'a>(
         if !graph_edges.is_empty() {
             let mut graph =
                 acquire_mutex_with_timeout(&ctx.shared.graph, "dependency_graph").await?;
-            for (from_node, to_node) in graph_edges {
+            for (from_node, to_node, to_version) in graph_edges {
+                // Versions are only kept for reporting, e.g. in cycle errors
+                if let Some(version) = ctx.input.dep.get_version() {
+                    graph.set_version(&from_node, version);
+                }
+                if let Some(version) = to_version {
+                    graph.set_version(&to_node, version);
+                }
                 graph.add_dependency(from_node, to_node);
             }
         }
@@ -1490,6 +1628,7 @@ pub async fn resolve_with_services(
     // Calculate concurrency based on CPU cores
     let cores = std::thread::available_parallelism().map(std::num::NonZero::get).unwrap_or(4);
     let max_concurrent = std::cmp::max(10, cores * 2);
+    let scan_permits = Arc::new(Semaphore::new(cores));
 
     // Extract ctx references for parallel access (conflict_detector needs &mut, so we keep it outside)
     let ctx_dependency_map = ctx.dependency_map;
@@ -1534,6 +1673,7 @@ pub async fn resolve_with_services(
                 let custom_names_clone = ctx_custom_names;
                 let manifest_overrides_clone = ctx_manifest_overrides;
                 let canonical_path_index_clone = Arc::clone(&canonical_path_index);
+                let scan_permits_clone = Arc::clone(&scan_permits);
 
                 async move {
                     let resource_type = resource_type
@@ -1559,6 +1699,7 @@ pub async fn resolve_with_services(
                             custom_names: custom_names_clone,
                             prepared_versions: &prepared_versions_clone,
                             canonical_path_index: canonical_path_index_clone,
+                            scan_permits: scan_permits_clone,
                         },
                         resolution: TransitiveResolutionContext {
                             ctx_base,
@@ -1632,6 +1773,11 @@ fn create_skill_md_dependency(dep: &ResourceDependency) -> ResourceDependency {
                 flatten: detailed.flatten,
                 install: detailed.install,
                 template_vars: detailed.template_vars.clone(),
+                sha256: detailed.sha256.clone(),
+                on_conflict: detailed.on_conflict,
+                preserve_structure: detailed.preserve_structure,
+                channel: detailed.channel.clone(),
+                priority: detailed.priority,
             }))
         }
     }
@@ -50,6 +50,20 @@ impl ResolutionMode {
     }
 }
 
+/// Which tag a version constraint resolves to when several satisfy it.
+///
+/// `Lowest` is meant for testing that constraints are not under-specified,
+/// like Cargo's `-Z minimal-versions`. Prefix and prerelease rules apply to
+/// both strategies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ResolutionStrategy {
+    /// Pick the highest matching version.
+    #[default]
+    Highest,
+    /// Pick the lowest matching version.
+    Lowest,
+}
+
 // ============================================================================
 // Core Resolution Context
 // ============================================================================
@@ -689,6 +703,11 @@ mod tests {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         }));
         let resource_id = build_resource_id(&dep);
         assert!(resource_id.contains("agents/helper.md"));
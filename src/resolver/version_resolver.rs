@@ -20,15 +20,20 @@
 use anyhow::{Context, Result};
 use dashmap::DashMap;
 use futures::stream::{self, StreamExt};
+use regex::Regex;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use super::types::ResolutionMode;
+use super::explain::{CandidateStatus, VersionExplanation, explain_tag_selection};
+use super::types::{ResolutionMode, ResolutionStrategy};
 use crate::cache::Cache;
 use crate::git::GitRepo;
+use crate::git::raw_ref::{is_raw_ref, raw_ref_spec};
 use crate::manifest::ResourceDependency;
 use crate::source::SourceManager;
+use crate::version::tag_pattern::TagPattern;
+use crate::version::{LATEST_BY_DATE, TagPreference};
 
 /// Version resolution entry tracking source and version to SHA mapping
 #[derive(Debug, Clone)]
@@ -135,6 +140,22 @@ pub struct VersionResolver {
     bare_repos: Arc<DashMap<String, PathBuf>>,
     /// Maximum concurrency for parallel version resolution
     max_concurrency: usize,
+    /// Whether to record resolution explanations (`--explain`)
+    explain: bool,
+    /// Recorded explanations, keyed by (source, version)
+    explanations: Arc<DashMap<(String, String), VersionExplanation>>,
+    /// Whether constraints pick the highest or lowest matching tag
+    strategy: ResolutionStrategy,
+    /// Which of several tags naming one release is resolved and recorded
+    tag_preference: TagPreference,
+    /// Whether ranges only consider tags with a `<tag>-validated` marker tag
+    require_validated: bool,
+    /// Release channel patterns over tag messages, keyed by (source, version)
+    channels: Arc<DashMap<(String, String), Regex>>,
+    /// Tag formats of sources that don't use `v1.2.3`-style tags, keyed by source
+    tag_patterns: Arc<DashMap<String, TagPattern>>,
+    /// Tags of each source, listed once per command, keyed by source
+    source_tags: Arc<DashMap<String, Vec<String>>>,
 }
 
 impl VersionResolver {
@@ -151,6 +172,14 @@ impl VersionResolver {
             resolved: Arc::new(DashMap::new()),
             bare_repos: Arc::new(DashMap::new()),
             max_concurrency: default_concurrency,
+            explain: false,
+            explanations: Arc::new(DashMap::new()),
+            strategy: ResolutionStrategy::default(),
+            tag_preference: TagPreference::default(),
+            require_validated: false,
+            channels: Arc::new(DashMap::new()),
+            tag_patterns: Arc::new(DashMap::new()),
+            source_tags: Arc::new(DashMap::new()),
         }
     }
 
@@ -162,9 +191,49 @@ impl VersionResolver {
             resolved: Arc::new(DashMap::new()),
             bare_repos: Arc::new(DashMap::new()),
             max_concurrency,
+            explain: false,
+            explanations: Arc::new(DashMap::new()),
+            strategy: ResolutionStrategy::default(),
+            tag_preference: TagPreference::default(),
+            require_validated: false,
+            channels: Arc::new(DashMap::new()),
+            tag_patterns: Arc::new(DashMap::new()),
+            source_tags: Arc::new(DashMap::new()),
         }
     }
 
+    /// Enables or disables recording of resolution explanations.
+    ///
+    /// When enabled, `resolve_all()` records the candidate tags considered for
+    /// each version and why the final ref was chosen. Disabled by default so
+    /// normal resolution does no extra work.
+    pub fn set_explain(&mut self, explain: bool) {
+        self.explain = explain;
+    }
+
+    /// Sets whether version constraints resolve to the highest or lowest matching tag.
+    pub fn set_strategy(&mut self, strategy: ResolutionStrategy) {
+        self.strategy = strategy;
+    }
+
+    /// Sets which tag is resolved and recorded when several tags name one release.
+    pub fn set_tag_preference(&mut self, preference: TagPreference) {
+        self.tag_preference = preference;
+    }
+
+    /// Sets whether version ranges only consider tags with a `<tag>-validated` marker.
+    pub fn set_require_validated(&mut self, require_validated: bool) {
+        self.require_validated = require_validated;
+    }
+
+    /// Returns recorded resolution explanations, sorted by source and version.
+    pub fn explanations(&self) -> Vec<VersionExplanation> {
+        let mut explanations: Vec<VersionExplanation> =
+            self.explanations.iter().map(|entry| entry.value().clone()).collect();
+        explanations.sort_by(|a, b| (&a.source, &a.requested).cmp(&(&b.source, &b.requested)));
+        explanations
+    }
+
     /// Adds a version to be resolved
     ///
     /// Multiple calls with the same (source, version) pair will be deduplicated.
@@ -196,6 +265,28 @@ impl VersionResolver {
         });
     }
 
+    /// Matches version constraints for `source` against tags of the form `pattern`.
+    pub fn set_tag_pattern(&self, source: &str, pattern: TagPattern) {
+        self.tag_patterns.insert(source.to_string(), pattern);
+    }
+
+    /// Restricts a version to tags whose annotation message matches `channel`.
+    ///
+    /// Only version ranges and `latest-by-date` can be restricted; `resolve_all()`
+    /// rejects a channel on any other version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `channel` is not a valid regex.
+    pub fn set_channel(&self, source: &str, version: Option<&str>, channel: &str) -> Result<()> {
+        let pattern = Regex::new(channel).with_context(|| {
+            format!("Invalid channel pattern '{channel}' for source '{source}'")
+        })?;
+        let version_key = version.unwrap_or("HEAD").to_string();
+        self.channels.insert((source.to_string(), version_key), pattern);
+        Ok(())
+    }
+
     /// Resolves all collected versions to their commit SHAs using cached repositories.
     ///
     /// This is the second phase of AGPM's two-phase resolution architecture. Call after `pre_sync_sources()`.
@@ -254,6 +345,24 @@ impl VersionResolver {
 
         // Process each source with batch resolution
         for (source, versions) in by_source {
+            if versions.iter().any(|(_, e)| crate::source::archive::is_archive_url(&e.url)) {
+                for (version_str, entry) in &versions {
+                    self.resolve_archive_version(&source, version_str, entry).await?;
+                    if let Some(ref pm) = progress {
+                        let completed =
+                            completed_counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+                        pm.mark_item_complete(
+                            &entry.unique_key(),
+                            Some(&entry.format_display()),
+                            completed,
+                            total_versions,
+                            "Resolving dependencies",
+                        );
+                    }
+                }
+                continue;
+            }
+
             // Repository must have been pre-synced
             let repo_path = self
                 .bare_repos
@@ -265,13 +374,16 @@ impl VersionResolver {
 
             let repo = GitRepo::new(&repo_path);
 
-            // Pre-fetch tags once per source (cached in GitRepo)
+            // Tags are listed once per source per command, since transitive
+            // dependencies call resolve_all again for every new version.
             // Always fetch tags - they're needed for both constraint resolution and ref type detection
             let tags_cache = if versions.iter().any(|(_, e)| !crate::utils::is_local_path(&e.url)) {
-                repo.list_tags().await.ok()
+                self.list_source_tags(&source, &repo).await.ok()
             } else {
                 None
             };
+            // Tag messages are only read when a version of this source has a channel
+            let mut tag_messages: Option<HashMap<String, String>> = None;
 
             // === PHASE 1: Resolve version constraints and determine refs ===
             // This phase processes each version entry to determine the final ref to resolve,
@@ -291,22 +403,169 @@ impl VersionResolver {
 
                 if is_local {
                     // Local directories don't need SHA resolution
+                    if self.explain {
+                        self.record_explanation(VersionExplanation::new(
+                            &source,
+                            version_str,
+                            "local path source, no version resolution",
+                        ));
+                    }
                     version_to_ref.push((version_str.clone(), entry.clone(), "local".to_string()));
                     continue;
                 }
 
+                // Restrict candidate tags to the release channel, if any
+                let channel =
+                    self.channels.get(&(source.clone(), version_str.clone())).map(|c| c.clone());
+                let in_channel = if let Some(pattern) = &channel {
+                    if !entry
+                        .version
+                        .as_deref()
+                        .is_some_and(|v| v == LATEST_BY_DATE || is_version_constraint(v))
+                    {
+                        anyhow::bail!(
+                            "`channel` requires a version range or '{LATEST_BY_DATE}', but source \
+                             '{source}' is requested at '{version_str}'"
+                        );
+                    }
+                    if tag_messages.is_none() {
+                        tag_messages = Some(repo.list_tag_messages().await?);
+                    }
+                    let messages = tag_messages.as_ref().expect("tag messages were just read");
+                    Some(
+                        messages
+                            .iter()
+                            .filter(|(_, message)| pattern.is_match(message))
+                            .map(|(tag, _)| tag.clone())
+                            .collect::<std::collections::HashSet<_>>(),
+                    )
+                } else {
+                    None
+                };
+                // With require_validated, ranges only consider tags upstream marked as validated
+                let validated = if self.require_validated
+                    && entry
+                        .version
+                        .as_deref()
+                        .is_some_and(|v| v == LATEST_BY_DATE || is_version_constraint(v))
+                {
+                    Some(validated_tags(tags_cache.as_deref().unwrap_or_default()))
+                } else {
+                    None
+                };
+                let mut channel_note = channel
+                    .as_ref()
+                    .map(|pattern| format!(" in channel '{pattern}'"))
+                    .unwrap_or_default();
+                if validated.is_some() {
+                    channel_note.push_str(&format!(" with a '{VALIDATED_SUFFIX}' marker tag"));
+                }
+                let in_channel = match (in_channel, validated) {
+                    (Some(in_channel), Some(validated)) => {
+                        Some(in_channel.intersection(&validated).cloned().collect())
+                    }
+                    (in_channel, validated) => in_channel.or(validated),
+                };
+
                 // Determine the resolved ref for this version
                 let resolved_ref = if let Some(ref version) = entry.version {
-                    if is_version_constraint(version) {
+                    if version == LATEST_BY_DATE {
+                        let (tag, _) = repo
+                            .list_tags_by_date()
+                            .await?
+                            .into_iter()
+                            .find(|(tag, _)| in_channel.as_ref().is_none_or(|t| t.contains(tag)))
+                            .with_context(|| {
+                                format!(
+                                    "Source '{source}' has no tags{channel_note} to resolve '{version}'"
+                                )
+                            })?;
+                        if self.explain {
+                            self.record_explanation(VersionExplanation::new(
+                                &source,
+                                version_str,
+                                format!("'{tag}' is the most recently created tag{channel_note}"),
+                            ));
+                        }
+                        tag
+                    } else if is_version_constraint(version) {
                         // Resolve version constraint to best matching tag
                         let tags = tags_cache.as_ref().ok_or_else(|| {
                             anyhow::anyhow!(
                                 "Tags should have been pre-fetched for constraint '{version}'"
                             )
                         })?;
+                        let channel_tags;
+                        let tags = match &in_channel {
+                            Some(allowed) => {
+                                channel_tags = tags
+                                    .iter()
+                                    .filter(|tag| allowed.contains(*tag))
+                                    .cloned()
+                                    .collect::<Vec<_>>();
+                                if channel_tags.is_empty() {
+                                    anyhow::bail!(
+                                        "Source '{source}' has no tags{channel_note} to resolve '{version}'"
+                                    );
+                                }
+                                &channel_tags
+                            }
+                            None => tags,
+                        };
+
+                        // With a tag pattern, constraints see the version part of
+                        // each fitting tag, which is mapped back to the tag afterwards
+                        let pattern_tags =
+                            self.tag_patterns.get(&source).map(|p| p.tags_by_version(tags));
+                        let pattern_versions;
+                        let tags = match &pattern_tags {
+                            Some(by_version) => {
+                                pattern_versions = by_version.keys().cloned().collect::<Vec<_>>();
+                                &pattern_versions
+                            }
+                            None => tags,
+                        };
+                        let to_tag = |candidate: String| match &pattern_tags {
+                            Some(by_version) => {
+                                by_version.get(&candidate).cloned().unwrap_or(candidate)
+                            }
+                            None => candidate,
+                        };
+
+                        let best = find_preferred_tag(
+                            version,
+                            tags.clone(),
+                            self.strategy,
+                            self.tag_preference,
+                        );
+                        let best_candidate = best.as_ref().ok().cloned();
+                        let best = best.map(to_tag);
+                        if self.explain {
+                            let extreme = match self.strategy {
+                                ResolutionStrategy::Highest => "highest",
+                                ResolutionStrategy::Lowest => "lowest",
+                            };
+                            let mut explanation = VersionExplanation::new(
+                                &source,
+                                version_str,
+                                match &best {
+                                    Ok(tag) => {
+                                        format!(
+                                            "'{tag}' is the {extreme} tag satisfying '{version}'{channel_note}"
+                                        )
+                                    }
+                                    Err(e) => e.to_string(),
+                                },
+                            );
+                            explanation.candidates =
+                                explain_tag_selection(version, tags, best_candidate.as_deref());
+                            for candidate in &mut explanation.candidates {
+                                candidate.tag = to_tag(std::mem::take(&mut candidate.tag));
+                            }
+                            self.record_explanation(explanation);
+                        }
 
-                        find_best_matching_tag(version, tags.clone())
-                            .with_context(|| format!("Failed to resolve version constraint '{version}' for source '{source}'"))?
+                        best.with_context(|| format!("Failed to resolve version constraint '{version}'{channel_note} for source '{source}'"))?
                     } else {
                         // Not a constraint, use as-is but determine if it's tag or branch
                         version.clone()
@@ -316,13 +575,53 @@ impl VersionResolver {
                     repo.get_default_branch().await.unwrap_or_else(|_| "main".to_string())
                 };
 
-                // Determine what ref to actually resolve
-                let ref_result = determine_ref_to_resolve(&resolved_ref, tags_cache.as_ref());
+                // Determine what ref to actually resolve. Raw refs are outside the
+                // fetched refspec, so they are fetched and resolved here.
+                let ref_result = match raw_ref_spec(&resolved_ref) {
+                    Some(spec) => RefResolutionResult::DirectSha(
+                        repo.fetch_raw_ref(spec).await.with_context(|| {
+                            format!("Failed to resolve '{resolved_ref}' for source '{source}'")
+                        })?,
+                    ),
+                    None => determine_ref_to_resolve(&resolved_ref, tags_cache.as_ref()),
+                };
+
+                if self.explain
+                    && !entry
+                        .version
+                        .as_deref()
+                        .is_some_and(|v| v == LATEST_BY_DATE || is_version_constraint(v))
+                {
+                    let reason = match (&entry.version, &ref_result) {
+                        (None, _) => {
+                            format!("no version specified, using default branch '{resolved_ref}'")
+                        }
+                        (Some(v), _) if is_raw_ref(v) => {
+                            format!(
+                                "'{v}' is a raw ref, fetched from the remote and not reproducible"
+                            )
+                        }
+                        (Some(_), RefResolutionResult::DirectSha(_)) => {
+                            "pinned to an exact commit SHA".to_string()
+                        }
+                        (Some(v), RefResolutionResult::DirectRef(_)) if v != "HEAD" => {
+                            format!("'{v}' is an exact tag or qualified ref")
+                        }
+                        (Some(v), _) => format!("'{v}' is not a tag, resolving as a branch"),
+                    };
+                    self.record_explanation(VersionExplanation::new(&source, version_str, reason));
+                }
 
                 match ref_result {
                     RefResolutionResult::DirectSha(sha) => {
                         // Already a SHA, store directly
                         let key = (source.clone(), version_str.clone());
+                        if self.explain {
+                            if let Some(mut explanation) = self.explanations.get_mut(&key) {
+                                explanation.resolved_ref = Some(resolved_ref.clone());
+                                explanation.resolved_sha = Some(sha.clone());
+                            }
+                        }
                         self.resolved.insert(
                             key,
                             ResolvedVersion {
@@ -398,7 +697,9 @@ impl VersionResolver {
             };
 
             // === PHASE 4: Store results ===
-            for (version_str, entry, ref_name) in version_to_ref {
+            // Tags of every commit, read once when a resolved ref is a non-semver tag alias
+            let mut tag_commits: Option<HashMap<String, String>> = None;
+            for (version_str, entry, mut ref_name) in version_to_ref {
                 if ref_name == "local" {
                     // Local sources don't get stored in resolved map
                     if let Some(ref pm) = progress {
@@ -418,6 +719,28 @@ impl VersionResolver {
                 let sha = sha_results.get(&ref_name).and_then(|v| v.clone());
 
                 if let Some(sha_value) = sha {
+                    // Record an alias such as `stable` as the semver tag on the same commit
+                    if self.tag_preference == TagPreference::Semver
+                        && tags_cache.as_ref().is_some_and(|tags| tags.contains(&ref_name))
+                        && parse_tags_to_versions(vec![ref_name.clone()]).is_empty()
+                    {
+                        if tag_commits.is_none() {
+                            tag_commits = Some(repo.list_tag_commits().await?);
+                        }
+                        let commits = tag_commits.as_ref().expect("tag commits were just read");
+                        if let Some(canonical) =
+                            self.tag_preference.canonical_tag(&ref_name, commits)
+                        {
+                            tracing::debug!(
+                                "RESOLVE: source='{}' tag '{}' recorded as '{}'",
+                                source,
+                                ref_name,
+                                canonical
+                            );
+                            ref_name = canonical.to_string();
+                        }
+                    }
+
                     tracing::debug!(
                         "RESOLVE: source='{}' version='{}' ref='{}' -> SHA={}",
                         source,
@@ -426,6 +749,15 @@ impl VersionResolver {
                         &sha_value[..8.min(sha_value.len())]
                     );
 
+                    if self.explain {
+                        if let Some(mut explanation) =
+                            self.explanations.get_mut(&(source.clone(), version_str.clone()))
+                        {
+                            explanation.resolved_ref = Some(ref_name.clone());
+                            explanation.resolved_sha = Some(sha_value.clone());
+                        }
+                    }
+
                     let key = (source.clone(), version_str);
                     self.resolved.insert(
                         key,
@@ -461,6 +793,59 @@ impl VersionResolver {
         Ok(())
     }
 
+    /// Resolves a version of an archive source to the version pinned by its URL.
+    ///
+    /// The archive id (a prefix of its SHA-256) stands in for the commit SHA.
+    async fn resolve_archive_version(
+        &self,
+        source: &str,
+        version_str: &str,
+        entry: &VersionEntry,
+    ) -> Result<()> {
+        let archive = crate::source::archive::ArchiveUrl::parse(&entry.url)?;
+        archive.check_version(entry.version.as_deref())?;
+        let (_, id) = self.cache.refresh_archive(&entry.url).await?;
+
+        if self.explain {
+            let mut explanation = VersionExplanation::new(
+                source,
+                version_str,
+                format!("archive source pinned to '{}' by its URL", archive.version),
+            );
+            explanation.resolved_ref = Some(archive.version.clone());
+            explanation.resolved_sha = Some(id.clone());
+            self.record_explanation(explanation);
+        }
+
+        self.resolved.insert(
+            (source.to_string(), version_str.to_string()),
+            ResolvedVersion {
+                sha: id,
+                resolved_ref: archive.version,
+            },
+        );
+        Ok(())
+    }
+
+    /// Stores an explanation, replacing any earlier one for the same version.
+    fn record_explanation(&self, explanation: VersionExplanation) {
+        tracing::debug!(
+            target: "explain",
+            "{}@{}: {} ({} candidates, {} selected)",
+            explanation.source,
+            explanation.requested,
+            explanation.reason,
+            explanation.candidates.len(),
+            explanation
+                .candidates
+                .iter()
+                .filter(|c| c.status == CandidateStatus::Selected)
+                .count()
+        );
+        let key = (explanation.source.clone(), explanation.requested.clone());
+        self.explanations.insert(key, explanation);
+    }
+
     /// Resolves a single version to SHA without affecting the batch
     ///
     /// This is useful for incremental resolution or testing.
@@ -523,6 +908,14 @@ impl VersionResolver {
         self.resolved.get(&key).map(|rv| rv.sha.clone())
     }
 
+    /// Gets the resolved SHA and reference for a given source and version
+    ///
+    /// Returns None if the version hasn't been resolved yet.
+    pub fn get_resolved(&self, source: &str, version: &str) -> Option<ResolvedVersion> {
+        let key = (source.to_string(), version.to_string());
+        self.resolved.get(&key).map(|rv| rv.clone())
+    }
+
     /// Gets all resolved SHAs as a `HashMap`
     ///
     /// Useful for bulk operations or debugging.
@@ -593,10 +986,19 @@ impl VersionResolver {
     ) -> Result<()> {
         // Group entries by source to get unique sources
         let mut unique_sources: HashMap<String, String> = HashMap::new();
+        // Exact refs per source that a read-only cache base must contain to be used
+        let mut source_refs: HashMap<String, Vec<String>> = HashMap::new();
 
         for entry_ref in self.entries.iter() {
             let entry = entry_ref.value();
             unique_sources.insert(entry.source.clone(), entry.url.clone());
+            if let Some(version) = entry.version.as_deref()
+                && version != "HEAD"
+                && version != "latest"
+                && !is_version_constraint(version)
+            {
+                source_refs.entry(entry.source.clone()).or_default().push(version.to_string());
+            }
         }
 
         let total = unique_sources.len();
@@ -625,6 +1027,7 @@ impl VersionResolver {
         let results: Vec<Result<(String, PathBuf), anyhow::Error>> = stream::iter(unique_sources)
             .map(|(source, url)| {
                 let cache = self.cache.clone();
+                let refs = source_refs.remove(&source).unwrap_or_default();
                 let progress_clone = progress.clone();
                 let completed_ref = &completed;
                 let total_count = total;
@@ -637,10 +1040,20 @@ impl VersionResolver {
                     }
 
                     // Clone or update the repository (this does the actual Git operations)
-                    let repo_path =
-                        cache.get_or_clone_source(&source, &url, None).await.with_context(
-                            || format!("Failed to sync repository for source '{source}'"),
-                        )?;
+                    let repo_path = if crate::source::archive::is_archive_url(&url) {
+                        // Keep the cause (e.g. a checksum mismatch) in the reported message
+                        cache.get_or_clone_source(&source, &url, None).await.map_err(|e| {
+                            anyhow::anyhow!("Failed to fetch archive for source '{source}': {e:#}")
+                        })?
+                    } else {
+                        let refs: Vec<&str> = refs.iter().map(String::as_str).collect();
+                        cache
+                            .get_or_clone_source_with_refs(&source, &url, &refs)
+                            .await
+                            .with_context(|| {
+                                format!("Failed to sync repository for source '{source}'")
+                            })?
+                    };
 
                     // Mark complete in progress window
                     if let Some(ref pm) = progress_clone {
@@ -714,13 +1127,29 @@ impl VersionResolver {
         self.bare_repos.insert(source, repo_path);
     }
 
+    /// Tags of `source`, listed from `repo` on first use and reused for the rest of the command.
+    ///
+    /// Repositories are fetched at most once per command, so the listing
+    /// cannot go stale in between.
+    async fn list_source_tags(&self, source: &str, repo: &GitRepo) -> Result<Vec<String>> {
+        if let Some(tags) = self.source_tags.get(source) {
+            return Ok(tags.clone());
+        }
+        let tags = repo.list_tags().await?;
+        self.source_tags.insert(source.to_string(), tags.clone());
+        Ok(tags)
+    }
+
     /// Clears all resolved versions and cached data
     ///
-    /// Useful for testing or when starting a fresh resolution.
+    /// Useful for testing or when starting a fresh resolution. Recorded
+    /// explanations and tag listings are kept: they are deterministic per
+    /// command and re-syncs during a single command should not discard them.
     pub fn clear(&self) {
         self.entries.clear();
         self.resolved.clear();
         self.bare_repos.clear();
+        self.channels.clear();
     }
 
     /// Returns the number of unique versions to resolve
@@ -827,6 +1256,36 @@ impl VersionResolutionService {
         }
     }
 
+    /// Enable or disable recording of resolution explanations.
+    pub fn set_explain(&mut self, explain: bool) {
+        self.version_resolver.set_explain(explain);
+    }
+
+    /// Set whether version constraints resolve to the highest or lowest matching tag.
+    pub fn set_strategy(&mut self, strategy: ResolutionStrategy) {
+        self.version_resolver.set_strategy(strategy);
+    }
+
+    /// Set which tag is resolved and recorded when several tags name one release.
+    pub fn set_tag_preference(&mut self, preference: TagPreference) {
+        self.version_resolver.set_tag_preference(preference);
+    }
+
+    /// Set whether version ranges only consider tags with a `<tag>-validated` marker.
+    pub fn set_require_validated(&mut self, require_validated: bool) {
+        self.version_resolver.set_require_validated(require_validated);
+    }
+
+    /// Match version constraints for `source` against tags of the form `pattern`.
+    pub fn set_tag_pattern(&self, source: &str, pattern: TagPattern) {
+        self.version_resolver.set_tag_pattern(source, pattern);
+    }
+
+    /// Get recorded resolution explanations, sorted by source and version.
+    pub fn explanations(&self) -> Vec<VersionExplanation> {
+        self.version_resolver.explanations()
+    }
+
     /// Pre-sync all source repositories needed for dependencies.
     ///
     /// This performs all Git network operations upfront:
@@ -849,7 +1308,8 @@ impl VersionResolutionService {
         self.version_resolver.clear();
 
         // Collect all unique (source, version) pairs
-        for (_name, dep) in deps {
+        let mut channels: HashMap<(&str, &str), (&str, Option<&str>)> = HashMap::new();
+        for (name, dep) in deps {
             if let Some(source) = dep.get_source() {
                 let version = dep.get_version(); // None means HEAD
 
@@ -865,6 +1325,30 @@ impl VersionResolutionService {
                     version,
                     dep.resolution_mode(),
                 );
+
+                // A (source, version) pair resolves once, so its dependencies must agree on the channel
+                let channel = dep.get_channel();
+                let key = (source, version.unwrap_or("HEAD"));
+                match channels.get(&key) {
+                    Some((other, other_channel)) if *other_channel != channel => {
+                        anyhow::bail!(
+                            "Dependencies '{}' and '{}' request {}@{} with different channels ({} and {})",
+                            other,
+                            name,
+                            key.0,
+                            key.1,
+                            other_channel.unwrap_or("none"),
+                            channel.unwrap_or("none")
+                        );
+                    }
+                    Some(_) => {}
+                    None => {
+                        channels.insert(key, (name, channel));
+                        if let Some(channel) = channel {
+                            self.version_resolver.set_channel(source, version, channel)?;
+                        }
+                    }
+                }
             }
         }
 
@@ -1143,10 +1627,7 @@ impl VersionResolutionService {
 
         // Ensure the bare repository path is registered
         if self.version_resolver.get_bare_repo_path(source_name).is_none() {
-            let (owner, repo) = crate::git::parse_git_url(&source_url)
-                .unwrap_or(("direct".to_string(), "repo".to_string()));
-            let bare_repo_path =
-                core.cache.cache_dir().join("sources").join(format!("{owner}_{repo}.git"));
+            let bare_repo_path = core.cache.bare_repo_path(&source_url);
             self.version_resolver.register_bare_repo(source_name.to_string(), bare_repo_path);
         }
 
@@ -1339,9 +1820,24 @@ enum RefResolutionResult {
 use crate::version::constraints::{ConstraintSet, VersionConstraint};
 use semver::Version;
 
+/// Suffix of the marker tags that flag a release as validated upstream.
+pub const VALIDATED_SUFFIX: &str = "-validated";
+
+/// Tags in `tags` with a companion `<tag>-validated` marker tag.
+///
+/// The marker tags themselves are never returned.
+#[must_use]
+pub fn validated_tags(tags: &[String]) -> std::collections::HashSet<String> {
+    let all: std::collections::HashSet<&str> = tags.iter().map(String::as_str).collect();
+    tags.iter()
+        .filter(|tag| all.contains(format!("{tag}{VALIDATED_SUFFIX}").as_str()))
+        .cloned()
+        .collect()
+}
+
 /// Checks if a string represents a version constraint rather than a direct reference.
 ///
-/// Version constraints contain operators like `^`, `~`, `>`, `<`, `=`, or special
+/// Version constraints contain operators like `^`, `~`, `>`, `<`, `=`, `||`, or special
 /// keywords. Direct references are branch names, tag names, or commit hashes.
 /// This function now supports prefixed constraints like `agents-^v1.0.0`.
 ///
@@ -1369,8 +1865,10 @@ pub fn is_version_constraint(version: &str) -> bool {
         || version_str.starts_with('>')
         || version_str.starts_with('<')
         || version_str.starts_with('=')
+        // Range constraints like ">=1.0.0, <2.0.0"
         || version_str.contains(',')
-    // Range constraints like ">=1.0.0, <2.0.0"
+        // Unions like "^1.0.0 || ^2.0.0"
+        || version_str.contains("||")
     {
         return true;
     }
@@ -1442,7 +1940,37 @@ pub fn parse_tags_to_versions(tags: Vec<String>) -> Vec<(String, Version)> {
 /// 2. Filtering tags to only those with matching prefix
 /// 3. Parsing the constraint and matching tags
 /// 4. Selecting the best match (usually the highest compatible version)
+///
+/// For unions like `^1.0.0 || ^2.0.0`, the highest tag satisfying any
+/// alternative is selected.
 pub fn find_best_matching_tag(constraint_str: &str, tags: Vec<String>) -> Result<String> {
+    find_matching_tag(constraint_str, tags, ResolutionStrategy::Highest)
+}
+
+/// Finds the highest or lowest tag matching a version constraint.
+///
+/// Behaves like [`find_best_matching_tag`], but with
+/// [`ResolutionStrategy::Lowest`] the lowest satisfying tag is selected
+/// instead. Prefix filtering and prerelease rules are the same for both.
+pub fn find_matching_tag(
+    constraint_str: &str,
+    tags: Vec<String>,
+    strategy: ResolutionStrategy,
+) -> Result<String> {
+    find_preferred_tag(constraint_str, tags, strategy, TagPreference::default())
+}
+
+/// Finds the tag matching a version constraint, choosing among tags of the same release.
+///
+/// Behaves like [`find_matching_tag`]. When several tags name the selected
+/// release (e.g. `v1.2.0` and `v1.2.0+build.7`), `preference` decides which
+/// one is returned, so the choice does not depend on tag listing order.
+pub fn find_preferred_tag(
+    constraint_str: &str,
+    tags: Vec<String>,
+    strategy: ResolutionStrategy,
+    preference: TagPreference,
+) -> Result<String> {
     // Extract prefix from constraint
     let (constraint_prefix, version_str) = crate::version::split_prefix_and_version(constraint_str);
 
@@ -1470,34 +1998,45 @@ pub fn find_best_matching_tag(constraint_str: &str, tags: Vec<String>) -> Result
         ));
     }
 
-    // Special case: wildcard (*) matches the highest available version
-    if version_str == "*" {
-        // tag_versions is already sorted highest first
-        return Ok(tag_versions[0].0.clone());
-    }
-
-    // Parse constraint using ONLY the version part (prefix already filtered)
-    // This ensures semver matching works correctly after prefix filtering
-    let constraint = VersionConstraint::parse(version_str)?;
-
     // Extract just the versions for constraint matching
     let versions: Vec<Version> = tag_versions.iter().map(|(_, v)| v.clone()).collect();
 
-    // Create a constraint set with just this constraint
-    let mut constraint_set = ConstraintSet::new();
-    constraint_set.add(constraint)?;
-
-    // Find the best match
-    if let Some(best_version) = constraint_set.find_best_match(&versions) {
-        // Find the original tag name for this version
-        for (tag_name, version) in tag_versions {
-            if &version == best_version {
-                return Ok(tag_name);
+    // Special case: wildcard (*) matches the highest available version, or the
+    // lowest stable one when resolving minimal versions
+    let best_match = if version_str == "*" {
+        // tag_versions is already sorted highest first
+        match strategy {
+            ResolutionStrategy::Highest => versions.first(),
+            ResolutionStrategy::Lowest => {
+                versions.iter().rev().find(|version| version.pre.is_empty()).or(versions.last())
             }
         }
-    }
+    } else {
+        // Parse the full constraint so every alternative of a union is checked for the
+        // same prefix; matching itself only looks at versions (prefix already filtered)
+        let constraint = VersionConstraint::parse(constraint_str)?;
+
+        // Create a constraint set with just this constraint
+        let mut constraint_set = ConstraintSet::new();
+        constraint_set.add(constraint)?;
+
+        // Find the best match
+        match strategy {
+            ResolutionStrategy::Highest => constraint_set.find_best_match(&versions),
+            ResolutionStrategy::Lowest => constraint_set.find_lowest_match(&versions),
+        }
+    };
 
-    Err(anyhow::anyhow!("No tag found matching constraint: {constraint_str}"))
+    // Pick the preferred of the tags naming the matched release
+    best_match
+        .and_then(|best_version| {
+            tag_versions
+                .iter()
+                .filter(|(_, version)| preference.same_release(version, best_version))
+                .min_by(|a, b| preference.cmp_tags((&a.0, &a.1), (&b.0, &b.1)))
+        })
+        .map(|(tag_name, _)| tag_name.clone())
+        .ok_or_else(|| anyhow::anyhow!("No tag found matching constraint: {constraint_str}"))
 }
 
 // ============================================================================
@@ -1685,6 +2224,56 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_validated_tags() {
+        let tags: Vec<String> = ["v1.0.0", "v1.0.0-validated", "v1.1.0", "v2.0.0-validated"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        let validated = validated_tags(&tags);
+        assert_eq!(validated, std::collections::HashSet::from(["v1.0.0".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_tags_listed_once_per_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        let git = crate::test_utils::TestGit::new(&repo_dir);
+        git.init().unwrap();
+        git.config_user().unwrap();
+        std::fs::write(repo_dir.join("agent.md"), "# Agent").unwrap();
+        git.add_all().unwrap();
+        git.commit("Initial").unwrap();
+        git.tag("v1.0.0").unwrap();
+
+        async fn resolve(resolver: &VersionResolver, repo: &Path, version: &str) -> String {
+            let source = "source".to_string();
+            resolver.add_version(
+                &source,
+                "https://example.com/repo.git",
+                Some(version),
+                ResolutionMode::Version,
+            );
+            resolver.register_bare_repo(source.clone(), repo.to_path_buf());
+            resolver.resolve_all(None).await.unwrap();
+            resolver.get_all_resolved_full()[&(source, version.to_string())].resolved_ref.clone()
+        }
+
+        let cache = Cache::with_dir(temp_dir.path().join("cache")).unwrap();
+        let resolver = VersionResolver::new(cache.clone());
+        assert_eq!(resolve(&resolver, &repo_dir, "^1.0.0").await, "v1.0.0");
+
+        // A later dependency on the same source reuses the first listing, so
+        // a tag created since then is not seen within the same command
+        git.tag("v1.1.0").unwrap();
+        resolver.clear();
+        assert_eq!(resolve(&resolver, &repo_dir, "^1.0").await, "v1.0.0");
+
+        let fresh = VersionResolver::new(cache);
+        assert_eq!(resolve(&fresh, &repo_dir, "^1.0").await, "v1.1.0");
+    }
+
     #[tokio::test]
     async fn test_version_resolver_deduplication() {
         let temp_dir = TempDir::new().unwrap();
@@ -1787,4 +2376,125 @@ mod tests {
         );
         assert_eq!(format_source_display("relative", "../some/path"), "relative (../some/path)");
     }
+
+    #[test]
+    fn test_find_best_matching_tag_union() {
+        let tags: Vec<String> = ["v1.2.0", "v1.9.3", "v2.0.0", "v2.4.1", "v3.0.0", "main"]
+            .iter()
+            .map(|t| (*t).to_string())
+            .collect();
+
+        assert!(is_version_constraint("^1 || ^2"));
+        assert!(is_version_constraint("1.x || 2.x"));
+
+        // Highest match across both ranges, excluding 3.x
+        assert_eq!(find_best_matching_tag("^1 || ^2", tags.clone()).unwrap(), "v2.4.1");
+        assert_eq!(find_best_matching_tag("1.x || 2.x", tags.clone()).unwrap(), "v2.4.1");
+        // Order of alternatives does not matter
+        assert_eq!(find_best_matching_tag("^v2.0.0 || ^v1.0.0", tags.clone()).unwrap(), "v2.4.1");
+        // Falls back to the lower range when the higher one has no match
+        assert_eq!(find_best_matching_tag("^1 || ^4", tags.clone()).unwrap(), "v1.9.3");
+        assert!(find_best_matching_tag("^4 || ^5", tags).is_err());
+    }
+
+    #[test]
+    fn test_find_best_matching_tag_prerelease_channel() {
+        let tags: Vec<String> = [
+            "v1.0.0-alpha.1",
+            "v1.0.0-beta.1",
+            "v1.0.0-beta.2",
+            "v0.9.0",
+            "agents-v1.0.0-beta.3",
+            "agents-v1.0.0-alpha.9",
+        ]
+        .iter()
+        .map(|t| (*t).to_string())
+        .collect();
+
+        assert!(is_version_constraint("^1.0.0-beta"));
+        assert_eq!(find_best_matching_tag("^1.0.0-beta", tags.clone()).unwrap(), "v1.0.0-beta.2");
+        assert_eq!(
+            find_best_matching_tag("^v1.0.0-beta.1", tags.clone()).unwrap(),
+            "v1.0.0-beta.2"
+        );
+        assert!(find_best_matching_tag("^1.0.0-rc", tags.clone()).is_err());
+        // Without a channel, prereleases stay hidden
+        assert!(find_best_matching_tag("^1.0.0", tags.clone()).is_err());
+        // Prefixed channels only see their own tags
+        assert_eq!(
+            find_best_matching_tag("agents-^v1.0.0-beta", tags.clone()).unwrap(),
+            "agents-v1.0.0-beta.3"
+        );
+        assert_eq!(
+            find_matching_tag("agents-^v1.0.0-alpha", tags, ResolutionStrategy::Lowest).unwrap(),
+            "agents-v1.0.0-alpha.9"
+        );
+    }
+
+    #[test]
+    fn test_find_best_matching_tag_union_with_prefix() {
+        let tags: Vec<String> =
+            ["agents-v1.1.0", "agents-v2.2.0", "agents-v3.0.0", "snippets-v2.9.0", "v2.5.0"]
+                .iter()
+                .map(|t| (*t).to_string())
+                .collect();
+
+        assert_eq!(
+            find_best_matching_tag("agents-^v1.0.0 || agents-^v2.0.0", tags.clone()).unwrap(),
+            "agents-v2.2.0"
+        );
+        // Alternatives must share a prefix
+        assert!(
+            find_best_matching_tag("agents-^v1.0.0 || snippets-^v2.0.0", tags.clone()).is_err()
+        );
+        assert!(find_best_matching_tag("agents-^v1.0.0 || ^v2.0.0", tags).is_err());
+    }
+
+    #[test]
+    fn test_find_matching_tag_strategy() {
+        let tags: Vec<String> =
+            ["v0.9.0", "v1.0.0-rc.1", "v1.0.0", "v1.2.0", "v1.4.2", "v2.0.0", "agents-v1.1.0"]
+                .iter()
+                .map(|t| (*t).to_string())
+                .collect();
+
+        let highest = |c: &str| find_matching_tag(c, tags.clone(), ResolutionStrategy::Highest);
+        let lowest = |c: &str| find_matching_tag(c, tags.clone(), ResolutionStrategy::Lowest);
+
+        assert_eq!(highest("^1.0.0").unwrap(), "v1.4.2");
+        assert_eq!(lowest("^1.0.0").unwrap(), "v1.0.0");
+        assert_eq!(lowest("~1.2.0").unwrap(), "v1.2.0");
+        assert_eq!(lowest(">=0.9.0").unwrap(), "v0.9.0");
+        assert_eq!(lowest("*").unwrap(), "v0.9.0");
+        // Prefixes still select their own tags
+        assert_eq!(lowest("agents-^v1.0.0").unwrap(), "agents-v1.1.0");
+        assert!(lowest("^3.0.0").is_err());
+    }
+
+    #[test]
+    fn test_find_preferred_tag_same_release() {
+        let tags: Vec<String> = ["v1.2.0+build.7", "1.2.0", "v1.2.0", "v1.1.0", "stable"]
+            .iter()
+            .map(|t| (*t).to_string())
+            .collect();
+        let find = |c: &str, preference| {
+            find_preferred_tag(c, tags.clone(), ResolutionStrategy::Highest, preference)
+        };
+
+        assert_eq!(find("^1.0.0", TagPreference::Semver).unwrap(), "v1.2.0");
+        assert_eq!(find("*", TagPreference::Semver).unwrap(), "v1.2.0");
+        assert_eq!(find("=1.2.0", TagPreference::Semver).unwrap(), "v1.2.0");
+        // `requested` treats build metadata as a distinct version and breaks ties by name
+        assert_eq!(find("^1.0.0", TagPreference::Requested).unwrap(), "v1.2.0+build.7");
+        assert_eq!(
+            find_preferred_tag(
+                "^1.0.0",
+                tags.clone(),
+                ResolutionStrategy::Lowest,
+                TagPreference::Semver
+            )
+            .unwrap(),
+            "v1.1.0"
+        );
+    }
 }
@@ -0,0 +1,625 @@
+//! Versioned archive sources.
+//!
+//! Not every upstream publishes a Git repository; some only publish release
+//! tarballs. A source whose URL points at a `.tar.gz`, `.tgz` or `.zip` file is
+//! downloaded once, extracted into the cache and then used like a checkout:
+//!
+//! ```toml
+//! [sources]
+//! vendor = "https://example.com/releases/v1.0.0.tar.gz#sha256=9f86d08..."
+//! ```
+//!
+//! - The version is taken from the URL (the last `X.Y.Z` component, with an
+//!   optional `v` prefix) and pins every dependency on the source. A
+//!   dependency may omit `version` or repeat the same version; constraints,
+//!   branches and `latest` cannot be resolved against a single archive.
+//! - An optional `#sha256=<hex>` fragment is verified against the downloaded
+//!   bytes before anything is extracted.
+//! - When the archive has a single top-level directory named after the
+//!   version (as GitHub release tarballs do), that directory becomes the
+//!   source root.
+//! - Only regular files and directories are extracted; symlinks and entries
+//!   escaping the destination are skipped or rejected.
+//!
+//! Archives are fetched over HTTP(S) or from `file://` URLs. The lockfile
+//! records the first 40 hex digits of the archive's SHA-256 as the resolved
+//! commit, so a replaced upstream archive is detected on the next install.
+//!
+//! The `ETag` and `Last-Modified` headers of an HTTP download are kept as
+//! [`HttpValidators`]. Resolving the source again sends them back as
+//! `If-None-Match` / `If-Modified-Since`, and a `304 Not Modified` answer
+//! keeps the cached extraction without downloading the archive again.
+
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Maximum size of a downloaded archive (100 MB).
+const MAX_ARCHIVE_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Maximum total size of the files extracted from an archive (1 GB).
+const MAX_EXTRACTED_SIZE: u64 = 1024 * 1024 * 1024;
+
+static VERSION_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[/_-])(v?\d+\.\d+\.\d+(?:-[0-9A-Za-z][0-9A-Za-z.-]*)?)")
+        .expect("valid version pattern")
+});
+
+/// Supported archive formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// Gzip-compressed tarball (`.tar.gz` or `.tgz`)
+    TarGz,
+    /// Zip archive (`.zip`)
+    Zip,
+}
+
+impl ArchiveFormat {
+    fn from_path(path: &str) -> Option<(Self, &str)> {
+        let lower = path.to_ascii_lowercase();
+        [(".tar.gz", Self::TarGz), (".tgz", Self::TarGz), (".zip", Self::Zip)]
+            .into_iter()
+            .find(|(ext, _)| lower.ends_with(ext))
+            .map(|(ext, format)| (format, &path[..path.len() - ext.len()]))
+    }
+}
+
+/// Whether `url` refers to a versioned archive rather than a Git repository.
+///
+/// Only remote and `file://` URLs are considered; plain filesystem paths are
+/// always treated as local directory sources.
+#[must_use]
+pub fn is_archive_url(url: &str) -> bool {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return false;
+    };
+    if !matches!(scheme, "http" | "https" | "file") {
+        return false;
+    }
+    let path = rest.split(['?', '#']).next().unwrap_or(rest);
+    ArchiveFormat::from_path(path).is_some()
+}
+
+/// HTTP cache validators returned with a downloaded archive.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpValidators {
+    /// Value of the `ETag` header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// Value of the `Last-Modified` header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+}
+
+impl HttpValidators {
+    fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        let header =
+            |name| headers.get(name).and_then(|value| value.to_str().ok()).map(ToString::to_string);
+        Self {
+            etag: header(reqwest::header::ETAG),
+            last_modified: header(reqwest::header::LAST_MODIFIED),
+        }
+    }
+
+    /// Whether the server sent neither header.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+/// Outcome of [`ArchiveUrl::download_if_modified`].
+#[derive(Debug)]
+pub enum ArchiveDownload {
+    /// The server answered `304 Not Modified`
+    NotModified,
+    /// The archive was downloaded and its checksum verified
+    Fetched {
+        /// Archive bytes
+        bytes: Vec<u8>,
+        /// SHA-256 of the archive as lowercase hex
+        digest: String,
+        /// Validators to send with the next request
+        validators: HttpValidators,
+    },
+}
+
+/// A parsed archive source URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveUrl {
+    /// URL to download, without the checksum fragment
+    pub url: String,
+    /// Version taken from the URL, as written (e.g. `v1.0.0`)
+    pub version: String,
+    /// Expected SHA-256 of the archive as lowercase hex, if given
+    pub checksum: Option<String>,
+    /// Archive format, from the file extension
+    pub format: ArchiveFormat,
+}
+
+impl ArchiveUrl {
+    /// Parse an archive source URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is not an archive URL, contains no version
+    /// or has a malformed checksum fragment.
+    pub fn parse(url: &str) -> Result<Self> {
+        if !is_archive_url(url) {
+            bail!("'{url}' is not a .tar.gz, .tgz or .zip URL");
+        }
+
+        let (base, fragment) = match url.split_once('#') {
+            Some((base, fragment)) => (base, Some(fragment)),
+            None => (url, None),
+        };
+
+        let checksum = fragment
+            .map(|fragment| {
+                let hex = fragment.strip_prefix("sha256=").with_context(|| {
+                    format!("Unsupported fragment '#{fragment}' in archive URL; expected '#sha256=<hex>'")
+                })?;
+                if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    bail!("Invalid SHA-256 checksum '{hex}': expected 64 hex characters");
+                }
+                Ok(hex.to_ascii_lowercase())
+            })
+            .transpose()?;
+
+        let path = base.split_once("://").map_or(base, |(_, rest)| rest);
+        let path = path.split('?').next().unwrap_or(path);
+        let (format, stem) =
+            ArchiveFormat::from_path(path).expect("is_archive_url checked the extension");
+
+        let version = VERSION_PATTERN
+            .captures_iter(stem)
+            .last()
+            .map(|caps| caps[1].to_string())
+            .filter(|version| semver::Version::parse(version.trim_start_matches('v')).is_ok())
+            .with_context(|| {
+                format!(
+                    "Archive URL '{base}' does not contain a version. \
+                     Use a versioned release URL such as https://example.com/v1.0.0.tar.gz"
+                )
+            })?;
+
+        Ok(Self {
+            url: base.to_string(),
+            version,
+            checksum,
+            format,
+        })
+    }
+
+    /// Check a dependency's `version` against the version pinned by the URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `requested` names a different version, a version
+    /// constraint or a branch.
+    pub fn check_version(&self, requested: Option<&str>) -> Result<()> {
+        let Some(requested) = requested else {
+            return Ok(());
+        };
+        let same = requested == self.version
+            || matches!(
+                (
+                    semver::Version::parse(requested.trim_start_matches('v')),
+                    semver::Version::parse(self.version.trim_start_matches('v')),
+                ),
+                (Ok(a), Ok(b)) if a == b
+            );
+        if !same {
+            bail!(
+                "Archive source '{}' is pinned to version '{}' by its URL and cannot resolve '{requested}'. \
+                 Remove the 'version' field or point the source at another release",
+                self.url,
+                self.version
+            );
+        }
+        Ok(())
+    }
+
+    /// Directory name for this archive in the cache.
+    ///
+    /// Includes the version for readability and a hash of the URL so that
+    /// different archives never share a directory.
+    #[must_use]
+    pub fn cache_dir_name(&self) -> String {
+        let hash = hex::encode(Sha256::digest(self.url.as_bytes()));
+        format!("{}_{}", self.version, &hash[..16])
+    }
+
+    /// Download the archive and verify its checksum.
+    ///
+    /// Returns the archive bytes and their SHA-256 as lowercase hex.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the download fails, the archive is larger than
+    /// 100 MB or the checksum does not match.
+    pub async fn download(&self) -> Result<(Vec<u8>, String)> {
+        match self.download_if_modified(None).await? {
+            ArchiveDownload::Fetched {
+                bytes,
+                digest,
+                ..
+            } => Ok((bytes, digest)),
+            ArchiveDownload::NotModified => {
+                bail!("Unexpected 304 Not Modified for archive {}", self.url)
+            }
+        }
+    }
+
+    /// Download the archive unless it is unchanged since `validators` were recorded.
+    ///
+    /// For HTTP(S) URLs the validators are sent as `If-None-Match` and
+    /// `If-Modified-Since`. `file://` URLs are always read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the download fails, the archive is larger than
+    /// 100 MB or the checksum does not match.
+    pub async fn download_if_modified(
+        &self,
+        validators: Option<&HttpValidators>,
+    ) -> Result<ArchiveDownload> {
+        let (bytes, validators) = if let Some(path) = self.url.strip_prefix("file://") {
+            let len = tokio::fs::metadata(path)
+                .await
+                .with_context(|| format!("Failed to read archive {}", self.url))?
+                .len();
+            if len > MAX_ARCHIVE_SIZE {
+                bail!("Archive {} is larger than {MAX_ARCHIVE_SIZE} bytes", self.url);
+            }
+            let bytes = tokio::fs::read(path)
+                .await
+                .with_context(|| format!("Failed to read archive {}", self.url))?;
+            (bytes, HttpValidators::default())
+        } else {
+            let client =
+                reqwest::Client::builder().timeout(std::time::Duration::from_secs(300)).build()?;
+            let mut request = client.get(&self.url).header("User-Agent", "agpm");
+            if let Some(validators) = validators {
+                if let Some(etag) = &validators.etag {
+                    request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+                }
+                if let Some(last_modified) = &validators.last_modified {
+                    request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+                }
+            }
+            let response = request
+                .send()
+                .await
+                .with_context(|| format!("Failed to download archive {}", self.url))?;
+            if response.status() == reqwest::StatusCode::NOT_MODIFIED && validators.is_some() {
+                return Ok(ArchiveDownload::NotModified);
+            }
+            if !response.status().is_success() {
+                bail!("Failed to download archive {}: HTTP {}", self.url, response.status());
+            }
+            if response.content_length().is_some_and(|len| len > MAX_ARCHIVE_SIZE) {
+                bail!("Archive {} is larger than {MAX_ARCHIVE_SIZE} bytes", self.url);
+            }
+            let validators = HttpValidators::from_headers(response.headers());
+            (read_limited(response, MAX_ARCHIVE_SIZE, &self.url).await?, validators)
+        };
+
+        let digest = hex::encode(Sha256::digest(&bytes));
+        if let Some(expected) = &self.checksum
+            && *expected != digest
+        {
+            bail!("Checksum mismatch for archive {}: expected {expected}, got {digest}", self.url);
+        }
+
+        Ok(ArchiveDownload::Fetched {
+            bytes,
+            digest,
+            validators,
+        })
+    }
+}
+
+/// Read the body of `response`, failing as soon as it exceeds `limit` bytes.
+///
+/// `Content-Length` is optional (e.g. chunked responses), so the body is
+/// streamed and counted rather than buffered whole before the size check.
+async fn read_limited(mut response: reqwest::Response, limit: u64, url: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) =
+        response.chunk().await.with_context(|| format!("Failed to download archive {url}"))?
+    {
+        if (bytes.len() + chunk.len()) as u64 > limit {
+            bail!("Archive {url} is larger than {limit} bytes");
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+/// The identifier recorded as the resolved commit for an archive digest.
+#[must_use]
+pub fn archive_id(digest: &str) -> &str {
+    &digest[..40.min(digest.len())]
+}
+
+/// Extract `bytes` into `dest`, replacing any previous contents.
+///
+/// The archive is unpacked into a staging directory next to `dest` first, so
+/// an interrupted extraction never leaves a partial source behind. A single
+/// top-level directory whose name contains `version` (`pkg-1.0.0/`) is
+/// stripped.
+///
+/// # Errors
+///
+/// Returns an error if the archive is corrupt, contains entries that would
+/// escape the destination, or expands to more than 1 GB.
+pub fn extract(bytes: &[u8], format: ArchiveFormat, version: &str, dest: &Path) -> Result<()> {
+    let parent = dest.parent().context("Archive destination has no parent directory")?;
+    std::fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create {}", parent.display()))?;
+    let staging = tempfile::Builder::new()
+        .prefix(".extract-")
+        .tempdir_in(parent)
+        .with_context(|| format!("Failed to create staging directory in {}", parent.display()))?;
+
+    match format {
+        ArchiveFormat::TarGz => extract_tar_gz(bytes, staging.path(), MAX_EXTRACTED_SIZE)?,
+        ArchiveFormat::Zip => extract_zip(bytes, staging.path(), MAX_EXTRACTED_SIZE)?,
+    }
+
+    let root =
+        versioned_top_level_dir(staging.path(), version)?.unwrap_or_else(|| staging.path().into());
+    if dest.exists() {
+        std::fs::remove_dir_all(dest)
+            .with_context(|| format!("Failed to remove {}", dest.display()))?;
+    }
+    std::fs::rename(&root, dest)
+        .with_context(|| format!("Failed to move extracted archive to {}", dest.display()))?;
+    Ok(())
+}
+
+/// Extract a `.tar.gz` archive into `dest`, writing at most `limit` bytes.
+fn extract_tar_gz(bytes: &[u8], dest: &Path, limit: u64) -> Result<()> {
+    let mut total: u64 = 0;
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(bytes));
+    for entry in archive.entries().context("Failed to read tar archive")? {
+        let mut entry = entry.context("Failed to read tar archive entry")?;
+        if !matches!(
+            entry.header().entry_type(),
+            tar::EntryType::Regular | tar::EntryType::Directory
+        ) {
+            continue;
+        }
+        // The header size is exactly what unpacking writes for a regular file
+        total = total.saturating_add(entry.size());
+        if total > limit {
+            bail!("Archive expands to more than {limit} bytes");
+        }
+        let path = entry.path()?.into_owned();
+        if !entry
+            .unpack_in(dest)
+            .with_context(|| format!("Failed to extract {}", path.display()))?
+        {
+            bail!("Archive entry '{}' escapes the extraction directory", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Extract a `.zip` archive into `dest`, writing at most `limit` bytes.
+fn extract_zip(bytes: &[u8], dest: &Path, limit: u64) -> Result<()> {
+    let mut total: u64 = 0;
+    let mut archive =
+        zip::ZipArchive::new(std::io::Cursor::new(bytes)).context("Failed to read zip archive")?;
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        let Some(relative) = file.enclosed_name() else {
+            bail!("Archive entry '{}' escapes the extraction directory", file.name());
+        };
+        let target = dest.join(relative);
+        if file.is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else if file.is_file() {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            // The declared size can lie, so count what is actually decompressed
+            let mut content = Vec::new();
+            (&mut file).take(limit - total + 1).read_to_end(&mut content)?;
+            total += content.len() as u64;
+            if total > limit {
+                bail!("Archive expands to more than {limit} bytes");
+            }
+            std::fs::write(&target, content)
+                .with_context(|| format!("Failed to extract {}", target.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// The only entry of `dir`, if it is a directory named after `version`.
+///
+/// Resource directories such as `agents/` are never stripped, even when they
+/// are the only top-level entry.
+fn versioned_top_level_dir(dir: &Path, version: &str) -> Result<Option<PathBuf>> {
+    let entries: Vec<_> = std::fs::read_dir(dir)?.collect::<std::io::Result<_>>()?;
+    let version = version.trim_start_matches('v');
+    match entries.as_slice() {
+        [entry]
+            if entry.file_type()?.is_dir()
+                && entry.file_name().to_string_lossy().contains(version) =>
+        {
+            Ok(Some(entry.path()))
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tar_gz(files: &[(&str, &str)]) -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, content.as_bytes()).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[test]
+    fn test_is_archive_url() {
+        assert!(is_archive_url("https://example.com/releases/v1.0.0.tar.gz"));
+        assert!(is_archive_url("https://example.com/pkg-1.0.0.TGZ?download=1"));
+        assert!(is_archive_url("file:///tmp/v1.0.0.zip#sha256=abc"));
+        assert!(!is_archive_url("https://github.com/org/repo.git"));
+        assert!(!is_archive_url("/tmp/v1.0.0.tar.gz"));
+        assert!(!is_archive_url("git@github.com:org/v1.0.0.tar.gz"));
+    }
+
+    #[test]
+    fn test_parse_archive_url() {
+        let checksum = "a".repeat(64);
+        let archive = ArchiveUrl::parse(&format!(
+            "https://example.com/releases/v1.2.3.tar.gz#sha256={checksum}"
+        ))
+        .unwrap();
+        assert_eq!(archive.url, "https://example.com/releases/v1.2.3.tar.gz");
+        assert_eq!(archive.version, "v1.2.3");
+        assert_eq!(archive.checksum.as_deref(), Some(checksum.as_str()));
+        assert_eq!(archive.format, ArchiveFormat::TarGz);
+
+        let archive =
+            ArchiveUrl::parse("https://example.com/v2.0.0/agents-2.0.0-rc.1.zip").unwrap();
+        assert_eq!(archive.version, "2.0.0-rc.1");
+        assert_eq!(archive.format, ArchiveFormat::Zip);
+
+        assert!(ArchiveUrl::parse("https://example.com/latest.tar.gz").is_err());
+        assert!(ArchiveUrl::parse("https://example.com/v1.0.0.tar.gz#md5=abc").is_err());
+        assert!(ArchiveUrl::parse("https://example.com/v1.0.0.tar.gz#sha256=abc").is_err());
+    }
+
+    #[test]
+    fn test_check_version() {
+        let archive = ArchiveUrl::parse("https://example.com/v1.0.0.tar.gz").unwrap();
+        assert!(archive.check_version(None).is_ok());
+        assert!(archive.check_version(Some("v1.0.0")).is_ok());
+        assert!(archive.check_version(Some("1.0.0")).is_ok());
+        assert!(archive.check_version(Some("v1.1.0")).is_err());
+        assert!(archive.check_version(Some("^1.0.0")).is_err());
+        assert!(archive.check_version(Some("latest")).is_err());
+    }
+
+    #[test]
+    fn test_extract_strips_top_level_dir() {
+        let temp = TempDir::new().unwrap();
+        let bytes = tar_gz(&[
+            ("pkg-1.0.0/agents/helper.md", "# Helper"),
+            ("pkg-1.0.0/snippets/style.md", "# Style"),
+        ]);
+        let dest = temp.path().join("archives/pkg");
+
+        extract(&bytes, ArchiveFormat::TarGz, "v1.0.0", &dest).unwrap();
+        assert_eq!(std::fs::read_to_string(dest.join("agents/helper.md")).unwrap(), "# Helper");
+        assert!(dest.join("snippets/style.md").exists());
+
+        // Extracting again replaces the previous contents; a lone resource
+        // directory is kept
+        let bytes = tar_gz(&[("agents/other.md", "# Other")]);
+        extract(&bytes, ArchiveFormat::TarGz, "v1.0.0", &dest).unwrap();
+        assert!(dest.join("agents/other.md").exists());
+        assert!(!dest.join("agents/helper.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_download_verifies_checksum() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("v1.0.0.tar.gz");
+        let bytes = tar_gz(&[("agent.md", "# Agent")]);
+        std::fs::write(&path, &bytes).unwrap();
+        let digest = hex::encode(Sha256::digest(&bytes));
+
+        let url = format!("file://{}", path.display());
+        let archive = ArchiveUrl::parse(&format!("{url}#sha256={digest}")).unwrap();
+        let (downloaded, actual) = archive.download().await.unwrap();
+        assert_eq!(downloaded, bytes);
+        assert_eq!(actual, digest);
+
+        let wrong = ArchiveUrl::parse(&format!("{url}#sha256={}", "0".repeat(64))).unwrap();
+        let err = wrong.download().await.unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_read_limited_without_content_length() {
+        use tokio::io::AsyncWriteExt;
+
+        // A chunked response: the size is only known while streaming
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1.0.0.tar.gz", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut response =
+                    b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n"
+                        .to_vec();
+                for _ in 0..3 {
+                    response.extend_from_slice(b"400\r\n");
+                    response.extend_from_slice(&[b'x'; 1024]);
+                    response.extend_from_slice(b"\r\n");
+                }
+                response.extend_from_slice(b"0\r\n\r\n");
+                let _ = stream.write_all(&response).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+
+        let response = reqwest::get(&url).await.unwrap();
+        assert_eq!(response.content_length(), None);
+        let err = read_limited(response, 2048, &url).await.unwrap_err();
+        assert!(err.to_string().contains("larger than 2048 bytes"), "{err}");
+
+        let response = reqwest::get(&url).await.unwrap();
+        assert_eq!(read_limited(response, 4096, &url).await.unwrap().len(), 3072);
+    }
+
+    #[test]
+    fn test_extract_stops_at_size_limit() {
+        let temp = TempDir::new().unwrap();
+        // 4 MB of zeros compresses to a few KB
+        let zeros = "\0".repeat(4 * 1024 * 1024);
+        let limit = 1024 * 1024;
+        for dir in ["tar", "zip", "ok"] {
+            std::fs::create_dir(temp.path().join(dir)).unwrap();
+        }
+
+        let bytes = tar_gz(&[("agents/a.md", "# Agent"), ("agents/bomb.md", &zeros)]);
+        assert!((bytes.len() as u64) < limit / 10);
+        let err = extract_tar_gz(&bytes, &temp.path().join("tar"), limit).unwrap_err();
+        assert!(err.to_string().contains("expands to more than"), "{err}");
+        assert!(!temp.path().join("tar/agents/bomb.md").exists());
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        writer.start_file("agents/bomb.md", zip::write::SimpleFileOptions::default()).unwrap();
+        std::io::Write::write_all(&mut writer, zeros.as_bytes()).unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+        assert!((bytes.len() as u64) < limit / 10);
+        let err = extract_zip(&bytes, &temp.path().join("zip"), limit).unwrap_err();
+        assert!(err.to_string().contains("expands to more than"), "{err}");
+        assert!(!temp.path().join("zip/agents/bomb.md").exists());
+
+        // Within the limit both formats extract
+        extract_tar_gz(&tar_gz(&[("agents/a.md", "# Agent")]), &temp.path().join("ok"), limit)
+            .unwrap();
+    }
+}
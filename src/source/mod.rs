@@ -7,6 +7,8 @@
 //!
 //! - [`Source`] - Individual repository with metadata
 //! - [`SourceManager`] - Manages multiple sources with sync/verify operations
+//! - [`archive`] - Sources published as versioned `.tar.gz`/`.zip` archives
+//! - [`scan`] - Walking the resource files of a repository checkout
 //!
 //! # Configuration
 //!
@@ -21,6 +23,9 @@
 //! - Parallel sync operations with file-based locking
 //! - Automatic cleanup and validation of invalid caches
 
+pub mod archive;
+pub mod scan;
+
 use crate::cache::lock::CacheLock;
 use crate::config::GlobalConfig;
 use crate::core::AgpmError;
@@ -28,6 +33,7 @@ use crate::git::{GitRepo, parse_git_url};
 use crate::manifest::Manifest;
 use crate::utils::fs::ensure_dir;
 use crate::utils::security::validate_path_security;
+use crate::utils::terminal::WARNING;
 use anyhow::{Context, Result};
 use futures::future::join_all;
 use serde::{Deserialize, Serialize};
@@ -225,7 +231,18 @@ impl SourceManager {
 
         // Load global config and merge sources
         let global_config = GlobalConfig::load().await.unwrap_or_default();
-        let merged_sources = global_config.merge_sources(&manifest.sources);
+        let mut merged_sources = global_config.merge_sources(&manifest.sources);
+
+        // Fall back to the configured URLs if the keychain can't be queried;
+        // authenticated fetches will then fail with the usual Git auth error
+        if let Err(e) = global_config.apply_credential_store(&mut merged_sources) {
+            eprintln!("{WARNING}{e}");
+        }
+        if let Err(e) =
+            global_config.apply_token_env(&mut merged_sources, manifest.manifest_dir.as_deref())
+        {
+            eprintln!("{WARNING}{e:#}");
+        }
 
         // Load all merged sources
         for (name, url) in &merged_sources {
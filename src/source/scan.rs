@@ -0,0 +1,119 @@
+//! Walking the resource files of a source repository checkout.
+//!
+//! Shared by `agpm bundle lint`, which validates every file, and `agpm index`,
+//! which extracts their metadata. Only Markdown and JSON files are visited,
+//! in file name order; hidden files and directories such as `.git` are
+//! skipped.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use crate::core::ResourceType;
+use crate::utils::normalize_path_for_storage;
+
+/// A Markdown or JSON file found in a repository.
+#[derive(Debug, Clone)]
+pub struct ResourceFile {
+    /// Absolute path of the file.
+    pub path: PathBuf,
+    /// Path relative to the repository root, with forward slashes.
+    pub relative: String,
+    /// Whether the file is JSON rather than Markdown.
+    pub is_json: bool,
+}
+
+impl ResourceFile {
+    /// Whether any parent directory of the file is called `name`.
+    #[must_use]
+    pub fn in_directory(&self, name: &str) -> bool {
+        Path::new(&self.relative)
+            .parent()
+            .is_some_and(|dir| dir.components().any(|c| c.as_os_str() == name))
+    }
+
+    /// Resource type implied by the nearest parent directory named after a
+    /// type, e.g. `agents/review/security.md` is an agent.
+    #[must_use]
+    pub fn resource_type(&self) -> Option<ResourceType> {
+        Path::new(&self.relative)
+            .parent()?
+            .components()
+            .rev()
+            .find_map(|c| ResourceType::from_frontmatter_str(&c.as_os_str().to_string_lossy()))
+            .filter(|t| !matches!(t, ResourceType::Skill) || self.relative.ends_with("SKILL.md"))
+    }
+}
+
+/// Iterate over the Markdown and JSON files below `root`.
+///
+/// `root` should be canonical so that the relative paths are computed
+/// correctly.
+pub fn resource_files(root: &Path) -> impl Iterator<Item = Result<ResourceFile>> + '_ {
+    walkdir::WalkDir::new(root)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(move |entry| {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err(e.into())),
+            };
+            if !entry.file_type().is_file() {
+                return None;
+            }
+            let path = entry.into_path();
+            let is_json = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+            if !is_json && !crate::markdown::is_markdown_file(&path) {
+                return None;
+            }
+            let relative = normalize_path_for_storage(path.strip_prefix(root).unwrap_or(&path));
+            Some(Ok(ResourceFile {
+                path,
+                relative,
+                is_json,
+            }))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resource_files() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        for file in [
+            "agents/review/security.md",
+            "snippets/style.md",
+            "mcp-servers/fs.json",
+            "skills/lint/SKILL.md",
+            "skills/lint/notes.md",
+            "scripts/run.sh",
+            ".github/ci.md",
+            "README.md",
+        ] {
+            std::fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+            std::fs::write(root.join(file), "").unwrap();
+        }
+
+        let files: Vec<ResourceFile> = resource_files(root).collect::<Result<_>>().unwrap();
+        let found: Vec<(&str, Option<ResourceType>)> =
+            files.iter().map(|f| (f.relative.as_str(), f.resource_type())).collect();
+        assert_eq!(
+            found,
+            [
+                ("README.md", None),
+                ("agents/review/security.md", Some(ResourceType::Agent)),
+                ("mcp-servers/fs.json", Some(ResourceType::McpServer)),
+                ("skills/lint/SKILL.md", Some(ResourceType::Skill)),
+                ("skills/lint/notes.md", None),
+                ("snippets/style.md", Some(ResourceType::Snippet)),
+            ]
+        );
+        assert!(files[2].is_json);
+        assert!(files[1].in_directory("review"));
+    }
+}
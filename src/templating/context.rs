@@ -168,6 +168,8 @@ pub struct TemplateContextBuilder {
     /// Shared via `Arc<Mutex>` for safe concurrent access
     dependency_specs_cache:
         Arc<Mutex<HashMap<String, BTreeMap<String, crate::manifest::DependencySpec>>>>,
+    /// Frontmatter key declaring dependencies (`[transitive] field` in the manifest)
+    dependency_field: String,
 }
 
 impl TemplateContextBuilder {
@@ -193,9 +195,17 @@ impl TemplateContextBuilder {
             render_cache: Arc::new(Mutex::new(RenderCache::new())),
             custom_names_cache: Arc::new(Mutex::new(HashMap::new())),
             dependency_specs_cache: Arc::new(Mutex::new(HashMap::new())),
+            dependency_field: crate::manifest::TransitivePolicy::DEFAULT_FIELD.to_string(),
         }
     }
 
+    /// Read dependency declarations from `field` instead of `dependencies`.
+    #[must_use]
+    pub fn with_dependency_field(mut self, field: &str) -> Self {
+        self.dependency_field = field.to_string();
+        self
+    }
+
     /// Clear the render cache.
     ///
     /// Should be called after installation completes to free memory
@@ -630,6 +640,10 @@ impl DependencyExtractor for TemplateContextBuilder {
         &self.dependency_specs_cache
     }
 
+    fn dependency_field(&self) -> &str {
+        &self.dependency_field
+    }
+
     async fn build_dependencies_data(
         &self,
         current_resource: &crate::lockfile::LockedResource,
@@ -15,6 +15,7 @@ use crate::lockfile::{LockedResource, ResourceId};
 use super::extractors::{DependencyExtractor, create_dependency_ref_string};
 use crate::templating::cache::RenderCacheKey;
 use crate::templating::context::DependencyData;
+use crate::templating::includes::render_includes;
 use crate::templating::renderer::TemplateRenderer;
 use crate::templating::utils::to_native_path_display;
 
@@ -392,6 +393,8 @@ pub(crate) async fn build_dependencies_data<T: DependencyExtractor>(
                     Ok(dep_context) => {
                         // Render the dependency's content
                         if let Some(content) = raw_content {
+                            let includes =
+                                render_includes(extractor, resource, &content, None).await?;
                             let mut renderer = TemplateRenderer::new(
                                     true,
                                     extractor.project_dir().clone(),
@@ -403,7 +406,8 @@ pub(crate) async fn build_dependencies_data<T: DependencyExtractor>(
                                         resource.name,
                                         dep_type
                                     )
-                                })?;
+                                })?
+                                .with_includes(includes);
 
                             // Create metadata for dependency rendering with basic chain info
                             let metadata = crate::templating::renderer::RenderingMetadata {
@@ -108,6 +108,9 @@ pub(crate) trait DependencyExtractor: ContentExtractor {
         &self,
     ) -> &Arc<std::sync::Mutex<HashMap<String, BTreeMap<String, crate::manifest::DependencySpec>>>>;
 
+    /// Get the frontmatter key that declares dependencies
+    fn dependency_field(&self) -> &str;
+
     /// Extract custom dependency names from a resource's frontmatter.
     ///
     /// Parses the resource file to extract the `dependencies` declaration with `name:` fields
@@ -220,120 +223,108 @@ pub(crate) trait DependencyExtractor: ContentExtractor {
                 "templating_dependencies",
             )?;
 
-            // Use templated parsing to handle conditional blocks ({% if %}) in frontmatter
-            if let Ok(doc) = crate::markdown::MarkdownDocument::parse_with_templating(
+            // Use templated parsing to handle conditional blocks ({% if %}) in frontmatter,
+            // merging root-level dependencies (from the configured key) and agpm.dependencies
+            if let Ok(dependency_metadata) = crate::metadata::MetadataExtractor::extract_with_field(
+                &source_path,
                 &content,
                 Some(resource.variant_inputs.json()),
-                Some(&source_path),
+                None,
+                self.dependency_field(),
             ) {
-                // Extract dependencies from parsed metadata
-                if let Some(markdown_metadata) = &doc.metadata {
-                    // Convert MarkdownMetadata to DependencyMetadata
-                    // Merge both root-level dependencies and agpm.dependencies
-                    let dependency_metadata = crate::manifest::DependencyMetadata::new(
-                        markdown_metadata.dependencies.clone(),
-                        markdown_metadata.get_agpm_metadata(),
-                    );
-
-                    if let Some(deps_map) = dependency_metadata.get_dependencies() {
-                        // Process each resource type (agents, snippets, commands, etc.)
-                        for (resource_type_str, deps_array) in deps_map {
-                            // Convert frontmatter type to lockfile type (singular)
-                            let Some(resource_type) =
-                                crate::core::ResourceType::from_frontmatter_str(
-                                    resource_type_str.as_str(),
-                                )
-                            else {
-                                continue; // Skip unknown types
-                            };
-                            let lockfile_type = resource_type.to_string();
+                if let Some(deps_map) = dependency_metadata.get_dependencies() {
+                    // Process each resource type (agents, snippets, commands, etc.)
+                    for (resource_type_str, deps_array) in deps_map {
+                        // Convert frontmatter type to lockfile type (singular)
+                        let Some(resource_type) = crate::core::ResourceType::from_frontmatter_str(
+                            resource_type_str.as_str(),
+                        ) else {
+                            continue; // Skip unknown types
+                        };
+                        let lockfile_type = resource_type.to_string();
 
-                            // Get lockfile entries for this type only (O(1) lookup instead of O(n) iteration)
-                            let type_entries = match lockfile_lookup.get(&lockfile_type) {
-                                Some(entries) => entries,
-                                None => continue, // No lockfile deps of this type
-                            };
+                        // Get lockfile entries for this type only (O(1) lookup instead of O(n) iteration)
+                        let type_entries = match lockfile_lookup.get(&lockfile_type) {
+                            Some(entries) => entries,
+                            None => continue, // No lockfile deps of this type
+                        };
 
-                            // deps_array is Vec<DependencySpec>
-                            for dep_spec in deps_array {
-                                let path = &dep_spec.path;
-                                if let Some(custom_name) = &dep_spec.name {
-                                    // Extract basename from the path (without extension)
-                                    let basename = std::path::Path::new(path)
-                                        .file_stem()
-                                        .and_then(|s| s.to_str())
-                                        .unwrap_or(path);
-
-                                    tracing::info!(
-                                        "Found custom name '{}' for path '{}' (basename: '{}') in resource '{}'",
-                                        custom_name,
-                                        path,
-                                        basename,
-                                        resource.name
-                                    );
-
-                                    // Check if basename has template variables
-                                    if basename.contains("{{") {
-                                        // Template variable in basename - try suffix matching
-                                        // e.g., "{{ agpm.project.language }}-best-practices" -> "-best-practices"
-                                        if let Some(static_suffix_start) = basename.find("}}") {
-                                            let static_suffix =
-                                                &basename[static_suffix_start + 2..];
+                        // deps_array is Vec<DependencySpec>
+                        for dep_spec in deps_array {
+                            let path = &dep_spec.path;
+                            if let Some(custom_name) = &dep_spec.name {
+                                // Extract basename from the path (without extension)
+                                let basename = std::path::Path::new(path)
+                                    .file_stem()
+                                    .and_then(|s| s.to_str())
+                                    .unwrap_or(path);
 
+                                tracing::info!(
+                                    "Found custom name '{}' for path '{}' (basename: '{}') in resource '{}'",
+                                    custom_name,
+                                    path,
+                                    basename,
+                                    resource.name
+                                );
+
+                                // Check if basename has template variables
+                                if basename.contains("{{") {
+                                    // Template variable in basename - try suffix matching
+                                    // e.g., "{{ agpm.project.language }}-best-practices" -> "-best-practices"
+                                    if let Some(static_suffix_start) = basename.find("}}") {
+                                        let static_suffix = &basename[static_suffix_start + 2..];
+
+                                        tracing::info!(
+                                            "  Extracted suffix '{}' from templated basename '{}' in resource '{}'",
+                                            static_suffix,
+                                            basename,
+                                            resource.name
+                                        );
+
+                                        // Search for any lockfile basename ending with this suffix
+                                        let mut found_count = 0;
+                                        for (lockfile_basename, lockfile_dep_ref) in type_entries {
                                             tracing::info!(
-                                                "  Extracted suffix '{}' from templated basename '{}' in resource '{}'",
+                                                "    Checking lockfile basename '{}' against suffix '{}': match={}",
+                                                lockfile_basename,
                                                 static_suffix,
-                                                basename,
-                                                resource.name
+                                                lockfile_basename.ends_with(static_suffix)
                                             );
 
-                                            // Search for any lockfile basename ending with this suffix
-                                            let mut found_count = 0;
-                                            for (lockfile_basename, lockfile_dep_ref) in
-                                                type_entries
-                                            {
+                                            if lockfile_basename.ends_with(static_suffix) {
                                                 tracing::info!(
-                                                    "    Checking lockfile basename '{}' against suffix '{}': match={}",
-                                                    lockfile_basename,
-                                                    static_suffix,
-                                                    lockfile_basename.ends_with(static_suffix)
-                                                );
-
-                                                if lockfile_basename.ends_with(static_suffix) {
-                                                    tracing::info!(
-                                                        "  [MATCH] Adding custom name '{}' for lockfile entry '{}' (basename: '{}')",
-                                                        custom_name,
-                                                        lockfile_dep_ref,
-                                                        lockfile_basename
-                                                    );
-                                                    custom_names.insert(
-                                                        lockfile_dep_ref.clone(),
-                                                        custom_name.to_string(),
-                                                    );
-                                                    found_count += 1;
-                                                }
-                                            }
-
-                                            if found_count == 0 {
-                                                tracing::warn!(
-                                                    "  [NO MATCH] No lockfile entries found ending with suffix '{}' for custom name '{}' in resource '{}'",
-                                                    static_suffix,
+                                                    "  [MATCH] Adding custom name '{}' for lockfile entry '{}' (basename: '{}')",
                                                     custom_name,
-                                                    resource.name
+                                                    lockfile_dep_ref,
+                                                    lockfile_basename
                                                 );
-                                            }
-                                        }
-                                    } else {
-                                        // No template variables - exact basename match (O(n) but only within type)
-                                        for (lockfile_basename, lockfile_dep_ref) in type_entries {
-                                            if lockfile_basename == basename {
                                                 custom_names.insert(
                                                     lockfile_dep_ref.clone(),
                                                     custom_name.to_string(),
                                                 );
-                                                break; // Found exact match, no need to continue
+                                                found_count += 1;
                                             }
                                         }
+
+                                        if found_count == 0 {
+                                            tracing::warn!(
+                                                "  [NO MATCH] No lockfile entries found ending with suffix '{}' for custom name '{}' in resource '{}'",
+                                                static_suffix,
+                                                custom_name,
+                                                resource.name
+                                            );
+                                        }
+                                    }
+                                } else {
+                                    // No template variables - exact basename match (O(n) but only within type)
+                                    for (lockfile_basename, lockfile_dep_ref) in type_entries {
+                                        if lockfile_basename == basename {
+                                            custom_names.insert(
+                                                lockfile_dep_ref.clone(),
+                                                custom_name.to_string(),
+                                            );
+                                            break; // Found exact match, no need to continue
+                                        }
                                     }
                                 }
                             }
@@ -359,7 +350,7 @@ pub(crate) trait DependencyExtractor: ContentExtractor {
             // Parse JSON and extract dependencies field
             if let Ok(json_value) = serde_json::from_str::<serde_json::Value>(&templated_content) {
                 // Extract both root-level dependencies and agpm.dependencies
-                let root_deps = json_value.get("dependencies").and_then(|v| {
+                let root_deps = json_value.get(self.dependency_field()).and_then(|v| {
                     serde_json::from_value::<
                         BTreeMap<String, Vec<crate::manifest::DependencySpec>>,
                     >(v.clone())
@@ -552,68 +543,60 @@ pub(crate) trait DependencyExtractor: ContentExtractor {
                 "templating_dependencies",
             )?;
 
-            // Use templated parsing to handle conditional blocks ({% if %}) in frontmatter
-            if let Ok(doc) = crate::markdown::MarkdownDocument::parse_with_templating(
+            // Use templated parsing to handle conditional blocks ({% if %}) in frontmatter,
+            // merging root-level dependencies (from the configured key) and agpm.dependencies
+            if let Ok(dependency_metadata) = crate::metadata::MetadataExtractor::extract_with_field(
+                &source_path,
                 &content,
                 Some(resource.variant_inputs.json()),
-                Some(&source_path),
+                None,
+                self.dependency_field(),
             ) {
-                // Extract dependencies from parsed metadata
-                if let Some(markdown_metadata) = &doc.metadata {
-                    // Convert MarkdownMetadata to DependencyMetadata
-                    let dependency_metadata = crate::manifest::DependencyMetadata::new(
-                        markdown_metadata.dependencies.clone(),
-                        markdown_metadata.get_agpm_metadata(),
-                    );
-
-                    if let Some(deps_map) = dependency_metadata.get_dependencies() {
-                        // Process each resource type
-                        for (resource_type_str, deps_array) in deps_map {
-                            // Convert frontmatter type to ResourceType
-                            let Some(resource_type) =
-                                crate::core::ResourceType::from_frontmatter_str(
-                                    resource_type_str.as_str(),
+                if let Some(deps_map) = dependency_metadata.get_dependencies() {
+                    // Process each resource type
+                    for (resource_type_str, deps_array) in deps_map {
+                        // Convert frontmatter type to ResourceType
+                        let Some(resource_type) = crate::core::ResourceType::from_frontmatter_str(
+                            resource_type_str.as_str(),
+                        ) else {
+                            continue;
+                        };
+
+                        // Store each DependencySpec with its lockfile reference as key
+                        for dep_spec in deps_array {
+                            // Canonicalize the frontmatter path to match lockfile format
+                            // Frontmatter paths are relative to the resource file itself
+                            // We need to resolve them relative to source root (not filesystem paths!)
+                            let canonical_path =
+                                canonicalize_dep_path(&dep_spec.path, &resource.path);
+
+                            // Remove extension to match lockfile format
+                            let normalized_path = std::path::Path::new(&canonical_path)
+                                .with_extension("")
+                                .to_string_lossy()
+                                .to_string();
+
+                            // Build the dependency reference string WITHOUT version
+                            // Cache key should only use path to match any version of this dependency
+                            // Version is for resolution purposes, not for identifying the spec
+                            let dep_ref = if let Some(ref src) = resource.source {
+                                LockfileDependencyRef::git(
+                                    src.clone(),
+                                    resource_type,
+                                    normalized_path,
+                                    None, // No version in cache key
+                                )
+                                .to_string()
+                            } else {
+                                LockfileDependencyRef::local(
+                                    resource_type,
+                                    normalized_path,
+                                    None, // No version in cache key
                                 )
-                            else {
-                                continue;
+                                .to_string()
                             };
 
-                            // Store each DependencySpec with its lockfile reference as key
-                            for dep_spec in deps_array {
-                                // Canonicalize the frontmatter path to match lockfile format
-                                // Frontmatter paths are relative to the resource file itself
-                                // We need to resolve them relative to source root (not filesystem paths!)
-                                let canonical_path =
-                                    canonicalize_dep_path(&dep_spec.path, &resource.path);
-
-                                // Remove extension to match lockfile format
-                                let normalized_path = std::path::Path::new(&canonical_path)
-                                    .with_extension("")
-                                    .to_string_lossy()
-                                    .to_string();
-
-                                // Build the dependency reference string WITHOUT version
-                                // Cache key should only use path to match any version of this dependency
-                                // Version is for resolution purposes, not for identifying the spec
-                                let dep_ref = if let Some(ref src) = resource.source {
-                                    LockfileDependencyRef::git(
-                                        src.clone(),
-                                        resource_type,
-                                        normalized_path,
-                                        None, // No version in cache key
-                                    )
-                                    .to_string()
-                                } else {
-                                    LockfileDependencyRef::local(
-                                        resource_type,
-                                        normalized_path,
-                                        None, // No version in cache key
-                                    )
-                                    .to_string()
-                                };
-
-                                dependency_specs.insert(dep_ref, dep_spec.clone());
-                            }
+                            dependency_specs.insert(dep_ref, dep_spec.clone());
                         }
                     }
                 }
@@ -635,7 +618,7 @@ pub(crate) trait DependencyExtractor: ContentExtractor {
 
             if let Ok(json_value) = serde_json::from_str::<serde_json::Value>(&templated_content) {
                 // Extract both root-level dependencies and agpm.dependencies
-                let root_deps = json_value.get("dependencies").and_then(|v| {
+                let root_deps = json_value.get(self.dependency_field()).and_then(|v| {
                     serde_json::from_value::<
                         BTreeMap<String, Vec<crate::manifest::DependencySpec>>,
                     >(v.clone())
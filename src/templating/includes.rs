@@ -0,0 +1,158 @@
+//! Resource includes for templates.
+//!
+//! A templated resource can embed the body of another resource from the same
+//! manifest with `{% include "name" %}`, where `name` is the manifest name of
+//! the included resource. This is how a shared preamble kept as a snippet ends
+//! up in every agent that needs it.
+//!
+//! Included resources are rendered with their own template context before
+//! being embedded, and may include other resources in turn. Because each
+//! include is rendered on demand from its source, installation order does not
+//! matter. Include cycles are reported as errors naming the full chain.
+
+use anyhow::{Context as _, Result, bail};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::lockfile::{LockedResource, ResourceId};
+
+use super::dependencies::DependencyExtractor;
+use super::renderer::{RenderingMetadata, TemplateRenderer};
+
+/// Matches `{% include "name" %}` tags, with optional whitespace control.
+pub(crate) static INCLUDE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\{%-?\s*include\s+["']([^"']+)["']\s*-?%\}"#).expect("valid include pattern")
+});
+
+/// Names of the resources included by `content`, in order of first use.
+pub(crate) fn include_names(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for captures in INCLUDE_PATTERN.captures_iter(content) {
+        let name = &captures[1];
+        if !names.iter().any(|existing| existing == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Render every resource included by `content`, keyed by include name.
+///
+/// `current` is the resource whose content is being rendered. Names resolve
+/// to lockfile entries by manifest name, preferring entries for the same tool
+/// as `current`.
+///
+/// # Errors
+///
+/// Returns an error if an included name is not in the lockfile, if its
+/// content cannot be read or rendered, or if the includes form a cycle.
+pub(crate) async fn render_includes<T: DependencyExtractor>(
+    extractor: &T,
+    current: &LockedResource,
+    content: &str,
+    max_content_file_size: Option<u64>,
+) -> Result<HashMap<String, String>> {
+    let mut stack = vec![current.display_name().to_string()];
+    render_includes_with_stack(extractor, current, content, max_content_file_size, &mut stack).await
+}
+
+async fn render_includes_with_stack<T: DependencyExtractor>(
+    extractor: &T,
+    current: &LockedResource,
+    content: &str,
+    max_content_file_size: Option<u64>,
+    stack: &mut Vec<String>,
+) -> Result<HashMap<String, String>> {
+    let mut rendered = HashMap::new();
+
+    for name in include_names(content) {
+        if stack.contains(&name) {
+            bail!("Include cycle detected: {} -> {}", stack.join(" -> "), name);
+        }
+
+        let lockfile = extractor.lockfile();
+        let candidates: Vec<&LockedResource> =
+            lockfile.all_resources().into_iter().filter(|r| r.lookup_name() == name).collect();
+        let Some(included) = candidates
+            .iter()
+            .find(|r| r.tool == current.tool)
+            .or_else(|| candidates.first())
+            .copied()
+        else {
+            bail!(
+                "Resource '{}' includes '{}', which is not a dependency in the manifest",
+                current.display_name(),
+                name
+            );
+        };
+
+        let Some((body, has_templating)) = extractor.extract_content(included).await else {
+            bail!("Failed to read included resource '{}' ({})", name, included.path);
+        };
+
+        // Only templated resources are rendered, like dependency content
+        let body = if has_templating {
+            stack.push(name.clone());
+            let result =
+                Box::pin(render_included(extractor, included, &body, max_content_file_size, stack))
+                    .await;
+            stack.pop();
+            result.with_context(|| {
+                format!("Failed to render '{}' included by '{}'", name, current.display_name())
+            })?
+        } else {
+            body
+        };
+
+        rendered.insert(name, body);
+    }
+
+    Ok(rendered)
+}
+
+/// Render the body of an included resource with its own context and includes.
+async fn render_included<T: DependencyExtractor>(
+    extractor: &T,
+    resource: &LockedResource,
+    body: &str,
+    max_content_file_size: Option<u64>,
+    stack: &mut Vec<String>,
+) -> Result<String> {
+    let includes =
+        render_includes_with_stack(extractor, resource, body, max_content_file_size, stack).await?;
+    let context = extractor
+        .build_context_with_visited(
+            &ResourceId::from_resource(resource),
+            resource.variant_inputs.json(),
+            &mut Default::default(),
+        )
+        .await?;
+
+    let metadata = RenderingMetadata {
+        resource_name: resource.name.clone(),
+        resource_type: resource.resource_type,
+        dependency_chain: vec![],
+        source_path: Some(resource.path.clone().into()),
+        depth: stack.len(),
+    };
+    let mut renderer =
+        TemplateRenderer::new(true, extractor.project_dir().clone(), max_content_file_size)?
+            .with_includes(includes);
+    Ok(renderer.render_template(body, &context, Some(&metadata))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_include_names() {
+        let content = r#"{% include "preamble" %}
+Body
+{%- include 'footer' -%}
+{% include "preamble" %}
+{{ "include" }}"#;
+        assert_eq!(include_names(content), ["preamble", "footer"]);
+    }
+}
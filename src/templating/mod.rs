@@ -22,10 +22,17 @@
 //!
 //! - `content`: Read project-specific files (e.g., `{{ 'docs/guide.md' | content }}`)
 //!
+//! # Resource Includes
+//!
+//! `{% include "name" %}` embeds the rendered body of another resource from the
+//! manifest, looked up by its manifest name. Included resources are rendered with
+//! their own context, may include others in turn, and include cycles are errors.
+//! See [`includes`] for details.
+//!
 //! # Syntax Restrictions
 //!
 //! For security and safety, the following Tera features are disabled:
-//! - `{% include %}` tags (no file system access)
+//! - `{% include %}` of arbitrary templates (only manifest resources can be included)
 //! - `{% extends %}` tags (no template inheritance)
 //! - `{% import %}` tags (no external template imports)
 //! - Custom functions that access the file system or network (except content filter)
@@ -46,6 +53,7 @@ pub mod context;
 pub mod dependencies;
 pub mod error;
 pub mod filters;
+pub mod includes;
 pub mod renderer;
 pub mod utils;
 
@@ -12,6 +12,7 @@ use tera::{Context as TeraContext, Tera};
 
 use super::error::{ErrorLocation, TemplateError};
 use super::filters;
+use super::includes::INCLUDE_PATTERN;
 use crate::core::ResourceType;
 
 /// Maximum allowed Levenshtein distance as a percentage of target length for suggestions.
@@ -49,7 +50,8 @@ pub struct DependencyChainEntry {
 /// # Security
 ///
 /// The renderer is configured with security restrictions:
-/// - No file system access via includes/extends (except content filter)
+/// - No file system access via includes/extends (except content filter);
+///   `{% include %}` only embeds pre-rendered resources from the manifest
 /// - No network access
 /// - Sandboxed template execution
 /// - Custom functions are carefully vetted
@@ -61,6 +63,8 @@ pub struct TemplateRenderer {
     project_dir: PathBuf,
     /// Maximum file size for content filter
     max_content_file_size: Option<u64>,
+    /// Pre-rendered content of included resources, keyed by include name
+    includes: HashMap<String, String>,
 }
 
 impl TemplateRenderer {
@@ -89,9 +93,50 @@ impl TemplateRenderer {
             enabled,
             project_dir,
             max_content_file_size,
+            includes: HashMap::new(),
         })
     }
 
+    /// Provide the rendered content for `{% include "name" %}` tags.
+    ///
+    /// Includes are resolved ahead of rendering (see
+    /// [`crate::templating::includes`]) and embedded verbatim, so their
+    /// content is not rendered a second time.
+    #[must_use]
+    pub fn with_includes(mut self, includes: HashMap<String, String>) -> Self {
+        self.includes = includes;
+        self
+    }
+
+    /// Replace include tags with placeholders for their pre-rendered content.
+    ///
+    /// Returns the modified content and a map from placeholder to content, or
+    /// the name of the first include that has no rendered content.
+    fn protect_includes(&self, content: &str) -> Result<(String, HashMap<String, String>), String> {
+        let mut placeholders = HashMap::new();
+        let mut missing = None;
+
+        let protected = INCLUDE_PATTERN.replace_all(content, |captures: &regex::Captures| {
+            let name = &captures[1];
+            match self.includes.get(name) {
+                Some(included) => {
+                    let placeholder_id = format!("__AGPM_INCLUDE_{}__", placeholders.len());
+                    placeholders.insert(placeholder_id.clone(), included.clone());
+                    placeholder_id
+                }
+                None => {
+                    missing.get_or_insert_with(|| name.to_string());
+                    captures[0].to_string()
+                }
+            }
+        });
+
+        match missing {
+            Some(name) => Err(name),
+            None => Ok((protected.into_owned(), placeholders)),
+        }
+    }
+
     /// Protect literal blocks from template rendering by replacing them with placeholders.
     ///
     /// This method scans for ```literal fenced code blocks and replaces them with
@@ -310,6 +355,18 @@ impl TemplateRenderer {
         // Step 1: Protect literal blocks before any rendering
         let (protected_content, placeholders) = self.protect_literal_blocks(template_content);
 
+        // Step 1b: Swap include tags for the included resources' rendered content
+        let (protected_content, included) =
+            self.protect_includes(&protected_content).map_err(|name| {
+                TemplateError::SyntaxError {
+                    message: format!(
+                        "Included resource '{}' was not resolved before rendering",
+                        name
+                    ),
+                    location: Box::new(Self::build_error_location(metadata, None, None)),
+                }
+            })?;
+
         // Log the template context for debugging
         tracing::debug!("Rendering template with context");
         Self::log_context_as_kv(context);
@@ -337,7 +394,11 @@ impl TemplateRenderer {
 
         tracing::debug!("Template rendering complete");
 
-        // Step 3: Restore literal blocks after rendering is complete
+        // Step 3: Restore includes and literal blocks after rendering is complete
+        let mut rendered = rendered;
+        for (placeholder_id, content) in included {
+            rendered = rendered.replace(&placeholder_id, &content);
+        }
         let restored = self.restore_literal_blocks(&rendered, placeholders);
 
         // Return restored content (literal blocks have been restored)
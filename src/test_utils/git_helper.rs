@@ -130,6 +130,15 @@ impl TestGit {
         Ok(())
     }
 
+    /// Point `ref_name` at `target`, e.g. to simulate a pull-request ref
+    pub fn update_ref(&self, ref_name: &str, target: &str) -> Result<()> {
+        self.run_git_command(
+            &["update-ref", ref_name, target],
+            &format!("Failed to update ref: {}", ref_name),
+        )?;
+        Ok(())
+    }
+
     /// Create and checkout a branch
     pub fn create_branch(&self, branch_name: &str) -> Result<()> {
         self.run_git_command(
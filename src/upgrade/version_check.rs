@@ -7,6 +7,7 @@ use tracing::{debug, info};
 
 use crate::config::GlobalConfig;
 use crate::upgrade::SelfUpdater;
+use crate::utils::terminal::PACKAGE;
 
 /// Cached version information with notification tracking.
 ///
@@ -355,7 +356,7 @@ impl VersionChecker {
 
         eprintln!();
         eprintln!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
-        eprintln!("{} A new version of AGPM is available!", "📦".bright_cyan());
+        eprintln!("{PACKAGE}A new version of AGPM is available!");
         eprintln!();
         eprintln!("  Current version: {}", current_version.yellow());
         eprintln!("  Latest version:  {}", latest_version.green().bold());
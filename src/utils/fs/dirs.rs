@@ -162,8 +162,9 @@ pub fn ensure_dir_exists(path: &Path) -> Result<()> {
 pub fn copy_dir(src: &Path, dst: &Path) -> Result<()> {
     ensure_dir(dst)?;
 
-    for entry in
-        fs::read_dir(src).with_context(|| format!("Failed to read directory: {}", src.display()))?
+    let safe_src = crate::utils::platform::windows_long_path(src);
+    for entry in fs::read_dir(&safe_src)
+        .with_context(|| format!("Failed to read directory: {}", src.display()))?
     {
         let entry = entry?;
         let file_type = entry.file_type()?;
@@ -173,7 +174,8 @@ pub fn copy_dir(src: &Path, dst: &Path) -> Result<()> {
         if file_type.is_dir() {
             copy_dir(&src_path, &dst_path)?;
         } else if file_type.is_file() {
-            fs::copy(&src_path, &dst_path).with_context(|| {
+            let safe_dst = crate::utils::platform::windows_long_path(&dst_path);
+            fs::copy(&src_path, &safe_dst).with_context(|| {
                 format!("Failed to copy file from {} to {}", src_path.display(), dst_path.display())
             })?;
         }
@@ -228,8 +230,9 @@ pub fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
 /// - **Unix**: Respects file permissions
 /// - **All platforms**: Atomic operation where supported by filesystem
 pub fn remove_dir_all(path: &Path) -> Result<()> {
-    if path.exists() {
-        fs::remove_dir_all(path)
+    let safe_path = crate::utils::platform::windows_long_path(path);
+    if safe_path.exists() {
+        fs::remove_dir_all(&safe_path)
             .with_context(|| format!("Failed to remove directory: {}", path.display()))?;
     }
     Ok(())
@@ -387,6 +390,34 @@ mod tests {
         assert!(!dir.exists());
     }
 
+    #[cfg(windows)]
+    #[test]
+    fn test_long_path_operations() {
+        let temp = tempdir().unwrap();
+        let mut deep = temp.path().to_path_buf();
+        for i in 0..12 {
+            deep = deep.join(format!("deeply_nested_install_directory_{i:02}"));
+        }
+        assert!(deep.as_os_str().len() > 260);
+
+        let src = deep.join("src");
+        ensure_dir(&src.join("subdir")).unwrap();
+        crate::utils::fs::atomic_write(&src.join("subdir/file.md"), b"# Deep").unwrap();
+
+        let dst = deep.join("dst");
+        copy_dir(&src, &dst).unwrap();
+
+        let copied = dst.join("subdir/file.md");
+        assert_eq!(
+            crate::utils::fs::calculate_checksum(&copied).unwrap(),
+            crate::utils::fs::calculate_checksum(&src.join("subdir/file.md")).unwrap()
+        );
+        assert!(crate::lockfile::LockFile::compute_checksum(&copied).is_ok());
+
+        remove_dir_all(&dst).unwrap();
+        assert!(!crate::utils::platform::windows_long_path(&dst).exists());
+    }
+
     #[test]
     fn test_remove_dir_all_nonexistent() {
         let temp = tempdir().unwrap();
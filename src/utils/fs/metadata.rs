@@ -193,7 +193,7 @@ pub async fn get_directory_size(path: &Path) -> Result<u64> {
 /// - [`calculate_checksums_parallel`] for batch processing
 /// - [`hex`] crate for hexadecimal encoding
 pub fn calculate_checksum(path: &Path) -> Result<String> {
-    let content = fs::read(path)
+    let content = fs::read(crate::utils::platform::windows_long_path(path))
         .with_context(|| format!("Failed to read file for checksum: {}", path.display()))?;
 
     let mut hasher = Sha256::new();
@@ -103,7 +103,11 @@ pub async fn copy_files_parallel(sources_and_destinations: &[(PathBuf, PathBuf)]
             }
 
             // Copy file
-            fs::copy(&src, &dst).with_context(|| {
+            fs::copy(
+                crate::utils::platform::windows_long_path(&src),
+                crate::utils::platform::windows_long_path(&dst),
+            )
+            .with_context(|| {
                 format!("Failed to copy file from {} to {}", src.display(), dst.display())
             })?;
 
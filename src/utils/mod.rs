@@ -7,9 +7,11 @@
 //! # Modules
 //!
 //! - [`fs`] - File system operations with atomic writes and safe copying
+//! - [`log_file`] - Rotating, credential-redacting log files for `--log-file`
 //! - [`manifest_utils`] - Utilities for loading and validating manifests
 //! - [`platform`] - Platform-specific helpers and path resolution
 //! - [`progress`] - Multi-phase progress tracking for long-running operations
+//! - [`terminal`] - Color and emoji control for terminal output
 //!
 //! # Cross-Platform Considerations
 //!
@@ -43,11 +45,13 @@ use anyhow::Context;
 use std::path::{Path, PathBuf};
 
 pub mod fs;
+pub mod log_file;
 pub mod manifest_utils;
 pub mod path_validation;
 pub mod platform;
 pub mod progress;
 pub mod security;
+pub mod terminal;
 pub use fs::{
     atomic_write, compare_file_times, copy_dir, create_temp_file, ensure_dir,
     file_exists_and_readable, get_modified_time, normalize_path, read_json_file, read_text_file,
@@ -59,8 +63,8 @@ pub use manifest_utils::{
 };
 pub use path_validation::{
     ensure_directory_exists, ensure_within_directory, find_project_root, safe_canonicalize,
-    safe_relative_path, sanitize_file_name, validate_no_traversal, validate_project_path,
-    validate_resource_path,
+    safe_relative_path, sanitize_file_name, validate_install_path_length, validate_no_traversal,
+    validate_project_path, validate_resource_path,
 };
 pub use platform::{
     compute_relative_install_path, get_git_command, get_home_dir, is_windows,
@@ -199,6 +199,60 @@ pub fn validate_resource_path(
     Ok(full_path)
 }
 
+/// Longest file or directory name supported by common filesystems.
+pub const MAX_PATH_COMPONENT_LEN: usize = 255;
+
+/// Longest absolute path AGPM can write to.
+///
+/// On Windows this is the extended-length limit reached through the `\\?\`
+/// prefix applied by [`crate::utils::platform::windows_long_path`], rather
+/// than the legacy 260 character `MAX_PATH`.
+#[cfg(windows)]
+pub const MAX_INSTALL_PATH_LEN: usize = 32_767;
+
+/// Longest absolute path AGPM can write to.
+#[cfg(not(windows))]
+pub const MAX_INSTALL_PATH_LEN: usize = 4_096;
+
+/// Validates that an install target is short enough to be written.
+///
+/// # Arguments
+/// * `project_dir` - The project root directory
+/// * `installed_at` - The install path, relative to the project root
+///
+/// # Errors
+/// Returns an error if a single path component exceeds
+/// [`MAX_PATH_COMPONENT_LEN`] or the full path exceeds [`MAX_INSTALL_PATH_LEN`]
+pub fn validate_install_path_length(project_dir: &Path, installed_at: &str) -> Result<()> {
+    let full_path = project_dir.join(installed_at);
+
+    if let Some(component) =
+        full_path.components().find(|c| c.as_os_str().len() > MAX_PATH_COMPONENT_LEN)
+    {
+        return Err(anyhow!(
+            "Install path '{}' has a {}-character component '{}', but the limit is {}.\n\
+             Use a shorter `filename` or `target` for this dependency.",
+            installed_at,
+            component.as_os_str().len(),
+            component.as_os_str().to_string_lossy(),
+            MAX_PATH_COMPONENT_LEN
+        ));
+    }
+
+    let length = full_path.as_os_str().len();
+    if length > MAX_INSTALL_PATH_LEN {
+        return Err(anyhow!(
+            "Install path '{}' is {} characters long, but the limit is {}.\n\
+             Move the project to a shallower directory or use a shorter `target` or `filename`.",
+            full_path.display(),
+            length,
+            MAX_INSTALL_PATH_LEN
+        ));
+    }
+
+    Ok(())
+}
+
 /// Sanitizes a file name to remove potentially dangerous characters.
 ///
 /// # Arguments
@@ -262,6 +316,20 @@ mod tests {
         assert!(validate_no_traversal(Path::new("../../escape")).is_err());
     }
 
+    #[test]
+    fn test_validate_install_path_length() {
+        let project_dir = Path::new("project");
+        assert!(validate_install_path_length(project_dir, ".claude/agents/agpm/a.md").is_ok());
+
+        let long_name = format!(".claude/agents/{}.md", "a".repeat(MAX_PATH_COMPONENT_LEN));
+        let err = validate_install_path_length(project_dir, &long_name).unwrap_err();
+        assert!(err.to_string().contains("component"));
+
+        let deep = vec!["nested"; MAX_INSTALL_PATH_LEN / 7 + 1].join("/");
+        let err = validate_install_path_length(project_dir, &deep).unwrap_err();
+        assert!(err.to_string().contains("characters long"));
+    }
+
     #[test]
     fn test_sanitize_file_name() {
         assert_eq!(sanitize_file_name("valid-name_123.md"), "valid-name_123.md");
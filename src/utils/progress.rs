@@ -122,6 +122,15 @@ pub struct MultiPhaseProgress {
     active_window: Arc<Mutex<ActiveWindow>>,
 }
 
+/// Progress bar template placeholder, colored unless `--color` disabled styling.
+fn bar_placeholder() -> &'static str {
+    if crate::utils::terminal::is_styled() {
+        "{bar:40.cyan/blue}"
+    } else {
+        "{bar:40}"
+    }
+}
+
 impl MultiPhaseProgress {
     /// Create a new multi-phase progress manager
     pub fn new(enabled: bool) -> Self {
@@ -134,6 +143,11 @@ impl MultiPhaseProgress {
         }
     }
 
+    /// Whether progress output is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
     /// Start a new phase with a spinner
     pub fn start_phase(&self, phase: InstallationPhase, message: Option<&str>) {
         if !self.enabled {
@@ -188,7 +202,7 @@ impl MultiPhaseProgress {
 
         // Configure progress bar style
         let style = IndicatifStyle::default_bar()
-            .template("{msg} [{bar:40.cyan/blue}] {pos}/{len}")
+            .template(&format!("{{msg}} [{}] {{pos}}/{{len}}", bar_placeholder()))
             .unwrap()
             .progress_chars("=>-");
 
@@ -482,6 +496,21 @@ impl MultiPhaseProgress {
         }
     }
 
+    /// Replace the text of the active window's counter, e.g. with an aggregated summary.
+    pub fn set_counter_message(&self, message: &str) {
+        if !self.enabled {
+            return;
+        }
+
+        // Use try_lock to avoid blocking async executors - UI updates are best-effort
+        let Ok(window) = self.active_window.try_lock() else {
+            return;
+        };
+        if let Some(ref counter) = window.counter_bar {
+            counter.set_message(message.to_string());
+        }
+    }
+
     /// Mark a resource as complete and update progress counter.
     /// This clears the resource from its slot and updates the overall counter.
     ///
@@ -589,7 +618,7 @@ impl MultiPhaseProgress {
 
         let pb = self.multi.add(IndicatifBar::new(total));
         let style = IndicatifStyle::default_bar()
-            .template("  {msg} [{bar:40.cyan/blue}] {pos}/{len}")
+            .template(&format!("  {{msg}} [{}] {{pos}}/{{len}}", bar_placeholder()))
             .unwrap()
             .progress_chars("=>-");
         pb.set_style(style);
@@ -631,6 +660,7 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: Some(true),
             approximate_token_count: None,
+            deprecated: false,
         }
     }
 
@@ -0,0 +1,175 @@
+//! Color and emoji control for terminal output.
+//!
+//! Whether output is styled is decided once per process from the global
+//! `--color` flag:
+//!
+//! - `always`: colors and emoji are always emitted
+//! - `never`: plain text only
+//! - `auto` (default): styled unless `NO_COLOR` is set or stdout is not a
+//!   terminal, so redirected output and CI logs stay free of escape codes
+//!
+//! Colors are controlled through the `colored` override, so every
+//! `.green()`-style call site honors the choice. Emoji are written through the
+//! [`Emoji`] constants below, which fall back to plain text (or nothing) when
+//! styling is off. The `✓`, `✗` and `⚠` status markers are ordinary text and
+//! are kept in plain output.
+
+use std::fmt;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// When to use colors and emoji, selected with `--color`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Style output only when stdout is a terminal and `NO_COLOR` is unset
+    #[default]
+    Auto,
+    /// Always style output
+    Always,
+    /// Never style output
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether output should be styled for this choice.
+    ///
+    /// `no_color` is the value of the `NO_COLOR` environment variable; like
+    /// the convention at <https://no-color.org>, an empty value is ignored.
+    #[must_use]
+    pub fn should_style(self, no_color: Option<&str>, stdout_is_terminal: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => no_color.is_none_or(str::is_empty) && stdout_is_terminal,
+        }
+    }
+}
+
+static STYLED: AtomicBool = AtomicBool::new(true);
+
+/// Apply `choice` to all output of this process.
+///
+/// On Windows, ANSI support is enabled in the console when styling is on.
+pub fn set_color_choice(choice: ColorChoice) {
+    let no_color = std::env::var("NO_COLOR").ok();
+    let styled = choice.should_style(no_color.as_deref(), std::io::stdout().is_terminal());
+
+    STYLED.store(styled, Ordering::Relaxed);
+    colored::control::set_override(styled);
+
+    #[cfg(windows)]
+    if styled {
+        colored::control::set_virtual_terminal(true).ok();
+    }
+}
+
+/// Whether output is currently styled with colors and emoji.
+#[must_use]
+pub fn is_styled() -> bool {
+    STYLED.load(Ordering::Relaxed)
+}
+
+/// An emoji prefix with a plain-text fallback for unstyled output.
+///
+/// Both forms include their trailing spacing, so call sites read
+/// `println!("{SEARCH}Scanning...")`.
+#[derive(Debug, Clone, Copy)]
+pub struct Emoji {
+    styled: &'static str,
+    plain: &'static str,
+}
+
+impl Emoji {
+    /// The text to print for the current color choice.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        if is_styled() {
+            self.styled
+        } else {
+            self.plain
+        }
+    }
+}
+
+impl fmt::Display for Emoji {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Successful completion.
+pub const SUCCESS: Emoji = Emoji {
+    styled: "✅ ",
+    plain: "✓ ",
+};
+/// Failure.
+pub const FAILURE: Emoji = Emoji {
+    styled: "❌ ",
+    plain: "✗ ",
+};
+/// Warning.
+pub const WARNING: Emoji = Emoji {
+    styled: "⚠️  ",
+    plain: "⚠ ",
+};
+/// Informational note.
+pub const INFO: Emoji = Emoji {
+    styled: "ℹ️  ",
+    plain: "",
+};
+/// Scanning or checking.
+pub const SEARCH: Emoji = Emoji {
+    styled: "🔍 ",
+    plain: "",
+};
+/// Packages and installation.
+pub const PACKAGE: Emoji = Emoji {
+    styled: "📦 ",
+    plain: "",
+};
+/// Removal.
+pub const TRASH: Emoji = Emoji {
+    styled: "🗑️  ",
+    plain: "",
+};
+/// Nothing to do.
+pub const SPARKLES: Emoji = Emoji {
+    styled: "✨ ",
+    plain: "",
+};
+/// Tips and suggestions.
+pub const TIP: Emoji = Emoji {
+    styled: "💡 ",
+    plain: "",
+};
+/// Starting a long operation.
+pub const ROCKET: Emoji = Emoji {
+    styled: "🚀 ",
+    plain: "",
+};
+/// Completion of a multi-step operation.
+pub const PARTY: Emoji = Emoji {
+    styled: "🎉 ",
+    plain: "",
+};
+/// Resolution in progress.
+pub const REFRESH: Emoji = Emoji {
+    styled: "🔄 ",
+    plain: "",
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_style() {
+        assert!(ColorChoice::Always.should_style(Some("1"), false));
+        assert!(!ColorChoice::Never.should_style(None, true));
+
+        assert!(ColorChoice::Auto.should_style(None, true));
+        assert!(ColorChoice::Auto.should_style(Some(""), true));
+        assert!(!ColorChoice::Auto.should_style(Some("1"), true));
+        assert!(!ColorChoice::Auto.should_style(None, false));
+    }
+}
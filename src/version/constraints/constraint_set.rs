@@ -286,6 +286,33 @@ impl ConstraintSet {
         candidates.first().copied()
     }
 
+    /// Find the lowest version that satisfies all constraints.
+    ///
+    /// The counterpart of [`find_best_match`](Self::find_best_match) used by
+    /// `--resolution=lowest`. Prereleases are filtered the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use agpm_cli::version::constraints::{ConstraintSet, VersionConstraint};
+    /// use semver::Version;
+    ///
+    /// let mut set = ConstraintSet::new();
+    /// set.add(VersionConstraint::parse("^1.0.0")?)?;
+    ///
+    /// let versions = vec![Version::parse("0.9.0")?, Version::parse("1.0.0")?, Version::parse("1.5.0")?];
+    /// assert_eq!(set.find_lowest_match(&versions), Some(&Version::parse("1.0.0")?));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    #[must_use]
+    pub fn find_lowest_match<'a>(&self, versions: &'a [Version]) -> Option<&'a Version> {
+        let allows_prerelease = self.allows_prerelease();
+        versions
+            .iter()
+            .filter(|v| self.satisfies(v) && (allows_prerelease || v.pre.is_empty()))
+            .min()
+    }
+
     /// Check if any constraint in this set allows prerelease versions.
     ///
     /// This method determines the prerelease policy for the entire constraint set.
@@ -392,40 +419,30 @@ impl ConstraintSet {
                         return true;
                     }
                 }
-                (VersionConstraint::GitRef(r1), VersionConstraint::GitRef(r2)) => {
-                    if r1 != r2 {
-                        return true;
-                    }
+                (VersionConstraint::GitRef(r1), VersionConstraint::GitRef(r2)) if r1 != r2 => {
+                    return true;
                 }
                 // For Requirement constraints, different prefixes = no conflict
                 (
                     VersionConstraint::Exact {
-                        prefix: p1,
                         ..
                     }
                     | VersionConstraint::Requirement {
-                        prefix: p1,
                         ..
                     },
                     VersionConstraint::Requirement {
-                        prefix: p2,
                         ..
                     },
                 )
                 | (
                     VersionConstraint::Requirement {
-                        prefix: p1,
                         ..
                     },
                     VersionConstraint::Exact {
-                        prefix: p2,
                         ..
                     },
                 ) => {
-                    // Different prefixes = different namespaces, no conflict
-                    if p1 != p2 {
-                        // Continue to next pair
-                    }
+                    // Different prefixes = different namespaces, no conflict.
                     // Same prefix - could do more sophisticated conflict detection here
                 }
                 _ => {
@@ -476,6 +493,23 @@ mod tests {
         assert_eq!(best, &Version::parse("1.5.0").unwrap());
     }
 
+    #[test]
+    fn test_find_lowest_match() {
+        let mut set = ConstraintSet::new();
+        set.add(VersionConstraint::parse("^1.0.0").unwrap()).unwrap();
+
+        let versions = vec![
+            Version::parse("2.0.0").unwrap(),
+            Version::parse("1.5.0").unwrap(),
+            Version::parse("1.0.0").unwrap(),
+            Version::parse("1.0.0-alpha.1").unwrap(),
+            Version::parse("0.9.0").unwrap(),
+        ];
+
+        let lowest = set.find_lowest_match(&versions).unwrap();
+        assert_eq!(lowest, &Version::parse("1.0.0").unwrap());
+    }
+
     #[test]
     fn test_constraint_conflicts() -> Result<()> {
         let mut set = ConstraintSet::new();
@@ -85,6 +85,7 @@
 //! | `>=1.0.0` | Greater than or equal | `">=1.0.0"` |
 //! | `<2.0.0` | Less than | `"<2.0.0"` |
 //! | `>=1.0.0, <2.0.0` | Range constraint | Multiple constraints |
+//! | `^1.0.0 \|\| ^2.0.0` | Union of alternatives | Highest version matching any |
 //! | `main` | Git branch reference | Branch name |
 //! | `latest` | Git tag or branch name | Just a regular ref |
 //! | `v1.0.0` | Git tag reference | Tag name |
@@ -135,6 +136,7 @@ pub use resolver::ConstraintResolver;
 ///
 /// - [`Exact`](Self::Exact): Matches exactly one specific semantic version
 /// - [`Requirement`](Self::Requirement): Matches versions using semver ranges
+/// - [`Union`](Self::Union): Matches versions satisfying any of several `||`-separated alternatives
 /// - [`GitRef`](Self::GitRef): Matches specific Git branches, tags, or commit hashes (including "latest")
 ///
 /// # Examples
@@ -176,6 +178,7 @@ pub use resolver::ConstraintResolver;
 /// - `"agents-v1.0.0"`: Exact version with prefix
 /// - `"agents-^v1.0.0"`: Compatible version range with prefix
 /// - Prefixed constraints only match tags with the same prefix
+/// - `"agents-^v1.0.0 || agents-^v2.0.0"`: Every alternative of a union uses the same prefix
 #[derive(Debug, Clone)]
 pub enum VersionConstraint {
     /// Exact version match with optional prefix (e.g., "1.0.0", "agents-v1.0.0")
@@ -190,6 +193,16 @@ pub enum VersionConstraint {
         req: VersionReq,
     },
 
+    /// Any of several `||`-separated semver alternatives sharing one prefix
+    /// (e.g., "^1.0.0 || ^2.0.0", "agents-^v1.0.0 || agents-^v2.0.0").
+    ///
+    /// Each alternative is an unprefixed [`Exact`](Self::Exact) or
+    /// [`Requirement`](Self::Requirement) constraint.
+    Union {
+        prefix: Option<String>,
+        alternatives: Vec<VersionConstraint>,
+    },
+
     /// Git tag or branch name (including "latest" - it's just a tag name)
     GitRef(String),
 }
@@ -206,7 +219,8 @@ impl VersionConstraint {
     /// 1. **Special keywords**: `"*"` (wildcard for any version)
     /// 2. **Exact versions**: `"1.0.0"`, `"v1.0.0"` (without range operators)
     /// 3. **Version requirements**: `"^1.0.0"`, `"~1.2.0"`, `">=1.0.0"`, `"<2.0.0"`
-    /// 4. **Git references**: Any string that doesn't match the above patterns (including "latest")
+    /// 4. **Unions**: `"^1.0.0 || ^2.0.0"`, where every alternative must be one of the above
+    /// 5. **Git references**: Any string that doesn't match the above patterns (including "latest")
     ///
     /// # Arguments
     ///
@@ -231,6 +245,7 @@ impl VersionConstraint {
     /// let tilde = VersionConstraint::parse("~1.2.0")?;      // 1.2.x compatible
     /// let gte = VersionConstraint::parse(">=1.0.0")?;       // Greater or equal
     /// let range = VersionConstraint::parse(">1.0.0, <2.0.0")?; // Range
+    /// let union = VersionConstraint::parse("^1.0.0 || ^2.0.0")?; // 1.x.x or 2.x.x
     ///
     /// // Special keywords
     /// let any = VersionConstraint::parse("*")?;             // Any version
@@ -245,11 +260,16 @@ impl VersionConstraint {
     ///
     /// # Error Handling
     ///
-    /// This method only returns errors for malformed semantic version strings.
-    /// Git references and special keywords always parse successfully.
+    /// This method only returns errors for malformed semantic version strings
+    /// and for unions whose alternatives are not all semver constraints with the
+    /// same prefix. Git references and special keywords always parse successfully.
     pub fn parse(constraint: &str) -> Result<Self> {
         let trimmed = constraint.trim();
 
+        if trimmed.contains("||") {
+            return Self::parse_union(trimmed);
+        }
+
         // Extract prefix from constraint first (e.g., "agents-^v1.0.0" → (Some("agents"), "^v1.0.0"))
         let (prefix, version_str) = crate::version::split_prefix_and_version(trimmed);
 
@@ -302,6 +322,49 @@ impl VersionConstraint {
         Ok(Self::GitRef(trimmed.to_string()))
     }
 
+    /// Parse a `||`-separated union such as `"^1.0.0 || agents-^v2.0.0"`.
+    fn parse_union(trimmed: &str) -> Result<Self> {
+        let mut prefix = None;
+        let mut alternatives = Vec::new();
+
+        for (index, alternative) in trimmed.split("||").map(str::trim).enumerate() {
+            if alternative.is_empty() {
+                return Err(anyhow::anyhow!("Empty alternative in version constraint '{trimmed}'"));
+            }
+
+            let (alternative_prefix, version_str) =
+                crate::version::split_prefix_and_version(alternative);
+            if index == 0 {
+                prefix = alternative_prefix;
+            } else if alternative_prefix != prefix {
+                return Err(anyhow::anyhow!(
+                    "All alternatives in version constraint '{trimmed}' must use the same prefix"
+                ));
+            }
+
+            match Self::parse(version_str)? {
+                constraint @ (Self::Exact {
+                    ..
+                }
+                | Self::Requirement {
+                    ..
+                }) => {
+                    alternatives.push(constraint);
+                }
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "'{alternative}' in version constraint '{trimmed}' is not a semver constraint"
+                    ));
+                }
+            }
+        }
+
+        Ok(Self::Union {
+            prefix,
+            alternatives,
+        })
+    }
+
     /// Check if a semantic version satisfies this constraint.
     ///
     /// This method tests whether a given semantic version matches the requirements
@@ -309,6 +372,7 @@ impl VersionConstraint {
     ///
     /// - **Exact**: Version must match exactly
     /// - **Requirement**: Version must satisfy the semver range
+    /// - **Union**: Version must satisfy at least one alternative
     /// - **`GitRef`**: Never matches semantic versions (Git refs are matched separately)
     ///
     /// # Arguments
@@ -348,6 +412,10 @@ impl VersionConstraint {
                 req,
                 ..
             } => req.matches(version),
+            Self::Union {
+                alternatives,
+                ..
+            } => alternatives.iter().any(|alternative| alternative.matches(version)),
             Self::GitRef(_) => false, // Git refs don't match semver versions
         }
     }
@@ -464,6 +532,10 @@ impl VersionConstraint {
             | Self::Requirement {
                 prefix,
                 ..
+            }
+            | Self::Union {
+                prefix,
+                ..
             } => prefix.as_ref(),
             _ => None,
         };
@@ -486,7 +558,8 @@ impl VersionConstraint {
     /// # Returns
     ///
     /// Returns `Some(VersionReq)` for constraints that can be expressed as semantic
-    /// version requirements, or `None` for Git reference constraints.
+    /// version requirements, or `None` for Git reference constraints and unions,
+    /// which a single `VersionReq` cannot express.
     ///
     /// # Conversion Rules
     ///
@@ -531,6 +604,9 @@ impl VersionConstraint {
                 req,
                 ..
             } => Some(req.clone()),
+            Self::Union {
+                ..
+            } => None, // semver has no `||` operator
             Self::GitRef(_) => None, // Git refs cannot be converted to version requirements
         }
     }
@@ -544,7 +620,14 @@ impl VersionConstraint {
     /// # Prerelease Policy
     ///
     /// - **`GitRef`**: Allows prereleases (Git refs may point to any commit)
-    /// - **Exact/Requirement**: Excludes prereleases unless explicitly specified
+    /// - **Exact/Requirement**: Excludes prereleases unless the constraint itself
+    ///   carries a prerelease identifier (e.g. `^1.0.0-beta`, `=2.0.0-rc.1`)
+    /// - **Union**: Allows prereleases if any alternative does
+    ///
+    /// A prerelease constraint does not open up every prerelease: semver only
+    /// matches prereleases of the same `major.minor.patch` that sort at or after
+    /// the constraint's own identifier, so `^1.0.0-beta` matches `1.0.0-beta.2`
+    /// and `1.0.0-rc.1` but not `1.0.0-alpha.1` or `1.1.0-beta.1`.
     ///
     /// # Returns
     ///
@@ -564,6 +647,9 @@ impl VersionConstraint {
     ///
     /// let exact = VersionConstraint::parse("1.0.0")?;
     /// assert!(!exact.allows_prerelease()); // Exact stable version
+    ///
+    /// let beta = VersionConstraint::parse("^1.0.0-beta")?;
+    /// assert!(beta.allows_prerelease()); // Tracks the beta channel
     /// # Ok::<(), anyhow::Error>(())
     /// ```
     ///
@@ -572,20 +658,97 @@ impl VersionConstraint {
     /// During version resolution, if any constraint in a set allows prereleases,
     /// the entire constraint set will consider prerelease versions as candidates.
     #[must_use]
-    pub const fn allows_prerelease(&self) -> bool {
-        matches!(self, Self::GitRef(_))
+    pub fn allows_prerelease(&self) -> bool {
+        match self {
+            Self::Exact {
+                version,
+                ..
+            } => !version.pre.is_empty(),
+            Self::Requirement {
+                req,
+                ..
+            } => req.comparators.iter().any(|c| !c.pre.is_empty()),
+            Self::Union {
+                alternatives,
+                ..
+            } => alternatives.iter().any(Self::allows_prerelease),
+            Self::GitRef(_) => true,
+        }
+    }
+
+    /// Check whether `pin`, used in place of this constraint, still satisfies it.
+    ///
+    /// An exact pin must match this constraint, including its prefix. A range
+    /// pin is only accepted in place of another range with the same prefix,
+    /// since it could resolve away from an exact version. Git refs must be
+    /// identical.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use agpm_cli::version::constraints::VersionConstraint;
+    ///
+    /// let declared = VersionConstraint::parse("^1.0.0")?;
+    /// assert!(declared.is_satisfied_by(&VersionConstraint::parse("v1.2.0")?));
+    /// assert!(!declared.is_satisfied_by(&VersionConstraint::parse("v2.0.0")?));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    #[must_use]
+    pub fn is_satisfied_by(&self, pin: &Self) -> bool {
+        match (self, pin) {
+            (Self::GitRef(declared), Self::GitRef(pinned)) => declared == pinned,
+            (Self::GitRef(_), _) | (_, Self::GitRef(_)) => false,
+            (
+                _,
+                Self::Exact {
+                    prefix,
+                    version,
+                },
+            ) => self.matches_version_info(&crate::version::VersionInfo {
+                prefix: prefix.clone(),
+                version: version.clone(),
+                tag: version.to_string(),
+                prerelease: !version.pre.is_empty(),
+            }),
+            (
+                Self::Exact {
+                    ..
+                },
+                _,
+            ) => false,
+            _ => self.prefix() == pin.prefix(),
+        }
+    }
+
+    /// The tag prefix of a semver constraint.
+    fn prefix(&self) -> Option<&str> {
+        match self {
+            Self::Exact {
+                prefix,
+                ..
+            }
+            | Self::Requirement {
+                prefix,
+                ..
+            }
+            | Self::Union {
+                prefix,
+                ..
+            } => prefix.as_deref(),
+            Self::GitRef(_) => None,
+        }
     }
 
     /// Check if this constraint represents a semantic version constraint.
     ///
-    /// Returns `true` for [`Exact`](Self::Exact) and [`Requirement`](Self::Requirement)
-    /// variants, `false` for [`GitRef`](Self::GitRef). This distinguishes between
+    /// Returns `true` for [`Exact`](Self::Exact), [`Requirement`](Self::Requirement)
+    /// and [`Union`](Self::Union) variants, `false` for [`GitRef`](Self::GitRef). This distinguishes between
     /// stable version tags (e.g., `v1.0.0`, `^1.0.0`) and floating refs (e.g.,
     /// branch names like `main`, commit SHAs).
     ///
     /// # Returns
     ///
-    /// - `true` for semver constraints (`Exact` or `Requirement`)
+    /// - `true` for semver constraints (`Exact`, `Requirement` or `Union`)
     /// - `false` for git references (`GitRef`)
     ///
     /// # Examples
@@ -614,7 +777,7 @@ impl VersionConstraint {
     /// more stable, reproducible builds.
     #[must_use]
     pub const fn is_semver(&self) -> bool {
-        matches!(self, Self::Exact { .. } | Self::Requirement { .. })
+        matches!(self, Self::Exact { .. } | Self::Requirement { .. } | Self::Union { .. })
     }
 }
 
@@ -641,6 +804,21 @@ impl fmt::Display for VersionConstraint {
                     write!(f, "{req}")
                 }
             }
+            Self::Union {
+                prefix,
+                alternatives,
+            } => {
+                for (index, alternative) in alternatives.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, " || ")?;
+                    }
+                    if let Some(p) = prefix {
+                        write!(f, "{p}-")?;
+                    }
+                    write!(f, "{alternative}")?;
+                }
+                Ok(())
+            }
             Self::GitRef(ref_name) => write!(f, "{ref_name}"),
         }
     }
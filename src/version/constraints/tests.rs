@@ -179,6 +179,62 @@ fn test_requirement_constraint_allows_prerelease() {
     assert!(!exact.allows_prerelease());
 }
 
+#[test]
+fn test_prerelease_channel_constraints() {
+    use super::ConstraintSet;
+
+    assert!(VersionConstraint::parse("^1.0.0-beta").unwrap().allows_prerelease());
+    assert!(VersionConstraint::parse("1.0.0-rc.1").unwrap().allows_prerelease());
+    assert!(VersionConstraint::parse("^1.0.0 || ^2.0.0-alpha").unwrap().allows_prerelease());
+
+    let versions: Vec<Version> =
+        ["1.0.0-alpha.1", "1.0.0-beta.1", "1.0.0-beta.2", "1.1.0-beta.1", "0.9.0"]
+            .iter()
+            .map(|v| Version::parse(v).unwrap())
+            .collect();
+    let best = |constraint: &str| {
+        let mut set = ConstraintSet::new();
+        set.add(VersionConstraint::parse(constraint).unwrap()).unwrap();
+        set.find_best_match(&versions).map(ToString::to_string)
+    };
+
+    // A channel matches itself and later channels of the same version only
+    assert_eq!(best("^1.0.0-beta").as_deref(), Some("1.0.0-beta.2"));
+    assert_eq!(best("^1.0.0-alpha").as_deref(), Some("1.0.0-beta.2"));
+    assert_eq!(best("^1.0.0-rc").as_deref(), None);
+    assert_eq!(best("~1.0.0-beta.1").as_deref(), Some("1.0.0-beta.2"));
+    // Stable-only constraints still hide prereleases
+    assert_eq!(best("^1.0.0").as_deref(), None);
+    assert_eq!(best("^0.9.0").as_deref(), Some("0.9.0"));
+
+    // A stable release of the channel's version wins over its prereleases
+    let mut with_release = versions.clone();
+    with_release.push(Version::parse("1.0.0").unwrap());
+    let mut set = ConstraintSet::new();
+    set.add(VersionConstraint::parse("^1.0.0-beta").unwrap()).unwrap();
+    assert_eq!(set.find_best_match(&with_release).unwrap().to_string(), "1.0.0");
+}
+
+#[test]
+fn test_is_satisfied_by() {
+    let satisfied = |declared: &str, pin: &str| {
+        VersionConstraint::parse(declared)
+            .unwrap()
+            .is_satisfied_by(&VersionConstraint::parse(pin).unwrap())
+    };
+
+    assert!(satisfied("^1.0.0", "v1.2.0"));
+    assert!(satisfied("v1.2.0", "1.2.0"));
+    assert!(satisfied("^1.0.0", "~1.2.0"));
+    assert!(satisfied("main", "main"));
+    assert!(!satisfied("^1.0.0", "v2.0.0"));
+    assert!(!satisfied("v1.0.0", "^1.0.0"));
+    assert!(!satisfied("agents-^v1.0.0", "v1.2.0"));
+    assert!(satisfied("agents-^v1.0.0", "agents-v1.2.0"));
+    assert!(!satisfied("main", "v1.0.0"));
+    assert!(!satisfied("^1.0.0", "develop"));
+}
+
 #[test]
 fn test_parse_with_whitespace() {
     let constraint = VersionConstraint::parse("  1.0.0  ").unwrap();
@@ -329,3 +385,52 @@ fn test_prefix_with_hyphens() {
         _ => panic!("Expected Exact constraint"),
     }
 }
+
+#[test]
+fn test_parse_union() {
+    let constraint = VersionConstraint::parse("^1 || ^2").unwrap();
+    assert!(matches!(constraint, VersionConstraint::Union { .. }));
+    assert!(constraint.is_semver());
+    assert!(!constraint.allows_prerelease());
+    assert!(constraint.to_version_req().is_none());
+
+    assert!(constraint.matches(&Version::parse("1.4.0").unwrap()));
+    assert!(constraint.matches(&Version::parse("2.0.0").unwrap()));
+    assert!(!constraint.matches(&Version::parse("3.0.0").unwrap()));
+    assert!(!constraint.matches(&Version::parse("0.9.0").unwrap()));
+
+    // Exact alternatives and x-ranges are allowed
+    let constraint = VersionConstraint::parse("1.0.0 || 2.x").unwrap();
+    assert!(constraint.matches(&Version::parse("1.0.0").unwrap()));
+    assert!(!constraint.matches(&Version::parse("1.0.1").unwrap()));
+    assert!(constraint.matches(&Version::parse("2.7.0").unwrap()));
+
+    assert_eq!(VersionConstraint::parse("^1.0.0||~2.1.0").unwrap().to_string(), "^1.0.0 || ~2.1.0");
+}
+
+#[test]
+fn test_parse_union_with_prefix() {
+    let constraint = VersionConstraint::parse("agents-^v1.0.0 || agents-^v2.0.0").unwrap();
+    assert_eq!(constraint.to_string(), "agents-^1.0.0 || agents-^2.0.0");
+
+    let version_info = |prefix: Option<&str>, version: &str| crate::version::VersionInfo {
+        prefix: prefix.map(str::to_string),
+        version: Version::parse(version).unwrap(),
+        tag: String::new(),
+        prerelease: false,
+    };
+    assert!(constraint.matches_version_info(&version_info(Some("agents"), "2.3.0")));
+    assert!(!constraint.matches_version_info(&version_info(Some("agents"), "3.0.0")));
+    assert!(!constraint.matches_version_info(&version_info(None, "2.3.0")));
+
+    // Mixed prefixes are rejected
+    assert!(VersionConstraint::parse("agents-^v1.0.0 || snippets-^v2.0.0").is_err());
+    assert!(VersionConstraint::parse("agents-^v1.0.0 || ^v2.0.0").is_err());
+}
+
+#[test]
+fn test_parse_union_invalid() {
+    assert!(VersionConstraint::parse("^1.0.0 ||").is_err());
+    assert!(VersionConstraint::parse("^1.0.0 || main").is_err());
+    assert!(VersionConstraint::parse("^1.0.0 || ^bad").is_err());
+}
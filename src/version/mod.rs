@@ -130,7 +130,8 @@
 //! AGPM provides sophisticated prerelease version management:
 //!
 //! - **Default exclusion**: Most constraints exclude prereleases for stability
-//! - **Explicit inclusion**: Use Git refs to include them
+//! - **Explicit inclusion**: Use Git refs, or a range that names a prerelease
+//!   channel such as `^1.0.0-beta`, to include them
 //! - **Constraint inheritance**: If any constraint allows prereleases, all do
 //! - **Version precedence**: Stable versions are preferred when available
 //!
@@ -153,6 +154,7 @@
 use crate::git::GitRepo;
 use anyhow::{Context, Result};
 use regex::Regex;
+use schemars::JsonSchema;
 use semver::{Version, VersionReq};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -357,6 +359,102 @@ pub struct VersionInfo {
     pub prerelease: bool,
 }
 
+/// Which tag wins when several tags name the same release (`tag_preference` in `agpm.toml`).
+///
+/// A commit can carry more than one tag, e.g. `v1.2.0` next to a moving
+/// `stable` alias, or `v1.2.0` next to `v1.2.0+build.7`. The preference decides
+/// deterministically which tag a dependency resolves to and which one is
+/// recorded in `agpm.lock`; the commit is the same either way.
+///
+/// # Examples
+///
+/// ```rust
+/// use agpm_cli::version::TagPreference;
+/// use std::collections::HashMap;
+///
+/// let commits = HashMap::from([
+///     ("v1.2.0".to_string(), "abc".to_string()),
+///     ("stable".to_string(), "abc".to_string()),
+/// ]);
+/// assert_eq!(TagPreference::Semver.canonical_tag("stable", &commits), Some("v1.2.0"));
+/// assert_eq!(TagPreference::Requested.canonical_tag("stable", &commits), None);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TagPreference {
+    /// Prefer plain semver tags. A pinned non-semver alias is recorded as the
+    /// highest semver tag on the same commit, and among tags of equal semver
+    /// precedence the one without a prefix or build metadata wins.
+    #[default]
+    Semver,
+    /// Record tags exactly as requested. Tags with identical versions are
+    /// ordered by name.
+    Requested,
+}
+
+impl TagPreference {
+    /// Whether this is the default preference (used to skip serialization).
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether two tag versions name the same release under this preference.
+    ///
+    /// [`Semver`](Self::Semver) ignores build metadata, as semver precedence
+    /// does; [`Requested`](Self::Requested) requires identical versions.
+    #[must_use]
+    pub fn same_release(self, a: &Version, b: &Version) -> bool {
+        match self {
+            Self::Semver => a.cmp_precedence(b).is_eq(),
+            Self::Requested => a == b,
+        }
+    }
+
+    /// Order two tags of the same release, most preferred first.
+    #[must_use]
+    pub fn cmp_tags(self, a: (&str, &Version), b: (&str, &Version)) -> std::cmp::Ordering {
+        match self {
+            Self::Semver => {
+                let rank = |(tag, version): (&str, &Version)| {
+                    let (prefix, version_str) = split_prefix_and_version(tag);
+                    (prefix.is_some(), !version.build.is_empty(), !version_str.starts_with('v'))
+                };
+                rank(a).cmp(&rank(b)).then_with(|| a.0.cmp(b.0))
+            }
+            Self::Requested => a.0.cmp(b.0),
+        }
+    }
+
+    /// The semver tag to record instead of the non-semver tag `alias`.
+    ///
+    /// `tag_commits` maps every tag to the commit it points at (see
+    /// [`GitRepo::list_tag_commits`]). Returns the highest semver tag on the
+    /// alias's commit, or `None` if `alias` is itself a semver tag, no semver
+    /// tag shares its commit, or the preference is [`Requested`](Self::Requested).
+    #[must_use]
+    pub fn canonical_tag<'a>(
+        self,
+        alias: &str,
+        tag_commits: &'a std::collections::HashMap<String, String>,
+    ) -> Option<&'a str> {
+        if self != Self::Semver || VersionResolver::parse_tag(alias).is_ok() {
+            return None;
+        }
+        let commit = tag_commits.get(alias)?;
+        tag_commits
+            .iter()
+            .filter(|(_, tag_commit)| *tag_commit == commit)
+            .filter_map(|(tag, _)| {
+                VersionResolver::parse_tag(tag).ok().map(|(_, version)| (tag.as_str(), version))
+            })
+            .min_by(|a, b| {
+                b.1.cmp_precedence(&a.1).then_with(|| self.cmp_tags((a.0, &a.1), (b.0, &b.1)))
+            })
+            .map(|(tag, _)| tag)
+    }
+}
+
 /// Resolves semantic versions from Git repository tags.
 ///
 /// `VersionResolver` provides the core functionality for discovering, parsing, and
@@ -422,6 +520,8 @@ pub struct VersionInfo {
 /// ```
 pub struct VersionResolver {
     versions: Vec<Arc<VersionInfo>>,
+    /// Non-semver tags (e.g. `stable`) and the semver release on the same commit
+    aliases: Vec<(String, Arc<VersionInfo>)>,
 }
 
 impl VersionResolver {
@@ -445,6 +545,7 @@ impl VersionResolver {
     pub const fn new() -> Self {
         Self {
             versions: Vec::new(),
+            aliases: Vec::new(),
         }
     }
 
@@ -512,11 +613,29 @@ impl VersionResolver {
             }
         }
 
-        // Sort versions in descending order (newest first)
-        versions.sort_by(|a, b| b.version.cmp(&a.version));
+        // Sort versions in descending order (newest first); several tags of one
+        // release are ordered by the default tag preference
+        let preference = TagPreference::default();
+        versions.sort_by(|a, b| {
+            b.version
+                .cmp_precedence(&a.version)
+                .then_with(|| preference.cmp_tags((&a.tag, &a.version), (&b.tag, &b.version)))
+        });
+
+        // Non-semver tags resolve to the semver release sharing their commit
+        let mut aliases = Vec::new();
+        let tag_commits = repo.list_tag_commits().await?;
+        for tag in tag_commits.keys() {
+            if let Some(canonical) = preference.canonical_tag(tag, &tag_commits)
+                && let Some(info) = versions.iter().find(|v| v.tag == canonical)
+            {
+                aliases.push((tag.clone(), Arc::clone(info)));
+            }
+        }
 
         Ok(Self {
             versions,
+            aliases,
         })
     }
 
@@ -599,6 +718,8 @@ impl VersionResolver {
     /// # Prerelease Handling
     ///
     /// - **Default behavior**: Prereleases are excluded from semver range matching
+    /// - **Prerelease ranges**: A range carrying a prerelease (e.g. `^1.0.0-beta`)
+    ///   matches prereleases of that version at or after its identifier
     /// - **Explicit matches**: Direct version/tag matches include prereleases
     ///
     /// # Examples
@@ -636,6 +757,10 @@ impl VersionResolver {
     /// - **Highest version wins**: Newer semantic versions are preferred
     /// - **Stable over prerelease**: Stable versions preferred unless prereleases explicitly allowed
     /// - **First match for tags**: Tag name matching returns the first occurrence
+    /// - **Aliases resolve to their release**: A non-semver tag such as `stable`
+    ///   resolves to the semver tag on the same commit
+    /// - **Equal-precedence tags**: Of tags naming one release (e.g. `v1.2.0`
+    ///   and `v1.2.0+build.7`), the one preferred by [`TagPreference::Semver`] wins
     pub fn resolve(&self, requirement: &str) -> Result<Option<Arc<VersionInfo>>> {
         // Extract prefix and version part (e.g., "agents-^v1.0.0" → (Some("agents"), "^v1.0.0"))
         let (prefix, version_str) = split_prefix_and_version(requirement);
@@ -654,9 +779,12 @@ impl VersionResolver {
 
         // Try as semantic version requirement using centralized parser
         if let Ok(req) = parse_version_req(version_str) {
+            // Prereleases are excluded unless the requirement names a channel
+            // itself (e.g. `^1.0.0-beta`); semver then only matches that channel
+            let allows_prerelease = req.comparators.iter().any(|c| !c.pre.is_empty());
             return Ok(matching_prefix
                 .iter()
-                .filter(|v| !v.prerelease) // Exclude prereleases by default
+                .filter(|v| allows_prerelease || !v.prerelease)
                 .find(|v| req.matches(&v.version))
                 .map(|&v| Arc::clone(v)));
         }
@@ -668,7 +796,8 @@ impl VersionResolver {
             }
         }
 
-        Ok(None)
+        // Try a non-semver alias of a release (e.g. `stable` tagged next to `v1.2.0`)
+        Ok(self.aliases.iter().find(|(alias, _)| alias == requirement).map(|(_, v)| Arc::clone(v)))
     }
 
     /// Get the latest version including prereleases.
@@ -1124,6 +1253,10 @@ impl Default for VersionResolver {
 /// assert!(matches_requirement("1.5.0", ">=1.0.0, <2.0.0")?);
 /// assert!(!matches_requirement("2.0.0", ">=1.0.0, <2.0.0")?);
 ///
+/// // Unions match if any alternative matches
+/// assert!(matches_requirement("2.3.0", "^1.0.0 || ^2.0.0")?);
+/// assert!(!matches_requirement("3.0.0", "^1.0.0 || ^2.0.0")?);
+///
 /// // Pre-release handling
 /// assert!(matches_requirement("1.0.0-beta.1", "^1.0.0-beta")?);
 /// # Ok(())
@@ -1153,6 +1286,16 @@ impl Default for VersionResolver {
 /// - Testing version constraints programmatically
 /// - Implementing custom version resolution logic
 pub fn matches_requirement(version: &str, requirement: &str) -> Result<bool> {
+    // Unions like "^1.0.0 || ^2.0.0" match if any alternative does
+    if requirement.contains("||") {
+        for alternative in requirement.split("||") {
+            if matches_requirement(version, alternative.trim())? {
+                return Ok(true);
+            }
+        }
+        return Ok(false);
+    }
+
     // Extract prefixes from both version and requirement
     let (version_prefix, version_str) = split_prefix_and_version(version);
     let (req_prefix, req_str) = split_prefix_and_version(requirement);
@@ -1177,6 +1320,14 @@ pub fn matches_requirement(version: &str, requirement: &str) -> Result<bool> {
     Ok(req.matches(&version))
 }
 
+/// Version keyword selecting the most recently created tag of a source.
+///
+/// Tags are ordered by creation time rather than by semantic version: the
+/// tagger date for annotated tags and the commit date for lightweight tags.
+/// This suits repositories that do not bump versions consistently. The
+/// selected tag is recorded in the lockfile like any resolved range.
+pub const LATEST_BY_DATE: &str = "latest-by-date";
+
 /// Parse a version constraint string into a structured constraint type.
 ///
 /// This function analyzes a constraint string and determines whether it represents
@@ -1254,7 +1405,9 @@ pub fn matches_requirement(version: &str, requirement: &str) -> Result<bool> {
 /// Version and tag specifications are identified by:
 /// - Valid semantic version parsing (with or without `v` prefix)
 /// - Valid semantic version requirement parsing (ranges, comparisons)
+/// - Unions of requirements such as `"^1.0.0 || ^2.0.0"`
 /// - Wildcard `"*"` for any version
+/// - The [`LATEST_BY_DATE`] keyword
 ///
 /// # Branch Name Fallback
 ///
@@ -1272,6 +1425,10 @@ pub fn matches_requirement(version: &str, requirement: &str) -> Result<bool> {
 /// - Building version constraint objects from strings
 #[must_use]
 pub fn parse_version_constraint(constraint: &str) -> VersionConstraint {
+    if constraint == LATEST_BY_DATE {
+        return VersionConstraint::Tag(constraint.to_string());
+    }
+
     // Check if it looks like a commit hash (40 hex chars or abbreviated)
     if constraint.len() >= 7 && constraint.chars().all(|c| c.is_ascii_hexdigit()) {
         return VersionConstraint::Commit(constraint.to_string());
@@ -1284,6 +1441,8 @@ pub fn parse_version_constraint(constraint: &str) -> VersionConstraint {
     if Version::parse(version_str.trim_start_matches('v')).is_ok()
         || parse_version_req(version_str).is_ok()
         || version_str == "*"
+        || (constraint.contains("||")
+            && constraints::VersionConstraint::parse(constraint).is_ok_and(|c| c.is_semver()))
     {
         return VersionConstraint::Tag(constraint.to_string());
     }
@@ -1313,6 +1472,12 @@ pub mod conflict;
 /// resolution. See the module documentation for comprehensive examples.
 pub mod constraints;
 
+/// Per-source tag formats such as `release/{version}`.
+///
+/// The [`tag_pattern`] module extracts versions from tags that the default
+/// prefix parsing does not understand, as configured under `[tag-patterns]`.
+pub mod tag_pattern;
+
 /// Represents different types of version constraints in AGPM.
 ///
 /// `VersionConstraint` is a simple enum that categorizes version references into
@@ -1452,6 +1617,60 @@ mod tests {
         assert_eq!(resolver.resolve("~1.1.0").unwrap().unwrap().tag, "v1.1.0");
     }
 
+    #[tokio::test]
+    async fn test_version_resolution_prerelease_channel() {
+        let temp_dir = TempDir::new().unwrap();
+        let git = TestGit::new(temp_dir.path());
+        git.init().unwrap();
+        git.config_user().unwrap();
+        std::fs::write(temp_dir.path().join("README.md"), "Test").unwrap();
+        git.add_all().unwrap();
+        git.commit("Initial commit").unwrap();
+        for tag in ["v1.0.0-alpha.1", "v1.0.0-beta.2", "v1.0.0-rc.1", "agents-v1.0.0-beta.5"] {
+            git.tag(tag).unwrap();
+        }
+        let resolver =
+            VersionResolver::from_git_tags(&GitRepo::new(temp_dir.path())).await.unwrap();
+
+        assert_eq!(resolver.resolve("^1.0.0-beta").unwrap().unwrap().tag, "v1.0.0-rc.1");
+        assert_eq!(
+            resolver.resolve(">=1.0.0-beta, <1.0.0-rc").unwrap().unwrap().tag,
+            "v1.0.0-beta.2"
+        );
+        assert_eq!(resolver.resolve("~1.0.0-alpha.1").unwrap().unwrap().tag, "v1.0.0-rc.1");
+        assert!(resolver.resolve("^1.0.0").unwrap().is_none());
+        // Prefixes still isolate channels
+        assert_eq!(
+            resolver.resolve("agents-^v1.0.0-beta").unwrap().unwrap().tag,
+            "agents-v1.0.0-beta.5"
+        );
+        assert!(resolver.resolve("snippets-^v1.0.0-beta").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_version_resolution_same_commit_tags() {
+        let temp_dir = TempDir::new().unwrap();
+        let git = TestGit::new(temp_dir.path());
+        git.init().unwrap();
+        git.config_user().unwrap();
+        std::fs::write(temp_dir.path().join("README.md"), "Test").unwrap();
+        git.add_all().unwrap();
+        git.commit("Initial commit").unwrap();
+        for tag in ["v1.2.0+build.7", "v1.2.0", "1.2.0", "stable"] {
+            git.tag(tag).unwrap();
+        }
+        let resolver =
+            VersionResolver::from_git_tags(&GitRepo::new(temp_dir.path())).await.unwrap();
+
+        // Equal precedence resolves to the plain `v` tag regardless of listing order
+        assert_eq!(resolver.resolve("^1.0.0").unwrap().unwrap().tag, "v1.2.0");
+        assert_eq!(resolver.resolve("1.2.0").unwrap().unwrap().tag, "v1.2.0");
+        // Non-semver aliases resolve to the release they point at
+        let stable = resolver.resolve("stable").unwrap().unwrap();
+        assert_eq!(stable.tag, "v1.2.0");
+        assert!(resolver.resolve("nightly").unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_has_version() {
         let (_temp, repo) = create_test_repo_with_tags();
@@ -1517,6 +1736,10 @@ mod tests {
             parse_version_constraint("latest"),
             VersionConstraint::Branch("latest".to_string())
         );
+        assert_eq!(
+            parse_version_constraint(LATEST_BY_DATE),
+            VersionConstraint::Tag(LATEST_BY_DATE.to_string())
+        );
         assert_eq!(
             parse_version_constraint("latest-prerelease"),
             VersionConstraint::Branch("latest-prerelease".to_string())
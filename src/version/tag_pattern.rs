@@ -0,0 +1,117 @@
+//! Per-source tag formats for version resolution.
+//!
+//! Tags are normally parsed with [`split_prefix_and_version`], which
+//! understands `v1.2.3` and hyphen-prefixed monorepo tags such as
+//! `agents-v1.2.3`. Sources that tag releases differently, for example
+//! `release/1.2.3`, declare their format in the manifest:
+//!
+//! ```toml
+//! [tag-patterns]
+//! community = "release/{version}"
+//! ```
+//!
+//! For such a source, version constraints are matched against the text in
+//! place of `{version}`, and tags that do not fit the pattern are ignored. The
+//! tag itself is what gets resolved and recorded in the lockfile.
+//!
+//! [`split_prefix_and_version`]: super::split_prefix_and_version
+
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// Placeholder marking the version within a tag pattern.
+pub const VERSION_PLACEHOLDER: &str = "{version}";
+
+/// A tag format such as `release/{version}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagPattern {
+    prefix: String,
+    suffix: String,
+}
+
+impl TagPattern {
+    /// Parse a pattern containing `{version}` exactly once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the placeholder is missing or repeated.
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let Some((prefix, suffix)) = pattern.split_once(VERSION_PLACEHOLDER) else {
+            anyhow::bail!("Tag pattern '{pattern}' must contain {VERSION_PLACEHOLDER}");
+        };
+        if suffix.contains(VERSION_PLACEHOLDER) {
+            anyhow::bail!("Tag pattern '{pattern}' must contain {VERSION_PLACEHOLDER} only once");
+        }
+        Ok(Self {
+            prefix: prefix.to_string(),
+            suffix: suffix.to_string(),
+        })
+    }
+
+    /// The version part of `tag`, or `None` if the tag does not fit the pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use agpm_cli::version::tag_pattern::TagPattern;
+    ///
+    /// let pattern = TagPattern::parse("release/{version}")?;
+    /// assert_eq!(pattern.extract("release/1.2.3"), Some("1.2.3"));
+    /// assert_eq!(pattern.extract("v1.2.3"), None);
+    /// # anyhow::Ok(())
+    /// ```
+    #[must_use]
+    pub fn extract<'a>(&self, tag: &'a str) -> Option<&'a str> {
+        tag.strip_prefix(&self.prefix)?
+            .strip_suffix(&self.suffix)
+            .filter(|version| !version.is_empty())
+    }
+
+    /// Map the version part of every fitting tag to the tag it came from.
+    ///
+    /// If two tags carry the same version text, the first one listed wins.
+    #[must_use]
+    pub fn tags_by_version(&self, tags: &[String]) -> HashMap<String, String> {
+        let mut versions = HashMap::new();
+        for tag in tags {
+            if let Some(version) = self.extract(tag) {
+                versions.entry(version.to_string()).or_insert_with(|| tag.clone());
+            }
+        }
+        versions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_requires_one_placeholder() {
+        assert!(TagPattern::parse("release/{version}").is_ok());
+        assert!(TagPattern::parse("{version}-stable").is_ok());
+        assert!(TagPattern::parse("release/").is_err());
+        assert!(TagPattern::parse("{version}/{version}").is_err());
+    }
+
+    #[test]
+    fn test_extract_with_prefix_and_suffix() {
+        let pattern = TagPattern::parse("pkg@{version}-final").unwrap();
+        assert_eq!(pattern.extract("pkg@1.0.0-final"), Some("1.0.0"));
+        assert_eq!(pattern.extract("pkg@-final"), None);
+        assert_eq!(pattern.extract("pkg@1.0.0"), None);
+    }
+
+    #[test]
+    fn test_tags_by_version_skips_other_tags() {
+        let pattern = TagPattern::parse("release/{version}").unwrap();
+        let tags: Vec<String> = ["release/1.0.0", "release/v2.0.0", "v3.0.0", "nightly"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let versions = pattern.tags_by_version(&tags);
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions["1.0.0"], "release/1.0.0");
+        assert_eq!(versions["v2.0.0"], "release/v2.0.0");
+    }
+}
@@ -70,6 +70,7 @@ pub struct ManifestBuilder {
     hooks: Vec<DependencyEntry>,
     mcp_servers: Vec<DependencyEntry>,
     skills: Vec<DependencyEntry>,
+    raw_sections: Vec<String>,
 }
 
 /// Configuration for the [target] section
@@ -688,6 +689,17 @@ impl ManifestBuilder {
         self
     }
 
+    /// Append a TOML section verbatim, for options the builder does not model
+    ///
+    /// # Example
+    /// ```rust
+    /// builder.add_raw("[installer]\nheader = true\n")
+    /// ```
+    pub fn add_raw(mut self, section: &str) -> Self {
+        self.raw_sections.push(section.to_string());
+        self
+    }
+
     /// Build the final TOML string
     ///
     /// Constructs a valid agpm.toml manifest from the builder state.
@@ -866,6 +878,12 @@ impl ManifestBuilder {
             }
         }
 
+        // Raw sections last, so they cannot split the sections above
+        for section in &self.raw_sections {
+            toml.push_str(section);
+            toml.push('\n');
+        }
+
         toml
     }
 }
@@ -66,6 +66,7 @@
 //! - `new()` - Create test project with temp directories
 //! - `create_source_repo(name)` - Create empty source repository
 //! - `create_standard_v1_repo(name)` - **NEW**: Create repo with v1.0.0 tag
+//! - `with_community_source(files, dependencies)` - Create project with a `community` source and manifest
 //! - `write_manifest(content)` - Write agpm.toml
 //! - `run_agpm(args)` - Run AGPM CLI command
 //!
@@ -207,6 +208,15 @@ impl TestGit {
         Ok(())
     }
 
+    /// Point a ref at a commit, e.g. to simulate a pull-request ref
+    pub fn update_ref(&self, ref_name: &str, target: &str) -> Result<()> {
+        self.run_git_command(
+            &["update-ref", ref_name, target],
+            &format!("Failed to update ref: {}", ref_name),
+        )?;
+        Ok(())
+    }
+
     /// Ensure we're on a specific branch, creating it if it doesn't exist
     /// This is useful when the default branch name is unknown (master vs main)
     pub fn ensure_branch(&self, branch_name: &str) -> Result<()> {
@@ -442,6 +452,47 @@ impl TestProject {
         Ok((repo, url))
     }
 
+    /// Create a project whose manifest depends on a `community` source
+    ///
+    /// The source repository holds `files`, given as `(path, content)` pairs
+    /// relative to the repository root, committed and tagged `v1.0.0`. The
+    /// manifest declares the source and whatever `dependencies` adds to it;
+    /// nothing is installed.
+    ///
+    /// Returns the project, the source repository and its bare file:// URL.
+    /// To publish later commits, commit and tag them in the repository and
+    /// call `bare_file_url` again.
+    ///
+    /// # Example
+    /// ```rust
+    /// let (project, _, _) = TestProject::with_community_source(
+    ///     &[("agents/reviewer.md", "# Reviewer\n")],
+    ///     |m| m.add_standard_agent("reviewer", "community", "agents/reviewer.md"),
+    /// )
+    /// .await?;
+    /// project.run_agpm(&["install"])?.assert_success();
+    /// ```
+    pub async fn with_community_source<F>(
+        files: &[(&str, &str)],
+        dependencies: F,
+    ) -> Result<(Self, TestSourceRepo, String)>
+    where
+        F: FnOnce(ManifestBuilder) -> ManifestBuilder,
+    {
+        let project = Self::new().await?;
+        let repo = project.create_source_repo("community").await?;
+        for (path, content) in files {
+            repo.create_file(path, content).await?;
+        }
+        repo.commit_all("Initial version")?;
+        repo.tag_version("v1.0.0")?;
+        let url = repo.bare_file_url(project.sources_path()).await?;
+
+        let manifest = dependencies(ManifestBuilder::new().add_source("community", &url)).build();
+        project.write_manifest(&manifest).await?;
+        Ok((project, repo, url))
+    }
+
     /// Run a AGPM command in the project directory
     pub fn run_agpm(&self, args: &[&str]) -> Result<CommandOutput> {
         self.run_agpm_with_env(args, &[])
@@ -452,12 +503,22 @@ impl TestProject {
         &self,
         args: &[&str],
         env_vars: &[(&str, &str)],
+    ) -> Result<CommandOutput> {
+        self.run_agpm_in_dir(&self.project_dir, args, env_vars)
+    }
+
+    /// Run a AGPM command from a directory other than the project directory
+    pub fn run_agpm_in_dir(
+        &self,
+        dir: &Path,
+        args: &[&str],
+        env_vars: &[(&str, &str)],
     ) -> Result<CommandOutput> {
         let agpm_binary = env!("CARGO_BIN_EXE_agpm");
         let mut cmd = Command::new(agpm_binary);
 
         cmd.args(args)
-            .current_dir(&self.project_dir)
+            .current_dir(dir)
             .env("AGPM_CACHE_DIR", &self.cache_dir)
             .env("NO_COLOR", "1")
             // Disable SSH host key checking for remote test environments
@@ -901,7 +962,8 @@ impl TestSourceRepo {
     }
 
     /// Get a file:// URL for a bare clone of this repository
-    /// Creates the bare repo in the parent's sources directory
+    /// Creates the bare repo in the parent's sources directory, replacing any
+    /// earlier clone so that new commits and tags are published
     ///
     /// # Implementation Note
     /// Automatically ensures the repository is on the 'main' branch before creating
@@ -915,6 +977,9 @@ impl TestSourceRepo {
         let bare_name =
             format!("{}.git", self.path.file_name().and_then(|n| n.to_str()).unwrap_or("repo"));
         let bare_path = sources_dir.join(bare_name);
+        if bare_path.exists() {
+            fs::remove_dir_all(&bare_path).await?;
+        }
         self.to_bare_repo(&bare_path).await?;
         Ok(format!("file://{}", normalize_path_for_storage(&bare_path)))
     }
@@ -0,0 +1,82 @@
+//! Integration tests for the `agpm audit` command.
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+const HOOK: &str = r#"{
+  "events": ["SessionStart"],
+  "type": "command",
+  "command": "echo 'Session started'"
+}"#;
+const MCP_SERVER: &str = r#"{
+  "command": "npx",
+  "args": ["@test/server"],
+  "env": { "GITHUB_TOKEN": "${GITHUB_TOKEN}", "LOG_LEVEL": "info" }
+}"#;
+
+#[tokio::test]
+async fn test_audit_reports_findings() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("community").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer\n").await?;
+    fs::create_dir_all(source_repo.path.join("hooks")).await?;
+    fs::write(source_repo.path.join("hooks/session.json"), HOOK).await?;
+    fs::create_dir_all(source_repo.path.join("mcp-servers")).await?;
+    fs::write(source_repo.path.join("mcp-servers/github.json"), MCP_SERVER).await?;
+    source_repo.commit_all("Initial version")?;
+    source_repo.tag_version("v1.0.0")?;
+    source_repo.git.ensure_branch("main")?;
+    let url = source_repo.bare_file_url(project.sources_path()).await?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_agent("reviewer", |d| d.source("community").path("agents/reviewer.md").branch("main"))
+        .add_hook("session", |d| d.source("community").path("hooks/session.json").version("v1.0.0"))
+        .add_mcp_server("github", |d| {
+            d.source("community").path("mcp-servers/github.json").version("v1.0.0")
+        })
+        .build();
+    project.write_manifest(&manifest).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    // Medium findings alone do not fail the audit
+    let output = project.run_agpm(&["audit", "--format", "json"])?;
+    output.assert_success();
+    let report: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    assert_eq!(report["high"], 0);
+    let codes: Vec<_> =
+        report["findings"].as_array().unwrap().iter().map(|f| f["code"].clone()).collect();
+    assert_eq!(codes, ["branch-pin", "hook-command", "mcp-env-secret"]);
+    assert_eq!(report["findings"][2]["message"], "receives secrets through GITHUB_TOKEN");
+
+    // A source outside the allow-list is a high-severity finding
+    let config_path = project.project_path().join("config.toml");
+    fs::write(&config_path, "[audit]\nallowed_sources = [\"https://github.com/my-org/\"]\n")
+        .await?;
+    let output = project.run_agpm(&["--config", config_path.to_str().unwrap(), "audit"])?;
+    assert!(!output.success, "High-severity findings should fail the audit");
+    assert!(
+        output.stdout.contains("high [unlisted-source] source 'community'"),
+        "{}",
+        output.stdout
+    );
+    assert!(output.stderr.contains("1 high-severity finding(s)"), "{}", output.stderr);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_audit_flags_plain_http_source() -> Result<()> {
+    let project = TestProject::new().await?;
+    let manifest = ManifestBuilder::new()
+        .add_source("mirror", "http://git.example.com/agents.git")
+        .add_standard_agent("reviewer", "mirror", "agents/reviewer.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let output = project.run_agpm(&["audit"])?;
+    assert!(!output.success);
+    assert!(output.stdout.contains("high [insecure-source] source 'mirror'"), "{}", output.stdout);
+    Ok(())
+}
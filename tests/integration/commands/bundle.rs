@@ -0,0 +1,114 @@
+//! Integration tests for the bundle command.
+//!
+//! Tests cover:
+//! - Linting a clean source repository
+//! - Problem reporting and exit status
+//! - JSON output
+//! - Exporting a tarball to a chosen location
+
+use crate::common::TestProject;
+use anyhow::Result;
+
+/// Test that a well-formed source repository passes lint
+#[tokio::test]
+async fn test_bundle_lint_clean_repository() -> Result<()> {
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource(
+        "agents",
+        "reviewer",
+        "---\ndescription: Reviewer\ndependencies:\n  snippets:\n    - path: snippets/style.md\n---\n# Reviewer\n",
+    )
+    .await?;
+    repo.add_resource("snippets", "style", "# Style guide").await?;
+
+    let repo_path = repo.path.to_string_lossy().to_string();
+    let output = project.run_agpm(&["bundle", "lint", &repo_path])?;
+    output.assert_success();
+    assert!(output.stdout.contains("Checked 2 file(s), no problems found"), "{}", output.stdout);
+
+    Ok(())
+}
+
+/// Test that problems are reported and the command fails
+#[tokio::test]
+async fn test_bundle_lint_reports_problems() -> Result<()> {
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource(
+        "agents",
+        "reviewer",
+        "---\ndependencies:\n  snippets:\n    - path: snippets/missing.md\n---\nSee [guide](./guide.md).\n",
+    )
+    .await?;
+    tokio::fs::create_dir_all(repo.path.join("mcp-servers")).await?;
+    tokio::fs::write(repo.path.join("mcp-servers/broken.json"), "{ \"command\": ").await?;
+
+    let repo_path = repo.path.to_string_lossy().to_string();
+    let output = project.run_agpm(&["bundle", "lint", &repo_path])?;
+    assert!(!output.success, "lint should fail:\n{}", output.stdout);
+    assert!(
+        output.stdout.contains("agents/reviewer.md: Dependency 'snippets/missing.md' not found"),
+        "{}",
+        output.stdout
+    );
+    assert!(output.stdout.contains("agents/reviewer.md: Broken link: ./guide.md"));
+    assert!(output.stdout.contains("mcp-servers/broken.json: Invalid JSON"));
+    assert!(output.stdout.contains("Checked 2 file(s): 3 problem(s)"));
+
+    let output = project.run_agpm(&["bundle", "lint", &repo_path, "--format", "json"])?;
+    assert!(!output.success);
+    let report: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    assert_eq!(report["files_checked"], 2);
+    assert_eq!(report["valid"], false);
+    assert_eq!(report["problems"].as_array().unwrap().len(), 3);
+
+    Ok(())
+}
+
+/// Test that export writes the artifact to the requested directory and name
+#[tokio::test]
+async fn test_bundle_export_output_dir() -> Result<()> {
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource("agents", "reviewer", "# Reviewer\n").await?;
+    repo.commit_all("Initial version")?;
+
+    let repo_path = repo.path.to_string_lossy().to_string();
+    let output_dir = project.project_path().join("dist/artifacts");
+    let output_dir_str = output_dir.to_string_lossy().to_string();
+    let args = [
+        "bundle",
+        "export",
+        &repo_path,
+        "--output-dir",
+        &output_dir_str,
+        "--name",
+        "v1.0.0.tar.gz",
+    ];
+    let output = project.run_agpm(&args)?;
+    output.assert_success();
+
+    // The missing output directory is created and the repository is left untouched
+    let artifact = output_dir.join("v1.0.0.tar.gz");
+    assert!(artifact.is_file(), "{}", output.stdout);
+    assert!(output.stdout.contains("Exported 1 file(s) to"), "{}", output.stdout);
+    assert!(!repo.path.join("community.tar.gz").exists());
+
+    let mut archive =
+        tar::Archive::new(flate2::read::GzDecoder::new(std::fs::File::open(&artifact)?));
+    let entries: Vec<String> = archive
+        .entries()?
+        .map(|e| Ok(e?.path()?.to_string_lossy().to_string()))
+        .collect::<Result<_>>()?;
+    assert_eq!(entries, ["agents/reviewer.md"]);
+
+    // Overwriting requires --force
+    let output = project.run_agpm(&args)?;
+    assert!(!output.success, "existing artifact should not be replaced");
+    assert!(output.stderr.contains("already exists"), "{}", output.stderr);
+    let forced: Vec<&str> = args.iter().copied().chain(["--force"]).collect();
+    project.run_agpm(&forced)?.assert_success();
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! Integration tests for the `agpm graph` command.
+
+use anyhow::Result;
+use std::collections::HashSet;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+/// An agent that depends on a snippet and a command.
+const FILES: &[(&str, &str)] = &[
+    (
+        "agents/reviewer.md",
+        "---\ndependencies:\n  snippets:\n    - path: ../snippets/style.md\n  commands:\n    - path: ../commands/lint.md\n---\n# Reviewer\n",
+    ),
+    ("snippets/style.md", "# Style\n"),
+    ("commands/lint.md", "# Lint\n"),
+];
+
+#[tokio::test]
+async fn test_graph_dot_output() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, |m| {
+        m.add_standard_agent("reviewer", "community", "agents/reviewer.md")
+    })
+    .await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let output = project.run_agpm(&["graph"])?;
+    output.assert_success();
+    let dot = output.stdout.trim();
+    assert!(dot.starts_with("digraph dependencies {"), "{dot}");
+    assert!(dot.ends_with('}'), "{dot}");
+
+    // Every edge connects declared nodes
+    let mut nodes = HashSet::new();
+    let mut edges = Vec::new();
+    for line in dot.lines().map(str::trim).filter(|l| l.starts_with('"')) {
+        assert!(line.ends_with(';'), "unterminated statement: {line}");
+        if let Some((from, to)) = line.trim_end_matches(';').split_once(" -> ") {
+            edges.push((from.to_string(), to.to_string()));
+        } else {
+            let (id, attrs) = line.split_once(" [").unwrap();
+            assert!(attrs.starts_with("label=\""), "{line}");
+            nodes.insert(id.to_string());
+        }
+    }
+    assert_eq!(nodes.len(), 3, "{dot}");
+    assert_eq!(edges.len(), 2, "{dot}");
+    for (from, to) in &edges {
+        assert!(nodes.contains(from) && nodes.contains(to), "dangling edge {from} -> {to}");
+        assert!(from.contains("agent:"), "edges start at the agent: {from}");
+    }
+    assert!(dot.contains(r#"[label="snippet\nsnippets/style\nv1.0.0"]"#), "{dot}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_graph_json_output() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, |m| {
+        m.add_standard_agent("reviewer", "community", "agents/reviewer.md")
+    })
+    .await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let output = project.run_agpm(&["graph", "--format", "json"])?;
+    output.assert_success();
+    let json: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    let nodes = json["nodes"].as_array().unwrap();
+    let types: Vec<_> = nodes.iter().map(|n| n["type"].as_str().unwrap()).collect();
+    assert_eq!(types, vec!["agent", "command", "snippet"]);
+    assert!(nodes.iter().all(|n| n["version"] == "v1.0.0" && n["source"] == "community"));
+    assert_eq!(json["edges"].as_array().unwrap().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_graph_without_lockfile() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.write_manifest(&ManifestBuilder::new().build()).await?;
+
+    let output = project.run_agpm(&["graph"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("agpm install"), "{}", output.stderr);
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! Integration tests for the `agpm index` command.
+
+use anyhow::Result;
+use std::process::Command;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+fn read_index(project: &TestProject) -> Result<serde_json::Value> {
+    let content = std::fs::read_to_string(project.project_path().join("agpm-index.json"))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+#[tokio::test]
+async fn test_index_source_resources() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("community").await?;
+    source_repo
+        .add_resource(
+            "agents",
+            "reviewer",
+            "---\ntitle: Code Reviewer\ndescription: Reviews code\ntags: [review]\n\
+             dependencies:\n  snippets:\n    - path: snippets/style.md\n---\n# Reviewer\n",
+        )
+        .await?;
+    source_repo.add_resource("snippets", "style", "---\ntitle: Style\n---\n# Style\n").await?;
+    source_repo.commit_all("Initial version")?;
+    source_repo.tag_version("v1.0.0")?;
+    let url = source_repo.bare_file_url(project.sources_path()).await?;
+    project.write_manifest(&ManifestBuilder::new().add_source("community", &url).build()).await?;
+
+    let output = project.run_agpm(&["index"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("community v1.0.0"), "{}", output.stdout);
+
+    let index = read_index(&project)?;
+    let source = &index["sources"]["community"];
+    assert_eq!(source["version"], "v1.0.0");
+    assert_eq!(source["commit"].as_str().unwrap().len(), 40);
+    let reviewer = &source["resources"]["agents/reviewer.md"];
+    assert_eq!(reviewer["type"], "agent");
+    assert_eq!(reviewer["title"], "Code Reviewer");
+    assert_eq!(reviewer["tags"], serde_json::json!(["review"]));
+    assert_eq!(reviewer["dependencies"]["snippets"], serde_json::json!(["snippets/style.md"]));
+    assert_eq!(source["resources"]["snippets/style.md"]["type"], "snippet");
+
+    // Nothing changed upstream, so the source is reused from the index
+    let output = project.run_agpm(&["index"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("(unchanged)"), "{}", output.stdout);
+
+    // A new release is picked up and re-read
+    source_repo.add_resource("agents", "tester", "---\ntitle: Tester\n---\n# Tester\n").await?;
+    source_repo.commit_all("Add tester")?;
+    source_repo.tag_version("v1.1.0")?;
+    let bare = url.strip_prefix("file://").unwrap();
+    let push = Command::new("git")
+        .args(["push", "-q", bare, "main", "v1.1.0"])
+        .current_dir(&source_repo.path)
+        .output()?;
+    assert!(push.status.success(), "{}", String::from_utf8_lossy(&push.stderr));
+    let output = project.run_agpm(&["index"])?;
+    output.assert_success();
+    assert!(!output.stdout.contains("(unchanged)"), "{}", output.stdout);
+    let index = read_index(&project)?;
+    assert_eq!(index["sources"]["community"]["version"], "v1.1.0");
+    assert_eq!(index["sources"]["community"]["resources"]["agents/tester.md"]["title"], "Tester");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_index_unknown_source() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.write_manifest(&ManifestBuilder::new().build()).await?;
+
+    let output = project.run_agpm(&["index", "missing"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("Source 'missing' is not defined"), "{}", output.stderr);
+    assert!(!fs::try_exists(project.project_path().join("agpm-index.json")).await?);
+    Ok(())
+}
@@ -2,7 +2,7 @@ use predicates::prelude::*;
 use std::env;
 use tokio::fs;
 
-use crate::common::{ManifestBuilder, TestProject};
+use crate::common::{ManifestBuilder, ResourceConfigBuilder, TestProject};
 use crate::fixtures::ManifestFixture;
 
 /// Test listing installed resources from lockfile
@@ -744,6 +744,52 @@ installed_at = "snippets/local-utils.md"
     assert!(output.stdout.contains("local"));
 }
 
+/// Test the exact tab-separated layout of `--parseable`
+#[tokio::test]
+async fn test_list_parseable() {
+    let project = TestProject::new().await.unwrap();
+    let manifest_content = ManifestFixture::with_local().content;
+    project.write_manifest(&manifest_content).await.unwrap();
+
+    let lockfile_content = r#"# Auto-generated lockfile - DO NOT EDIT
+version = 1
+
+[[sources]]
+name = "official"
+url = "https://github.com/example-org/agpm-official.git"
+commit = "abc123456789abcdef123456789abcdef12345678"
+fetched_at = "2024-01-01T00:00:00Z"
+
+[[agents]]
+name = "my-agent"
+source = "official"
+path = "agents/my-agent.md"
+version = "v1.0.0"
+resolved_commit = "abc123456789abcdef123456789abcdef12345678"
+checksum = "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+installed_at = ".claude/agents/my-agent.md"
+
+[[snippets]]
+name = "local-utils"
+path = "./snippets/local-utils.md"
+checksum = "sha256:local987654321fedcba987654321fedcba987654321fedcba"
+installed_at = ".agpm/snippets/local-utils.md"
+"#;
+    fs::write(project.project_path().join("agpm.lock"), lockfile_content).await.unwrap();
+
+    let output = project.run_agpm(&["list", "--parseable", "--sort", "name"]).unwrap();
+    output.assert_success();
+    assert_eq!(
+        output.stdout,
+        "snippet\tlocal-utils\t\t\t.agpm/snippets/local-utils.md\n\
+         agent\tmy-agent\tofficial\tv1.0.0\t.claude/agents/my-agent.md\n"
+    );
+
+    // Decorated formats cannot be combined with it
+    let output = project.run_agpm(&["list", "--parseable", "--format", "json"]).unwrap();
+    assert!(!output.success);
+}
+
 /// Test list help command
 #[tokio::test]
 async fn test_list_help() {
@@ -834,3 +880,168 @@ installed_at = "agents/my-agent.md"
     assert!(!output.success);
     assert!(output.stderr.contains("Invalid format") || output.stderr.contains("invalid"));
 }
+
+/// Test reconciling the lockfile with the files on disk
+#[tokio::test]
+async fn test_list_installed_reconciles_filesystem() {
+    let project = TestProject::new().await.unwrap();
+    let source_repo = project.create_source_repo("test-source").await.unwrap();
+    source_repo.add_resource("agents", "reviewer", "# Reviewer").await.unwrap();
+    source_repo.add_resource("agents", "helper", "# Helper").await.unwrap();
+    source_repo.commit_all("Initial version").unwrap();
+    source_repo.tag_version("v1.0.0").unwrap();
+
+    let manifest = ManifestBuilder::new()
+        .add_source(
+            "test-source",
+            &source_repo.bare_file_url(project.sources_path()).await.unwrap(),
+        )
+        .add_standard_agent("reviewer", "test-source", "agents/reviewer.md")
+        .add_standard_agent("helper", "test-source", "agents/helper.md")
+        .build();
+    project.write_manifest(&manifest).await.unwrap();
+    project.run_agpm(&["install"]).unwrap().assert_success();
+
+    let agents_dir = project.project_path().join(".claude/agents/agpm");
+    fs::remove_file(agents_dir.join("helper.md")).await.unwrap();
+    fs::write(agents_dir.join("stray.md"), "# Stray").await.unwrap();
+
+    let output = project.run_agpm(&["list", "--installed", "--format", "json"]).unwrap();
+    output.assert_success();
+    let json: serde_json::Value = serde_json::from_str(&output.stdout).unwrap();
+    let statuses: Vec<(&str, &str)> = json["resources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| (r["path"].as_str().unwrap(), r["status"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            (".claude/agents/agpm/helper.md", "missing"),
+            (".claude/agents/agpm/reviewer.md", "tracked"),
+            (".claude/agents/agpm/stray.md", "untracked"),
+        ]
+    );
+    assert_eq!(json["summary"]["untracked"], 1);
+
+    let output = project.run_agpm(&["list", "--tree", "--installed"]).unwrap();
+    output
+        .assert_success()
+        .assert_stdout_contains("└── agents/")
+        .assert_stdout_contains("stray.md  untracked")
+        .assert_stdout_contains("1 tracked, 1 untracked, 1 missing");
+
+    // --tree only applies to the filesystem view
+    let output = project.run_agpm(&["list", "--tree"]).unwrap();
+    assert!(!output.success);
+}
+
+/// Test that commit-pinned entries are described relative to their nearest tag
+#[tokio::test]
+async fn test_list_commit_pin_shows_nearest_tag() {
+    let project = TestProject::new().await.unwrap();
+    let source_repo = project.create_source_repo("test-source").await.unwrap();
+    source_repo.add_resource("agents", "reviewer", "# Reviewer").await.unwrap();
+    source_repo.add_resource("agents", "helper", "# Helper").await.unwrap();
+    source_repo.commit_all("Initial version").unwrap();
+    source_repo.tag_version("v1.0.0").unwrap();
+    for i in 0..3 {
+        source_repo.add_resource("agents", "reviewer", &format!("# Reviewer {i}")).await.unwrap();
+        source_repo.commit_all("Update reviewer").unwrap();
+    }
+    let sha = source_repo.git.get_commit_hash().unwrap();
+    let short_sha = &sha[..12];
+
+    let manifest = ManifestBuilder::new()
+        .add_source(
+            "test-source",
+            &source_repo.bare_file_url(project.sources_path()).await.unwrap(),
+        )
+        .add_agent("reviewer", |d| {
+            d.source("test-source").path("agents/reviewer.md").rev(short_sha)
+        })
+        .add_standard_agent("helper", "test-source", "agents/helper.md")
+        .build();
+    project.write_manifest(&manifest).await.unwrap();
+    project.run_agpm(&["install"]).unwrap().assert_success();
+
+    let output = project.run_agpm(&["list"]).unwrap();
+    output.assert_success();
+    assert!(
+        output.stdout.contains(&format!("{short_sha} (v1.0.0+3)")),
+        "Expected nearest tag annotation:\n{}",
+        output.stdout
+    );
+
+    let output = project.run_agpm(&["list", "--format", "json"]).unwrap();
+    output.assert_success();
+    let json: serde_json::Value = serde_json::from_str(&output.stdout).unwrap();
+    let items = json.as_array().unwrap();
+    let pinned = items.iter().find(|i| i["path"] == "agents/reviewer.md").unwrap();
+    assert_eq!(pinned["nearest_tag"], "v1.0.0");
+    assert_eq!(pinned["commits_since_tag"], 3);
+    let tagged = items.iter().find(|i| i["path"] == "agents/helper.md").unwrap();
+    assert!(tagged.get("nearest_tag").is_none(), "Tag-pinned entries are not described");
+}
+
+/// Test grouping a resource installed for two tools
+#[tokio::test]
+async fn test_list_duplicates_across_tools() {
+    let project = TestProject::new().await.unwrap();
+    let source_repo = project.create_source_repo("test-source").await.unwrap();
+    source_repo.add_resource("agents", "reviewer", "# Reviewer").await.unwrap();
+    source_repo.add_resource("agents", "helper", "# Helper").await.unwrap();
+    source_repo.commit_all("Initial version").unwrap();
+    source_repo.tag_version("v1.0.0").unwrap();
+
+    let manifest = ManifestBuilder::new()
+        .add_source(
+            "test-source",
+            &source_repo.bare_file_url(project.sources_path()).await.unwrap(),
+        )
+        .with_tools_config(|t| {
+            t.tool("claude-code", |tc| {
+                tc.path(".claude")
+                    .enabled(true)
+                    .agents(ResourceConfigBuilder::default().path("agents/agpm"))
+            })
+            .tool("opencode", |tc| {
+                tc.path(".opencode")
+                    .enabled(true)
+                    .agents(ResourceConfigBuilder::default().path("agent/agpm"))
+            })
+        })
+        .add_standard_agent("reviewer", "test-source", "agents/reviewer.md")
+        .add_agent("reviewer-oc", |d| {
+            d.source("test-source").path("agents/reviewer.md").version("v1.0.0").tool("opencode")
+        })
+        .add_standard_agent("helper", "test-source", "agents/helper.md")
+        .build();
+    project.write_manifest(&manifest).await.unwrap();
+    project.run_agpm(&["install"]).unwrap().assert_success();
+
+    let output =
+        project.run_agpm(&["list", "--duplicates-across-tools", "--format", "json"]).unwrap();
+    output.assert_success();
+    let json: serde_json::Value = serde_json::from_str(&output.stdout).unwrap();
+    let resources = json["resources"].as_array().unwrap();
+    assert_eq!(resources.len(), 2, "{json}");
+    let reviewer =
+        resources.iter().find(|r| r["path"] == "agents/reviewer.md").expect("reviewer group");
+    assert_eq!(reviewer["multi_tool"], true);
+    let tools: Vec<&str> =
+        reviewer["tools"].as_array().unwrap().iter().map(|t| t["tool"].as_str().unwrap()).collect();
+    assert_eq!(tools, vec!["claude-code", "opencode"]);
+    assert_eq!(json["summary"]["multi_tool"], 1);
+
+    let output = project.run_agpm(&["list", "--duplicates-across-tools"]).unwrap();
+    output
+        .assert_success()
+        .assert_stdout_contains("multi-tool")
+        .assert_stdout_contains("2 resources, 1 installed into more than one tool");
+
+    let output =
+        project.run_agpm(&["list", "--duplicates-across-tools", "--format", "yaml"]).unwrap();
+    assert!(!output.success);
+}
@@ -0,0 +1,73 @@
+//! Integration tests for the `agpm lock sort` command.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+#[tokio::test]
+async fn test_lock_sort_restores_resolved_order() -> Result<()> {
+    // Two sources with several resources of each type
+    let (project, _, community_url) = TestProject::with_community_source(
+        &[
+            ("agents/reviewer.md", "# Reviewer\n"),
+            ("agents/architect.md", "# Architect\n"),
+            ("snippets/style.md", "# Style\n"),
+        ],
+        |m| m,
+    )
+    .await?;
+    let tools = project.create_source_repo("tools").await?;
+    tools.add_resource("agents", "linter", "# Linter\n").await?;
+    tools.add_resource("commands", "deploy", "# Deploy\n").await?;
+    tools.commit_all("Initial version")?;
+    tools.tag_version("v1.0.0")?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &community_url)
+        .add_source("tools", &tools.bare_file_url(project.sources_path()).await?)
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .add_standard_agent("architect", "community", "agents/architect.md")
+        .add_standard_agent("linter", "tools", "agents/linter.md")
+        .add_standard_snippet("style", "community", "snippets/style.md")
+        .add_command("deploy", |d| d.source("tools").path("commands/deploy.md").version("v1.0.0"))
+        .build();
+    project.write_manifest(&manifest).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let lockfile_path = project.project_path().join("agpm.lock");
+    let resolved = project.read_lockfile().await?;
+
+    // Simulate a hand-merged lockfile: reversed entries and non-canonical formatting
+    let mut shuffled = project.load_lockfile()?;
+    shuffled.sources.reverse();
+    shuffled.agents.reverse();
+    let shuffled = toml::to_string(&shuffled)?;
+    assert_ne!(shuffled, resolved);
+    tokio::fs::write(&lockfile_path, &shuffled).await?;
+
+    let output = project.run_agpm(&["lock", "sort"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("Sorted"), "{}", output.stdout);
+    assert_eq!(project.read_lockfile().await?, resolved);
+
+    // A freshly resolved lockfile is already canonical
+    let output = project.run_agpm(&["lock", "sort"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("already in canonical order"), "{}", output.stdout);
+    assert_eq!(project.read_lockfile().await?, resolved);
+
+    // Resolving again produces the same bytes
+    project.run_agpm(&["install"])?.assert_success();
+    assert_eq!(project.read_lockfile().await?, resolved);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_lock_sort_requires_lockfile() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.write_manifest(&ManifestBuilder::new().build()).await?;
+
+    let output = project.run_agpm(&["lock", "sort"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("No lockfile found"), "{}", output.stderr);
+    Ok(())
+}
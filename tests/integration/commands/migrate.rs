@@ -209,6 +209,78 @@ command1 = {{ source = "community", path = "commands/command1.md", version = "v1
     Ok(())
 }
 
+/// Write a legacy CCPM project: ccpm.toml, ccpm.lock, and a .gitignore naming them.
+async fn write_legacy_ccpm_project(project: &TestProject) -> Result<()> {
+    let dir = project.project_path();
+    fs::write(
+        dir.join("ccpm.toml"),
+        "# Resolved versions are pinned in ccpm.lock\n[sources]\n\n[agents]\nhelper = \"../shared/helper.md\"\n",
+    )
+    .await?;
+    fs::write(dir.join("ccpm.lock"), "version = 1\n").await?;
+    fs::write(dir.join(".gitignore"), "node_modules/\nccpm.private.toml\n").await?;
+    Ok(())
+}
+
+/// Test that migration rewrites references to the legacy names and is idempotent
+#[tokio::test]
+async fn test_migrate_ccpm_updates_references() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    write_legacy_ccpm_project(&project).await?;
+    let dir = project.project_path();
+
+    let output = project.run_agpm(&["migrate", "--skip-install"])?;
+    output.assert_success();
+
+    let manifest = fs::read_to_string(dir.join("agpm.toml")).await?;
+    assert!(
+        manifest.starts_with("# Migrated from ccpm.toml by `agpm migrate`."),
+        "agpm.toml should note the migration:\n{manifest}"
+    );
+    assert!(manifest.contains("pinned in agpm.lock"));
+    assert!(
+        !manifest.lines().skip(1).any(|line| line.contains("ccpm")),
+        "No legacy references should remain:\n{manifest}"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join(".gitignore")).await?,
+        "node_modules/\nagpm.private.toml\n"
+    );
+    assert!(!dir.join("ccpm.lock").exists());
+
+    // Running again is a no-op
+    let output = project.run_agpm(&["migrate", "--skip-install"])?;
+    output.assert_success().assert_stdout_contains("No legacy CCPM files found");
+    assert_eq!(fs::read_to_string(dir.join("agpm.toml")).await?, manifest);
+
+    Ok(())
+}
+
+/// Test that --force replaces existing agpm.* files with the legacy ones
+#[tokio::test]
+async fn test_migrate_ccpm_force_overwrites_existing() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    write_legacy_ccpm_project(&project).await?;
+    let dir = project.project_path();
+    fs::write(dir.join("agpm.toml"), "[sources]\n").await?;
+
+    let output = project.run_agpm(&["migrate", "--skip-install"])?;
+    assert!(!output.success, "Should refuse to overwrite agpm.toml without --force");
+    assert!(output.stderr.contains("--force"), "Error should suggest --force: {}", output.stderr);
+    assert_eq!(fs::read_to_string(dir.join("agpm.toml")).await?, "[sources]\n");
+
+    let output = project.run_agpm(&["migrate", "--skip-install", "--force"])?;
+    output.assert_success();
+    assert!(!dir.join("ccpm.toml").exists());
+    assert!(fs::read_to_string(dir.join("agpm.toml")).await?.contains("helper"));
+
+    Ok(())
+}
+
 // ============================================================================
 // Section 2: Legacy Gitignore Format Migration Tests
 // ============================================================================
@@ -1,14 +1,38 @@
 //! CLI command tests
 //!
 //! Tests for AGPM CLI commands:
+//! - Bundle linting and export for source repositories
 //! - List command functionality
+//! - Lockfile canonicalization (`lock sort`)
 //! - Dependency tree visualization
+//! - Dependency graph export (DOT and JSON)
+//! - Pinning manifest constraints to the lockfile
+//! - Restoring installed resources from the lockfile (`reinstall`)
+//! - Previewing removals (`remove --dry-run`)
+//! - Checking resolution without installing (`resolve`, `--manifest-only`)
+//! - Manifest JSON Schema export (`schema`)
 //! - Validation command
+//! - Project status summary
+//! - Indexing the resources of every source
+//! - Verifying locked tags against upstream sources
+//! - Security audit of sources, pins, hooks and MCP servers
 //! - Self-upgrade functionality
 //! - Migration command (CCPM → AGPM, gitignore format)
 
+mod audit;
+mod bundle;
+mod graph;
+mod index;
 mod list;
+mod lock;
 mod migrate;
+mod pin;
+mod reinstall;
+mod remove;
+mod resolve;
+mod schema;
+mod status;
 mod tree;
 mod upgrade;
 mod validate;
+mod verify_sources;
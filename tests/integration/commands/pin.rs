@@ -0,0 +1,88 @@
+//! Integration tests for the `agpm pin` command.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject, TestSourceRepo};
+
+const FILES: &[(&str, &str)] =
+    &[("agents/reviewer.md", "# Reviewer v1.0.0\n"), ("snippets/style.md", "# Style\n")];
+
+/// A range-constrained agent and a branch-tracked snippet.
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest
+        .add_agent("reviewer", |d| {
+            d.source("community").path("agents/reviewer.md").version("^1.0.0")
+        })
+        .add_snippet("style", |d| {
+            d.source("community").path("snippets/style.md").branch("main").tool("claude-code")
+        })
+}
+
+/// Publish v1.2.0 of the reviewer and return the commit `main` points at.
+async fn release_v1_2_0(project: &TestProject, repo: &TestSourceRepo) -> Result<String> {
+    repo.add_resource("agents", "reviewer", "# Reviewer v1.2.0\n").await?;
+    repo.commit_all("Release v1.2.0")?;
+    repo.tag_version("v1.2.0")?;
+    repo.bare_file_url(project.sources_path()).await?;
+    repo.git.get_head_sha()
+}
+
+#[tokio::test]
+async fn test_pin_converts_ranges_to_exact_versions() -> Result<()> {
+    let (project, repo, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    let sha = release_v1_2_0(&project, &repo).await?;
+    let manifest_path = project.project_path().join("agpm.toml");
+    let manifest = tokio::fs::read_to_string(&manifest_path).await?;
+    project.write_manifest(&format!("# Project manifest\n{manifest}")).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let output = project.run_agpm(&["pin"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("^1.0.0 -> v1.2.0"), "{}", output.stdout);
+
+    let manifest = tokio::fs::read_to_string(&manifest_path).await?;
+    assert!(manifest.starts_with("# Project manifest\n"), "comments must be preserved");
+    assert!(manifest.contains("version = \"v1.2.0\""), "{manifest}");
+    assert!(!manifest.contains("^1.0.0"), "{manifest}");
+    assert!(manifest.contains(&format!("rev = \"{sha}\"")), "{manifest}");
+    assert!(manifest.contains("branch = \"main\""), "the tracked branch is kept: {manifest}");
+
+    // The pinned manifest resolves to the same state, and nothing is left to pin
+    let before = project.load_lockfile()?;
+    project.run_agpm(&["install"])?.assert_success();
+    let after = project.load_lockfile()?;
+    assert_eq!(before.agents[0].resolved_commit, after.agents[0].resolved_commit);
+    assert_eq!(before.snippets[0].resolved_commit, after.snippets[0].resolved_commit);
+
+    let output = project.run_agpm(&["pin"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("already pinned"), "{}", output.stdout);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pin_dry_run_leaves_manifest_unchanged() -> Result<()> {
+    let (project, repo, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    release_v1_2_0(&project, &repo).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let manifest_path = project.project_path().join("agpm.toml");
+    let original = tokio::fs::read_to_string(&manifest_path).await?;
+
+    let output = project.run_agpm(&["pin", "--dry-run"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("^1.0.0 -> v1.2.0"), "{}", output.stdout);
+    assert!(output.stdout.contains("Would pin 2 dependencies"), "{}", output.stdout);
+    assert_eq!(tokio::fs::read_to_string(&manifest_path).await?, original);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pin_requires_lockfile() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.write_manifest("[agents]\n").await?;
+
+    let output = project.run_agpm(&["pin"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("No lockfile found"), "{}", output.stderr);
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! Integration tests for the `agpm reinstall` command.
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+const FILES: &[(&str, &str)] = &[
+    ("agents/reviewer.md", "# Reviewer\n"),
+    ("snippets/style.md", "# Style\n"),
+    (
+        "mcp-servers/filesystem.json",
+        r#"{"command": "npx", "args": ["-y", "@modelcontextprotocol/server-filesystem"]}"#,
+    ),
+];
+
+/// An agent, a snippet and an MCP server.
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .add_standard_snippet("style", "community", "snippets/style.md")
+        .add_mcp_server("filesystem", |d| {
+            d.source("community").path("mcp-servers/filesystem.json").version("v1.0.0")
+        })
+}
+
+#[tokio::test]
+async fn test_reinstall_restores_corrupted_file() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let agent_path = project.project_path().join(".claude/agents/agpm/reviewer.md");
+    let snippet_path = project.project_path().join(".agpm/snippets/style.md");
+    let lockfile_before = fs::read_to_string(project.project_path().join("agpm.lock")).await?;
+
+    fs::write(&agent_path, "# Edited by hand\n").await?;
+    fs::write(&snippet_path, "# Also edited\n").await?;
+
+    let output = project.run_agpm(&["reinstall", "reviewer"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("Reinstalled reviewer"), "{}", output.stdout);
+
+    assert_eq!(fs::read_to_string(&agent_path).await?, "# Reviewer\n");
+    // Other resources are left alone and nothing is re-resolved
+    assert_eq!(fs::read_to_string(&snippet_path).await?, "# Also edited\n");
+    let lockfile_after = fs::read_to_string(project.project_path().join("agpm.lock")).await?;
+    assert_eq!(lockfile_before, lockfile_after);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reinstall_restores_mcp_server_fragment() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let mcp_path = project.project_path().join(".mcp.json");
+
+    let mut config: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&mcp_path).await?)?;
+    config["mcpServers"]["filesystem"]["command"] = "broken".into();
+    config["mcpServers"]["user-server"] = serde_json::json!({"command": "mine"});
+    fs::write(&mcp_path, serde_json::to_string_pretty(&config)?).await?;
+
+    project.run_agpm(&["reinstall", "filesystem"])?.assert_success();
+
+    let config: serde_json::Value = serde_json::from_str(&fs::read_to_string(&mcp_path).await?)?;
+    assert_eq!(config["mcpServers"]["filesystem"]["command"], "npx");
+    assert_eq!(config["mcpServers"]["user-server"]["command"], "mine");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reinstall_unknown_name() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let output = project.run_agpm(&["reinstall", "missing"])?;
+    assert!(!output.success);
+    assert!(
+        output.stderr.contains("No resource named 'missing' in agpm.lock"),
+        "{}",
+        output.stderr
+    );
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! Integration tests for `agpm remove --dry-run`.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+const FILES: &[(&str, &str)] = &[
+    (
+        "agents/reviewer.md",
+        "---\ndependencies:\n  snippets:\n    - path: ../snippets/style.md\n---\n# Reviewer\n",
+    ),
+    ("agents/helper.md", "# Helper\n"),
+    ("snippets/style.md", "# Style\n"),
+];
+
+/// A reviewer agent that pulls in a snippet, and an unrelated helper agent.
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest.add_standard_agent("reviewer", "community", "agents/reviewer.md").add_standard_agent(
+        "helper",
+        "community",
+        "agents/helper.md",
+    )
+}
+
+/// Items listed under `title:` in a dry-run preview.
+fn preview_section(stdout: &str, title: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .skip_while(|line| !line.trim().starts_with(title))
+        .skip(1)
+        .take_while(|line| line.starts_with("    "))
+        .map(|line| line.trim().to_string())
+        .filter(|item| item != "(none)")
+        .collect()
+}
+
+#[tokio::test]
+async fn test_remove_dry_run_matches_actual_removal() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let manifest_before =
+        tokio::fs::read_to_string(project.project_path().join("agpm.toml")).await?;
+    let lockfile_before = project.read_lockfile().await?;
+    let locked = project.load_lockfile()?;
+    let reviewer = locked.agents.iter().find(|a| a.lookup_name() == "reviewer").unwrap();
+    let style = &locked.snippets[0];
+    assert_eq!(style.manifest_alias, None, "style is transitive");
+
+    let output = project.run_agpm(&["remove", "dep", "agent", "reviewer", "--dry-run"])?;
+    output.assert_success();
+    let preview = output.stdout;
+    assert!(preview.contains("Would remove agent 'reviewer'"), "{preview}");
+    assert_eq!(preview_section(&preview, "Manifest entry"), vec!["[agents] reviewer"]);
+    let installed = preview_section(&preview, "Installed files");
+    assert_eq!(installed, vec![reviewer.installed_at.clone()], "{preview}");
+    let lock_entries = preview_section(&preview, "Lockfile entries");
+    assert_eq!(lock_entries, vec![format!("agent {}", reviewer.name)], "{preview}");
+    assert!(preview_section(&preview, "Merged config").is_empty(), "{preview}");
+    let orphaned = preview_section(&preview, "Orphaned transitive dependencies");
+    assert_eq!(orphaned, vec![format!("snippet {}", style.name)], "{preview}");
+
+    // Nothing changed
+    let manifest_after =
+        tokio::fs::read_to_string(project.project_path().join("agpm.toml")).await?;
+    assert_eq!(manifest_after, manifest_before);
+    assert_eq!(project.read_lockfile().await?, lockfile_before);
+    assert!(project.project_path().join(&reviewer.installed_at).exists());
+
+    // The real removal deletes exactly what the preview listed
+    project.run_agpm(&["remove", "dep", "agent", "reviewer"])?.assert_success();
+    for path in &installed {
+        assert!(!project.project_path().join(path).exists(), "{path} should be deleted");
+    }
+    let after = project.load_lockfile()?;
+    assert_eq!(locked.all_resources().len() - after.all_resources().len(), lock_entries.len());
+    assert!(after.agents.iter().all(|a| a.lookup_name() != "reviewer"));
+    assert!(
+        project.project_path().join(&style.installed_at).exists(),
+        "orphans stay until install"
+    );
+    let helper = after.agents.iter().find(|a| a.lookup_name() == "helper").unwrap();
+    assert!(project.project_path().join(&helper.installed_at).exists());
+
+    // The next install prunes the orphaned snippet
+    project.run_agpm(&["install"])?.assert_success();
+    assert!(project.load_lockfile()?.snippets.is_empty());
+    assert!(!project.project_path().join(&style.installed_at).exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_remove_source_dry_run_lists_all_entries() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let lockfile_before = project.read_lockfile().await?;
+
+    let output = project.run_agpm(&["remove", "source", "community", "--force", "--dry-run"])?;
+    output.assert_success();
+    assert_eq!(preview_section(&output.stdout, "Manifest entry"), vec!["[sources] community"]);
+    assert_eq!(preview_section(&output.stdout, "Lockfile entries").len(), 3, "{}", output.stdout);
+    assert_eq!(preview_section(&output.stdout, "Installed files").len(), 3, "{}", output.stdout);
+    assert_eq!(project.read_lockfile().await?, lockfile_before);
+    Ok(())
+}
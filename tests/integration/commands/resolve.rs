@@ -0,0 +1,114 @@
+//! Tests for `agpm resolve`, including `--manifest-only`.
+
+use anyhow::Result;
+use std::collections::BTreeSet;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+/// `(path, version, commit)` of every resource in a `resolve --format json` report.
+fn resolved(report: &serde_json::Value) -> BTreeSet<(String, String, String)> {
+    report["resources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| {
+            let field = |key: &str| r[key].as_str().unwrap_or_default().to_string();
+            (field("path"), field("version"), field("commit"))
+        })
+        .collect()
+}
+
+/// Test that manifest-only resolution follows transitive dependencies without creating worktrees
+#[tokio::test]
+async fn test_resolve_manifest_only_creates_no_worktrees() -> Result<()> {
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource("snippets", "style", "# Style v1\n").await?;
+    repo.add_resource(
+        "agents",
+        "reviewer",
+        "---\ndependencies:\n  snippets:\n    - path: ../snippets/style.md\n---\n\n# Reviewer\n",
+    )
+    .await?;
+    repo.commit_all("Initial version")?;
+    repo.tag_version("v1.0.0")?;
+    repo.add_resource("snippets", "style", "# Style v1.1\n").await?;
+    repo.commit_all("Update style")?;
+    repo.tag_version("v1.1.0")?;
+
+    let url = repo.bare_file_url(project.sources_path()).await?;
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_agent("reviewer", |d| {
+            d.source("community").path("agents/reviewer.md").version("^1.0.0")
+        })
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let output = project.run_agpm(&["resolve", "--manifest-only"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("Resolved 2 resource(s) (manifest only)"), "{}", output.stdout);
+    assert!(output.stdout.contains("community@v1.1.0"), "{}", output.stdout);
+
+    let output = project.run_agpm(&["resolve", "--manifest-only", "--format", "json"])?;
+    output.assert_success();
+    let report: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    assert_eq!(report["success"], true);
+    let manifest_only = resolved(&report);
+    let paths: Vec<(&str, &str)> =
+        manifest_only.iter().map(|(path, version, _)| (path.as_str(), version.as_str())).collect();
+    assert_eq!(paths, [("agents/reviewer.md", "v1.1.0"), ("snippets/style.md", "v1.1.0")]);
+
+    let worktrees = project.cache_path().join("worktrees");
+    let created: Vec<_> = match std::fs::read_dir(&worktrees) {
+        Ok(entries) => entries
+            .filter_map(|entry| Some(entry.ok()?.file_name().to_string_lossy().into_owned()))
+            .filter(|name| !name.starts_with('.'))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    assert!(created.is_empty(), "no worktrees expected, found {created:?}");
+    assert!(!project.project_path().join("agpm.lock").exists());
+    assert!(!project.project_path().join(".claude").exists());
+
+    // The full resolver arrives at the same versions and commits
+    let output = project.run_agpm(&["resolve", "--format", "json"])?;
+    output.assert_success();
+    let report: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    assert_eq!(report["manifest_only"], false);
+    assert_eq!(resolved(&report), manifest_only);
+    Ok(())
+}
+
+/// Test that manifest-only resolution reports a dependency missing at its resolved version
+#[tokio::test]
+async fn test_resolve_manifest_only_reports_failure() -> Result<()> {
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource(
+        "agents",
+        "reviewer",
+        "---\ndependencies:\n  snippets:\n    - path: snippets/missing.md\n---\n\n# Reviewer\n",
+    )
+    .await?;
+    repo.commit_all("Initial version")?;
+    repo.tag_version("v1.0.0")?;
+
+    let url = repo.bare_file_url(project.sources_path()).await?;
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let output = project.run_agpm(&["resolve", "--manifest-only", "--format", "json"])?;
+    assert!(!output.success);
+    let report: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    assert_eq!(report["success"], false);
+    let error = report["error"].as_str().unwrap();
+    assert!(
+        error.contains("'snippets/missing.md' does not exist in source 'community' at v1.0.0"),
+        "{error}"
+    );
+    Ok(())
+}
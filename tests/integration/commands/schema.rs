@@ -0,0 +1,48 @@
+//! Integration tests for `agpm schema`.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+fn validate(schema: &serde_json::Value, manifest: &str) -> Vec<String> {
+    let validator = jsonschema::validator_for(schema).expect("schema should compile");
+    let instance = serde_json::to_value(toml::from_str::<toml::Value>(manifest).unwrap()).unwrap();
+    validator.iter_errors(&instance).map(|e| format!("{} at {}", e, e.instance_path)).collect()
+}
+
+#[tokio::test]
+async fn test_schema_validates_project_manifest() -> Result<()> {
+    let project = TestProject::new().await?;
+    let manifest = ManifestBuilder::new()
+        .add_source("community", "https://github.com/example/community.git")
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .add_local_snippet("style", "../shared/style.md")
+        .build();
+    let manifest =
+        format!("{manifest}\n[patch.agents.reviewer]\nwhen = \"<v2.0.0\"\nmodel = \"haiku\"\n");
+    project.write_manifest(&manifest).await?;
+
+    let output = project.run_agpm(&["schema"])?;
+    output.assert_success();
+    let schema: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    assert_eq!(schema["title"], "agpm.toml");
+    assert_eq!(validate(&schema, &manifest), Vec::<String>::new());
+
+    let invalid = manifest.replace("path = \"agents/reviewer.md\"", "path = 42");
+    assert!(!validate(&schema, &invalid).is_empty(), "a numeric path should be rejected");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_schema_output_file() -> Result<()> {
+    let project = TestProject::new().await?;
+    let output = project.run_agpm(&["schema", "--output", "agpm.schema.json"])?;
+    output.assert_success();
+    assert!(output.stdout.is_empty(), "{}", output.stdout);
+
+    let written =
+        tokio::fs::read_to_string(project.project_path().join("agpm.schema.json")).await?;
+    let stdout = project.run_agpm(&["schema"])?.stdout;
+    assert_eq!(written, stdout);
+    Ok(())
+}
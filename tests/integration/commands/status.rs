@@ -0,0 +1,87 @@
+//! Integration tests for the `agpm status` command.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+const FILES: &[(&str, &str)] = &[
+    ("agents/reviewer.md", "# Reviewer\n"),
+    ("snippets/style.md", "# Style\n"),
+    ("commands/lint.md", "# Lint\n"),
+];
+
+/// A tagged agent and a branch-tracked snippet.
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .add_snippet("style", |d| d.source("community").path("snippets/style.md").branch("main"))
+}
+
+fn status_json(project: &TestProject) -> Result<serde_json::Value> {
+    let output = project.run_agpm(&["status", "--format", "json"])?;
+    output.assert_success();
+    Ok(serde_json::from_str(&output.stdout)?)
+}
+
+#[tokio::test]
+async fn test_status_clean_project() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let status = status_json(&project)?;
+    assert_eq!(status["lockfile"], "up-to-date");
+    assert_eq!(status["dependencies"]["agents"], 1);
+    assert_eq!(status["dependencies"]["snippets"], 1);
+    assert_eq!(status["locked_resources"], 2);
+    assert_eq!(status["missing_files"], serde_json::json!([]));
+    assert_eq!(
+        status["branch_tracked"],
+        serde_json::json!([{ "type": "snippet", "name": "style", "branch": "main" }])
+    );
+    assert!(status["cache"]["size_bytes"].as_u64().unwrap() > 0);
+
+    let output = project.run_agpm(&["status"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("up to date"), "{}", output.stdout);
+    assert!(output.stdout.contains("snippet 'style' tracks main"), "{}", output.stdout);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_status_reports_drift_and_missing_files() -> Result<()> {
+    let (project, _, url) = TestProject::with_community_source(FILES, dependencies).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let agent_path = project.project_path().join(".claude/agents/agpm/reviewer.md");
+    assert!(agent_path.exists());
+    tokio::fs::remove_file(&agent_path).await?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .add_snippet("style", |d| d.source("community").path("snippets/style.md").branch("main"))
+        .add_standard_command("lint", "community", "commands/lint.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let status = status_json(&project)?;
+    assert_eq!(status["lockfile"], "out-of-date");
+    assert_eq!(status["dependencies"]["commands"], 1);
+    assert_eq!(status["missing_files"], serde_json::json!([".claude/agents/agpm/reviewer.md"]));
+
+    let output = project.run_agpm(&["status"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("out of date"), "{}", output.stdout);
+    assert!(output.stdout.contains("1 missing"), "{}", output.stdout);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_status_without_lockfile() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.write_manifest(&ManifestBuilder::new().build()).await?;
+
+    let status = status_json(&project)?;
+    assert_eq!(status["lockfile"], "missing");
+    assert!(status.get("locked_resources").is_none());
+    Ok(())
+}
@@ -0,0 +1,61 @@
+//! Integration tests for the `agpm verify-sources` command.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+#[tokio::test]
+async fn test_verify_sources_flags_repointed_tag() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("community").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer\n").await?;
+    source_repo.add_resource("snippets", "style", "# Style\n").await?;
+    source_repo.commit_all("Initial version")?;
+    source_repo.tag_version("v1.0.0")?;
+    source_repo.git.ensure_branch("main")?;
+    let url = source_repo.bare_file_url(project.sources_path()).await?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .add_snippet("style", |d| d.source("community").path("snippets/style.md").branch("main"))
+        .build();
+    project.write_manifest(&manifest).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let output = project.run_agpm(&["verify-sources"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("All 1 locked tag(s)"), "{}", output.stdout);
+
+    // Force the tag onto a new commit upstream, as a force-push would
+    source_repo.add_resource("agents", "reviewer", "# Reviewer (tampered)\n").await?;
+    source_repo.commit_all("Rewrite reviewer")?;
+    let retag = std::process::Command::new("git")
+        .args(["tag", "-f", "v1.0.0"])
+        .current_dir(&source_repo.path)
+        .output()?;
+    assert!(retag.status.success());
+    let bare_path = project.sources_path().join("community.git");
+    std::fs::remove_dir_all(&bare_path)?;
+    source_repo.to_bare_repo(&bare_path).await?;
+
+    let output = project.run_agpm(&["verify-sources", "--format", "json"])?;
+    assert!(!output.success, "A moved tag should fail verification");
+    let report: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    assert_eq!(report["checked"], 1);
+    assert_eq!(report["moved"], 1);
+    let entry = &report["entries"][0];
+    assert_eq!(entry["name"], "reviewer");
+    assert_eq!(entry["tag"], "v1.0.0");
+    assert_eq!(entry["status"], "moved");
+    assert_ne!(entry["locked_commit"], entry["remote_commit"]);
+
+    let output = project.run_agpm(&["verify-sources"])?;
+    assert!(!output.success);
+    assert!(
+        output.stdout.contains("agent 'reviewer' (community@v1.0.0): tag moved"),
+        "{}",
+        output.stdout
+    );
+    Ok(())
+}
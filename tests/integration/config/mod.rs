@@ -4,6 +4,7 @@
 //! - Claude Code hooks integration
 //! - Patch/override functionality
 //! - Tool enable/disable management
+//! - Source tokens from `[token_env]` and `.env.agpm`
 //! - Version conflict detection
 
 mod conflicts;
@@ -12,4 +13,5 @@ mod hooks;
 mod patches;
 mod private_deps;
 mod test_template_vars_storage;
+mod token_env;
 mod tools;
@@ -5,6 +5,7 @@
 //! - User-level patches from agpm.private.toml
 //! - Conflict detection and resolution
 //! - Lockfile tracking of applied patches
+//! - Version-conditional patches (`when`)
 //! - CLI visibility of patched resources
 
 use anyhow::Result;
@@ -1591,3 +1592,78 @@ model = "claude-3-haiku"
         output.stderr
     );
 }
+
+/// Manifest pinning `my-agent` to `version` with a patch gated on `<v2.0.0`.
+fn conditional_patch_manifest(url: &str, version: &str) -> String {
+    format!(
+        r#"[sources]
+test = "{url}"
+
+[agents]
+my-agent = {{ source = "test", path = "agents/model-agent.md", version = "{version}" }}
+
+[patch.agents.my-agent]
+when = "<v2.0.0"
+model = "claude-3-haiku"
+"#
+    )
+}
+
+#[tokio::test]
+async fn test_conditional_patch_applies_only_below_threshold() {
+    test_config::init_test_env();
+    let project = TestProject::new().await.unwrap();
+
+    let repo = project.create_source_repo("test-repo").await.unwrap();
+    repo.add_resource("agents", "model-agent", "---\nmodel: gpt-4\n---\n# Test Agent\n")
+        .await
+        .unwrap();
+    repo.commit_all("Initial version").unwrap();
+    repo.tag_version("v1.0.0").unwrap();
+    repo.add_resource("agents", "model-agent", "---\nmodel: gpt-4\n---\n# Test Agent v2\n")
+        .await
+        .unwrap();
+    repo.commit_all("Second version").unwrap();
+    repo.tag_version("v2.1.0").unwrap();
+    let url = repo.bare_file_url(project.sources_path()).await.unwrap();
+    let installed_path = project.project_path().join(".claude/agents/agpm/model-agent.md");
+
+    // v1.0.0 satisfies `<v2.0.0`, so the patch applies
+    project.write_manifest(&conditional_patch_manifest(&url, "v1.0.0")).await.unwrap();
+    project.run_agpm(&["install"]).unwrap().assert_success();
+    let content = fs::read_to_string(&installed_path).await.unwrap();
+    assert!(content.contains("model: claude-3-haiku"), "patch should apply:\n{content}");
+    assert!(!content.contains("when"), "when is not written to the resource:\n{content}");
+    let lockfile = project.load_lockfile().unwrap();
+    let applied = &lockfile.agents[0].applied_patches;
+    assert_eq!(applied.get("model").and_then(|v| v.as_str()), Some("claude-3-haiku"));
+    assert!(!applied.contains_key("when"));
+
+    // v2.1.0 does not, so the original model is kept
+    project.write_manifest(&conditional_patch_manifest(&url, "v2.1.0")).await.unwrap();
+    project.run_agpm(&["install"]).unwrap().assert_success();
+    let content = fs::read_to_string(&installed_path).await.unwrap();
+    assert!(content.contains("model: gpt-4"), "patch should be skipped:\n{content}");
+    assert!(content.contains("# Test Agent v2"));
+    let lockfile = project.load_lockfile().unwrap();
+    assert!(lockfile.agents[0].applied_patches.is_empty());
+}
+
+#[tokio::test]
+async fn test_validate_rejects_invalid_patch_condition() {
+    test_config::init_test_env();
+    let project = TestProject::new().await.unwrap();
+    let (url, _) = create_repo_with_model_agent(&project).await.unwrap();
+
+    let manifest = conditional_patch_manifest(&url, "v1.0.0").replace("<v2.0.0", "not-a-version");
+    project.write_manifest(&manifest).await.unwrap();
+
+    let output = project.run_agpm(&["validate"]).unwrap();
+    assert!(!output.success, "validate should reject an invalid when constraint");
+    assert!(
+        output.stderr.contains("not-a-version") || output.stdout.contains("not-a-version"),
+        "Error should mention the invalid constraint. Output:\nstdout: {}\nstderr: {}",
+        output.stdout,
+        output.stderr
+    );
+}
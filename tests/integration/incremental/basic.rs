@@ -227,3 +227,67 @@ async fn test_incremental_nonexistent_dependency() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that `update --branches-only` moves branch-tracked entries and leaves
+/// tag-constrained ones at their locked versions
+#[tokio::test]
+async fn test_update_branches_only_leaves_tagged_deps() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+
+    let remote = project.create_source_repo("remote").await?;
+    remote.add_resource("agents", "tagged", "---\nname: tagged\n---\nTagged v1.0.0").await?;
+    remote.add_resource("agents", "tracking", "---\nname: tracking\n---\nTracking v1").await?;
+    remote.commit_all("Initial agents")?;
+    remote.tag_version("v1.0.0")?;
+    remote.git.ensure_branch("main")?;
+
+    let remote_url = remote.bare_file_url(project.sources_path()).await?;
+    let manifest = ManifestBuilder::new()
+        .add_source("remote", &remote_url)
+        .add_agent("tagged", |d| d.source("remote").path("agents/tagged.md").version("^v1.0.0"))
+        .add_agent("tracking", |d| d.source("remote").path("agents/tracking.md").branch("main"))
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install should succeed. Stderr: {}", output.stderr);
+    let initial = project.load_lockfile()?;
+
+    // Move both the branch and the tag range forward
+    remote.add_resource("agents", "tagged", "---\nname: tagged\n---\nTagged v1.1.0").await?;
+    remote.add_resource("agents", "tracking", "---\nname: tracking\n---\nTracking v2").await?;
+    remote.commit_all("Update agents")?;
+    remote.tag_version("v1.1.0")?;
+    let bare_path = project.sources_path().join("remote.git");
+    std::fs::remove_dir_all(&bare_path)?;
+    remote.to_bare_repo(&bare_path).await?;
+
+    let output = project.run_agpm(&["update", "--branches-only"])?;
+    assert!(output.success, "Update should succeed. Stderr: {}", output.stderr);
+
+    let updated = project.load_lockfile()?;
+    let find = |lockfile: &agpm_cli::lockfile::LockFile, name: &str| {
+        lockfile.agents.iter().find(|a| a.name == name).cloned().expect("agent not found")
+    };
+
+    let tagged = find(&updated, "agents/tagged");
+    assert_eq!(tagged.version.as_deref(), Some("v1.0.0"));
+    assert_eq!(tagged.resolved_commit, find(&initial, "agents/tagged").resolved_commit);
+
+    let tracking = find(&updated, "agents/tracking");
+    assert_ne!(tracking.resolved_commit, find(&initial, "agents/tracking").resolved_commit);
+    assert_eq!(tracking.resolved_commit.as_deref(), Some(remote.git.get_commit_hash()?.as_str()));
+
+    let content =
+        tokio::fs::read_to_string(project.project_path().join(".claude/agents/agpm/tracking.md"))
+            .await?;
+    assert!(content.contains("Tracking v2"), "Branch entry not reinstalled: {content}");
+    let content =
+        tokio::fs::read_to_string(project.project_path().join(".claude/agents/agpm/tagged.md"))
+            .await?;
+    assert!(content.contains("Tagged v1.0.0"), "Tagged entry moved: {content}");
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+//! Installation from versioned archive sources (`.tar.gz` release URLs).
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+use crate::common::TestProject;
+
+/// Write a `.tar.gz` fixture with a `pkg-1.0.0/` top-level directory, like a
+/// GitHub release tarball. Returns its `file://` URL and SHA-256.
+fn write_fixture_archive(dir: &Path, files: &[(&str, &str)]) -> Result<(String, String)> {
+    let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    for (path, content) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, format!("pkg-1.0.0/{path}"), content.as_bytes())?;
+    }
+    let bytes = builder.into_inner()?.finish()?;
+
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join("v1.0.0.tar.gz");
+    std::fs::write(&path, &bytes)?;
+    let url = format!("file://{}", path.display()).replace('\\', "/");
+    Ok((url, hex::encode(Sha256::digest(&bytes))))
+}
+
+fn fixture_files() -> Vec<(&'static str, &'static str)> {
+    vec![
+        (
+            "agents/reviewer.md",
+            "---\ndescription: Reviewer\ndependencies:\n  snippets:\n    - path: snippets/style.md\n---\n# Reviewer\n",
+        ),
+        ("snippets/style.md", "# Style guide\n"),
+    ]
+}
+
+#[tokio::test]
+async fn test_install_from_archive_source() -> Result<()> {
+    let project = TestProject::new().await?;
+    let (url, checksum) =
+        write_fixture_archive(&project.sources_path().join("releases"), &fixture_files())?;
+
+    project
+        .write_manifest(&format!(
+            r#"[sources]
+vendor = "{url}#sha256={checksum}"
+
+[agents]
+reviewer = {{ source = "vendor", path = "agents/reviewer.md" }}
+"#
+        ))
+        .await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    let installed = project.project_path().join(".claude/agents/agpm/reviewer.md");
+    assert!(tokio::fs::read_to_string(&installed).await?.contains("# Reviewer"));
+    assert!(project.project_path().join(".claude/snippets/agpm/style.md").exists());
+
+    let lockfile = project.load_lockfile()?;
+    let agent = &lockfile.agents[0];
+    assert_eq!(agent.version.as_deref(), Some("v1.0.0"));
+    assert_eq!(agent.resolved_commit.as_deref(), Some(&checksum[..40]));
+    assert_eq!(lockfile.snippets[0].resolved_commit.as_deref(), Some(&checksum[..40]));
+
+    // Reinstalling from the lockfile reuses the extracted archive
+    tokio::fs::remove_file(&installed).await?;
+    project.run_agpm(&["install", "--frozen"])?.assert_success();
+    assert!(installed.exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_archive_source_rejects_bad_checksum_and_version() -> Result<()> {
+    let project = TestProject::new().await?;
+    let (url, _) =
+        write_fixture_archive(&project.sources_path().join("releases"), &fixture_files())?;
+
+    project
+        .write_manifest(&format!(
+            r#"[sources]
+vendor = "{url}#sha256={}"
+
+[snippets]
+style = {{ source = "vendor", path = "snippets/style.md" }}
+"#,
+            "0".repeat(64)
+        ))
+        .await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("Checksum mismatch"), "{}", output.stderr);
+
+    project
+        .write_manifest(&format!(
+            r#"[sources]
+vendor = "{url}"
+
+[snippets]
+style = {{ source = "vendor", path = "snippets/style.md", version = "latest" }}
+"#
+        ))
+        .await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("pinned to version 'v1.0.0'"), "{}", output.stderr);
+    Ok(())
+}
@@ -0,0 +1,78 @@
+//! Tests for `agpm install --changed-only`.
+//!
+//! Verifies that only resources differing from the lockfile are reinstalled
+//! and that an up-to-date project sees no file writes at all.
+
+use anyhow::Result;
+use std::path::Path;
+use std::time::SystemTime;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+fn mtime(path: &Path) -> Result<SystemTime> {
+    Ok(std::fs::metadata(path)?.modified()?)
+}
+
+const FILES: &[(&str, &str)] =
+    &[("agents/alpha.md", "# Alpha\n\nAlpha agent."), ("agents/beta.md", "# Beta\n\nBeta agent.")];
+
+/// Two agents tracking a branch, so the lockfile fast path does not apply
+/// and resolution runs on every install.
+fn track_main(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest
+        .add_agent("alpha", |d| d.source("community").path("agents/alpha.md").version("main"))
+        .add_agent("beta", |d| d.source("community").path("agents/beta.md").version("main"))
+}
+
+/// An up-to-date project must not have any file rewritten, including the lockfile.
+#[tokio::test]
+async fn test_changed_only_no_writes_when_unchanged() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, track_main).await?;
+    project.run_agpm(&["install", "--quiet"])?.assert_success();
+
+    let alpha = project.project_path().join(".claude/agents/agpm/alpha.md");
+    let beta = project.project_path().join(".claude/agents/agpm/beta.md");
+    let lockfile = project.project_path().join("agpm.lock");
+    let before = [mtime(&alpha)?, mtime(&beta)?, mtime(&lockfile)?];
+    let lockfile_before = project.read_lockfile().await?;
+
+    // Make sure a rewrite would be observable even on coarse-grained filesystems
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+
+    let output = project.run_agpm(&["install", "--quiet", "--changed-only"])?;
+    assert!(output.success, "Changed-only install failed: {}", output.stderr);
+
+    let after = [mtime(&alpha)?, mtime(&beta)?, mtime(&lockfile)?];
+    assert_eq!(before, after, "No files should be written when nothing changed");
+    assert_eq!(lockfile_before, project.read_lockfile().await?);
+
+    Ok(())
+}
+
+/// Locally edited or deleted files are restored; untouched files are left alone.
+#[tokio::test]
+async fn test_changed_only_restores_modified_and_missing_files() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, track_main).await?;
+    project.run_agpm(&["install", "--quiet"])?.assert_success();
+
+    let alpha = project.project_path().join(".claude/agents/agpm/alpha.md");
+    let beta = project.project_path().join(".claude/agents/agpm/beta.md");
+    let beta_before = mtime(&beta)?;
+
+    tokio::fs::write(&alpha, "locally edited").await?;
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+
+    let output = project.run_agpm(&["install", "--quiet", "--changed-only"])?;
+    assert!(output.success, "Changed-only install failed: {}", output.stderr);
+
+    let content = tokio::fs::read_to_string(&alpha).await?;
+    assert!(content.contains("Alpha agent"), "Edited file should be restored. Got: {content}");
+    assert_eq!(beta_before, mtime(&beta)?, "Unchanged file should not be rewritten");
+
+    tokio::fs::remove_file(&beta).await?;
+    let output = project.run_agpm(&["install", "--quiet", "--changed-only"])?;
+    assert!(output.success, "Changed-only install failed: {}", output.stderr);
+    assert!(beta.exists(), "Missing file should be reinstalled");
+
+    Ok(())
+}
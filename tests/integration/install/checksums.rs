@@ -0,0 +1,78 @@
+//! Tests for the `CHECKSUMS.txt` sidecar (`installer.emit_checksums`, `--emit-checksums`).
+
+use anyhow::Result;
+use std::process::Command;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+const FILES: &[(&str, &str)] = &[
+    ("agents/reviewer.md", "---\ndescription: Reviewer\n---\n\n# Reviewer\n"),
+    ("snippets/style.md", "# Style\n"),
+];
+
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest.add_standard_agent("reviewer", "community", "agents/reviewer.md").add_standard_snippet(
+        "style",
+        "community",
+        "snippets/style.md",
+    )
+}
+
+/// Runs `sha256sum -c CHECKSUMS.txt` in the project, or `None` without `sha256sum`.
+fn sha256sum_check(project: &TestProject) -> Option<std::process::Output> {
+    Command::new("sha256sum")
+        .args(["-c", "CHECKSUMS.txt"])
+        .current_dir(project.project_path())
+        .output()
+        .ok()
+}
+
+#[tokio::test]
+async fn test_emit_checksums_flag_writes_sha256sum_sidecar() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+    assert!(!project.project_path().join("CHECKSUMS.txt").exists(), "off by default");
+
+    project.run_agpm(&["install", "--emit-checksums"])?.assert_success();
+    let sidecar = tokio::fs::read_to_string(project.project_path().join("CHECKSUMS.txt")).await?;
+    let lockfile = project.load_lockfile()?;
+    let lines: Vec<&str> = sidecar.lines().collect();
+    assert_eq!(lines.len(), 2, "{sidecar}");
+    for entry in lockfile.agents.iter().chain(&lockfile.snippets) {
+        let digest = entry.checksum.strip_prefix("sha256:").unwrap();
+        assert!(lines.contains(&format!("{digest}  {}", entry.installed_at).as_str()), "{sidecar}");
+    }
+
+    if let Some(output) = sha256sum_check(&project) {
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    }
+
+    // Tampering with an installed file is detected by the standard tool
+    let agent = project.project_path().join(&lockfile.agents[0].installed_at);
+    tokio::fs::write(&agent, "# Tampered\n").await?;
+    if let Some(output) = sha256sum_check(&project) {
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stdout).contains("FAILED"));
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_emit_checksums_covers_banners() -> Result<()> {
+    // Lockfile checksums exclude the banner and footer, the sidecar must not
+    let (project, _, _) = TestProject::with_community_source(FILES, |m| {
+        dependencies(m)
+            .add_raw("[installer]\nheader = true\nfooter = true\nemit_checksums = true\n")
+    })
+    .await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+    let sidecar = tokio::fs::read_to_string(project.project_path().join("CHECKSUMS.txt")).await?;
+    assert_eq!(sidecar.lines().count(), 2, "{sidecar}");
+
+    if let Some(output) = sha256sum_check(&project) {
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    }
+    Ok(())
+}
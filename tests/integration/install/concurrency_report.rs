@@ -0,0 +1,53 @@
+//! Tests for lock and worktree timings reported by `--concurrency-report`.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+#[tokio::test]
+async fn test_concurrency_report_counts_worktree_reuse() -> Result<()> {
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource("agents", "reviewer", "# Reviewer\n").await?;
+    repo.add_resource("agents", "writer", "# Writer\n").await?;
+    repo.commit_all("Initial version")?;
+    repo.tag_version("v1.0.0")?;
+    let url = repo.bare_file_url(project.sources_path()).await?;
+
+    // Both agents resolve to the same commit and share one worktree
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .add_standard_agent("writer", "community", "agents/writer.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let output =
+        project.run_agpm(&["install", "--quiet", "--concurrency-report", "--format", "json"])?;
+    output.assert_success();
+    let report: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    assert_eq!(report["worktrees_created"], 1, "{report}");
+    assert!(report["worktrees_reused"].as_u64().unwrap() >= 1, "{report}");
+    assert!(report["lock_acquisitions"].as_u64().unwrap() > 0, "{report}");
+    assert!(report["lock_wait_max_ms"].is_number(), "{report}");
+    assert!(report["cache_hit_rate"].as_f64().unwrap() > 0.0, "{report}");
+
+    // A second run finds the worktree from the first one on disk
+    let output = project.run_agpm(&["update", "--quiet", "--concurrency-report"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("Concurrency report:"), "{}", output.stdout);
+    assert!(output.stdout.contains("0 created"), "{}", output.stdout);
+    assert!(output.stdout.contains("Cache hits: 100%"), "{}", output.stdout);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_format_requires_a_report() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.write_manifest(&ManifestBuilder::new().build()).await?;
+
+    let output = project.run_agpm(&["install", "--format", "json"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("--since"), "{}", output.stderr);
+    Ok(())
+}
@@ -0,0 +1,50 @@
+//! Tests for concurrent `agpm install` runs in the same project.
+
+use crate::common::{ManifestBuilder, TestProject};
+use anyhow::Result;
+
+/// Two installs started at once serialize on the project lock and leave a valid lockfile.
+#[tokio::test]
+async fn test_concurrent_installs_do_not_corrupt_lockfile() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("test-source").await?;
+    for name in ["alpha", "beta", "gamma", "delta"] {
+        source_repo.add_resource("agents", name, &format!("# {name}")).await?;
+    }
+    source_repo.add_resource("snippets", "shared", "# Shared").await?;
+    source_repo.commit_all("Add resources")?;
+    source_repo.tag_version("v1.0.0")?;
+    let repo_url = source_repo.bare_file_url(project.sources_path()).await?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("test-source", &repo_url)
+        .add_standard_agent("alpha", "test-source", "agents/alpha.md")
+        .add_standard_agent("beta", "test-source", "agents/beta.md")
+        .add_standard_agent("gamma", "test-source", "agents/gamma.md")
+        .add_standard_agent("delta", "test-source", "agents/delta.md")
+        .add_snippet("shared", |d| {
+            d.source("test-source").path("snippets/shared.md").version("v1.0.0")
+        })
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let (first, second) = tokio::join!(
+        project.run_agpm_async(&["install", "--quiet"]),
+        project.run_agpm_async(&["install", "--quiet"]),
+    );
+    let (first, second) = (first?, second?);
+    assert!(first.success, "First install failed:\n{}", first.stderr);
+    assert!(second.success, "Second install failed:\n{}", second.stderr);
+
+    let lockfile = project.load_lockfile()?;
+    assert_eq!(lockfile.agents.len(), 4, "Lockfile agents: {:?}", lockfile.agents);
+    assert_eq!(lockfile.snippets.len(), 1);
+    assert_eq!(lockfile.sources.len(), 1);
+
+    // The lockfile is complete and consistent with what is on disk
+    project.run_agpm(&["install", "--frozen", "--verify"])?.assert_success();
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//! Tests for `sha256` content pins on dependencies.
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+
+use crate::common::TestProject;
+
+const CONTENT: &str = "# Reviewer\n\nReviews code.\n";
+
+fn sha256_hex(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+/// `[agents]` section with the v1.0.0 reviewer pinned to `sha256`.
+fn pinned_reviewer(sha256: &str) -> String {
+    format!(
+        "[agents]\nreviewer = {{ source = \"community\", path = \"agents/reviewer.md\", \
+         version = \"v1.0.0\", sha256 = \"{sha256}\" }}\n"
+    )
+}
+
+#[tokio::test]
+async fn test_matching_sha256_installs() -> Result<()> {
+    let pin = pinned_reviewer(&format!("sha256:{}", sha256_hex(CONTENT)));
+    let (project, _, _) =
+        TestProject::with_community_source(&[("agents/reviewer.md", CONTENT)], |m| m.add_raw(&pin))
+            .await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    let installed = project.project_path().join(".claude/agents/agpm/reviewer.md");
+    assert_eq!(tokio::fs::read_to_string(&installed).await?, CONTENT);
+    let lockfile = project.load_lockfile()?;
+    assert_eq!(lockfile.agents[0].checksum, format!("sha256:{}", sha256_hex(CONTENT)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mismatching_sha256_fails_without_writing() -> Result<()> {
+    let wrong = sha256_hex("something else");
+    let pin = pinned_reviewer(&wrong);
+    let (project, _, _) =
+        TestProject::with_community_source(&[("agents/reviewer.md", CONTENT)], |m| m.add_raw(&pin))
+            .await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success, "install must fail on a content hash mismatch");
+    assert!(output.stderr.contains("Content hash mismatch"), "{}", output.stderr);
+    assert!(output.stderr.contains(&format!("expected: sha256:{wrong}")), "{}", output.stderr);
+    assert!(
+        output.stderr.contains(&format!("actual:   sha256:{}", sha256_hex(CONTENT))),
+        "{}",
+        output.stderr
+    );
+    assert!(!project.project_path().join(".claude/agents/agpm/reviewer.md").exists());
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//! Tests for deprecation warnings driven by resource frontmatter.
+//!
+//! Verifies that resources marked `deprecated: true` upstream are reported during
+//! install, flagged in the lockfile and `agpm list`, and fail `validate --strict`.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+const DEPRECATED_AGENT: &str = r"---
+description: Legacy reviewer
+deprecated: true
+deprecated_message: No longer maintained
+replacement: agents/reviewer.md
+---
+
+# Legacy Reviewer
+";
+
+const FILES: &[(&str, &str)] = &[
+    ("agents/legacy.md", DEPRECATED_AGENT),
+    ("agents/reviewer.md", "# Reviewer\n\nCurrent reviewer."),
+];
+
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest.add_standard_agent("legacy", "community", "agents/legacy.md").add_standard_agent(
+        "reviewer",
+        "community",
+        "agents/reviewer.md",
+    )
+}
+
+#[tokio::test]
+async fn test_install_warns_about_deprecated_resources() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install failed: {}", output.stderr);
+    assert!(
+        output.stderr.contains(
+            "agent 'legacy' is deprecated: No longer maintained (use 'agents/reviewer.md' instead)"
+        ),
+        "Expected deprecation warning. Stderr: {}",
+        output.stderr
+    );
+    assert!(!output.stderr.contains("'reviewer' is deprecated"));
+
+    let lockfile = project.read_lockfile().await?;
+    assert!(
+        lockfile.contains("deprecated = true"),
+        "Lockfile should flag deprecation:\n{lockfile}"
+    );
+    assert_eq!(lockfile.matches("deprecated = true").count(), 1);
+
+    let output = project.run_agpm(&["list", "--format", "json"])?;
+    assert!(output.success, "List failed: {}", output.stderr);
+    let items: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    let deprecated: Vec<&str> = items
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|item| item["deprecated"] == true)
+        .map(|item| item["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(deprecated, vec!["agents/legacy"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_validate_strict_fails_on_deprecated_resources() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+
+    let output = project.run_agpm(&["install", "--quiet"])?;
+    assert!(output.success, "Install failed: {}", output.stderr);
+
+    let output = project.run_agpm(&["validate", "--check-lock"])?;
+    assert!(output.success, "Non-strict validate should pass: {}", output.stderr);
+    assert!(output.stdout.contains("agent 'legacy' is deprecated"), "Stdout: {}", output.stdout);
+
+    let output = project.run_agpm(&["validate", "--check-lock", "--strict"])?;
+    assert!(!output.success, "Strict validate should fail on deprecated resources");
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Tests for reporting byte-identical installed resources with `--report-duplicates`.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+#[tokio::test]
+async fn test_report_duplicates_groups_identical_content() -> Result<()> {
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource("agents", "reviewer", "# Reviewer\n").await?;
+    repo.add_resource("agents", "writer", "# Writer\n").await?;
+    repo.commit_all("Initial version")?;
+    repo.tag_version("v1.0.0")?;
+    let url = repo.bare_file_url(project.sources_path()).await?;
+
+    // `reviewer` and `review` install the same upstream file under different names
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .add_standard_agent("writer", "community", "agents/writer.md")
+        .add_command("review", |d| {
+            d.source("community").path("agents/reviewer.md").version("v1.0.0")
+        })
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let output = project.run_agpm(&["install", "--quiet", "--report-duplicates"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("Duplicate content:"), "{}", output.stdout);
+    assert!(output.stdout.contains("(2 copies)"), "{}", output.stdout);
+    assert!(output.stdout.contains(".claude/agents/agpm/reviewer.md"), "{}", output.stdout);
+    assert!(output.stdout.contains(".claude/commands/agpm/reviewer.md"), "{}", output.stdout);
+    assert!(!output.stdout.contains("writer"), "{}", output.stdout);
+    assert!(
+        output.stdout.contains("Total: 1 duplicate sets, 1 redundant files"),
+        "{}",
+        output.stdout
+    );
+
+    // Without duplicates the report says so
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+    let output = project.run_agpm(&["install", "--quiet", "--report-duplicates"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("No duplicate resource content"), "{}", output.stdout);
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//! Tests for tools declared in resource frontmatter (`tool: opencode`).
+
+use anyhow::Result;
+use std::fs as sync_fs;
+
+use crate::common::{ManifestBuilder, ResourceConfigBuilder, TestProject};
+
+fn with_both_tools(builder: ManifestBuilder) -> ManifestBuilder {
+    builder.with_tools_config(|t| {
+        t.tool("claude-code", |tc| {
+            tc.path(".claude")
+                .enabled(true)
+                .agents(ResourceConfigBuilder::default().path("agents/agpm"))
+        })
+        .tool("opencode", |tc| {
+            tc.path(".opencode")
+                .enabled(true)
+                .agents(ResourceConfigBuilder::default().path("agent/agpm"))
+        })
+    })
+}
+
+/// Test that a dependency without `tool` uses the tool declared in frontmatter
+#[tokio::test]
+async fn test_frontmatter_tool_replaces_default() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("community").await?;
+    let agents_dir = source_repo.path.join("agents");
+    sync_fs::create_dir_all(&agents_dir)?;
+    sync_fs::write(
+        agents_dir.join("helper.md"),
+        "---\ntitle: Helper\ntool: opencode\n---\n\nAn OpenCode helper.\n",
+    )?;
+    sync_fs::write(agents_dir.join("reviewer.md"), "---\ntitle: Reviewer\n---\n\nA reviewer.\n")?;
+    source_repo.commit_all("Add agents")?;
+    source_repo.tag_version("v1.0.0")?;
+
+    let url = source_repo.bare_file_url(project.sources_path()).await?;
+    let manifest = with_both_tools(ManifestBuilder::new().add_source("community", &url))
+        .add_agent("helper", |d| d.source("community").path("agents/helper.md").version("v1.0.0"))
+        .add_agent("reviewer", |d| {
+            d.source("community").path("agents/reviewer.md").version("v1.0.0")
+        })
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    let lockfile = project.load_lockfile()?;
+    let helper = lockfile.agents.iter().find(|a| a.name.contains("helper")).unwrap();
+    assert_eq!(helper.tool.as_deref(), Some("opencode"));
+    assert!(helper.installed_at.starts_with(".opencode/"), "{}", helper.installed_at);
+    assert!(project.project_path().join(&helper.installed_at).exists());
+
+    let reviewer = lockfile.agents.iter().find(|a| a.name.contains("reviewer")).unwrap();
+    assert_eq!(reviewer.tool.as_deref(), Some("claude-code"));
+
+    // The lockfile stays up to date with the manifest
+    project.run_agpm(&["install", "--frozen"])?.assert_success();
+    Ok(())
+}
+
+/// Test that an explicit `tool` that contradicts the frontmatter is rejected
+#[tokio::test]
+async fn test_frontmatter_tool_conflicts_with_manifest() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("community").await?;
+    let agents_dir = source_repo.path.join("agents");
+    sync_fs::create_dir_all(&agents_dir)?;
+    sync_fs::write(
+        agents_dir.join("helper.md"),
+        "---\ntitle: Helper\ntool: opencode\n---\n\nAn OpenCode helper.\n",
+    )?;
+    source_repo.commit_all("Add agent")?;
+    source_repo.tag_version("v1.0.0")?;
+
+    let url = source_repo.bare_file_url(project.sources_path()).await?;
+    let manifest = with_both_tools(ManifestBuilder::new().add_source("community", &url))
+        .add_agent("helper", |d| {
+            d.source("community").path("agents/helper.md").version("v1.0.0").tool("claude-code")
+        })
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success);
+    assert!(
+        output.stderr.contains(
+            "agent 'helper' sets tool 'claude-code', but its frontmatter declares 'opencode'"
+        ),
+        "{}",
+        output.stderr
+    );
+    assert!(!project.project_path().join("agpm.lock").exists());
+    Ok(())
+}
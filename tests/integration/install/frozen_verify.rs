@@ -0,0 +1,73 @@
+//! Tests for verifying installed files against the lockfile (`--frozen --verify`).
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+const FILES: &[(&str, &str)] =
+    &[("agents/reviewer.md", "# Reviewer\n"), ("commands/deploy.md", "# Deploy\n")];
+
+/// An agent and a command.
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest.add_standard_agent("reviewer", "community", "agents/reviewer.md").add_standard_command(
+        "deploy",
+        "community",
+        "commands/deploy.md",
+    )
+}
+
+#[tokio::test]
+async fn test_frozen_verify_passes_for_untouched_install() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let lockfile = project.read_lockfile().await?;
+
+    let output = project.run_agpm(&["install", "--frozen", "--verify"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("Installed files match agpm.lock"), "{}", output.stdout);
+    assert_eq!(project.read_lockfile().await?, lockfile);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_frozen_verify_detects_tampered_and_missing_files() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let agent = project.project_path().join(".claude/agents/agpm/reviewer.md");
+    let command = project.project_path().join(".claude/commands/agpm/deploy.md");
+    tokio::fs::write(&agent, "# Reviewer\nTampered\n").await?;
+    tokio::fs::remove_file(&command).await?;
+
+    let output = project.run_agpm(&["install", "--frozen", "--verify"])?;
+    assert!(!output.success);
+    assert!(
+        output.stderr.contains("modified: .claude/agents/agpm/reviewer.md"),
+        "{}",
+        output.stderr
+    );
+    assert!(
+        output.stderr.contains("missing: .claude/commands/agpm/deploy.md"),
+        "{}",
+        output.stderr
+    );
+
+    // Verification never repairs anything
+    assert_eq!(tokio::fs::read_to_string(&agent).await?, "# Reviewer\nTampered\n");
+    assert!(!command.exists());
+
+    // A regular frozen install restores the locked content
+    project.run_agpm(&["install", "--frozen"])?.assert_success();
+    project.run_agpm(&["install", "--frozen", "--verify"])?.assert_success();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_verify_requires_frozen() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let output = project.run_agpm(&["install", "--verify"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("--frozen"), "{}", output.stderr);
+    Ok(())
+}
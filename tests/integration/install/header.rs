@@ -0,0 +1,155 @@
+//! Tests for the `installer.header` banner and `installer.footer` block on installed files.
+//!
+//! Verifies that Markdown and JSON resources get a "Managed by AGPM" banner in a
+//! format that keeps them valid, that Markdown resources get a provenance footer,
+//! and that neither ever affects checksums.
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+const AGENT: &str = "---\ndescription: Reviewer\n---\n\n# Reviewer\n";
+const SETTINGS: &str = "{\n  \"model\": \"sonnet\"\n}\n";
+
+const FILES: &[(&str, &str)] =
+    &[("agents/reviewer.md", AGENT), ("snippets/settings.json", SETTINGS)];
+
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest.add_standard_agent("reviewer", "community", "agents/reviewer.md").add_standard_snippet(
+        "settings",
+        "community",
+        "snippets/settings.json",
+    )
+}
+
+fn installed_path(lockfile: &str, name: &str) -> String {
+    let value: toml::Value = toml::from_str(lockfile).unwrap();
+    value
+        .as_table()
+        .unwrap()
+        .values()
+        .filter_map(toml::Value::as_array)
+        .flatten()
+        .find(|entry| entry["name"].as_str().is_some_and(|n| n.ends_with(name)))
+        .and_then(|entry| entry["installed_at"].as_str())
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_header_added_to_markdown_and_json() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, |m| {
+        dependencies(m).add_raw("[installer]\nheader = true\n")
+    })
+    .await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install failed: {}", output.stderr);
+    let lockfile = project.read_lockfile().await?;
+
+    let agent =
+        fs::read_to_string(project.project_path().join(installed_path(&lockfile, "reviewer")))
+            .await?;
+    assert_eq!(
+        agent,
+        "---\ndescription: Reviewer\n---\n<!-- Managed by AGPM — do not edit. Source: community@v1.0.0 (agents/reviewer.md) -->\n\n# Reviewer\n"
+    );
+
+    let settings =
+        fs::read_to_string(project.project_path().join(installed_path(&lockfile, "settings")))
+            .await?;
+    let json: serde_json::Value = serde_json::from_str(&settings)?;
+    assert_eq!(json["model"], "sonnet");
+    assert!(json["_agpm"].as_str().unwrap().starts_with("Managed by AGPM — do not edit"));
+
+    let output = project.run_agpm(&["validate", "--check-lock"])?;
+    assert!(output.success, "Validate failed: {}\n{}", output.stdout, output.stderr);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_header_does_not_change_checksums() -> Result<()> {
+    let (plain, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    let output = plain.run_agpm(&["install"])?;
+    assert!(output.success, "Install failed: {}", output.stderr);
+
+    let (project, _, _) = TestProject::with_community_source(FILES, |m| {
+        dependencies(m).add_raw("[installer]\nheader = true\n")
+    })
+    .await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install failed: {}", output.stderr);
+
+    let checksums = |lockfile: &str| -> Vec<String> {
+        lockfile.lines().filter(|line| line.starts_with("checksum")).map(String::from).collect()
+    };
+    let with_header = project.read_lockfile().await?;
+    assert_eq!(checksums(&with_header), checksums(&plain.read_lockfile().await?));
+
+    // Disabling the option removes the banner without touching the lockfile checksums
+    let manifest = fs::read_to_string(project.project_path().join("agpm.toml")).await?;
+    project.write_manifest(&manifest.replace("header = true", "header = false")).await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install failed: {}", output.stderr);
+
+    let agent =
+        fs::read_to_string(project.project_path().join(installed_path(&with_header, "reviewer")))
+            .await?;
+    assert_eq!(agent, AGENT);
+    assert_eq!(checksums(&project.read_lockfile().await?), checksums(&with_header));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_footer_records_version_and_commit() -> Result<()> {
+    let (plain, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    let output = plain.run_agpm(&["install"])?;
+    assert!(output.success, "Install failed: {}", output.stderr);
+
+    let (project, _, _) = TestProject::with_community_source(FILES, |m| {
+        dependencies(m).add_raw("[installer]\nfooter = true\n")
+    })
+    .await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install failed: {}", output.stderr);
+    let lockfile = project.read_lockfile().await?;
+    let commit = project.load_lockfile()?.agents[0].resolved_commit.clone().unwrap();
+
+    let agent =
+        fs::read_to_string(project.project_path().join(installed_path(&lockfile, "reviewer")))
+            .await?;
+    assert_eq!(
+        agent,
+        format!(
+            "{AGENT}\n<!-- agpm:footer\nsource: community\nversion: v1.0.0\ncommit: {commit}\npath: agents/reviewer.md\n-->\n"
+        )
+    );
+
+    // JSON files have no comment syntax and are left without a footer
+    let settings =
+        fs::read_to_string(project.project_path().join(installed_path(&lockfile, "settings")))
+            .await?;
+    assert_eq!(settings, SETTINGS);
+
+    // The footer is excluded from checksums
+    let checksums = |lockfile: &str| -> Vec<String> {
+        lockfile.lines().filter(|line| line.starts_with("checksum")).map(String::from).collect()
+    };
+    assert_eq!(checksums(&lockfile), checksums(&plain.read_lockfile().await?));
+    let output = project.run_agpm(&["validate", "--check-lock"])?;
+    assert!(output.success, "Validate failed: {}\n{}", output.stdout, output.stderr);
+
+    // Reinstalling leaves the file and lockfile as they are
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install failed: {}", output.stderr);
+    assert_eq!(checksums(&project.read_lockfile().await?), checksums(&lockfile));
+    let reinstalled =
+        fs::read_to_string(project.project_path().join(installed_path(&lockfile, "reviewer")))
+            .await?;
+    assert_eq!(reinstalled, agent);
+
+    Ok(())
+}
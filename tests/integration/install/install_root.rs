@@ -0,0 +1,106 @@
+//! Tests for `--install-root`, which remaps every tool directory under one root.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+const HOOK: &str = r#"{
+  "events": ["SessionStart"],
+  "type": "command",
+  "command": "echo 'Session started'"
+}"#;
+const MCP_SERVER: &str = r#"{
+  "command": "npx",
+  "args": ["@test/server"]
+}"#;
+
+const FILES: &[(&str, &str)] = &[
+    ("agents/reviewer.md", "# Reviewer\n"),
+    ("hooks/session.json", HOOK),
+    ("mcp-servers/db.json", MCP_SERVER),
+];
+
+/// An agent, a hook and an MCP server.
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .add_hook("session", |d| d.source("community").path("hooks/session.json").version("v1.0.0"))
+        .add_mcp_server("db", |d| {
+            d.source("community").path("mcp-servers/db.json").version("v1.0.0")
+        })
+}
+
+fn assert_installed_under(root: &Path, claude_dir: &str) {
+    let claude = root.join(claude_dir);
+    assert!(
+        claude.join("agents/agpm/reviewer.md").exists(),
+        "agent missing under {}",
+        claude.display()
+    );
+    assert!(
+        claude.join("settings.local.json").exists(),
+        "hooks missing under {}",
+        claude.display()
+    );
+    assert!(root.join(".mcp.json").exists(), "MCP config missing under {}", root.display());
+}
+
+#[tokio::test]
+async fn test_install_under_absolute_root() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    let root = project.project_path().parent().unwrap().join("image-root");
+
+    project
+        .run_agpm(&["--install-root", root.to_str().unwrap(), "install", "--no-lock"])?
+        .assert_success();
+
+    assert_installed_under(&root, ".claude");
+    assert!(!project.project_path().join(".claude").exists());
+    assert!(!project.project_path().join(".mcp.json").exists());
+    assert!(!project.project_path().join("agpm.lock").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_install_root_composes_with_tool_path_override() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, |m| {
+        dependencies(m).add_raw("[tools.claude-code]\npath = \"config/claude\"\n")
+    })
+    .await?;
+
+    project.run_agpm(&["--install-root", "build", "install"])?.assert_success();
+
+    // A relative root is taken relative to the project directory
+    let root = project.project_path().join("build");
+    assert_installed_under(&root, "config/claude");
+
+    // The lockfile records the remapped paths
+    let lockfile = project.load_lockfile()?;
+    assert_eq!(lockfile.agents[0].installed_at, "build/config/claude/agents/agpm/reviewer.md");
+    assert_eq!(lockfile.hooks[0].installed_at, "build/config/claude/settings.local.json");
+    assert_eq!(lockfile.mcp_servers[0].installed_at, "build/.mcp.json");
+
+    // Without the flag, the next install moves everything back into the project
+    project.run_agpm(&["install"])?.assert_success();
+    assert_installed_under(project.project_path(), "config/claude");
+    let lockfile = project.load_lockfile()?;
+    assert_eq!(lockfile.agents[0].installed_at, "config/claude/agents/agpm/reviewer.md");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_install_root_rejected_with_frozen() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let output = project.run_agpm(&["--install-root", "build", "install", "--frozen"])?;
+    assert!(!output.success);
+    assert!(
+        output.stderr.contains("--install-root cannot be combined with --frozen"),
+        "{}",
+        output.stderr
+    );
+    assert!(!project.project_path().join("build").exists());
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! Tests for the `[install]` section switching whole resource types off.
+//!
+//! Disabled types stay in the lockfile with `install = false` unless
+//! `--prune-disabled` is given, and are never merged into tool configuration.
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+const HOOK: &str = r#"{
+  "events": ["SessionStart"],
+  "type": "command",
+  "command": "echo 'Session started'"
+}"#;
+const MCP_SERVER: &str = r#"{
+  "command": "npx",
+  "args": ["@test/server"]
+}"#;
+
+const FILES: &[(&str, &str)] = &[
+    ("agents/reviewer.md", "# Reviewer\n"),
+    ("hooks/session.json", HOOK),
+    ("mcp-servers/db.json", MCP_SERVER),
+];
+
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .add_hook("session", |d| d.source("community").path("hooks/session.json").version("v1.0.0"))
+        .add_mcp_server("db", |d| {
+            d.source("community").path("mcp-servers/db.json").version("v1.0.0")
+        })
+}
+
+#[tokio::test]
+async fn test_disabled_types_recorded_but_not_installed() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, |m| {
+        dependencies(m).add_raw("[install]\nhooks = false\nmcp-servers = false\n")
+    })
+    .await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    assert!(project.project_path().join(".claude/agents/agpm/reviewer.md").exists());
+    assert!(!project.project_path().join(".claude/settings.local.json").exists());
+    assert!(!project.project_path().join(".mcp.json").exists());
+
+    let lockfile = project.load_lockfile()?;
+    assert_eq!(lockfile.agents[0].install, None);
+    assert_eq!(lockfile.hooks[0].install, Some(false));
+    assert_eq!(lockfile.mcp_servers[0].install, Some(false));
+
+    // The lockfile stays valid for a frozen install
+    project.run_agpm(&["install", "--frozen"])?.assert_success();
+    assert!(!project.project_path().join(".mcp.json").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prune_disabled_omits_types_from_lockfile() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, |m| {
+        dependencies(m).add_raw("[install]\nhooks = false\n")
+    })
+    .await?;
+
+    project.run_agpm(&["install", "--prune-disabled"])?.assert_success();
+
+    let lockfile = project.load_lockfile()?;
+    assert!(lockfile.hooks.is_empty());
+    assert_eq!(lockfile.mcp_servers.len(), 1);
+    assert!(!project.project_path().join(".claude/settings.local.json").exists());
+    assert!(project.project_path().join(".mcp.json").exists());
+
+    // Without the flag the hook comes back as not installed
+    project.run_agpm(&["install"])?.assert_success();
+    assert_eq!(project.load_lockfile()?.hooks[0].install, Some(false));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reenabling_type_installs_it() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, |m| {
+        dependencies(m).add_raw("[install]\nhooks = false\n")
+    })
+    .await?;
+    project.run_agpm(&["install"])?.assert_success();
+    assert!(!project.project_path().join(".claude/settings.local.json").exists());
+
+    let manifest = fs::read_to_string(project.project_path().join("agpm.toml")).await?;
+    project.write_manifest(&manifest.replace("hooks = false", "hooks = true")).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let settings =
+        fs::read_to_string(project.project_path().join(".claude/settings.local.json")).await?;
+    assert!(settings.contains("Session started"));
+    assert_ne!(project.load_lockfile()?.hooks[0].install, Some(false));
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! Tests for `--manifest-path` run from a directory outside the project.
+
+use anyhow::Result;
+use std::path::PathBuf;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+const FILES: &[(&str, &str)] = &[("agents/reviewer.md", "---\nmodel: opus\n---\n# Reviewer\n")];
+
+/// A Git agent and a local agent.
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .add_local_agent("helper", "local/agents/helper.md")
+}
+
+/// Add the local agent and a private patch, and return an unrelated sibling
+/// directory to run commands from.
+async fn add_local_files(project: &TestProject) -> Result<PathBuf> {
+    project.create_local_resource("local/agents/helper.md", "# Helper\n").await?;
+    fs::write(
+        project.project_path().join("agpm.private.toml"),
+        "[patch.agents.reviewer]\nmodel = \"haiku\"\n",
+    )
+    .await?;
+
+    let elsewhere = project.project_path().parent().unwrap().join("elsewhere");
+    fs::create_dir_all(&elsewhere).await?;
+    Ok(elsewhere)
+}
+
+#[tokio::test]
+async fn test_install_with_manifest_path_from_other_directory() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    let elsewhere = add_local_files(&project).await?;
+    let manifest_path = project.project_path().join("agpm.toml");
+
+    project
+        .run_agpm_in_dir(
+            &elsewhere,
+            &["--manifest-path", manifest_path.to_str().unwrap(), "install"],
+            &[],
+        )?
+        .assert_success();
+
+    // Lockfile and resources land next to the manifest, not in the working directory
+    assert!(project.project_path().join("agpm.lock").exists());
+    let agents = project.project_path().join(".claude/agents/agpm");
+    assert_eq!(fs::read_to_string(agents.join("helper.md")).await?, "# Helper\n");
+    let reviewer = fs::read_to_string(agents.join("reviewer.md")).await?;
+    assert!(reviewer.contains("model: haiku"), "private patch should apply: {reviewer}");
+    assert_eq!(std::fs::read_dir(&elsewhere)?.count(), 0, "nothing is written to the CWD");
+
+    let lockfile = project.load_lockfile()?;
+    assert_eq!(lockfile.agents.len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_manifest_path_relative_and_directory() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    let elsewhere = add_local_files(&project).await?;
+
+    project
+        .run_agpm_in_dir(&elsewhere, &["--manifest-path", "../project/agpm.toml", "install"], &[])?
+        .assert_success();
+    assert!(project.project_path().join("agpm.lock").exists());
+
+    // A directory is taken to mean the agpm.toml inside it
+    let output =
+        project.run_agpm_in_dir(&elsewhere, &["--manifest-path", "../project", "list"], &[])?;
+    output.assert_success();
+    assert!(output.stdout.contains("reviewer"), "{}", output.stdout);
+    assert!(output.stdout.contains("helper"), "{}", output.stdout);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_manifest_path_missing_file() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    let elsewhere = add_local_files(&project).await?;
+
+    let output = project.run_agpm_in_dir(
+        &elsewhere,
+        &["--manifest-path", "missing/agpm.toml", "install"],
+        &[],
+    )?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("Manifest not found at"), "{}", output.stderr);
+    assert!(output.stderr.contains("missing"), "{}", output.stderr);
+    assert!(!project.project_path().join("agpm.lock").exists());
+    Ok(())
+}
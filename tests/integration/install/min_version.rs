@@ -0,0 +1,64 @@
+//! Tests for resources that declare `min_agpm_version` in their frontmatter.
+
+use anyhow::Result;
+
+use crate::common::TestProject;
+
+const FUTURE_AGENT: &str = "---\nmin_agpm_version: 999.0.0\n---\n# Future\n";
+
+#[tokio::test]
+async fn test_resource_requiring_newer_agpm_fails_install() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.create_local_resource("local/agents/future.md", FUTURE_AGENT).await?;
+    project
+        .create_local_resource(
+            "local/agents/current.md",
+            "---\nmin_agpm_version: 0.1.0\n---\n# Current\n",
+        )
+        .await?;
+    project
+        .write_manifest(
+            r#"[agents]
+future = { path = "local/agents/future.md" }
+current = { path = "local/agents/current.md" }
+"#,
+        )
+        .await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success, "install should fail: {}", output.stdout);
+    assert!(
+        output.stderr.contains("agent 'future' requires AGPM 999.0.0 or newer"),
+        "{}",
+        output.stderr
+    );
+    assert!(!output.stderr.contains("'current'"), "{}", output.stderr);
+    assert!(!project.project_path().join(".claude/agents/agpm/future.md").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_warn_policy_installs_resource_requiring_newer_agpm() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.create_local_resource("local/agents/future.md", FUTURE_AGENT).await?;
+    project
+        .write_manifest(
+            r#"[installer]
+min_version_policy = "warn"
+
+[agents]
+future = { path = "local/agents/future.md" }
+"#,
+        )
+        .await?;
+
+    let output = project.run_agpm(&["install"])?;
+    output.assert_success();
+    assert!(
+        output.stderr.contains("Warning: agent 'future' requires AGPM 999.0.0 or newer"),
+        "{}",
+        output.stderr
+    );
+    assert!(project.project_path().join(".claude/agents/agpm/future.md").exists());
+    Ok(())
+}
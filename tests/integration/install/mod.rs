@@ -2,19 +2,61 @@
 //!
 //! Tests for resource installation and deployment:
 //! - Basic installation workflows (formerly deploy.rs)
+//! - Versioned archive sources
 //! - Install field and content embedding
+//! - `sha256` content pins
+//! - Resource types switched off in `[install]` (`--prune-disabled`)
 //! - Incremental dependency addition
+//! - Remapping tool directories under `--install-root`
+//! - Explicit `--manifest-path` from another working directory
 //! - Multi-artifact installation
 //! - Multi-resource management
 //! - Artifact cleanup and removal
+//! - Removing everything the lockfile no longer records (`--prune`)
+//! - Concurrent installs serializing on the project lock
+//! - Lock contention and worktree reuse metrics (`--concurrency-report`)
 //! - Progress display functionality
+//! - Install ordering with dependency `priority`
 //! - Mutable dependency reinstallation scenarios
+//! - Changed-only reinstallation (`--changed-only`)
+//! - Verifying installed files against the lockfile (`--frozen --verify`)
+//! - `CHECKSUMS.txt` sidecars for `sha256sum -c` (`--emit-checksums`)
+//! - Installing a subset of dependencies by name
+//! - Reporting byte-identical installed resources (`--report-duplicates`)
+//! - Deprecation warnings from resource frontmatter
+//! - Tools declared in resource frontmatter (`tool: opencode`)
+//! - Resources requiring a newer AGPM (`min_agpm_version`)
+//! - "Managed by AGPM" banners and provenance footers (`installer.header`, `installer.footer`)
+//! - Symlinked local resources (`install_mode = "symlink"`)
+//! - Local checkouts replacing sources (`[path-overrides]`)
+//! - Installing `[workspace]` members from a root manifest
 
+mod archive;
 mod basic;
+mod changed_only;
+mod checksums;
 mod cleanup;
+mod concurrency_report;
+mod concurrent;
+mod content_hash;
+mod deprecated;
+mod duplicates;
+mod frontmatter_tool;
+mod frozen_verify;
+mod header;
 mod incremental_add;
 mod install_field;
+mod install_root;
+mod install_toggles;
+mod manifest_path;
+mod min_version;
 mod multi_artifact;
 mod multi_resource;
 mod mutable_deps;
+mod path_overrides;
+mod priority;
 mod progress_display;
+mod prune;
+mod subset;
+mod symlink;
+mod workspace;
@@ -0,0 +1,106 @@
+//! Integration tests for `[path-overrides]` local checkouts.
+
+use crate::common::TestProject;
+use anyhow::Result;
+
+/// An overridden source is read from the local checkout without touching the
+/// lockfile, and the remote content returns once the override is removed.
+#[tokio::test]
+async fn test_path_override_reads_local_content() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource("agents", "reviewer", "# Remote reviewer").await?;
+    repo.commit_all("Add reviewer")?;
+    repo.tag_version("v1.0.0")?;
+    let url = repo.bare_file_url(project.sources_path()).await?;
+
+    // A local working copy of the same repository with uncommitted changes
+    let checkout = project.sources_path().join("community-dev");
+    tokio::fs::create_dir_all(checkout.join("agents")).await?;
+    tokio::fs::write(checkout.join("agents/reviewer.md"), "# Local reviewer").await?;
+
+    project
+        .write_manifest(&format!(
+            r#"[sources]
+community = "{url}"
+
+[agents]
+reviewer = {{ source = "community", path = "agents/reviewer.md", version = "v1.0.0" }}
+"#
+        ))
+        .await?;
+    tokio::fs::write(
+        project.project_path().join("agpm.private.toml"),
+        format!(
+            "[path-overrides]\ncommunity = \"{}\"\n",
+            checkout.display().to_string().replace('\\', "/")
+        ),
+    )
+    .await?;
+
+    let output = project.run_agpm(&["install"])?;
+    output.assert_success();
+    assert!(
+        output.stderr.contains("Path override active: 'community'"),
+        "Stderr: {}",
+        output.stderr
+    );
+
+    let installed =
+        tokio::fs::read_to_string(project.project_path().join(".claude/agents/agpm/reviewer.md"))
+            .await?;
+    assert!(installed.contains("# Local reviewer"));
+
+    // Local checksums never reach the shared lockfile
+    assert!(output.stderr.contains("agpm.lock was not updated"), "Stderr: {}", output.stderr);
+    assert!(!project.project_path().join("agpm.lock").exists());
+
+    // Without the override the remote content is installed and locked
+    tokio::fs::remove_file(project.project_path().join("agpm.private.toml")).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let installed =
+        tokio::fs::read_to_string(project.project_path().join(".claude/agents/agpm/reviewer.md"))
+            .await?;
+    assert!(installed.contains("# Remote reviewer"));
+
+    let lockfile = project.load_lockfile()?;
+    assert_eq!(lockfile.agents[0].version.as_deref(), Some("v1.0.0"));
+    assert!(lockfile.agents[0].resolved_commit.is_some());
+
+    Ok(())
+}
+
+/// An override pointing at a missing directory fails instead of silently
+/// installing remote content.
+#[tokio::test]
+async fn test_path_override_missing_directory_fails() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource("agents", "reviewer", "# Remote reviewer").await?;
+    repo.commit_all("Add reviewer")?;
+    repo.tag_version("v1.0.0")?;
+    let url = repo.bare_file_url(project.sources_path()).await?;
+
+    project
+        .write_manifest(&format!(
+            r#"[sources]
+community = "{url}"
+
+[path-overrides]
+community = "../does-not-exist"
+
+[agents]
+reviewer = {{ source = "community", path = "agents/reviewer.md", version = "v1.0.0" }}
+"#
+        ))
+        .await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("points to a missing directory"), "Stderr: {}", output.stderr);
+    Ok(())
+}
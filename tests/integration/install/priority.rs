@@ -0,0 +1,51 @@
+//! Tests for ordering installation with the dependency `priority` field.
+
+use anyhow::Result;
+
+use crate::common::TestProject;
+
+/// Test that a high-priority script is written before the resources and hook that depend on it
+#[tokio::test]
+async fn test_priority_installs_script_before_hook() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.create_local_resource("local/agents/a-agent.md", "# Agent\n").await?;
+    project.create_local_resource("local/scripts/lint.sh", "#!/bin/sh\necho lint\n").await?;
+    let hook = serde_json::json!({
+        "events": ["PreToolUse"],
+        "matcher": "Bash",
+        "type": "command",
+        "command": ".claude/scripts/agpm/lint.sh",
+    });
+    project.create_local_resource("local/hooks/pre-lint.json", &hook.to_string()).await?;
+
+    // Agents sort before scripts by default, so only the priority puts the script first
+    project
+        .write_manifest(
+            r#"
+[agents]
+a-agent = "local/agents/a-agent.md"
+
+[scripts]
+lint = { path = "local/scripts/lint.sh", flatten = true, priority = 10 }
+
+[hooks]
+pre-lint = "local/hooks/pre-lint.json"
+"#,
+        )
+        .await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    let modified = |path: &str| -> Result<std::time::SystemTime> {
+        Ok(std::fs::metadata(project.project_path().join(path))?.modified()?)
+    };
+    let script = modified(".claude/scripts/agpm/lint.sh")?;
+    assert!(script <= modified(".claude/agents/agpm/a-agent.md")?, "script installed after agent");
+    assert!(script <= modified(".claude/settings.local.json")?, "script installed after hook");
+
+    let settings =
+        std::fs::read_to_string(project.project_path().join(".claude/settings.local.json"))?;
+    assert!(settings.contains(".claude/scripts/agpm/lint.sh"));
+
+    Ok(())
+}
@@ -4,6 +4,8 @@ use anyhow::Result;
 use std::fs;
 use tempfile::TempDir;
 
+use crate::common::{ManifestBuilder, TestProject};
+
 #[tokio::test]
 async fn test_small_installation_display() -> Result<()> {
     let temp = TempDir::new()?;
@@ -34,6 +36,11 @@ async fn test_small_installation_display() -> Result<()> {
                 flatten: None,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
         );
     }
@@ -81,6 +88,11 @@ async fn test_large_installation_display() -> Result<()> {
                 flatten: None,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
         );
     }
@@ -126,6 +138,11 @@ async fn test_quiet_mode_no_progress() -> Result<()> {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
         })),
     );
     manifest.save(&manifest_path)?;
@@ -143,3 +160,36 @@ async fn test_quiet_mode_no_progress() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_non_tty_install_logs_periodic_summaries() -> Result<()> {
+    let project = TestProject::new().await?;
+    let mut builder = ManifestBuilder::new();
+    for i in 1..=20 {
+        let file = format!("agent{i}.md");
+        project.create_local_resource(&file, &format!("# Agent {i}\n")).await?;
+        builder = builder.add_local_agent(&format!("agent{i}"), &file);
+    }
+    for i in 1..=5 {
+        let file = format!("snippet{i}.md");
+        project.create_local_resource(&file, &format!("# Snippet {i}\n")).await?;
+        builder = builder.add_local_snippet(&format!("snippet{i}"), &file);
+    }
+    project.write_manifest(&builder.build()).await?;
+
+    // Output is piped, so the summary is logged every tenth of the install
+    let output = project.run_agpm(&["install"])?;
+    output.assert_success();
+    let summaries: Vec<&str> =
+        output.stdout.lines().filter(|line| line.trim_start().starts_with("installed ")).collect();
+    assert_eq!(summaries.len(), 9, "{}", output.stdout);
+    assert!(summaries.iter().all(|line| line.contains("/25 (")), "{summaries:?}");
+    assert_eq!(summaries.last().unwrap().trim(), "installed 25/25 (agents 20, snippets 5)");
+
+    // Nothing is logged in quiet mode
+    fs::remove_dir_all(project.project_path().join(".claude"))?;
+    let output = project.run_agpm(&["install", "--quiet"])?;
+    output.assert_success();
+    assert!(!output.stdout.contains("installed "), "{}", output.stdout);
+    Ok(())
+}
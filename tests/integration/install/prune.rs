@@ -0,0 +1,70 @@
+//! Tests for `install --prune`, which removes whatever the lockfile no longer
+//! accounts for, even when the previous lockfile is gone.
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+const MCP_SERVER: &str = r#"{
+  "command": "npx",
+  "args": ["@test/server"]
+}"#;
+
+#[tokio::test]
+async fn test_install_prune_removes_dependencies_missing_from_lockfile() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("test-source").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer\n").await?;
+    source_repo.add_resource("agents", "retired", "# Retired\n").await?;
+    fs::create_dir_all(source_repo.path.join("mcp-servers")).await?;
+    fs::write(source_repo.path.join("mcp-servers/db.json"), MCP_SERVER).await?;
+    source_repo.commit_all("Initial version")?;
+    source_repo.tag_version("v1.0.0")?;
+    let url = source_repo.bare_file_url(project.sources_path()).await?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("test-source", &url)
+        .add_standard_agent("reviewer", "test-source", "agents/reviewer.md")
+        .add_standard_agent("retired", "test-source", "agents/retired.md")
+        .add_mcp_server("db", |d| {
+            d.source("test-source").path("mcp-servers/db.json").version("v1.0.0")
+        })
+        .build();
+    project.write_manifest(&manifest).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    // A server added by hand must survive pruning
+    let mcp_path = project.project_path().join(".mcp.json");
+    let mut mcp: serde_json::Value = serde_json::from_str(&fs::read_to_string(&mcp_path).await?)?;
+    mcp["mcpServers"]["mine"] = serde_json::json!({ "command": "my-server" });
+    fs::write(&mcp_path, serde_json::to_string_pretty(&mcp)?).await?;
+
+    // Drop everything but the reviewer and lose the lockfile that recorded the rest
+    let manifest = ManifestBuilder::new()
+        .add_source("test-source", &url)
+        .add_standard_agent("reviewer", "test-source", "agents/reviewer.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+    fs::remove_file(project.project_path().join("agpm.lock")).await?;
+
+    let retired = project.project_path().join(".claude/agents/agpm/retired.md");
+    project.run_agpm(&["install"])?.assert_success();
+    assert!(retired.exists(), "a plain install has no record of the retired agent");
+
+    let output = project.run_agpm(&["install", "--prune"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("Pruned"), "{}", output.stdout);
+    assert!(!retired.exists());
+    assert!(project.project_path().join(".claude/agents/agpm/reviewer.md").exists());
+
+    let mcp: serde_json::Value = serde_json::from_str(&fs::read_to_string(&mcp_path).await?)?;
+    assert!(mcp["mcpServers"].get("db").is_none(), "{mcp}");
+    assert_eq!(mcp["mcpServers"]["mine"]["command"], "my-server");
+
+    // Nothing is left to prune
+    let output = project.run_agpm(&["install", "--prune"])?;
+    output.assert_success();
+    assert!(!output.stdout.contains("Pruned"), "{}", output.stdout);
+    Ok(())
+}
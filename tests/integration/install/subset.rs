@@ -0,0 +1,118 @@
+//! Tests for installing a subset of dependencies by name (`agpm install <name>...`).
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject, TestSourceRepo};
+
+const REVIEWER: &str =
+    "---\ndependencies:\n  snippets:\n    - path: ../snippets/style.md\n---\n# Reviewer\n";
+
+fn manifest(url: &str, version: &str) -> String {
+    ManifestBuilder::new()
+        .add_source("community", url)
+        .add_agent("reviewer", |d| {
+            d.source("community").path("agents/reviewer.md").version(version)
+        })
+        .add_agent("architect", |d| {
+            d.source("community").path("agents/architect.md").version(version)
+        })
+        .add_command("deploy", |d| {
+            d.source("community").path("commands/deploy.md").version(version)
+        })
+        .build()
+}
+
+const FILES: &[(&str, &str)] = &[
+    ("agents/reviewer.md", REVIEWER),
+    ("agents/architect.md", "# Architect\n"),
+    ("snippets/style.md", "# Style\n"),
+    ("commands/deploy.md", "# Deploy\n"),
+];
+
+/// Publish v2.0.0 of the source, where every resource changes.
+async fn release_v2(project: &TestProject, repo: &TestSourceRepo) -> Result<()> {
+    repo.add_resource("agents", "reviewer", &format!("{REVIEWER}v2\n")).await?;
+    repo.add_resource("agents", "architect", "# Architect\nv2\n").await?;
+    repo.add_resource("snippets", "style", "# Style\nv2\n").await?;
+    repo.add_resource("commands", "deploy", "# Deploy\nv2\n").await?;
+    repo.commit_all("Second version")?;
+    repo.tag_version("v2.0.0")?;
+    repo.bare_file_url(project.sources_path()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_install_named_dependency_with_transitive_deps() -> Result<()> {
+    let (project, _, url) = TestProject::with_community_source(FILES, |m| m).await?;
+    project.write_manifest(&manifest(&url, "v1.0.0")).await?;
+
+    project.run_agpm(&["install", "reviewer"])?.assert_success();
+
+    let lockfile = project.load_lockfile()?;
+    let names: Vec<_> = lockfile.all_resources().iter().map(|r| r.name.clone()).collect();
+    assert_eq!(names.len(), 2, "{names:?}");
+    assert!(names.iter().any(|n| n.ends_with("reviewer")), "{names:?}");
+    assert!(names.iter().any(|n| n.ends_with("style")), "{names:?}");
+    for entry in lockfile.all_resources() {
+        assert!(project.project_path().join(&entry.installed_at).exists());
+    }
+    assert!(!project.project_path().join(".claude/agents/agpm/architect.md").exists());
+    assert!(!project.project_path().join(".claude/commands/agpm/deploy.md").exists());
+
+    // A full install afterwards picks up the rest
+    project.run_agpm(&["install"])?.assert_success();
+    assert_eq!(project.load_lockfile()?.all_resources().len(), 4);
+    assert!(project.project_path().join(".claude/agents/agpm/architect.md").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_install_named_dependency_leaves_others_untouched() -> Result<()> {
+    let (project, repo, url) = TestProject::with_community_source(FILES, |m| m).await?;
+    release_v2(&project, &repo).await?;
+    project.write_manifest(&manifest(&url, "v1.0.0")).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let architect = project.project_path().join(".claude/agents/agpm/architect.md");
+    let deploy = project.project_path().join(".claude/commands/agpm/deploy.md");
+    let architect_before = tokio::fs::read_to_string(&architect).await?;
+    let deploy_before = tokio::fs::read_to_string(&deploy).await?;
+    let deploy_modified = tokio::fs::metadata(&deploy).await?.modified()?;
+
+    project.write_manifest(&manifest(&url, "v2.0.0")).await?;
+    project.run_agpm(&["install", "reviewer"])?.assert_success();
+
+    let reviewer =
+        tokio::fs::read_to_string(project.project_path().join(".claude/agents/agpm/reviewer.md"))
+            .await?;
+    assert!(reviewer.contains("v2"), "{reviewer}");
+    assert_eq!(tokio::fs::read_to_string(&architect).await?, architect_before);
+    assert_eq!(tokio::fs::read_to_string(&deploy).await?, deploy_before);
+    assert_eq!(tokio::fs::metadata(&deploy).await?.modified()?, deploy_modified);
+
+    let lockfile = project.load_lockfile()?;
+    let version = |suffix: &str| {
+        lockfile
+            .all_resources()
+            .into_iter()
+            .find(|r| r.name.ends_with(suffix))
+            .and_then(|r| r.version.clone())
+    };
+    assert_eq!(version("reviewer").as_deref(), Some("v2.0.0"));
+    assert_eq!(version("style").as_deref(), Some("v2.0.0"));
+    assert_eq!(version("architect").as_deref(), Some("v1.0.0"));
+    assert_eq!(version("deploy").as_deref(), Some("v1.0.0"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_install_unknown_name_fails() -> Result<()> {
+    let (project, _, url) = TestProject::with_community_source(FILES, |m| m).await?;
+    project.write_manifest(&manifest(&url, "v1.0.0")).await?;
+
+    let output = project.run_agpm(&["install", "reviewer", "missing"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("'missing' not found in manifest"), "{}", output.stderr);
+    assert!(!project.project_path().join("agpm.lock").exists());
+    Ok(())
+}
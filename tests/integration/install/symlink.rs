@@ -0,0 +1,100 @@
+//! Tests for `install_mode = "symlink"` with local dependencies.
+//!
+//! Local resources installed unchanged become symlinks to their source files, so
+//! edits propagate without reinstalling. Git sources are always copied.
+
+#![cfg(unix)]
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+#[tokio::test]
+async fn test_symlink_mode_links_local_resources() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.create_local_resource("local/agents/helper.md", "# Helper\n").await?;
+    let vendor = project.project_path().join("vendor");
+    fs::create_dir_all(vendor.join("snippets")).await?;
+    fs::write(vendor.join("snippets/style.md"), "# Style\n").await?;
+
+    project
+        .write_manifest(&format!(
+            r#"[sources]
+vendor = "{}"
+
+[installer]
+install_mode = "symlink"
+
+[agents]
+helper = {{ path = "local/agents/helper.md" }}
+
+[snippets]
+style = {{ source = "vendor", path = "snippets/style.md", tool = "claude-code" }}
+"#,
+            vendor.display().to_string().replace('\\', "/")
+        ))
+        .await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let agent = project.project_path().join(".claude/agents/agpm/helper.md");
+    let snippet = project.project_path().join(".claude/snippets/agpm/style.md");
+    assert!(agent.is_symlink(), "agent should be a symlink");
+    assert!(snippet.is_symlink(), "snippet from a local directory source should be a symlink");
+    assert_eq!(
+        fs::read_link(&agent).await?,
+        project.project_path().join("local/agents/helper.md").canonicalize()?
+    );
+
+    // Edits to the source show up without reinstalling
+    fs::write(project.project_path().join("local/agents/helper.md"), "# Helper v2\n").await?;
+    assert_eq!(fs::read_to_string(&agent).await?, "# Helper v2\n");
+
+    // Reinstalling keeps the links and does not touch the source
+    project.run_agpm(&["install"])?.assert_success();
+    assert!(agent.is_symlink());
+    assert_eq!(fs::read_to_string(&agent).await?, "# Helper v2\n");
+
+    // Switching back to copy mode replaces the links with files
+    let manifest = fs::read_to_string(project.project_path().join("agpm.toml")).await?;
+    project.write_manifest(&manifest.replace("install_mode = \"symlink\"", "")).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    assert!(!agent.is_symlink());
+    assert_eq!(fs::read_to_string(&agent).await?, "# Helper v2\n");
+    assert_eq!(
+        fs::read_to_string(project.project_path().join("local/agents/helper.md")).await?,
+        "# Helper v2\n"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_symlink_flag_copies_remote_and_templated_resources() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.create_local_resource("local/agents/plain.md", "# Plain\n").await?;
+    project
+        .create_local_resource(
+            "local/agents/templated.md",
+            "---\nagpm:\n  templating: true\n---\n# {{ agpm.resource.name }}\n",
+        )
+        .await?;
+    let source_repo = project.create_source_repo("test-source").await?;
+    source_repo.add_resource("agents", "remote", "# Remote\n").await?;
+    source_repo.commit_all("Initial version")?;
+    source_repo.tag_version("v1.0.0")?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("test-source", &source_repo.bare_file_url(project.sources_path()).await?)
+        .add_standard_agent("remote", "test-source", "agents/remote.md")
+        .add_local_agent("plain", "local/agents/plain.md")
+        .add_local_agent("templated", "local/agents/templated.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+    project.run_agpm(&["install", "--symlink"])?.assert_success();
+
+    let agents = project.project_path().join(".claude/agents/agpm");
+    assert!(agents.join("plain.md").is_symlink());
+    assert!(!agents.join("remote.md").is_symlink(), "Git sources are always copied");
+    assert!(!agents.join("templated.md").is_symlink(), "rendered content is copied");
+    Ok(())
+}
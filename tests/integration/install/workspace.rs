@@ -0,0 +1,104 @@
+//! Tests for installing `[workspace]` members from a root manifest.
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+/// Root manifest listing two members, `api` and `web`, that both use the
+/// `community` source. Each member installs the reviewer at its given version.
+async fn setup_workspace(api_version: &str, web_version: &str) -> Result<TestProject> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("community").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer v1\n").await?;
+    source_repo.add_resource("snippets", "style", "# Style\n").await?;
+    source_repo.commit_all("Initial version")?;
+    source_repo.tag_version("v1.0.0")?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer v2\n").await?;
+    source_repo.commit_all("Second version")?;
+    source_repo.tag_version("v2.0.0")?;
+    let url = source_repo.bare_file_url(project.sources_path()).await?;
+
+    for (member, version) in [("api", api_version), ("web", web_version)] {
+        let mut builder =
+            ManifestBuilder::new().add_source("community", &url).add_agent("reviewer", |d| {
+                d.source("community").path("agents/reviewer.md").version(version)
+            });
+        if member == "web" {
+            builder = builder.add_standard_snippet("style", "community", "snippets/style.md");
+        }
+        let member_dir = project.project_path().join(member);
+        fs::create_dir_all(&member_dir).await?;
+        fs::write(member_dir.join("agpm.toml"), builder.build()).await?;
+    }
+    project.write_manifest("[workspace]\nmembers = [\"api\", \"web\"]\n").await?;
+    Ok(project)
+}
+
+#[tokio::test]
+async fn test_install_workspace_members() -> Result<()> {
+    let project = setup_workspace("v1.0.0", "v1.0.0").await?;
+
+    let output = project.run_agpm(&["install"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("Installing workspace member api"), "{}", output.stdout);
+    assert!(output.stdout.contains("Installing workspace member web"), "{}", output.stdout);
+
+    // Every member gets its own lockfile and resources
+    let root = project.project_path();
+    for member in ["api", "web"] {
+        let lockfile = fs::read_to_string(root.join(member).join("agpm.lock")).await?;
+        assert!(lockfile.contains("name = \"agents/reviewer\""), "{member}: {lockfile}");
+        let agent =
+            fs::read_to_string(root.join(member).join(".claude/agents/agpm/reviewer.md")).await?;
+        assert_eq!(agent, "# Reviewer v1\n");
+    }
+    let web_lockfile = fs::read_to_string(root.join("web/agpm.lock")).await?;
+    assert!(web_lockfile.contains("name = \"snippets/style\""));
+    let api_lockfile = fs::read_to_string(root.join("api/agpm.lock")).await?;
+    assert!(!api_lockfile.contains("snippets/style"));
+
+    // The root only lists members, so it has nothing to lock itself
+    assert!(!root.join("agpm.lock").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_install_workspace_reports_version_conflicts() -> Result<()> {
+    let project = setup_workspace("v1.0.0", "v2.0.0").await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success, "Members disagreeing on a resource should fail the install");
+    assert!(
+        output.stderr.contains("Workspace members resolve 1 resource(s) differently"),
+        "{}",
+        output.stderr
+    );
+    assert!(output.stderr.contains("agents/reviewer:"), "{}", output.stderr);
+    assert!(
+        output.stderr.contains("api: community:agents/reviewer.md@v1.0.0"),
+        "{}",
+        output.stderr
+    );
+    assert!(
+        output.stderr.contains("web: community:agents/reviewer.md@v2.0.0"),
+        "{}",
+        output.stderr
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_install_workspace_missing_member() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.write_manifest("[workspace]\nmembers = [\"missing\"]\n").await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success);
+    assert!(
+        output.stderr.contains("Workspace member 'missing' has no agpm.toml"),
+        "{}",
+        output.stderr
+    );
+    Ok(())
+}
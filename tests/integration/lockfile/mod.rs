@@ -6,9 +6,15 @@
 //! - Deterministic generation
 //! - Checksum computation and validation
 //! - Migration from older lockfile formats
+//! - Targeted updates that leave other entries untouched
+//! - Reporting changes against an older lockfile with `--since`
+//! - Lockfile backups and `agpm update --rollback`
 
 mod checksums;
 mod determinism;
 mod migration;
+mod partial_update;
+mod rollback;
+mod since;
 mod stability;
 mod staleness;
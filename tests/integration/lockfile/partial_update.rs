@@ -0,0 +1,93 @@
+//! Integration tests for targeted `agpm update <name>` lockfile writes.
+//!
+//! Updating a single dependency rewrites only its lockfile entry; every other
+//! line, including hand-written comments, stays byte-identical.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+#[tokio::test]
+async fn test_update_one_dependency_keeps_other_lines() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("community").await?;
+    for name in ["alpha", "beta", "gamma"] {
+        source_repo.add_resource("agents", name, &format!("# {name} v1\n")).await?;
+    }
+    source_repo.commit_all("Initial version")?;
+    source_repo.tag_version("v1.0.0")?;
+    let url = source_repo.bare_file_url(project.sources_path()).await?;
+
+    let mut builder = ManifestBuilder::new().add_source("community", &url);
+    for name in ["alpha", "beta", "gamma"] {
+        builder = builder.add_agent(name, |d| {
+            d.source("community").path(&format!("agents/{name}.md")).version("^1.0.0")
+        });
+    }
+    project.write_manifest(&builder.build()).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    // A reviewer annotates the lockfile by hand
+    let lockfile_path = project.project_path().join("agpm.lock");
+    let original = std::fs::read_to_string(&lockfile_path)?;
+    let annotated = original.replacen("[[agents]]", "# Reviewed 2025-06-01\n[[agents]]", 1);
+    std::fs::write(&lockfile_path, &annotated)?;
+
+    // Publish v1.1.0, which changes every agent
+    for name in ["alpha", "beta", "gamma"] {
+        source_repo.add_resource("agents", name, &format!("# {name} v1.1\n")).await?;
+    }
+    source_repo.commit_all("Second version")?;
+    source_repo.tag_version("v1.1.0")?;
+    let bare_path = project.sources_path().join("community.git");
+    std::fs::remove_dir_all(&bare_path)?;
+    source_repo.to_bare_repo(&bare_path).await?;
+
+    project.run_agpm(&["update", "beta"])?.assert_success();
+
+    let updated = std::fs::read_to_string(&lockfile_path)?;
+    let lockfile = project.load_lockfile()?;
+    let version = |name: &str| {
+        lockfile
+            .agents
+            .iter()
+            .find(|a| a.manifest_alias.as_deref() == Some(name))
+            .unwrap()
+            .version
+            .clone()
+    };
+    assert_eq!(version("beta").as_deref(), Some("v1.1.0"));
+    assert_eq!(version("alpha").as_deref(), Some("v1.0.0"));
+    assert_eq!(version("gamma").as_deref(), Some("v1.0.0"));
+
+    // Only lines inside the `beta` entry differ, besides the refreshed fetch
+    // time of the source, and the layout is unchanged
+    let (before, after): (Vec<_>, Vec<_>) =
+        (annotated.lines().collect(), updated.lines().collect());
+    assert_eq!(before.len(), after.len(), "{updated}");
+    let beta_start = before.iter().position(|l| l.contains("beta")).unwrap();
+    let beta_end = beta_start + before[beta_start..].iter().position(|l| l.is_empty()).unwrap();
+    for (i, (old, new)) in before.iter().zip(&after).enumerate() {
+        if old != new && !old.starts_with("fetched_at = ") {
+            assert!(
+                (beta_start - 2..beta_end).contains(&i),
+                "line {i} changed: {old:?} -> {new:?}"
+            );
+        }
+    }
+    assert_ne!(annotated, updated);
+    assert!(updated.contains("# Reviewed 2025-06-01\n[[agents]]"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_interactive_requires_terminal() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.write_manifest(&ManifestBuilder::new().build()).await?;
+
+    let output = project.run_agpm(&["update", "--interactive"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("--interactive requires a terminal"), "{}", output.stderr);
+    assert!(output.stderr.contains("agpm update my-agent"), "{}", output.stderr);
+    Ok(())
+}
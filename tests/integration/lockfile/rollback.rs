@@ -0,0 +1,55 @@
+//! Tests for lockfile backups and `agpm update --rollback`.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+fn backup_count(project: &TestProject) -> usize {
+    std::fs::read_dir(project.project_path().join(".agpm/backups/lockfiles"))
+        .map(|entries| entries.count())
+        .unwrap_or(0)
+}
+
+#[tokio::test]
+async fn test_update_rollback_restores_previous_lockfile() -> Result<()> {
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource("agents", "reviewer", "# Reviewer 1.0\n").await?;
+    repo.commit_all("Initial version")?;
+    repo.tag_version("v1.0.0")?;
+    repo.add_resource("agents", "reviewer", "# Reviewer 1.1\n").await?;
+    repo.commit_all("Release v1.1.0")?;
+    repo.tag_version("v1.1.0")?;
+    let url = repo.bare_file_url(project.sources_path()).await?;
+
+    let manifest = |version: &str| {
+        ManifestBuilder::new()
+            .add_source("community", &url)
+            .add_agent("reviewer", |d| {
+                d.source("community").path("agents/reviewer.md").version(version)
+            })
+            .build()
+    };
+    project.write_manifest(&manifest("v1.0.0")).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    // The first install has no previous lockfile to back up
+    assert_eq!(backup_count(&project), 0);
+
+    project.write_manifest(&manifest("v1.1.0")).await?;
+    project.run_agpm(&["update"])?.assert_success();
+    assert_eq!(backup_count(&project), 1);
+    let installed = project.project_path().join(".claude/agents/agpm/reviewer.md");
+    assert!(tokio::fs::read_to_string(&installed).await?.contains("Reviewer 1.1"));
+
+    project.run_agpm(&["update", "--rollback"])?.assert_success();
+    let lockfile = project.load_lockfile()?;
+    assert_eq!(lockfile.agents[0].version.as_deref(), Some("v1.0.0"));
+    assert!(tokio::fs::read_to_string(&installed).await?.contains("Reviewer 1.0"));
+    // The backup is consumed and restoring it does not create a new one
+    assert_eq!(backup_count(&project), 0);
+
+    let output = project.run_agpm(&["update", "--rollback"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("No lockfile backup"), "{}", output.stderr);
+    Ok(())
+}
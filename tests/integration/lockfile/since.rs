@@ -0,0 +1,114 @@
+//! Tests for reporting lockfile changes with `--since <lockfile>`.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject, TestSourceRepo};
+
+const FILES: &[(&str, &str)] =
+    &[("agents/reviewer.md", "# Reviewer 1.0\n"), ("commands/deploy.md", "# Deploy\n")];
+
+/// An agent and a command at v1.0.0.
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest.add_standard_agent("reviewer", "community", "agents/reviewer.md").add_standard_command(
+        "deploy",
+        "community",
+        "commands/deploy.md",
+    )
+}
+
+/// Install, keeping a copy of the resulting lockfile as `old.lock`.
+async fn install_and_keep_lockfile(project: &TestProject) -> Result<()> {
+    project.run_agpm(&["install"])?.assert_success();
+    tokio::fs::copy(
+        project.project_path().join("agpm.lock"),
+        project.project_path().join("old.lock"),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Publish v1.1.0 with a new snippet, then move the agent to it, drop the
+/// command and add the snippet.
+async fn change_manifest(project: &TestProject, repo: &TestSourceRepo) -> Result<()> {
+    repo.add_resource("agents", "reviewer", "# Reviewer 1.1\n").await?;
+    repo.add_resource("snippets", "style", "# Style\n").await?;
+    repo.commit_all("Release v1.1.0")?;
+    repo.tag_version("v1.1.0")?;
+    let url = repo.bare_file_url(project.sources_path()).await?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_agent("reviewer", |d| {
+            d.source("community").path("agents/reviewer.md").version("v1.1.0")
+        })
+        .add_snippet("style", |d| d.source("community").path("snippets/style.md").version("v1.1.0"))
+        .build();
+    project.write_manifest(&manifest).await
+}
+
+#[tokio::test]
+async fn test_install_since_reports_json_diff() -> Result<()> {
+    let (project, repo, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    install_and_keep_lockfile(&project).await?;
+    change_manifest(&project, &repo).await?;
+
+    let output =
+        project.run_agpm(&["install", "--quiet", "--since", "old.lock", "--format", "json"])?;
+    output.assert_success();
+
+    let report: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    let changes: Vec<_> = report["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| {
+            (
+                c["resource_type"].as_str().unwrap().to_string(),
+                c["kind"].as_str().unwrap().to_string(),
+                c["old_version"].as_str().map(str::to_string),
+                c["new_version"].as_str().map(str::to_string),
+            )
+        })
+        .collect();
+    let some = |v: &str| Some(v.to_string());
+    assert_eq!(
+        changes,
+        vec![
+            ("agent".into(), "changed".into(), some("v1.0.0"), some("v1.1.0")),
+            ("command".into(), "removed".into(), some("v1.0.0"), None),
+            ("snippet".into(), "added".into(), None, some("v1.1.0")),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_since_text_report_and_no_changes() -> Result<()> {
+    let (project, repo, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    install_and_keep_lockfile(&project).await?;
+
+    let output = project.run_agpm(&["install", "--quiet", "--since", "old.lock"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("No lockfile changes"), "{}", output.stdout);
+
+    change_manifest(&project, &repo).await?;
+    let output = project.run_agpm(&["update", "--quiet", "--since", "old.lock"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("v1.0.0 → v1.1.0"), "{}", output.stdout);
+    assert!(output.stdout.contains("Total: 1 added, 1 removed, 1 changed"), "{}", output.stdout);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_since_requires_existing_lockfile() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    install_and_keep_lockfile(&project).await?;
+
+    let output = project.run_agpm(&["install", "--since", "missing.lock"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("--since"), "{}", output.stderr);
+
+    let output = project.run_agpm(&["install", "--format", "json"])?;
+    assert!(!output.success);
+    Ok(())
+}
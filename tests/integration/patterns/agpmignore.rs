@@ -0,0 +1,72 @@
+//! Integration tests for the project-level `.agpmignore` file.
+
+use crate::common::{ManifestBuilder, TestProject};
+use anyhow::Result;
+use tokio::fs;
+
+/// Paths matched by `.agpmignore` never reach the lockfile or the install directory.
+#[tokio::test]
+async fn test_agpmignore_filters_remote_pattern_matches() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    let test_repo = project.create_source_repo("test-repo").await?;
+    test_repo.add_resource("agents", "reviewer", "# Reviewer").await?;
+    test_repo.add_resource("agents", "planner.draft", "# Draft planner").await?;
+    test_repo.add_resource("agents", "keep.draft", "# Kept draft").await?;
+    test_repo.add_resource("agents/WIP", "experiment", "# Experiment").await?;
+    test_repo.commit_all("Add agents")?;
+    test_repo.tag_version("v1.0.0")?;
+    let repo_url = test_repo.bare_file_url(project.sources_path()).await?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("test-repo", &repo_url)
+        .add_agent("all-agents", |d| d.source("test-repo").path("agents/**/*.md").version("v1.0.0"))
+        .build();
+    project.write_manifest(&manifest).await?;
+    fs::write(
+        project.project_path().join(".agpmignore"),
+        "# Work in progress\nWIP/\n*.draft.md\n!keep.draft.md\n",
+    )
+    .await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    let lockfile = project.read_lockfile().await?;
+    assert!(lockfile.contains("agents/reviewer.md"), "Lockfile:\n{lockfile}");
+    assert!(lockfile.contains("agents/keep.draft.md"), "Negated rule ignored:\n{lockfile}");
+    assert!(!lockfile.contains("planner.draft"), "Ignored file resolved:\n{lockfile}");
+    assert!(!lockfile.contains("WIP"), "Ignored directory resolved:\n{lockfile}");
+
+    let agents_dir = project.project_path().join(".claude/agents/agpm");
+    assert!(agents_dir.join("reviewer.md").exists());
+    assert!(!agents_dir.join("planner.draft.md").exists());
+    assert!(!agents_dir.join("experiment.md").exists());
+
+    Ok(())
+}
+
+/// Local pattern dependencies honor the same ignore file.
+#[tokio::test]
+async fn test_agpmignore_filters_local_pattern_matches() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    let local_dir = project.project_path().join("local-agents");
+    fs::create_dir_all(local_dir.join("scratch")).await?;
+    fs::write(local_dir.join("helper.md"), "# Helper").await?;
+    fs::write(local_dir.join("scratch/idea.md"), "# Idea").await?;
+
+    let manifest =
+        ManifestBuilder::new().add_agent("local", |d| d.path("local-agents/**/*.md")).build();
+    project.write_manifest(&manifest).await?;
+    fs::write(project.project_path().join(".agpmignore"), "/local-agents/scratch/\n").await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    let lockfile = project.read_lockfile().await?;
+    assert!(lockfile.contains("helper.md"), "Lockfile:\n{lockfile}");
+    assert!(!lockfile.contains("idea"), "Ignored file resolved:\n{lockfile}");
+
+    Ok(())
+}
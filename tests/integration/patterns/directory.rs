@@ -0,0 +1,74 @@
+//! Integration tests for directory references (`path = "agents/review/"`).
+
+use crate::common::TestProject;
+use anyhow::Result;
+
+fn installed_paths(project: &TestProject) -> Result<Vec<String>> {
+    let lockfile = project.load_lockfile()?;
+    let mut installed: Vec<_> = lockfile.agents.iter().map(|a| a.installed_at.clone()).collect();
+    installed.sort_unstable();
+    Ok(installed)
+}
+
+/// A trailing `/` installs the agents directly in the directory, but not nested ones.
+#[tokio::test]
+async fn test_directory_reference_installs_direct_children() -> Result<()> {
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource("agents", "review/linter", "# Linter").await?;
+    repo.add_resource("agents", "review/tester", "# Tester").await?;
+    repo.add_resource("agents", "review/legacy/old", "# Old").await?;
+    repo.add_resource("agents", "planner", "# Planner").await?;
+    tokio::fs::write(repo.path.join("agents/review/NOTES.txt"), "not an agent").await?;
+    repo.commit_all("Add agents")?;
+    repo.tag_version("v1.0.0")?;
+    let url = repo.bare_file_url(project.sources_path()).await?;
+
+    project
+        .write_manifest(&format!(
+            r#"[sources]
+community = "{url}"
+
+[agents]
+review = {{ source = "community", path = "agents/review/", version = "v1.0.0" }}
+"#
+        ))
+        .await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    assert_eq!(
+        installed_paths(&project)?,
+        [".claude/agents/agpm/linter.md", ".claude/agents/agpm/tester.md"]
+    );
+    let lockfile = project.load_lockfile()?;
+    let mut names: Vec<_> = lockfile.agents.iter().map(|a| a.name.as_str()).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["agents/review/linter", "agents/review/tester"]);
+    assert!(lockfile.agents.iter().all(|a| a.manifest_alias.as_deref() == Some("review")));
+
+    // Re-resolving produces the same lockfile
+    let before = project.read_lockfile().await?;
+    project.run_agpm(&["update"])?.assert_success();
+    assert_eq!(project.read_lockfile().await?, before);
+    Ok(())
+}
+
+/// Directory references also work for local directories.
+#[tokio::test]
+async fn test_local_directory_reference() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.create_local_resource("local/agents/helper.md", "# Helper").await?;
+    project.create_local_resource("local/agents/nested/deep.md", "# Deep").await?;
+
+    project
+        .write_manifest(
+            r#"[agents]
+helpers = { path = "local/agents/" }
+"#,
+        )
+        .await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    assert_eq!(installed_paths(&project)?, [".claude/agents/agpm/helper.md"]);
+    Ok(())
+}
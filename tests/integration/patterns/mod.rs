@@ -2,7 +2,15 @@
 //!
 //! Tests for pattern-based dependency resolution:
 //! - Basic pattern matching and expansion
+//! - Directory references (`path = "agents/"`)
+//! - Project-wide `.agpmignore` filtering
+//! - Renaming colliding matches with `on_conflict = "rename"`
+//! - Mirroring source layout with `preserve_structure = true`
 //! - Dependency refresh and update logic
 
+mod agpmignore;
 mod basic;
+mod directory;
+mod on_conflict;
+mod preserve_structure;
 mod refresh;
@@ -0,0 +1,81 @@
+//! Integration tests for the per-dependency `on_conflict` policy.
+
+use crate::common::TestProject;
+use anyhow::Result;
+
+/// Write a manifest with two pattern dependencies matching `agents/helper.md`
+/// in different sources, with `extra` appended to each dependency.
+async fn setup_colliding_patterns(project: &TestProject, extra: &str) -> Result<()> {
+    let official = project.create_source_repo("official").await?;
+    official.add_resource("agents", "helper", "# Official helper").await?;
+    official.commit_all("Add helper")?;
+    official.tag_version("v1.0.0")?;
+    let official_url = official.bare_file_url(project.sources_path()).await?;
+
+    let community = project.create_source_repo("community").await?;
+    community.add_resource("agents", "helper", "# Community helper").await?;
+    community.commit_all("Add helper")?;
+    community.tag_version("v1.0.0")?;
+    let community_url = community.bare_file_url(project.sources_path()).await?;
+
+    let manifest = format!(
+        r#"[sources]
+official = "{official_url}"
+community = "{community_url}"
+
+[agents]
+official = {{ source = "official", path = "agents/*.md", version = "v1.0.0"{extra} }}
+community = {{ source = "community", path = "agents/*.md", version = "v1.0.0"{extra} }}
+"#
+    );
+    project.write_manifest(&manifest).await?;
+    Ok(())
+}
+
+/// Without a policy, two sources installing the same filename is an error.
+#[tokio::test]
+async fn test_colliding_pattern_matches_fail_by_default() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    setup_colliding_patterns(&project, "").await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success, "Install should fail on the collision");
+    assert!(output.stderr.contains("Target path conflicts detected"), "Stderr: {}", output.stderr);
+    assert!(!project.project_path().join(".claude/agents/agpm/helper.md").exists());
+
+    Ok(())
+}
+
+/// With `on_conflict = "rename"`, each colliding file is prefixed with its source.
+#[tokio::test]
+async fn test_on_conflict_rename_prefixes_source_name() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    setup_colliding_patterns(&project, r#", on_conflict = "rename""#).await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    let agents_dir = project.project_path().join(".claude/agents/agpm");
+    let official = tokio::fs::read_to_string(agents_dir.join("official-helper.md")).await?;
+    let community = tokio::fs::read_to_string(agents_dir.join("community-helper.md")).await?;
+    assert!(official.contains("# Official helper"));
+    assert!(community.contains("# Community helper"));
+    assert!(!agents_dir.join("helper.md").exists());
+
+    let lockfile = project.load_lockfile()?;
+    let mut installed: Vec<_> = lockfile.agents.iter().map(|a| a.installed_at.as_str()).collect();
+    installed.sort_unstable();
+    assert_eq!(
+        installed,
+        [".claude/agents/agpm/community-helper.md", ".claude/agents/agpm/official-helper.md"]
+    );
+
+    // The renamed paths are stable across a frozen reinstall
+    project.run_agpm(&["install", "--frozen"])?.assert_success();
+    assert!(agents_dir.join("official-helper.md").exists());
+
+    Ok(())
+}
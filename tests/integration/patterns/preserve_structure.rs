@@ -0,0 +1,85 @@
+//! Integration tests for `preserve_structure` on pattern dependencies.
+
+use crate::common::TestProject;
+use anyhow::Result;
+
+/// Write a manifest with a recursive agent pattern over a nested source layout,
+/// with `extra` appended to the dependency.
+async fn setup_nested_pattern(project: &TestProject, extra: &str) -> Result<()> {
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource("agents", "planner", "# Planner").await?;
+    repo.add_resource("agents", "team/review/linter", "# Linter").await?;
+    repo.add_resource("agents", "team/tester", "# Tester").await?;
+    repo.commit_all("Add nested agents")?;
+    repo.tag_version("v1.0.0")?;
+    let url = repo.bare_file_url(project.sources_path()).await?;
+
+    let manifest = format!(
+        r#"[sources]
+community = "{url}"
+
+[agents]
+team = {{ source = "community", path = "agents/**/*.md", version = "v1.0.0"{extra} }}
+"#
+    );
+    project.write_manifest(&manifest).await?;
+    Ok(())
+}
+
+fn installed_paths(project: &TestProject) -> Result<Vec<String>> {
+    let lockfile = project.load_lockfile()?;
+    let mut installed: Vec<_> = lockfile.agents.iter().map(|a| a.installed_at.clone()).collect();
+    installed.sort_unstable();
+    Ok(installed)
+}
+
+/// By default agent pattern matches are flattened into the agents directory.
+#[tokio::test]
+async fn test_nested_pattern_flattens_by_default() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    setup_nested_pattern(&project, "").await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    assert_eq!(
+        installed_paths(&project)?,
+        [
+            ".claude/agents/agpm/linter.md",
+            ".claude/agents/agpm/planner.md",
+            ".claude/agents/agpm/tester.md"
+        ]
+    );
+    Ok(())
+}
+
+/// With `preserve_structure = true`, matches keep their layout below `agents/`.
+#[tokio::test]
+async fn test_preserve_structure_mirrors_pattern_base() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    setup_nested_pattern(&project, ", preserve_structure = true").await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    assert_eq!(
+        installed_paths(&project)?,
+        [
+            ".claude/agents/agpm/planner.md",
+            ".claude/agents/agpm/team/review/linter.md",
+            ".claude/agents/agpm/team/tester.md"
+        ]
+    );
+    let agents_dir = project.project_path().join(".claude/agents/agpm");
+    let linter = tokio::fs::read_to_string(agents_dir.join("team/review/linter.md")).await?;
+    assert!(linter.contains("# Linter"));
+    assert!(!agents_dir.join("linter.md").exists());
+
+    // The preserved paths are stable across a frozen reinstall
+    project.run_agpm(&["install", "--frozen"])?.assert_success();
+    assert!(agents_dir.join("team/tester.md").exists());
+
+    Ok(())
+}
@@ -34,6 +34,11 @@ fn create_test_manifest() -> agpm_cli::manifest::Manifest {
             flatten: None,
             install: None,
             template_vars: None,
+            sha256: None,
+            on_conflict: None,
+            preserve_structure: None,
+            channel: None,
+            priority: None,
             branch: None,
             rev: None,
             command: None,
@@ -0,0 +1,68 @@
+//! Tests for seeding the cache with `agpm cache import`.
+
+use anyhow::Result;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git").args(args).current_dir(dir).output()?;
+    assert!(output.status.success(), "git {args:?}: {}", String::from_utf8_lossy(&output.stderr));
+    Ok(())
+}
+
+const FILES: &[(&str, &str)] = &[("agents/reviewer.md", "# Reviewer\n")];
+
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest.add_standard_agent("reviewer", "community", "agents/reviewer.md")
+}
+
+#[tokio::test]
+async fn test_import_directory_then_install_offline() -> Result<()> {
+    // Nothing has been installed, so the cache is empty
+    let (project, _, url) = TestProject::with_community_source(FILES, dependencies).await?;
+    let bare = project.sources_path().join("community.git");
+    let checkouts = TempDir::new()?;
+    git(checkouts.path(), &["clone", "--quiet", &url, "community"])?;
+
+    let output = project.run_agpm(&["cache", "import", &checkouts.path().to_string_lossy()])?;
+    output.assert_success();
+    assert!(output.stdout.contains("Imported community"), "{}", output.stdout);
+
+    // The upstream is gone; installing must rely on the imported mirror alone
+    tokio::fs::remove_dir_all(&bare).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let installed = project.project_path().join(".claude/agents/agpm/reviewer.md");
+    assert_eq!(tokio::fs::read_to_string(installed).await?, "# Reviewer\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_import_with_source_warns_on_mismatched_remote() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    let bare = project.sources_path().join("community.git");
+    let checkouts = TempDir::new()?;
+    let checkout = checkouts.path().join("agents");
+    git(checkouts.path(), &["clone", "--quiet", &bare.to_string_lossy(), "agents"])?;
+    git(&checkout, &["remote", "set-url", "origin", "https://example.com/other/agents.git"])?;
+
+    let dir = checkout.to_string_lossy();
+    let args = ["cache", "import", &dir, "--source", "community", "--format", "json"];
+    let output = project.run_agpm(&args)?;
+    output.assert_success();
+    assert!(output.stderr.contains("https://example.com/other/agents.git"), "{}", output.stderr);
+    let report: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    assert_eq!(report["imported"][0]["source"], "community");
+
+    // A second import leaves the cached repository alone
+    let report: serde_json::Value = serde_json::from_str(&project.run_agpm(&args)?.stdout)?;
+    assert_eq!(report["imported"], serde_json::json!([]));
+    assert_eq!(report["already_cached"][0]["source"], "community");
+
+    let output = project.run_agpm(&["cache", "import", &dir, "--source", "missing"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("Source 'missing' is not defined"), "{}", output.stderr);
+    Ok(())
+}
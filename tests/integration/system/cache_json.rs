@@ -0,0 +1,77 @@
+//! Tests for `--format json` on the `agpm cache` subcommands.
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+const FILES: &[(&str, &str)] = &[("agents/reviewer.md", "# Reviewer\n")];
+
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest.add_standard_agent("reviewer", "community", "agents/reviewer.md")
+}
+
+/// Run a cache subcommand and parse its entire stdout as one JSON document.
+fn run_json(project: &TestProject, args: &[&str]) -> Result<Value> {
+    let output = project.run_agpm(args)?;
+    output.assert_success();
+    serde_json::from_str(&output.stdout)
+        .map_err(|e| anyhow::anyhow!("stdout is not valid JSON ({e}):\n{}", output.stdout))
+}
+
+#[tokio::test]
+async fn test_cache_stats_json() -> Result<()> {
+    // Install one agent so the cache holds a repository and a worktree
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let stats = run_json(&project, &["cache", "stats", "--format", "json"])?;
+    assert_eq!(
+        stats["location"].as_str().map(std::path::PathBuf::from),
+        Some(project.cache_path().to_path_buf())
+    );
+    assert!(stats["size_bytes"].as_u64().unwrap() > 0, "{stats}");
+    assert!(!stats["repositories"].as_array().unwrap().is_empty(), "{stats}");
+
+    // `info` is the same command, and the flag may precede the subcommand
+    assert_eq!(run_json(&project, &["cache", "--format", "json", "info"])?, stats);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cache_gc_json() -> Result<()> {
+    // Install one agent so the cache holds a repository and a worktree
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let report = run_json(&project, &["cache", "gc", "--max-age-days", "7", "--format", "json"])?;
+    assert_eq!(report["dry_run"], false);
+    assert_eq!(report["worktrees_scanned"], 1);
+    assert_eq!(report["removed"], Value::Array(Vec::new()));
+    assert_eq!(report["bytes_reclaimed"], 0);
+    assert_eq!(report["skipped_in_use"], Value::Array(Vec::new()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cache_clean_json() -> Result<()> {
+    // Install one agent so the cache holds a repository and a worktree
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let stale = project.cache_path().join("stale-repo");
+    tokio::fs::create_dir_all(&stale).await?;
+    tokio::fs::write(stale.join("data"), "x".repeat(4096)).await?;
+
+    let report = run_json(&project, &["cache", "clean", "--format", "json"])?;
+    assert_eq!(report["all"], false);
+    let removed = report["removed"].as_array().unwrap();
+    assert!(removed.contains(&Value::from("stale-repo")), "{report}");
+    assert!(report["bytes_reclaimed"].as_u64().unwrap() >= 4096, "{report}");
+    assert!(!stale.exists());
+
+    let report = run_json(&project, &["cache", "clean", "--all", "--format", "json"])?;
+    assert_eq!(report["all"], true);
+    assert!(report["stale_locks_removed"].is_u64(), "{report}");
+    assert!(!project.cache_path().exists());
+    Ok(())
+}
@@ -0,0 +1,41 @@
+//! Tests for `agpm cache path`.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+/// Test that `cache path` prints the cache directory and a source's bare repository
+#[tokio::test]
+async fn test_cache_path_prints_cache_dir() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("community").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer\n").await?;
+    source_repo.commit_all("Initial version")?;
+    source_repo.tag_version("v1.0.0")?;
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &source_repo.bare_file_url(project.sources_path()).await?)
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let output = project.run_agpm(&["cache", "path"])?;
+    output.assert_success();
+    assert_eq!(output.stdout.trim(), project.cache_path().display().to_string());
+
+    project.run_agpm(&["install"])?.assert_success();
+    let output = project.run_agpm(&["cache", "path", "--source", "community"])?;
+    output.assert_success();
+    let repo_path = std::path::PathBuf::from(output.stdout.trim());
+    assert!(repo_path.starts_with(project.cache_path().join("sources")), "{}", output.stdout);
+    assert!(repo_path.join("HEAD").exists(), "bare repository should exist at {repo_path:?}");
+
+    let output = project.run_agpm(&["cache", "path", "--format", "json"])?;
+    output.assert_success();
+    let report: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    assert_eq!(report["path"], project.cache_path().display().to_string());
+
+    let output = project.run_agpm(&["cache", "path", "--source", "missing"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("Source 'missing' is not defined"), "{}", output.stderr);
+    Ok(())
+}
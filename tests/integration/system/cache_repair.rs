@@ -0,0 +1,77 @@
+//! Tests for re-cloning one source with `agpm cache repair`.
+
+use anyhow::Result;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+fn bare_repo(project: &TestProject, source: &str) -> Result<PathBuf> {
+    let output = project.run_agpm(&["cache", "path", "--source", source])?;
+    output.assert_success();
+    Ok(PathBuf::from(output.stdout.trim()))
+}
+
+fn fsck_passes(repo: &PathBuf) -> Result<bool> {
+    Ok(Command::new("git")
+        .args(["fsck", "--connectivity-only"])
+        .current_dir(repo)
+        .output()?
+        .status
+        .success())
+}
+
+/// Test that repairing a corrupt source re-clones it and leaves other sources alone
+#[tokio::test]
+async fn test_repair_reclones_only_corrupt_source() -> Result<()> {
+    let project = TestProject::new().await?;
+    let mut manifest = ManifestBuilder::new();
+    for name in ["community", "tools"] {
+        let repo = project.create_source_repo(name).await?;
+        repo.add_resource("agents", name, &format!("# {name}\n")).await?;
+        repo.commit_all("Initial version")?;
+        repo.tag_version("v1.0.0")?;
+        let url = repo.bare_file_url(project.sources_path()).await?;
+        manifest = manifest.add_source(name, &url).add_standard_agent(
+            name,
+            name,
+            &format!("agents/{name}.md"),
+        );
+    }
+    project.write_manifest(&manifest.build()).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let community = bare_repo(&project, "community")?;
+    let tools = bare_repo(&project, "tools")?;
+    let worktrees = || -> Result<Vec<String>> {
+        let mut names: Vec<_> = std::fs::read_dir(project.cache_path().join("worktrees"))?
+            .filter_map(|entry| Some(entry.ok()?.file_name().to_string_lossy().into_owned()))
+            .filter(|name| !name.starts_with('.'))
+            .collect();
+        names.sort();
+        Ok(names)
+    };
+    let before = worktrees()?;
+    assert_eq!(before.len(), 2, "{before:?}");
+
+    // Simulate an interrupted clone: the object database is gone
+    std::fs::remove_dir_all(community.join("objects"))?;
+    std::fs::create_dir_all(community.join("objects"))?;
+    std::fs::write(tools.join("untouched"), "")?;
+    assert!(!fsck_passes(&community)?);
+
+    let output = project.run_agpm(&["cache", "repair", "--source", "community"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("Repaired community"), "{}", output.stdout);
+
+    assert!(fsck_passes(&community)?);
+    assert!(tools.join("untouched").exists(), "other sources must not be re-cloned");
+    let after = worktrees()?;
+    assert_eq!(after.len(), 1, "{after:?}");
+    assert!(before.contains(&after[0]));
+
+    let output = project.run_agpm(&["cache", "repair", "--source", "missing"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("Source 'missing' is not defined"), "{}", output.stderr);
+    Ok(())
+}
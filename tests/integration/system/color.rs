@@ -0,0 +1,43 @@
+//! Tests for the global `--color` flag.
+
+use crate::common::TestProject;
+use anyhow::Result;
+
+const ESC: char = '\u{1b}';
+
+/// Test that `--color=never` produces no ANSI escape codes or emoji
+#[tokio::test]
+async fn test_color_never_emits_plain_output() -> Result<()> {
+    let project = TestProject::new().await?;
+
+    let output = project.run_agpm(&["--color=never", "cache", "clean", "--all"])?;
+    output.assert_success();
+    assert!(!output.stdout.contains(ESC), "unexpected ANSI codes:\n{:?}", output.stdout);
+    assert!(!output.stdout.contains("🗑"), "unexpected emoji:\n{}", output.stdout);
+    assert!(output.stdout.contains("Cleaning all cache..."));
+    assert!(output.stdout.contains("✓ Cache cleared successfully"));
+
+    let output = project.run_agpm(&["--color=never", "cache", "info"])?;
+    output.assert_success();
+    assert!(!output.stdout.contains(ESC), "unexpected ANSI codes:\n{:?}", output.stdout);
+
+    Ok(())
+}
+
+/// Test that `--color=always` styles output even when redirected with NO_COLOR set
+#[tokio::test]
+async fn test_color_always_overrides_detection() -> Result<()> {
+    let project = TestProject::new().await?;
+
+    let output = project.run_agpm(&["--color=always", "cache", "clean", "--all"])?;
+    output.assert_success();
+    assert!(output.stdout.contains(ESC), "expected ANSI codes:\n{:?}", output.stdout);
+    assert!(output.stdout.contains("🗑️  Cleaning all cache..."));
+
+    // The default `auto` honors NO_COLOR and the redirected stdout
+    let output = project.run_agpm(&["cache", "clean", "--all"])?;
+    output.assert_success();
+    assert!(!output.stdout.contains(ESC), "unexpected ANSI codes:\n{:?}", output.stdout);
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 #![allow(deprecated)]
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 use tokio::fs;
 
@@ -210,6 +210,52 @@ async fn test_line_endings() {
     assert!(output.stdout.contains("✓"));
 }
 
+/// Test that `integrity.normalize_eol` gives CRLF and LF content the same checksum
+#[tokio::test]
+async fn test_normalize_eol_checksums() -> Result<()> {
+    let project = TestProject::new().await?;
+    let lf = "---\ntitle: Reviewer\n---\n\n# Reviewer\n\nReviews code.\n";
+    let crlf = lf.replace('\n', "\r\n");
+    project.create_local_resource("local/unix.md", lf).await?;
+    project.create_local_resource("local/windows.md", &crlf).await?;
+
+    let manifest = ManifestBuilder::new()
+        .add_local_agent("unix", "local/unix.md")
+        .add_local_agent("windows", "local/windows.md")
+        .build();
+    let checksums = |project: &TestProject| -> Result<(String, String)> {
+        let lockfile = project.load_lockfile()?;
+        let checksum = |name: &str| {
+            lockfile
+                .agents
+                .iter()
+                .find(|a| a.manifest_alias.as_deref() == Some(name))
+                .map(|a| a.checksum.clone())
+                .context(format!("no lockfile entry for {name}"))
+        };
+        Ok((checksum("unix")?, checksum("windows")?))
+    };
+
+    // Without the option the line endings are part of the checksum
+    project.write_manifest(&manifest).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let (unix, windows) = checksums(&project)?;
+    assert_ne!(unix, windows);
+
+    project.write_manifest(&format!("{manifest}\n[integrity]\nnormalize_eol = true\n")).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let (unix_normalized, windows_normalized) = checksums(&project)?;
+    assert_eq!(unix_normalized, windows_normalized);
+    assert_eq!(unix_normalized, unix);
+
+    // Installed files keep their line endings and verify against the normalized checksum
+    let installed =
+        fs::read_to_string(project.project_path().join(".claude/agents/agpm/windows.md")).await?;
+    assert!(installed.contains("\r\n"));
+    project.run_agpm(&["install", "--frozen", "--verify"])?.assert_success();
+    Ok(())
+}
+
 /// Test git command handling across platforms
 #[tokio::test]
 async fn test_git_command_platform() {
@@ -600,3 +600,23 @@ async fn test_helpful_error_messages() {
         output.stderr
     ); // Specific field or helpful suggestion
 }
+
+/// Test that `--json-errors` reports a failure as a single JSON object
+#[tokio::test]
+async fn test_json_errors_output() {
+    let project = TestProject::new().await.unwrap();
+
+    let output = project.run_agpm(&["--json-errors", "list"]).unwrap();
+    assert!(!output.success, "Expected command to fail without a manifest");
+
+    let line = output.stderr.lines().rfind(|l| !l.trim().is_empty()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(line)
+        .unwrap_or_else(|e| panic!("stderr is not JSON ({e}): {}", output.stderr));
+    let error = &json["error"];
+    assert_eq!(error["code"], "manifest-not-found");
+    assert!(error["message"].as_str().unwrap().contains("agpm.toml"), "{json}");
+    assert!(error["context"].is_array(), "{json}");
+    assert!(error["details"].is_string(), "{json}");
+    assert!(!error["suggestions"].as_array().unwrap().is_empty(), "{json}");
+    assert!(!output.stderr.contains("error:"), "text output should be replaced: {}", output.stderr);
+}
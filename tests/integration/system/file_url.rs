@@ -166,3 +166,60 @@ async fn test_file_url_with_uncommitted_changes() -> Result<()> {
 
     Ok(())
 }
+
+/// Find the `config` files of bare repositories in the cache.
+fn cached_repo_configs(dir: &Path) -> Vec<String> {
+    let mut configs = Vec::new();
+    for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if path.extension().is_some_and(|ext| ext == "git") {
+                configs.extend(std::fs::read_to_string(path.join("config")));
+            } else {
+                configs.extend(cached_repo_configs(&path));
+            }
+        }
+    }
+    configs
+}
+
+#[tokio::test]
+async fn test_file_url_partial_clone_from_config() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("partial-source").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer\n\nFull content.").await?;
+    source_repo.commit_all("Initial commit")?;
+    source_repo.tag_version("v1.0.0")?;
+
+    // file:// sources must allow filtered clones for --filter=blob:none to apply
+    let file_url = path_to_file_url(&source_repo.path).await;
+    std::process::Command::new("git")
+        .args(["config", "uploadpack.allowFilter", "true"])
+        .current_dir(&source_repo.path)
+        .output()?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("local", &file_url)
+        .add_standard_agent("reviewer", "local", "agents/reviewer.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let config_path = project.project_path().join("agpm-config.toml");
+    fs::write(&config_path, "[git]\npartial = true\n").await?;
+
+    project.run_agpm(&["--config", config_path.to_str().unwrap(), "install"])?.assert_success();
+
+    let configs = cached_repo_configs(project.cache_path());
+    assert!(!configs.is_empty(), "Expected a cached bare repository");
+    assert!(
+        configs.iter().all(|c| c.contains("promisor = true")),
+        "Cache clones should be partial: {configs:?}"
+    );
+
+    // Worktree checkout fetched the blobs, so installed files are complete
+    let installed =
+        fs::read_to_string(project.project_path().join(".claude/agents/agpm/reviewer.md")).await?;
+    assert!(installed.contains("Full content."), "Installed file incomplete: {installed}");
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! Tests for disabling Git TLS verification (`--insecure` / `git.insecure_ssl`).
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+const WARNING: &str = "TLS certificate verification is DISABLED";
+
+const FILES: &[(&str, &str)] = &[("agents/reviewer.md", "# Reviewer\n")];
+
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest.add_standard_agent("reviewer", "community", "agents/reviewer.md")
+}
+
+#[tokio::test]
+async fn test_insecure_flag_prints_warning() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+
+    let output = project.run_agpm(&["--insecure", "install", "--quiet"])?;
+    output.assert_success();
+    assert!(output.stderr.contains(WARNING), "{}", output.stderr);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_no_warning_by_default() -> Result<()> {
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+
+    let output = project.run_agpm(&["install"])?;
+    output.assert_success();
+    assert!(!output.stderr.contains(WARNING), "{}", output.stderr);
+    Ok(())
+}
+
+/// The config setting reaches every Git process AGPM spawns.
+///
+/// A `git` wrapper on `PATH` records `GIT_SSL_NO_VERIFY` before running the real Git.
+#[cfg(unix)]
+#[tokio::test]
+async fn test_insecure_ssl_config_applied_to_git() -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (project, _, _) = TestProject::with_community_source(FILES, dependencies).await?;
+    let config_path = project.project_path().join("agpm-config.toml");
+    fs::write(&config_path, "[git]\ninsecure_ssl = true\n").await?;
+
+    let real_git = std::process::Command::new("sh").args(["-c", "command -v git"]).output()?;
+    let real_git = String::from_utf8(real_git.stdout)?.trim().to_string();
+    let bin_dir = project.project_path().parent().unwrap().join("bin");
+    let log_path = bin_dir.join("git.log");
+    fs::create_dir_all(&bin_dir).await?;
+    let wrapper = bin_dir.join("git");
+    fs::write(
+        &wrapper,
+        format!(
+            "#!/bin/sh\necho \"ssl_no_verify=$GIT_SSL_NO_VERIFY\" >> '{}'\nexec '{}' \"$@\"\n",
+            log_path.display(),
+            real_git
+        ),
+    )
+    .await?;
+    fs::set_permissions(&wrapper, std::fs::Permissions::from_mode(0o755)).await?;
+    let path = format!("{}:{}", bin_dir.display(), std::env::var("PATH").unwrap_or_default());
+
+    let output = project.run_agpm_with_env(
+        &["--config", config_path.to_str().unwrap(), "install"],
+        &[("PATH", path.as_str())],
+    )?;
+    output.assert_success();
+    assert!(output.stderr.contains(WARNING), "{}", output.stderr);
+
+    let log = fs::read_to_string(&log_path).await?;
+    assert!(!log.is_empty(), "git wrapper was not used");
+    assert!(log.lines().all(|line| line == "ssl_no_verify=true"), "{log}");
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Tests for the global `--log-file` and `--log-rotate` flags.
+
+use crate::common::{ManifestBuilder, TestProject};
+use anyhow::Result;
+
+/// Test that `--log-file` records debug logs while the console stays quiet
+#[tokio::test]
+async fn test_log_file_records_debug_logs() -> Result<()> {
+    let project = TestProject::new().await?;
+    let (_repo, url) = project.create_standard_v1_repo("official").await?;
+    let manifest = ManifestBuilder::new()
+        .add_source("official", &url)
+        .add_standard_agent("my-agent", "official", "agents/test-agent.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let log_path = project.project_path().join("logs/agpm.log");
+    let output = project.run_agpm(&[
+        "--log-file",
+        log_path.to_str().unwrap(),
+        "--log-rotate",
+        "10MB",
+        "install",
+    ])?;
+    output.assert_success();
+    assert!(!output.stdout.contains("DEBUG"), "console should stay quiet:\n{}", output.stdout);
+
+    let log = tokio::fs::read_to_string(&log_path).await?;
+    assert!(log.contains("DEBUG"), "expected debug entries in log file:\n{log}");
+    assert!(!log.contains('\u{1b}'), "log file should not contain ANSI codes");
+
+    // A second run appends instead of truncating
+    let first_len = log.len();
+    project.run_agpm(&["--log-file", log_path.to_str().unwrap(), "list"])?.assert_success();
+    assert!(tokio::fs::metadata(&log_path).await?.len() > first_len as u64);
+
+    Ok(())
+}
+
+/// Test that `--log-rotate` rejects values that are neither a size nor `daily`
+#[tokio::test]
+async fn test_log_rotate_rejects_invalid_policy() -> Result<()> {
+    let project = TestProject::new().await?;
+
+    let output =
+        project.run_agpm(&["--log-file", "agpm.log", "--log-rotate", "weekly", "cache", "info"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("invalid rotation 'weekly'"), "{}", output.stderr);
+
+    Ok(())
+}
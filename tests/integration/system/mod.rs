@@ -2,15 +2,35 @@
 //!
 //! Tests for system-level functionality:
 //! - Cache and worktree management
+//! - Age-based worktree cleanup
+//! - JSON output of the `cache` subcommands
+//! - Seeding the cache with `cache import`
+//! - Locating the cache with `cache path`
+//! - Re-cloning one corrupt repository with `cache repair`
 //! - Cross-platform compatibility (Windows, macOS, Linux)
 //! - file:// URL support
+//! - Dependencies inside Git submodules
+//! - Disabling Git TLS verification with `--insecure`
 //! - Parallelism and concurrency control
 //! - .gitignore management
-//! - Error handling and edge cases
+//! - Error handling and edge cases, including `--json-errors` output
+//! - Output styling with `--color`
+//! - Rotating log files with `--log-file` and `--log-rotate`
+//! - Throwaway cache with `--no-cache`, staged under `AGPM_TMPDIR`
 
 mod cache;
+mod cache_import;
+mod cache_json;
+mod cache_path;
+mod cache_repair;
+mod color;
 mod cross_platform;
 mod errors;
 mod file_url;
 mod gitignore;
+mod insecure_ssl;
+mod log_file;
+mod no_cache;
 mod parallelism;
+mod submodules;
+mod worktree_retention;
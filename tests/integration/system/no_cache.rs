@@ -0,0 +1,74 @@
+//! Tests for the global `--no-cache` flag.
+
+use crate::common::{ManifestBuilder, TestProject};
+use anyhow::Result;
+
+/// Test that `--no-cache` installs from a temporary cache and leaves the real one untouched
+#[tokio::test]
+async fn test_no_cache_leaves_real_cache_untouched() -> Result<()> {
+    let project = TestProject::new().await?;
+    let (_repo, url) = project.create_standard_v1_repo("official").await?;
+    let manifest = ManifestBuilder::new()
+        .add_source("official", &url)
+        .add_standard_agent("my-agent", "official", "agents/test-agent.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let tmp_dir = project.project_path().join("tmp");
+    tokio::fs::create_dir_all(&tmp_dir).await?;
+    let output = project
+        .run_agpm_with_env(&["--no-cache", "install"], &[("TMPDIR", tmp_dir.to_str().unwrap())])?;
+    output.assert_success();
+
+    let lockfile = project.load_lockfile()?;
+    assert!(project.project_path().join(&lockfile.agents[0].installed_at).exists());
+    assert!(
+        std::fs::read_dir(project.cache_path())?.next().is_none(),
+        "real cache should stay empty"
+    );
+    assert!(std::fs::read_dir(&tmp_dir)?.next().is_none(), "temporary cache should be deleted");
+
+    // A normal run afterwards populates the real cache
+    tokio::fs::remove_file(project.project_path().join("agpm.lock")).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    assert!(std::fs::read_dir(project.cache_path())?.next().is_some());
+    Ok(())
+}
+
+/// Test that `AGPM_TMPDIR` moves the temporary cache and is validated at startup
+#[tokio::test]
+async fn test_no_cache_stages_under_agpm_tmpdir() -> Result<()> {
+    let project = TestProject::new().await?;
+    let (_repo, url) = project.create_standard_v1_repo("official").await?;
+    let manifest = ManifestBuilder::new()
+        .add_source("official", &url)
+        .add_standard_agent("my-agent", "official", "agents/test-agent.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    // Created on demand, and preferred over TMPDIR
+    let staging = project.project_path().join("staging/agpm");
+    let system_tmp = project.project_path().join("tmp");
+    tokio::fs::create_dir_all(&system_tmp).await?;
+    let log_path = project.project_path().join("agpm.log");
+    let output = project.run_agpm_with_env(
+        &["--log-file", log_path.to_str().unwrap(), "--no-cache", "install"],
+        &[("AGPM_TMPDIR", staging.to_str().unwrap()), ("TMPDIR", system_tmp.to_str().unwrap())],
+    )?;
+    output.assert_success();
+
+    let log = tokio::fs::read_to_string(&log_path).await?;
+    let expected = format!("Using temporary cache at {}", staging.join("agpm-cache-").display());
+    assert!(log.contains(&expected), "expected {expected} in log:\n{log}");
+    assert!(std::fs::read_dir(&staging)?.next().is_none(), "temporary cache should be deleted");
+    assert!(std::fs::read_dir(&system_tmp)?.next().is_none());
+
+    // A temp dir that cannot be written fails before anything else happens
+    let file = project.project_path().join("not-a-dir");
+    tokio::fs::write(&file, "").await?;
+    let output =
+        project.run_agpm_with_env(&["install"], &[("AGPM_TMPDIR", file.to_str().unwrap())])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("not-a-dir"), "{}", output.stderr);
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! Tests for dependencies that point into a Git submodule (`git.submodules`).
+
+use anyhow::Result;
+use std::process::Command;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject, TestSourceRepo};
+
+fn dependencies(manifest: ManifestBuilder) -> ManifestBuilder {
+    manifest
+        .add_agent("local", |d| d.source("community").path("agents/local.md").version("^1.0.0"))
+        .add_agent("vendored", |d| {
+            d.source("community").path("vendor/agents/vendored.md").version("^1.0.0")
+        })
+}
+
+/// Publish v1.1.0 of the source, whose `vendor/` directory is a submodule containing an agent.
+async fn release_vendor_submodule(project: &TestProject, repo: &TestSourceRepo) -> Result<()> {
+    let inner = project.create_source_repo("inner").await?;
+    inner.add_resource("agents", "vendored", "# Vendored agent\n").await?;
+    inner.commit_all("Initial version")?;
+
+    let output = Command::new("git")
+        .args(["-c", "protocol.file.allow=always", "submodule", "add", "-q"])
+        .arg(&inner.path)
+        .arg("vendor")
+        .current_dir(&repo.path)
+        .output()?;
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    repo.commit_all("Add vendor submodule")?;
+    repo.tag_version("v1.1.0")?;
+    repo.bare_file_url(project.sources_path()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dependency_inside_submodule() -> Result<()> {
+    let (project, repo, _) =
+        TestProject::with_community_source(&[("agents/local.md", "# Local agent\n")], dependencies)
+            .await?;
+    release_vendor_submodule(&project, &repo).await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    let agents = project.project_path().join(".claude/agents/agpm");
+    assert_eq!(fs::read_to_string(agents.join("vendored.md")).await?, "# Vendored agent\n");
+    assert_eq!(fs::read_to_string(agents.join("local.md")).await?, "# Local agent\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_submodules_can_be_disabled() -> Result<()> {
+    let (project, repo, _) =
+        TestProject::with_community_source(&[("agents/local.md", "# Local agent\n")], dependencies)
+            .await?;
+    release_vendor_submodule(&project, &repo).await?;
+    let config_path = project.project_path().join("agpm-config.toml");
+    fs::write(&config_path, "[git]\nsubmodules = false\n").await?;
+
+    let output = project.run_agpm(&["--config", config_path.to_str().unwrap(), "install"])?;
+    assert!(!output.success, "submodule content should be missing with git.submodules = false");
+    assert!(output.stderr.contains("vendored"), "{}", output.stderr);
+    Ok(())
+}
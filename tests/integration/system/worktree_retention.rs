@@ -0,0 +1,125 @@
+//! Tests for age-based worktree cleanup (`cache.worktree_max_age_days` and `agpm cache gc`).
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject, TestSourceRepo};
+
+const DAY: u64 = 24 * 60 * 60;
+
+/// Publish and install v2.0.0 of the agent after v1.0.0, leaving two worktrees in the cache.
+async fn install_v2(project: &TestProject, repo: &TestSourceRepo) -> Result<()> {
+    repo.add_resource("agents", "reviewer", "# Reviewer v2\n").await?;
+    repo.commit_all("Second version")?;
+    repo.tag_version("v2.0.0")?;
+    let url = repo.bare_file_url(project.sources_path()).await?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_agent("reviewer", |d| {
+            d.source("community").path("agents/reviewer.md").version("v2.0.0")
+        })
+        .build();
+    project.write_manifest(&manifest).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    Ok(())
+}
+
+fn registry_path(project: &TestProject) -> PathBuf {
+    project.cache_path().join("worktrees/.state.json")
+}
+
+/// Move the `last_used` time of the worktree whose agent contains `marker` back by `age` seconds.
+async fn backdate_worktree(project: &TestProject, marker: &str, age: u64) -> Result<PathBuf> {
+    let path = registry_path(project);
+    let mut registry: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).await?)?;
+    let record = registry["entries"]
+        .as_object_mut()
+        .unwrap()
+        .values_mut()
+        .find(|record| {
+            let agent = Path::new(record["path"].as_str().unwrap()).join("agents/reviewer.md");
+            std::fs::read_to_string(agent).is_ok_and(|content| content.contains(marker))
+        })
+        .unwrap_or_else(|| panic!("no worktree recorded for {marker}"));
+    let last_used = record["last_used"].as_u64().unwrap();
+    record["last_used"] = (last_used - age).into();
+    let worktree = PathBuf::from(record["path"].as_str().unwrap());
+    fs::write(&path, serde_json::to_string_pretty(&registry)?).await?;
+    Ok(worktree)
+}
+
+async fn recorded_worktrees(project: &TestProject) -> Result<usize> {
+    let registry: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(registry_path(project)).await?)?;
+    Ok(registry["entries"].as_object().map_or(0, serde_json::Map::len))
+}
+
+#[tokio::test]
+async fn test_cache_gc_removes_stale_worktrees() -> Result<()> {
+    let (project, repo, _) =
+        TestProject::with_community_source(&[("agents/reviewer.md", "# Reviewer v1\n")], |m| {
+            m.add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        })
+        .await?;
+    project.run_agpm(&["install"])?.assert_success();
+    install_v2(&project, &repo).await?;
+    assert_eq!(recorded_worktrees(&project).await?, 2);
+    let stale = backdate_worktree(&project, "Reviewer v1", 30 * DAY).await?;
+    assert!(stale.exists());
+
+    let output = project.run_agpm(&["cache", "gc", "--max-age-days", "7", "--dry-run"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("Would remove 1 of 2 worktrees"), "{}", output.stdout);
+    assert!(stale.exists(), "dry run must not remove anything");
+
+    let output = project.run_agpm(&["cache", "gc", "--max-age-days", "7"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("Removed 1 of 2 worktrees"), "{}", output.stdout);
+    assert!(!stale.exists());
+    assert_eq!(recorded_worktrees(&project).await?, 1);
+
+    // The remaining worktree is still usable
+    project.run_agpm(&["install"])?.assert_success();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cache_gc_requires_age_limit() -> Result<()> {
+    let project = TestProject::new().await?;
+    let output = project.run_agpm(&["cache", "gc"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("cache.worktree_max_age_days"), "{}", output.stderr);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_install_applies_configured_worktree_max_age() -> Result<()> {
+    let (project, repo, _) =
+        TestProject::with_community_source(&[("agents/reviewer.md", "# Reviewer v1\n")], |m| {
+            m.add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        })
+        .await?;
+    project.run_agpm(&["install"])?.assert_success();
+    install_v2(&project, &repo).await?;
+    let stale = backdate_worktree(&project, "Reviewer v1", 30 * DAY).await?;
+    // The worktree the install needs is old too, but is refreshed when it is used
+    let current = backdate_worktree(&project, "Reviewer v2", 30 * DAY).await?;
+
+    let config_path = project.project_path().join("agpm-config.toml");
+    fs::write(&config_path, "[cache]\nworktree_max_age_days = 7\n").await?;
+    // A fresh install has to check the worktree out again
+    fs::remove_file(project.project_path().join("agpm.lock")).await?;
+
+    let output = project.run_agpm(&["--config", config_path.to_str().unwrap(), "install"])?;
+    output.assert_success();
+    assert!(!stale.exists(), "stale worktree should be removed");
+    assert!(current.exists(), "worktree used by this install must be kept");
+    assert!(
+        fs::read_to_string(project.project_path().join(".claude/agents/agpm/reviewer.md"))
+            .await?
+            .contains("Reviewer v2")
+    );
+    Ok(())
+}
@@ -0,0 +1,108 @@
+//! Integration tests for `{% include %}` of other manifest resources.
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+/// An agent embeds a shared snippet, which is rendered with its own context
+#[tokio::test]
+async fn test_agent_includes_shared_snippet() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("test").await?;
+
+    source_repo
+        .add_resource(
+            "snippets",
+            "preamble",
+            r#"---
+agpm:
+  templating: true
+---
+You are part of the team. Preamble: {{ agpm.resource.name }}.
+"#,
+        )
+        .await?;
+    source_repo
+        .add_resource(
+            "agents",
+            "reviewer",
+            r#"---
+agpm:
+  templating: true
+---
+# Reviewer
+
+{% include "preamble" %}
+Review code carefully.
+"#,
+        )
+        .await?;
+    source_repo.commit_all("Add resources")?;
+    source_repo.tag_version("v1.0.0")?;
+    let source_url = source_repo.bare_file_url(project.sources_path()).await?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("test", &source_url)
+        .add_standard_agent("reviewer", "test", "agents/reviewer.md")
+        .add_standard_snippet("preamble", "test", "snippets/preamble.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    let installed =
+        fs::read_to_string(project.project_path().join(".claude/agents/agpm/reviewer.md")).await?;
+    assert!(
+        installed
+            .contains("# Reviewer\n\nYou are part of the team. Preamble: snippets/preamble.\n"),
+        "Snippet should be rendered in its own context and embedded:\n{installed}"
+    );
+    assert!(installed.contains("Review code carefully."));
+    assert!(!installed.contains("{% include"), "Include tag should be replaced:\n{installed}");
+    Ok(())
+}
+
+/// Resources that include each other fail with the include chain
+#[tokio::test]
+async fn test_include_cycle_fails() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("test").await?;
+
+    for (name, other) in [("first", "second"), ("second", "first")] {
+        source_repo
+            .add_resource(
+                "snippets",
+                name,
+                &format!("---\nagpm:\n  templating: true\n---\n{{% include \"{other}\" %}}\n"),
+            )
+            .await?;
+    }
+    source_repo
+        .add_resource(
+            "agents",
+            "reviewer",
+            "---\nagpm:\n  templating: true\n---\n{% include \"first\" %}\n",
+        )
+        .await?;
+    source_repo.commit_all("Add resources")?;
+    source_repo.tag_version("v1.0.0")?;
+    let source_url = source_repo.bare_file_url(project.sources_path()).await?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("test", &source_url)
+        .add_standard_agent("reviewer", "test", "agents/reviewer.md")
+        .add_standard_snippet("first", "test", "snippets/first.md")
+        .add_standard_snippet("second", "test", "snippets/second.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success, "Include cycle should fail the install");
+    assert!(
+        output.stderr.contains("Include cycle detected: reviewer -> first -> second -> first"),
+        "Error should name the include chain:\n{}",
+        output.stderr
+    );
+    Ok(())
+}
@@ -3,6 +3,7 @@
 //! Tests for template rendering functionality:
 //! - Basic template rendering
 //! - Content filter (`{{ 'path' | content }}`) functionality
+//! - Including other manifest resources with `{% include %}`
 //! - Project-level template variables
 //! - Resource-specific template variables
 //! - Transitive dependencies with conditional frontmatter
@@ -11,6 +12,7 @@
 
 mod content_filter;
 mod error_clarity;
+mod includes;
 mod project_vars;
 mod resource_vars;
 mod test_basic_rendering;
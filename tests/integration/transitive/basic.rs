@@ -269,6 +269,66 @@ Depends on Agent A (creates cycle)
     Ok(())
 }
 
+/// Test that a cycle across resource types reports every hop with source and version
+#[tokio::test]
+async fn test_transitive_cycle_error_lists_full_path() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+
+    // reviewer (agent) → guide (snippet) → check (command) → reviewer (agent)
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource(
+        "agents",
+        "reviewer",
+        "---\ndependencies:\n  snippets:\n    - path: ../snippets/guide.md\n      version: v1.0.0\n---\n# Reviewer\n",
+    )
+    .await?;
+    repo.add_resource(
+        "snippets",
+        "guide",
+        "---\ndependencies:\n  commands:\n    - path: ../commands/check.md\n      version: v1.0.0\n---\n# Guide\n",
+    )
+    .await?;
+    repo.add_resource(
+        "commands",
+        "check",
+        "---\ndependencies:\n  agents:\n    - path: ../agents/reviewer.md\n      version: v1.0.0\n---\n# Check\n",
+    )
+    .await?;
+    repo.commit_all("Add resources with a cross-type cycle")?;
+    repo.tag_version("v1.0.0")?;
+
+    let source_url = repo.bare_file_url(project.sources_path()).await?;
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &source_url)
+        .add_standard_agent("reviewer", "community", "agents/reviewer.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success, "Install should fail due to circular dependency");
+    let cycle = [
+        "community/agent:agents/reviewer@v1.0.0",
+        "community/snippet:snippets/guide@v1.0.0",
+        "community/command:commands/check@v1.0.0",
+    ];
+    // The cycle may be reported starting from any of its members
+    let found = (0..cycle.len()).any(|start| {
+        let chain: Vec<_> = (0..=cycle.len()).map(|i| cycle[(start + i) % cycle.len()]).collect();
+        output.stderr.contains(&chain.join(" → "))
+    });
+    assert!(found, "Error should list the full cycle, got: {}", output.stderr);
+    assert!(
+        output.stderr.contains("closes the cycle"),
+        "Error should suggest how to break the cycle, got: {}",
+        output.stderr
+    );
+    assert!(!project.project_path().join("agpm.lock").exists());
+
+    Ok(())
+}
+
 /// Test diamond dependencies (same resource via multiple paths)
 #[tokio::test]
 async fn test_transitive_diamond_dependencies() -> Result<()> {
@@ -803,3 +863,66 @@ Depends on shared snippet.
 
     Ok(())
 }
+
+/// Test that `--save-transitive` promotes transitive dependencies to direct entries
+#[tokio::test]
+async fn test_install_save_transitive_promotes_dependencies() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+
+    let community_repo = project.create_source_repo("community").await?;
+    community_repo.add_resource("agents", "helper", "# Helper Agent\n").await?;
+    community_repo
+        .add_resource(
+            "agents",
+            "main-app",
+            r#"---
+dependencies:
+  agents:
+    - path: ./helper.md
+      version: v1.0.0
+---
+
+# Main App Agent
+"#,
+        )
+        .await?;
+    community_repo.commit_all("Initial commit")?;
+    community_repo.tag_version("v1.0.0")?;
+
+    let source_url = community_repo.bare_file_url(project.sources_path()).await?;
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &source_url)
+        .add_standard_agent("main-app", "community", "agents/main-app.md")
+        .build();
+    let manifest = format!("# Project dependencies\n{manifest}");
+    project.write_manifest(&manifest).await?;
+
+    let output = project.run_agpm(&["install", "--save-transitive"])?;
+    assert!(output.success, "Install should succeed. Stderr: {}", output.stderr);
+    assert!(output.stdout.contains("Added 1 transitive dependency"), "{}", output.stdout);
+
+    let updated = tokio::fs::read_to_string(project.project_path().join("agpm.toml")).await?;
+    assert!(
+        updated.starts_with(manifest.trim_end()),
+        "Existing manifest content must be preserved:\n{updated}"
+    );
+    let parsed: toml::Value = toml::from_str(&updated)?;
+    let helper = &parsed["agents"]["helper"];
+    assert_eq!(helper["source"].as_str(), Some("community"));
+    assert_eq!(helper["path"].as_str(), Some("agents/helper.md"));
+    assert_eq!(helper["version"].as_str(), Some("v1.0.0"));
+
+    // The helper is now a direct dependency, so a second run adds nothing
+    let output = project.run_agpm(&["install", "--save-transitive"])?;
+    assert!(output.success, "Second install should succeed. Stderr: {}", output.stderr);
+    assert!(output.stdout.contains("No transitive dependencies"), "{}", output.stdout);
+    let rerun = tokio::fs::read_to_string(project.project_path().join("agpm.toml")).await?;
+    assert_eq!(rerun, updated);
+
+    let lockfile = project.read_lockfile().await?;
+    assert!(lockfile.contains("manifest_alias = \"helper\""), "Lockfile:\n{lockfile}");
+
+    Ok(())
+}
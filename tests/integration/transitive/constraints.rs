@@ -0,0 +1,102 @@
+//! Tests for the `[constraints]` section, which pins the version of transitive
+//! dependencies without declaring them in the manifest.
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject, TestSourceRepo};
+
+/// An agent requiring `^1.0.0` of `snippets/shared.md`.
+const FILES: &[(&str, &str)] = &[
+    (
+        "agents/reviewer.md",
+        r#"---
+dependencies:
+  snippets:
+    - path: ../snippets/shared.md
+      version: "^1.0.0"
+---
+# Reviewer
+"#,
+    ),
+    ("snippets/shared.md", "# Shared 1.0\n"),
+];
+
+/// Publish v1.1.0 and v2.0.0 of `snippets/shared.md`.
+async fn release_shared(project: &TestProject, repo: &TestSourceRepo) -> Result<()> {
+    for (tag, content) in [("v1.1.0", "# Shared 1.1\n"), ("v2.0.0", "# Shared 2.0\n")] {
+        repo.add_resource("snippets", "shared", content).await?;
+        repo.commit_all(&format!("Release {tag}"))?;
+        repo.tag_version(tag)?;
+    }
+    repo.bare_file_url(project.sources_path()).await?;
+    Ok(())
+}
+
+fn manifest(url: &str, constraints: &str) -> String {
+    let mut manifest = ManifestBuilder::new()
+        .add_source("community", url)
+        .add_agent("reviewer", |d| {
+            d.source("community").path("agents/reviewer.md").version("v2.0.0")
+        })
+        .build();
+    manifest.push_str(constraints);
+    manifest
+}
+
+#[tokio::test]
+async fn test_constraint_pins_transitive_version() -> Result<()> {
+    let (project, repo, url) = TestProject::with_community_source(FILES, |m| m).await?;
+    release_shared(&project, &repo).await?;
+    let snippet_path = project.project_path().join(".claude/snippets/agpm/shared.md");
+
+    // Without a pin the highest matching release is used
+    project.write_manifest(&manifest(&url, "")).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    assert_eq!(fs::read_to_string(&snippet_path).await?, "# Shared 1.1\n");
+
+    project
+        .write_manifest(&manifest(&url, "\n[constraints]\n\"snippets/shared\" = \"v1.0.0\"\n"))
+        .await?;
+    project.run_agpm(&["install"])?.assert_success();
+    assert_eq!(fs::read_to_string(&snippet_path).await?, "# Shared 1.0\n");
+
+    let lockfile = project.load_lockfile()?;
+    let shared = lockfile.snippets.iter().find(|s| s.name == "snippets/shared").unwrap();
+    assert_eq!(shared.version.as_deref(), Some("v1.0.0"));
+    assert!(lockfile.agents.iter().all(|a| a.version.as_deref() == Some("v2.0.0")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_constraint_outside_transitive_requirement_fails() -> Result<()> {
+    let (project, repo, url) = TestProject::with_community_source(FILES, |m| m).await?;
+    release_shared(&project, &repo).await?;
+    project
+        .write_manifest(&manifest(&url, "\n[constraints]\n\"snippets/shared\" = \"v2.0.0\"\n"))
+        .await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success);
+    assert!(
+        output.stderr.contains("the [constraints] pin 'v2.0.0' does not satisfy"),
+        "{}",
+        output.stderr
+    );
+    assert!(!project.project_path().join("agpm.lock").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invalid_constraint_rejected() -> Result<()> {
+    let (project, repo, url) = TestProject::with_community_source(FILES, |m| m).await?;
+    release_shared(&project, &repo).await?;
+    project
+        .write_manifest(&manifest(&url, "\n[constraints]\n\"snippets/shared\" = \"\"\n"))
+        .await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("Invalid [constraints] entry"), "{}", output.stderr);
+    Ok(())
+}
@@ -0,0 +1,68 @@
+//! Tests for `resolution.duplicate_name`, which decides what happens when the
+//! same resource name is reached through several sources.
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+/// Sources `source1` (utils v1.0.0) and `source2` (utils v2.0.0), each with an
+/// agent depending on its own `utils`, and a manifest using both agents.
+async fn setup_duplicate_utils(project: &TestProject) -> Result<String> {
+    let mut manifest = ManifestBuilder::new();
+    for (source, agent, version) in [("source1", "app", "v1.0.0"), ("source2", "tool", "v2.0.0")] {
+        let repo = project.create_source_repo(source).await?;
+        repo.add_resource("agents", "utils", &format!("# Utils {version} from {source}\n")).await?;
+        repo.add_resource(
+            "agents",
+            agent,
+            &format!(
+                "---\ndependencies:\n  agents:\n    - path: ./utils.md\n      version: {version}\n---\n\n# {agent}\n"
+            ),
+        )
+        .await?;
+        repo.commit_all("Release")?;
+        repo.tag_version(version)?;
+        let url = repo.bare_file_url(project.sources_path()).await?;
+        manifest = manifest.add_source(source, &url).add_agent(agent, |d| {
+            d.source(source).path(&format!("agents/{agent}.md")).version(version)
+        });
+    }
+    Ok(manifest.build())
+}
+
+#[tokio::test]
+async fn test_duplicate_name_newest_picks_highest_version() -> Result<()> {
+    let project = TestProject::new().await?;
+    let manifest = setup_duplicate_utils(&project).await?;
+
+    // The default policy is strict
+    project.write_manifest(&manifest).await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("Target path conflicts"), "{}", output.stderr);
+
+    project
+        .write_manifest(&format!("{manifest}\n[resolution]\nduplicate_name = \"newest\"\n"))
+        .await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let lockfile = project.load_lockfile()?;
+    let utils: Vec<_> = lockfile.agents.iter().filter(|a| a.name.ends_with("utils")).collect();
+    assert_eq!(utils.len(), 1, "{utils:?}");
+    assert_eq!(utils[0].source.as_deref(), Some("source2"));
+    assert_eq!(utils[0].version.as_deref(), Some("v2.0.0"));
+    let installed = project.project_path().join(&utils[0].installed_at);
+    assert_eq!(fs::read_to_string(&installed).await?, "# Utils v2.0.0 from source2\n");
+
+    // The agent from source1 now depends on the winning copy
+    let app = lockfile.agents.iter().find(|a| a.name.ends_with("app")).unwrap();
+    assert!(
+        app.dependencies.iter().any(|d| d.starts_with("source2/agent:") && d.contains("utils")),
+        "{:?}",
+        app.dependencies
+    );
+
+    project.run_agpm(&["install", "--frozen"])?.assert_success();
+    Ok(())
+}
@@ -6,16 +6,22 @@
 //! - Local file transitive dependencies
 //! - Version conflict resolution
 //! - Cross-type and cross-source dependencies
+//! - Same-named resources from several sources (`resolution.duplicate_name`)
 //! - Complex dependency graphs (diamond patterns, cycles)
 //! - Dependency merging and deduplication
 //! - Direct dependencies overriding transitive ones
 //! - Checksum-based conflict detection for local dependencies
 //! - Parallel processing and concurrent operations
+//! - Per-resource-type `[transitive]` policy, custom dependency key and
+//!   source restrictions
+//! - Pinning transitive versions with `[constraints]`
 
 mod basic;
 mod checksum_conflicts;
 mod complex;
+mod constraints;
 mod cross_type;
+mod duplicate_name;
 mod install_false_conflicts;
 mod local;
 mod lock_ordering_deadlock_prevention;
@@ -26,5 +32,6 @@ mod parallel_processing_conflicts_tests;
 mod parallel_processing_scale_tests;
 mod patterns;
 mod tool_inheritance;
+mod type_policy;
 mod version_conflicts;
 mod versions;
@@ -0,0 +1,188 @@
+//! Tests for the `[transitive]` policy, which stops following frontmatter
+//! dependencies of selected resource types and can read them from a custom key.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+#[tokio::test]
+async fn test_snippet_dependencies_ignored_when_disabled() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource("snippets", "helper", "# Helper\n").await?;
+    repo.add_resource("snippets", "boilerplate", "# Boilerplate\n").await?;
+    repo.add_resource(
+        "agents",
+        "main",
+        r#"---
+dependencies:
+  snippets:
+    - path: ../snippets/helper.md
+---
+# Main Agent
+"#,
+    )
+    .await?;
+    // Copied boilerplate: a snippet declaring its own dependency
+    repo.add_resource(
+        "snippets",
+        "style",
+        r#"---
+dependencies:
+  snippets:
+    - path: boilerplate.md
+---
+# Style
+"#,
+    )
+    .await?;
+    repo.commit_all("Add resources")?;
+    repo.tag_version("v1.0.0")?;
+
+    let mut manifest = ManifestBuilder::new()
+        .add_source("community", &repo.bare_file_url(project.sources_path()).await?)
+        .add_standard_agent("main", "community", "agents/main.md")
+        .add_standard_snippet("style", "community", "snippets/style.md")
+        .build();
+    manifest.push_str("\n[transitive]\nsnippets = false\n");
+    project.write_manifest(&manifest).await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    let lockfile = project.load_lockfile()?;
+    let snippets: Vec<_> = lockfile.snippets.iter().map(|s| s.name.as_str()).collect();
+    assert!(snippets.iter().any(|name| name.ends_with("helper")), "agent deps are followed");
+    assert!(
+        !snippets.iter().any(|name| name.ends_with("boilerplate")),
+        "snippet deps must be ignored: {snippets:?}"
+    );
+    let style = lockfile.snippets.iter().find(|s| s.name.ends_with("style")).unwrap();
+    assert!(style.dependencies.is_empty(), "{:?}", style.dependencies);
+    assert!(!project.project_path().join(".agpm/snippets/boilerplate.md").exists());
+
+    // Re-enabling snippet dependencies picks up the boilerplate again
+    let manifest = manifest.replace("\n[transitive]\nsnippets = false\n", "");
+    project.write_manifest(&manifest).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let lockfile = project.load_lockfile()?;
+    assert!(lockfile.snippets.iter().any(|s| s.name.ends_with("boilerplate")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dependencies_read_from_custom_field() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource("snippets", "helper", "# Helper\n").await?;
+    // `dependencies` belongs to another tool and has a different shape
+    repo.add_resource(
+        "agents",
+        "main",
+        r#"---
+dependencies:
+  - some-other-tool-package
+agpm_dependencies:
+  snippets:
+    - path: ../snippets/helper.md
+---
+# Main Agent
+"#,
+    )
+    .await?;
+    repo.commit_all("Add resources")?;
+    repo.tag_version("v1.0.0")?;
+
+    let base = ManifestBuilder::new()
+        .add_source("community", &repo.bare_file_url(project.sources_path()).await?)
+        .add_standard_agent("main", "community", "agents/main.md")
+        .build();
+
+    // By default the custom key is just another frontmatter field
+    project.write_manifest(&base).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    assert!(project.load_lockfile()?.snippets.is_empty());
+
+    let manifest = format!("{base}\n[transitive]\nfield = \"agpm_dependencies\"\n");
+    project.write_manifest(&manifest).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let lockfile = project.load_lockfile()?;
+    assert!(
+        lockfile.snippets.iter().any(|s| s.name.ends_with("helper")),
+        "{:?}",
+        lockfile.snippets
+    );
+    let main = lockfile.agents.iter().find(|a| a.name.ends_with("main")).unwrap();
+    assert_eq!(main.dependencies.len(), 1, "{:?}", main.dependencies);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invalid_custom_field_is_rejected() -> Result<()> {
+    let project = TestProject::new().await?;
+    let manifest = "[transitive]\nfield = \"agpm\"\n";
+    project.write_manifest(manifest).await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("Invalid [transitive] field 'agpm'"), "{}", output.stderr);
+    Ok(())
+}
+
+/// Source with an agent that depends on a snippet, and a manifest installing the agent.
+async fn setup_restricted_project(transitive: &str) -> Result<TestProject> {
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("community").await?;
+    repo.add_resource("snippets", "helper", "# Helper\n").await?;
+    repo.add_resource(
+        "agents",
+        "main",
+        r#"---
+dependencies:
+  snippets:
+    - path: ../snippets/helper.md
+---
+# Main Agent
+"#,
+    )
+    .await?;
+    repo.commit_all("Add resources")?;
+    repo.tag_version("v1.0.0")?;
+
+    let mut manifest = ManifestBuilder::new()
+        .add_source("community", &repo.bare_file_url(project.sources_path()).await?)
+        .add_standard_agent("main", "community", "agents/main.md")
+        .build();
+    manifest.push_str(transitive);
+    project.write_manifest(&manifest).await?;
+    Ok(project)
+}
+
+#[tokio::test]
+async fn test_restrict_sources_allows_manifest_sources() -> Result<()> {
+    let project = setup_restricted_project("\n[transitive]\nrestrict_sources = true\n").await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+    let lockfile = project.load_lockfile()?;
+    assert!(lockfile.snippets.iter().any(|s| s.name.ends_with("helper")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restrict_sources_rejects_sources_outside_allowlist() -> Result<()> {
+    let project = setup_restricted_project(
+        "\n[transitive]\nrestrict_sources = true\nallowed_sources = [\"company\"]\n",
+    )
+    .await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success, "install should fail: {}", output.stdout);
+    assert!(output.stderr.contains("from source 'community'"), "{}", output.stderr);
+    assert!(output.stderr.contains("restrict_sources does not permit"), "{}", output.stderr);
+    assert!(!project.project_path().join("agpm.lock").exists());
+    Ok(())
+}
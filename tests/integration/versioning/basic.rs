@@ -143,6 +143,119 @@ async fn test_install_with_caret_version_range() {
     assert!(!installed.contains("v2.0.0"));
 }
 
+#[tokio::test]
+async fn test_version_constraint_union() {
+    test_config::init_test_env();
+    let project = TestProject::new().await.unwrap();
+    let source_repo = project.create_source_repo("versioned").await.unwrap();
+
+    setup_git_repo_with_versions(&source_repo).await.unwrap();
+
+    // Either ~1.0 or ^2: the highest match across both alternatives wins
+    let manifest = ManifestBuilder::new()
+        .add_source(
+            "versioned",
+            &format!("file://{}", normalize_path_for_storage(&source_repo.path)),
+        )
+        .add_agent("example", |d| {
+            d.source("versioned").path("agents/example.md").version("~1.0 || ^2")
+        })
+        .add_snippet("utils", |d| {
+            d.source("versioned").path("snippets/utils.md").version("~1.1 || ^3")
+        })
+        .build();
+    project.write_manifest(&manifest).await.unwrap();
+
+    let output = project.run_agpm(&["install"]).unwrap();
+    output.assert_success();
+
+    let installed =
+        fs::read_to_string(project.project_path().join(".claude/agents/agpm/example.md"))
+            .await
+            .unwrap();
+    assert!(installed.contains("v2.0.0"));
+
+    let lockfile = project.load_lockfile().unwrap();
+    let agent = lockfile.agents.iter().find(|a| a.name.contains("example")).unwrap();
+    assert_eq!(agent.version.as_deref(), Some("v2.0.0"));
+    // No 3.x tag exists, so the ~1.1 alternative is used
+    let snippet = lockfile.snippets.iter().find(|s| s.name.contains("utils")).unwrap();
+    assert_eq!(snippet.version.as_deref(), Some("v1.1.0"));
+}
+
+#[tokio::test]
+async fn test_install_explain_json_reports_candidates() {
+    test_config::init_test_env();
+    let project = TestProject::new().await.unwrap();
+    let source_repo = project.create_source_repo("versioned").await.unwrap();
+
+    setup_git_repo_with_versions(&source_repo).await.unwrap();
+
+    let manifest = ManifestBuilder::new()
+        .add_source(
+            "versioned",
+            &format!("file://{}", normalize_path_for_storage(&source_repo.path)),
+        )
+        .add_agent("example", |d| d.source("versioned").path("agents/example.md").version("^1.0.0"))
+        .build();
+    project.write_manifest(&manifest).await.unwrap();
+
+    // No --quiet: the JSON report alone must be on stdout
+    let output = project.run_agpm(&["install", "--explain=json"]).unwrap();
+    output.assert_success();
+
+    let report: serde_json::Value = serde_json::from_str(&output.stdout).unwrap();
+    let entry = &report.as_array().unwrap()[0];
+    assert_eq!(entry["name"], "example");
+    assert_eq!(entry["resource_type"], "agents");
+    assert_eq!(entry["requested"], "^1.0.0");
+    assert_eq!(entry["resolved_ref"], "v1.2.0");
+
+    let status = |tag: &str| {
+        entry["candidates"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["tag"] == tag)
+            .map(|c| c["status"].as_str().unwrap().to_string())
+            .unwrap()
+    };
+    assert_eq!(status("v1.2.0"), "selected");
+    assert_eq!(status("v1.1.0"), "superseded");
+    assert_eq!(status("v2.0.0"), "unsatisfied");
+}
+
+#[tokio::test]
+async fn test_install_resolution_strategy() {
+    test_config::init_test_env();
+    let project = TestProject::new().await.unwrap();
+    let source_repo = project.create_source_repo("versioned").await.unwrap();
+
+    setup_git_repo_with_versions(&source_repo).await.unwrap();
+
+    let manifest = ManifestBuilder::new()
+        .add_source(
+            "versioned",
+            &format!("file://{}", normalize_path_for_storage(&source_repo.path)),
+        )
+        .add_agent("example", |d| d.source("versioned").path("agents/example.md").version("^1.0.0"))
+        .build();
+    project.write_manifest(&manifest).await.unwrap();
+    let installed_path = project.project_path().join(".claude/agents/agpm/example.md");
+
+    // Lowest matching version under --resolution=lowest
+    let output = project.run_agpm(&["install", "--resolution=lowest"]).unwrap();
+    output.assert_success();
+    assert!(fs::read_to_string(&installed_path).await.unwrap().contains("v1.0.0"));
+    assert!(project.read_lockfile().await.unwrap().contains("version = \"v1.0.0\""));
+
+    // Updating with the default strategy moves to the highest 1.x
+    let output = project.run_agpm(&["update"]).unwrap();
+    output.assert_success();
+    assert!(fs::read_to_string(&installed_path).await.unwrap().contains("v1.2.0"));
+    assert!(project.read_lockfile().await.unwrap().contains("version = \"v1.2.0\""));
+}
+
 #[tokio::test]
 async fn test_install_with_tilde_version_range() {
     test_config::init_test_env();
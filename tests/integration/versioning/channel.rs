@@ -0,0 +1,82 @@
+//! Tests for `channel`, which filters candidate tags by their annotation message.
+
+use anyhow::Result;
+use std::process::Command;
+use tokio::fs;
+
+use crate::common::{TestProject, TestSourceRepo};
+
+/// Commit a new reviewer version and tag it, annotated with `message` if given.
+async fn release(repo: &TestSourceRepo, tag: &str, message: Option<&str>) -> Result<()> {
+    repo.add_resource("agents", "reviewer", &format!("# Reviewer {tag}\n")).await?;
+    repo.commit_all(&format!("Release {tag}"))?;
+    let mut args = vec!["tag", tag];
+    if let Some(message) = message {
+        args.extend(["-a", "-m", message]);
+    }
+    let output = Command::new("git").args(&args).current_dir(&repo.path).output()?;
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    Ok(())
+}
+
+/// Source with stable v1.0.0 and v1.2.0, canary v1.3.0 and lightweight v1.4.0.
+async fn setup_channels(project: &TestProject) -> Result<String> {
+    let source_repo = project.create_source_repo("community").await?;
+    release(&source_repo, "v1.0.0", Some("Release 1.0.0\n\nchannel: stable")).await?;
+    release(&source_repo, "v1.2.0", Some("Release 1.2.0\n\nchannel: stable")).await?;
+    release(&source_repo, "v1.3.0", Some("Release 1.3.0\n\nchannel: canary")).await?;
+    release(&source_repo, "v1.4.0", None).await?;
+    source_repo.bare_file_url(project.sources_path()).await
+}
+
+async fn install_reviewer(project: &TestProject, url: &str, spec: &str) -> Result<()> {
+    project
+        .write_manifest(&format!(
+            "[sources]\ncommunity = \"{url}\"\n\n[agents]\n\
+             reviewer = {{ source = \"community\", path = \"agents/reviewer.md\", {spec} }}\n"
+        ))
+        .await
+}
+
+#[tokio::test]
+async fn test_channel_filters_constraint_candidates() -> Result<()> {
+    let project = TestProject::new().await?;
+    let url = setup_channels(&project).await?;
+
+    // Without a channel, the highest tag wins, lightweight or not
+    install_reviewer(&project, &url, "version = \"^1.0.0\"").await?;
+    project.run_agpm(&["install"])?.assert_success();
+    assert_eq!(project.load_lockfile()?.agents[0].version.as_deref(), Some("v1.4.0"));
+
+    install_reviewer(&project, &url, "version = \"^1.0.0\", channel = \"channel: stable\"").await?;
+    project.run_agpm(&["update"])?.assert_success();
+    assert_eq!(project.load_lockfile()?.agents[0].version.as_deref(), Some("v1.2.0"));
+    let installed = project.project_path().join(".claude/agents/agpm/reviewer.md");
+    assert_eq!(fs::read_to_string(&installed).await?, "# Reviewer v1.2.0\n");
+
+    install_reviewer(&project, &url, "version = \"latest-by-date\", channel = \"canary\"").await?;
+    project.run_agpm(&["update"])?.assert_success();
+    assert_eq!(project.load_lockfile()?.agents[0].version.as_deref(), Some("v1.3.0"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_channel_errors() -> Result<()> {
+    let project = TestProject::new().await?;
+    let url = setup_channels(&project).await?;
+
+    install_reviewer(&project, &url, "version = \"^1.0.0\", channel = \"nightly\"").await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success, "No tag in the channel should fail the install");
+    assert!(
+        output.stderr.contains("has no tags in channel 'nightly' to resolve '^1.0.0'"),
+        "{}",
+        output.stderr
+    );
+
+    install_reviewer(&project, &url, "version = \"v1.0.0\", channel = \"stable\"").await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success, "A channel on an exact tag should be rejected");
+    assert!(output.stderr.contains("`channel` requires a version range"), "{}", output.stderr);
+    Ok(())
+}
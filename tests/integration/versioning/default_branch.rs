@@ -0,0 +1,85 @@
+//! Tests for dependencies without a version on sources whose default branch is not `main`.
+
+use anyhow::Result;
+use std::process::Command;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+fn git(dir: &std::path::Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git").args(args).current_dir(dir).output()?;
+    assert!(output.status.success(), "git {args:?}: {}", String::from_utf8_lossy(&output.stderr));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_branchless_dependency_uses_remote_default_branch() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("community").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer on trunk\n").await?;
+    source_repo.commit_all("Initial version")?;
+    git(&source_repo.path, &["branch", "-M", "trunk"])?;
+
+    // A bare remote whose HEAD is trunk, with no main branch at all
+    let bare = project.sources_path().join("community.git");
+    git(
+        project.sources_path(),
+        &["clone", "--bare", "-q", &source_repo.path.to_string_lossy(), &bare.to_string_lossy()],
+    )?;
+    let url = format!("file://{}", bare.display());
+
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_agent("reviewer", |d| d.source("community").path("agents/reviewer.md"))
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+    let installed =
+        fs::read_to_string(project.project_path().join(".claude/agents/agpm/reviewer.md")).await?;
+    assert_eq!(installed, "# Reviewer on trunk\n");
+    let lockfile = project.load_lockfile()?;
+    assert_eq!(lockfile.agents[0].version.as_deref(), Some("origin/trunk"));
+    Ok(())
+}
+
+/// The default branch is asked from the remote rather than taken from the
+/// cached clone, which still points at whatever was the default when cloned.
+#[tokio::test]
+async fn test_default_branch_detected_after_remote_switch() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("community").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer on main\n").await?;
+    source_repo.add_resource("snippets", "style", "# Style\n").await?;
+    source_repo.commit_all("Initial version")?;
+    source_repo.tag_version("v1.0.0")?;
+    let url = source_repo.bare_file_url(project.sources_path()).await?;
+    let bare = url.strip_prefix("file://").unwrap().to_string();
+
+    // Populate the cache while the remote default is still main
+    let pinned = ManifestBuilder::new().add_source("community", &url).add_snippet("style", |d| {
+        d.source("community").path("snippets/style.md").version("v1.0.0")
+    });
+    project.write_manifest(&pinned.build()).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    // Upstream moves development to trunk and makes it the default branch
+    git(&source_repo.path, &["checkout", "-q", "-b", "trunk"])?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer on trunk\n").await?;
+    source_repo.commit_all("Move to trunk")?;
+    git(&source_repo.path, &["push", "-q", &bare, "trunk"])?;
+    git(std::path::Path::new(&bare), &["symbolic-ref", "HEAD", "refs/heads/trunk"])?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_snippet("style", |d| d.source("community").path("snippets/style.md").version("v1.0.0"))
+        .add_agent("reviewer", |d| d.source("community").path("agents/reviewer.md"))
+        .build();
+    project.write_manifest(&manifest).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let installed =
+        fs::read_to_string(project.project_path().join(".claude/agents/agpm/reviewer.md")).await?;
+    assert_eq!(installed, "# Reviewer on trunk\n");
+    Ok(())
+}
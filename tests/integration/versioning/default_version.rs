@@ -0,0 +1,78 @@
+//! Tests for `[default-versions]`, which gives versionless dependencies of a
+//! source a default constraint.
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::TestProject;
+
+/// Source with releases v1.0.0, v1.1.0 and v2.0.0 of two agents; v2.0.0 is the branch tip.
+async fn setup_releases(project: &TestProject) -> Result<String> {
+    let source_repo = project.create_source_repo("community").await?;
+    for tag in ["v1.0.0", "v1.1.0", "v2.0.0"] {
+        source_repo.add_resource("agents", "reviewer", &format!("# Reviewer {tag}\n")).await?;
+        source_repo.add_resource("agents", "helper", &format!("# Helper {tag}\n")).await?;
+        source_repo.commit_all(&format!("Release {tag}"))?;
+        source_repo.tag_version(tag)?;
+    }
+    source_repo.bare_file_url(project.sources_path()).await
+}
+
+#[tokio::test]
+async fn test_versionless_dependency_inherits_source_default() -> Result<()> {
+    let project = TestProject::new().await?;
+    let url = setup_releases(&project).await?;
+    project
+        .write_manifest(&format!(
+            r#"[sources]
+community = "{url}"
+
+[default-versions]
+community = "^1.0.0"
+
+[agents]
+reviewer = {{ source = "community", path = "agents/reviewer.md" }}
+pinned = {{ source = "community", path = "agents/helper.md", version = "v2.0.0" }}
+"#
+        ))
+        .await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    let lockfile = project.load_lockfile()?;
+    let version_of = |name: &str| {
+        lockfile
+            .agents
+            .iter()
+            .find(|a| a.manifest_alias.as_deref() == Some(name))
+            .and_then(|a| a.version.clone())
+    };
+    // The default constraint picks the highest 1.x release instead of the branch tip
+    assert_eq!(version_of("reviewer").as_deref(), Some("v1.1.0"));
+    // An explicit version still wins
+    assert_eq!(version_of("pinned").as_deref(), Some("v2.0.0"));
+
+    let installed = project.project_path().join(".claude/agents/agpm/reviewer.md");
+    assert_eq!(fs::read_to_string(&installed).await?, "# Reviewer v1.1.0\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_default_version_rejected_for_local_source() -> Result<()> {
+    let project = TestProject::new().await?;
+    project
+        .write_manifest(
+            r#"[sources]
+shared = "../shared"
+
+[default-versions]
+shared = "^1.0.0"
+"#,
+        )
+        .await?;
+
+    let output = project.run_agpm(&["validate"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("local path source"), "{}", output.stderr);
+    Ok(())
+}
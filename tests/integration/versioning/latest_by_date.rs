@@ -0,0 +1,61 @@
+//! Tests for the `latest-by-date` version keyword.
+
+use anyhow::Result;
+use std::process::Command;
+use tokio::fs;
+
+use crate::common::{ManifestBuilder, TestProject, TestSourceRepo};
+
+/// Commit a new reviewer version and create an annotated tag dated `date` (Unix seconds).
+async fn release(repo: &TestSourceRepo, tag: &str, date: u64) -> Result<()> {
+    repo.add_resource("agents", "reviewer", &format!("# Reviewer {tag}\n")).await?;
+    repo.commit_all(&format!("Release {tag}"))?;
+    let output = Command::new("git")
+        .args(["tag", "-a", tag, "-m", tag])
+        .env("GIT_COMMITTER_DATE", format!("@{date} +0000"))
+        .current_dir(&repo.path)
+        .output()?;
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_latest_by_date_selects_newest_tag() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("community").await?;
+    // Semver order and creation order disagree: v2.0.0 is the newest tag
+    release(&source_repo, "v1.0.0", 1_700_000_000).await?;
+    release(&source_repo, "v3.0.0", 1_700_100_000).await?;
+    release(&source_repo, "v2.0.0", 1_700_200_000).await?;
+    let url = source_repo.bare_file_url(project.sources_path()).await?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_agent("reviewer", |d| {
+            d.source("community").path("agents/reviewer.md").version("latest-by-date")
+        })
+        .build();
+    project.write_manifest(&manifest).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let lockfile = project.load_lockfile()?;
+    assert_eq!(lockfile.agents[0].version.as_deref(), Some("v2.0.0"));
+    let installed = project.project_path().join(".claude/agents/agpm/reviewer.md");
+    assert_eq!(fs::read_to_string(&installed).await?, "# Reviewer v2.0.0\n");
+
+    // A newer tag is picked up by update, not by install
+    release(&source_repo, "v1.1.0", 1_700_300_000).await?;
+    let bare = url.strip_prefix("file://").unwrap();
+    let output = Command::new("git")
+        .args(["push", "-q", bare, "main", "v1.1.0"])
+        .current_dir(&source_repo.path)
+        .output()?;
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    project.run_agpm(&["install"])?.assert_success();
+    assert_eq!(project.load_lockfile()?.agents[0].version.as_deref(), Some("v2.0.0"));
+
+    project.run_agpm(&["update"])?.assert_success();
+    assert_eq!(project.load_lockfile()?.agents[0].version.as_deref(), Some("v1.1.0"));
+    assert_eq!(fs::read_to_string(&installed).await?, "# Reviewer v1.1.0\n");
+    Ok(())
+}
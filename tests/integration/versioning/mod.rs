@@ -3,10 +3,30 @@
 //! Tests for version management:
 //! - Basic version constraint handling
 //! - Prefixed version tags (monorepo-style)
+//! - Remote default branch for dependencies without a version
+//! - Source default constraints for dependencies without a version (`[default-versions]`)
+//! - Newest tag by creation date (`latest-by-date`)
+//! - Release channels from tag annotation messages (`channel`)
 //! - Outdated dependency detection
+//! - Paths that move between releases (`[path-aliases]`)
+//! - Tag-only policy (`require_tag`)
+//! - Only versions with a `<tag>-validated` marker tag (`require_validated`)
+//! - Several tags on one release (`tag_preference`)
+//! - Raw ref versions such as pull-request heads (`ref:`)
+//! - Non-standard tag formats such as `release/1.2.3` (`[tag-patterns]`)
 //! - Update progress reporting
 
 mod basic;
+mod channel;
+mod default_branch;
+mod default_version;
+mod latest_by_date;
 mod outdated;
+mod path_alias;
 mod prefixed;
 mod progress;
+mod raw_ref;
+mod require_tag;
+mod require_validated;
+mod tag_pattern;
+mod tag_preference;
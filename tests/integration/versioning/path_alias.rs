@@ -0,0 +1,86 @@
+//! Tests for `[path-aliases]`, which redirects manifest paths of sources that
+//! move files between releases.
+
+use anyhow::Result;
+use std::fs as sync_fs;
+use tokio::fs;
+
+use crate::common::TestProject;
+
+/// Source where `agents/linter.md` moves to `agents/quality/linter.md` in v2.0.0.
+async fn setup_moved_agent(project: &TestProject) -> Result<String> {
+    let source_repo = project.create_source_repo("community").await?;
+    source_repo.add_resource("agents", "linter", "# Linter v1\n").await?;
+    source_repo.commit_all("Release v1.0.0")?;
+    source_repo.tag_version("v1.0.0")?;
+
+    let quality_dir = source_repo.path.join("agents/quality");
+    sync_fs::create_dir_all(&quality_dir)?;
+    sync_fs::remove_file(source_repo.path.join("agents/linter.md"))?;
+    sync_fs::write(quality_dir.join("linter.md"), "# Linter v2\n")?;
+    source_repo.commit_all("Release v2.0.0")?;
+    source_repo.tag_version("v2.0.0")?;
+    source_repo.bare_file_url(project.sources_path()).await
+}
+
+fn manifest(url: &str, version: &str) -> String {
+    format!(
+        r#"[sources]
+community = "{url}"
+
+[path-aliases.community]
+"agents/linter.md" = [
+    {{ versions = "<2.0.0", path = "agents/linter.md" }},
+    {{ versions = ">=2.0.0", path = "agents/quality/linter.md" }},
+]
+
+[agents]
+linter = {{ source = "community", path = "agents/linter.md", version = "{version}" }}
+"#
+    )
+}
+
+#[tokio::test]
+async fn test_path_alias_differs_between_version_ranges() -> Result<()> {
+    let project = TestProject::new().await?;
+    let url = setup_moved_agent(&project).await?;
+
+    for (version, path, content) in [
+        ("^1.0.0", "agents/linter.md", "# Linter v1\n"),
+        ("^2.0.0", "agents/quality/linter.md", "# Linter v2\n"),
+    ] {
+        project.write_manifest(&manifest(&url, version)).await?;
+        project.run_agpm(&["install"])?.assert_success();
+
+        let lockfile = project.load_lockfile()?;
+        let linter = &lockfile.agents[0];
+        assert_eq!(linter.path, path, "{version}");
+        let installed = project.project_path().join(&linter.installed_at);
+        assert_eq!(fs::read_to_string(&installed).await?, content);
+
+        // The actual path in the lockfile is not treated as a manifest change
+        project.run_agpm(&["install", "--frozen"])?.assert_success();
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_path_alias_requires_known_source() -> Result<()> {
+    let project = TestProject::new().await?;
+    project
+        .write_manifest(
+            r#"[path-aliases.missing]
+"agents/linter.md" = "agents/quality/linter.md"
+"#,
+        )
+        .await?;
+
+    let output = project.run_agpm(&["validate"])?;
+    assert!(!output.success);
+    assert!(
+        output.stderr.contains("[path-aliases] entry 'missing' does not match any source"),
+        "{}",
+        output.stderr
+    );
+    Ok(())
+}
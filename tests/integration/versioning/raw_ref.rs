@@ -0,0 +1,86 @@
+//! Tests for raw ref versions (`ref:refs/pull/42/head`).
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject, TestSourceRepo};
+
+const PULL_REF: &str = "refs/pull/42/head";
+
+/// Commit `content` on a detached HEAD, point [`PULL_REF`] at it and return to `main`.
+///
+/// The commit is only reachable through the pull-request ref, like a PR from a fork.
+async fn push_proposal(source_repo: &TestSourceRepo, content: &str) -> Result<String> {
+    let base = source_repo.git.get_head_sha()?;
+    source_repo.git.checkout(&base)?;
+    source_repo.add_resource("agents", "reviewer", content).await?;
+    source_repo.commit_all("Proposed change")?;
+    let proposal = source_repo.git.get_head_sha()?;
+    source_repo.git.update_ref(PULL_REF, &proposal)?;
+    source_repo.git.checkout("main")?;
+    Ok(proposal)
+}
+
+#[tokio::test]
+async fn test_install_from_pull_request_ref() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("community").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer\n").await?;
+    source_repo.commit_all("Initial version")?;
+    source_repo.tag_version("v1.0.0")?;
+    source_repo.git.ensure_branch("main")?;
+    let proposal = push_proposal(&source_repo, "# Reviewer (proposed)\n").await?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &source_repo.file_url())
+        .add_agent("reviewer", |d| {
+            d.source("community").path("agents/reviewer.md").version(&format!("ref:{PULL_REF}"))
+        })
+        .build();
+    project.write_manifest(&manifest).await?;
+    project.run_agpm(&["install"])?.assert_success();
+
+    let lockfile = project.load_lockfile()?;
+    let reviewer = &lockfile.agents[0];
+    assert_eq!(reviewer.version.as_deref(), Some("ref:refs/pull/42/head"));
+    assert_eq!(reviewer.resolved_commit.as_deref(), Some(proposal.as_str()));
+    let installed = project.project_path().join(&reviewer.installed_at);
+    assert_eq!(tokio::fs::read_to_string(&installed).await?, "# Reviewer (proposed)\n");
+
+    // Flagged as non-reproducible
+    let list = project.run_agpm(&["list"])?;
+    list.assert_success();
+    assert!(list.stdout.contains("reviewer (mutable ref)"), "{}", list.stdout);
+    let audit = project.run_agpm(&["audit"])?;
+    assert!(audit.stdout.contains("ref-pin"), "{}", audit.stdout);
+    assert!(audit.stdout.contains(PULL_REF), "{}", audit.stdout);
+
+    // A force-push is picked up by the next update
+    let pushed = push_proposal(&source_repo, "# Reviewer (revised)\n").await?;
+    assert_ne!(pushed, proposal);
+    project.run_agpm(&["update"])?.assert_success();
+    let lockfile = project.load_lockfile()?;
+    assert_eq!(lockfile.agents[0].resolved_commit.as_deref(), Some(pushed.as_str()));
+    assert_eq!(tokio::fs::read_to_string(&installed).await?, "# Reviewer (revised)\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_missing_pull_request_ref_fails() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("community").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer\n").await?;
+    source_repo.commit_all("Initial version")?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &source_repo.file_url())
+        .add_agent("reviewer", |d| {
+            d.source("community").path("agents/reviewer.md").version("ref:refs/pull/7/head")
+        })
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success, "a missing ref must fail");
+    assert!(output.stderr.contains("refs/pull/7/head"), "{}", output.stderr);
+    Ok(())
+}
@@ -0,0 +1,68 @@
+//! Tests for the `require_tag` manifest policy.
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+/// Source with tags `v1.0.0` and `stable` and a `main` branch.
+async fn setup_source(project: &TestProject) -> Result<String> {
+    let source_repo = project.create_source_repo("community").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer\n").await?;
+    source_repo.add_resource("snippets", "style", "# Style\n").await?;
+    source_repo.commit_all("Initial version")?;
+    source_repo.tag_version("v1.0.0")?;
+    source_repo.git.tag("stable")?;
+    source_repo.git.ensure_branch("main")?;
+    source_repo.bare_file_url(project.sources_path()).await
+}
+
+#[tokio::test]
+async fn test_require_tag_rejects_branch() -> Result<()> {
+    let project = TestProject::new().await?;
+    let url = setup_source(&project).await?;
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_agent("reviewer", |d| {
+            d.source("community").path("agents/reviewer.md").version("v1.0.0")
+        })
+        .add_snippet("style", |d| {
+            d.source("community").path("snippets/style.md").branch("main").tool("claude-code")
+        })
+        .build();
+    project.write_manifest(&format!("require_tag = true\n{manifest}")).await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success, "branch dependency must be rejected");
+    assert!(output.stderr.contains("require_tag"), "{}", output.stderr);
+    assert!(output.stderr.contains("snippet 'style' tracks branch 'main'"), "{}", output.stderr);
+    assert!(!output.stderr.contains("reviewer"), "tagged dependency is fine: {}", output.stderr);
+    assert!(!project.project_path().join("agpm.lock").exists());
+
+    // Without the policy the same manifest installs
+    project.write_manifest(&manifest).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_require_tag_accepts_tags_and_ranges() -> Result<()> {
+    let project = TestProject::new().await?;
+    let url = setup_source(&project).await?;
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_agent("reviewer", |d| {
+            d.source("community").path("agents/reviewer.md").version("^1.0.0")
+        })
+        .add_snippet("style", |d| {
+            d.source("community").path("snippets/style.md").version("stable").tool("claude-code")
+        })
+        .build();
+    project.write_manifest(&format!("require_tag = true\n{manifest}")).await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+    let lockfile = project.load_lockfile()?;
+    assert_eq!(lockfile.agents[0].version.as_deref(), Some("v1.0.0"));
+    // `stable` shares a commit with `v1.0.0`, which is recorded instead
+    assert_eq!(lockfile.snippets[0].version.as_deref(), Some("v1.0.0"));
+    Ok(())
+}
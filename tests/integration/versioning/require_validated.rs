@@ -0,0 +1,75 @@
+//! Tests for resolving only upstream-validated versions (`require_validated`).
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+/// Source where `v1.0.0` has a `v1.0.0-validated` marker and `v1.1.0` does not.
+async fn setup_source(project: &TestProject) -> Result<String> {
+    let source_repo = project.create_source_repo("community").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer v1.0.0\n").await?;
+    source_repo.commit_all("Initial version")?;
+    source_repo.tag_version("v1.0.0")?;
+    source_repo.git.tag("v1.0.0-validated")?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer v1.1.0\n").await?;
+    source_repo.commit_all("Unvalidated release")?;
+    source_repo.tag_version("v1.1.0")?;
+    source_repo.bare_file_url(project.sources_path()).await
+}
+
+#[tokio::test]
+async fn test_require_validated_skips_unvalidated_versions() -> Result<()> {
+    let project = TestProject::new().await?;
+    let url = setup_source(&project).await?;
+    let manifest = |version: &str| {
+        ManifestBuilder::new()
+            .add_source("community", &url)
+            .add_agent("reviewer", |d| {
+                d.source("community").path("agents/reviewer.md").version(version)
+            })
+            .build()
+    };
+
+    for version in ["^1.0.0", "latest-by-date"] {
+        project.write_manifest(&format!("require_validated = true\n{}", manifest(version))).await?;
+        project.run_agpm(&["update"])?.assert_success();
+        let lockfile = project.load_lockfile()?;
+        assert_eq!(lockfile.agents[0].version.as_deref(), Some("v1.0.0"), "{version}");
+        let installed = project.project_path().join(&lockfile.agents[0].installed_at);
+        assert!(std::fs::read_to_string(installed)?.contains("Reviewer v1.0.0"));
+    }
+
+    // Without the policy the newest release is picked
+    project.write_manifest(&manifest("^1.0.0")).await?;
+    project.run_agpm(&["update"])?.assert_success();
+    let lockfile = project.load_lockfile()?;
+    assert_eq!(lockfile.agents[0].version.as_deref(), Some("v1.1.0"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_require_validated_without_validated_tags() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("community").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer\n").await?;
+    source_repo.commit_all("Initial version")?;
+    source_repo.tag_version("v1.0.0")?;
+    let url = source_repo.bare_file_url(project.sources_path()).await?;
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &url)
+        .add_agent("reviewer", |d| {
+            d.source("community").path("agents/reviewer.md").version("^1.0.0")
+        })
+        .build();
+    project.write_manifest(&format!("require_validated = true\n{manifest}")).await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success);
+    assert!(
+        output.stderr.contains("has no tags with a '-validated' marker tag to resolve '^1.0.0'"),
+        "{}",
+        output.stderr
+    );
+    assert!(!project.project_path().join("agpm.lock").exists());
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! Tests for `[tag-patterns]`, which resolves version constraints against
+//! sources whose tags are not `v1.2.3`-style.
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::TestProject;
+
+/// Source with two agents, tagged `release/1.0.0`, `release/1.2.0` and `release/2.0.0`, plus a
+/// plain `v9.0.0` tag that does not fit the pattern.
+async fn setup_releases(project: &TestProject) -> Result<String> {
+    let source_repo = project.create_source_repo("community").await?;
+    for tag in ["release/1.0.0", "release/1.2.0", "release/2.0.0", "v9.0.0"] {
+        source_repo.add_resource("agents", "reviewer", &format!("# Reviewer {tag}\n")).await?;
+        source_repo.add_resource("agents", "helper", &format!("# Helper {tag}\n")).await?;
+        source_repo.commit_all(&format!("Release {tag}"))?;
+        source_repo.tag_version(tag)?;
+    }
+    source_repo.bare_file_url(project.sources_path()).await
+}
+
+#[tokio::test]
+async fn test_constraint_resolves_against_tag_pattern() -> Result<()> {
+    let project = TestProject::new().await?;
+    let url = setup_releases(&project).await?;
+    project
+        .write_manifest(&format!(
+            r#"[sources]
+community = "{url}"
+
+[tag-patterns]
+community = "release/{{version}}"
+
+[agents]
+reviewer = {{ source = "community", path = "agents/reviewer.md", version = "^1.0.0" }}
+latest = {{ source = "community", path = "agents/helper.md", version = "*" }}
+"#
+        ))
+        .await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    let lockfile = project.load_lockfile()?;
+    let version_of = |name: &str| {
+        lockfile
+            .agents
+            .iter()
+            .find(|a| a.manifest_alias.as_deref() == Some(name))
+            .and_then(|a| a.version.clone())
+    };
+    // The tag itself is recorded, and tags outside the pattern are ignored
+    assert_eq!(version_of("reviewer").as_deref(), Some("release/1.2.0"));
+    assert_eq!(version_of("latest").as_deref(), Some("release/2.0.0"));
+
+    let installed = project.project_path().join(".claude/agents/agpm/reviewer.md");
+    assert_eq!(fs::read_to_string(&installed).await?, "# Reviewer release/1.2.0\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tag_pattern_without_placeholder_is_rejected() -> Result<()> {
+    let project = TestProject::new().await?;
+    let url = setup_releases(&project).await?;
+    project
+        .write_manifest(&format!(
+            r#"[sources]
+community = "{url}"
+
+[tag-patterns]
+community = "release/"
+"#
+        ))
+        .await?;
+
+    let output = project.run_agpm(&["validate"])?;
+    assert!(!output.success);
+    assert!(output.stderr.contains("[tag-patterns] entry 'community'"), "{}", output.stderr);
+    Ok(())
+}
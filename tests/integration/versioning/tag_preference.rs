@@ -0,0 +1,59 @@
+//! Tests for resolving releases that carry several tags (`tag_preference`).
+
+use anyhow::Result;
+
+use crate::common::{ManifestBuilder, TestProject};
+
+/// Source whose only commit is tagged `v1.2.0`, `1.2.0`, `v1.2.0+build.7` and `stable`.
+async fn setup_source(project: &TestProject) -> Result<String> {
+    let source_repo = project.create_source_repo("community").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer\n").await?;
+    source_repo.commit_all("Initial version")?;
+    for tag in ["v1.2.0+build.7", "1.2.0", "stable"] {
+        source_repo.git.tag(tag)?;
+    }
+    source_repo.tag_version("v1.2.0")?;
+    source_repo.bare_file_url(project.sources_path()).await
+}
+
+fn manifest(url: &str, version: &str) -> String {
+    ManifestBuilder::new()
+        .add_source("community", url)
+        .add_agent("reviewer", |d| {
+            d.source("community").path("agents/reviewer.md").version(version)
+        })
+        .build()
+}
+
+fn locked_version(project: &TestProject) -> Result<Option<String>> {
+    Ok(project.load_lockfile()?.agents[0].version.clone())
+}
+
+#[tokio::test]
+async fn test_alias_tag_records_semver_tag() -> Result<()> {
+    let project = TestProject::new().await?;
+    let url = setup_source(&project).await?;
+
+    project.write_manifest(&manifest(&url, "stable")).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    assert_eq!(locked_version(&project)?.as_deref(), Some("v1.2.0"));
+
+    // Ranges pick the plain tag among tags of equal precedence
+    project.write_manifest(&manifest(&url, "^1.0.0")).await?;
+    project.run_agpm(&["install"])?.assert_success();
+    assert_eq!(locked_version(&project)?.as_deref(), Some("v1.2.0"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tag_preference_requested_keeps_alias() -> Result<()> {
+    let project = TestProject::new().await?;
+    let url = setup_source(&project).await?;
+
+    project
+        .write_manifest(&format!("tag_preference = \"requested\"\n{}", manifest(&url, "stable")))
+        .await?;
+    project.run_agpm(&["install"])?.assert_success();
+    assert_eq!(locked_version(&project)?.as_deref(), Some("stable"));
+    Ok(())
+}
@@ -74,6 +74,11 @@ async fn test_heavy_stress_500_dependencies() -> Result<()> {
                     flatten: None,
                     install: None,
                     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                    sha256: None,
+                    on_conflict: None,
+                    preserve_structure: None,
+                    channel: None,
+                    priority: None,
                 })),
             );
             total_agents += 1;
@@ -313,6 +318,11 @@ async fn test_heavy_stress_500_updates() -> Result<()> {
                     flatten: None,
                     install: None,
                     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                    sha256: None,
+                    on_conflict: None,
+                    preserve_structure: None,
+                    channel: None,
+                    priority: None,
                 })),
             );
             total_agents += 1;
@@ -379,6 +389,11 @@ async fn test_heavy_stress_500_updates() -> Result<()> {
                     flatten: None,
                     install: None,
                     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                    sha256: None,
+                    on_conflict: None,
+                    preserve_structure: None,
+                    channel: None,
+                    priority: None,
                 })),
             );
         }
@@ -907,6 +922,11 @@ async fn test_stress_trust_lockfile_checksums_mode() -> Result<()> {
                 flatten: None,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                sha256: None,
+                on_conflict: None,
+                preserve_structure: None,
+                channel: None,
+                priority: None,
             })),
         );
     }
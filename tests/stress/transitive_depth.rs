@@ -417,6 +417,77 @@ This agent depends on the shared library.
     Ok(())
 }
 
+/// Tests transitive resolution time for one agent fanning out to many snippets
+///
+/// Every snippet has frontmatter to parse, so this exercises the bounded
+/// frontmatter scan pool. Resolving twice from scratch must produce the same
+/// lockfile regardless of the order in which the scans finish.
+#[tokio::test]
+async fn test_wide_fanout_resolution() -> Result<()> {
+    const FANOUT: usize = 200;
+
+    let project = TestProject::new().await?;
+    let repo = project.create_source_repo("fanout").await?;
+
+    let mut root = String::from("---\nname: Fan-out Root\ndependencies:\n  snippets:\n");
+    for i in 0..FANOUT {
+        root.push_str(&format!("    - path: ../snippets/leaf-{i:03}.md\n"));
+        repo.add_resource(
+            "snippets",
+            &format!("leaf-{i:03}"),
+            &format!(
+                "---\nname: Leaf {i}\ndescription: Fan-out leaf {i}\ntags: [stress, fanout]\n---\n# Leaf {i}\n"
+            ),
+        )
+        .await?;
+    }
+    root.push_str("---\n# Fan-out Root\n");
+    repo.add_resource("agents", "root", &root).await?;
+    repo.commit_all("Add fan-out graph")?;
+    repo.tag_version("v1.0.0")?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("fanout", &format!("file://{}", repo.path.display()))
+        .add_standard_agent("root", "fanout", "agents/root.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let lockfile_path = project.project_path().join("agpm.lock");
+    let mut lockfiles = Vec::new();
+    for run in 1..=2 {
+        if lockfile_path.exists() {
+            tokio::fs::remove_file(&lockfile_path).await?;
+        }
+
+        let start = Instant::now();
+        let output = project.run_agpm(&["install", "--quiet"])?;
+        let duration = start.elapsed();
+        assert!(output.success, "Fan-out install failed: stderr: {}", &output.stderr);
+
+        // Log performance (no assertion - rely on nextest timeout for hangs)
+        println!(
+            "Run {}: resolved and installed {} transitive snippets in {}ms",
+            run,
+            FANOUT,
+            duration.as_millis()
+        );
+        // Drop the fetch timestamps, which differ between runs by design
+        let lockfile = tokio::fs::read_to_string(&lockfile_path).await?;
+        lockfiles.push(
+            lockfile
+                .lines()
+                .filter(|line| !line.starts_with("fetched_at"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+    }
+
+    assert_eq!(lockfiles[0].matches("[[snippets]]").count(), FANOUT);
+    assert_eq!(lockfiles[0], lockfiles[1], "Fan-out resolution should be deterministic");
+
+    Ok(())
+}
+
 /// Helper function to get current memory usage (platform-specific)
 fn get_memory_usage() -> usize {
     #[cfg(unix)]